@@ -16,6 +16,9 @@
 // under the License.
 
 use clap::Parser;
+use datafusion::catalog::catalog::CatalogProvider;
+use datafusion::catalog::persistent_catalog::PersistentCatalogProvider;
+use datafusion::catalog::schema::MemorySchemaProvider;
 use datafusion::error::{DataFusionError, Result};
 use datafusion::execution::context::SessionConfig;
 use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
@@ -79,6 +82,12 @@ struct Args {
         help = "Reduce printing other than the results and work quietly"
     )]
     quiet: bool,
+
+    #[clap(
+        long,
+        help = "Directory storing the tables, views and schemas of the default catalog, which are restored on startup"
+    )]
+    catalog_dir: Option<String>,
 }
 
 #[tokio::main]
@@ -104,6 +113,9 @@ pub async fn main() -> Result<()> {
     let runtime_env = create_runtime_env()?;
     let mut ctx =
         SessionContext::with_config_rt(session_config.clone(), Arc::new(runtime_env));
+    if let Some(ref path) = args.catalog_dir {
+        register_persistent_catalog(&ctx, &session_config, path)?;
+    }
     ctx.refresh_catalogs().await?;
     // install dynamic catalog provider that knows how to open files
     ctx.register_catalog_list(Arc::new(DynamicFileCatalog::new(
@@ -146,6 +158,24 @@ pub async fn main() -> Result<()> {
     }
 }
 
+/// Replace the default catalog with one stored in the directory `path`
+fn register_persistent_catalog(
+    ctx: &SessionContext,
+    session_config: &SessionConfig,
+    path: &str,
+) -> Result<()> {
+    let options = &session_config.options().catalog;
+    let catalog = PersistentCatalogProvider::try_new(path)?;
+    if catalog.schema(&options.default_schema).is_none() {
+        catalog.register_schema(
+            &options.default_schema,
+            Arc::new(MemorySchemaProvider::new()),
+        )?;
+    }
+    ctx.register_catalog(options.default_catalog.clone(), Arc::new(catalog));
+    Ok(())
+}
+
 fn create_runtime_env() -> Result<RuntimeEnv> {
     let rn_config = RuntimeConfig::new();
     RuntimeEnv::new(rn_config)
@@ -208,7 +208,10 @@ config_namespace! {
         /// target batch size is determined by the configuration setting
         pub coalesce_batches: bool, default = true
 
-        /// Should DataFusion collect statistics after listing files
+        /// Should DataFusion collect statistics after listing files. Listing
+        /// tables then also collect the statistics of their files when they are
+        /// first queried, which lets the optimizer reorder joins by their number
+        /// of rows
         pub collect_statistics: bool, default = false
 
         /// Number of partitions for query execution. Increasing partitions can increase
@@ -235,6 +238,12 @@ config_namespace! {
         ///
         /// Defaults to the number of CPU cores on the system
         pub planning_concurrency: usize, default = num_cpus::get()
+
+        /// Number of partitions a hash join splits both of its inputs into when
+        /// the build side does not fit into memory and has to be spilled to disk.
+        /// Partitions of the build side which still do not fit into memory are
+        /// partitioned again. Must be at least 1
+        pub hash_join_spill_partitions: usize, default = 16
     }
 }
 
@@ -251,6 +260,11 @@ config_namespace! {
         /// the parquet file
         pub pruning: bool, default = true
 
+        /// If true, the parquet reader reads the bloom filters of the columns
+        /// compared with literals by `=` or `IN` in the predicate, if present,
+        /// and skips the row groups that cannot contain matching values
+        pub bloom_filter_enabled: bool, default = true
+
         /// If true, the parquet reader skip the optional embedded metadata that may be in
         /// the file Schema. This setting can help avoid schema conflicts when querying
         /// multiple parquet files with schemas containing compatible types but different metadata
@@ -297,6 +311,12 @@ config_namespace! {
         /// repartitioning to increase parallelism to leverage more CPU cores
         pub enable_round_robin_repartition: bool, default = true
 
+        /// When set to true, the logical plan optimizer will reorder inner joins
+        /// between three or more relations to reduce the estimated size of the
+        /// intermediate results. The estimates are based on the statistics of the
+        /// scanned tables, joins of tables without statistics are not reordered.
+        pub enable_join_reordering: bool, default = true
+
         /// When set to true, the optimizer will insert filters before a join between
         /// a nullable and non-nullable column to filter out nulls on the nullable side. This
         /// filter can add additional overhead when the file format does not fully support
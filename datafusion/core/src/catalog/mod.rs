@@ -22,6 +22,7 @@
 pub mod catalog;
 pub mod information_schema;
 pub mod listing_schema;
+pub mod persistent_catalog;
 pub mod schema;
 
 pub use datafusion_sql::{ResolvedTableReference, TableReference};
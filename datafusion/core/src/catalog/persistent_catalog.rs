@@ -0,0 +1,535 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! persistent_catalog contains a CatalogProvider that stores the definitions
+//! of its schemas, tables and views in a local directory
+
+use crate::catalog::catalog::CatalogProvider;
+use crate::catalog::schema::SchemaProvider;
+use crate::datasource::view::ViewTable;
+use crate::datasource::TableProvider;
+use crate::execution::context::SessionState;
+use async_trait::async_trait;
+use dashmap::DashMap;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::{CreateView, DdlStatement, LogicalPlan};
+use datafusion_sql::parser::{DFParser, Statement};
+use log::warn;
+use percent_encoding::{
+    percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC,
+};
+use std::any::Any;
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use url::Url;
+
+/// Extension of the files holding the definitions of tables and views
+const DEFINITION_EXTENSION: &str = "sql";
+
+/// Extension of the files definitions are written to before replacing the
+/// previous definition
+const TEMPORARY_EXTENSION: &str = "tmp";
+
+/// Characters escaped in the names of the files and directories of a catalog
+const NAME_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'_').remove(b'-');
+
+/// A `CatalogProvider` that persists the statements creating its schemas,
+/// tables and views in a local directory, so that they can be restored by a
+/// later process.
+///
+/// Every schema is a subdirectory of the catalog directory, containing one
+/// `<table>.sql` file per table with the `CREATE EXTERNAL TABLE` or
+/// `CREATE VIEW` statement that created it. Relative `LOCATION`s of external
+/// tables are stored as absolute paths. Tables without a definition, for
+/// example those created with `CREATE TABLE AS` or registered with
+/// [`SessionContext::register_batch`], are only kept in memory.
+///
+/// Opening a catalog only restores its schemas. Its tables are created by
+/// [`PersistentCatalogProvider::refresh`], which
+/// [`SessionContext::refresh_catalogs`] calls for every registered
+/// `PersistentCatalogProvider`.
+///
+/// [`SessionContext::register_batch`]: crate::execution::context::SessionContext::register_batch
+/// [`SessionContext::refresh_catalogs`]: crate::execution::context::SessionContext::refresh_catalogs
+pub struct PersistentCatalogProvider {
+    path: PathBuf,
+    schemas: DashMap<String, Arc<PersistentSchemaProvider>>,
+}
+
+impl PersistentCatalogProvider {
+    /// Open the catalog stored in the directory `path`, creating the
+    /// directory if it does not exist
+    pub fn try_new(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        fs::create_dir_all(&path)?;
+
+        let schemas = DashMap::new();
+        for entry in fs::read_dir(&path)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                let name = decode_name(&entry.file_name())?;
+                schemas
+                    .insert(name, Arc::new(PersistentSchemaProvider::new(entry.path())));
+            }
+        }
+        Ok(Self { path, schemas })
+    }
+
+    /// Return the directory this catalog is stored in
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Create the tables and views stored in this catalog that are not
+    /// registered yet.
+    ///
+    /// Views are planned against the catalogs of `state`, which should
+    /// include this catalog for views to refer to its tables. As views may
+    /// depend on each other, definitions that fail are retried as long as
+    /// others succeed. Tables that still fail to load, for example because
+    /// their files were removed, are skipped with a warning.
+    pub async fn refresh(&self, state: &SessionState) -> Result<()> {
+        let schemas: Vec<_> = self.schemas.iter().map(|s| s.value().clone()).collect();
+        let mut pending = vec![];
+        for schema in schemas {
+            for (name, definition) in schema.definitions()? {
+                if !schema.table_exist(&name) {
+                    pending.push((schema.clone(), name, definition));
+                }
+            }
+        }
+
+        while !pending.is_empty() {
+            let num_pending = pending.len();
+            let mut failed = vec![];
+            for (schema, name, definition) in pending {
+                match create_table(state, &definition).await {
+                    Ok(table) => {
+                        schema.tables.insert(name, table);
+                    }
+                    Err(e) => failed.push((schema, name, definition, e)),
+                }
+            }
+            if failed.len() == num_pending {
+                for (schema, name, _, e) in failed {
+                    warn!(
+                        "Skipping table {name} of the persistent catalog schema {}: {e}",
+                        schema.path.display()
+                    );
+                }
+                break;
+            }
+            pending = failed
+                .into_iter()
+                .map(|(schema, name, definition, _)| (schema, name, definition))
+                .collect();
+        }
+        Ok(())
+    }
+
+    fn schema_path(&self, name: &str) -> PathBuf {
+        self.path.join(encode_name(name))
+    }
+}
+
+impl CatalogProvider for PersistentCatalogProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema_names(&self) -> Vec<String> {
+        self.schemas.iter().map(|s| s.key().clone()).collect()
+    }
+
+    fn schema(&self, name: &str) -> Option<Arc<dyn SchemaProvider>> {
+        self.schemas
+            .get(name)
+            .map(|s| s.value().clone() as Arc<dyn SchemaProvider>)
+    }
+
+    /// Adds a new, empty schema to this catalog. The tables of `schema` are
+    /// not copied, so registering a schema with tables is an error.
+    fn register_schema(
+        &self,
+        name: &str,
+        schema: Arc<dyn SchemaProvider>,
+    ) -> Result<Option<Arc<dyn SchemaProvider>>> {
+        if !schema.table_names().is_empty() {
+            return Err(DataFusionError::NotImplemented(
+                "Registering a schema with tables in a persistent catalog".to_string(),
+            ));
+        }
+
+        let path = self.schema_path(name);
+        let replaced = self.schemas.remove(name).map(|(_, s)| s);
+        if replaced.is_some() {
+            fs::remove_dir_all(&path)?;
+        }
+        fs::create_dir_all(&path)?;
+        self.schemas
+            .insert(name.into(), Arc::new(PersistentSchemaProvider::new(path)));
+        Ok(replaced.map(|s| s as Arc<dyn SchemaProvider>))
+    }
+
+    fn deregister_schema(
+        &self,
+        name: &str,
+        cascade: bool,
+    ) -> Result<Option<Arc<dyn SchemaProvider>>> {
+        let schema = match self.schemas.get(name) {
+            Some(schema) => schema.value().clone(),
+            None => return Ok(None),
+        };
+        let table_names = schema.table_names();
+        if !table_names.is_empty() && !cascade {
+            return Err(DataFusionError::Execution(format!(
+                "Cannot drop schema {} because other tables depend on it: {}",
+                name,
+                itertools::join(table_names.iter(), ", ")
+            )));
+        }
+
+        fs::remove_dir_all(&schema.path)?;
+        self.schemas.remove(name);
+        Ok(Some(schema))
+    }
+}
+
+/// A `SchemaProvider` of a [`PersistentCatalogProvider`], storing the
+/// definitions of its tables in a directory
+pub struct PersistentSchemaProvider {
+    path: PathBuf,
+    tables: DashMap<String, Arc<dyn TableProvider>>,
+}
+
+impl PersistentSchemaProvider {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            tables: DashMap::new(),
+        }
+    }
+
+    /// Return the directory this schema is stored in
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn definition_path(&self, name: &str) -> PathBuf {
+        self.path
+            .join(format!("{}.{DEFINITION_EXTENSION}", encode_name(name)))
+    }
+
+    /// Read the names and definitions of the tables stored in this schema,
+    /// skipping the files that cannot be read with a warning
+    fn definitions(&self) -> Result<Vec<(String, String)>> {
+        let mut definitions = vec![];
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if path.extension() != Some(OsStr::new(DEFINITION_EXTENSION)) {
+                continue;
+            }
+            let definition = path.file_stem().map(|stem| {
+                Ok::<_, DataFusionError>((decode_name(stem)?, fs::read_to_string(&path)?))
+            });
+            match definition {
+                Some(Ok(definition)) => definitions.push(definition),
+                Some(Err(e)) => {
+                    warn!("Skipping the table definition {}: {e}", path.display())
+                }
+                None => {}
+            }
+        }
+        Ok(definitions)
+    }
+}
+
+#[async_trait]
+impl SchemaProvider for PersistentSchemaProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        self.tables
+            .iter()
+            .map(|table| table.key().clone())
+            .collect()
+    }
+
+    async fn table(&self, name: &str) -> Option<Arc<dyn TableProvider>> {
+        self.tables.get(name).map(|table| table.value().clone())
+    }
+
+    fn register_table(
+        &self,
+        name: String,
+        table: Arc<dyn TableProvider>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        if self.table_exist(name.as_str()) {
+            return Err(DataFusionError::Execution(format!(
+                "The table {name} already exists"
+            )));
+        }
+        if let Some(definition) = table.get_table_definition() {
+            write_atomic(
+                &self.definition_path(&name),
+                &absolute_location(definition)?,
+            )?;
+        }
+        Ok(self.tables.insert(name, table))
+    }
+
+    fn deregister_table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        if !self.table_exist(name) {
+            return Ok(None);
+        }
+        match fs::remove_file(self.definition_path(name)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        Ok(self.tables.remove(name).map(|(_, table)| table))
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        self.tables.contains_key(name)
+    }
+}
+
+/// Create the table or view defined by the statement `definition`
+async fn create_table(
+    state: &SessionState,
+    definition: &str,
+) -> Result<Arc<dyn TableProvider>> {
+    match state.create_logical_plan(definition).await? {
+        LogicalPlan::Ddl(DdlStatement::CreateExternalTable(cmd)) => {
+            let file_type = cmd.file_type.to_uppercase();
+            let factory =
+                state
+                    .table_factories()
+                    .get(file_type.as_str())
+                    .ok_or_else(|| {
+                        DataFusionError::Execution(format!(
+                            "Unable to find factory for {}",
+                            cmd.file_type
+                        ))
+                    })?;
+            factory.create(state, &cmd).await
+        }
+        LogicalPlan::Ddl(DdlStatement::CreateView(CreateView {
+            input,
+            definition,
+            ..
+        })) => Ok(Arc::new(ViewTable::try_new((*input).clone(), definition)?)),
+        _ => Err(DataFusionError::Execution(format!(
+            "Cannot restore a table from the definition: {definition}"
+        ))),
+    }
+}
+
+/// Write `contents` to a temporary file that is then renamed to `path`, so
+/// that a crash never leaves a partially written file at `path`
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let temporary = path.with_extension(TEMPORARY_EXTENSION);
+    let mut file = File::create(&temporary)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+/// Return `definition` with a relative `LOCATION` on the local filesystem
+/// replaced by the absolute path it refers to, so that the table is
+/// restored from the same files by a process running in another directory
+fn absolute_location(definition: &str) -> Result<Cow<'_, str>> {
+    let mut cmd = match DFParser::parse_sql(definition)?.pop_front() {
+        Some(Statement::CreateExternalTable(cmd)) => cmd,
+        _ => return Ok(Cow::Borrowed(definition)),
+    };
+    let is_relative = !Path::new(&cmd.location).is_absolute()
+        && matches!(
+            Url::parse(&cmd.location),
+            Err(url::ParseError::RelativeUrlWithoutBase)
+        );
+    if !is_relative {
+        return Ok(Cow::Borrowed(definition));
+    }
+    let absolute = std::env::current_dir()?.join(&cmd.location);
+    cmd.location = absolute
+        .to_str()
+        .ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "Cannot store the location {} in a persistent catalog",
+                absolute.display()
+            ))
+        })?
+        .to_string();
+    Ok(Cow::Owned(cmd.to_string()))
+}
+
+fn encode_name(name: &str) -> String {
+    utf8_percent_encode(name, NAME_ENCODE_SET).to_string()
+}
+
+fn decode_name(file_name: &OsStr) -> Result<String> {
+    let invalid = || {
+        DataFusionError::Execution(format!(
+            "Invalid file name in persistent catalog: {file_name:?}"
+        ))
+    };
+    let file_name = file_name.to_str().ok_or_else(invalid)?;
+    percent_decode_str(file_name)
+        .decode_utf8()
+        .map(|name| name.into_owned())
+        .map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::catalog::schema::MemorySchemaProvider;
+    use crate::prelude::SessionContext;
+    use datafusion_common::assert_contains;
+    use tempfile::TempDir;
+
+    /// Create a context whose default catalog is stored in `path`
+    async fn open(path: &Path) -> Result<SessionContext> {
+        let ctx = SessionContext::new();
+        let catalog = PersistentCatalogProvider::try_new(path)?;
+        if catalog.schema("public").is_none() {
+            catalog.register_schema("public", Arc::new(MemorySchemaProvider::new()))?;
+        }
+        ctx.register_catalog("datafusion", Arc::new(catalog));
+        ctx.refresh_catalogs().await?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn restore_tables_views_and_schemas() -> Result<()> {
+        let dir = TempDir::new()?;
+
+        let ctx = open(dir.path()).await?;
+        ctx.sql("CREATE SCHEMA other").await?;
+        ctx.sql(
+            "CREATE EXTERNAL TABLE example STORED AS CSV WITH HEADER ROW \
+            LOCATION 'tests/data/example.csv'",
+        )
+        .await?;
+        ctx.sql("CREATE VIEW other.a_view AS SELECT a, b FROM example")
+            .await?;
+        ctx.sql("CREATE TABLE memory AS VALUES (1)").await?;
+        // the relative location is stored as an absolute path
+        let definition = fs::read_to_string(dir.path().join("public/example.sql"))?;
+        let location = std::env::current_dir()?.join("tests/data/example.csv");
+        assert_contains!(definition, format!("LOCATION '{}'", location.display()));
+
+        let ctx = open(dir.path()).await?;
+        let results = ctx
+            .sql("SELECT * FROM other.a_view")
+            .await?
+            .collect()
+            .await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | 2 |",
+            "+---+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+        // tables without a definition are not persisted
+        assert!(ctx.table("memory").await.is_err());
+
+        ctx.sql("DROP VIEW other.a_view").await?;
+        let ctx = open(dir.path()).await?;
+        assert!(ctx.table("other.a_view").await.is_err());
+        assert!(ctx.table("example").await.is_ok());
+
+        ctx.sql("DROP SCHEMA other CASCADE").await?;
+        let ctx = open(dir.path()).await?;
+        let catalog = ctx.catalog("datafusion").unwrap();
+        assert_eq!(catalog.schema_names(), vec!["public"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skip_tables_failing_to_load() -> Result<()> {
+        let dir = TempDir::new()?;
+
+        let ctx = open(dir.path()).await?;
+        ctx.sql(
+            "CREATE EXTERNAL TABLE example STORED AS CSV WITH HEADER ROW \
+            LOCATION 'tests/data/example.csv'",
+        )
+        .await?;
+        let public = dir.path().join("public");
+        fs::write(
+            public.join("missing.sql"),
+            "CREATE EXTERNAL TABLE missing STORED AS CSV LOCATION '/does/not/exist.csv'",
+        )?;
+        fs::write(public.join("invalid.sql"), "SELECT")?;
+        // an interrupted write leaves a temporary file that is ignored
+        fs::write(public.join("partial.tmp"), "CREATE EXTERNAL")?;
+
+        let ctx = open(dir.path()).await?;
+        let catalog = ctx.catalog("datafusion").unwrap();
+        let schema = catalog.schema("public").unwrap();
+        assert_eq!(schema.table_names(), vec!["example"]);
+        Ok(())
+    }
+
+    #[test]
+    fn store_absolute_location() -> Result<()> {
+        let cwd = std::env::current_dir()?;
+        let definition = "CREATE EXTERNAL TABLE t (location VARCHAR) \
+            STORED AS CSV OPTIONS ('location' 'it''s') LOCATION 'it''s/data.csv'";
+        let expected = format!(
+            "CREATE EXTERNAL TABLE t (location VARCHAR) \
+            STORED AS CSV OPTIONS ('location' 'it''s') LOCATION '{}'",
+            cwd.join("it's/data.csv")
+                .display()
+                .to_string()
+                .replace('\'', "''")
+        );
+        assert_eq!(absolute_location(definition)?, expected);
+
+        for definition in [
+            "CREATE EXTERNAL TABLE t STORED AS CSV LOCATION '/absolute/data.csv'",
+            "CREATE EXTERNAL TABLE t STORED AS CSV LOCATION 's3://bucket/data.csv'",
+            "CREATE VIEW v AS SELECT 'relative/data.csv'",
+        ] {
+            assert_eq!(absolute_location(definition)?, definition);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn encode_decode_name() -> Result<()> {
+        for name in ["simple_name", "with.dot", "with/slash", "Ünïcode", ".."] {
+            let encoded = encode_name(name);
+            assert!(!encoded.contains(['.', '/']));
+            assert_eq!(decode_name(OsStr::new(&encoded))?, name);
+        }
+        Ok(())
+    }
+}
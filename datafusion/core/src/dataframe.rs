@@ -30,8 +30,9 @@ use parquet::file::properties::WriterProperties;
 use datafusion_common::from_slice::FromSlice;
 use datafusion_common::{Column, DFSchema, ScalarValue};
 use datafusion_expr::{
-    avg, count, is_null, max, median, min, stddev, utils::COUNT_STAR_EXPANSION,
-    TableProviderFilterPushDown, UNNAMED_TABLE,
+    avg, count, is_null, max, median, min, stddev,
+    utils::{expand_wildcard_with_options, COUNT_STAR_EXPANSION},
+    TableProviderFilterPushDown, WildcardOptions, UNNAMED_TABLE,
 };
 
 use crate::arrow::datatypes::Schema;
@@ -150,6 +151,29 @@ impl DataFrame {
         Ok(DataFrame::new(self.session_state, project_plan))
     }
 
+    /// Create a projection of all columns, adjusted by `options` the way
+    /// `SELECT * EXCLUDE (..) RENAME (..) REPLACE (..)` does.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let options = WildcardOptions::new()
+    ///     .with_exclude("a")
+    ///     .with_replace("b", col("b") * lit(2));
+    /// let df = df.select_wildcard(options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_wildcard(self, options: WildcardOptions) -> Result<DataFrame> {
+        let expr_list =
+            expand_wildcard_with_options(self.plan.schema(), &self.plan, Some(&options))?;
+        self.select(expr_list)
+    }
+
     /// Expand each list element of a column to multiple rows.
     ///
     /// ```
@@ -1184,6 +1208,29 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn select_wildcard_with_options() -> Result<()> {
+        // build plan using Table API
+        let t = test_table().await?;
+        let options = WildcardOptions::new()
+            .with_exclude("c2")
+            .with_rename("c1", "one")
+            .with_replace("c3", col("c3") + lit(1i64));
+        let t2 = t.select_wildcard(options)?;
+        let plan = t2.plan.clone();
+
+        // build query using SQL
+        let sql_plan = create_plan(
+            "SELECT * EXCLUDE (c2) RENAME (c1 AS one) REPLACE (c3 + 1 AS c3) FROM aggregate_test_100",
+        )
+        .await?;
+
+        // the two plans should be identical
+        assert_same_plan(&plan, &sql_plan);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn select_with_window_exprs() -> Result<()> {
         // build plan using Table API
@@ -0,0 +1,85 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CteWorkTable`] is the table a recursive query reads its own rows from
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+
+use crate::datasource::{TableProvider, TableType};
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::logical_expr::Expr;
+use crate::physical_plan::work_table::WorkTableExec;
+use crate::physical_plan::ExecutionPlan;
+
+/// The table a recursive common table expression refers to within its
+/// recursive term, holding the rows of the previous iteration.
+///
+/// The rows are only known while the query is executed, so scanning the
+/// table creates a [`WorkTableExec`] that is connected to the rows by the
+/// enclosing [`RecursiveQueryExec`].
+///
+/// [`RecursiveQueryExec`]: crate::physical_plan::recursive_query::RecursiveQueryExec
+pub struct CteWorkTable {
+    /// Name of the recursive query
+    name: String,
+    /// Schema of the rows of the recursive query
+    table_schema: SchemaRef,
+}
+
+impl CteWorkTable {
+    /// Create a new work table for the recursive query called `name`
+    pub fn new(name: &str, table_schema: SchemaRef) -> Self {
+        Self {
+            name: name.to_owned(),
+            table_schema,
+        }
+    }
+}
+
+#[async_trait]
+impl TableProvider for CteWorkTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table_schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Temporary
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(WorkTableExec::try_new(
+            self.name.clone(),
+            self.table_schema.clone(),
+            projection.cloned(),
+        )?))
+    }
+}
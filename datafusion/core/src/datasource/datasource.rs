@@ -98,6 +98,19 @@ pub trait TableProvider: Sync + Send {
         None
     }
 
+    /// Whether this table can take part in transactions. Such tables enlist
+    /// a [`TransactionalTable`] with the transaction in progress (see
+    /// [`SessionState::transaction`]) when they are scanned or modified, so
+    /// their changes are committed or rolled back with the transaction.
+    ///
+    /// Tables that do not support transactions cannot be modified while a
+    /// transaction is in progress.
+    ///
+    /// [`TransactionalTable`]: crate::execution::transaction::TransactionalTable
+    fn supports_transactions(&self) -> bool {
+        false
+    }
+
     /// Insert into this table
     async fn insert_into(
         &self,
@@ -107,6 +120,34 @@ pub trait TableProvider: Sync + Send {
         let msg = "Insertion not implemented for this table".to_owned();
         Err(DataFusionError::NotImplemented(msg))
     }
+
+    /// Delete the rows of this table that match all of the `filters`, or
+    /// all rows if there are none. The returned plan produces a single row
+    /// with the number of deleted rows in a `count` column.
+    async fn delete_from(
+        &self,
+        _state: &SessionState,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let msg = "Deletion not implemented for this table".to_owned();
+        Err(DataFusionError::NotImplemented(msg))
+    }
+
+    /// Update the rows of this table that match all of the `filters`, or
+    /// all rows if there are none, by setting each of the named columns in
+    /// `assignments` to the value of its expression. The expressions are
+    /// evaluated against the rows before the update. The returned plan
+    /// produces a single row with the number of updated rows in a `count`
+    /// column.
+    async fn update(
+        &self,
+        _state: &SessionState,
+        _assignments: Vec<(String, Expr)>,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let msg = "Update not implemented for this table".to_owned();
+        Err(DataFusionError::NotImplemented(msg))
+    }
 }
 
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
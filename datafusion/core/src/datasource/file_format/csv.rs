@@ -22,7 +22,9 @@ use std::any::Any;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use arrow::csv::WriterBuilder;
 use arrow::datatypes::{DataType, Field, Fields, Schema};
+use arrow::record_batch::RecordBatch;
 use arrow::{self, datatypes::SchemaRef};
 use async_trait::async_trait;
 use bytes::{Buf, Bytes};
@@ -34,7 +36,7 @@ use futures::stream::BoxStream;
 use futures::{pin_mut, Stream, StreamExt, TryStreamExt};
 use object_store::{delimited::newline_delimited_stream, ObjectMeta, ObjectStore};
 
-use super::FileFormat;
+use super::{BatchSerializer, FileFormat};
 use crate::datasource::file_format::file_type::FileCompressionType;
 use crate::datasource::file_format::DEFAULT_SCHEMA_INFER_MAX_RECORD;
 use crate::error::Result;
@@ -162,6 +164,11 @@ impl CsvFormat {
     pub fn delimiter(&self) -> u8 {
         self.delimiter
     }
+
+    /// The `FileCompressionType` of the CSV files.
+    pub fn file_compression_type(&self) -> &FileCompressionType {
+        &self.file_compression_type
+    }
 }
 
 #[async_trait]
@@ -220,6 +227,43 @@ impl FileFormat for CsvFormat {
         );
         Ok(Arc::new(exec))
     }
+
+    fn create_serializer(&self, _schema: SchemaRef) -> Result<Box<dyn BatchSerializer>> {
+        if self.file_compression_type.is_compressed() {
+            return Err(DataFusionError::NotImplemented(
+                "Writing compressed CSV files is not implemented".to_string(),
+            ));
+        }
+        Ok(Box::new(CsvSerializer {
+            has_header: self.has_header,
+            delimiter: self.delimiter,
+        }))
+    }
+}
+
+/// [`BatchSerializer`] of [`CsvFormat`]
+struct CsvSerializer {
+    /// Whether the header still has to be written
+    has_header: bool,
+    delimiter: u8,
+}
+
+impl BatchSerializer for CsvSerializer {
+    fn serialize(&mut self, batch: &RecordBatch) -> Result<Bytes> {
+        let mut buffer = Vec::with_capacity(4096);
+        let mut writer = WriterBuilder::new()
+            .has_headers(self.has_header)
+            .with_delimiter(self.delimiter)
+            .build(&mut buffer);
+        writer.write(batch)?;
+        drop(writer);
+        self.has_header = false;
+        Ok(Bytes::from(buffer))
+    }
+
+    fn finish(self: Box<Self>) -> Result<Bytes> {
+        Ok(Bytes::new())
+    }
 }
 
 impl CsvFormat {
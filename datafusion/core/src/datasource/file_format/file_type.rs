@@ -30,6 +30,11 @@ use async_compression::tokio::bufread::{
     XzDecoder as AsyncXzDecoder, XzEncoder as AsyncXzEncoder,
     ZstdDecoder as AsyncZstdDecoer, ZstdEncoder as AsyncZstdEncoder,
 };
+#[cfg(feature = "compression")]
+use async_compression::tokio::write::{
+    BzEncoder as AsyncBzWriter, GzipEncoder as AsyncGzWriter, XzEncoder as AsyncXzWriter,
+    ZstdEncoder as AsyncZstdWriter,
+};
 
 use bytes::Bytes;
 #[cfg(feature = "compression")]
@@ -43,6 +48,7 @@ use futures::StreamExt;
 #[cfg(feature = "compression")]
 use futures::TryStreamExt;
 use std::str::FromStr;
+use tokio::io::AsyncWrite;
 #[cfg(feature = "compression")]
 use tokio_util::io::{ReaderStream, StreamReader};
 #[cfg(feature = "compression")]
@@ -111,6 +117,11 @@ impl FileCompressionType {
         variant: UNCOMPRESSED,
     };
 
+    /// Read only access to self.variant
+    pub fn get_variant(&self) -> &CompressionTypeVariant {
+        &self.variant
+    }
+
     /// The file is compressed or not
     pub const fn is_compressed(&self) -> bool {
         self.variant.is_compressed()
@@ -180,6 +191,32 @@ impl FileCompressionType {
         })
     }
 
+    /// Given an `AsyncWrite`, create an `AsyncWrite` which compresses the data
+    /// written to it with `FileCompressionType`. The compressed data are
+    /// completed when the returned writer is shut down.
+    pub fn convert_async_writer(
+        &self,
+        w: Box<dyn AsyncWrite + Send + Unpin>,
+    ) -> Result<Box<dyn AsyncWrite + Send + Unpin>> {
+        Ok(match self.variant {
+            #[cfg(feature = "compression")]
+            GZIP => Box::new(AsyncGzWriter::new(w)),
+            #[cfg(feature = "compression")]
+            BZIP2 => Box::new(AsyncBzWriter::new(w)),
+            #[cfg(feature = "compression")]
+            XZ => Box::new(AsyncXzWriter::new(w)),
+            #[cfg(feature = "compression")]
+            ZSTD => Box::new(AsyncZstdWriter::new(w)),
+            #[cfg(not(feature = "compression"))]
+            GZIP | BZIP2 | XZ | ZSTD => {
+                return Err(DataFusionError::NotImplemented(
+                    "Compression feature is not enabled".to_owned(),
+                ))
+            }
+            UNCOMPRESSED => w,
+        })
+    }
+
     /// Given a `Read`, create a `Read` which data are decompressed with `FileCompressionType`.
     pub fn convert_read<T: std::io::Read + Send + 'static>(
         &self,
@@ -26,14 +26,17 @@ use arrow::datatypes::Schema;
 use arrow::datatypes::SchemaRef;
 use arrow::json::reader::infer_json_schema_from_iterator;
 use arrow::json::reader::ValueIter;
+use arrow::json::LineDelimitedWriter;
+use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 
+use datafusion_common::DataFusionError;
 use datafusion_physical_expr::PhysicalExpr;
 use object_store::{GetResult, ObjectMeta, ObjectStore};
 
-use super::FileFormat;
 use super::FileScanConfig;
+use super::{BatchSerializer, FileFormat};
 use crate::datasource::file_format::file_type::FileCompressionType;
 use crate::datasource::file_format::DEFAULT_SCHEMA_INFER_MAX_RECORD;
 use crate::error::Result;
@@ -77,6 +80,11 @@ impl JsonFormat {
         self.file_compression_type = file_compression_type;
         self
     }
+
+    /// The `FileCompressionType` of the JSON files.
+    pub fn file_compression_type(&self) -> &FileCompressionType {
+        &self.file_compression_type
+    }
 }
 
 #[async_trait]
@@ -148,6 +156,32 @@ impl FileFormat for JsonFormat {
         let exec = NdJsonExec::new(conf, self.file_compression_type.to_owned());
         Ok(Arc::new(exec))
     }
+
+    fn create_serializer(&self, _schema: SchemaRef) -> Result<Box<dyn BatchSerializer>> {
+        if self.file_compression_type.is_compressed() {
+            return Err(DataFusionError::NotImplemented(
+                "Writing compressed JSON files is not implemented".to_string(),
+            ));
+        }
+        Ok(Box::new(JsonSerializer {}))
+    }
+}
+
+/// [`BatchSerializer`] of [`JsonFormat`]
+struct JsonSerializer {}
+
+impl BatchSerializer for JsonSerializer {
+    fn serialize(&mut self, batch: &RecordBatch) -> Result<Bytes> {
+        let mut buffer = Vec::with_capacity(4096);
+        let mut writer = LineDelimitedWriter::new(&mut buffer);
+        writer.write_batches(std::slice::from_ref(batch))?;
+        writer.finish()?;
+        Ok(Bytes::from(buffer))
+    }
+
+    fn finish(self: Box<Self>) -> Result<Bytes> {
+        Ok(Bytes::new())
+    }
 }
 
 #[cfg(test)]
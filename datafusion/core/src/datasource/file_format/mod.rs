@@ -32,12 +32,14 @@ use std::fmt;
 use std::sync::Arc;
 
 use crate::arrow::datatypes::SchemaRef;
-use crate::error::Result;
+use crate::arrow::record_batch::RecordBatch;
+use crate::error::{DataFusionError, Result};
 use crate::physical_plan::file_format::FileScanConfig;
 use crate::physical_plan::{ExecutionPlan, Statistics};
 
 use crate::execution::context::SessionState;
 use async_trait::async_trait;
+use bytes::Bytes;
 use datafusion_physical_expr::PhysicalExpr;
 use object_store::{ObjectMeta, ObjectStore};
 
@@ -86,6 +88,26 @@ pub trait FileFormat: Send + Sync + fmt::Debug {
         conf: FileScanConfig,
         filters: Option<&Arc<dyn PhysicalExpr>>,
     ) -> Result<Arc<dyn ExecutionPlan>>;
+
+    /// Create a [`BatchSerializer`] for writing record batches of the given
+    /// schema into a new file of this format.
+    ///
+    /// Formats that do not support writing return an error.
+    fn create_serializer(&self, _schema: SchemaRef) -> Result<Box<dyn BatchSerializer>> {
+        Err(DataFusionError::NotImplemented(format!(
+            "Writing is not implemented for {self:?}"
+        )))
+    }
+}
+
+/// Serializes record batches into the content of a single file of a
+/// [`FileFormat`], so that it can be written incrementally.
+pub trait BatchSerializer: Send {
+    /// Serialize `batch`, returning the bytes to append to the file
+    fn serialize(&mut self, batch: &RecordBatch) -> Result<Bytes>;
+
+    /// Finish the file, returning the bytes to append after the last batch
+    fn finish(self: Box<Self>) -> Result<Bytes>;
 }
 
 #[cfg(test)]
@@ -18,23 +18,27 @@
 //! Parquet format abstractions
 
 use std::any::Any;
+use std::io::Write;
 use std::sync::Arc;
 
 use arrow::datatypes::SchemaRef;
 use arrow::datatypes::{Fields, Schema};
+use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
-use bytes::{BufMut, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use datafusion_common::DataFusionError;
 use datafusion_physical_expr::PhysicalExpr;
 use hashbrown::HashMap;
 use object_store::{ObjectMeta, ObjectStore};
-use parquet::arrow::parquet_to_arrow_schema;
+use parking_lot::Mutex;
+use parquet::arrow::{parquet_to_arrow_schema, ArrowWriter};
 use parquet::file::footer::{decode_footer, decode_metadata};
 use parquet::file::metadata::ParquetMetaData;
+use parquet::file::properties::WriterProperties;
 use parquet::file::statistics::Statistics as ParquetStatistics;
 
-use super::FileFormat;
 use super::FileScanConfig;
+use super::{BatchSerializer, FileFormat};
 use crate::arrow::array::{
     BooleanArray, Float32Array, Float64Array, Int32Array, Int64Array,
 };
@@ -66,6 +70,8 @@ pub struct ParquetFormat {
     metadata_size_hint: Option<usize>,
     /// Override the global setting for `skip_metadata`
     skip_metadata: Option<bool>,
+    /// Properties of the files written in this format
+    writer_properties: Option<WriterProperties>,
 }
 
 impl ParquetFormat {
@@ -120,6 +126,20 @@ impl ParquetFormat {
         self.skip_metadata
             .unwrap_or(config_options.execution.parquet.skip_metadata)
     }
+
+    /// Set the properties, like the compression, of the files written in
+    /// this format
+    ///
+    /// - If `None`, the defaults of [`WriterProperties`] are used
+    pub fn with_writer_properties(mut self, props: Option<WriterProperties>) -> Self {
+        self.writer_properties = props;
+        self
+    }
+
+    /// Return the properties of the files written in this format, if set
+    pub fn writer_properties(&self) -> Option<&WriterProperties> {
+        self.writer_properties.as_ref()
+    }
 }
 
 /// Clears all metadata (Schema level and field level) on an iterator
@@ -204,6 +224,56 @@ impl FileFormat for ParquetFormat {
             self.metadata_size_hint(state.config_options()),
         )))
     }
+
+    fn create_serializer(&self, schema: SchemaRef) -> Result<Box<dyn BatchSerializer>> {
+        let buffer = SharedBuffer::default();
+        let writer =
+            ArrowWriter::try_new(buffer.clone(), schema, self.writer_properties.clone())?;
+        Ok(Box::new(ParquetSerializer { buffer, writer }))
+    }
+}
+
+/// In-memory sink of an [`ArrowWriter`], drained after every batch
+#[derive(Clone, Default)]
+struct SharedBuffer {
+    buffer: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SharedBuffer {
+    /// Removes and returns the bytes written so far
+    fn take(&self) -> Bytes {
+        Bytes::from(std::mem::take(&mut *self.buffer.lock()))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// [`BatchSerializer`] of [`ParquetFormat`]
+struct ParquetSerializer {
+    buffer: SharedBuffer,
+    writer: ArrowWriter<SharedBuffer>,
+}
+
+impl BatchSerializer for ParquetSerializer {
+    fn serialize(&mut self, batch: &RecordBatch) -> Result<Bytes> {
+        self.writer.write(batch)?;
+        Ok(self.buffer.take())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Bytes> {
+        let Self { buffer, writer } = *self;
+        writer.close()?;
+        Ok(buffer.take())
+    }
 }
 
 fn summarize_min_max(
@@ -545,7 +615,7 @@ pub(crate) mod test_util {
     use crate::test::object_store::local_unpartitioned_file;
     use arrow::record_batch::RecordBatch;
     use parquet::arrow::ArrowWriter;
-    use parquet::file::properties::WriterProperties;
+    use parquet::file::properties::{WriterProperties, WriterPropertiesBuilder};
     use tempfile::NamedTempFile;
 
     /// How many rows per page should be written
@@ -559,6 +629,16 @@ pub(crate) mod test_util {
     pub async fn store_parquet(
         batches: Vec<RecordBatch>,
         multi_page: bool,
+    ) -> Result<(Vec<ObjectMeta>, Vec<NamedTempFile>)> {
+        store_parquet_with_properties(batches, multi_page, |builder| builder).await
+    }
+
+    /// Writes `batches` to temporary parquet files like [`store_parquet`],
+    /// with writer properties customized by `properties`
+    pub async fn store_parquet_with_properties(
+        batches: Vec<RecordBatch>,
+        multi_page: bool,
+        properties: impl Fn(WriterPropertiesBuilder) -> WriterPropertiesBuilder,
     ) -> Result<(Vec<ObjectMeta>, Vec<NamedTempFile>)> {
         // Each batch writes to their own file
         let files: Vec<_> = batches
@@ -566,7 +646,7 @@ pub(crate) mod test_util {
             .map(|batch| {
                 let mut output = NamedTempFile::new().expect("creating temp file");
 
-                let builder = WriterProperties::builder();
+                let builder = properties(WriterProperties::builder());
                 let props = if multi_page {
                     builder.set_data_page_row_count_limit(ROWS_PER_PAGE)
                 } else {
@@ -45,11 +45,50 @@ use datafusion_expr::expr::ScalarUDF;
 use datafusion_expr::{Expr, Volatility};
 use object_store::path::Path;
 use object_store::{ObjectMeta, ObjectStore};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
 
 const FILE_SIZE_COLUMN_NAME: &str = "_df_part_file_size_";
 const FILE_PATH_COLUMN_NAME: &str = "_df_part_file_path_";
 const FILE_MODIFIED_COLUMN_NAME: &str = "_df_part_file_modified_";
 
+/// Directory value used by hive for NULL partition values
+pub(crate) const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Characters escaped by hive in partition directory values
+const HIVE_ESCAPE_SET: &AsciiSet = &CONTROLS
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'\'')
+    .add(b'*')
+    .add(b'/')
+    .add(b':')
+    .add(b'=')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'{')
+    .add(b'[')
+    .add(b']')
+    .add(b'^');
+
+/// Escapes a partition value the way hive does, so that it can be used
+/// as the value part of a `name=value` partition directory
+pub(crate) fn escape_partition_value(value: &str) -> String {
+    utf8_percent_encode(value, HIVE_ESCAPE_SET).to_string()
+}
+
+/// Parses the value part of a `name=value` partition directory, undoing
+/// the escaping of [`escape_partition_value`]
+fn parse_partition_value(value: &str, data_type: &DataType) -> Result<ScalarValue> {
+    if value == HIVE_DEFAULT_PARTITION {
+        return ScalarValue::try_from(data_type);
+    }
+    let value = percent_decode_str(value).decode_utf8().map_err(|e| {
+        DataFusionError::Execution(format!("Invalid partition value {value}: {e}"))
+    })?;
+    ScalarValue::try_from_string(value.into_owned(), data_type)
+}
+
 /// Check whether the given expression can be resolved using only the columns `col_names`.
 /// This means that if this function returns true:
 /// - the table provider can filter the table partition values with this expression
@@ -207,16 +246,13 @@ pub async fn pruned_partition_list<'a>(
                     p.iter()
                         .zip(table_partition_cols)
                         .map(|(&part_value, part_column)| {
-                            ScalarValue::try_from_string(
-                                part_value.to_string(),
-                                &part_column.1,
-                            )
-                            .unwrap_or_else(|_| {
-                                panic!(
-                                    "Failed to cast str {} to type {}",
-                                    part_value, part_column.1
-                                )
-                            })
+                            parse_partition_value(part_value, &part_column.1)
+                                .unwrap_or_else(|_| {
+                                    panic!(
+                                        "Failed to cast str {} to type {}",
+                                        part_value, part_column.1
+                                    )
+                                })
                         })
                         .collect()
                 });
@@ -283,10 +319,8 @@ fn paths_to_batch(
             length_builder.append_value(file_meta.size as u64);
             modified_builder.append_value(file_meta.last_modified.timestamp_millis());
             for (i, part_val) in partition_values.iter().enumerate() {
-                let scalar_val = ScalarValue::try_from_string(
-                    part_val.to_string(),
-                    &table_partition_cols[i].1,
-                )?;
+                let scalar_val =
+                    parse_partition_value(part_val, &table_partition_cols[i].1)?;
                 partition_scalar_values[i].push(scalar_val);
             }
         } else {
@@ -315,8 +349,9 @@ fn paths_to_batch(
         Field::new(FILE_SIZE_COLUMN_NAME, DataType::UInt64, false),
         Field::new(FILE_MODIFIED_COLUMN_NAME, DataType::Date64, true),
     ];
-    for part_col in table_partition_cols {
-        fields.push(Field::new(&part_col.0, part_col.1.to_owned(), false));
+    for (part_col, part_array) in table_partition_cols.iter().zip(&col_arrays[3..]) {
+        let nullable = part_array.null_count() > 0;
+        fields.push(Field::new(&part_col.0, part_col.1.to_owned(), nullable));
     }
 
     let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), col_arrays)?;
@@ -631,6 +666,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_partition_value_escaping() {
+        let value = "a/b=c%d e";
+        let escaped = escape_partition_value(value);
+        assert_eq!(escaped, "a%2Fb%3Dc%25d e");
+        assert_eq!(
+            parse_partition_value(&escaped, &DataType::Utf8).unwrap(),
+            ScalarValue::Utf8(Some(value.to_string()))
+        );
+        assert_eq!(
+            parse_partition_value(HIVE_DEFAULT_PARTITION, &DataType::Int32).unwrap(),
+            ScalarValue::Int32(None)
+        );
+    }
+
     #[test]
     fn test_path_batch_roundtrip_no_partiton() {
         let files = vec![
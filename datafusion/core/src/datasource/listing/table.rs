@@ -21,7 +21,7 @@ use std::str::FromStr;
 use std::{any::Any, sync::Arc};
 
 use arrow::compute::SortOptions;
-use arrow::datatypes::{DataType, Field, SchemaBuilder, SchemaRef};
+use arrow::datatypes::{DataType, Field, Schema, SchemaBuilder, SchemaRef};
 use async_trait::async_trait;
 use dashmap::DashMap;
 use datafusion_common::ToDFSchema;
@@ -31,6 +31,7 @@ use datafusion_physical_expr::{create_physical_expr, PhysicalSortExpr};
 use futures::{future, stream, StreamExt, TryStreamExt};
 use object_store::path::Path;
 use object_store::ObjectMeta;
+use parking_lot::RwLock;
 
 use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
 use crate::datasource::{
@@ -49,8 +50,9 @@ use crate::{
     execution::context::SessionState,
     logical_expr::Expr,
     physical_plan::{
-        empty::EmptyExec, file_format::FileScanConfig, project_schema, ExecutionPlan,
-        Statistics,
+        empty::EmptyExec,
+        file_format::{FileScanConfig, FileWriteConfig, FileWriteExec},
+        project_schema, ExecutionPlan, Statistics,
     },
 };
 
@@ -551,6 +553,10 @@ pub struct ListingTable {
     options: ListingOptions,
     definition: Option<String>,
     collected_statistics: StatisticsCache,
+    /// Statistics of all the files of the table, collected by
+    /// [`ListingTable::collect_statistics`]. Shared with the plans inserting
+    /// into the table, which invalidate them once they wrote new files
+    table_statistics: Arc<RwLock<Option<Statistics>>>,
     infinite_source: bool,
 }
 
@@ -588,12 +594,37 @@ impl ListingTable {
             options,
             definition: None,
             collected_statistics: Default::default(),
+            table_statistics: Default::default(),
             infinite_source,
         };
 
         Ok(table)
     }
 
+    /// Makes the partition columns named `names` nullable. Rows with a NULL
+    /// partition value are stored in the hive default partition directory.
+    pub fn with_nullable_partition_cols(mut self, names: &[String]) -> Self {
+        let num_file_fields = self.file_schema.fields().len();
+        let fields: Vec<_> = self
+            .table_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                if idx >= num_file_fields && names.contains(field.name()) {
+                    Arc::new(field.as_ref().clone().with_nullable(true))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        self.table_schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            self.table_schema.metadata().clone(),
+        ));
+        self
+    }
+
     /// Specify the SQL definition for this table, if any
     pub fn with_definition(mut self, defintion: Option<String>) -> Self {
         self.definition = defintion;
@@ -610,6 +641,35 @@ impl ListingTable {
         &self.options
     }
 
+    /// Returns the statistics of all the files of the table, which are then
+    /// also returned by [`TableProvider::statistics`], e.g. to let the
+    /// logical optimizer reorder joins by the number of rows of the tables.
+    ///
+    /// The files are only listed on the first call, the statistics are cached
+    /// until [`ListingTable::invalidate_statistics`] is called. Returns `None`
+    /// if [`ListingOptions::collect_stat`] is not set or the table is an
+    /// infinite source.
+    pub async fn collect_statistics(
+        &self,
+        state: &SessionState,
+    ) -> Result<Option<Statistics>> {
+        if !self.options.collect_stat || self.infinite_source {
+            return Ok(None);
+        }
+        if let Some(statistics) = self.table_statistics.read().clone() {
+            return Ok(Some(statistics));
+        }
+        let (_, statistics) = self.list_files_for_scan(state, &[], None).await?;
+        *self.table_statistics.write() = Some(statistics.clone());
+        Ok(Some(statistics))
+    }
+
+    /// Discards the statistics cached by [`ListingTable::collect_statistics`],
+    /// e.g. after files were added to the table
+    pub fn invalidate_statistics(&self) {
+        *self.table_statistics.write() = None;
+    }
+
     /// If file_sort_order is specified, creates the appropriate physical expressions
     fn try_create_output_ordering(&self) -> Result<Option<Vec<PhysicalSortExpr>>> {
         let file_sort_order =
@@ -665,6 +725,12 @@ impl TableProvider for ListingTable {
         TableType::Base
     }
 
+    /// Returns the statistics of the files of the table, once they have been
+    /// collected by [`ListingTable::collect_statistics`]
+    fn statistics(&self) -> Option<Statistics> {
+        self.table_statistics.read().clone()
+    }
+
     async fn scan(
         &self,
         state: &SessionState,
@@ -758,6 +824,84 @@ impl TableProvider for ListingTable {
     fn get_table_definition(&self) -> Option<&str> {
         self.definition.as_deref()
     }
+
+    /// Inserts the execution results of `input` by writing them into new
+    /// files in the [`FileFormat`] of this table below its path. If the
+    /// table has partition columns, the rows are written into the hive
+    /// style `col=value/` directories of their partitions.
+    async fn insert_into(
+        &self,
+        _state: &SessionState,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // Check that the schema of the plan matches the schema of this table.
+        let input_schema = input.schema();
+        let schema_matches = input_schema.fields().len()
+            == self.table_schema.fields().len()
+            && input_schema
+                .fields()
+                .iter()
+                .zip(self.table_schema.fields())
+                .all(|(input, table)| input.data_type() == table.data_type());
+        if !schema_matches {
+            return Err(DataFusionError::Plan(
+                "Inserting query must have the same schema with the table.".to_string(),
+            ));
+        }
+
+        let table_path = match self.table_paths.as_slice() {
+            [table_path] if table_path.is_collection() => table_path,
+            _ => {
+                return Err(DataFusionError::NotImplemented(
+                    "Inserting into a ListingTable is only supported if it has a single directory as table path".to_string(),
+                ))
+            }
+        };
+
+        // The files are compressed as a whole while they are written, so the
+        // rows are serialized by the uncompressed variant of the format
+        let format = &self.options.format;
+        let (format, file_compression_type): (Arc<dyn FileFormat>, _) =
+            if let Some(csv) = format.as_any().downcast_ref::<CsvFormat>() {
+                (
+                    Arc::new(
+                        CsvFormat::default()
+                            .with_has_header(csv.has_header())
+                            .with_delimiter(csv.delimiter()),
+                    ),
+                    csv.file_compression_type().clone(),
+                )
+            } else if let Some(json) = format.as_any().downcast_ref::<JsonFormat>() {
+                (
+                    Arc::new(JsonFormat::default()),
+                    json.file_compression_type().clone(),
+                )
+            } else {
+                (format.clone(), FileCompressionType::UNCOMPRESSED)
+            };
+
+        let config = FileWriteConfig {
+            object_store_url: table_path.object_store(),
+            table_path: table_path.prefix().clone(),
+            file_extension: self.options.file_extension.clone(),
+            file_name: None,
+            format,
+            file_compression_type,
+            file_schema: self.file_schema.clone(),
+            table_partition_cols: self
+                .options
+                .table_partition_cols
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect(),
+        };
+        // The statistics no longer cover the files of the table once the new
+        // files are written
+        let table_statistics = Arc::clone(&self.table_statistics);
+        let exec = FileWriteExec::new(input, config)
+            .with_on_written(Arc::new(move || *table_statistics.write() = None));
+        Ok(Arc::new(exec))
+    }
 }
 
 impl ListingTable {
@@ -834,11 +978,15 @@ mod tests {
         datasource::file_format::{avro::AvroFormat, parquet::ParquetFormat},
         execution::options::ReadOptions,
         logical_expr::{col, lit},
+        physical_plan::collect,
         test::{columns, object_store::register_test_store},
     };
+    use arrow::array::Int32Array;
     use arrow::datatypes::{DataType, Schema};
+    use arrow::record_batch::RecordBatch;
     use chrono::DateTime;
     use datafusion_common::assert_contains;
+    use parquet::arrow::ArrowWriter;
     use rstest::*;
     use std::fs::File;
     use tempfile::TempDir;
@@ -1426,4 +1574,231 @@ mod tests {
         meta2.location = Path::from("test2");
         assert!(cache.get(&meta2).is_none());
     }
+
+    /// Creates a listing table of `file_fields` stored in `format` below
+    /// `path`, partitioned by `partition_cols`
+    fn insert_table(
+        path: &std::path::Path,
+        format: Arc<dyn FileFormat>,
+        file_type: FileType,
+        file_fields: Vec<Field>,
+        partition_cols: Vec<(String, DataType)>,
+    ) -> Result<ListingTable> {
+        let table_path = ListingTableUrl::parse(path.to_str().unwrap())?;
+        let options = ListingOptions::new(format)
+            .with_file_extension(file_type.get_ext())
+            .with_table_partition_cols(partition_cols);
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(Arc::new(Schema::new(file_fields)));
+        ListingTable::try_new(config)
+    }
+
+    #[tokio::test]
+    async fn test_insert_into() -> Result<()> {
+        let formats: Vec<(Arc<dyn FileFormat>, FileType)> = vec![
+            (Arc::new(CsvFormat::default()), FileType::CSV),
+            (Arc::new(JsonFormat::default()), FileType::JSON),
+            (Arc::new(ParquetFormat::default()), FileType::PARQUET),
+        ];
+        for (format, file_type) in formats {
+            let ctx = SessionContext::new();
+            let tmp_dir = TempDir::new()?;
+            let file_fields = vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Utf8, true),
+            ];
+            let table =
+                insert_table(tmp_dir.path(), format, file_type, file_fields, vec![])?;
+            ctx.register_table("t", Arc::new(table))?;
+
+            let sql = "INSERT INTO t VALUES (1, 'x'), (2, 'y')";
+            ctx.sql(sql).await?.collect().await?;
+            let sql = "INSERT INTO t SELECT a + 2, b FROM t";
+            ctx.sql(sql).await?.collect().await?;
+
+            let batches = ctx.sql("SELECT * FROM t").await?.collect().await?;
+            let expected = vec![
+                "+---+---+",
+                "| a | b |",
+                "+---+---+",
+                "| 1 | x |",
+                "| 2 | y |",
+                "| 3 | x |",
+                "| 4 | y |",
+                "+---+---+",
+            ];
+            crate::assert_batches_sorted_eq!(expected, &batches);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_into_compressed() -> Result<()> {
+        for file_type in ["CSV", "JSON"] {
+            for compression in ["GZIP", "BZIP2", "XZ", "ZSTD"] {
+                let ctx = SessionContext::new();
+                let tmp_dir = TempDir::new()?;
+                let sql = format!(
+                    "CREATE EXTERNAL TABLE t (a INT, b VARCHAR) STORED AS {file_type} \
+                     COMPRESSION TYPE {compression} LOCATION '{}'",
+                    tmp_dir.path().to_str().unwrap()
+                );
+                ctx.sql(&sql).await?.collect().await?;
+
+                let sql = "INSERT INTO t VALUES (1, 'x'), (2, 'y')";
+                ctx.sql(sql).await?.collect().await?;
+
+                let batches = ctx.sql("SELECT * FROM t").await?.collect().await?;
+                let expected = vec![
+                    "+---+---+",
+                    "| a | b |",
+                    "+---+---+",
+                    "| 1 | x |",
+                    "| 2 | y |",
+                    "+---+---+",
+                ];
+                crate::assert_batches_sorted_eq!(expected, &batches);
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_statistics_collected_when_queried() -> Result<()> {
+        let config = SessionConfig::new().with_collect_statistics(true);
+        let ctx = SessionContext::with_config(config);
+        let tmp_dir = TempDir::new()?;
+        let batch = RecordBatch::try_from_iter(vec![(
+            "a",
+            Arc::new(Int32Array::from(vec![1])) as _,
+        )])?;
+        let file = File::create(tmp_dir.path().join("1.parquet"))?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+
+        let sql = format!(
+            "CREATE EXTERNAL TABLE t STORED AS PARQUET LOCATION '{}'",
+            tmp_dir.path().to_str().unwrap()
+        );
+        ctx.sql(&sql).await?.collect().await?;
+        let provider = ctx.table_provider("t").await?;
+        let table = provider.as_any().downcast_ref::<ListingTable>().unwrap();
+        // the files are not listed when the table is registered
+        assert!(table.statistics().is_none());
+
+        ctx.sql("SELECT * FROM t").await?.collect().await?;
+        assert_eq!(table.statistics().unwrap().num_rows, Some(1));
+
+        // inserting invalidates the statistics once the files are written,
+        // but not when it is planned
+        let sql = "INSERT INTO t VALUES (2), (3)";
+        let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+        assert!(table.statistics().is_some());
+        collect(plan, ctx.task_ctx()).await?;
+        assert!(table.statistics().is_none());
+
+        ctx.sql("SELECT * FROM t").await?.collect().await?;
+        assert_eq!(table.statistics().unwrap().num_rows, Some(3));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_into_partitioned() -> Result<()> {
+        let ctx = SessionContext::new();
+        let tmp_dir = TempDir::new()?;
+        let table = insert_table(
+            tmp_dir.path(),
+            Arc::new(ParquetFormat::default()),
+            FileType::PARQUET,
+            vec![Field::new("a", DataType::Int32, true)],
+            vec![
+                ("year".to_string(), DataType::Utf8),
+                ("month".to_string(), DataType::Utf8),
+            ],
+        )?;
+        ctx.register_table("t", Arc::new(table))?;
+
+        let sql =
+            "INSERT INTO t VALUES (1, '2022', '12'), (2, '2023', '1'), (3, '2022', '12')";
+        ctx.sql(sql).await?.collect().await?;
+
+        assert!(tmp_dir.path().join("year=2022").join("month=12").is_dir());
+        assert!(tmp_dir.path().join("year=2023").join("month=1").is_dir());
+
+        let sql = "SELECT a, year, month FROM t WHERE year = '2022'";
+        let batches = ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+---+------+-------+",
+            "| a | year | month |",
+            "+---+------+-------+",
+            "| 1 | 2022 | 12    |",
+            "| 3 | 2022 | 12    |",
+            "+---+------+-------+",
+        ];
+        crate::assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_into_partitioned_escaped() -> Result<()> {
+        let ctx = SessionContext::new();
+        let tmp_dir = TempDir::new()?;
+        let sql = format!(
+            "CREATE EXTERNAL TABLE t (a INT, p VARCHAR) \
+             STORED AS CSV WITH HEADER ROW PARTITIONED BY (p) LOCATION '{}'",
+            tmp_dir.path().to_str().unwrap()
+        );
+        ctx.sql(&sql).await?.collect().await?;
+        // the partition column is declared nullable
+        let schema = ctx.table_provider("t").await?.schema();
+        assert!(schema.field_with_name("p")?.is_nullable());
+
+        let sql = "INSERT INTO t VALUES (1, 'x/y=z%'), (2, NULL), (3, 'x')";
+        ctx.sql(sql).await?.collect().await?;
+
+        assert!(tmp_dir.path().join("p=x%2Fy%3Dz%25").is_dir());
+        assert!(tmp_dir.path().join("p=__HIVE_DEFAULT_PARTITION__").is_dir());
+
+        let batches = ctx.sql("SELECT a, p FROM t").await?.collect().await?;
+        let expected = vec![
+            "+---+--------+",
+            "| a | p      |",
+            "+---+--------+",
+            "| 1 | x/y=z% |",
+            "| 2 |        |",
+            "| 3 | x      |",
+            "+---+--------+",
+        ];
+        crate::assert_batches_sorted_eq!(expected, &batches);
+
+        let sql = "SELECT a FROM t WHERE p IS NULL OR p = 'x/y=z%'";
+        let batches = ctx.sql(sql).await?.collect().await?;
+        let expected = vec!["+---+", "| a |", "+---+", "| 1 |", "| 2 |", "+---+"];
+        crate::assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_into_unsupported_format() -> Result<()> {
+        let ctx = SessionContext::new();
+        let tmp_dir = TempDir::new()?;
+        let table_path = ListingTableUrl::parse(tmp_dir.path().to_str().unwrap())?;
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let options = ListingOptions::new(Arc::new(AvroFormat {}));
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(schema);
+        ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+
+        let err = ctx
+            .sql("INSERT INTO t VALUES (1)")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "Writing is not implemented for AvroFormat");
+        Ok(())
+    }
 }
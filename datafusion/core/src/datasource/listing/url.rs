@@ -87,6 +87,30 @@ impl ListingTableUrl {
         }
     }
 
+    /// Parse a provided string as the location of files to be written
+    ///
+    /// Unlike [`Self::parse`], paths on the local filesystem do not need to
+    /// exist, and are never interpreted as glob expressions. A path ending
+    /// with `/` is a directory.
+    pub(crate) fn parse_output(s: impl AsRef<str>) -> Result<Self> {
+        let s = s.as_ref();
+
+        let is_path = std::path::Path::new(s).is_absolute()
+            || matches!(Url::parse(s), Err(url::ParseError::RelativeUrlWithoutBase));
+        if !is_path {
+            return Self::parse(s);
+        }
+
+        let path = std::env::current_dir()?.join(s);
+        let url = if s.ends_with('/') || s.ends_with(std::path::MAIN_SEPARATOR) {
+            Url::from_directory_path(path)
+        } else {
+            Url::from_file_path(path)
+        }
+        .map_err(|_| DataFusionError::Internal(format!("Can not open path: {s}")))?;
+        Ok(Self::new(url, None))
+    }
+
     /// Creates a new [`ListingTableUrl`] interpreting `s` as a filesystem path
     fn parse_path(s: &str) -> Result<Self> {
         let (prefix, glob) = match split_glob_expression(s) {
@@ -124,6 +148,17 @@ impl ListingTableUrl {
         self.url.scheme()
     }
 
+    /// Returns the path prefix of this [`ListingTableUrl`] in its object store
+    pub(crate) fn prefix(&self) -> &Path {
+        &self.prefix
+    }
+
+    /// Returns true if this [`ListingTableUrl`] points to a directory
+    /// rather than a single file
+    pub(crate) fn is_collection(&self) -> bool {
+        self.url.as_str().ends_with('/')
+    }
+
     /// Strips the prefix of this [`ListingTableUrl`] from the provided path, returning
     /// an iterator of the remaining path segments
     pub(crate) fn strip_prefix<'a, 'b: 'a>(
@@ -146,8 +181,7 @@ impl ListingTableUrl {
         file_extension: &'a str,
     ) -> BoxStream<'a, Result<ObjectMeta>> {
         // If the prefix is a file, use a head request, otherwise list
-        let is_dir = self.url.as_str().ends_with('/');
-        let list = match is_dir {
+        let list = match self.is_collection() {
             true => futures::stream::once(store.list(Some(&self.prefix)))
                 .try_flatten()
                 .boxed(),
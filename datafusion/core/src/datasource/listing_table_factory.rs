@@ -83,6 +83,19 @@ impl TableProviderFactory for ListingTableFactory {
             ),
         };
 
+        // partition columns declared nullable may contain NULL values, which
+        // are written to the hive default partition
+        let nullable_partition_cols: Vec<_> = cmd
+            .table_partition_cols
+            .iter()
+            .filter(|col| {
+                cmd.schema
+                    .field_with_unqualified_name(col)
+                    .map_or(false, |f| f.is_nullable())
+            })
+            .cloned()
+            .collect();
+
         let (provided_schema, table_partition_cols) = if cmd.schema.fields().is_empty() {
             (
                 None,
@@ -159,8 +172,9 @@ impl TableProviderFactory for ListingTableFactory {
         let config = ListingTableConfig::new(table_path)
             .with_listing_options(options)
             .with_schema(resolved_schema);
-        let table =
-            ListingTable::try_new(config)?.with_definition(cmd.definition.clone());
+        let table = ListingTable::try_new(config)?
+            .with_nullable_partition_cols(&nullable_partition_cols)
+            .with_definition(cmd.definition.clone());
         Ok(Arc::new(table))
     }
 }
@@ -19,35 +19,50 @@
 
 use futures::StreamExt;
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
-use tokio::sync::RwLock;
+use datafusion_common::ToDFSchema;
+use datafusion_optimizer::utils::conjunction;
+use datafusion_physical_expr::{create_physical_expr, PhysicalExpr};
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
 
 use crate::datasource::{TableProvider, TableType};
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::SessionState;
+use crate::execution::transaction::{TransactionId, TransactionalTable};
 use crate::logical_expr::Expr;
 use crate::physical_plan::common;
 use crate::physical_plan::common::AbortOnDropSingle;
 use crate::physical_plan::memory::MemoryExec;
-use crate::physical_plan::memory::MemoryWriteExec;
-use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::memory::{MemoryDmlExec, MemoryDmlOp, MemoryWriteExec};
 use crate::physical_plan::{repartition::RepartitionExec, Partitioning};
+use crate::physical_plan::{ExecutionPlan, Statistics};
 
 /// Type alias for partition data
 pub type PartitionData = Arc<RwLock<Vec<RecordBatch>>>;
 
+/// Number of committed modifications of an in-memory table, locked while the
+/// table is modified
+pub type TableVersion = Arc<Mutex<u64>>;
+
 /// In-memory data source for presenting a `Vec<RecordBatch>` as a
 /// data source that can be queried by DataFusion. This allows data to
 /// be pre-loaded into memory and then repeatedly queried without
 /// incurring additional file I/O overhead.
+///
+/// A [`MemTable`] supports transactions with snapshot isolation: a
+/// transaction works on a copy of the table taken when it first touches the
+/// table, which replaces the table when the transaction commits. Committing
+/// fails if another transaction modified the table in the meantime.
 #[derive(Debug)]
 pub struct MemTable {
     schema: SchemaRef,
     pub(crate) batches: Vec<PartitionData>,
+    transactions: Arc<MemTableTransactions>,
 }
 
 impl MemTable {
@@ -58,12 +73,14 @@ impl MemTable {
             .flatten()
             .all(|batches| schema.contains(&batches.schema()))
         {
+            let batches = partitions
+                .into_iter()
+                .map(|e| Arc::new(RwLock::new(e)))
+                .collect::<Vec<_>>();
             Ok(Self {
                 schema,
-                batches: partitions
-                    .into_iter()
-                    .map(|e| Arc::new(RwLock::new(e)))
-                    .collect::<Vec<_>>(),
+                transactions: Arc::new(MemTableTransactions::new(batches.clone())),
+                batches,
             })
         } else {
             Err(DataFusionError::Plan(
@@ -129,6 +146,47 @@ impl MemTable {
         }
         MemTable::try_new(schema.clone(), data)
     }
+
+    /// Return the partitions visible in `state`: the snapshot of the
+    /// transaction in progress, if any, or the committed partitions
+    async fn partitions(&self, state: &SessionState, modify: bool) -> Vec<PartitionData> {
+        match state.transaction() {
+            Some(txn) => {
+                txn.enlist(self.transactions.clone());
+                self.transactions.snapshot(txn.id(), modify).await
+            }
+            None => self.batches.clone(),
+        }
+    }
+
+    /// Return the version to bump when modifying the partitions visible in
+    /// `state`, which only the committed partitions have
+    fn committed_version(&self, state: &SessionState) -> Option<TableVersion> {
+        match state.transaction() {
+            Some(_) => None,
+            None => Some(self.transactions.version.clone()),
+        }
+    }
+
+    /// Create a physical expression selecting the rows that match all
+    /// `filters`, or `None` if there are no filters
+    fn create_physical_filter(
+        &self,
+        state: &SessionState,
+        filters: Vec<Expr>,
+    ) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+        conjunction(filters)
+            .map(|expr| {
+                let df_schema = self.schema.as_ref().clone().to_dfschema()?;
+                create_physical_expr(
+                    &expr,
+                    &df_schema,
+                    &self.schema,
+                    state.execution_props(),
+                )
+            })
+            .transpose()
+    }
 }
 
 #[async_trait]
@@ -145,15 +203,34 @@ impl TableProvider for MemTable {
         TableType::Base
     }
 
+    /// Computes the statistics of the current rows of this [`MemTable`].
+    /// Returns `None` while the table is being modified.
+    fn statistics(&self) -> Option<Statistics> {
+        let partitions = self
+            .batches
+            .iter()
+            .map(|partition| partition.try_read().ok().map(|batches| batches.clone()))
+            .collect::<Option<Vec<_>>>()?;
+        Some(common::compute_record_batch_statistics(
+            &partitions,
+            &self.schema,
+            None,
+        ))
+    }
+
+    fn supports_transactions(&self) -> bool {
+        true
+    }
+
     async fn scan(
         &self,
-        _state: &SessionState,
+        state: &SessionState,
         projection: Option<&Vec<usize>>,
         _filters: &[Expr],
         _limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let mut partitions = vec![];
-        for arc_inner_vec in self.batches.iter() {
+        for arc_inner_vec in self.partitions(state, false).await.iter() {
             let inner_vec = arc_inner_vec.read().await;
             partitions.push(inner_vec.clone())
         }
@@ -177,7 +254,7 @@ impl TableProvider for MemTable {
     /// * A `Result` indicating success or failure.
     async fn insert_into(
         &self,
-        _state: &SessionState,
+        state: &SessionState,
         input: Arc<dyn ExecutionPlan>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         // Create a physical plan from the logical plan.
@@ -203,11 +280,196 @@ impl TableProvider for MemTable {
             input
         };
 
-        Ok(Arc::new(MemoryWriteExec::try_new(
+        let exec = MemoryWriteExec::try_new(
             input,
-            self.batches.clone(),
+            self.partitions(state, true).await,
             self.schema.clone(),
-        )?))
+        )?;
+        Ok(Arc::new(match self.committed_version(state) {
+            Some(version) => exec.with_version(version),
+            None => exec,
+        }))
+    }
+
+    /// Deletes the rows of this [`MemTable`] that match all `filters`.
+    async fn delete_from(
+        &self,
+        state: &SessionState,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let exec = MemoryDmlExec::new(
+            MemoryDmlOp::Delete,
+            self.create_physical_filter(state, filters)?,
+            self.partitions(state, true).await,
+            self.schema.clone(),
+        );
+        Ok(Arc::new(match self.committed_version(state) {
+            Some(version) => exec.with_version(version),
+            None => exec,
+        }))
+    }
+
+    /// Updates the rows of this [`MemTable`] that match all `filters`.
+    /// The values of the `assignments` are cast to the types of their columns.
+    async fn update(
+        &self,
+        state: &SessionState,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let df_schema = self.schema.as_ref().clone().to_dfschema()?;
+        let assignments = assignments
+            .iter()
+            .map(|(name, expr)| {
+                let index = self.schema.index_of(name)?;
+                let expr = create_physical_expr(
+                    expr,
+                    &df_schema,
+                    &self.schema,
+                    state.execution_props(),
+                )?;
+                Ok((index, expr))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let exec = MemoryDmlExec::new(
+            MemoryDmlOp::Update(assignments),
+            self.create_physical_filter(state, filters)?,
+            self.partitions(state, true).await,
+            self.schema.clone(),
+        );
+        Ok(Arc::new(match self.committed_version(state) {
+            Some(version) => exec.with_version(version),
+            None => exec,
+        }))
+    }
+}
+
+/// The copies of a [`MemTable`] made by the transactions it takes part in
+#[derive(Debug)]
+struct MemTableTransactions {
+    /// The committed partitions, shared with the [`MemTable`]
+    committed: Vec<PartitionData>,
+    /// Number of committed modifications of the table. Held while taking
+    /// snapshots, modifying the committed partitions, and from preparing
+    /// a transaction until it commits, so all of them see a consistent table.
+    version: TableVersion,
+    snapshots: parking_lot::Mutex<HashMap<TransactionId, Snapshot>>,
+}
+
+#[derive(Debug)]
+struct Snapshot {
+    partitions: Vec<PartitionData>,
+    /// The committed version the snapshot was taken from
+    version: u64,
+    /// Whether the transaction modified the snapshot
+    modified: bool,
+    /// The lock on the committed version, held once the transaction is
+    /// prepared
+    prepared: Option<OwnedMutexGuard<u64>>,
+}
+
+impl MemTableTransactions {
+    fn new(committed: Vec<PartitionData>) -> Self {
+        Self {
+            committed,
+            version: Arc::new(Mutex::new(0)),
+            snapshots: parking_lot::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return the snapshot of `txn`, taking it on first use
+    async fn snapshot(&self, txn: TransactionId, modify: bool) -> Vec<PartitionData> {
+        if let Some(snapshot) = self.snapshots.lock().get_mut(&txn) {
+            snapshot.modified |= modify;
+            return snapshot.partitions.clone();
+        }
+
+        let version = self.version.lock().await;
+        let mut partitions = Vec::with_capacity(self.committed.len());
+        for partition in &self.committed {
+            let batches = partition.read().await.clone();
+            partitions.push(Arc::new(RwLock::new(batches)));
+        }
+        let mut snapshots = self.snapshots.lock();
+        let snapshot = snapshots.entry(txn).or_insert(Snapshot {
+            partitions,
+            version: *version,
+            modified: false,
+            prepared: None,
+        });
+        snapshot.modified |= modify;
+        snapshot.partitions.clone()
+    }
+
+    /// Return the version of the snapshot of `txn` if it modified the table
+    fn modified_version(&self, txn: TransactionId) -> Option<u64> {
+        self.snapshots
+            .lock()
+            .get(&txn)
+            .filter(|snapshot| snapshot.modified)
+            .map(|snapshot| snapshot.version)
+    }
+}
+
+fn serialization_failure() -> DataFusionError {
+    DataFusionError::Execution(
+        "Could not commit transaction due to a concurrent update of the table"
+            .to_string(),
+    )
+}
+
+#[async_trait]
+impl TransactionalTable for MemTableTransactions {
+    async fn prepare(&self, txn: TransactionId) -> Result<()> {
+        let snapshot_version = match self.modified_version(txn) {
+            Some(snapshot_version) => snapshot_version,
+            None => return Ok(()),
+        };
+        // Keep the table from being modified until the transaction commits
+        // or rolls back
+        let version = self.version.clone().lock_owned().await;
+        if snapshot_version != *version {
+            return Err(serialization_failure());
+        }
+        if let Some(snapshot) = self.snapshots.lock().get_mut(&txn) {
+            snapshot.prepared = Some(version);
+        }
+        Ok(())
+    }
+
+    async fn commit(&self, txn: TransactionId) -> Result<()> {
+        let snapshot = self.snapshots.lock().remove(&txn);
+        let (partitions, mut version) = match snapshot {
+            Some(Snapshot {
+                partitions,
+                prepared: Some(version),
+                ..
+            }) => (partitions, version),
+            Some(Snapshot { modified: true, .. }) => {
+                return Err(DataFusionError::Internal(format!(
+                    "Transaction {txn} modified the table but was not prepared"
+                )))
+            }
+            _ => return Ok(()),
+        };
+
+        for (committed, partition) in self.committed.iter().zip(&partitions) {
+            let batches = partition.read().await.clone();
+            *committed.write().await = batches;
+        }
+        *version += 1;
+        Ok(())
+    }
+
+    async fn rollback(&self, txn: TransactionId) -> Result<()> {
+        self.release(txn);
+        Ok(())
+    }
+
+    fn release(&self, txn: TransactionId) {
+        // also releases the lock on the committed version if prepared
+        self.snapshots.lock().remove(&txn);
     }
 }
 
@@ -218,9 +480,11 @@ mod tests {
     use crate::from_slice::FromSlice;
     use crate::physical_plan::collect;
     use crate::prelude::SessionContext;
+    use crate::{assert_batches_eq, assert_batches_sorted_eq};
     use arrow::array::Int32Array;
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow::error::ArrowError;
+    use datafusion_common::assert_contains;
     use datafusion_expr::LogicalPlanBuilder;
     use futures::StreamExt;
     use std::collections::HashMap;
@@ -263,6 +527,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_statistics() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ]));
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_slice([1, 2, 3])),
+                Arc::new(Int32Array::from(vec![None, None, Some(9)])),
+            ],
+        )?;
+
+        let provider = MemTable::try_new(schema, vec![vec![batch.clone()], vec![batch]])?;
+
+        let statistics = provider.statistics().unwrap();
+        assert_eq!(statistics.num_rows, Some(6));
+        assert!(statistics.is_exact);
+        let null_counts: Vec<_> = statistics
+            .column_statistics
+            .unwrap()
+            .iter()
+            .map(|column| column.null_count)
+            .collect();
+        assert_eq!(null_counts, vec![Some(0), Some(4)]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_without_projection() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -582,4 +877,293 @@ mod tests {
         assert_eq!(resulting_data_in_table[0].len(), 2);
         Ok(())
     }
+
+    fn register_dml_table(session_ctx: &SessionContext) -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let batch1 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_slice([1, 2, 3])),
+                Arc::new(Int32Array::from(vec![Some(10), None, Some(30)])),
+            ],
+        )?;
+        let batch2 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_slice([4, 5])),
+                Arc::new(Int32Array::from(vec![Some(40), Some(50)])),
+            ],
+        )?;
+        let table = MemTable::try_new(schema, vec![vec![batch1], vec![batch2]])?;
+        session_ctx.register_table("t", Arc::new(table))?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_from() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        register_dml_table(&session_ctx)?;
+
+        let sql = "DELETE FROM t WHERE a > 1 AND b < 45";
+        let count = session_ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+-------+",
+            "| count |",
+            "+-------+",
+            "| 2     |",
+            "+-------+",
+        ];
+        assert_batches_eq!(expected, &count);
+
+        let rows = session_ctx.sql("SELECT * FROM t").await?.collect().await?;
+        let expected = vec![
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 1 | 10 |",
+            "| 2 |    |",
+            "| 5 | 50 |",
+            "+---+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &rows);
+
+        let sql = "DELETE FROM t";
+        let count = session_ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+-------+",
+            "| count |",
+            "+-------+",
+            "| 3     |",
+            "+-------+",
+        ];
+        assert_batches_eq!(expected, &count);
+
+        let rows = session_ctx.sql("SELECT * FROM t").await?.collect().await?;
+        assert_eq!(rows.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        register_dml_table(&session_ctx)?;
+
+        let sql = "UPDATE t SET b = a * 100, a = b WHERE b IS NOT NULL AND a <> 5";
+        let count = session_ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+-------+",
+            "| count |",
+            "+-------+",
+            "| 3     |",
+            "+-------+",
+        ];
+        assert_batches_eq!(expected, &count);
+
+        let rows = session_ctx.sql("SELECT * FROM t").await?.collect().await?;
+        let expected = vec![
+            "+----+-----+",
+            "| a  | b   |",
+            "+----+-----+",
+            "| 10 | 100 |",
+            "| 2  |     |",
+            "| 30 | 300 |",
+            "| 40 | 400 |",
+            "| 5  | 50  |",
+            "+----+-----+",
+        ];
+        assert_batches_sorted_eq!(expected, &rows);
+
+        // the update is not applied if it fails for any row
+        let sql = "UPDATE t SET a = b";
+        let err = session_ctx.sql(sql).await?.collect().await.unwrap_err();
+        assert_contains!(err.to_string(), "non-nullable");
+
+        let rows = session_ctx.sql("SELECT * FROM t").await?.collect().await?;
+        assert_batches_sorted_eq!(expected, &rows);
+        Ok(())
+    }
+
+    async fn count_rows(session_ctx: &SessionContext) -> Result<usize> {
+        let rows = session_ctx.sql("SELECT * FROM t").await?.collect().await?;
+        Ok(rows.iter().map(|b| b.num_rows()).sum())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rollback() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        register_dml_table(&session_ctx)?;
+
+        session_ctx.sql("BEGIN").await?.collect().await?;
+        let sql = "UPDATE t SET b = 0 WHERE a = 2";
+        session_ctx.sql(sql).await?.collect().await?;
+        session_ctx
+            .sql("DELETE FROM t WHERE a = 1")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(count_rows(&session_ctx).await?, 4);
+        session_ctx.sql("ROLLBACK").await?.collect().await?;
+
+        assert_eq!(count_rows(&session_ctx).await?, 5);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_snapshot_isolation() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let table = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+        let session1 = SessionContext::new();
+        session1.register_table("t", table.clone())?;
+        let session2 = SessionContext::new();
+        session2.register_table("t", table)?;
+
+        session1.sql("BEGIN").await?.collect().await?;
+        session2.sql("BEGIN").await?.collect().await?;
+        // take the snapshot of the second transaction
+        assert_eq!(count_rows(&session2).await?, 0);
+
+        let sql = "INSERT INTO t VALUES (1, 10)";
+        session1.sql(sql).await?.collect().await?;
+        assert_eq!(count_rows(&session1).await?, 1);
+        assert_eq!(count_rows(&session2).await?, 0);
+        session1.sql("COMMIT").await?.collect().await?;
+
+        // changes committed after the snapshot was taken are not visible
+        assert_eq!(count_rows(&session2).await?, 0);
+        session2.sql("COMMIT").await?.collect().await?;
+        assert_eq!(count_rows(&session2).await?, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_conflict() -> Result<()> {
+        let session1 = SessionContext::new();
+        register_dml_table(&session1)?;
+        let session2 = SessionContext::new();
+        session2.register_table("t", session1.table_provider("t").await?)?;
+
+        session1.sql("BEGIN").await?.collect().await?;
+        session2.sql("BEGIN").await?.collect().await?;
+        let sql = "DELETE FROM t WHERE a = 1";
+        session1.sql(sql).await?.collect().await?;
+        let sql = "DELETE FROM t WHERE a = 2";
+        session2.sql(sql).await?.collect().await?;
+
+        session1.sql("COMMIT").await?.collect().await?;
+        let err = session2.sql("COMMIT").await.unwrap_err();
+        assert_contains!(err.to_string(), "concurrent update");
+
+        // the transaction of the second session was rolled back
+        assert_eq!(count_rows(&session2).await?, 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_conflict_with_non_transactional_write() -> Result<()> {
+        let session1 = SessionContext::new();
+        register_dml_table(&session1)?;
+        let session2 = SessionContext::new();
+        session2.register_table("t", session1.table_provider("t").await?)?;
+
+        session1.sql("BEGIN").await?.collect().await?;
+        let sql = "DELETE FROM t WHERE a = 1";
+        session1.sql(sql).await?.collect().await?;
+        let sql = "DELETE FROM t WHERE a = 2";
+        session2.sql(sql).await?.collect().await?;
+
+        let err = session1.sql("COMMIT").await.unwrap_err();
+        assert_contains!(err.to_string(), "concurrent update");
+
+        // the row deleted outside of the transaction stays deleted, and the
+        // row deleted in the transaction is restored
+        assert_eq!(count_rows(&session1).await?, 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_commit_is_atomic() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let t = Arc::new(MemTable::try_new(schema.clone(), vec![vec![]])?);
+        let u = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+        let session1 = SessionContext::new();
+        session1.register_table("t", t.clone())?;
+        session1.register_table("u", u.clone())?;
+        let session2 = SessionContext::new();
+        session2.register_table("u", u.clone())?;
+
+        session1.sql("BEGIN").await?.collect().await?;
+        let sql = "INSERT INTO t VALUES (1, 10)";
+        session1.sql(sql).await?.collect().await?;
+        let sql = "INSERT INTO u VALUES (1, 10)";
+        session1.sql(sql).await?.collect().await?;
+        let sql = "INSERT INTO u VALUES (2, 20)";
+        session2.sql(sql).await?.collect().await?;
+
+        let err = session1.sql("COMMIT AND CHAIN").await.unwrap_err();
+        assert_contains!(err.to_string(), "concurrent update");
+
+        // neither table was committed, no new transaction was chained, and
+        // the snapshots of the transaction were discarded
+        assert_eq!(count_rows(&session1).await?, 0);
+        let err = session1.sql("COMMIT").await.unwrap_err();
+        assert_contains!(err.to_string(), "no transaction in progress");
+        assert!(t.transactions.snapshots.lock().is_empty());
+        assert!(u.transactions.snapshots.lock().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dropped_transaction_releases_snapshot() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let t = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+        let session1 = SessionContext::new();
+        session1.register_table("t", t.clone())?;
+        let session2 = SessionContext::new();
+        session2.register_table("t", t.clone())?;
+
+        session1.sql("BEGIN").await?.collect().await?;
+        let sql = "INSERT INTO t VALUES (1, 10)";
+        session1.sql(sql).await?.collect().await?;
+        assert_eq!(t.transactions.snapshots.lock().len(), 1);
+
+        // dropping the session drops its transaction without committing it
+        drop(session1);
+        assert!(t.transactions.snapshots.lock().is_empty());
+        let sql = "INSERT INTO t VALUES (2, 20)";
+        session2.sql(sql).await?.collect().await?;
+        assert_eq!(count_rows(&session2).await?, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_only_transaction() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        register_dml_table(&session_ctx)?;
+
+        session_ctx
+            .sql("START TRANSACTION READ ONLY")
+            .await?
+            .collect()
+            .await?;
+        let sql = "DELETE FROM t WHERE a = 1";
+        let err = session_ctx.sql(sql).await?.collect().await.unwrap_err();
+        assert_contains!(err.to_string(), "read-only transaction");
+        session_ctx.sql("COMMIT").await?.collect().await?;
+
+        let err = session_ctx.sql("COMMIT").await.unwrap_err();
+        assert_contains!(err.to_string(), "no transaction in progress");
+        Ok(())
+    }
 }
@@ -18,6 +18,7 @@
 //! DataFusion data sources
 
 #![allow(clippy::module_inception)]
+pub mod cte_worktable;
 pub mod datasource;
 pub mod default_table_source;
 pub mod empty;
@@ -26,6 +27,7 @@ pub mod listing;
 pub mod listing_table_factory;
 pub mod memory;
 pub mod streaming;
+pub mod table_function;
 pub mod view;
 
 // backwards compatibility
@@ -0,0 +1,613 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Table functions, called in the FROM clause of a query like
+//! `SELECT * FROM generate_series(1, 10)`, and the built-in ones
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::array::Int64Array;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion_common::parsers::CompressionTypeVariant;
+use datafusion_common::{
+    DFSchema, DataFusionError, OwnedTableReference, Result, ScalarValue,
+};
+use datafusion_expr::{CreateExternalTable, Expr};
+use datafusion_optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext};
+use datafusion_physical_expr::execution_props::ExecutionProps;
+use futures::TryStreamExt;
+
+use crate::datasource::listing::ListingTableUrl;
+use crate::datasource::streaming::{PartitionStream, StreamingTable};
+use crate::datasource::TableProvider;
+use crate::execution::context::{SessionState, TaskContext};
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::SendableRecordBatchStream;
+
+/// A function returning a table, that can be called in the FROM clause of a
+/// query.
+///
+/// The arguments of a call are evaluated to constants when the query is
+/// planned, and the function creates a [`TableProvider`] from them.
+#[async_trait]
+pub trait TableFunction: Send + Sync {
+    /// Create the table returned by calling this function with `args`
+    async fn call(
+        &self,
+        state: &SessionState,
+        args: &TableFunctionArgs,
+    ) -> Result<Arc<dyn TableProvider>>;
+}
+
+/// The constant arguments of a call of a [`TableFunction`], like
+/// `read_csv('data.csv', has_header => false)`
+#[derive(Debug, Clone, Default)]
+pub struct TableFunctionArgs {
+    positional: Vec<ScalarValue>,
+    named: Vec<(String, ScalarValue)>,
+}
+
+impl TableFunctionArgs {
+    /// Create new arguments from the values of the positional and named ones
+    pub fn new(positional: Vec<ScalarValue>, named: Vec<(String, ScalarValue)>) -> Self {
+        Self { positional, named }
+    }
+
+    /// Evaluate the arguments `args` of a call of the table function `name`.
+    /// Named arguments are given as an [`Expr::Alias`] of their value.
+    pub fn try_from_exprs(
+        name: &str,
+        args: &[Expr],
+        execution_props: &ExecutionProps,
+    ) -> Result<Self> {
+        let context = SimplifyContext::new(execution_props)
+            .with_schema(Arc::new(DFSchema::empty()));
+        let simplifier = ExprSimplifier::new(context);
+        let evaluate = |expr: &Expr| {
+            let not_constant = || {
+                DataFusionError::Plan(format!(
+                    "Arguments of table function '{name}' must be constants, got {expr}"
+                ))
+            };
+            if !expr.to_columns()?.is_empty() {
+                return Err(not_constant());
+            }
+            match simplifier.simplify(expr.clone())? {
+                Expr::Literal(value) => Ok(value),
+                _ => Err(not_constant()),
+            }
+        };
+
+        let mut positional = vec![];
+        let mut named = vec![];
+        for arg in args {
+            match arg {
+                Expr::Alias(expr, arg_name) => {
+                    named.push((arg_name.clone(), evaluate(expr)?))
+                }
+                expr => positional.push(evaluate(expr)?),
+            }
+        }
+        Ok(Self { positional, named })
+    }
+
+    /// Return the values of the positional arguments
+    pub fn positional(&self) -> &[ScalarValue] {
+        &self.positional
+    }
+
+    /// Return the names and values of the named arguments
+    pub fn named_args(&self) -> &[(String, ScalarValue)] {
+        &self.named
+    }
+
+    /// Return the value of the named argument `name`, if given
+    pub fn named(&self, name: &str) -> Option<&ScalarValue> {
+        self.named
+            .iter()
+            .find(|(arg_name, _)| arg_name == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Return an error if there is a named argument not in `allowed`
+    pub fn check_named(&self, function: &str, allowed: &[&str]) -> Result<()> {
+        match self
+            .named
+            .iter()
+            .find(|(name, _)| !allowed.contains(&name.as_str()))
+        {
+            Some((name, _)) => Err(DataFusionError::Plan(format!(
+                "Unknown argument '{name}' of table function '{function}'"
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Return the built-in table functions, by name
+pub fn builtin_table_functions() -> HashMap<String, Arc<dyn TableFunction>> {
+    let mut functions: HashMap<String, Arc<dyn TableFunction>> = HashMap::new();
+    functions.insert(
+        "generate_series".to_string(),
+        Arc::new(GenerateSeries::new("generate_series", true)),
+    );
+    functions.insert(
+        "range".to_string(),
+        Arc::new(GenerateSeries::new("range", false)),
+    );
+    for (name, file_type) in [
+        ("read_parquet", "PARQUET"),
+        ("read_csv", "CSV"),
+        ("read_json", "JSON"),
+        ("read_avro", "AVRO"),
+    ] {
+        functions.insert(name.to_string(), Arc::new(ReadFiles::new(name, file_type)));
+    }
+    functions
+}
+
+/// The `generate_series(start, stop[, step])` and `range(start, stop[, step])`
+/// table functions, returning a single `Int64` column named after the
+/// function with the values from `start` to `stop` by `step`.
+///
+/// `generate_series` includes `stop`, like in PostgreSQL, while `range`
+/// excludes it.
+#[derive(Debug)]
+pub struct GenerateSeries {
+    name: &'static str,
+    inclusive: bool,
+}
+
+impl GenerateSeries {
+    fn new(name: &'static str, inclusive: bool) -> Self {
+        Self { name, inclusive }
+    }
+
+    fn int64_arg(&self, value: &ScalarValue) -> Result<i64> {
+        let int64 = ScalarValue::try_from_array(
+            &arrow::compute::cast(&value.to_array(), &DataType::Int64)?,
+            0,
+        )?;
+        match int64 {
+            ScalarValue::Int64(Some(v)) => Ok(v),
+            _ => Err(DataFusionError::Plan(format!(
+                "Arguments of table function '{}' must be non-null integers, got {value}",
+                self.name
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl TableFunction for GenerateSeries {
+    async fn call(
+        &self,
+        _state: &SessionState,
+        args: &TableFunctionArgs,
+    ) -> Result<Arc<dyn TableProvider>> {
+        args.check_named(self.name, &[])?;
+        let (start, stop, step) = match args.positional() {
+            [start, stop] => (self.int64_arg(start)?, self.int64_arg(stop)?, 1),
+            [start, stop, step] => (
+                self.int64_arg(start)?,
+                self.int64_arg(stop)?,
+                self.int64_arg(step)?,
+            ),
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "Table function '{}' expects 2 or 3 arguments",
+                    self.name
+                )))
+            }
+        };
+        if step == 0 {
+            return Err(DataFusionError::Plan(format!(
+                "The step of table function '{}' cannot be zero",
+                self.name
+            )));
+        }
+
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            self.name,
+            DataType::Int64,
+            false,
+        )]));
+        let partition = Arc::new(SeriesPartition {
+            schema: schema.clone(),
+            start,
+            stop,
+            step,
+            inclusive: self.inclusive,
+        });
+        Ok(Arc::new(StreamingTable::try_new(schema, vec![partition])?))
+    }
+}
+
+/// Generates the values of a [`GenerateSeries`] call in batches
+struct SeriesPartition {
+    schema: SchemaRef,
+    start: i64,
+    stop: i64,
+    step: i64,
+    inclusive: bool,
+}
+
+impl SeriesPartition {
+    fn contains(&self, value: i64) -> bool {
+        match (self.step > 0, self.inclusive) {
+            (true, true) => value <= self.stop,
+            (true, false) => value < self.stop,
+            (false, true) => value >= self.stop,
+            (false, false) => value > self.stop,
+        }
+    }
+}
+
+impl PartitionStream for SeriesPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let batch_size = ctx.session_config().batch_size();
+        let partition = SeriesPartition {
+            schema: self.schema.clone(),
+            ..*self
+        };
+        let mut next = Some(self.start);
+        let batches = std::iter::from_fn(move || {
+            let mut values = Vec::with_capacity(batch_size);
+            while values.len() < batch_size {
+                match next {
+                    Some(value) if partition.contains(value) => {
+                        values.push(value);
+                        next = value.checked_add(partition.step);
+                    }
+                    _ => break,
+                }
+            }
+            (!values.is_empty()).then(|| {
+                RecordBatch::try_new(
+                    partition.schema.clone(),
+                    vec![Arc::new(Int64Array::from(values))],
+                )
+                .map_err(DataFusionError::from)
+            })
+        });
+        Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            futures::stream::iter(batches),
+        ))
+    }
+}
+
+/// The `read_parquet`, `read_csv`, `read_json` and `read_avro` table
+/// functions, reading the files at the location given as their only
+/// positional argument, like a table created with `CREATE EXTERNAL TABLE`.
+///
+/// They accept the named arguments:
+/// * `hive`: when true, the directories of the files named `key=value` are
+///   read as partition columns
+/// * `compression`: the compression of the files, except for Parquet and Avro
+/// * `has_header` and `delimiter` for CSV files. As with `CREATE EXTERNAL
+///   TABLE`, CSV files have no header row unless `has_header` is true
+pub struct ReadFiles {
+    name: &'static str,
+    file_type: &'static str,
+}
+
+impl ReadFiles {
+    fn new(name: &'static str, file_type: &'static str) -> Self {
+        Self { name, file_type }
+    }
+
+    fn allowed_args(&self) -> &'static [&'static str] {
+        match self.file_type {
+            "CSV" => &["hive", "compression", "has_header", "delimiter"],
+            "JSON" => &["hive", "compression"],
+            _ => &["hive"],
+        }
+    }
+
+    fn bool_arg(
+        &self,
+        args: &TableFunctionArgs,
+        name: &str,
+        default: bool,
+    ) -> Result<bool> {
+        match args.named(name) {
+            None => Ok(default),
+            Some(ScalarValue::Boolean(Some(value))) => Ok(*value),
+            Some(value) => Err(self.invalid_arg(name, value)),
+        }
+    }
+
+    fn string_arg<'a>(
+        &self,
+        args: &'a TableFunctionArgs,
+        name: &str,
+    ) -> Result<Option<&'a str>> {
+        match args.named(name) {
+            None => Ok(None),
+            Some(ScalarValue::Utf8(Some(value))) => Ok(Some(value)),
+            Some(value) => Err(self.invalid_arg(name, value)),
+        }
+    }
+
+    fn invalid_arg(&self, name: &str, value: &ScalarValue) -> DataFusionError {
+        DataFusionError::Plan(format!(
+            "Invalid value {value} for argument '{name}' of table function '{}'",
+            self.name
+        ))
+    }
+}
+
+#[async_trait]
+impl TableFunction for ReadFiles {
+    async fn call(
+        &self,
+        state: &SessionState,
+        args: &TableFunctionArgs,
+    ) -> Result<Arc<dyn TableProvider>> {
+        args.check_named(self.name, self.allowed_args())?;
+        let location = match args.positional() {
+            [ScalarValue::Utf8(Some(location))] => location.clone(),
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "Table function '{}' expects the location of the files as its only positional argument",
+                    self.name
+                )))
+            }
+        };
+
+        let delimiter = match self.string_arg(args, "delimiter")? {
+            None => ',',
+            Some(delimiter) if delimiter.len() == 1 => delimiter.as_bytes()[0] as char,
+            Some(delimiter) => {
+                return Err(self.invalid_arg("delimiter", &ScalarValue::from(delimiter)))
+            }
+        };
+        let file_compression_type = match self.string_arg(args, "compression")? {
+            None => CompressionTypeVariant::UNCOMPRESSED,
+            Some(compression) => CompressionTypeVariant::from_str(compression)
+                .map_err(|e| DataFusionError::Plan(e.to_string()))?,
+        };
+        let table_partition_cols = if self.bool_arg(args, "hive", false)? {
+            hive_partition_columns(state, &location).await?
+        } else {
+            vec![]
+        };
+
+        let cmd = CreateExternalTable {
+            schema: Arc::new(DFSchema::empty()),
+            name: OwnedTableReference::bare(self.name),
+            location,
+            file_type: self.file_type.to_string(),
+            has_header: self.bool_arg(args, "has_header", false)?,
+            delimiter,
+            table_partition_cols,
+            if_not_exists: false,
+            definition: None,
+            file_compression_type,
+            order_exprs: vec![],
+            options: HashMap::new(),
+        };
+        let factory = state.table_factories().get(self.file_type).ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "Unable to find factory for {}",
+                self.file_type
+            ))
+        })?;
+        factory.create(state, &cmd).await
+    }
+}
+
+/// Return the names of the partition columns of the files at `location`,
+/// from the `key=value` directories of its first file
+async fn hive_partition_columns(
+    state: &SessionState,
+    location: &str,
+) -> Result<Vec<String>> {
+    let table_path = ListingTableUrl::parse(location)?;
+    let store = state.runtime_env().object_store(&table_path)?;
+    let first_file = table_path
+        .list_all_files(store.as_ref(), "")
+        .try_next()
+        .await?;
+    let Some(first_file) = first_file else {
+        return Ok(vec![]);
+    };
+
+    let segments: Vec<_> = match table_path.strip_prefix(&first_file.location) {
+        Some(segments) => segments.collect(),
+        None => return Ok(vec![]),
+    };
+    let directories = &segments[..segments.len().saturating_sub(1)];
+    Ok(directories
+        .iter()
+        .filter_map(|segment| segment.split_once('=').map(|(key, _)| key.to_string()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::prelude::SessionContext;
+
+    #[tokio::test]
+    async fn generate_series_and_range() -> Result<()> {
+        let ctx = SessionContext::new();
+
+        let sql = "SELECT * FROM generate_series(1, 7, 3)";
+        let results = ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+-----------------+",
+            "| generate_series |",
+            "+-----------------+",
+            "| 1               |",
+            "| 4               |",
+            "| 7               |",
+            "+-----------------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        let sql = "SELECT r.range AS value FROM range(3, -2 + 1, -1) AS r";
+        let results = ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+-------+",
+            "| value |",
+            "+-------+",
+            "| 3     |",
+            "| 2     |",
+            "| 1     |",
+            "| 0     |",
+            "+-------+",
+        ];
+        assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn generate_series_across_batches() -> Result<()> {
+        let ctx = SessionContext::with_config(
+            crate::prelude::SessionConfig::new().with_batch_size(3),
+        );
+        let sql = "SELECT count(*), sum(generate_series) FROM generate_series(1, 10)";
+        let results = ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+-----------------+--------------------------------------+",
+            "| COUNT(UInt8(1)) | SUM(generate_series.generate_series) |",
+            "+-----------------+--------------------------------------+",
+            "| 10              | 55                                   |",
+            "+-----------------+--------------------------------------+",
+        ];
+        assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_csv() -> Result<()> {
+        let ctx = SessionContext::new();
+        let sql = "SELECT * FROM read_csv('tests/data/example.csv', has_header => true)";
+        let results = ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+---+---+---+",
+            "| a | b | c |",
+            "+---+---+---+",
+            "| 1 | 2 | 3 |",
+            "+---+---+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+
+    /// Returns a table with a row for each of its arguments
+    struct ArgsTable;
+
+    #[async_trait]
+    impl TableFunction for ArgsTable {
+        async fn call(
+            &self,
+            _state: &SessionState,
+            args: &TableFunctionArgs,
+        ) -> Result<Arc<dyn TableProvider>> {
+            let names = args.positional().iter().map(|_| None).chain(
+                args.named_args()
+                    .iter()
+                    .map(|(name, _)| Some(name.as_str())),
+            );
+            let values = args
+                .positional()
+                .iter()
+                .chain(args.named_args().iter().map(|(_, value)| value))
+                .map(|value| value.to_string());
+            let batch = RecordBatch::try_from_iter(vec![
+                (
+                    "name",
+                    Arc::new(arrow::array::StringArray::from_iter(names)) as _,
+                ),
+                (
+                    "value",
+                    Arc::new(arrow::array::StringArray::from_iter_values(values)) as _,
+                ),
+            ])?;
+            let table =
+                crate::datasource::MemTable::try_new(batch.schema(), vec![vec![batch]])?;
+            Ok(Arc::new(table))
+        }
+    }
+
+    #[tokio::test]
+    async fn register_table_function() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.register_table_function("args", Arc::new(ArgsTable));
+
+        let sql = "SELECT * FROM ARGS(1 + 2, 'a', Flag => 2 > 1)";
+        let results = ctx.sql(sql).await?.collect().await?;
+        let expected = vec![
+            "+------+-------+",
+            "| name | value |",
+            "+------+-------+",
+            "|      | 3     |",
+            "|      | a     |",
+            "| flag | true  |",
+            "+------+-------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        let sql = "SELECT * FROM args(x)";
+        let err = ctx.sql(sql).await.unwrap_err();
+        datafusion_common::assert_contains!(
+            err.to_string(),
+            "Arguments of table function 'args' must be constants"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_table_function_calls() -> Result<()> {
+        let ctx = SessionContext::new();
+        for (sql, error) in [
+            (
+                "SELECT * FROM generate_series(1)",
+                "expects 2 or 3 arguments",
+            ),
+            ("SELECT * FROM generate_series(1, 2, 0)", "cannot be zero"),
+            (
+                "SELECT * FROM generate_series(1, 2, step => 1)",
+                "Unknown argument 'step'",
+            ),
+            (
+                "SELECT * FROM read_csv('tests/data/example.csv', hive => 'yes')",
+                "Invalid value yes for argument 'hive'",
+            ),
+            (
+                "SELECT * FROM no_such_function(1)",
+                "table function 'no_such_function' not found",
+            ),
+        ] {
+            let err = ctx.sql(sql).await.unwrap_err();
+            datafusion_common::assert_contains!(err.to_string(), error);
+        }
+        Ok(())
+    }
+}
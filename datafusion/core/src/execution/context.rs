@@ -33,11 +33,11 @@ use crate::{
 };
 use datafusion_expr::{
     logical_plan::{DdlStatement, Statement},
-    DescribeTable, StringifiedPlan,
+    DescribeTable, Expr, StringifiedPlan,
 };
 pub use datafusion_physical_expr::execution_props::ExecutionProps;
 use datafusion_physical_expr::var_provider::is_system_variables;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::collections::hash_map::Entry;
 use std::string::String;
 use std::sync::Arc;
@@ -59,14 +59,17 @@ use crate::catalog::{
 };
 use crate::dataframe::DataFrame;
 use crate::datasource::{
+    cte_worktable::CteWorkTable,
     listing::{ListingTableConfig, ListingTableUrl},
-    provider_as_source, TableProvider,
+    provider_as_source,
+    table_function::{builtin_table_functions, TableFunction, TableFunctionArgs},
+    TableProvider,
 };
 use crate::error::{DataFusionError, Result};
 use crate::logical_expr::{
     CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateMemoryTable,
     CreateView, DropCatalogSchema, DropTable, DropView, Explain, LogicalPlan,
-    LogicalPlanBuilder, SetVariable, TableSource, TableType, UNNAMED_TABLE,
+    LogicalPlanBuilder, SetVariable, TableSource, TableType, WindowUDF, UNNAMED_TABLE,
 };
 use crate::optimizer::OptimizerRule;
 use datafusion_sql::{planner::ParserOptions, ResolvedTableReference, TableReference};
@@ -75,6 +78,9 @@ use crate::physical_optimizer::coalesce_batches::CoalesceBatches;
 use crate::physical_optimizer::repartition::Repartition;
 
 use crate::config::ConfigOptions;
+use crate::execution::transaction::{
+    DefaultTransactionManager, Transaction, TransactionManager,
+};
 use crate::execution::{runtime_env::RuntimeEnv, FunctionRegistry};
 use crate::physical_optimizer::dist_enforcement::EnforceDistribution;
 use crate::physical_plan::file_format::{plan_to_csv, plan_to_json, plan_to_parquet};
@@ -101,6 +107,7 @@ use url::Url;
 
 use crate::catalog::information_schema::{InformationSchemaProvider, INFORMATION_SCHEMA};
 use crate::catalog::listing_schema::ListingSchemaProvider;
+use crate::catalog::persistent_catalog::PersistentCatalogProvider;
 use crate::datasource::object_store::ObjectStoreUrl;
 use crate::physical_optimizer::global_sort_selection::GlobalSortSelection;
 use crate::physical_optimizer::pipeline_checker::PipelineChecker;
@@ -240,13 +247,18 @@ impl SessionContext {
         Self::with_config(SessionConfig::new())
     }
 
-    /// Finds any [`ListingSchemaProvider`]s and instructs them to reload tables from "disk"
+    /// Finds any [`ListingSchemaProvider`]s and instructs them to reload tables from "disk",
+    /// and restores the tables of any [`PersistentCatalogProvider`]s
     pub async fn refresh_catalogs(&self) -> Result<()> {
         let cat_names = self.catalog_names().clone();
         for cat_name in cat_names.iter() {
             let cat = self.catalog(cat_name.as_str()).ok_or_else(|| {
                 DataFusionError::Internal("Catalog not found!".to_string())
             })?;
+            let persistent = cat.as_any().downcast_ref::<PersistentCatalogProvider>();
+            if let Some(persistent) = persistent {
+                persistent.refresh(&self.state()).await?;
+            }
             for schema_name in cat.schema_names() {
                 let schema = cat.schema(schema_name.as_str()).ok_or_else(|| {
                     DataFusionError::Internal("Schema not found!".to_string())
@@ -322,6 +334,12 @@ impl SessionContext {
         self.state.read().runtime_env.clone()
     }
 
+    /// Return the [`TransactionManager`] tracking the transaction in progress
+    /// in this `SessionContext`
+    pub fn transaction_manager(&self) -> Arc<dyn TransactionManager> {
+        self.state.read().transaction_manager.clone()
+    }
+
     /// Returns an id that uniquely identifies this `SessionContext`.
     pub fn session_id(&self) -> String {
         self.session_id.clone()
@@ -385,7 +403,14 @@ impl SessionContext {
                 DdlStatement::DropView(cmd) => self.drop_view(cmd).await,
                 DdlStatement::DropCatalogSchema(cmd) => self.drop_schema(cmd).await,
             },
-            // TODO what about the other statements (like TransactionStart and TransactionEnd)
+            LogicalPlan::Statement(Statement::TransactionStart(stmt)) => {
+                self.transaction_manager().begin(&stmt)?;
+                self.return_empty_dataframe()
+            }
+            LogicalPlan::Statement(Statement::TransactionEnd(stmt)) => {
+                self.transaction_manager().end(&stmt).await?;
+                self.return_empty_dataframe()
+            }
             LogicalPlan::Statement(Statement::SetVariable(stmt)) => {
                 self.set_variable(stmt).await
             }
@@ -794,6 +819,35 @@ impl SessionContext {
             .insert(f.name.clone(), Arc::new(f));
     }
 
+    /// Registers a window UDF within this context.
+    ///
+    /// Note in SQL queries, window function names are looked up using
+    /// lowercase unless the query uses quotes. For example,
+    ///
+    /// `SELECT MY_UDWF(x)...` will look for a window function named `"my_udwf"`
+    /// `SELECT "my_UDWF"(x)` will look for a window function named `"my_UDWF"`
+    pub fn register_udwf(&self, f: WindowUDF) {
+        self.state
+            .write()
+            .window_functions
+            .insert(f.name.clone(), Arc::new(f));
+    }
+
+    /// Registers a table function within this context, callable in the
+    /// FROM clause of a query.
+    ///
+    /// Note in SQL queries, table function names are looked up using
+    /// lowercase unless the query uses quotes. For example,
+    ///
+    /// `SELECT * FROM MY_FUNC(1)` will look for a function named `"my_func"`
+    /// `SELECT * FROM "my_FUNC"(1)` will look for a function named `"my_FUNC"`
+    pub fn register_table_function(&self, name: &str, function: Arc<dyn TableFunction>) {
+        self.state
+            .write()
+            .table_functions
+            .insert(name.to_string(), function);
+    }
+
     /// Creates a [`DataFrame`] for reading a data source.
     ///
     /// For more control such as reading multiple files, you can use
@@ -1252,6 +1306,10 @@ impl FunctionRegistry for SessionContext {
     fn udaf(&self, name: &str) -> Result<Arc<AggregateUDF>> {
         self.state.read().udaf(name)
     }
+
+    fn udwf(&self, name: &str) -> Result<Arc<WindowUDF>> {
+        self.state.read().udwf(name)
+    }
 }
 
 /// A planner used to add extensions to DataFusion logical and physical plans.
@@ -1302,6 +1360,10 @@ pub struct SessionState {
     scalar_functions: HashMap<String, Arc<ScalarUDF>>,
     /// Aggregate functions registered in the context
     aggregate_functions: HashMap<String, Arc<AggregateUDF>>,
+    /// Window functions registered in the context
+    window_functions: HashMap<String, Arc<WindowUDF>>,
+    /// Table functions, callable in the FROM clause of a query
+    table_functions: HashMap<String, Arc<dyn TableFunction>>,
     /// Session configuration
     config: SessionConfig,
     /// Execution properties
@@ -1316,6 +1378,8 @@ pub struct SessionState {
     table_factories: HashMap<String, Arc<dyn TableProviderFactory>>,
     /// Runtime environment
     runtime_env: Arc<RuntimeEnv>,
+    /// Tracks the transaction in progress in this session
+    transaction_manager: Arc<dyn TransactionManager>,
 }
 
 impl Debug for SessionState {
@@ -1443,10 +1507,13 @@ impl SessionState {
             catalog_list,
             scalar_functions: HashMap::new(),
             aggregate_functions: HashMap::new(),
+            window_functions: HashMap::new(),
+            table_functions: builtin_table_functions(),
             config,
             execution_props: ExecutionProps::new(),
             runtime_env: runtime,
             table_factories,
+            transaction_manager: Arc::new(DefaultTransactionManager::new()),
         }
     }
 
@@ -1549,6 +1616,15 @@ impl SessionState {
         self
     }
 
+    /// Replace the transaction manager
+    pub fn with_transaction_manager(
+        mut self,
+        transaction_manager: Arc<dyn TransactionManager>,
+    ) -> Self {
+        self.transaction_manager = transaction_manager;
+        self
+    }
+
     /// Replace the analyzer rules
     pub fn with_analyzer_rules(
         mut self,
@@ -1642,42 +1718,37 @@ impl SessionState {
         statement: &datafusion_sql::parser::Statement,
     ) -> Result<Vec<OwnedTableReference>> {
         use crate::catalog::information_schema::INFORMATION_SCHEMA_TABLES;
-        use datafusion_sql::parser::Statement as DFStatement;
+        use datafusion_sql::parser::{CopyToSource, Statement as DFStatement};
         use sqlparser::ast::*;
 
         // Getting `TableProviders` is async but planing is not -- thus pre-fetch
         // table providers for all relations referenced in this query
         let mut relations = hashbrown::HashSet::with_capacity(10);
 
-        match statement {
-            DFStatement::Statement(s) => {
-                struct RelationVisitor<'a>(&'a mut hashbrown::HashSet<ObjectName>);
+        struct RelationVisitor<'a>(&'a mut hashbrown::HashSet<ObjectName>);
 
-                impl<'a> Visitor for RelationVisitor<'a> {
-                    type Break = ();
+        impl<'a> Visitor for RelationVisitor<'a> {
+            type Break = ();
 
-                    fn pre_visit_relation(
-                        &mut self,
-                        relation: &ObjectName,
-                    ) -> ControlFlow<()> {
-                        self.0.get_or_insert_with(relation, |_| relation.clone());
-                        ControlFlow::Continue(())
-                    }
+            fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<()> {
+                self.0.get_or_insert_with(relation, |_| relation.clone());
+                ControlFlow::Continue(())
+            }
 
-                    fn pre_visit_statement(
-                        &mut self,
-                        statement: &Statement,
-                    ) -> ControlFlow<()> {
-                        if let Statement::ShowCreate {
-                            obj_type: ShowCreateObject::Table | ShowCreateObject::View,
-                            obj_name,
-                        } = statement
-                        {
-                            self.0.get_or_insert_with(obj_name, |_| obj_name.clone());
-                        }
-                        ControlFlow::Continue(())
-                    }
+            fn pre_visit_statement(&mut self, statement: &Statement) -> ControlFlow<()> {
+                if let Statement::ShowCreate {
+                    obj_type: ShowCreateObject::Table | ShowCreateObject::View,
+                    obj_name,
+                } = statement
+                {
+                    self.0.get_or_insert_with(obj_name, |_| obj_name.clone());
                 }
+                ControlFlow::Continue(())
+            }
+        }
+
+        match statement {
+            DFStatement::Statement(s) => {
                 let mut visitor = RelationVisitor(&mut relations);
                 let _ = s.as_ref().visit(&mut visitor);
             }
@@ -1688,6 +1759,15 @@ impl SessionState {
                 relations
                     .get_or_insert_with(&table.table_name, |_| table.table_name.clone());
             }
+            DFStatement::CopyTo(copy) => match &copy.source {
+                CopyToSource::Relation(table_name) => {
+                    relations.get_or_insert_with(table_name, |_| table_name.clone());
+                }
+                CopyToSource::Query(query) => {
+                    let mut visitor = RelationVisitor(&mut relations);
+                    let _ = query.as_ref().visit(&mut visitor);
+                }
+            },
         }
 
         // Always include information_schema if available
@@ -1718,32 +1798,71 @@ impl SessionState {
         let mut provider = SessionContextProvider {
             state: self,
             tables: HashMap::with_capacity(references.len()),
+            table_functions: HashMap::new(),
+            unresolved_table_functions: Mutex::new(vec![]),
         };
 
         let enable_ident_normalization =
             self.config.options().sql_parser.enable_ident_normalization;
         let parse_float_as_decimal =
             self.config.options().sql_parser.parse_float_as_decimal;
+        let collect_statistics = self.config.collect_statistics();
         for reference in references {
             let table = reference.table();
             let resolved = self.resolve_table_ref(&reference);
             if let Entry::Vacant(v) = provider.tables.entry(resolved.to_string()) {
                 if let Ok(schema) = self.schema_for_ref(resolved) {
                     if let Some(table) = schema.table(table).await {
+                        // Provide the statistics of listing tables to the
+                        // optimizer, the files are listed once per table
+                        if collect_statistics {
+                            if let Some(listing_table) =
+                                table.as_any().downcast_ref::<ListingTable>()
+                            {
+                                listing_table.collect_statistics(self).await?;
+                            }
+                        }
                         v.insert(provider_as_source(table));
                     }
                 }
             }
         }
 
-        let query = SqlToRel::new_with_options(
-            &provider,
-            ParserOptions {
-                parse_float_as_decimal,
-                enable_ident_normalization,
-            },
-        );
-        query.statement_to_plan(statement)
+        // Table functions may need to do IO to create their tables, so the
+        // calls found while planning are resolved here and the statement is
+        // planned again until all of them are resolved
+        loop {
+            let result = {
+                let query = SqlToRel::new_with_options(
+                    &provider,
+                    ParserOptions {
+                        parse_float_as_decimal,
+                        enable_ident_normalization,
+                    },
+                );
+                query.statement_to_plan(statement.clone())
+            };
+            let unresolved =
+                std::mem::take(&mut *provider.unresolved_table_functions.lock());
+            if result.is_ok() || unresolved.is_empty() {
+                return result;
+            }
+
+            for (name, args) in unresolved {
+                let function = self.table_functions.get(&name).ok_or_else(|| {
+                    DataFusionError::Plan(format!("table function '{name}' not found"))
+                })?;
+                let function_args = TableFunctionArgs::try_from_exprs(
+                    &name,
+                    &args,
+                    &self.execution_props,
+                )?;
+                let table = function.call(self, &function_args).await?;
+                provider
+                    .table_functions
+                    .insert((name, args), provider_as_source(table));
+            }
+        }
     }
 
     /// Creates a [`LogicalPlan`] from the provided SQL string
@@ -1850,6 +1969,16 @@ impl SessionState {
         &self.runtime_env
     }
 
+    /// Return the transaction manager
+    pub fn transaction_manager(&self) -> &Arc<dyn TransactionManager> {
+        &self.transaction_manager
+    }
+
+    /// Return the transaction in progress in this session, if any
+    pub fn transaction(&self) -> Option<Arc<Transaction>> {
+        self.transaction_manager.current()
+    }
+
     /// Return the execution properties
     pub fn execution_props(&self) -> &ExecutionProps {
         &self.execution_props
@@ -1890,6 +2019,16 @@ impl SessionState {
         &self.aggregate_functions
     }
 
+    /// Return reference to window functions
+    pub fn window_functions(&self) -> &HashMap<String, Arc<WindowUDF>> {
+        &self.window_functions
+    }
+
+    /// Return reference to table_functions
+    pub fn table_functions(&self) -> &HashMap<String, Arc<dyn TableFunction>> {
+        &self.table_functions
+    }
+
     /// Return version of the cargo package that produced this query
     pub fn version(&self) -> &str {
         env!("CARGO_PKG_VERSION")
@@ -1899,6 +2038,11 @@ impl SessionState {
 struct SessionContextProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<String, Arc<dyn TableSource>>,
+    /// Tables returned by the table function calls of the statement
+    table_functions: HashMap<(String, Vec<Expr>), Arc<dyn TableSource>>,
+    /// Table function calls found while planning the statement, that are
+    /// not in `table_functions` yet
+    unresolved_table_functions: Mutex<Vec<(String, Vec<Expr>)>>,
 }
 
 impl<'a> ContextProvider for SessionContextProvider<'a> {
@@ -1918,6 +2062,30 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
         self.state.aggregate_functions().get(name).cloned()
     }
 
+    fn get_window_meta(&self, name: &str) -> Option<Arc<WindowUDF>> {
+        self.state.window_functions().get(name).cloned()
+    }
+
+    fn get_table_function_source(
+        &self,
+        name: &str,
+        args: Vec<Expr>,
+    ) -> Result<Arc<dyn TableSource>> {
+        let key = (name.to_string(), args);
+        if let Some(source) = self.table_functions.get(&key) {
+            return Ok(source.clone());
+        }
+        if !self.state.table_functions().contains_key(name) {
+            return Err(DataFusionError::Plan(format!(
+                "table function '{name}' not found"
+            )));
+        }
+        self.unresolved_table_functions.lock().push(key);
+        Err(DataFusionError::Plan(format!(
+            "table function '{name}' is not resolved yet"
+        )))
+    }
+
     fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType> {
         if variable_names.is_empty() {
             return None;
@@ -1939,6 +2107,15 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
     fn options(&self) -> &ConfigOptions {
         self.state.config_options()
     }
+
+    fn create_cte_work_table(
+        &self,
+        name: &str,
+        schema: SchemaRef,
+    ) -> Result<Arc<dyn TableSource>> {
+        let table = Arc::new(CteWorkTable::new(name, schema));
+        Ok(provider_as_source(table))
+    }
 }
 
 impl FunctionRegistry for SessionState {
@@ -1965,6 +2142,16 @@ impl FunctionRegistry for SessionState {
             ))
         })
     }
+
+    fn udwf(&self, name: &str) -> Result<Arc<WindowUDF>> {
+        let result = self.window_functions.get(name);
+
+        result.cloned().ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "There is no UDWF named \"{name}\" in the registry"
+            ))
+        })
+    }
 }
 
 impl OptimizerConfig for SessionState {
@@ -1994,6 +2181,7 @@ impl From<&SessionState> for TaskContext {
             state.config.clone(),
             state.scalar_functions.clone(),
             state.aggregate_functions.clone(),
+            state.window_functions.clone(),
             state.runtime_env.clone(),
         )
     }
@@ -18,6 +18,7 @@
 //! Shared state for query planning and execution.
 
 pub mod context;
+pub mod transaction;
 // backwards compatibility
 pub use crate::datasource::file_format::options;
 
@@ -0,0 +1,249 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Transactions started with `BEGIN` and concluded with `COMMIT` or `ROLLBACK`
+//!
+//! A [`TransactionManager`] on the [`SessionState`] tracks the transaction in
+//! progress in a session. Tables that support transactions (see
+//! [`TableProvider::supports_transactions`]) enlist a [`TransactionalTable`]
+//! with the [`Transaction`] the first time they are scanned or modified in it,
+//! and are then committed or rolled back together.
+//!
+//! [`SessionState`]: crate::execution::context::SessionState
+//! [`TableProvider::supports_transactions`]: crate::datasource::TableProvider::supports_transactions
+
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion_expr::{
+    TransactionAccessMode, TransactionConclusion, TransactionEnd,
+    TransactionIsolationLevel, TransactionStart,
+};
+use parking_lot::Mutex;
+
+use crate::error::{DataFusionError, Result};
+
+/// Identifies a transaction, unique within the process
+pub type TransactionId = u64;
+
+static NEXT_TRANSACTION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A table taking part in a transaction.
+///
+/// Committing a transaction first prepares all of its tables and only
+/// commits them once every table prepared successfully. If preparing any
+/// table fails, all of them are rolled back.
+#[async_trait]
+pub trait TransactionalTable: Debug + Send + Sync {
+    /// Check that the changes of `txn` can be committed, for example that
+    /// they do not conflict with changes committed since `txn` started.
+    ///
+    /// The check must stay valid until `txn` commits or rolls back, for
+    /// example by holding a lock on the table until then.
+    async fn prepare(&self, txn: TransactionId) -> Result<()>;
+
+    /// Make the changes of `txn` visible outside of the transaction. Every
+    /// check that can make this fail belongs in [`Self::prepare`], as the
+    /// other tables of `txn` may already be committed.
+    async fn commit(&self, txn: TransactionId) -> Result<()>;
+
+    /// Discard the changes of `txn`
+    async fn rollback(&self, txn: TransactionId) -> Result<()>;
+
+    /// Release whatever is still held for `txn` when it is dropped, which
+    /// discards its changes if it neither committed nor rolled back
+    fn release(&self, _txn: TransactionId) {}
+}
+
+/// A transaction in progress
+#[derive(Debug)]
+pub struct Transaction {
+    id: TransactionId,
+    access_mode: TransactionAccessMode,
+    isolation_level: TransactionIsolationLevel,
+    tables: Mutex<Vec<Arc<dyn TransactionalTable>>>,
+}
+
+impl Transaction {
+    /// Create a new transaction with a fresh [`TransactionId`]
+    pub fn new(
+        access_mode: TransactionAccessMode,
+        isolation_level: TransactionIsolationLevel,
+    ) -> Self {
+        Self {
+            id: NEXT_TRANSACTION_ID.fetch_add(1, Ordering::Relaxed),
+            access_mode,
+            isolation_level,
+            tables: Mutex::new(vec![]),
+        }
+    }
+
+    /// Return the id of this transaction
+    pub fn id(&self) -> TransactionId {
+        self.id
+    }
+
+    /// Return whether this transaction is allowed to write
+    pub fn access_mode(&self) -> &TransactionAccessMode {
+        &self.access_mode
+    }
+
+    /// Return the isolation level this transaction was started with
+    pub fn isolation_level(&self) -> &TransactionIsolationLevel {
+        &self.isolation_level
+    }
+
+    /// Make `table` take part in this transaction. Enlisting the same table
+    /// more than once has no effect.
+    pub fn enlist(&self, table: Arc<dyn TransactionalTable>) {
+        let mut tables = self.tables.lock();
+        let ptr = Arc::as_ptr(&table) as *const ();
+        if !tables.iter().any(|t| Arc::as_ptr(t) as *const () == ptr) {
+            tables.push(table);
+        }
+    }
+
+    /// Commit the changes of all tables taking part in this transaction, or
+    /// roll all of them back if any of them fails to prepare
+    pub async fn commit(&self) -> Result<()> {
+        let mut tables = self.tables.lock().clone();
+        // Prepare the tables in the same order in all transactions, so the
+        // locks they hold until committing cannot deadlock
+        tables.sort_by_key(|table| Arc::as_ptr(table) as *const () as usize);
+        for table in &tables {
+            if let Err(e) = table.prepare(self.id).await {
+                self.rollback_tables(&tables).await;
+                return Err(e);
+            }
+        }
+        let mut result = Ok(());
+        for table in &tables {
+            // all tables are prepared, so keep committing the other tables,
+            // and report the first error
+            if let Err(e) = table.commit(self.id).await {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Discard the changes of all tables taking part in this transaction
+    pub async fn rollback(&self) -> Result<()> {
+        let tables = self.tables.lock().clone();
+        let mut result = Ok(());
+        for table in &tables {
+            // keep rolling back the other tables, and report the first error
+            if let Err(e) = table.rollback(self.id).await {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Roll back all `tables` after a failed prepare, whose error is reported
+    /// instead of the errors of the rollback
+    async fn rollback_tables(&self, tables: &[Arc<dyn TransactionalTable>]) {
+        for table in tables {
+            let _ = table.rollback(self.id).await;
+        }
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        for table in self.tables.get_mut().iter() {
+            table.release(self.id);
+        }
+    }
+}
+
+/// Tracks the transaction in progress in a session and executes the
+/// `BEGIN`, `COMMIT` and `ROLLBACK` statements of that session
+#[async_trait]
+pub trait TransactionManager: Debug + Send + Sync {
+    /// Start a new transaction
+    fn begin(&self, start: &TransactionStart) -> Result<()>;
+
+    /// Return the transaction in progress, if any
+    fn current(&self) -> Option<Arc<Transaction>>;
+
+    /// Commit or roll back the transaction in progress
+    async fn end(&self, end: &TransactionEnd) -> Result<()>;
+}
+
+/// The default [`TransactionManager`], allowing a single transaction at a
+/// time per session.
+///
+/// Tables taking part in a transaction are expected to provide snapshot
+/// isolation, which is used whatever isolation level the transaction was
+/// started with.
+#[derive(Debug, Default)]
+pub struct DefaultTransactionManager {
+    current: Mutex<Option<Arc<Transaction>>>,
+}
+
+impl DefaultTransactionManager {
+    /// Create a new transaction manager without a transaction in progress
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TransactionManager for DefaultTransactionManager {
+    fn begin(&self, start: &TransactionStart) -> Result<()> {
+        let mut current = self.current.lock();
+        if current.is_some() {
+            return Err(DataFusionError::Execution(
+                "There is already a transaction in progress".to_string(),
+            ));
+        }
+        *current = Some(Arc::new(Transaction::new(
+            start.access_mode.clone(),
+            start.isolation_level.clone(),
+        )));
+        Ok(())
+    }
+
+    fn current(&self) -> Option<Arc<Transaction>> {
+        self.current.lock().clone()
+    }
+
+    async fn end(&self, end: &TransactionEnd) -> Result<()> {
+        let txn = self.current.lock().take().ok_or_else(|| {
+            DataFusionError::Execution("There is no transaction in progress".to_string())
+        })?;
+        match end.conclusion {
+            TransactionConclusion::Commit => txn.commit().await?,
+            TransactionConclusion::Rollback => txn.rollback().await?,
+        };
+        // a transaction that failed to end is not chained
+        if end.chain {
+            *self.current.lock() = Some(Arc::new(Transaction::new(
+                txn.access_mode.clone(),
+                txn.isolation_level.clone(),
+            )));
+        }
+        Ok(())
+    }
+}
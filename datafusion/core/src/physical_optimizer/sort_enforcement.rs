@@ -38,12 +38,13 @@ use crate::config::ConfigOptions;
 use crate::error::Result;
 use crate::physical_optimizer::sort_pushdown::{pushdown_sorts, SortPushDown};
 use crate::physical_optimizer::utils::{
-    add_sort_above, find_indices, is_coalesce_partitions, is_limit, is_repartition,
-    is_sort, is_sort_preserving_merge, is_sorted, is_union, is_window,
-    merge_and_order_indices, set_difference,
+    add_sort_above, add_sort_above_with_fetch, find_indices, is_coalesce_partitions,
+    is_limit, is_repartition, is_sort, is_sort_preserving_merge, is_sorted, is_union,
+    is_window, merge_and_order_indices, set_difference,
 };
 use crate::physical_optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use crate::physical_plan::limit::GlobalLimitExec;
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use crate::physical_plan::windows::{
@@ -421,10 +422,25 @@ fn parallelize_sorts(
         let mut prev_layer = plan.clone();
         update_child_to_remove_coalesce(&mut prev_layer, &mut coalesce_onwards[0])?;
         let sort_exprs = get_sort_exprs(&plan)?;
-        add_sort_above(&mut prev_layer, sort_exprs.to_vec())?;
-        let spm = SortPreservingMergeExec::new(sort_exprs.to_vec(), prev_layer);
+        // Keep the limit of a global sort on the local sorts, so that each
+        // partition only keeps its top rows
+        let fetch = plan
+            .as_any()
+            .downcast_ref::<SortExec>()
+            .and_then(|sort_exec| sort_exec.fetch());
+        add_sort_above_with_fetch(&mut prev_layer, sort_exprs.to_vec(), fetch)?;
+        let spm: Arc<dyn ExecutionPlan> = Arc::new(SortPreservingMergeExec::new(
+            sort_exprs.to_vec(),
+            prev_layer,
+        ));
+        // The merged output still has up to `fetch` rows from every
+        // partition, so the global limit must be kept above the merge
+        let plan = match fetch {
+            Some(fetch) => Arc::new(GlobalLimitExec::new(spm, 0, Some(fetch))),
+            None => spm,
+        };
         return Ok(Transformed::Yes(PlanWithCorrespondingCoalescePartitions {
-            plan: Arc::new(spm),
+            plan,
             coalesce_onwards: vec![None],
         }));
     } else if is_coalesce_partitions(&plan) {
@@ -974,7 +990,7 @@ mod tests {
     use crate::physical_plan::filter::FilterExec;
     use crate::physical_plan::joins::utils::JoinOn;
     use crate::physical_plan::joins::SortMergeJoinExec;
-    use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
+    use crate::physical_plan::limit::LocalLimitExec;
     use crate::physical_plan::memory::MemoryExec;
     use crate::physical_plan::repartition::RepartitionExec;
     use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
@@ -2638,6 +2654,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_parallelize_sort_with_fetch() -> Result<()> {
+        let schema = create_test_schema()?;
+
+        let source = parquet_exec(&schema);
+        let repartition = repartition_exec(source);
+        let coalesce = Arc::new(CoalescePartitionsExec::new(repartition)) as _;
+        let sort_exprs = vec![sort_expr("nullable_col", &schema)];
+        let physical_plan =
+            Arc::new(SortExec::new(sort_exprs, coalesce).with_fetch(Some(10)))
+                as Arc<dyn ExecutionPlan>;
+
+        // The local sorts keep the fetch of the global sort, which is kept by
+        // a limit above the merge
+        let expected_input = vec![
+            "SortExec: fetch=10, expr=[nullable_col@0 ASC]",
+            "  CoalescePartitionsExec",
+            "    RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=1",
+            "      ParquetExec: file_groups={1 group: [[x]]}, projection=[nullable_col, non_nullable_col]",
+        ];
+        let expected_optimized = vec![
+            "GlobalLimitExec: skip=0, fetch=10",
+            "  SortPreservingMergeExec: [nullable_col@0 ASC]",
+            "    SortExec: fetch=10, expr=[nullable_col@0 ASC]",
+            "      RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=1",
+            "        ParquetExec: file_groups={1 group: [[x]]}, projection=[nullable_col, non_nullable_col]",
+        ];
+        assert_optimized!(expected_input, expected_optimized, physical_plan);
+        Ok(())
+    }
+
     #[tokio::test]
     // With new change in SortEnforcement EnforceSorting->EnforceDistribution->EnforceSorting
     // should produce same result with EnforceDistribution+EnforceSorting
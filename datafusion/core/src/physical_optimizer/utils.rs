@@ -39,6 +39,16 @@ use datafusion_physical_expr::PhysicalSortExpr;
 pub fn add_sort_above(
     node: &mut Arc<dyn ExecutionPlan>,
     sort_expr: Vec<PhysicalSortExpr>,
+) -> Result<()> {
+    add_sort_above_with_fetch(node, sort_expr, None)
+}
+
+/// Same as [`add_sort_above`], but if `fetch` is set, the added `SortExec`
+/// only keeps the first `fetch` rows of each partition.
+pub fn add_sort_above_with_fetch(
+    node: &mut Arc<dyn ExecutionPlan>,
+    sort_expr: Vec<PhysicalSortExpr>,
+    fetch: Option<usize>,
 ) -> Result<()> {
     // If the ordering requirement is already satisfied, do not add a sort.
     if !ordering_satisfy(
@@ -47,7 +57,7 @@ pub fn add_sort_above(
         || node.equivalence_properties(),
         || node.ordering_equivalence_properties(),
     ) {
-        let new_sort = SortExec::new(sort_expr, node.clone());
+        let new_sort = SortExec::new(sort_expr, node.clone()).with_fetch(fetch);
 
         *node = Arc::new(if node.output_partitioning().partition_count() > 1 {
             new_sort.with_preserve_partitioning(true)
@@ -585,11 +585,12 @@ impl BoundedAggregateStream {
                             filter_opt.as_ref(),
                             offsets,
                         )?;
+                        // variable-length states may grow the aggregation buffer
+                        let buffer_size_pre = group_state.aggregation_buffer.capacity();
                         let mut state_accessor =
                             RowAccessor::new_from_layout(self.row_aggr_layout.clone());
-                        state_accessor
-                            .point_to(0, group_state.aggregation_buffer.as_mut_slice());
-                        match self.mode {
+                        state_accessor.point_to(0, &mut group_state.aggregation_buffer);
+                        let res = match self.mode {
                             AggregateMode::Partial | AggregateMode::Single => {
                                 accumulator.update_batch(&values, &mut state_accessor)
                             }
@@ -597,7 +598,10 @@ impl BoundedAggregateStream {
                                 // note: the aggregation here is over states, not values, thus the merge
                                 accumulator.merge_batch(&values, &mut state_accessor)
                             }
-                        }
+                        };
+                        let buffer_size_post = group_state.aggregation_buffer.capacity();
+                        *allocated += buffer_size_post.saturating_sub(buffer_size_pre);
+                        res
                     })?;
                 // normal accumulators
                 group_state
@@ -640,6 +644,7 @@ impl BoundedAggregateStream {
         groups_with_rows: &[usize],
         row_values: &[Vec<ArrayRef>],
         row_filter_values: &[Option<ArrayRef>],
+        allocated: &mut usize,
     ) -> Result<()> {
         let filter_bool_array = row_filter_values
             .iter()
@@ -652,9 +657,10 @@ impl BoundedAggregateStream {
         for group_idx in groups_with_rows {
             let group_state =
                 &mut self.aggr_state.ordered_group_states[*group_idx].group_state;
+            let buffer_size_pre = group_state.aggregation_buffer.capacity();
             let mut state_accessor =
                 RowAccessor::new_from_layout(self.row_aggr_layout.clone());
-            state_accessor.point_to(0, group_state.aggregation_buffer.as_mut_slice());
+            state_accessor.point_to(0, &mut group_state.aggregation_buffer);
             for idx in &group_state.indices {
                 for (accumulator, values_array, filter_array) in izip!(
                     self.row_accumulators.iter_mut(),
@@ -679,6 +685,10 @@ impl BoundedAggregateStream {
             }
             // clear the group indices in this group
             group_state.indices.clear();
+            *allocated += group_state
+                .aggregation_buffer
+                .capacity()
+                .saturating_sub(buffer_size_pre);
         }
 
         Ok(())
@@ -758,6 +768,7 @@ impl BoundedAggregateStream {
                     &groups_with_rows,
                     &row_aggr_input_values,
                     &row_filter_values,
+                    &mut allocated,
                 )?;
             } else {
                 // Collect all indices + offsets based on keys in this vec
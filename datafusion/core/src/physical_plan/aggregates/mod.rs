@@ -34,8 +34,8 @@ use arrow::compute::DEFAULT_CAST_OPTIONS;
 use arrow::datatypes::{Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use datafusion_common::utils::longest_consecutive_prefix;
-use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::Accumulator;
+use datafusion_common::{ColumnStatistics, DataFusionError, Result};
+use datafusion_expr::{Accumulator, GroupsAccumulator};
 use datafusion_physical_expr::{
     aggregate::row_accumulator::RowAccumulator,
     equivalence::project_equivalence_properties,
@@ -472,6 +472,36 @@ impl AggregateExec {
             ))
         }
     }
+
+    /// Statistics of the group columns of the output, if the aggregation has a
+    /// single grouping set and the statistics of its input columns are known
+    fn group_statistics(
+        &self,
+        input_stats: &Statistics,
+    ) -> Option<Vec<ColumnStatistics>> {
+        let input_col_stats = input_stats.column_statistics.as_ref()?;
+        if self.group_by.groups.len() != 1 || self.group_by.contains_null() {
+            return None;
+        }
+
+        let group_stats = self
+            .group_by
+            .expr
+            .iter()
+            .map(|(expr, _)| match expr.as_any().downcast_ref::<Column>() {
+                Some(column) => {
+                    let stats = &input_col_stats[column.index()];
+                    ColumnStatistics {
+                        // all NULL values of a column form a single group
+                        null_count: stats.null_count.map(|count| count.min(1)),
+                        ..stats.clone()
+                    }
+                }
+                None => ColumnStatistics::default(),
+            })
+            .collect();
+        Some(group_stats)
+    }
 }
 
 impl ExecutionPlan for AggregateExec {
@@ -663,7 +693,6 @@ impl ExecutionPlan for AggregateExec {
     fn statistics(&self) -> Statistics {
         // TODO stats: group expressions:
         // - once expressions will be able to compute their own stats, use it here
-        // - case where we group by on a column for which with have the `distinct` stat
         // TODO stats: aggr expression:
         // - aggregations somtimes also preserve invariants such as min, max...
         match self.mode {
@@ -676,16 +705,46 @@ impl ExecutionPlan for AggregateExec {
                     ..Default::default()
                 }
             }
-            _ => Statistics {
-                // the output row count is surely not larger than its input row count
-                num_rows: self.input.statistics().num_rows,
-                is_exact: false,
-                ..Default::default()
-            },
+            _ => {
+                let input_stats = self.input.statistics();
+                let group_stats = self.group_statistics(&input_stats);
+                // the output row count is surely not larger than its input row count,
+                // nor than the number of combinations of distinct group values, unless
+                // the groups of each partition are output separately
+                let max_groups = match self.mode {
+                    AggregateMode::Partial => None,
+                    _ => group_stats.as_deref().and_then(max_group_count),
+                };
+                let num_rows = match (input_stats.num_rows, max_groups) {
+                    (Some(num_rows), Some(groups)) => Some(num_rows.min(groups)),
+                    (num_rows, _) => num_rows,
+                };
+                let column_statistics = group_stats.map(|mut column_statistics| {
+                    column_statistics
+                        .resize(self.schema.fields().len(), ColumnStatistics::default());
+                    column_statistics
+                });
+                Statistics {
+                    num_rows,
+                    column_statistics,
+                    is_exact: false,
+                    ..Default::default()
+                }
+            }
         }
     }
 }
 
+/// The maximum number of groups given the statistics of the group columns,
+/// if the distinct counts of all of them are known
+fn max_group_count(group_stats: &[ColumnStatistics]) -> Option<usize> {
+    group_stats.iter().try_fold(1_usize, |count, stats| {
+        // NULL values form a group of their own, unless known to be absent
+        let null_group = usize::from(stats.null_count != Some(0));
+        count.checked_mul(stats.distinct_count? + null_group)
+    })
+}
+
 fn create_schema(
     input_schema: &Schema,
     group_expr: &[(Arc<dyn PhysicalExpr>, String)],
@@ -813,6 +872,7 @@ fn merge_expressions(
 
 pub(crate) type AccumulatorItem = Box<dyn Accumulator>;
 pub(crate) type RowAccumulatorItem = Box<dyn RowAccumulator>;
+pub(crate) type GroupsAccumulatorItem = Box<dyn GroupsAccumulator>;
 
 fn create_accumulators(
     aggr_expr: &[Arc<dyn AggregateExpr>],
@@ -837,6 +897,15 @@ fn create_row_accumulators(
         .collect::<Result<Vec<_>>>()
 }
 
+fn create_groups_accumulators(
+    aggr_expr: &[Arc<dyn AggregateExpr>],
+) -> Result<Vec<GroupsAccumulatorItem>> {
+    aggr_expr
+        .iter()
+        .map(|expr| expr.create_groups_accumulator())
+        .collect::<Result<Vec<_>>>()
+}
+
 /// returns a vector of ArrayRefs, where each entry corresponds to either the
 /// final value (mode = Final, FinalPartitioned and Single) or states (mode = Partial)
 fn finalize_aggregation(
@@ -948,20 +1017,23 @@ fn evaluate_group_by(
 #[cfg(test)]
 mod tests {
     use crate::execution::context::{SessionConfig, TaskContext};
+    use crate::execution::disk_manager::DiskManagerConfig;
     use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
     use crate::from_slice::FromSlice;
     use crate::physical_plan::aggregates::{
         get_working_mode, AggregateExec, AggregateMode, PhysicalGroupBy,
     };
-    use crate::physical_plan::expressions::{col, Avg};
-    use crate::test::exec::{assert_strong_count_converges_to_zero, BlockingExec};
+    use crate::physical_plan::expressions::{col, Avg, Sum};
+    use crate::test::exec::{
+        assert_strong_count_converges_to_zero, BlockingExec, StatisticsExec,
+    };
     use crate::test::{assert_is_pending, csv_exec_sorted};
     use crate::{assert_batches_sorted_eq, physical_plan::common};
-    use arrow::array::{Float64Array, UInt32Array};
+    use arrow::array::{Float64Array, Int64Array, UInt32Array};
     use arrow::compute::{concat_batches, SortOptions};
     use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
     use arrow::record_batch::RecordBatch;
-    use datafusion_common::{DataFusionError, Result, ScalarValue};
+    use datafusion_common::{ColumnStatistics, DataFusionError, Result, ScalarValue};
     use datafusion_physical_expr::expressions::{lit, ApproxDistinct, Count, Median};
     use datafusion_physical_expr::{AggregateExpr, PhysicalExpr, PhysicalSortExpr};
     use futures::{FutureExt, Stream};
@@ -974,6 +1046,7 @@ mod tests {
         FullyOrdered, PartiallyOrdered,
     };
     use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+    use crate::physical_plan::memory::MemoryExec;
     use crate::physical_plan::{
         ExecutionPlan, Partitioning, RecordBatchStream, SendableRecordBatchStream,
         Statistics,
@@ -1434,11 +1507,16 @@ mod tests {
             Arc::new(TestYieldingExec { yield_first: true });
         let input_schema = input.schema();
 
+        // without a disk manager, grouped aggregations can not spill
         let session_ctx = SessionContext::with_config_rt(
             SessionConfig::default(),
             Arc::new(
-                RuntimeEnv::new(RuntimeConfig::default().with_memory_limit(1, 1.0))
-                    .unwrap(),
+                RuntimeEnv::new(
+                    RuntimeConfig::default()
+                        .with_memory_limit(1, 1.0)
+                        .with_disk_manager(DiskManagerConfig::Disabled),
+                )
+                .unwrap(),
             ),
         );
         let task_ctx = session_ctx.task_ctx();
@@ -1516,6 +1594,178 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_spill_grouped_aggregate() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::UInt32, false),
+            Field::new("b", DataType::Float64, false),
+        ]));
+        // 4 batches with the same 2000 distinct groups each
+        let batches = (0..4)
+            .map(|_| {
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(UInt32Array::from_iter_values(0..2000)),
+                        Arc::new(Float64Array::from_iter_values(
+                            (0..2000).map(|v| v as f64),
+                        )),
+                    ],
+                )
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+
+        let session_ctx = SessionContext::with_config_rt(
+            SessionConfig::default().with_batch_size(50),
+            Arc::new(RuntimeEnv::new(
+                RuntimeConfig::default().with_memory_limit(100_000, 1.0),
+            )?),
+        );
+        let task_ctx = session_ctx.task_ctx();
+
+        let groups =
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]);
+        // `Avg` uses a row accumulator, `Median` a normal one
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![
+            Arc::new(Avg::new(
+                col("b", &schema)?,
+                "AVG(b)".to_string(),
+                DataType::Float64,
+            )),
+            Arc::new(Median::new(
+                col("b", &schema)?,
+                "MEDIAN(b)".to_string(),
+                DataType::Float64,
+            )),
+        ];
+
+        let aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Single,
+            groups,
+            aggregates,
+            vec![None; 2],
+            input,
+            schema.clone(),
+        )?);
+
+        let result = common::collect(aggregate.execute(0, task_ctx)?).await?;
+        let batch = concat_batches(&aggregate.schema(), &result)?;
+        assert_eq!(batch.num_rows(), 2000);
+
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<UInt32Array>()
+            .unwrap();
+        let avg = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        let median = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        let mut seen = vec![false; 2000];
+        for row in 0..batch.num_rows() {
+            let group = a.value(row);
+            assert!(!seen[group as usize], "group {group} emitted twice");
+            seen[group as usize] = true;
+            assert_eq!(avg.value(row), group as f64);
+            assert_eq!(median.value(row), group as f64);
+        }
+
+        let metrics = aggregate.metrics().unwrap();
+        assert!(metrics.spill_count().unwrap() > 0);
+        assert!(metrics.spilled_bytes().unwrap() > 0);
+        assert_eq!(metrics.output_rows().unwrap(), 2000);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sum_partial_ordered_final_hash() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from_slice([1, 1, 2, 2, 3])),
+                Arc::new(Int64Array::from(vec![
+                    Some(1),
+                    Some(2),
+                    None,
+                    None,
+                    Some(5),
+                ])),
+            ],
+        )?;
+        let input = Arc::new(
+            MemoryExec::try_new(
+                &[vec![batch.clone()], vec![batch]],
+                schema.clone(),
+                None,
+            )?
+            .with_sort_information(vec![sort_expr("a", &schema)]),
+        );
+
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+
+        let groups =
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]);
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![Arc::new(Sum::new(
+            col("b", &schema)?,
+            "SUM(b)".to_string(),
+            DataType::Int64,
+        ))];
+
+        // The partial aggregate runs on ordered input with row accumulators
+        let partial_aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Partial,
+            groups,
+            aggregates.clone(),
+            vec![None],
+            input,
+            schema.clone(),
+        )?);
+        let stream = partial_aggregate.execute_typed(0, task_ctx.clone())?;
+        assert!(matches!(stream, StreamType::BoundedAggregate(_)));
+
+        // The final aggregate runs on unordered input with groups accumulators
+        let merge = Arc::new(CoalescePartitionsExec::new(partial_aggregate));
+        let final_groups =
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]);
+        let final_aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Final,
+            final_groups,
+            aggregates,
+            vec![None],
+            merge,
+            schema,
+        )?);
+        let stream = final_aggregate.execute_typed(0, task_ctx.clone())?;
+        assert!(matches!(stream, StreamType::GroupedHashAggregateStream(_)));
+
+        let result = common::collect(final_aggregate.execute(0, task_ctx)?).await?;
+        let expected = vec![
+            "+---+--------+",
+            "| a | SUM(b) |",
+            "+---+--------+",
+            "| 1 | 6      |",
+            "| 2 |        |",
+            "| 3 | 10     |",
+            "+---+--------+",
+        ];
+        assert_batches_sorted_eq!(expected, &result);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_drop_cancel_without_groups() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -1590,4 +1840,73 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_statistics_with_group_by() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+            Field::new("c", DataType::Float64, true),
+        ]);
+        let a_stats = ColumnStatistics {
+            null_count: Some(0),
+            max_value: Some(ScalarValue::Int32(Some(10))),
+            min_value: Some(ScalarValue::Int32(Some(1))),
+            distinct_count: Some(10),
+        };
+        let b_stats = ColumnStatistics {
+            null_count: Some(20),
+            distinct_count: Some(5),
+            ..Default::default()
+        };
+        let input = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Some(1000),
+                column_statistics: Some(vec![
+                    a_stats.clone(),
+                    b_stats,
+                    ColumnStatistics::default(),
+                ]),
+                ..Default::default()
+            },
+            schema.clone(),
+        ));
+
+        let group_by = PhysicalGroupBy::new_single(vec![
+            (col("a", &schema)?, "a".to_string()),
+            (col("b", &schema)?, "b".to_string()),
+        ]);
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![Arc::new(Avg::new(
+            col("c", &schema)?,
+            "AVG(c)".to_string(),
+            DataType::Float64,
+        ))];
+        let aggregate = AggregateExec::try_new(
+            AggregateMode::Single,
+            group_by,
+            aggregates,
+            vec![None],
+            input,
+            Arc::new(schema),
+        )?;
+
+        let statistics = aggregate.statistics();
+        // 10 values of a times 5 values and NULL of b
+        assert_eq!(statistics.num_rows, Some(60));
+        assert!(!statistics.is_exact);
+        assert_eq!(
+            statistics.column_statistics,
+            Some(vec![
+                a_stats,
+                ColumnStatistics {
+                    null_count: Some(1),
+                    distinct_count: Some(5),
+                    ..Default::default()
+                },
+                ColumnStatistics::default(),
+            ])
+        );
+
+        Ok(())
+    }
 }
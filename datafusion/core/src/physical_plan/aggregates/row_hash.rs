@@ -38,14 +38,20 @@ use crate::physical_plan::aggregates::utils::{
 };
 use crate::physical_plan::aggregates::{
     evaluate_group_by, evaluate_many, evaluate_optional, group_schema, AggregateMode,
-    PhysicalGroupBy, RowAccumulatorItem,
+    GroupsAccumulatorItem, PhysicalGroupBy, RowAccumulatorItem,
 };
-use crate::physical_plan::metrics::{BaselineMetrics, RecordOutput};
-use crate::physical_plan::{aggregates, AggregateExpr, PhysicalExpr};
+use crate::physical_plan::common::IPCWriter;
+use crate::physical_plan::expressions::Column;
+use crate::physical_plan::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MemTrackingMetrics, RecordOutput,
+};
+use crate::physical_plan::sorts::sort::read_spill_as_stream;
+use crate::physical_plan::sorts::streaming_merge;
+use crate::physical_plan::{aggregates, AggregateExpr, PhysicalExpr, PhysicalSortExpr};
 use crate::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
 use arrow::array::*;
-use arrow::compute::cast;
-use arrow::datatypes::DataType;
+use arrow::compute::{cast, concat_batches, take, SortOptions};
+use arrow::datatypes::{DataType, Schema};
 use arrow::{datatypes::SchemaRef, record_batch::RecordBatch};
 use datafusion_common::cast::as_boolean_array;
 use datafusion_common::{Result, ScalarValue};
@@ -54,12 +60,16 @@ use datafusion_row::accessor::RowAccessor;
 use datafusion_row::layout::RowLayout;
 use hashbrown::raw::RawTable;
 use itertools::izip;
+use log::debug;
+use tempfile::NamedTempFile;
 
 /// Grouping aggregate with row-format aggregation states inside.
 ///
 /// For each aggregation entry, we use:
 /// - [Arrow-row] represents grouping keys for fast hash computation and comparison directly on raw bytes.
 /// - [WordAligned] row to store aggregation state, designed to be CPU-friendly when updates over every field are often.
+/// - [`GroupsAccumulator`]s, which keep the state of all groups together and are updated once per
+///   input batch, for the aggregates supporting them.
 ///
 /// The architecture is the following:
 ///
@@ -69,12 +79,29 @@ use itertools::izip;
 /// 4. The state's RecordBatch is `merge`d to a new state
 /// 5. The state is mapped to the final value
 ///
+/// If the memory reservation can not grow while reading the input (and the
+/// [`DiskManager`] allows temporary files), the groups are handled as follows:
+///
+/// * In [`AggregateMode::Partial`] the intermediate state of all groups is
+///   emitted early and the hash table is cleared, as the final aggregation
+///   merges groups which appear more than once.
+/// * In all other modes the groups are sorted by their group keys and their
+///   intermediate state is spilled to disk. Once the input is exhausted the
+///   sorted spill files are merged and re-aggregated in
+///   [`AggregateMode::Final`]; as the merged stream is ordered by the group
+///   keys, groups are emitted as soon as they are complete.
+///
 /// [WordAligned]: datafusion_row::layout
+/// [`GroupsAccumulator`]: datafusion_expr::GroupsAccumulator
+/// [`DiskManager`]: crate::execution::disk_manager::DiskManager
 pub(crate) struct GroupedHashAggregateStream {
     schema: SchemaRef,
     input: SendableRecordBatchStream,
     mode: AggregateMode,
 
+    /// All aggregate expressions, in the order of the output schema
+    aggr_expr: Vec<Arc<dyn AggregateExpr>>,
+
     normal_aggr_expr: Vec<Arc<dyn AggregateExpr>>,
     /// Aggregate expressions not supporting row accumulation
     normal_aggregate_expressions: Vec<Vec<Arc<dyn PhysicalExpr>>>,
@@ -90,6 +117,16 @@ pub(crate) struct GroupedHashAggregateStream {
     row_aggr_schema: SchemaRef,
     row_aggr_layout: Arc<RowLayout>,
 
+    /// Aggregate expressions supporting vectorized accumulation of all groups
+    groups_aggregate_expressions: Vec<Vec<Arc<dyn PhysicalExpr>>>,
+    /// Filter expression for each groups aggregate expression
+    groups_filter_expressions: Vec<Option<Arc<dyn PhysicalExpr>>>,
+    groups_accumulators: Vec<GroupsAccumulatorItem>,
+    /// The final values or intermediate states of all groups of the groups
+    /// accumulators, one array per output field, evaluated once for all
+    /// batches emitted before the state is cleared
+    groups_accumulator_output: Option<Vec<ArrayRef>>,
+
     group_by: PhysicalGroupBy,
 
     aggr_state: AggregationState,
@@ -107,7 +144,41 @@ pub(crate) struct GroupedHashAggregateStream {
     /// keeps range for each accumulator in the field
     /// first element in the array corresponds to normal accumulators
     /// second element in the array corresponds to row accumulators
-    indices: [Vec<Range<usize>>; 2],
+    /// third element in the array corresponds to groups accumulators
+    indices: [Vec<Range<usize>>; 3],
+    /// same as `indices`, but for the intermediate state fields of each
+    /// accumulator (identical to `indices` in [`AggregateMode::Partial`])
+    state_indices: [Vec<Range<usize>>; 3],
+
+    /// true if the input is ordered by the group keys, in which case every
+    /// group is emitted as soon as it can not receive more rows
+    ordered_input: bool,
+    /// rows of the last group seen in an ordered input, which may continue
+    /// in the next input batch
+    carry: Option<RecordBatch>,
+    /// true once the input stream is exhausted
+    input_done: bool,
+    spill_state: SpillState,
+    context: Arc<TaskContext>,
+    partition: usize,
+}
+
+/// Spill files written by a [`GroupedHashAggregateStream`]
+struct SpillState {
+    /// true if the stream may spill (or emit early) when its memory
+    /// reservation can not grow
+    can_spill: bool,
+    /// Spill files, each containing intermediate aggregate state sorted by
+    /// the group keys
+    spills: Vec<NamedTempFile>,
+    /// Schema of the spill files: group columns followed by the state fields
+    /// of all aggregate expressions
+    spill_schema: SchemaRef,
+    /// Sort order of the spill files
+    spill_expr: Vec<PhysicalSortExpr>,
+    /// Stream merging and re-aggregating the spill files, created once the
+    /// input is exhausted
+    merged: Option<SendableRecordBatchStream>,
 }
 
 impl GroupedHashAggregateStream {
@@ -129,12 +200,20 @@ impl GroupedHashAggregateStream {
         let timer = baseline_metrics.elapsed_compute().timer();
 
         let mut start_idx = group_by.expr.len();
+        let mut state_start_idx = group_by.expr.len();
+        let mut groups_aggr_expr = vec![];
+        let mut groups_agg_indices = vec![];
+        let mut groups_agg_state_indices = vec![];
+        let mut groups_aggregate_expressions = vec![];
+        let mut groups_filter_expressions = vec![];
         let mut row_aggr_expr = vec![];
         let mut row_agg_indices = vec![];
+        let mut row_agg_state_indices = vec![];
         let mut row_aggregate_expressions = vec![];
         let mut row_filter_expressions = vec![];
         let mut normal_aggr_expr = vec![];
         let mut normal_agg_indices = vec![];
+        let mut normal_agg_state_indices = vec![];
         let mut normal_aggregate_expressions = vec![];
         let mut normal_filter_expressions = vec![];
         // The expressions to evaluate the batch, one vec of expressions per aggregation.
@@ -153,10 +232,11 @@ impl GroupedHashAggregateStream {
             .zip(all_aggregate_expressions.into_iter())
             .zip(filter_expressions.into_iter())
         {
+            let n_state_fields = expr.state_fields()?.len();
             let n_fields = match mode {
                 // In partial aggregation, we keep additional fields in order to successfully
                 // merge aggregation results downstream.
-                AggregateMode::Partial => expr.state_fields()?.len(),
+                AggregateMode::Partial => n_state_fields,
                 _ => 1,
             };
             // Stores range of each expression:
@@ -164,21 +244,36 @@ impl GroupedHashAggregateStream {
                 start: start_idx,
                 end: start_idx + n_fields,
             };
-            if expr.row_accumulator_supported() {
+            let state_range = Range {
+                start: state_start_idx,
+                end: state_start_idx + n_state_fields,
+            };
+            if expr.groups_accumulator_supported() {
+                groups_aggregate_expressions.push(others);
+                groups_filter_expressions.push(filter.clone());
+                groups_agg_indices.push(aggr_range);
+                groups_agg_state_indices.push(state_range);
+                groups_aggr_expr.push(expr.clone());
+            } else if expr.row_accumulator_supported() {
                 row_aggregate_expressions.push(others);
                 row_filter_expressions.push(filter.clone());
                 row_agg_indices.push(aggr_range);
+                row_agg_state_indices.push(state_range);
                 row_aggr_expr.push(expr.clone());
             } else {
                 normal_aggregate_expressions.push(others);
                 normal_filter_expressions.push(filter.clone());
                 normal_agg_indices.push(aggr_range);
+                normal_agg_state_indices.push(state_range);
                 normal_aggr_expr.push(expr.clone());
             }
             start_idx += n_fields;
+            state_start_idx += n_state_fields;
         }
 
         let row_accumulators = aggregates::create_row_accumulators(&row_aggr_expr)?;
+        let groups_accumulators =
+            aggregates::create_groups_accumulators(&groups_aggr_expr)?;
 
         let row_aggr_schema = aggr_state_schema(&row_aggr_expr);
 
@@ -193,9 +288,34 @@ impl GroupedHashAggregateStream {
 
         let row_aggr_layout = Arc::new(RowLayout::new(&row_aggr_schema));
 
+        let mut spill_fields = group_schema.fields().to_vec();
+        spill_fields.extend(aggr_state_schema(&aggr_expr).fields().iter().cloned());
+        let spill_schema = Arc::new(Schema::new(spill_fields));
+        // group keys are stored in row format, whose byte order matches an
+        // ascending, nulls first lexicographical order of the group columns
+        let spill_expr = group_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| PhysicalSortExpr {
+                expr: Arc::new(Column::new(field.name(), idx)),
+                options: SortOptions::default(),
+            })
+            .collect();
+        let can_spill = context.runtime_env().disk_manager.tmp_files_enabled();
+        let spill_state = SpillState {
+            can_spill,
+            spills: vec![],
+            spill_schema,
+            spill_expr,
+            merged: None,
+        };
+
         let name = format!("GroupedHashAggregateStream[{partition}]");
         let aggr_state = AggregationState {
-            reservation: MemoryConsumer::new(name).register(context.memory_pool()),
+            reservation: MemoryConsumer::new(name)
+                .with_can_spill(can_spill)
+                .register(context.memory_pool()),
             map: RawTable::with_capacity(0),
             group_states: Vec::with_capacity(0),
         };
@@ -208,6 +328,7 @@ impl GroupedHashAggregateStream {
             schema: Arc::clone(&schema),
             input,
             mode,
+            aggr_expr,
             normal_aggr_expr,
             normal_aggregate_expressions,
             normal_filter_expressions,
@@ -217,6 +338,10 @@ impl GroupedHashAggregateStream {
             row_converter,
             row_aggr_schema,
             row_aggr_layout,
+            groups_aggregate_expressions,
+            groups_filter_expressions,
+            groups_accumulators,
+            groups_accumulator_output: None,
             group_by,
             aggr_state,
             exec_state,
@@ -225,7 +350,18 @@ impl GroupedHashAggregateStream {
             batch_size,
             scalar_update_factor,
             row_group_skip_position: 0,
-            indices: [normal_agg_indices, row_agg_indices],
+            indices: [normal_agg_indices, row_agg_indices, groups_agg_indices],
+            state_indices: [
+                normal_agg_state_indices,
+                row_agg_state_indices,
+                groups_agg_state_indices,
+            ],
+            ordered_input: false,
+            carry: None,
+            input_done: false,
+            spill_state,
+            context,
+            partition,
         })
     }
 }
@@ -240,30 +376,51 @@ impl Stream for GroupedHashAggregateStream {
         let elapsed_compute = self.baseline_metrics.elapsed_compute().clone();
 
         loop {
+            if let Some(merged) = self.spill_state.merged.as_mut() {
+                return merged.poll_next_unpin(cx);
+            }
+
             match self.exec_state {
                 ExecutionState::ReadingInput => {
                     match ready!(self.input.poll_next_unpin(cx)) {
                         // new batch to aggregate
                         Some(Ok(batch)) => {
                             let timer = elapsed_compute.timer();
-                            let result = self.group_aggregate_batch(batch);
-                            timer.done();
-
+                            let result = if self.ordered_input {
+                                self.group_aggregate_ordered_batch(batch)
+                            } else {
+                                self.group_aggregate_batch(batch)
+                            };
                             // allocate memory
                             // This happens AFTER we actually used the memory, but simplifies the whole accounting and we are OK with
                             // overshooting a bit. Also this means we either store the whole record batch or not.
-                            let result = result.and_then(|allocated| {
-                                self.aggr_state.reservation.try_grow(allocated)
-                            });
+                            let result = result
+                                .and_then(|allocated| self.reserve_or_spill(allocated));
+                            timer.done();
 
                             if let Err(e) = result {
                                 return Poll::Ready(Some(Err(e)));
                             }
+
+                            // all groups of an ordered input aggregated so far are complete
+                            if self.ordered_input
+                                && !self.aggr_state.group_states.is_empty()
+                            {
+                                self.exec_state = ExecutionState::ProducingOutput;
+                            }
                         }
                         // inner had error, return to caller
                         Some(Err(e)) => return Poll::Ready(Some(Err(e))),
                         // inner is done, producing output
                         None => {
+                            self.input_done = true;
+                            let timer = elapsed_compute.timer();
+                            let result = self.finish_input();
+                            timer.done();
+
+                            if let Err(e) = result {
+                                return Poll::Ready(Some(Err(e)));
+                            }
                             self.exec_state = ExecutionState::ProducingOutput;
                         }
                     }
@@ -283,9 +440,14 @@ impl Stream for GroupedHashAggregateStream {
                             return Poll::Ready(Some(Ok(batch)));
                         }
                         // end of output
-                        Ok(None) => {
+                        Ok(None) if self.input_done => {
                             self.exec_state = ExecutionState::Done;
                         }
+                        // groups were emitted early, continue with the input
+                        Ok(None) => {
+                            self.clear_state();
+                            self.exec_state = ExecutionState::ReadingInput;
+                        }
                         // error making output
                         Err(error) => return Poll::Ready(Some(Err(error))),
                     }
@@ -304,11 +466,16 @@ impl RecordBatchStream for GroupedHashAggregateStream {
 
 impl GroupedHashAggregateStream {
     // Update the row_aggr_state according to groub_by values (result of group_by_expressions)
+    // The rows of each group are only collected into its `indices` if there
+    // are row or normal accumulators, which update the groups one at a time
     fn update_group_state(
         &mut self,
         group_values: &[ArrayRef],
+        group_indices: &mut Vec<usize>,
         allocated: &mut usize,
     ) -> Result<Vec<usize>> {
+        let collect_indices =
+            !self.row_accumulators.is_empty() || !self.normal_aggr_expr.is_empty();
         let group_rows = self.row_converter.convert_columns(group_values)?;
         let n_rows = group_rows.num_rows();
         // 1.1 construct the key from the group values
@@ -317,6 +484,9 @@ impl GroupedHashAggregateStream {
 
         // track which entries in `aggr_state` have rows in this batch to aggregate
         let mut groups_with_rows = vec![];
+        // the group of each input row
+        group_indices.clear();
+        group_indices.reserve(n_rows);
 
         // 1.1 Calculate the group keys for the group values
         let mut batch_hashes = vec![0; n_rows];
@@ -338,14 +508,18 @@ impl GroupedHashAggregateStream {
             match entry {
                 // Existing entry for this group value
                 Some((_hash, group_idx)) => {
-                    let group_state = &mut group_states[*group_idx];
+                    if collect_indices {
+                        let group_state = &mut group_states[*group_idx];
 
-                    // 1.3
-                    if group_state.indices.is_empty() {
-                        groups_with_rows.push(*group_idx);
-                    };
+                        // 1.3
+                        if group_state.indices.is_empty() {
+                            groups_with_rows.push(*group_idx);
+                        };
 
-                    group_state.indices.push_accounted(row as u32, allocated); // remember this row
+                        // remember this row
+                        group_state.indices.push_accounted(row as u32, allocated);
+                    }
+                    group_indices.push(*group_idx);
                 }
                 //  1.2 Need to create new entry
                 None => {
@@ -359,7 +533,11 @@ impl GroupedHashAggregateStream {
                             self.row_aggr_layout.fixed_part_width()
                         ],
                         accumulator_set,
-                        indices: vec![row as u32], // 1.3
+                        indices: if collect_indices {
+                            vec![row as u32] // 1.3
+                        } else {
+                            vec![]
+                        },
                     };
                     let group_idx = group_states.len();
 
@@ -389,7 +567,10 @@ impl GroupedHashAggregateStream {
 
                     group_states.push_accounted(group_state, allocated);
 
-                    groups_with_rows.push(group_idx);
+                    if collect_indices {
+                        groups_with_rows.push(group_idx);
+                    }
+                    group_indices.push(group_idx);
                 }
             };
         }
@@ -430,11 +611,12 @@ impl GroupedHashAggregateStream {
                             filter_opt.as_ref(),
                             offsets,
                         )?;
+                        // variable-length states may grow the aggregation buffer
+                        let buffer_size_pre = group_state.aggregation_buffer.capacity();
                         let mut state_accessor =
                             RowAccessor::new_from_layout(self.row_aggr_layout.clone());
-                        state_accessor
-                            .point_to(0, group_state.aggregation_buffer.as_mut_slice());
-                        match self.mode {
+                        state_accessor.point_to(0, &mut group_state.aggregation_buffer);
+                        let res = match self.mode {
                             AggregateMode::Partial | AggregateMode::Single => {
                                 accumulator.update_batch(&values, &mut state_accessor)
                             }
@@ -442,7 +624,10 @@ impl GroupedHashAggregateStream {
                                 // note: the aggregation here is over states, not values, thus the merge
                                 accumulator.merge_batch(&values, &mut state_accessor)
                             }
-                        }
+                        };
+                        let buffer_size_post = group_state.aggregation_buffer.capacity();
+                        *allocated += buffer_size_post.saturating_sub(buffer_size_pre);
+                        res
                     })?;
                 // normal accumulators
                 group_state
@@ -485,6 +670,7 @@ impl GroupedHashAggregateStream {
         groups_with_rows: &[usize],
         row_values: &[Vec<ArrayRef>],
         row_filter_values: &[Option<ArrayRef>],
+        allocated: &mut usize,
     ) -> Result<()> {
         let filter_bool_array = row_filter_values
             .iter()
@@ -496,9 +682,10 @@ impl GroupedHashAggregateStream {
 
         for group_idx in groups_with_rows {
             let group_state = &mut self.aggr_state.group_states[*group_idx];
+            let buffer_size_pre = group_state.aggregation_buffer.capacity();
             let mut state_accessor =
                 RowAccessor::new_from_layout(self.row_aggr_layout.clone());
-            state_accessor.point_to(0, group_state.aggregation_buffer.as_mut_slice());
+            state_accessor.point_to(0, &mut group_state.aggregation_buffer);
             for idx in &group_state.indices {
                 for (accumulator, values_array, filter_array) in izip!(
                     self.row_accumulators.iter_mut(),
@@ -523,11 +710,56 @@ impl GroupedHashAggregateStream {
             }
             // clear the group indices in this group
             group_state.indices.clear();
+            *allocated += group_state
+                .aggregation_buffer
+                .capacity()
+                .saturating_sub(buffer_size_pre);
         }
 
         Ok(())
     }
 
+    /// Update the groups accumulators with all rows of a batch, where
+    /// `group_indices` contains the group of each row.
+    ///
+    /// The groups are identified by their index into `group_states`.
+    fn update_groups_accumulators(
+        &mut self,
+        group_indices: &[usize],
+        groups_values: &[Vec<ArrayRef>],
+        groups_filter_values: &[Option<ArrayRef>],
+        allocated: &mut usize,
+    ) -> Result<()> {
+        let total_num_groups = self.aggr_state.group_states.len();
+
+        for (accumulator, values, filter_opt) in izip!(
+            self.groups_accumulators.iter_mut(),
+            groups_values.iter(),
+            groups_filter_values.iter()
+        ) {
+            let opt_filter = match filter_opt {
+                Some(f) => Some(as_boolean_array(f)?),
+                None => None,
+            };
+            let size_pre = accumulator.size();
+            match self.mode {
+                AggregateMode::Partial | AggregateMode::Single => accumulator
+                    .update_batch(values, group_indices, opt_filter, total_num_groups),
+                AggregateMode::FinalPartitioned | AggregateMode::Final => {
+                    // note: the aggregation here is over states, not values, thus the merge
+                    accumulator.merge_batch(
+                        values,
+                        group_indices,
+                        opt_filter,
+                        total_num_groups,
+                    )
+                }
+            }?;
+            *allocated += accumulator.size().saturating_sub(size_pre);
+        }
+        Ok(())
+    }
+
     /// Perform group-by aggregation for the given [`RecordBatch`].
     ///
     /// If successful, this returns the additional number of bytes that were allocated during this process.
@@ -548,11 +780,31 @@ impl GroupedHashAggregateStream {
         let row_filter_values = evaluate_optional(&self.row_filter_expressions, &batch)?;
         let normal_filter_values =
             evaluate_optional(&self.normal_filter_expressions, &batch)?;
+        let groups_aggr_input_values =
+            evaluate_many(&self.groups_aggregate_expressions, &batch)?;
+        let groups_filter_values =
+            evaluate_optional(&self.groups_filter_expressions, &batch)?;
 
         let row_converter_size_pre = self.row_converter.size();
+        let mut group_indices = vec![];
         for group_values in &group_by_values {
-            let groups_with_rows =
-                self.update_group_state(group_values, &mut allocated)?;
+            let groups_with_rows = self.update_group_state(
+                group_values,
+                &mut group_indices,
+                &mut allocated,
+            )?;
+            if !self.groups_accumulators.is_empty() {
+                self.update_groups_accumulators(
+                    &group_indices,
+                    &groups_aggr_input_values,
+                    &groups_filter_values,
+                    &mut allocated,
+                )?;
+            }
+            if self.row_accumulators.is_empty() && self.normal_aggr_expr.is_empty() {
+                // only groups accumulators, which are already updated
+                continue;
+            }
             // Decide the accumulators update mode, use scalar value to update the accumulators when all of the conditions are meet:
             // 1) The aggregation mode is Partial or Single
             // 2) There is not normal aggregation expressions
@@ -566,6 +818,7 @@ impl GroupedHashAggregateStream {
                     &groups_with_rows,
                     &row_aggr_input_values,
                     &row_filter_values,
+                    &mut allocated,
                 )?;
             } else {
                 // Collect all indices + offsets based on keys in this vec
@@ -604,6 +857,200 @@ impl GroupedHashAggregateStream {
             .saturating_sub(row_converter_size_pre);
         Ok(allocated)
     }
+
+    /// Perform group-by aggregation for a [`RecordBatch`] of an input that is
+    /// ordered by the group keys.
+    ///
+    /// The rows of the last group in `batch` may continue in the next batch,
+    /// so they are held back in `carry`. Hence, all groups aggregated by this
+    /// call are complete.
+    fn group_aggregate_ordered_batch(&mut self, batch: RecordBatch) -> Result<usize> {
+        let batch = match self.carry.take() {
+            Some(carry) => concat_batches(&batch.schema(), [&carry, &batch])?,
+            None => batch,
+        };
+        let num_rows = batch.num_rows();
+        if num_rows == 0 {
+            return Ok(0);
+        }
+
+        let group_by_values = evaluate_group_by(&self.group_by, &batch)?;
+        let group_rows = self.row_converter.convert_columns(&group_by_values[0])?;
+        let last_group = group_rows.row(num_rows - 1);
+        let mut split = num_rows - 1;
+        while split > 0 && group_rows.row(split - 1) == last_group {
+            split -= 1;
+        }
+
+        self.carry = Some(batch.slice(split, num_rows - split));
+        if split == 0 {
+            return Ok(0);
+        }
+        self.group_aggregate_batch(batch.slice(0, split))
+    }
+
+    /// Grows the memory reservation by `allocated` bytes.
+    ///
+    /// If the reservation can not grow and spilling is possible, either
+    /// schedules the groups for early emission ([`AggregateMode::Partial`])
+    /// or spills them to disk.
+    fn reserve_or_spill(&mut self, allocated: usize) -> Result<()> {
+        let err = match self.aggr_state.reservation.try_grow(allocated) {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+        if !self.spill_state.can_spill || self.aggr_state.group_states.is_empty() {
+            return Err(err);
+        }
+
+        match self.mode {
+            AggregateMode::Partial => {
+                debug!("Emitting partial aggregation state early to free memory");
+                self.exec_state = ExecutionState::ProducingOutput;
+                Ok(())
+            }
+            AggregateMode::Final
+            | AggregateMode::FinalPartitioned
+            | AggregateMode::Single => self.spill(),
+        }
+    }
+
+    /// Aggregates the rows held back for an ordered input and, if any
+    /// groups were spilled before, starts merging the spill files.
+    fn finish_input(&mut self) -> Result<()> {
+        if let Some(carry) = self.carry.take() {
+            let allocated = self.group_aggregate_batch(carry)?;
+            self.aggr_state.reservation.try_grow(allocated)?;
+        }
+
+        if !self.spill_state.spills.is_empty() {
+            if !self.aggr_state.group_states.is_empty() {
+                self.spill()?;
+            }
+            self.spill_state.merged = Some(self.merge_spills()?);
+        }
+        Ok(())
+    }
+
+    /// Sorts all groups by their group keys, writes their intermediate state
+    /// to a new spill file and clears the in-memory aggregation state.
+    fn spill(&mut self) -> Result<()> {
+        let spillfile = self
+            .context
+            .runtime_env()
+            .disk_manager
+            .create_tmp_file("Grouped hash aggregation")?;
+
+        let mut group_states = std::mem::take(&mut self.aggr_state.group_states)
+            .into_iter()
+            .enumerate()
+            .collect::<Vec<_>>();
+        group_states.sort_unstable_by(|(_, a), (_, b)| {
+            a.group_by_values.row().cmp(&b.group_by_values.row())
+        });
+        let (order, group_states): (Vec<_>, Vec<_>) = group_states
+            .into_iter()
+            .map(|(group_idx, group_state)| (group_idx as u32, group_state))
+            .unzip();
+        self.aggr_state.group_states = group_states;
+
+        // the state of the groups accumulators is indexed by group, so it is
+        // reordered like the groups
+        if !self.groups_accumulators.is_empty() {
+            let order = UInt32Array::from(order);
+            let state = self
+                .evaluate_groups_accumulators(true)?
+                .iter()
+                .map(|array| Ok(take(array.as_ref(), &order, None)?))
+                .collect::<Result<Vec<_>>>()?;
+            self.groups_accumulator_output = Some(state);
+        }
+
+        let spill_schema = self.spill_state.spill_schema.clone();
+        let mut writer = IPCWriter::new(spillfile.path(), spill_schema.as_ref())?;
+        let num_groups = self.aggr_state.group_states.len();
+        let mut start = 0;
+        while start < num_groups {
+            let end = min(start + self.batch_size, num_groups);
+            let batch = self.build_batch(start..end, true, spill_schema.clone())?;
+            writer.write(&batch)?;
+            start = end;
+        }
+        writer.finish()?;
+
+        debug!(
+            "Spilled {} groups of GroupedHashAggregateStream[{}] to disk, memory released {}",
+            num_groups,
+            self.partition,
+            self.aggr_state.reservation.size(),
+        );
+        self.baseline_metrics
+            .record_spill(writer.num_bytes as usize);
+        self.spill_state.spills.push(spillfile);
+        self.clear_state();
+        Ok(())
+    }
+
+    /// Returns a stream that merges all spill files by their group keys and
+    /// aggregates the merged intermediate state to the final result.
+    fn merge_spills(&mut self) -> Result<SendableRecordBatchStream> {
+        let spill_schema = self.spill_state.spill_schema.clone();
+        let streams = self
+            .spill_state
+            .spills
+            .drain(..)
+            .map(|spill| read_spill_as_stream(spill, spill_schema.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        // the merge is an internal step of this stream, so its metrics are
+        // not reported
+        let tracking_metrics = MemTrackingMetrics::new(
+            &ExecutionPlanMetricsSet::new(),
+            self.context.memory_pool(),
+            self.partition,
+        );
+        let input = streaming_merge(
+            streams,
+            spill_schema.clone(),
+            &self.spill_state.spill_expr,
+            tracking_metrics,
+            self.batch_size,
+            None,
+        )?;
+
+        let group_by = PhysicalGroupBy::new_single(
+            self.spill_state
+                .spill_expr
+                .iter()
+                .zip(spill_schema.fields())
+                .map(|(sort_expr, field)| (sort_expr.expr.clone(), field.name().clone()))
+                .collect(),
+        );
+        let mut stream = GroupedHashAggregateStream::new(
+            AggregateMode::Final,
+            self.schema.clone(),
+            group_by,
+            self.aggr_expr.clone(),
+            vec![None; self.aggr_expr.len()],
+            input,
+            self.baseline_metrics.clone(),
+            self.batch_size,
+            self.scalar_update_factor,
+            self.context.clone(),
+            self.partition,
+        )?;
+        stream.ordered_input = true;
+        stream.spill_state.can_spill = false;
+        Ok(Box::pin(stream))
+    }
+
+    /// Clears all groups and releases their memory reservation
+    fn clear_state(&mut self) {
+        self.aggr_state.map = RawTable::with_capacity(0);
+        self.aggr_state.group_states = Vec::with_capacity(0);
+        self.aggr_state.reservation.free();
+        self.groups_accumulator_output = None;
+        self.row_group_skip_position = 0;
+    }
 }
 
 /// The state of all the groups
@@ -650,77 +1097,114 @@ impl GroupedHashAggregateStream {
             skip_items + self.batch_size,
             self.aggr_state.group_states.len(),
         );
-        let group_state_chunk = &self.aggr_state.group_states[skip_items..end_idx];
-
-        if group_state_chunk.is_empty() {
+        if skip_items == end_idx {
             let schema = self.schema.clone();
             return Ok(Some(RecordBatch::new_empty(schema)));
         }
 
+        let emit_state = matches!(self.mode, AggregateMode::Partial);
+        self.build_batch(skip_items..end_idx, emit_state, self.schema.clone())
+            .map(Some)
+    }
+
+    /// Evaluates the intermediate state (`emit_state`) or the final value of
+    /// all groups of the groups accumulators, one array per output field.
+    ///
+    /// This resets the groups accumulators to no groups.
+    fn evaluate_groups_accumulators(
+        &mut self,
+        emit_state: bool,
+    ) -> Result<Vec<ArrayRef>> {
+        let mut columns = vec![];
+        for accumulator in self.groups_accumulators.iter_mut() {
+            if emit_state {
+                columns.extend(accumulator.state()?);
+            } else {
+                columns.push(accumulator.evaluate()?);
+            }
+        }
+        Ok(columns)
+    }
+
+    /// Create a RecordBatch with the group keys and either the intermediate
+    /// state (`emit_state`) or the final value of the accumulators for the
+    /// groups in `range`.
+    fn build_batch(
+        &mut self,
+        range: Range<usize>,
+        emit_state: bool,
+        schema: SchemaRef,
+    ) -> Result<RecordBatch> {
+        if self.groups_accumulator_output.is_none() {
+            self.groups_accumulator_output =
+                Some(self.evaluate_groups_accumulators(emit_state)?);
+        }
+
+        let group_state_chunk = &self.aggr_state.group_states[range.clone()];
+        let indices = if emit_state {
+            &self.state_indices
+        } else {
+            &self.indices
+        };
+
         // Buffers for each distinct group (i.e. row accumulator memories)
         let mut state_buffers = group_state_chunk
             .iter()
             .map(|gs| gs.aggregation_buffer.clone())
             .collect::<Vec<_>>();
 
-        let output_fields = self.schema.fields();
+        let output_fields = schema.fields();
         // Store row accumulator results (either final output or intermediate state):
-        let row_columns = match self.mode {
-            AggregateMode::Partial => {
-                read_as_batch(&state_buffers, &self.row_aggr_schema)
-            }
-            AggregateMode::Final
-            | AggregateMode::FinalPartitioned
-            | AggregateMode::Single => {
-                let mut results = vec![];
-                for (idx, acc) in self.row_accumulators.iter().enumerate() {
-                    let mut state_accessor = RowAccessor::new(&self.row_aggr_schema);
-                    let current = state_buffers
-                        .iter_mut()
-                        .map(|buffer| {
-                            state_accessor.point_to(0, buffer);
-                            acc.evaluate(&state_accessor)
-                        })
-                        .collect::<Result<Vec<_>>>()?;
-                    // Get corresponding field for row accumulator
-                    let field = &output_fields[self.indices[1][idx].start];
-                    let result = if current.is_empty() {
-                        Ok(arrow::array::new_empty_array(field.data_type()))
-                    } else {
-                        let item = ScalarValue::iter_to_array(current)?;
-                        // cast output if needed (e.g. for types like Dictionary where
-                        // the intermediate GroupByScalar type was not the same as the
-                        // output
-                        cast(&item, field.data_type())
-                    }?;
-                    results.push(result);
-                }
-                results
+        let row_columns = if emit_state {
+            read_as_batch(&state_buffers, &self.row_aggr_schema)
+        } else {
+            let mut results = vec![];
+            for (idx, acc) in self.row_accumulators.iter().enumerate() {
+                let mut state_accessor = RowAccessor::new(&self.row_aggr_schema);
+                let current = state_buffers
+                    .iter_mut()
+                    .map(|buffer| {
+                        state_accessor.point_to(0, buffer);
+                        acc.evaluate(&state_accessor)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                // Get corresponding field for row accumulator
+                let field = &output_fields[indices[1][idx].start];
+                let result = if current.is_empty() {
+                    Ok(arrow::array::new_empty_array(field.data_type()))
+                } else {
+                    let item = ScalarValue::iter_to_array(current)?;
+                    // cast output if needed (e.g. for types like Dictionary where
+                    // the intermediate GroupByScalar type was not the same as the
+                    // output
+                    cast(&item, field.data_type())
+                }?;
+                results.push(result);
             }
+            results
         };
 
         // Store normal accumulator results (either final output or intermediate state):
         let mut columns = vec![];
-        for (idx, &Range { start, end }) in self.indices[0].iter().enumerate() {
+        for (idx, &Range { start, end }) in indices[0].iter().enumerate() {
             for (field_idx, field) in output_fields[start..end].iter().enumerate() {
-                let current = match self.mode {
-                    AggregateMode::Partial => ScalarValue::iter_to_array(
-                        group_state_chunk.iter().map(|group_state| {
+                let current = if emit_state {
+                    ScalarValue::iter_to_array(group_state_chunk.iter().map(
+                        |group_state| {
                             group_state.accumulator_set[idx]
                                 .state()
                                 .map(|v| v[field_idx].clone())
                                 .expect("Unexpected accumulator state in hash aggregate")
-                        }),
-                    ),
-                    AggregateMode::Final
-                    | AggregateMode::FinalPartitioned
-                    | AggregateMode::Single => ScalarValue::iter_to_array(
-                        group_state_chunk.iter().map(|group_state| {
+                        },
+                    ))
+                } else {
+                    ScalarValue::iter_to_array(group_state_chunk.iter().map(
+                        |group_state| {
                             group_state.accumulator_set[idx]
                                 .evaluate()
                                 .expect("Unexpected accumulator state in hash aggregate")
-                        }),
-                    ),
+                        },
+                    ))
                 }?;
                 // Cast output if needed (e.g. for types like Dictionary where
                 // the intermediate GroupByScalar type was not the same as the
@@ -730,6 +1214,21 @@ impl GroupedHashAggregateStream {
             }
         }
 
+        // Store groups accumulator results (either final output or intermediate state):
+        let groups_fields = indices[2]
+            .iter()
+            .flat_map(|&Range { start, end }| output_fields[start..end].iter());
+        let groups_columns = self
+            .groups_accumulator_output
+            .iter()
+            .flatten()
+            .zip(groups_fields)
+            .map(|(array, field)| {
+                let current = array.slice(range.start, range.len());
+                Ok(cast(&current, field.data_type())?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         // Stores the group by fields
         let group_buffers = group_state_chunk
             .iter()
@@ -737,9 +1236,8 @@ impl GroupedHashAggregateStream {
             .collect::<Vec<_>>();
         let mut output: Vec<ArrayRef> = self.row_converter.convert_rows(group_buffers)?;
 
-        // The size of the place occupied by row and normal accumulators
-        let extra: usize = self
-            .indices
+        // The size of the place occupied by all accumulators
+        let extra: usize = indices
             .iter()
             .flatten()
             .map(|Range { start, end }| end - start)
@@ -747,16 +1245,20 @@ impl GroupedHashAggregateStream {
         let empty_arr = new_null_array(&DataType::Null, 1);
         output.extend(std::iter::repeat(empty_arr).take(extra));
 
-        // Write results of both accumulator types to the corresponding location in
+        // Write results of all accumulator types to the corresponding location in
         // the output schema:
-        let results = [columns.into_iter(), row_columns.into_iter()];
+        let results = [
+            columns.into_iter(),
+            row_columns.into_iter(),
+            groups_columns.into_iter(),
+        ];
         for (outer, mut current) in results.into_iter().enumerate() {
-            for &Range { start, end } in self.indices[outer].iter() {
+            for &Range { start, end } in indices[outer].iter() {
                 for item in output.iter_mut().take(end).skip(start) {
                     *item = current.next().expect("Columns cannot be empty");
                 }
             }
         }
-        Ok(Some(RecordBatch::try_new(self.schema.clone(), output)?))
+        Ok(RecordBatch::try_new(schema, output)?)
     }
 }
@@ -24,6 +24,7 @@ mod csv;
 mod file_stream;
 mod json;
 mod parquet;
+mod write;
 
 pub(crate) use self::csv::plan_to_csv;
 pub use self::csv::{CsvConfig, CsvExec, CsvOpener};
@@ -40,6 +41,7 @@ use datafusion_physical_expr::PhysicalSortExpr;
 pub use file_stream::{FileOpenFuture, FileOpener, FileStream};
 pub(crate) use json::plan_to_json;
 pub use json::{JsonOpener, NdJsonExec};
+pub use write::{FileWriteConfig, FileWriteExec};
 
 use crate::datasource::{
     listing::{FileRange, PartitionedFile},
@@ -187,10 +189,16 @@ impl FileScanConfig {
                 }
             } else {
                 let partition_idx = idx - self.file_schema.fields().len();
+                // NULL values are read from the hive default partition
+                let nullable = self.file_groups.iter().flatten().any(|file| {
+                    file.partition_values
+                        .get(partition_idx)
+                        .map_or(false, ScalarValue::is_null)
+                });
                 table_fields.push(Field::new(
                     &self.table_partition_cols[partition_idx].0,
                     self.table_partition_cols[partition_idx].1.to_owned(),
-                    false,
+                    nullable,
                 ));
                 // TODO provide accurate stat for partition column (#1186)
                 table_cols_stats.push(ColumnStatistics::default())
@@ -596,6 +604,10 @@ fn create_output_array(
     len: usize,
 ) -> ArrayRef {
     if let ScalarValue::Dictionary(key_type, dict_val) = &val {
+        // The NULL partition value must be null in the keys, not in the values
+        if dict_val.is_null() {
+            return new_null_array(&val.get_datatype(), len);
+        }
         match key_type.as_ref() {
             DataType::Int8 => {
                 return create_dict_array(
@@ -669,6 +681,7 @@ fn create_output_array(
 }
 
 /// A single file or part of a file that should be read, along with its schema, statistics
+#[derive(Clone)]
 pub struct FileMeta {
     /// Path for the file (e.g. URL, filesystem path, etc)
     pub object_meta: ObjectMeta,
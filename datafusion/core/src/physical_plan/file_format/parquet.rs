@@ -59,6 +59,7 @@ use parquet::basic::{ConvertedType, LogicalType};
 use parquet::file::{metadata::ParquetMetaData, properties::WriterProperties};
 use parquet::schema::types::ColumnDescriptor;
 
+mod bloom_filter;
 mod metrics;
 mod page_filter;
 mod row_filter;
@@ -86,6 +87,9 @@ pub struct ParquetExec {
     /// Override for `Self::with_enable_page_index`. If None, uses
     /// values from base_config
     enable_page_index: Option<bool>,
+    /// Override for `Self::with_enable_bloom_filter`. If None, uses
+    /// values from base_config
+    enable_bloom_filter: Option<bool>,
     /// Base configuration for this scan
     base_config: FileScanConfig,
     projected_statistics: Statistics,
@@ -155,6 +159,7 @@ impl ParquetExec {
             pushdown_filters: None,
             reorder_filters: None,
             enable_page_index: None,
+            enable_bloom_filter: None,
             base_config,
             projected_schema,
             projected_statistics,
@@ -248,6 +253,20 @@ impl ParquetExec {
             .unwrap_or(config_options.execution.parquet.enable_page_index)
     }
 
+    /// If enabled, the reader will read the bloom filters of the columns
+    /// referenced by `col = literal` and `col IN (...)` predicates, and use
+    /// them to skip row groups that cannot contain matching values
+    pub fn with_enable_bloom_filter(mut self, enable_bloom_filter: bool) -> Self {
+        self.enable_bloom_filter = Some(enable_bloom_filter);
+        self
+    }
+
+    /// Return the value described in [`Self::with_enable_bloom_filter`]
+    fn enable_bloom_filter(&self, config_options: &ConfigOptions) -> bool {
+        self.enable_bloom_filter
+            .unwrap_or(config_options.execution.parquet.bloom_filter_enabled)
+    }
+
     /// Redistribute files across partitions according to their size
     pub fn get_repartitioned(
         &self,
@@ -392,6 +411,7 @@ impl ExecutionPlan for ParquetExec {
             pushdown_filters: self.pushdown_filters(config_options),
             reorder_filters: self.reorder_filters(config_options),
             enable_page_index: self.enable_page_index(config_options),
+            enable_bloom_filter: self.enable_bloom_filter(config_options),
         };
 
         let stream =
@@ -453,6 +473,7 @@ struct ParquetOpener {
     pushdown_filters: bool,
     reorder_filters: bool,
     enable_page_index: bool,
+    enable_bloom_filter: bool,
 }
 
 impl FileOpener for ParquetOpener {
@@ -465,6 +486,21 @@ impl FileOpener for ParquetOpener {
             &self.metrics,
         );
 
+        let enable_bloom_filter =
+            self.enable_bloom_filter && self.pruning_predicate.is_some();
+        // The stream builder owns its reader, so bloom filters are read
+        // through a reader of their own
+        let bloom_filter_reader = enable_bloom_filter
+            .then(|| {
+                self.parquet_file_reader_factory.create_reader(
+                    self.partition_index,
+                    file_meta.clone(),
+                    self.metadata_size_hint,
+                    &self.metrics,
+                )
+            })
+            .transpose()?;
+
         let reader: Box<dyn AsyncFileReader> =
             self.parquet_file_reader_factory.create_reader(
                 self.partition_index,
@@ -529,14 +565,31 @@ impl FileOpener for ParquetOpener {
 
             // Row group pruning: attempt to skip entire row_groups
             // using metadata on the row groups
-            let file_metadata = builder.metadata();
-            let row_groups = row_groups::prune_row_groups(
+            let file_metadata = builder.metadata().clone();
+            let mut row_groups = row_groups::prune_row_groups(
                 file_metadata.row_groups(),
                 file_range,
                 pruning_predicate.as_ref().map(|p| p.as_ref()),
                 &file_metrics,
             );
 
+            // Bloom filter pruning: skip row groups whose bloom filters
+            // rule out all the values an equality predicate looks for
+            if let (Some(mut reader), Some(predicate)) =
+                (bloom_filter_reader, &pruning_predicate)
+            {
+                if !row_groups.is_empty() {
+                    row_groups = row_groups::prune_row_groups_by_bloom_filters(
+                        reader.as_mut(),
+                        file_metadata.as_ref(),
+                        row_groups,
+                        predicate.as_ref(),
+                        &file_metrics,
+                    )
+                    .await;
+                }
+            }
+
             // page index pruning: if all data on individual pages can
             // be ruled using page metadata, rows from other columns
             // with that range can be skipped as well
@@ -704,7 +757,7 @@ pub async fn plan_to_parquet(
                     .map(|batch| {
                         writer.write(&batch?).map_err(DataFusionError::ParquetError)
                     })
-                    .try_collect()
+                    .try_collect::<()>()
                     .await
                     .map_err(DataFusionError::from)?;
 
@@ -769,7 +822,7 @@ mod tests {
     // See also `parquet_exec` integration test
 
     use super::*;
-    use crate::datasource::file_format::parquet::test_util::store_parquet;
+    use crate::datasource::file_format::parquet::test_util::store_parquet_with_properties;
     use crate::datasource::file_format::test_util::scan_format;
     use crate::datasource::listing::{FileRange, PartitionedFile};
     use crate::datasource::object_store::ObjectStoreUrl;
@@ -783,7 +836,7 @@ mod tests {
         datasource::file_format::{parquet::ParquetFormat, FileFormat},
         physical_plan::collect,
     };
-    use arrow::array::{ArrayRef, Float32Array, Int32Array};
+    use arrow::array::{ArrayRef, Float32Array, Float64Array, Int32Array, StructArray};
     use arrow::datatypes::Schema;
     use arrow::record_batch::RecordBatch;
     use arrow::{
@@ -821,6 +874,7 @@ mod tests {
         predicate: Option<Expr>,
         pushdown_predicate: bool,
         page_index_predicate: bool,
+        bloom_filter: bool,
     }
 
     impl RoundTrip {
@@ -853,6 +907,11 @@ mod tests {
             self
         }
 
+        fn with_bloom_filter(mut self) -> Self {
+            self.bloom_filter = true;
+            self
+        }
+
         /// run the test, returning only the resulting RecordBatches
         async fn round_trip_to_batches(
             self,
@@ -869,6 +928,7 @@ mod tests {
                 predicate,
                 pushdown_predicate,
                 page_index_predicate,
+                bloom_filter,
             } = self;
 
             let file_schema = match schema {
@@ -884,7 +944,12 @@ mod tests {
             // If testing with page_index_predicate, write parquet
             // files with multiple pages
             let multi_page = page_index_predicate;
-            let (meta, _files) = store_parquet(batches, multi_page).await.unwrap();
+            let (meta, _files) =
+                store_parquet_with_properties(batches, multi_page, |builder| {
+                    builder.set_bloom_filter_enabled(bloom_filter)
+                })
+                .await
+                .unwrap();
             let file_groups = meta.into_iter().map(Into::into).collect();
 
             // set up predicate (this is normally done by a layer higher up)
@@ -917,6 +982,10 @@ mod tests {
                 parquet_exec = parquet_exec.with_enable_page_index(true);
             }
 
+            if bloom_filter {
+                parquet_exec = parquet_exec.with_enable_bloom_filter(true);
+            }
+
             let session_ctx = SessionContext::new();
             let task_ctx = session_ctx.task_ctx();
             let parquet_exec = Arc::new(parquet_exec);
@@ -1781,6 +1850,101 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn parquet_bloom_filter_exec_metrics() {
+        // the min/max statistics of each file cover all the values below
+        let batches = ["mmm", "nnn", "ooo"]
+            .into_iter()
+            .map(|middle| {
+                let c1: ArrayRef = Arc::new(StringArray::from(vec![
+                    Some("aaa"),
+                    Some(middle),
+                    Some("zzz"),
+                ]));
+                create_batch(vec![("c1", c1)])
+            })
+            .collect::<Vec<_>>();
+
+        let rt = RoundTrip::new()
+            .with_predicate(col("c1").eq(lit("nnn")))
+            .with_bloom_filter()
+            .round_trip(batches.clone())
+            .await;
+        let metrics = rt.parquet_exec.metrics().unwrap();
+        assert_eq!(rt.batches.unwrap().len(), 1);
+        assert_eq!(get_value(&metrics, "row_groups_pruned"), 0);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 2);
+
+        let filter = col("c1")
+            .in_list(vec![lit("mmm"), lit("ooo")], false)
+            .and(col("c1").not_eq(lit("bbb")));
+        let rt = RoundTrip::new()
+            .with_predicate(filter)
+            .with_bloom_filter()
+            .round_trip(batches.clone())
+            .await;
+        let metrics = rt.parquet_exec.metrics().unwrap();
+        assert_eq!(rt.batches.unwrap().len(), 2);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 1);
+
+        // files written without bloom filters are not pruned
+        let rt = RoundTrip::new()
+            .with_predicate(col("c1").eq(lit("nnn")))
+            .round_trip(batches)
+            .await;
+        let metrics = rt.parquet_exec.metrics().unwrap();
+        assert_eq!(rt.batches.unwrap().len(), 3);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 0);
+    }
+
+    #[tokio::test]
+    async fn parquet_bloom_filter_nested_column() {
+        // The leaf `s.id` of the struct column has the same name as the top
+        // level column `id`, and comes first in the parquet schema
+        let nested: ArrayRef = Arc::new(StructArray::from(vec![(
+            Arc::new(Field::new("id", DataType::Int32, true)),
+            Arc::new(Int32Array::from(vec![10, 20, 30])) as ArrayRef,
+        )]));
+        let id: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let batch = create_batch(vec![("s", nested), ("id", id)]);
+
+        let rt = RoundTrip::new()
+            .with_predicate(col("id").eq(lit(1)))
+            .with_bloom_filter()
+            .round_trip(vec![batch])
+            .await;
+        let metrics = rt.parquet_exec.metrics().unwrap();
+        let batches = rt.batches.unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 3);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 0);
+    }
+
+    #[tokio::test]
+    async fn parquet_bloom_filter_float_zero() {
+        let c1: ArrayRef = Arc::new(Float64Array::from(vec![-0.0, 1.0, f64::NAN]));
+        let batch = create_batch(vec![("c1", c1)]);
+
+        // 0.0 compares equal to the -0.0 stored in the file
+        let rt = RoundTrip::new()
+            .with_predicate(col("c1").eq(lit(0.0)))
+            .with_bloom_filter()
+            .round_trip(vec![batch.clone()])
+            .await;
+        let metrics = rt.parquet_exec.metrics().unwrap();
+        assert_eq!(rt.batches.unwrap().len(), 1);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 0);
+
+        // other values within the min/max statistics are still pruned
+        let rt = RoundTrip::new()
+            .with_predicate(col("c1").eq(lit(0.5)))
+            .with_bloom_filter()
+            .round_trip(vec![batch])
+            .await;
+        let metrics = rt.parquet_exec.metrics().unwrap();
+        assert_eq!(rt.batches.unwrap().len(), 0);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 1);
+    }
+
     #[tokio::test]
     async fn parquet_exec_metrics() {
         let c1: ArrayRef = Arc::new(StringArray::from(vec![
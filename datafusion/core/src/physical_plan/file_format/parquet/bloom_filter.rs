@@ -0,0 +1,165 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reading the split block bloom filters (SBBF) of parquet column chunks
+//! through an [`AsyncFileReader`]
+
+use std::hash::Hasher;
+
+use bytes::Buf;
+use parquet::arrow::async_reader::AsyncFileReader;
+use parquet::data_type::AsBytes;
+use parquet::errors::ParquetError;
+use parquet::file::metadata::ColumnChunkMetaData;
+use parquet::format::{
+    BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader,
+};
+use thrift::protocol::{TCompactInputProtocol, TSerializable};
+use twox_hash::XxHash64;
+
+use crate::error::Result;
+
+/// Salt as defined in the [spec](https://github.com/apache/parquet-format/blob/master/BloomFilter.md#technical-approach)
+const SALT: [u32; 8] = [
+    0x47b6137b_u32,
+    0x44974d91_u32,
+    0x8824ad5b_u32,
+    0xa2b7289d_u32,
+    0x705495c7_u32,
+    0x2df1424b_u32,
+    0x9efc4947_u32,
+    0x5c6bfb31_u32,
+];
+
+/// The header is a thrift struct of four fields, each encoded in at most
+/// five bytes
+const HEADER_SIZE_ESTIMATE: usize = 20;
+
+/// Each block is 256 bits, broken up into eight 32 bit words
+type Block = [u32; 8];
+
+/// A split block bloom filter of a single column chunk
+#[derive(Debug, Clone)]
+pub(crate) struct BloomFilter {
+    blocks: Vec<Block>,
+}
+
+impl BloomFilter {
+    /// Creates a bloom filter from its little endian encoded bitset
+    pub(crate) fn new(bitset: &[u8]) -> Self {
+        let blocks = bitset
+            .chunks_exact(32)
+            .map(|chunk| {
+                let mut block = [0_u32; 8];
+                for (word, bytes) in block.iter_mut().zip(chunk.chunks_exact(4)) {
+                    *word = u32::from_le_bytes(bytes.try_into().unwrap());
+                }
+                block
+            })
+            .collect();
+        Self { blocks }
+    }
+
+    /// Reads the bloom filter of `column`, returning `None` if it has none
+    pub(crate) async fn read<R: AsyncFileReader + ?Sized>(
+        reader: &mut R,
+        column: &ColumnChunkMetaData,
+    ) -> Result<Option<Self>> {
+        let offset = match column.bloom_filter_offset() {
+            Some(offset) => usize::try_from(offset).map_err(|_| {
+                ParquetError::General("Bloom filter offset is invalid".to_string())
+            })?,
+            None => return Ok(None),
+        };
+
+        let buffer = reader
+            .get_bytes(offset..offset + HEADER_SIZE_ESTIMATE)
+            .await?;
+        let mut buf_reader = buffer.reader();
+        let header = {
+            let mut prot = TCompactInputProtocol::new(&mut buf_reader);
+            BloomFilterHeader::read_from_in_protocol(&mut prot).map_err(|e| {
+                ParquetError::General(format!("Could not read bloom filter header: {e}"))
+            })?
+        };
+        let header_length = HEADER_SIZE_ESTIMATE - buf_reader.into_inner().remaining();
+
+        // these matches exist to future proof the singleton enums
+        match header.algorithm {
+            BloomFilterAlgorithm::BLOCK(_) => {}
+        }
+        match header.compression {
+            BloomFilterCompression::UNCOMPRESSED(_) => {}
+        }
+        match header.hash {
+            BloomFilterHash::XXHASH(_) => {}
+        }
+
+        let length = usize::try_from(header.num_bytes)
+            .ok()
+            .filter(|length| *length > 0 && length % 32 == 0)
+            .ok_or_else(|| {
+                ParquetError::General(format!(
+                    "Bloom filter length {} is invalid",
+                    header.num_bytes
+                ))
+            })?;
+        let bitset_offset = offset + header_length;
+        let bitset = reader
+            .get_bytes(bitset_offset..bitset_offset + length)
+            .await?;
+        Ok(Some(Self::new(&bitset)))
+    }
+
+    /// Returns false if `value` was definitely not inserted into the filter
+    pub(crate) fn check<T: AsBytes>(&self, value: &T) -> bool {
+        // per spec, values are hashed by xxHash with seed 0
+        let mut hasher = XxHash64::with_seed(0);
+        hasher.write(value.as_bytes());
+        let hash = hasher.finish();
+
+        let block_index =
+            (((hash >> 32).saturating_mul(self.blocks.len() as u64)) >> 32) as usize;
+        let block = &self.blocks[block_index];
+        let key = hash as u32;
+        block.iter().zip(SALT).all(|(word, salt)| {
+            let mask = 1 << (key.wrapping_mul(salt) >> 27);
+            word & mask != 0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_values_written_by_parquet_mr() {
+        // bloom filter produced by parquet-mr/spark for a column of the
+        // strings "a0" to "a9"
+        let bitset: &[u8] = &[
+            200, 1, 80, 20, 64, 68, 8, 109, 6, 37, 4, 67, 144, 80, 96, 32, 8, 132, 43,
+            33, 0, 5, 99, 65, 2, 0, 224, 44, 64, 78, 96, 4,
+        ];
+        let bloom_filter = BloomFilter::new(bitset);
+        for a in 0..10i64 {
+            let value = format!("a{a}");
+            assert!(bloom_filter.check(&value.as_str()));
+        }
+        assert!(!bloom_filter.check(&"b0"));
+    }
+}
@@ -45,6 +45,7 @@ use crate::physical_plan::file_format::parquet::{
 };
 
 use super::metrics::ParquetFileMetrics;
+use super::row_groups::top_level_column_name;
 
 /// A [`PagePruningPredicate`] provides the ability to construct a [`RowSelection`]
 /// based on parquet page level statistics, if any
@@ -278,7 +279,7 @@ fn find_column_index(
         .columns()
         .iter()
         .enumerate()
-        .find(|(_idx, c)| c.column_descr().name() == column.name())
+        .find(|(_idx, c)| top_level_column_name(c) == Some(column.name()))
         .map(|(idx, _c)| idx);
 
     if col_idx.is_none() {
@@ -15,16 +15,28 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use arrow::{
     array::ArrayRef,
     datatypes::{DataType, Schema},
 };
 use datafusion_common::Column;
 use datafusion_common::ScalarValue;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{
+    self as phys_expr, BinaryExpr, InListExpr, Literal,
+};
+use datafusion_physical_expr::PhysicalExpr;
 use log::debug;
 
+use parquet::arrow::async_reader::AsyncFileReader;
+use parquet::basic::Type as PhysicalType;
+use parquet::data_type::ByteArray;
 use parquet::file::{
-    metadata::RowGroupMetaData, statistics::Statistics as ParquetStatistics,
+    metadata::{ColumnChunkMetaData, ParquetMetaData, RowGroupMetaData},
+    statistics::Statistics as ParquetStatistics,
 };
 
 use crate::physical_plan::file_format::parquet::{
@@ -35,6 +47,7 @@ use crate::{
     physical_optimizer::pruning::{PruningPredicate, PruningStatistics},
 };
 
+use super::bloom_filter::BloomFilter;
 use super::ParquetFileMetrics;
 
 /// Returns a vector of indexes into `groups` which should be scanned.
@@ -92,6 +105,235 @@ pub(crate) fn prune_row_groups(
     filtered
 }
 
+/// Returns the subset of `row_groups` which should be scanned after
+/// consulting the bloom filters of the columns that `predicate` compares
+/// with literals by `=` or `IN`.
+///
+/// Row groups whose bloom filters cannot be read are kept.
+pub(crate) async fn prune_row_groups_by_bloom_filters<R: AsyncFileReader + ?Sized>(
+    reader: &mut R,
+    metadata: &ParquetMetaData,
+    row_groups: Vec<usize>,
+    predicate: &PruningPredicate,
+    metrics: &ParquetFileMetrics,
+) -> Vec<usize> {
+    let bloom_predicate = match BloomFilterPredicate::try_new(predicate.orig_expr()) {
+        Some(bloom_predicate) => bloom_predicate,
+        None => return row_groups,
+    };
+    let mut column_names = vec![];
+    bloom_predicate.column_names(&mut column_names);
+
+    let _timer = metrics.bloom_filter_eval_time.timer();
+    let mut filtered = Vec::with_capacity(row_groups.len());
+    for idx in row_groups {
+        let mut filters = HashMap::with_capacity(column_names.len());
+        for column in metadata.row_group(idx).columns() {
+            let name = match top_level_column_name(column) {
+                Some(name) => name,
+                None => continue,
+            };
+            if !column_names.contains(&name) || filters.contains_key(name) {
+                continue;
+            }
+            match BloomFilter::read(reader, column).await {
+                Ok(Some(sbbf)) => {
+                    filters.insert(name.to_string(), (sbbf, column.column_type()));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    debug!("Error reading bloom filter of column '{name}': {e}");
+                    metrics.predicate_evaluation_errors.add(1);
+                }
+            }
+        }
+
+        if bloom_predicate.may_contain(&filters) {
+            filtered.push(idx);
+        } else {
+            metrics.row_groups_pruned_bloom_filter.add(1);
+        }
+    }
+    filtered
+}
+
+/// Returns the name of `column` if it is a top level column.
+///
+/// Predicates only reference top level columns, whose path has a single
+/// part. Leaves of nested columns, e.g. `s.id`, may have the same name as a
+/// top level column.
+pub(crate) fn top_level_column_name(column: &ColumnChunkMetaData) -> Option<&str> {
+    match column.column_descr().path().parts() {
+        [name] => Some(name.as_str()),
+        _ => None,
+    }
+}
+
+/// The part of a predicate that can be evaluated against bloom filters
+#[derive(Debug)]
+enum BloomFilterPredicate {
+    /// The column is equal to one of the values
+    In {
+        column: String,
+        values: Vec<ScalarValue>,
+    },
+    And(Box<BloomFilterPredicate>, Box<BloomFilterPredicate>),
+    Or(Box<BloomFilterPredicate>, Box<BloomFilterPredicate>),
+}
+
+impl BloomFilterPredicate {
+    /// Extracts the `col = literal` and `col IN (...)` terms of `expr`,
+    /// returning `None` if there are none that can prune row groups
+    fn try_new(expr: &Arc<dyn PhysicalExpr>) -> Option<Self> {
+        let any = expr.as_any();
+        if let Some(binary) = any.downcast_ref::<BinaryExpr>() {
+            match binary.op() {
+                Operator::Eq => {
+                    let (column, literal) = match (
+                        binary.left().as_any().downcast_ref::<phys_expr::Column>(),
+                        binary.right().as_any().downcast_ref::<Literal>(),
+                        binary.right().as_any().downcast_ref::<phys_expr::Column>(),
+                        binary.left().as_any().downcast_ref::<Literal>(),
+                    ) {
+                        (Some(column), Some(literal), _, _)
+                        | (_, _, Some(column), Some(literal)) => (column, literal),
+                        _ => return None,
+                    };
+                    Some(Self::In {
+                        column: column.name().to_string(),
+                        values: vec![literal.value().clone()],
+                    })
+                }
+                // either side alone restricts the matching row groups
+                Operator::And => {
+                    match (Self::try_new(binary.left()), Self::try_new(binary.right())) {
+                        (Some(left), Some(right)) => {
+                            Some(Self::And(Box::new(left), Box::new(right)))
+                        }
+                        (Some(side), None) | (None, Some(side)) => Some(side),
+                        (None, None) => None,
+                    }
+                }
+                Operator::Or => {
+                    let left = Self::try_new(binary.left())?;
+                    let right = Self::try_new(binary.right())?;
+                    Some(Self::Or(Box::new(left), Box::new(right)))
+                }
+                _ => None,
+            }
+        } else if let Some(in_list) = any.downcast_ref::<InListExpr>() {
+            if in_list.negated() {
+                return None;
+            }
+            let column = in_list
+                .expr()
+                .as_any()
+                .downcast_ref::<phys_expr::Column>()?;
+            let values = in_list
+                .list()
+                .iter()
+                .map(|value| {
+                    value
+                        .as_any()
+                        .downcast_ref::<Literal>()
+                        .map(|literal| literal.value().clone())
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(Self::In {
+                column: column.name().to_string(),
+                values,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Appends the names of the columns referenced by this predicate
+    fn column_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Self::In { column, .. } => names.push(column),
+            Self::And(left, right) | Self::Or(left, right) => {
+                left.column_names(names);
+                right.column_names(names);
+            }
+        }
+    }
+
+    /// Returns false if the bloom filters rule out any row matching this
+    /// predicate. Columns without a bloom filter may contain any value.
+    fn may_contain(
+        &self,
+        filters: &HashMap<String, (BloomFilter, PhysicalType)>,
+    ) -> bool {
+        match self {
+            Self::In { column, values } => match filters.get(column) {
+                Some((sbbf, physical_type)) => values
+                    .iter()
+                    .any(|value| check_bloom_filter(sbbf, *physical_type, value)),
+                None => true,
+            },
+            Self::And(left, right) => {
+                left.may_contain(filters) && right.may_contain(filters)
+            }
+            Self::Or(left, right) => {
+                left.may_contain(filters) || right.may_contain(filters)
+            }
+        }
+    }
+}
+
+/// Returns false if the bloom filter of a column with the given physical type
+/// definitely does not contain `value`.
+///
+/// Values are hashed in their plain encoding, so only values whose type maps
+/// directly to the physical type of the column are checked. Floats that
+/// compare equal to other bit patterns are checked in all their encodings:
+/// a zero is checked as both `0.0` and `-0.0`, and NaNs are not checked.
+fn check_bloom_filter(
+    sbbf: &BloomFilter,
+    physical_type: PhysicalType,
+    value: &ScalarValue,
+) -> bool {
+    match (value, physical_type) {
+        (ScalarValue::Utf8(Some(v)), PhysicalType::BYTE_ARRAY)
+        | (ScalarValue::LargeUtf8(Some(v)), PhysicalType::BYTE_ARRAY) => {
+            sbbf.check(&ByteArray::from(v.as_str()))
+        }
+        (ScalarValue::Binary(Some(v)), PhysicalType::BYTE_ARRAY)
+        | (ScalarValue::LargeBinary(Some(v)), PhysicalType::BYTE_ARRAY) => {
+            sbbf.check(&ByteArray::from(v.clone()))
+        }
+        (ScalarValue::Int8(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::Int16(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::Int32(Some(v)), PhysicalType::INT32)
+        | (ScalarValue::Date32(Some(v)), PhysicalType::INT32) => sbbf.check(v),
+        (ScalarValue::UInt8(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::UInt16(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::UInt32(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::Int64(Some(v)), PhysicalType::INT64) => sbbf.check(v),
+        (ScalarValue::UInt64(Some(v)), PhysicalType::INT64) => sbbf.check(&(*v as i64)),
+        (ScalarValue::Float32(Some(v)), PhysicalType::FLOAT) => {
+            if v.is_nan() {
+                true
+            } else if *v == 0.0 {
+                sbbf.check(&0.0f32) || sbbf.check(&-0.0f32)
+            } else {
+                sbbf.check(v)
+            }
+        }
+        (ScalarValue::Float64(Some(v)), PhysicalType::DOUBLE) => {
+            if v.is_nan() {
+                true
+            } else if *v == 0.0 {
+                sbbf.check(&0.0f64) || sbbf.check(&-0.0f64)
+            } else {
+                sbbf.check(v)
+            }
+        }
+        _ => true,
+    }
+}
+
 /// Wraps parquet statistics in a way
 /// that implements [`PruningStatistics`]
 struct RowGroupPruningStatistics<'a> {
@@ -191,7 +433,7 @@ macro_rules! get_min_max_values {
         $self.row_group_metadata
             .columns()
             .iter()
-            .find(|c| c.column_descr().name() == &$column.name)
+            .find(|c| top_level_column_name(c) == Some($column.name.as_str()))
             .and_then(|c| if c.statistics().is_some() {Some((c.statistics().unwrap(), c.column_descr()))} else {None})
             .map(|(stats, column_descr)|
                 {
@@ -213,7 +455,7 @@ macro_rules! get_null_count_values {
                 .row_group_metadata
                 .columns()
                 .iter()
-                .find(|c| c.column_descr().name() == &$column.name)
+                .find(|c| top_level_column_name(c) == Some($column.name.as_str()))
             {
                 col.statistics().map(|s| s.null_count())
             } else {
@@ -841,6 +1083,50 @@ mod tests {
         Arc::new(SchemaDescriptor::new(Arc::new(schema)))
     }
 
+    #[test]
+    fn bloom_filter_predicate() {
+        let schema = Schema::new(vec![
+            Field::new("c1", DataType::Utf8, false),
+            Field::new("c2", DataType::Int32, false),
+        ]);
+        let try_new = |expr: Expr| {
+            BloomFilterPredicate::try_new(&logical2physical(&expr, &schema))
+                .map(|p| format!("{p:?}"))
+        };
+
+        assert_eq!(
+            try_new(lit(1).eq(col("c2"))).unwrap(),
+            "In { column: \"c2\", values: [Int32(1)] }"
+        );
+        assert_eq!(
+            try_new(col("c1").eq(lit("a")).and(col("c2").gt(lit(1)))).unwrap(),
+            "In { column: \"c1\", values: [Utf8(\"a\")] }"
+        );
+        assert_eq!(
+            try_new(col("c2").in_list(vec![lit(1), lit(2)], false).or(col("c1").eq(lit("a")))).unwrap(),
+            "Or(In { column: \"c2\", values: [Int32(1), Int32(2)] }, In { column: \"c1\", values: [Utf8(\"a\")] })"
+        );
+
+        // neither side of an OR can be ignored, and negations cannot prune
+        assert!(try_new(col("c1").eq(lit("a")).or(col("c2").gt(lit(1)))).is_none());
+        assert!(try_new(col("c2").in_list(vec![lit(1)], true)).is_none());
+        assert!(try_new(col("c2").not_eq(lit(1))).is_none());
+    }
+
+    #[test]
+    fn check_bloom_filter_nan() {
+        // an empty bloom filter contains no values
+        let bloom_filter = BloomFilter::new(&[0; 32]);
+        let check = |value: ScalarValue| {
+            check_bloom_filter(&bloom_filter, PhysicalType::DOUBLE, &value)
+        };
+
+        // NaNs have many bit patterns, so they are never pruned
+        assert!(check(ScalarValue::Float64(Some(f64::NAN))));
+        assert!(!check(ScalarValue::Float64(Some(2.0))));
+        assert!(!check(ScalarValue::Float64(Some(0.0))));
+    }
+
     fn parquet_file_metrics() -> ParquetFileMetrics {
         let metrics = Arc::new(ExecutionPlanMetricsSet::new());
         ParquetFileMetrics::new(0, "file.parquet", &metrics)
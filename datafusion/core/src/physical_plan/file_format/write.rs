@@ -0,0 +1,381 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution plan for writing record batches into new files
+
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::array::UInt32Array;
+use arrow::compute::take;
+use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::array_value_to_string;
+use datafusion_physical_expr::PhysicalSortExpr;
+use futures::StreamExt;
+use log::warn;
+use object_store::path::Path;
+use object_store::{MultipartId, ObjectStore};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use uuid::Uuid;
+
+use crate::datasource::file_format::file_type::FileCompressionType;
+use crate::datasource::file_format::{BatchSerializer, FileFormat};
+use crate::datasource::listing::{escape_partition_value, HIVE_DEFAULT_PARTITION};
+use crate::datasource::object_store::ObjectStoreUrl;
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::{
+    DisplayFormatType, Distribution, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+
+/// The base configurations to provide when creating a [`FileWriteExec`]
+#[derive(Debug, Clone)]
+pub struct FileWriteConfig {
+    /// Object store URL, used to get an [`ObjectStore`] instance from
+    /// [`RuntimeEnv::object_store`]
+    ///
+    /// [`RuntimeEnv::object_store`]: crate::execution::runtime_env::RuntimeEnv::object_store
+    pub object_store_url: ObjectStoreUrl,
+    /// Directory below which the new files are written
+    pub table_path: Path,
+    /// Extension of the new files, including the leading dot
+    pub file_extension: String,
+    /// Name of the single file to write below `table_path`, instead of
+    /// new uniquely named files. The input must have a single partition and
+    /// there must be no partition columns.
+    pub file_name: Option<String>,
+    /// Format of the new files
+    pub format: Arc<dyn FileFormat>,
+    /// Compression applied to the whole content of the new files
+    pub file_compression_type: FileCompressionType,
+    /// Schema of the new files, i.e. the input schema without the
+    /// partition columns
+    pub file_schema: SchemaRef,
+    /// Names of the hive style partition columns, which are the last
+    /// columns of the input
+    pub table_partition_cols: Vec<String>,
+}
+
+/// Execution plan for writing record batches into new files of a
+/// [`FileFormat`], as done when inserting into a [`ListingTable`].
+///
+/// Every input partition writes one new file into each hive style
+/// `col=value/` partition directory its rows belong to. The files are
+/// uploaded incrementally, and only become visible once they are complete.
+///
+/// [`ListingTable`]: crate::datasource::listing::ListingTable
+pub struct FileWriteExec {
+    /// Input plan that produces the record batches to be written.
+    input: Arc<dyn ExecutionPlan>,
+    /// Where and how to write the files
+    config: FileWriteConfig,
+    /// Called whenever an input partition has been written, even if writing
+    /// failed, as some files may already be complete
+    on_written: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl fmt::Debug for FileWriteExec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FileWriteExec: {:?}", self.config)
+    }
+}
+
+impl FileWriteExec {
+    /// Create a new execution plan writing the output of `input` as
+    /// configured by `config`
+    pub fn new(input: Arc<dyn ExecutionPlan>, config: FileWriteConfig) -> Self {
+        Self {
+            input,
+            config,
+            on_written: None,
+        }
+    }
+
+    /// Sets a function to call whenever an input partition has been
+    /// written, e.g. to invalidate state which depends on the files
+    pub fn with_on_written(mut self, on_written: Arc<dyn Fn() + Send + Sync>) -> Self {
+        self.on_written = Some(on_written);
+        self
+    }
+
+    /// Where and how the files are written
+    pub fn config(&self) -> &FileWriteConfig {
+        &self.config
+    }
+}
+
+impl ExecutionPlan for FileWriteExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(
+            self.input.output_partitioning().partition_count(),
+        )
+    }
+
+    fn benefits_from_input_partitioning(&self) -> bool {
+        false
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        // a single file is written from a single partition
+        if self.config.file_name.is_some() {
+            vec![Distribution::SinglePartition]
+        } else {
+            vec![Distribution::UnspecifiedDistribution]
+        }
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(FileWriteExec {
+            input: children[0].clone(),
+            config: self.config.clone(),
+            on_written: self.on_written.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let store = context
+            .runtime_env()
+            .object_store(&self.config.object_store_url)?;
+        let input = self.input.execute(partition, context)?;
+        let config = self.config.clone();
+        let on_written = self.on_written.clone();
+
+        // The stream produces no batches, only a possible error
+        let stream = futures::stream::once(async move {
+            let result = write_files(input, store, config).await;
+            if let Some(on_written) = on_written {
+                on_written();
+            }
+            result
+        })
+        .filter_map(|result| async move { result.err().map(Err) });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "FileWriteExec: path={}, format={:?}",
+                    self.config.table_path, self.config.format
+                )?;
+                if !self.config.table_partition_cols.is_empty() {
+                    write!(
+                        f,
+                        ", partition_cols=[{}]",
+                        self.config.table_partition_cols.join(", ")
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// A new file being uploaded
+struct FileWriter {
+    location: Path,
+    multipart_id: MultipartId,
+    writer: Box<dyn AsyncWrite + Send + Unpin>,
+    serializer: Box<dyn BatchSerializer>,
+}
+
+/// Writes all batches of `input` into new files, aborting the uploads of
+/// the unfinished files if writing fails
+async fn write_files(
+    mut input: SendableRecordBatchStream,
+    store: Arc<dyn ObjectStore>,
+    config: FileWriteConfig,
+) -> Result<()> {
+    let mut writers = HashMap::new();
+    let mut result = write_batches(&mut input, &store, &config, &mut writers).await;
+
+    for (_, writer) in writers {
+        let FileWriter {
+            location,
+            multipart_id,
+            mut writer,
+            serializer,
+        } = writer;
+        if result.is_ok() {
+            result = finish_file(serializer, &mut writer).await;
+            if result.is_ok() {
+                continue;
+            }
+        }
+        abort_file(&store, &location, &multipart_id).await;
+    }
+    result
+}
+
+/// Aborts the upload of a file after an error, which is returned instead of
+/// any failure to abort
+async fn abort_file(store: &Arc<dyn ObjectStore>, location: &Path, id: &MultipartId) {
+    if let Err(e) = store.abort_multipart(location, id).await {
+        warn!("Failed to abort the upload of {location}: {e}");
+    }
+}
+
+/// Writes the remaining bytes of a file and completes its upload
+async fn finish_file(
+    serializer: Box<dyn BatchSerializer>,
+    writer: &mut Box<dyn AsyncWrite + Send + Unpin>,
+) -> Result<()> {
+    let bytes = serializer.finish()?;
+    writer.write_all(&bytes).await?;
+    writer.shutdown().await?;
+    Ok(())
+}
+
+async fn write_batches(
+    input: &mut SendableRecordBatchStream,
+    store: &Arc<dyn ObjectStore>,
+    config: &FileWriteConfig,
+    writers: &mut HashMap<String, FileWriter>,
+) -> Result<()> {
+    // The same file name is used in all partition directories
+    let file_name = match &config.file_name {
+        Some(file_name) => file_name.clone(),
+        None => format!("{}{}", Uuid::new_v4(), config.file_extension),
+    };
+
+    while let Some(batch) = input.next().await {
+        for (partition_dir, batch) in split_by_partition(&batch?, config)? {
+            let writer = match writers.entry(partition_dir) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let serializer = config
+                        .format
+                        .create_serializer(config.file_schema.clone())?;
+                    // The partition directories are already escaped, parse
+                    // them as is instead of percent-encoding them again
+                    let location = Path::parse(format!(
+                        "{}/{}{}",
+                        config.table_path,
+                        entry.key(),
+                        file_name
+                    ))
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                    let (multipart_id, writer) = store.put_multipart(&location).await?;
+                    let writer =
+                        match config.file_compression_type.convert_async_writer(writer) {
+                            Ok(writer) => writer,
+                            Err(e) => {
+                                abort_file(store, &location, &multipart_id).await;
+                                return Err(e);
+                            }
+                        };
+                    entry.insert(FileWriter {
+                        location,
+                        multipart_id,
+                        writer,
+                        serializer,
+                    })
+                }
+            };
+
+            let bytes = writer.serializer.serialize(&batch)?;
+            writer.writer.write_all(&bytes).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Splits `batch` into the rows of each hive style partition directory,
+/// e.g. `year=2023/month=5/`, and removes the partition columns.
+///
+/// Partition values are escaped like hive does, and NULL values are written
+/// to the `__HIVE_DEFAULT_PARTITION__` directory
+fn split_by_partition(
+    batch: &RecordBatch,
+    config: &FileWriteConfig,
+) -> Result<Vec<(String, RecordBatch)>> {
+    let num_file_columns = config.file_schema.fields().len();
+    let file_columns = batch.columns()[..num_file_columns].to_vec();
+
+    if config.table_partition_cols.is_empty() {
+        let batch = RecordBatch::try_new(config.file_schema.clone(), file_columns)?;
+        return Ok(vec![(String::new(), batch)]);
+    }
+
+    let partition_columns = &batch.columns()[num_file_columns..];
+    let mut rows_by_dir: HashMap<String, Vec<u32>> = HashMap::new();
+    for row in 0..batch.num_rows() {
+        let mut dir = String::new();
+        for (name, column) in config.table_partition_cols.iter().zip(partition_columns) {
+            let value = if column.is_null(row) {
+                HIVE_DEFAULT_PARTITION.to_string()
+            } else {
+                escape_partition_value(&array_value_to_string(column, row)?)
+            };
+            dir.push_str(&format!("{name}={value}/"));
+        }
+        rows_by_dir.entry(dir).or_default().push(row as u32);
+    }
+
+    rows_by_dir
+        .into_iter()
+        .map(|(dir, rows)| {
+            let indices = UInt32Array::from(rows);
+            let columns = file_columns
+                .iter()
+                .map(|column| take(column.as_ref(), &indices, None))
+                .collect::<ArrowResult<Vec<_>>>()?;
+            let batch = RecordBatch::try_new(config.file_schema.clone(), columns)?;
+            Ok((dir, batch))
+        })
+        .collect()
+}
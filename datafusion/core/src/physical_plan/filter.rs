@@ -35,8 +35,13 @@ use arrow::compute::filter_record_batch;
 use arrow::datatypes::{DataType, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use datafusion_common::cast::as_boolean_array;
+use datafusion_common::ScalarValue;
 use datafusion_expr::Operator;
 use datafusion_physical_expr::expressions::BinaryExpr;
+use datafusion_physical_expr::intervals::statistics::{
+    estimate_predicate, PredicateEstimate,
+};
+use datafusion_physical_expr::intervals::IntervalBound;
 use datafusion_physical_expr::{split_conjunction, AnalysisContext};
 
 use log::trace;
@@ -177,8 +182,18 @@ impl ExecutionPlan for FilterExec {
     /// predicate's selectivity value can be determined for the incoming data.
     fn statistics(&self) -> Statistics {
         let input_stats = self.input.statistics();
-        let starter_ctx =
-            AnalysisContext::from_statistics(self.input.schema().as_ref(), &input_stats);
+        let schema = self.input.schema();
+        // Interval arithmetic supports conjunctions of range predicates, which
+        // the analysis below does not
+        if let Some(column_statistics) = &input_stats.column_statistics {
+            if let Ok(Some(estimate)) =
+                estimate_predicate(&self.predicate, &schema, column_statistics)
+            {
+                return estimated_statistics(&input_stats, column_statistics, estimate);
+            }
+        }
+
+        let starter_ctx = AnalysisContext::from_statistics(schema.as_ref(), &input_stats);
 
         let analysis_ctx = self.predicate.analyze(starter_ctx);
 
@@ -222,6 +237,75 @@ impl ExecutionPlan for FilterExec {
     }
 }
 
+/// Statistics of the output of a filter, given the statistics of its input
+/// and the estimated effect of its predicate
+fn estimated_statistics(
+    input_stats: &Statistics,
+    column_statistics: &[ColumnStatistics],
+    estimate: PredicateEstimate,
+) -> Statistics {
+    let PredicateEstimate {
+        selectivity,
+        column_ranges,
+    } = estimate;
+    let scale = |value: usize| (value as f64 * selectivity).ceil() as usize;
+    let num_rows = input_stats.num_rows.map(scale);
+
+    let mut column_statistics = column_statistics.to_vec();
+    for (index, range) in column_ranges {
+        let stats = &mut column_statistics[index];
+        stats.min_value = Some(inclusive_bound(range.lower, true));
+        stats.max_value = Some(inclusive_bound(range.upper, false));
+        // The predicate is only true for rows in which the column is not null
+        stats.null_count = stats.null_count.map(|_| 0);
+    }
+    if let Some(num_rows) = num_rows {
+        for stats in column_statistics.iter_mut() {
+            stats.distinct_count = stats.distinct_count.map(|count| count.min(num_rows));
+        }
+    }
+
+    Statistics {
+        num_rows,
+        total_byte_size: input_stats.total_byte_size.map(scale),
+        column_statistics: Some(column_statistics),
+        is_exact: false,
+    }
+}
+
+/// The value of an interval bound as an inclusive min or max statistic.
+/// Open bounds of integer columns are tightened to the next integer inside
+/// the interval, e.g. `b > 45` has a min of 46. Open bounds of other types
+/// are kept as is, and are inclusive approximations of the actual bounds.
+fn inclusive_bound(bound: IntervalBound, lower: bool) -> ScalarValue {
+    let data_type = bound.value.get_datatype();
+    let is_integer = matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+    );
+    if !bound.open || !is_integer || bound.value.is_null() {
+        return bound.value;
+    }
+    let one = match ScalarValue::new_one(&data_type) {
+        Ok(one) => one,
+        Err(_) => return bound.value,
+    };
+    let tightened = if lower {
+        bound.value.add_checked(one)
+    } else {
+        bound.value.sub_checked(one)
+    };
+    // An open bound at the limit of the type describes an empty interval
+    tightened.unwrap_or(bound.value)
+}
+
 /// The FilterExec streams wraps the input iterator and applies the predicate expression to
 /// determine which rows to include in its output batches
 struct FilterExecStream {
@@ -630,7 +714,7 @@ mod tests {
                     ..Default::default()
                 },
                 ColumnStatistics {
-                    min_value: Some(ScalarValue::Int32(Some(45))),
+                    min_value: Some(ScalarValue::Int32(Some(46))),
                     max_value: Some(ScalarValue::Int32(Some(50))),
                     ..Default::default()
                 }
@@ -640,6 +724,110 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_filter_statistics_conjunction() -> Result<()> {
+        // Table:
+        //      a: min=1, max=100, null_count=5, distinct_count=100
+        //      b: min=1, max=50, distinct_count=50
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let input = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Some(1000),
+                column_statistics: Some(vec![
+                    ColumnStatistics {
+                        min_value: Some(ScalarValue::Int32(Some(1))),
+                        max_value: Some(ScalarValue::Int32(Some(100))),
+                        null_count: Some(5),
+                        distinct_count: Some(100),
+                    },
+                    ColumnStatistics {
+                        min_value: Some(ScalarValue::Int32(Some(1))),
+                        max_value: Some(ScalarValue::Int32(Some(50))),
+                        null_count: Some(0),
+                        distinct_count: Some(50),
+                    },
+                ]),
+                ..Default::default()
+            },
+            schema.clone(),
+        ));
+
+        // WHERE a >= 10 AND a <= 25 AND b > 45
+        let predicate = binary(
+            binary(
+                binary(col("a", &schema)?, Operator::GtEq, lit(10i32), &schema)?,
+                Operator::And,
+                binary(col("a", &schema)?, Operator::LtEq, lit(25i32), &schema)?,
+                &schema,
+            )?,
+            Operator::And,
+            binary(col("b", &schema)?, Operator::Gt, lit(45i32), &schema)?,
+            &schema,
+        )?;
+        let filter: Arc<dyn ExecutionPlan> =
+            Arc::new(FilterExec::try_new(predicate, input)?);
+
+        let statistics = filter.statistics();
+        // 16 of the 100 values of 'a' and 5 of the 50 values of 'b' satisfy
+        // the predicate, i.e. 1000 * 0.16 * 0.1 = 16 rows
+        assert_eq!(statistics.num_rows, Some(16));
+        assert!(!statistics.is_exact);
+        assert_eq!(
+            statistics.column_statistics,
+            Some(vec![
+                ColumnStatistics {
+                    min_value: Some(ScalarValue::Int32(Some(10))),
+                    max_value: Some(ScalarValue::Int32(Some(25))),
+                    null_count: Some(0),
+                    distinct_count: Some(16),
+                },
+                ColumnStatistics {
+                    min_value: Some(ScalarValue::Int32(Some(46))),
+                    max_value: Some(ScalarValue::Int32(Some(50))),
+                    null_count: Some(0),
+                    distinct_count: Some(16),
+                }
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_filter_statistics_infeasible_predicate() -> Result<()> {
+        // Table:
+        //      a: min=1, max=100
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let input = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Some(100),
+                total_byte_size: Some(400),
+                column_statistics: Some(vec![ColumnStatistics {
+                    min_value: Some(ScalarValue::Int32(Some(1))),
+                    max_value: Some(ScalarValue::Int32(Some(100))),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            },
+            schema.clone(),
+        ));
+
+        // WHERE a > 200
+        let filter: Arc<dyn ExecutionPlan> = Arc::new(FilterExec::try_new(
+            binary(col("a", &schema)?, Operator::Gt, lit(200i32), &schema)?,
+            input,
+        )?);
+
+        let statistics = filter.statistics();
+        assert_eq!(statistics.num_rows, Some(0));
+        assert_eq!(statistics.total_byte_size, Some(0));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_filter_statistics_when_input_stats_missing() -> Result<()> {
         // Table:
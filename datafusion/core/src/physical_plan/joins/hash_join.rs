@@ -25,8 +25,10 @@ use arrow::array::{
     StringArray, TimestampNanosecondArray, UInt16Array, UInt32Array, UInt64Array,
     UInt8Array,
 };
+use arrow::compute::take;
 use arrow::datatypes::{ArrowNativeType, DataType};
 use arrow::datatypes::{Schema, SchemaRef};
+use arrow::error::Result as ArrowResult;
 use arrow::record_batch::RecordBatch;
 use arrow::{
     array::{
@@ -42,13 +44,17 @@ use arrow::{
     },
     util::bit_util,
 };
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use futures::{ready, Stream, StreamExt};
 use hashbrown::raw::RawTable;
+use log::debug;
 use smallvec::smallvec;
+use std::collections::VecDeque;
 use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::task::Poll;
 use std::{any::Any, usize, vec};
+use tempfile::NamedTempFile;
 
 use datafusion_common::cast::{as_dictionary_array, as_string_array};
 use datafusion_execution::memory_pool::MemoryReservation;
@@ -65,6 +71,7 @@ use crate::physical_plan::joins::utils::{
 use crate::physical_plan::{
     coalesce_batches::concat_batches,
     coalesce_partitions::CoalescePartitionsExec,
+    common::IPCWriter,
     expressions::Column,
     expressions::PhysicalSortExpr,
     hash_utils::create_hashes,
@@ -75,6 +82,7 @@ use crate::physical_plan::{
         JoinFilter, JoinOn,
     },
     metrics::{ExecutionPlanMetricsSet, MetricsSet},
+    sorts::sort::read_spill_as_stream,
     DisplayFormatType, Distribution, EquivalenceProperties, ExecutionPlan, Partitioning,
     PhysicalExpr, RecordBatchStream, SendableRecordBatchStream, Statistics,
 };
@@ -87,12 +95,55 @@ use crate::physical_plan::joins::hash_join_utils::JoinHashMap;
 
 type JoinLeftData = (JoinHashMap, RecordBatch, MemoryReservation);
 
+/// Build side of a [`HashJoinExec`]
+enum BuildSide {
+    /// The whole build side is held in memory
+    InMemory(JoinLeftData),
+    /// The build side did not fit into memory and was hash partitioned into
+    /// spill files
+    Spilled(SpilledBuildSide),
+}
+
+/// Build side of a [`HashJoinExec`] that was hash partitioned on its join
+/// keys into spill files
+struct SpilledBuildSide {
+    /// Schema of the build side
+    schema: SchemaRef,
+    /// One spill file per partition
+    partitions: Vec<NamedTempFile>,
+    /// Number of times the rows were partitioned before, which determines
+    /// the hash function of the partitioning
+    level: usize,
+}
+
+/// Maximum number of times a partition of the build side is partitioned
+/// again when it does not fit into memory. Partitioning stops earlier if a
+/// pass does not reduce the size of the largest partition, e.g. because all
+/// of its rows share the same join key
+const MAX_SPILL_LEVEL: usize = 4;
+
 /// Join execution plan executes partitions in parallel and combines them into a set of
 /// partitions.
 ///
 /// Filter expression expected to contain non-equality predicates that can not be pushed
 /// down to any of join inputs.
 /// In case of outer join, filter applied to only matched rows.
+///
+/// # Spilling
+///
+/// If the memory reservation for the build side can not grow, and the
+/// [`DiskManager`] allows temporary files, the join falls back to a grace
+/// hash join: the build side and, subsequently, the probe side are hash
+/// partitioned on their join keys into
+/// [`hash_join_spill_partitions`] spill files each. As matching rows always
+/// end up in partitions with the same index, the partitions are then joined
+/// one pair at a time, which requires only one partition of the build side
+/// to fit into memory. A partition of the build side which still does not fit
+/// is partitioned again, together with the corresponding partition of the
+/// probe side, using a different hash function.
+///
+/// [`DiskManager`]: crate::execution::disk_manager::DiskManager
+/// [`hash_join_spill_partitions`]: crate::config::ExecutionOptions::hash_join_spill_partitions
 #[derive(Debug)]
 pub struct HashJoinExec {
     /// left (build) side which gets hashed
@@ -108,7 +159,7 @@ pub struct HashJoinExec {
     /// The schema once the join is applied
     schema: SchemaRef,
     /// Build-side data
-    left_fut: OnceAsync<JoinLeftData>,
+    left_fut: OnceAsync<BuildSide>,
     /// Shares the `RandomState` for the hashing algorithm
     random_state: RandomState,
     /// Partitioning mode to use
@@ -397,7 +448,7 @@ impl ExecutionPlan for HashJoinExec {
 
         // we have the batches and the hash map with their keys. We can how create a stream
         // over the right that uses this information to issue new batches.
-        let right_stream = self.right.execute(partition, context)?;
+        let right_stream = self.right.execute(partition, context.clone())?;
 
         Ok(Box::pin(HashJoinStream {
             schema: self.schema(),
@@ -414,6 +465,9 @@ impl ExecutionPlan for HashJoinExec {
             null_equals_null: self.null_equals_null,
             is_exhausted: false,
             reservation,
+            context,
+            partition,
+            grace: None,
         }))
     }
 
@@ -458,7 +512,7 @@ async fn collect_left_input(
     context: Arc<TaskContext>,
     metrics: BuildProbeJoinMetrics,
     reservation: MemoryReservation,
-) -> Result<JoinLeftData> {
+) -> Result<BuildSide> {
     let schema = left.schema();
 
     let (left_input, left_input_partition) = if let Some(partition) = partition {
@@ -477,31 +531,105 @@ async fn collect_left_input(
 
     // Depending on partition argument load single partition or whole left side in memory
     let stream = left_input.execute(left_input_partition, context.clone())?;
+    collect_build_side(
+        stream,
+        schema,
+        on_left,
+        random_state,
+        &context,
+        &metrics,
+        reservation,
+        0,
+    )
+    .await
+}
+
+/// Collects the build side from `stream` into memory and builds its hash
+/// table, or hash partitions it into spill files on level `spill_level` if it
+/// does not fit into memory.
+#[allow(clippy::too_many_arguments)]
+async fn collect_build_side(
+    mut stream: SendableRecordBatchStream,
+    schema: SchemaRef,
+    on_left: Vec<Column>,
+    random_state: RandomState,
+    context: &TaskContext,
+    metrics: &BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+    spill_level: usize,
+) -> Result<BuildSide> {
+    // a single partition never reduces the size of the build side
+    let can_spill = context.runtime_env().disk_manager.tmp_files_enabled()
+        && context.session_config().hash_join_spill_partitions() > 1
+        && spill_level <= MAX_SPILL_LEVEL;
 
     // This operation performs 2 steps at once:
     // 1. creates a [JoinHashMap] of all batches from the stream
     // 2. stores the batches in a vector.
-    let initial = (Vec::new(), 0, metrics, reservation);
-    let (batches, num_rows, metrics, mut reservation) = stream
-        .try_fold(initial, |mut acc, batch| async {
-            let batch_size = batch.get_array_memory_size();
-            // Reserve memory for incoming batch
-            acc.3.try_grow(batch_size)?;
-            // Update metrics
-            acc.2.build_mem_used.add(batch_size);
-            acc.2.build_input_batches.add(1);
-            acc.2.build_input_rows.add(batch.num_rows());
-            // Update rowcount
-            acc.1 += batch.num_rows();
-            // Push batch to output
-            acc.0.push(batch);
-            Ok(acc)
-        })
-        .await?;
+    let mut batches = vec![];
+    let mut num_rows = 0;
+    while let Some(batch) = stream.next().await {
+        let batch = batch?;
+        // Update metrics, rows read back from spill files were counted before
+        if spill_level == 0 {
+            metrics.build_input_batches.add(1);
+            metrics.build_input_rows.add(batch.num_rows());
+        }
+
+        let batch_size = batch.get_array_memory_size();
+        // Reserve memory for incoming batch, or spill if that is not possible
+        if let Err(e) = reservation.try_grow(batch_size) {
+            if !can_spill {
+                return Err(e);
+            }
+            batches.push(batch);
+            return spill_build_side(
+                batches,
+                stream,
+                schema,
+                on_left,
+                context,
+                metrics,
+                reservation,
+                spill_level,
+            )
+            .await;
+        }
+        metrics.build_mem_used.add(batch_size);
+        // Update rowcount
+        num_rows += batch.num_rows();
+        // Push batch to output
+        batches.push(batch);
+    }
 
-    // Estimation of memory size, required for hashtable, prior to allocation.
-    // Final result can be verified using `RawTable.allocation_info()`
-    //
+    let estimated_hastable_size = estimate_hashtable_size(num_rows)?;
+    if let Err(e) = reservation.try_grow(estimated_hastable_size) {
+        if !can_spill {
+            return Err(e);
+        }
+        return spill_build_side(
+            batches,
+            stream,
+            schema,
+            on_left,
+            context,
+            metrics,
+            reservation,
+            spill_level,
+        )
+        .await;
+    }
+    metrics.build_mem_used.add(estimated_hastable_size);
+
+    let (hashmap, single_batch) =
+        build_hashmap(&schema, &batches, num_rows, &on_left, &random_state)?;
+
+    Ok(BuildSide::InMemory((hashmap, single_batch, reservation)))
+}
+
+/// Estimation of memory size, required for hashtable, prior to allocation.
+/// Final result can be verified using `RawTable.allocation_info()`
+fn estimate_hashtable_size(num_rows: usize) -> Result<usize> {
     // For majority of cases hashbrown overestimates buckets qty to keep ~1/8 of them empty.
     // This formula leads to overallocation for small tables (< 8 elements) but fine overall.
     let estimated_buckets = (num_rows.checked_mul(8).ok_or_else(|| {
@@ -513,11 +641,18 @@ async fn collect_left_input(
     // 32 bytes per `(u64, SmallVec<[u64; 1]>)`
     // + 1 byte for each bucket
     // + 16 bytes fixed
-    let estimated_hastable_size = 32 * estimated_buckets + estimated_buckets + 16;
-
-    reservation.try_grow(estimated_hastable_size)?;
-    metrics.build_mem_used.add(estimated_hastable_size);
+    Ok(32 * estimated_buckets + estimated_buckets + 16)
+}
 
+/// Creates a [JoinHashMap] of all `batches` and concatenates them into a
+/// single batch, so the hash map can directly index into its arrays
+fn build_hashmap(
+    schema: &SchemaRef,
+    batches: &[RecordBatch],
+    num_rows: usize,
+    on_left: &[Column],
+    random_state: &RandomState,
+) -> Result<(JoinHashMap, RecordBatch)> {
     let mut hashmap = JoinHashMap(RawTable::with_capacity(num_rows));
     let mut hashes_buffer = Vec::new();
     let mut offset = 0;
@@ -525,20 +660,202 @@ async fn collect_left_input(
         hashes_buffer.clear();
         hashes_buffer.resize(batch.num_rows(), 0);
         update_hash(
-            &on_left,
+            on_left,
             batch,
             &mut hashmap,
             offset,
-            &random_state,
+            random_state,
             &mut hashes_buffer,
         )?;
         offset += batch.num_rows();
     }
     // Merge all batches into a single batch, so we
     // can directly index into the arrays
-    let single_batch = concat_batches(&schema, &batches, num_rows)?;
+    let single_batch = concat_batches(schema, batches, num_rows)?;
 
-    Ok((hashmap, single_batch, reservation))
+    Ok((hashmap, single_batch))
+}
+
+/// Hash partitions the build side into spill files when it does not fit into
+/// memory. This includes the already collected `batches`, as well as the rest
+/// of `stream`.
+#[allow(clippy::too_many_arguments)]
+async fn spill_build_side(
+    batches: Vec<RecordBatch>,
+    mut stream: SendableRecordBatchStream,
+    schema: SchemaRef,
+    on_left: Vec<Column>,
+    context: &TaskContext,
+    metrics: &BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+    level: usize,
+) -> Result<BuildSide> {
+    let num_partitions = context.session_config().hash_join_spill_partitions();
+    debug!(
+        "Spilling build side of HashJoinExec into {num_partitions} partitions on level {level}"
+    );
+
+    let mut partitioner =
+        SpillPartitioner::try_new(context, &schema, on_left, num_partitions, level)?;
+    for batch in batches {
+        partitioner.insert(&batch)?;
+    }
+    reservation.free();
+    metrics.build_mem_used.set(0);
+
+    while let Some(batch) = stream.next().await {
+        let batch = batch?;
+        if level == 0 {
+            metrics.build_input_batches.add(1);
+            metrics.build_input_rows.add(batch.num_rows());
+        }
+        partitioner.insert(&batch)?;
+    }
+
+    // A partition of the previous level, which did not shrink when being
+    // partitioned again, would only be spilled over and over
+    let num_rows = partitioner.num_rows.iter().sum::<usize>();
+    let max_partition_rows = partitioner.num_rows.iter().max().copied();
+    if level > 0 && max_partition_rows == Some(num_rows) {
+        return Err(DataFusionError::ResourcesExhausted(format!(
+            "Partition of the build side of HashJoinExec with {num_rows} rows does \
+             not fit into memory, and partitioning it on its join keys does not \
+             reduce its size"
+        )));
+    }
+
+    Ok(BuildSide::Spilled(SpilledBuildSide {
+        schema,
+        partitions: partitioner.finish(metrics)?,
+        level,
+    }))
+}
+
+/// Loads one spilled partition of `level` of the build side into memory and
+/// builds its hash table. If it does not fit into memory, it is partitioned
+/// again on the next level.
+#[allow(clippy::too_many_arguments)]
+async fn load_spilled_partition(
+    path: PathBuf,
+    schema: SchemaRef,
+    on_left: Vec<Column>,
+    random_state: RandomState,
+    context: Arc<TaskContext>,
+    metrics: BuildProbeJoinMetrics,
+    reservation: MemoryReservation,
+    level: usize,
+) -> Result<BuildSide> {
+    let stream = read_spill_as_stream(path, schema.clone())?;
+    // the memory of the previous partition was released
+    metrics.build_mem_used.set(0);
+    collect_build_side(
+        stream,
+        schema,
+        on_left,
+        random_state,
+        &context,
+        &metrics,
+        reservation,
+        level + 1,
+    )
+    .await
+}
+
+/// Hash partitions record batches on their join keys into spill files
+struct SpillPartitioner {
+    /// Join key columns
+    on: Vec<Column>,
+    /// Random state used for partitioning. It differs from the one of the
+    /// hash table, so the rows of one partition are spread over its buckets,
+    /// and on every level, so the rows of one partition of the previous level
+    /// are spread over the partitions
+    random_state: RandomState,
+    /// One spill file per partition
+    files: Vec<NamedTempFile>,
+    /// Writers of the spill files
+    writers: Vec<IPCWriter>,
+    /// Number of rows written to each partition
+    num_rows: Vec<usize>,
+    /// Reused buffer of the hash values of a batch
+    hashes_buffer: Vec<u64>,
+}
+
+impl SpillPartitioner {
+    fn try_new(
+        context: &TaskContext,
+        schema: &Schema,
+        on: Vec<Column>,
+        num_partitions: usize,
+        level: usize,
+    ) -> Result<Self> {
+        let disk_manager = &context.runtime_env().disk_manager;
+        let files = (0..num_partitions)
+            .map(|_| disk_manager.create_tmp_file("HashJoin spilling"))
+            .collect::<Result<Vec<_>>>()?;
+        let writers = files
+            .iter()
+            .map(|file| IPCWriter::new(file.path(), schema))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            on,
+            random_state: RandomState::with_seeds(1 + level as u64, 2, 3, 4),
+            files,
+            writers,
+            num_rows: vec![0; num_partitions],
+            hashes_buffer: vec![],
+        })
+    }
+
+    /// Appends the rows of `batch` to the spill files of their partitions
+    fn insert(&mut self, batch: &RecordBatch) -> Result<()> {
+        let keys_values = self
+            .on
+            .iter()
+            .map(|c| Ok(c.evaluate(batch)?.into_array(batch.num_rows())))
+            .collect::<Result<Vec<_>>>()?;
+        self.hashes_buffer.clear();
+        self.hashes_buffer.resize(batch.num_rows(), 0);
+        create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
+
+        let num_partitions = self.writers.len() as u64;
+        let mut indices = vec![vec![]; self.writers.len()];
+        for (row, hash) in self.hashes_buffer.iter().enumerate() {
+            indices[(*hash % num_partitions) as usize].push(row as u32);
+        }
+
+        for ((writer, num_rows), indices) in self
+            .writers
+            .iter_mut()
+            .zip(self.num_rows.iter_mut())
+            .zip(indices)
+        {
+            if indices.is_empty() {
+                continue;
+            }
+            *num_rows += indices.len();
+            let indices = UInt32Array::from(indices);
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|c| take(c.as_ref(), &indices, None))
+                .collect::<ArrowResult<Vec<_>>>()?;
+            writer.write(&RecordBatch::try_new(batch.schema(), columns)?)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes all spill files and returns them in partition order
+    fn finish(mut self, metrics: &BuildProbeJoinMetrics) -> Result<Vec<NamedTempFile>> {
+        let mut spilled_bytes = 0;
+        for writer in self.writers.iter_mut() {
+            writer.finish()?;
+            spilled_bytes += writer.num_bytes as usize;
+        }
+        metrics.spill_count.add(1);
+        metrics.spilled_bytes.add(spilled_bytes);
+        Ok(self.files)
+    }
 }
 
 /// Updates `hash` with new entries from [RecordBatch] evaluated against the expressions `on`,
@@ -591,7 +908,7 @@ struct HashJoinStream {
     /// type of the join
     join_type: JoinType,
     /// future for data from left side
-    left_fut: OnceFut<JoinLeftData>,
+    left_fut: OnceFut<BuildSide>,
     /// Keeps track of the left side rows whether they are visited
     visited_left_side: Option<BooleanBufferBuilder>,
     /// right
@@ -608,6 +925,29 @@ struct HashJoinStream {
     null_equals_null: bool,
     /// Memory reservation
     reservation: MemoryReservation,
+    /// Task context, used to spill the probe side
+    context: Arc<TaskContext>,
+    /// Output partition of this stream
+    partition: usize,
+    /// Set if the build side was spilled
+    grace: Option<Box<GraceHashJoin>>,
+}
+
+/// Joins the partitions of a spilled build side with the corresponding
+/// partitions of the probe side, one pair at a time
+struct GraceHashJoin {
+    /// Spill files of the build side partitions
+    left_partitions: Vec<PathBuf>,
+    /// Level of the build side partitions
+    level: usize,
+    /// Schema of the build side
+    left_schema: SchemaRef,
+    /// Partitions the probe side, until it is exhausted
+    right_partitioner: Option<SpillPartitioner>,
+    /// Pairs of build and probe side partitions still to join
+    partitions: VecDeque<(PathBuf, NamedTempFile)>,
+    /// Join of the current pair of partitions
+    current: Option<Box<HashJoinStream>>,
 }
 
 impl RecordBatchStream for HashJoinStream {
@@ -1102,9 +1442,40 @@ impl HashJoinStream {
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Option<Result<RecordBatch>>> {
+        if self.grace.is_some() {
+            return self.poll_next_grace(cx);
+        }
+
         let build_timer = self.join_metrics.build_time.timer();
         let left_data = match ready!(self.left_fut.get(cx)) {
-            Ok(left_data) => left_data,
+            Ok(BuildSide::InMemory(left_data)) => left_data,
+            Ok(BuildSide::Spilled(spilled)) => {
+                build_timer.done();
+                let grace = SpillPartitioner::try_new(
+                    &self.context,
+                    &self.right.schema(),
+                    self.on_right.clone(),
+                    spilled.partitions.len(),
+                    spilled.level,
+                )
+                .map(|right_partitioner| GraceHashJoin {
+                    left_partitions: spilled
+                        .partitions
+                        .iter()
+                        .map(|file| file.path().to_path_buf())
+                        .collect(),
+                    level: spilled.level,
+                    left_schema: spilled.schema.clone(),
+                    right_partitioner: Some(right_partitioner),
+                    partitions: VecDeque::new(),
+                    current: None,
+                });
+                match grace {
+                    Ok(grace) => self.grace = Some(Box::new(grace)),
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                }
+                return self.poll_next_grace(cx);
+            }
             Err(e) => return Poll::Ready(Some(Err(e))),
         };
         build_timer.done();
@@ -1241,6 +1612,101 @@ impl HashJoinStream {
     }
 }
 
+impl HashJoinStream {
+    /// Polls the next batch of a join whose build side was spilled: first
+    /// partitions the whole probe side into spill files, then joins each
+    /// pair of build and probe side partitions with a [`HashJoinStream`].
+    fn poll_next_grace(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        let grace = self.grace.as_mut().expect("grace hash join state");
+        loop {
+            if let Some(partitioner) = grace.right_partitioner.as_mut() {
+                match ready!(self.right.poll_next_unpin(cx)) {
+                    Some(Ok(batch)) => {
+                        let timer = self.join_metrics.join_time.timer();
+                        let result = partitioner.insert(&batch);
+                        timer.done();
+                        if let Err(e) = result {
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                    }
+                    Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                    None => {
+                        let partitioner = grace.right_partitioner.take().unwrap();
+                        match partitioner.finish(&self.join_metrics) {
+                            Ok(right_partitions) => {
+                                grace.partitions = grace
+                                    .left_partitions
+                                    .iter()
+                                    .cloned()
+                                    .zip(right_partitions)
+                                    .collect();
+                            }
+                            Err(e) => return Poll::Ready(Some(Err(e))),
+                        }
+                    }
+                }
+                continue;
+            }
+
+            if let Some(current) = grace.current.as_mut() {
+                match ready!(current.poll_next_impl(cx)) {
+                    Some(result) => return Poll::Ready(Some(result)),
+                    None => grace.current = None,
+                }
+                continue;
+            }
+
+            let (left_path, right_file) = match grace.partitions.pop_front() {
+                Some(partitions) => partitions,
+                None => return Poll::Ready(None),
+            };
+            let right = match read_spill_as_stream(right_file, self.right.schema()) {
+                Ok(right) => right,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+            let reservation =
+                MemoryConsumer::new(format!("HashJoinInput[{}]", self.partition))
+                    .register(self.context.memory_pool());
+            let left_fut = OnceFut::new(load_spilled_partition(
+                left_path,
+                grace.left_schema.clone(),
+                self.on_left.clone(),
+                self.random_state.clone(),
+                self.context.clone(),
+                self.join_metrics.clone(),
+                reservation,
+                grace.level,
+            ));
+            let reservation =
+                MemoryConsumer::new(format!("HashJoinStream[{}]", self.partition))
+                    .register(self.context.memory_pool());
+
+            grace.current = Some(Box::new(HashJoinStream {
+                schema: self.schema.clone(),
+                on_left: self.on_left.clone(),
+                on_right: self.on_right.clone(),
+                filter: self.filter.clone(),
+                join_type: self.join_type,
+                left_fut,
+                visited_left_side: None,
+                right,
+                random_state: self.random_state.clone(),
+                is_exhausted: false,
+                join_metrics: self.join_metrics.clone(),
+                column_indices: self.column_indices.clone(),
+                null_equals_null: self.null_equals_null,
+                reservation,
+                context: self.context.clone(),
+                partition: self.partition,
+                grace: None,
+            }));
+        }
+    }
+}
+
 impl Stream for HashJoinStream {
     type Item = Result<RecordBatch>;
 
@@ -1258,6 +1724,7 @@ mod tests {
 
     use arrow::array::{ArrayRef, Date32Array, Int32Array, UInt32Builder, UInt64Builder};
     use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::util::pretty::pretty_format_batches;
     use smallvec::smallvec;
 
     use datafusion_common::ScalarValue;
@@ -1270,6 +1737,7 @@ mod tests {
     use crate::{
         assert_batches_sorted_eq,
         common::assert_contains,
+        execution::disk_manager::DiskManagerConfig,
         execution::runtime_env::{RuntimeConfig, RuntimeEnv},
         physical_plan::{
             common,
@@ -3023,7 +3491,9 @@ mod tests {
         ];
 
         for join_type in join_types {
-            let runtime_config = RuntimeConfig::new().with_memory_limit(100, 1.0);
+            let runtime_config = RuntimeConfig::new()
+                .with_memory_limit(100, 1.0)
+                .with_disk_manager(DiskManagerConfig::Disabled);
             let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
             let session_ctx =
                 SessionContext::with_config_rt(SessionConfig::default(), runtime);
@@ -3093,7 +3563,9 @@ mod tests {
         ];
 
         for join_type in join_types {
-            let runtime_config = RuntimeConfig::new().with_memory_limit(100, 1.0);
+            let runtime_config = RuntimeConfig::new()
+                .with_memory_limit(100, 1.0)
+                .with_disk_manager(DiskManagerConfig::Disabled);
             let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
             let session_config = SessionConfig::default().with_batch_size(50);
             let session_ctx = SessionContext::with_config_rt(session_config, runtime);
@@ -3123,4 +3595,207 @@ mod tests {
 
         Ok(())
     }
+
+    /// Builds a table of `num_batches` batches with 100 rows each, where
+    /// column `a` cycles through `num_keys` join keys
+    fn build_table_batches(
+        names: (&str, &str, &str),
+        num_batches: usize,
+        num_keys: i32,
+    ) -> Arc<dyn ExecutionPlan> {
+        let batches = (0..num_batches as i32)
+            .map(|batch| {
+                let range = batch * 100..(batch + 1) * 100;
+                build_table_i32(
+                    (names.0, &range.clone().map(|i| i % num_keys).collect()),
+                    (names.1, &range.clone().collect()),
+                    (names.2, &range.map(|i| i % 7).collect()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let schema = batches[0].schema();
+        Arc::new(MemoryExec::try_new(&[batches], schema, None).unwrap())
+    }
+
+    #[tokio::test]
+    async fn join_spill_to_disk() -> Result<()> {
+        let left = build_table_batches(("a", "b", "c"), 10, 100);
+        let right = build_table_batches(("a", "b", "c"), 10, 150);
+        let on = vec![(
+            Column::new_with_schema("a", &left.schema())?,
+            Column::new_with_schema("a", &right.schema())?,
+        )];
+
+        let join_types = vec![
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+        ];
+
+        for join_type in join_types {
+            for filter in [None, Some(prepare_join_filter())] {
+                let join = || {
+                    HashJoinExec::try_new(
+                        left.clone(),
+                        right.clone(),
+                        on.clone(),
+                        filter.clone(),
+                        &join_type,
+                        PartitionMode::CollectLeft,
+                        false,
+                    )
+                };
+
+                let task_ctx = SessionContext::new().task_ctx();
+                let stream = join()?.execute(0, task_ctx)?;
+                let expected = common::collect(stream).await?;
+
+                // The batches of the build side fit into memory, but not its
+                // hash table
+                let runtime_config = RuntimeConfig::new().with_memory_limit(40_000, 1.0);
+                let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+                let session_ctx =
+                    SessionContext::with_config_rt(SessionConfig::default(), runtime);
+                let join = join()?;
+                let stream = join.execute(0, session_ctx.task_ctx())?;
+                let actual = common::collect(stream).await?;
+
+                let sorted_lines = |batches: &[RecordBatch]| -> Result<Vec<String>> {
+                    let formatted = pretty_format_batches(batches)?.to_string();
+                    let mut lines =
+                        formatted.lines().map(String::from).collect::<Vec<_>>();
+                    lines.sort_unstable();
+                    Ok(lines)
+                };
+                assert_eq!(
+                    sorted_lines(&expected)?,
+                    sorted_lines(&actual)?,
+                    "{join_type:?}"
+                );
+
+                let metrics = join.metrics().unwrap();
+                assert!(metrics.spill_count().unwrap() > 0);
+                assert!(metrics.spilled_bytes().unwrap() > 0);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_spill_to_disk_repartition() -> Result<()> {
+        // unique join keys, so that partitioning splits the build side
+        let left = build_table_batches(("a", "b", "c"), 10, 1000);
+        let right = build_table_batches(("a", "b", "c"), 10, 1000);
+        let on = vec![(
+            Column::new_with_schema("a", &left.schema())?,
+            Column::new_with_schema("a", &right.schema())?,
+        )];
+        let join = || {
+            HashJoinExec::try_new(
+                left.clone(),
+                right.clone(),
+                on.clone(),
+                None,
+                &JoinType::Inner,
+                PartitionMode::CollectLeft,
+                false,
+            )
+        };
+
+        let task_ctx = SessionContext::new().task_ctx();
+        let expected = common::collect(join()?.execute(0, task_ctx)?).await?;
+
+        // Neither of the two partitions of the build side fits into memory,
+        // so they are partitioned again
+        let runtime_config = RuntimeConfig::new().with_memory_limit(30_000, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let config = SessionConfig::default().with_hash_join_spill_partitions(2);
+        let session_ctx = SessionContext::with_config_rt(config, runtime);
+        let join = join()?;
+        let actual = common::collect(join.execute(0, session_ctx.task_ctx())?).await?;
+
+        let expected = pretty_format_batches(&expected)?.to_string();
+        let actual = pretty_format_batches(&actual)?.to_string();
+        let mut expected = expected.lines().collect::<Vec<_>>();
+        let mut actual = actual.lines().collect::<Vec<_>>();
+        expected.sort_unstable();
+        actual.sort_unstable();
+        assert_eq!(expected, actual);
+
+        // both inputs are spilled once, and then partitions of both again
+        let metrics = join.metrics().unwrap();
+        assert!(metrics.spill_count().unwrap() > 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_spill_to_disk_zero_partitions() -> Result<()> {
+        let left = build_table_batches(("a", "b", "c"), 10, 1000);
+        let right = build_table_batches(("a", "b", "c"), 10, 1000);
+        let on = vec![(
+            Column::new_with_schema("a", &left.schema())?,
+            Column::new_with_schema("a", &right.schema())?,
+        )];
+        let join = HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &JoinType::Inner,
+            PartitionMode::CollectLeft,
+            false,
+        )?;
+
+        // 0 partitions is treated as a single partition, which can never
+        // reduce the size of the build side
+        let runtime_config = RuntimeConfig::new().with_memory_limit(30_000, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let config = SessionConfig::default()
+            .set_usize("datafusion.execution.hash_join_spill_partitions", 0);
+        let session_ctx = SessionContext::with_config_rt(config, runtime);
+        let stream = join.execute(0, session_ctx.task_ctx())?;
+        let err = common::collect(stream).await.unwrap_err();
+        assert_contains!(err.to_string(), "Resources exhausted");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_spill_to_disk_single_key() -> Result<()> {
+        // a single join key, so that partitioning never splits the build side
+        let left = build_table_batches(("a", "b", "c"), 10, 1);
+        let right = build_table_batches(("a", "b", "c"), 10, 1);
+        let on = vec![(
+            Column::new_with_schema("a", &left.schema())?,
+            Column::new_with_schema("a", &right.schema())?,
+        )];
+        let join = HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &JoinType::Inner,
+            PartitionMode::CollectLeft,
+            false,
+        )?;
+
+        let runtime_config = RuntimeConfig::new().with_memory_limit(30_000, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let session_ctx =
+            SessionContext::with_config_rt(SessionConfig::default(), runtime);
+        let stream = join.execute(0, session_ctx.task_ctx())?;
+        let err = common::collect(stream).await.unwrap_err();
+        assert_contains!(err.to_string(), "does not reduce its size");
+
+        // both inputs are spilled once, and partitioning the only non-empty
+        // partition of the build side again fails
+        let metrics = join.metrics().unwrap();
+        assert_eq!(metrics.spill_count(), Some(2));
+        Ok(())
+    }
 }
@@ -24,6 +24,8 @@ use std::any::Any;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::fmt::Formatter;
+use std::fs::File;
+use std::io::BufWriter;
 use std::mem;
 use std::ops::Range;
 use std::pin::Pin;
@@ -34,14 +36,19 @@ use arrow::array::*;
 use arrow::compute::{concat_batches, take, SortOptions};
 use arrow::datatypes::{DataType, SchemaRef, TimeUnit};
 use arrow::error::ArrowError;
+use arrow::ipc::writer::FileWriter;
 use arrow::record_batch::RecordBatch;
 use datafusion_physical_expr::PhysicalSortRequirement;
-use futures::{Stream, StreamExt};
+use futures::{ready, FutureExt, Stream, StreamExt};
+use tempfile::NamedTempFile;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::task::{self, JoinHandle};
 
 use crate::error::DataFusionError;
 use crate::error::Result;
 use crate::execution::context::TaskContext;
 use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use crate::execution::runtime_env::RuntimeEnv;
 use crate::logical_expr::JoinType;
 use crate::physical_plan::expressions::Column;
 use crate::physical_plan::expressions::PhysicalSortExpr;
@@ -50,6 +57,7 @@ use crate::physical_plan::joins::utils::{
     estimate_join_statistics, partitioned_join_output_partitioning, JoinOn,
 };
 use crate::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricBuilder, MetricsSet};
+use crate::physical_plan::sorts::sort::read_spill_as_stream;
 use crate::physical_plan::{
     metrics, DisplayFormatType, Distribution, EquivalenceProperties, ExecutionPlan,
     Partitioning, PhysicalExpr, RecordBatchStream, SendableRecordBatchStream, Statistics,
@@ -59,6 +67,13 @@ use datafusion_common::tree_node::{Transformed, TreeNode};
 
 /// join execution plan executes partitions in parallel and combines them into a set of
 /// partitions.
+///
+/// The rows of the buffered side that share the current join key are kept in
+/// memory. If they do not fit into the memory pool, the batches holding them
+/// are spilled to one file per join key, unless the [`DiskManager`] is
+/// disabled. The file is read back once, when the rows of its key are joined.
+///
+/// [`DiskManager`]: crate::execution::disk_manager::DiskManager
 #[derive(Debug)]
 pub struct SortMergeJoinExec {
     /// Left sorted joining execution plan
@@ -194,10 +209,35 @@ impl SortMergeJoinExec {
         })
     }
 
+    /// Left sorted joining execution plan
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// Right sorted joining execution plan
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
     /// Set of common columns used to join on
     pub fn on(&self) -> &[(Column, Column)] {
         &self.on
     }
+
+    /// How the join is performed
+    pub fn join_type(&self) -> JoinType {
+        self.join_type
+    }
+
+    /// Sort options of the join columns
+    pub fn sort_options(&self) -> &[SortOptions] {
+        &self.sort_options
+    }
+
+    /// Get null_equals_null
+    pub fn null_equals_null(&self) -> bool {
+        self.null_equals_null
+    }
 }
 
 impl ExecutionPlan for SortMergeJoinExec {
@@ -354,6 +394,7 @@ impl ExecutionPlan for SortMergeJoinExec {
             batch_size,
             SortMergeJoinMetrics::new(partition, &self.metrics),
             reservation,
+            context.runtime_env(),
         )?))
     }
 
@@ -402,6 +443,10 @@ struct SortMergeJoinMetrics {
     /// Peak memory used for buffered data.
     /// Calculated as sum of peak memory values across partitions
     peak_mem_used: metrics::Gauge,
+    /// Number of spill files written for buffered join keys
+    spill_count: metrics::Count,
+    /// Total memory size of the buffered batches spilled to disk
+    spilled_bytes: metrics::Count,
 }
 
 impl SortMergeJoinMetrics {
@@ -415,6 +460,8 @@ impl SortMergeJoinMetrics {
             MetricBuilder::new(metrics).counter("output_batches", partition);
         let output_rows = MetricBuilder::new(metrics).output_rows(partition);
         let peak_mem_used = MetricBuilder::new(metrics).gauge("peak_mem_used", partition);
+        let spill_count = MetricBuilder::new(metrics).spill_count(partition);
+        let spilled_bytes = MetricBuilder::new(metrics).spilled_bytes(partition);
 
         Self {
             join_time,
@@ -423,6 +470,8 @@ impl SortMergeJoinMetrics {
             output_batches,
             output_rows,
             peak_mem_used,
+            spill_count,
+            spilled_bytes,
         }
     }
 }
@@ -434,6 +483,9 @@ enum SMJState {
     Init,
     /// Polling one streamed row or one buffered batch, or both
     Polling,
+    /// Reading the spilled buffered batches of the current join key back
+    /// into memory before they are joined
+    LoadingSpilled,
     /// Joining polled data and making output
     JoinOutput,
     /// No more output
@@ -540,8 +592,14 @@ impl StreamedBatch {
 /// A buffered batch that contains contiguous rows with same join key
 #[derive(Debug)]
 struct BufferedBatch {
-    /// The buffered record batch
-    pub batch: RecordBatch,
+    /// The buffered record batch, or `None` while it is spilled to disk
+    pub batch: Option<RecordBatch>,
+    /// Where the batch was spilled to, if it did not fit into memory
+    pub spilled: Option<SpilledBatch>,
+    /// Whether memory is reserved for the batch
+    pub reserved: bool,
+    /// Number of rows of the batch
+    pub num_rows: usize,
     /// The range in which the rows share the same join key
     pub range: Range<usize>,
     /// Array refs of the join key
@@ -572,13 +630,79 @@ impl BufferedBatch {
             + mem::size_of::<usize>();
 
         BufferedBatch {
-            batch,
+            num_rows: batch.num_rows(),
+            batch: Some(batch),
+            spilled: None,
+            reserved: false,
             range,
             join_arrays,
             null_joined: vec![],
             size_estimation,
         }
     }
+
+    /// Returns the record batch, which must have been read back into memory
+    /// if it was spilled
+    fn record_batch(&self) -> Result<&RecordBatch> {
+        self.batch.as_ref().ok_or_else(|| {
+            DataFusionError::Internal(
+                "Spilled buffered batch was not read back into memory".to_string(),
+            )
+        })
+    }
+}
+
+/// The location of a spilled buffered batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SpilledBatch {
+    /// Id of the [`GroupSpill`] the batch was written to
+    spill_id: usize,
+    /// Index of the batch in the spill file
+    index: usize,
+}
+
+/// The spill file of the buffered batches of one join key that did not fit
+/// into memory. The batches are written by a blocking task as they are
+/// buffered.
+struct GroupSpill {
+    /// Id of the spill, increasing with each spilled join key
+    id: usize,
+    /// The spill file
+    file: NamedTempFile,
+    /// Sends the batches to the writer task, `None` once all the batches of
+    /// the join key are buffered
+    sender: Option<UnboundedSender<RecordBatch>>,
+    /// The writer task, `None` once it has finished
+    writer: Option<JoinHandle<Result<()>>>,
+    /// Number of batches sent to the writer task
+    num_batches: usize,
+}
+
+/// Reads a spill file of buffered batches back into memory
+struct SpillReader {
+    /// Id of the [`GroupSpill`] that is read
+    spill_id: usize,
+    /// The batches of the spill file
+    stream: SendableRecordBatchStream,
+    /// Index of the next batch of the stream in the spill file
+    next_index: usize,
+}
+
+/// Writes the batches received from `receiver` to the spill `file` on a
+/// blocking thread, until the sender is dropped
+fn spawn_spill_writer(
+    file: File,
+    schema: SchemaRef,
+    mut receiver: UnboundedReceiver<RecordBatch>,
+) -> JoinHandle<Result<()>> {
+    task::spawn_blocking(move || {
+        let mut writer = FileWriter::try_new(BufWriter::new(file), &schema)?;
+        while let Some(batch) = receiver.blocking_recv() {
+            writer.write(&batch)?;
+        }
+        writer.finish()?;
+        Ok(())
+    })
 }
 
 /// Sort-merge join stream that consumes streamed and buffered data stream
@@ -630,6 +754,14 @@ struct SMJStream {
     pub join_metrics: SortMergeJoinMetrics,
     /// Memory reservation
     pub reservation: MemoryReservation,
+    /// Runtime environment, whose disk manager buffered batches are spilled to
+    pub runtime_env: Arc<RuntimeEnv>,
+    /// Spill files of the join keys of the buffered batches
+    pub spills: VecDeque<GroupSpill>,
+    /// Id of the next spill file
+    pub next_spill_id: usize,
+    /// The spill file being read back into memory
+    pub spill_reader: Option<SpillReader>,
 }
 
 impl RecordBatchStream for SMJStream {
@@ -704,7 +836,22 @@ impl Stream for SMJStream {
                     self.current_ordering = self.compare_streamed_buffered()?;
                     self.state = SMJState::JoinOutput;
                 }
+                SMJState::LoadingSpilled => match self.poll_load_spilled(cx)? {
+                    Poll::Ready(()) => self.state = SMJState::JoinOutput,
+                    Poll::Pending => return Poll::Pending,
+                },
                 SMJState::JoinOutput => {
+                    if self.joins_buffered_rows()
+                        && self.buffered_data.scanning_batch_spilled()
+                    {
+                        // The staged output may reference the spilled batches
+                        // read so far, which are released before reading the
+                        // next one
+                        self.freeze_all()?;
+                        self.unload_spilled_batches();
+                        self.state = SMJState::LoadingSpilled;
+                        continue;
+                    }
                     self.join_partial()?;
 
                     if self.output_size < self.batch_size {
@@ -748,6 +895,7 @@ impl SMJStream {
         batch_size: usize,
         join_metrics: SortMergeJoinMetrics,
         reservation: MemoryReservation,
+        runtime_env: Arc<RuntimeEnv>,
     ) -> Result<Self> {
         let streamed_schema = streamed.schema();
         let buffered_schema = buffered.schema();
@@ -775,6 +923,10 @@ impl SMJStream {
             join_type,
             join_metrics,
             reservation,
+            runtime_env,
+            spills: VecDeque::new(),
+            next_spill_id: 0,
+            spill_reader: None,
         })
     }
 
@@ -828,17 +980,21 @@ impl SMJStream {
                     // pop previous buffered batches
                     while !self.buffered_data.batches.is_empty() {
                         let head_batch = self.buffered_data.head_batch();
-                        if head_batch.range.end == head_batch.batch.num_rows() {
+                        if head_batch.range.end == head_batch.num_rows {
                             self.freeze_dequeuing_buffered()?;
                             if let Some(buffered_batch) =
                                 self.buffered_data.batches.pop_front()
                             {
-                                self.reservation.shrink(buffered_batch.size_estimation);
+                                if buffered_batch.reserved {
+                                    self.reservation
+                                        .shrink(buffered_batch.size_estimation);
+                                }
                             }
                         } else {
                             break;
                         }
                     }
+                    self.release_spills();
                     if self.buffered_data.batches.is_empty() {
                         self.buffered_state = BufferedState::PollingFirst;
                     } else {
@@ -860,13 +1016,9 @@ impl SMJStream {
                         self.join_metrics.input_batches.add(1);
                         self.join_metrics.input_rows.add(batch.num_rows());
                         if batch.num_rows() > 0 {
-                            let buffered_batch =
+                            let mut buffered_batch =
                                 BufferedBatch::new(batch, 0..1, &self.on_buffered);
-                            self.reservation.try_grow(buffered_batch.size_estimation)?;
-                            self.join_metrics
-                                .peak_mem_used
-                                .set_max(self.reservation.size());
-
+                            self.allocate_reservation(&mut buffered_batch)?;
                             self.buffered_data.batches.push_back(buffered_batch);
                             self.buffered_state = BufferedState::PollingRest;
                         }
@@ -874,10 +1026,10 @@ impl SMJStream {
                 },
                 BufferedState::PollingRest => {
                     if self.buffered_data.tail_batch().range.end
-                        < self.buffered_data.tail_batch().batch.num_rows()
+                        < self.buffered_data.tail_batch().num_rows
                     {
                         while self.buffered_data.tail_batch().range.end
-                            < self.buffered_data.tail_batch().batch.num_rows()
+                            < self.buffered_data.tail_batch().num_rows
                         {
                             if is_join_arrays_equal(
                                 &self.buffered_data.head_batch().join_arrays,
@@ -887,6 +1039,7 @@ impl SMJStream {
                             )? {
                                 self.buffered_data.tail_batch_mut().range.end += 1;
                             } else {
+                                self.finish_buffered_spill();
                                 self.buffered_state = BufferedState::Ready;
                                 return Poll::Ready(Some(Ok(())));
                             }
@@ -897,22 +1050,19 @@ impl SMJStream {
                                 return Poll::Pending;
                             }
                             Poll::Ready(None) => {
+                                self.finish_buffered_spill();
                                 self.buffered_state = BufferedState::Ready;
                             }
                             Poll::Ready(Some(batch)) => {
                                 self.join_metrics.input_batches.add(1);
                                 self.join_metrics.input_rows.add(batch.num_rows());
                                 if batch.num_rows() > 0 {
-                                    let buffered_batch = BufferedBatch::new(
+                                    let mut buffered_batch = BufferedBatch::new(
                                         batch,
                                         0..0,
                                         &self.on_buffered,
                                     );
-                                    self.reservation
-                                        .try_grow(buffered_batch.size_estimation)?;
-                                    self.join_metrics
-                                        .peak_mem_used
-                                        .set_max(self.reservation.size());
+                                    self.allocate_reservation(&mut buffered_batch)?;
                                     self.buffered_data.batches.push_back(buffered_batch);
                                 }
                             }
@@ -929,6 +1079,249 @@ impl SMJStream {
         }
     }
 
+    /// Reserves memory for a new buffered batch. If the memory pool is
+    /// exhausted, spills the batches of the join key to disk instead, unless
+    /// spilling is disabled. Once a join key is spilled, all its following
+    /// batches are spilled too, so that the memory of the pool is available
+    /// to read them back one at a time.
+    fn allocate_reservation(&mut self, buffered_batch: &mut BufferedBatch) -> Result<()> {
+        if matches!(self.spills.back(), Some(spill) if spill.sender.is_some()) {
+            return self.spill_buffered_batch(buffered_batch);
+        }
+        match self.reservation.try_grow(buffered_batch.size_estimation) {
+            Ok(_) => {
+                buffered_batch.reserved = true;
+                self.join_metrics
+                    .peak_mem_used
+                    .set_max(self.reservation.size());
+                Ok(())
+            }
+            Err(_) if self.runtime_env.disk_manager.tmp_files_enabled() => {
+                self.spill_buffered_batches()?;
+                self.spill_buffered_batch(buffered_batch)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Spills the buffered batches which are in memory
+    fn spill_buffered_batches(&mut self) -> Result<()> {
+        // The staged output may reference the buffered batches
+        self.freeze_all()?;
+        self.unload_spilled_batches();
+        let mut batches = mem::take(&mut self.buffered_data.batches);
+        let result = batches
+            .iter_mut()
+            .filter(|buffered_batch| buffered_batch.batch.is_some())
+            .try_for_each(|buffered_batch| {
+                if buffered_batch.reserved {
+                    self.reservation.shrink(buffered_batch.size_estimation);
+                    buffered_batch.reserved = false;
+                }
+                self.spill_buffered_batch(buffered_batch)
+            });
+        self.buffered_data.batches = batches;
+        result
+    }
+
+    /// Sends `buffered_batch` to the spill file of the current join key,
+    /// which is created by the first batch of the key that is spilled
+    fn spill_buffered_batch(&mut self, buffered_batch: &mut BufferedBatch) -> Result<()> {
+        let batch = buffered_batch.batch.take().ok_or_else(|| {
+            DataFusionError::Internal("Buffered batch was already spilled".to_string())
+        })?;
+
+        if !matches!(self.spills.back(), Some(spill) if spill.sender.is_some()) {
+            let file = self
+                .runtime_env
+                .disk_manager
+                .create_tmp_file("Sort-merge join buffering")?;
+            let (sender, receiver) = unbounded_channel();
+            let writer = spawn_spill_writer(
+                file.reopen()?,
+                self.buffered_schema.clone(),
+                receiver,
+            );
+            self.spills.push_back(GroupSpill {
+                id: self.next_spill_id,
+                file,
+                sender: Some(sender),
+                writer: Some(writer),
+                num_batches: 0,
+            });
+            self.next_spill_id += 1;
+            self.join_metrics.spill_count.add(1);
+        }
+
+        let spill = self.spills.back_mut().unwrap();
+        self.join_metrics
+            .spilled_bytes
+            .add(batch.get_array_memory_size());
+        if let Some(sender) = &spill.sender {
+            // the writer task only stops early if it failed
+            sender.send(batch).map_err(|_| {
+                DataFusionError::Execution(
+                    "Sort-merge join spill writer stopped before all buffered batches were spilled"
+                        .to_string(),
+                )
+            })?;
+        }
+        buffered_batch.spilled = Some(SpilledBatch {
+            spill_id: spill.id,
+            index: spill.num_batches,
+        });
+        spill.num_batches += 1;
+        Ok(())
+    }
+
+    /// Closes the spill file of the current join key, once all its buffered
+    /// batches are polled
+    fn finish_buffered_spill(&mut self) {
+        if let Some(spill) = self.spills.back_mut() {
+            spill.sender = None;
+        }
+    }
+
+    /// Deletes the spill files no buffered batch has to be read from anymore
+    fn release_spills(&mut self) {
+        let first_spill_id = self
+            .buffered_data
+            .batches
+            .iter()
+            .filter(|batch| batch.batch.is_none())
+            .find_map(|batch| batch.spilled.map(|spilled| spilled.spill_id));
+        while let Some(spill) = self.spills.front() {
+            let released = spill.sender.is_none()
+                && first_spill_id.map_or(true, |spill_id| spill.id < spill_id);
+            if !released {
+                break;
+            }
+            self.spills.pop_front();
+        }
+    }
+
+    /// Whether the rows of the current buffered join key are output by
+    /// [`Self::join_partial`], so that they must be in memory
+    fn joins_buffered_rows(&self) -> bool {
+        match self.current_ordering {
+            Ordering::Equal => matches!(
+                self.join_type,
+                JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full
+            ),
+            Ordering::Greater => matches!(self.join_type, JoinType::Full),
+            Ordering::Less => false,
+        }
+    }
+
+    /// Releases the memory of the spilled batches that were read back
+    fn unload_spilled_batches(&mut self) {
+        for buffered_batch in self.buffered_data.batches.iter_mut() {
+            if buffered_batch.spilled.is_some() && buffered_batch.batch.is_some() {
+                buffered_batch.batch = None;
+                if buffered_batch.reserved {
+                    self.reservation.shrink(buffered_batch.size_estimation);
+                    buffered_batch.reserved = false;
+                }
+            }
+        }
+    }
+
+    /// Reads the spilled buffered batch being scanned back into memory. If
+    /// the memory pool is exhausted, the buffered batches in memory are
+    /// spilled to make room for it.
+    fn poll_load_spilled(&mut self, cx: &mut Context) -> Poll<Result<()>> {
+        let target = self.buffered_data.scanning_batch().spilled.ok_or_else(|| {
+            DataFusionError::Internal(
+                "Scanned buffered batch was not spilled".to_string(),
+            )
+        })?;
+        let batch = ready!(self.poll_read_spilled(cx, target))?;
+
+        let size_estimation = self.buffered_data.scanning_batch().size_estimation;
+        if self.reservation.try_grow(size_estimation).is_err() {
+            self.spill_buffered_batches()?;
+            self.finish_buffered_spill();
+            self.reservation.try_grow(size_estimation)?;
+        }
+        self.join_metrics
+            .peak_mem_used
+            .set_max(self.reservation.size());
+        let buffered_batch = self.buffered_data.scanning_batch_mut();
+        buffered_batch.reserved = true;
+        buffered_batch.batch = Some(batch);
+        Poll::Ready(Ok(()))
+    }
+
+    /// Reads the `target` batch of its spill file. The spill file is read
+    /// sequentially on a blocking thread, after its writer task has
+    /// finished, and is only reopened to read a batch preceding the last one
+    /// read.
+    fn poll_read_spilled(
+        &mut self,
+        cx: &mut Context,
+        target: SpilledBatch,
+    ) -> Poll<Result<RecordBatch>> {
+        loop {
+            if let Some(reader) = &mut self.spill_reader {
+                if reader.spill_id == target.spill_id && reader.next_index <= target.index
+                {
+                    match ready!(reader.stream.poll_next_unpin(cx)) {
+                        Some(batch) => {
+                            let batch = batch?;
+                            let index = reader.next_index;
+                            reader.next_index += 1;
+                            if index == target.index {
+                                return Poll::Ready(Ok(batch));
+                            }
+                        }
+                        None => {
+                            return Poll::Ready(Err(DataFusionError::Internal(format!(
+                                "Spill file {} has no buffered batch {}",
+                                target.spill_id, target.index
+                            ))))
+                        }
+                    }
+                    continue;
+                }
+                self.spill_reader = None;
+            }
+
+            let spill_id = target.spill_id;
+            let spill = self
+                .spills
+                .iter_mut()
+                .find(|spill| spill.id == spill_id)
+                .ok_or_else(|| {
+                    DataFusionError::Internal(format!(
+                        "Missing spill file {spill_id} of buffered batch"
+                    ))
+                })?;
+
+            if let Some(writer) = &mut spill.writer {
+                match writer.poll_unpin(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(result) => {
+                        spill.writer = None;
+                        result.map_err(|e| {
+                            DataFusionError::Execution(format!(
+                                "Error occurred while spilling {e}"
+                            ))
+                        })??;
+                    }
+                }
+            }
+
+            self.spill_reader = Some(SpillReader {
+                spill_id,
+                stream: read_spill_as_stream(
+                    spill.file.path().to_path_buf(),
+                    self.buffered_schema.clone(),
+                )?,
+                next_index: 0,
+            });
+        }
+    }
+
     /// Get comparison result of streamed row and buffered batches
     fn compare_streamed_buffered(&self) -> Result<Ordering> {
         if self.streamed_state == StreamedState::Exhausted {
@@ -995,6 +1388,7 @@ impl SMJStream {
         if join_buffered {
             // joining streamed/nulls and buffered
             while !self.buffered_data.scanning_finished()
+                && !self.buffered_data.scanning_batch_spilled()
                 && self.output_size < self.batch_size
             {
                 let scanning_idx = self.buffered_data.scanning_idx();
@@ -1068,7 +1462,7 @@ impl SMJStream {
             buffered_batch.null_joined.clear();
 
             let buffered_columns = buffered_batch
-                .batch
+                .record_batch()?
                 .columns()
                 .iter()
                 .map(|column| take(column, &buffered_indices, None))
@@ -1110,13 +1504,19 @@ impl SMJStream {
                 .collect::<Result<Vec<_>, ArrowError>>()?;
 
             let buffered_indices: UInt64Array = chunk.buffered_indices.finish();
+            // Streamed rows which are only joined to nulls do not need the
+            // buffered batch, which may still be spilled
+            let joins_buffered_rows =
+                buffered_indices.null_count() < buffered_indices.len();
 
             let mut buffered_columns =
                 if matches!(self.join_type, JoinType::LeftSemi | JoinType::LeftAnti) {
                     vec![]
-                } else if let Some(buffered_idx) = chunk.buffered_batch_idx {
+                } else if let Some(buffered_idx) =
+                    chunk.buffered_batch_idx.filter(|_| joins_buffered_rows)
+                {
                     self.buffered_data.batches[buffered_idx]
-                        .batch
+                        .record_batch()?
                         .columns()
                         .iter()
                         .map(|column| take(column, &buffered_indices, None))
@@ -1184,6 +1584,11 @@ impl BufferedData {
         self.batches.iter().any(|batch| !batch.range.is_empty())
     }
 
+    /// Whether the batch being scanned is spilled to disk
+    pub fn scanning_batch_spilled(&self) -> bool {
+        !self.scanning_finished() && self.scanning_batch().batch.is_none()
+    }
+
     pub fn scanning_reset(&mut self) {
         self.scanning_batch_idx = 0;
         self.scanning_offset = 0;
@@ -1393,9 +1798,11 @@ mod tests {
     use arrow::compute::SortOptions;
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow::record_batch::RecordBatch;
+    use arrow::util::pretty::pretty_format_batches;
 
     use crate::common::assert_contains;
     use crate::error::Result;
+    use crate::execution::disk_manager::DiskManagerConfig;
     use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
     use crate::logical_expr::JoinType;
     use crate::physical_plan::expressions::Column;
@@ -2322,7 +2729,9 @@ mod tests {
         ];
 
         for join_type in join_types {
-            let runtime_config = RuntimeConfig::new().with_memory_limit(100, 1.0);
+            let runtime_config = RuntimeConfig::new()
+                .with_memory_limit(100, 1.0)
+                .with_disk_manager(DiskManagerConfig::Disabled);
             let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
             let session_config = SessionConfig::default().with_batch_size(50);
             let session_ctx = SessionContext::with_config_rt(session_config, runtime);
@@ -2398,7 +2807,9 @@ mod tests {
         ];
 
         for join_type in join_types {
-            let runtime_config = RuntimeConfig::new().with_memory_limit(100, 1.0);
+            let runtime_config = RuntimeConfig::new()
+                .with_memory_limit(100, 1.0)
+                .with_disk_manager(DiskManagerConfig::Disabled);
             let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
             let session_config = SessionConfig::default().with_batch_size(50);
             let session_ctx = SessionContext::with_config_rt(session_config, runtime);
@@ -2424,4 +2835,197 @@ mod tests {
 
         Ok(())
     }
+
+    /// Joins `left` and `right` with all join types, once without a memory
+    /// limit and once with `memory_limit`, which requires spilling the
+    /// buffered batches, and checks that both produce the same output
+    async fn check_join_with_spilling(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        memory_limit: usize,
+    ) -> Result<()> {
+        let sort_options = vec![SortOptions::default(); on.len()];
+        let join_types = vec![
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+        ];
+
+        for join_type in join_types {
+            let join = join_with_options(
+                left.clone(),
+                right.clone(),
+                on.clone(),
+                join_type,
+                sort_options.clone(),
+                false,
+            )?;
+            let session_config = SessionConfig::default().with_batch_size(50);
+            let session_ctx = SessionContext::with_config(session_config.clone());
+            let stream = join.execute(0, session_ctx.task_ctx())?;
+            let expected = common::collect(stream).await?;
+
+            let runtime_config =
+                RuntimeConfig::new().with_memory_limit(memory_limit, 1.0);
+            let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+            let session_ctx = SessionContext::with_config_rt(session_config, runtime);
+            let join = join_with_options(
+                left.clone(),
+                right.clone(),
+                on.clone(),
+                join_type,
+                sort_options.clone(),
+                false,
+            )?;
+            let stream = join.execute(0, session_ctx.task_ctx())?;
+            let actual = common::collect(stream).await?;
+
+            assert_eq!(
+                pretty_format_batches(&expected)?.to_string(),
+                pretty_format_batches(&actual)?.to_string(),
+                "{join_type:?}"
+            );
+
+            let metrics = join.metrics().unwrap();
+            assert!(metrics.spill_count().unwrap() > 0);
+            assert!(metrics.spilled_bytes().unwrap() > 0);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spill_batch_exceeding_memory_limit() -> Result<()> {
+        let left = build_table(
+            ("a1", &vec![0, 1, 2, 3, 4, 5]),
+            ("b1", &vec![1, 2, 3, 4, 5, 6]),
+            ("c1", &vec![4, 5, 6, 7, 8, 9]),
+        );
+        let right = build_table(
+            ("a2", &vec![0, 10, 20, 30, 40]),
+            ("b2", &vec![1, 3, 4, 6, 8]),
+            ("c2", &vec![50, 60, 70, 80, 90]),
+        );
+        let on = vec![(
+            Column::new_with_schema("b1", &left.schema())?,
+            Column::new_with_schema("b2", &right.schema())?,
+        )];
+
+        // The buffered batch is spilled, but cannot be read back
+        let runtime_config = RuntimeConfig::new().with_memory_limit(100, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let session_ctx =
+            SessionContext::with_config_rt(SessionConfig::default(), runtime);
+        let join = join(left, right, on, JoinType::Inner)?;
+        let stream = join.execute(0, session_ctx.task_ctx())?;
+        let err = common::collect(stream).await.unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Resources exhausted: Failed to allocate additional"
+        );
+        assert_eq!(join.metrics().unwrap().spill_count(), Some(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spill_multi_batch() -> Result<()> {
+        // All rows share the same join key, so all batches of the buffered
+        // side are buffered at once
+        let left_batch_1 = build_table_i32(
+            ("a1", &vec![0, 1]),
+            ("b1", &vec![1, 1]),
+            ("c1", &vec![4, 5]),
+        );
+        let left_batch_2 = build_table_i32(
+            ("a1", &vec![2, 3]),
+            ("b1", &vec![1, 1]),
+            ("c1", &vec![6, 7]),
+        );
+        let left_batch_3 = build_table_i32(
+            ("a1", &vec![4, 5]),
+            ("b1", &vec![1, 2]),
+            ("c1", &vec![8, 9]),
+        );
+        let right_batch_1 = build_table_i32(
+            ("a2", &vec![0, 10]),
+            ("b2", &vec![1, 1]),
+            ("c2", &vec![50, 60]),
+        );
+        let right_batch_2 = build_table_i32(
+            ("a2", &vec![20, 30]),
+            ("b2", &vec![1, 1]),
+            ("c2", &vec![70, 80]),
+        );
+        let right_batch_3 =
+            build_table_i32(("a2", &vec![40]), ("b2", &vec![3]), ("c2", &vec![90]));
+        let left =
+            build_table_from_batches(vec![left_batch_1, left_batch_2, left_batch_3]);
+        let right =
+            build_table_from_batches(vec![right_batch_1, right_batch_2, right_batch_3]);
+        let on = vec![(
+            Column::new_with_schema("b1", &left.schema())?,
+            Column::new_with_schema("b2", &right.schema())?,
+        )];
+
+        // Room for a single buffered batch
+        check_join_with_spilling(left, right, on, 600).await
+    }
+
+    #[tokio::test]
+    async fn spill_one_file_per_join_key() -> Result<()> {
+        // The buffered side has two batches with join key 1 and two batches
+        // with join key 3
+        let left = build_table(
+            ("a1", &vec![0, 1, 2]),
+            ("b1", &vec![1, 1, 3]),
+            ("c1", &vec![4, 5, 6]),
+        );
+        let right = build_table_from_batches(vec![
+            build_table_i32(
+                ("a2", &vec![0, 10]),
+                ("b2", &vec![1, 1]),
+                ("c2", &vec![50, 60]),
+            ),
+            build_table_i32(("a2", &vec![20]), ("b2", &vec![1]), ("c2", &vec![70])),
+            build_table_i32(("a2", &vec![30]), ("b2", &vec![3]), ("c2", &vec![80])),
+            build_table_i32(("a2", &vec![40]), ("b2", &vec![3]), ("c2", &vec![90])),
+        ]);
+        let on = vec![(
+            Column::new_with_schema("b1", &left.schema())?,
+            Column::new_with_schema("b2", &right.schema())?,
+        )];
+
+        // Room for a single buffered batch
+        let runtime_config = RuntimeConfig::new().with_memory_limit(600, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let session_ctx =
+            SessionContext::with_config_rt(SessionConfig::default(), runtime);
+        let join = join(left, right, on, JoinType::Inner)?;
+        let stream = join.execute(0, session_ctx.task_ctx())?;
+        let batches = common::collect(stream).await?;
+
+        let expected = vec![
+            "+----+----+----+----+----+----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2 |",
+            "+----+----+----+----+----+----+",
+            "| 0  | 1  | 4  | 0  | 1  | 50 |",
+            "| 0  | 1  | 4  | 10 | 1  | 60 |",
+            "| 0  | 1  | 4  | 20 | 1  | 70 |",
+            "| 1  | 1  | 5  | 0  | 1  | 50 |",
+            "| 1  | 1  | 5  | 10 | 1  | 60 |",
+            "| 1  | 1  | 5  | 20 | 1  | 70 |",
+            "| 2  | 3  | 6  | 30 | 3  | 80 |",
+            "| 2  | 3  | 6  | 40 | 3  | 90 |",
+            "+----+----+----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        let metrics = join.metrics().unwrap();
+        assert_eq!(metrics.spill_count(), Some(2));
+        Ok(())
+    }
 }
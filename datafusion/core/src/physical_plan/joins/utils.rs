@@ -27,7 +27,7 @@ use arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use futures::future::{BoxFuture, Shared};
 use futures::{ready, FutureExt};
 use parking_lot::Mutex;
-use std::cmp::max;
+use std::cmp::{max, min};
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::future::Future;
@@ -521,28 +521,8 @@ fn estimate_join_cardinality(
         JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
             let left_num_rows = left_stats.num_rows?;
             let right_num_rows = right_stats.num_rows?;
-
-            // Take the left_col_stats and right_col_stats using the index
-            // obtained from index() method of the each element of 'on'.
-            let all_left_col_stats = left_stats.column_statistics?;
-            let all_right_col_stats = right_stats.column_statistics?;
-            let (left_col_stats, right_col_stats) = on
-                .iter()
-                .map(|(left, right)| {
-                    (
-                        all_left_col_stats[left.index()].clone(),
-                        all_right_col_stats[right.index()].clone(),
-                    )
-                })
-                .unzip::<_, _, Vec<_>, Vec<_>>();
-
-            let ij_cardinality = estimate_inner_join_cardinality(
-                left_num_rows,
-                right_num_rows,
-                left_col_stats,
-                right_col_stats,
-                left_stats.is_exact && right_stats.is_exact,
-            )?;
+            let ij_cardinality =
+                estimate_inner_join_cardinality_of(&left_stats, &right_stats, on)?;
 
             // The cardinality for inner join can also be used to estimate
             // the cardinality of left/right/full outer joins as long as it
@@ -566,9 +546,10 @@ fn estimate_join_cardinality(
                 // statistics which might yield subpar results (although it is
                 // true, esp regarding min/max). For a better estimation, we need
                 // filter selectivity analysis first.
-                column_statistics: all_left_col_stats
+                column_statistics: left_stats
+                    .column_statistics?
                     .into_iter()
-                    .chain(all_right_col_stats.into_iter())
+                    .chain(right_stats.column_statistics?)
                     .collect(),
             })
         }
@@ -576,10 +557,64 @@ fn estimate_join_cardinality(
         JoinType::LeftSemi
         | JoinType::RightSemi
         | JoinType::LeftAnti
-        | JoinType::RightAnti => None,
+        | JoinType::RightAnti => {
+            // Semi and anti joins only output rows of their outer side, so
+            // they can be estimated from its statistics alone
+            let outer_stats = match join_type {
+                JoinType::LeftSemi | JoinType::LeftAnti => &left_stats,
+                _ => &right_stats,
+            };
+            let num_rows = outer_stats.num_rows?;
+            let column_statistics = outer_stats.column_statistics.clone()?;
+            // The rows a semi join outputs each have a match, so there are no
+            // more of them than rows in the inner join. The rows an anti join
+            // outputs are only bounded by the size of its input.
+            let cardinality = match join_type {
+                JoinType::LeftSemi | JoinType::RightSemi => {
+                    estimate_inner_join_cardinality_of(&left_stats, &right_stats, on)
+                        .map_or(num_rows, |ij_cardinality| min(ij_cardinality, num_rows))
+                }
+                _ => num_rows,
+            };
+
+            Some(PartialJoinStatistics {
+                num_rows: cardinality,
+                column_statistics,
+            })
+        }
     }
 }
 
+/// Estimate the cardinality of the inner join of the inputs with the given
+/// statistics, using the statistics of their join columns `on`
+fn estimate_inner_join_cardinality_of(
+    left_stats: &Statistics,
+    right_stats: &Statistics,
+    on: &JoinOn,
+) -> Option<usize> {
+    // Take the left_col_stats and right_col_stats using the index
+    // obtained from index() method of the each element of 'on'.
+    let all_left_col_stats = left_stats.column_statistics.as_ref()?;
+    let all_right_col_stats = right_stats.column_statistics.as_ref()?;
+    let (left_col_stats, right_col_stats) = on
+        .iter()
+        .map(|(left, right)| {
+            (
+                all_left_col_stats[left.index()].clone(),
+                all_right_col_stats[right.index()].clone(),
+            )
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    estimate_inner_join_cardinality(
+        left_stats.num_rows?,
+        right_stats.num_rows?,
+        left_col_stats,
+        right_col_stats,
+        left_stats.is_exact && right_stats.is_exact,
+    )
+}
+
 /// Estimate the inner join cardinality by using the basic building blocks of
 /// column-level statistics and the total row count. This is a very naive and
 /// a very conservative implementation that can quickly give up if there is not
@@ -1023,6 +1058,10 @@ pub(crate) struct BuildProbeJoinMetrics {
     pub(crate) output_batches: metrics::Count,
     /// Number of rows produced by this operator
     pub(crate) output_rows: metrics::Count,
+    /// Number of times the inputs were spilled to disk
+    pub(crate) spill_count: metrics::Count,
+    /// Total bytes of the inputs spilled to disk
+    pub(crate) spilled_bytes: metrics::Count,
 }
 
 impl BuildProbeJoinMetrics {
@@ -1050,6 +1089,10 @@ impl BuildProbeJoinMetrics {
 
         let output_rows = MetricBuilder::new(metrics).output_rows(partition);
 
+        let spill_count = MetricBuilder::new(metrics).spill_count(partition);
+
+        let spilled_bytes = MetricBuilder::new(metrics).spilled_bytes(partition);
+
         Self {
             build_time,
             build_input_batches,
@@ -1060,6 +1103,8 @@ impl BuildProbeJoinMetrics {
             input_rows,
             output_batches,
             output_rows,
+            spill_count,
+            spilled_bytes,
         }
     }
 }
@@ -1536,6 +1581,83 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_semi_anti_join_cardinality() -> Result<()> {
+        // Same tables as in test_join_cardinality, whose inner join has
+        // an estimated cardinality of 800.
+        let left_col_stats = vec![
+            create_column_stats(Some(0), Some(100), Some(100)),
+            create_column_stats(Some(0), Some(500), Some(500)),
+            create_column_stats(Some(1000), Some(10000), None),
+        ];
+
+        let right_col_stats = vec![
+            create_column_stats(Some(0), Some(100), Some(50)),
+            create_column_stats(Some(0), Some(2000), Some(2500)),
+            create_column_stats(Some(0), Some(100), None),
+        ];
+
+        let cases = vec![
+            (JoinType::LeftSemi, 800, &left_col_stats),
+            (JoinType::RightSemi, 800, &right_col_stats),
+            (JoinType::LeftAnti, 1000, &left_col_stats),
+            (JoinType::RightAnti, 2000, &right_col_stats),
+        ];
+
+        for (join_type, expected_num_rows, expected_col_stats) in cases {
+            let join_on = vec![
+                (Column::new("a", 0), Column::new("c", 0)),
+                (Column::new("b", 1), Column::new("d", 1)),
+            ];
+
+            let partial_join_stats = estimate_join_cardinality(
+                &join_type,
+                create_stats(Some(1000), Some(left_col_stats.clone()), false),
+                create_stats(Some(2000), Some(right_col_stats.clone()), false),
+                &join_on,
+            )
+            .unwrap();
+            assert_eq!(partial_join_stats.num_rows, expected_num_rows);
+            assert_eq!(&partial_join_stats.column_statistics, expected_col_stats);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_semi_anti_join_cardinality_without_inner_stats() -> Result<()> {
+        let left_col_stats = vec![
+            create_column_stats(Some(0), Some(100), Some(100)),
+            create_column_stats(Some(0), Some(500), Some(500)),
+        ];
+        let join_on = vec![(Column::new("a", 0), Column::new("c", 0))];
+
+        // The statistics of the inner side are unknown, the estimate is
+        // bounded by the outer side
+        for join_type in [JoinType::LeftSemi, JoinType::LeftAnti] {
+            let partial_join_stats = estimate_join_cardinality(
+                &join_type,
+                create_stats(Some(1000), Some(left_col_stats.clone()), false),
+                create_stats(None, None, false),
+                &join_on,
+            )
+            .unwrap();
+            assert_eq!(partial_join_stats.num_rows, 1000);
+            assert_eq!(partial_join_stats.column_statistics, left_col_stats);
+        }
+
+        // Inner joins need the statistics of both sides
+        assert!(estimate_join_cardinality(
+            &JoinType::Inner,
+            create_stats(Some(1000), Some(left_col_stats), false),
+            create_stats(None, None, false),
+            &join_on,
+        )
+        .is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_join_cardinality_when_one_column_is_disjoint() -> Result<()> {
         // Left table (rows=1000)
@@ -19,11 +19,14 @@
 
 use super::expressions::PhysicalSortExpr;
 use super::{
-    common, project_schema, DisplayFormatType, ExecutionPlan, Partitioning,
+    common, project_schema, DisplayFormatType, ExecutionPlan, Partitioning, PhysicalExpr,
     RecordBatchStream, SendableRecordBatchStream, Statistics,
 };
 use crate::error::Result;
-use arrow::datatypes::SchemaRef;
+use arrow::array::{Array, ArrayRef, BooleanArray, UInt64Array};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{cast, filter_record_batch, not, prep_null_mask_filter};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use core::fmt;
 use futures::StreamExt;
@@ -31,10 +34,11 @@ use std::any::Any;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use crate::datasource::memory::PartitionData;
+use crate::datasource::memory::{PartitionData, TableVersion};
 use crate::execution::context::TaskContext;
 use crate::physical_plan::stream::RecordBatchStreamAdapter;
 use crate::physical_plan::Distribution;
+use datafusion_common::cast::as_boolean_array;
 use datafusion_common::DataFusionError;
 use futures::Stream;
 use tokio::sync::RwLock;
@@ -179,6 +183,26 @@ impl MemoryExec {
         self.sort_information = Some(sort_information);
         self
     }
+
+    /// The partitions to query
+    pub fn partitions(&self) -> &[Vec<RecordBatch>] {
+        &self.partitions
+    }
+
+    /// Schema representing the data before projection
+    pub fn original_schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Optional projection
+    pub fn projection(&self) -> &Option<Vec<usize>> {
+        &self.projection
+    }
+
+    /// Optional sort information
+    pub fn sort_information(&self) -> Option<&[PhysicalSortExpr]> {
+        self.sort_information.as_deref()
+    }
 }
 
 /// Iterator over batches
@@ -252,6 +276,8 @@ pub struct MemoryWriteExec {
     batches: Vec<PartitionData>,
     /// Schema describing the structure of the data.
     schema: SchemaRef,
+    /// Version of the MemTable, bumped by each write
+    version: Option<TableVersion>,
 }
 
 impl fmt::Debug for MemoryWriteExec {
@@ -309,11 +335,12 @@ impl ExecutionPlan for MemoryWriteExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(MemoryWriteExec::try_new(
-            children[0].clone(),
-            self.batches.clone(),
-            self.schema.clone(),
-        )?))
+        Ok(Arc::new(MemoryWriteExec {
+            input: children[0].clone(),
+            batches: self.batches.clone(),
+            schema: self.schema.clone(),
+            version: self.version.clone(),
+        }))
     }
 
     /// Execute the plan and return a stream of record batches for the specified partition.
@@ -331,6 +358,7 @@ impl ExecutionPlan for MemoryWriteExec {
             data,
             buffer: vec![],
             batch: self.batches[partition % batch_count].clone(),
+            version: self.version.clone(),
         };
 
         let stream = futures::stream::unfold(state, |mut state| async move {
@@ -340,7 +368,14 @@ impl ExecutionPlan for MemoryWriteExec {
                     Some(Err(e)) => return Some((Err(e), state)),
                     None => {
                         // stream is done, transfer all data to target PartitionData
-                        state.batch.write().await.append(&mut state.buffer);
+                        match &state.version {
+                            Some(version) => {
+                                let mut version = version.lock().await;
+                                state.batch.write().await.append(&mut state.buffer);
+                                *version += 1;
+                            }
+                            None => state.batch.write().await.append(&mut state.buffer),
+                        }
                         return None;
                     }
                 };
@@ -382,6 +417,8 @@ struct StreamState {
     buffer: Vec<RecordBatch>,
     /// target
     batch: PartitionData,
+    /// Version of the target table
+    version: Option<TableVersion>,
 }
 
 impl MemoryWriteExec {
@@ -396,8 +433,250 @@ impl MemoryWriteExec {
             input: plan,
             batches,
             schema,
+            version: None,
         })
     }
+
+    /// Bump `version` while holding its lock after writing the batches, so
+    /// concurrent transactions modifying a snapshot of the table detect the
+    /// write when they commit
+    pub fn with_version(mut self, version: TableVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+}
+
+/// Modification a [`MemoryDmlExec`] applies to the rows of an in-memory table
+#[derive(Debug, Clone)]
+pub enum MemoryDmlOp {
+    /// Delete the rows
+    Delete,
+    /// Set the columns at the given indices to the values of the expressions
+    Update(Vec<(usize, Arc<dyn PhysicalExpr>)>),
+}
+
+impl fmt::Display for MemoryDmlOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MemoryDmlOp::Delete => write!(f, "Delete"),
+            MemoryDmlOp::Update(assignments) => {
+                let assignments = assignments
+                    .iter()
+                    .map(|(index, expr)| format!("{index}={expr}"))
+                    .collect::<Vec<_>>();
+                write!(f, "Update({})", assignments.join(", "))
+            }
+        }
+    }
+}
+
+/// Execution plan for deleting or updating the rows of an in-memory table
+/// that match a filter. It produces a single row with the number of
+/// affected rows in a `count` column.
+///
+/// All partitions of the table are locked while the statement is applied,
+/// and are only modified if it succeeds on all of them.
+pub struct MemoryDmlExec {
+    /// Modification to apply
+    op: MemoryDmlOp,
+    /// Rows to modify, all rows if `None`
+    filter: Option<Arc<dyn PhysicalExpr>>,
+    /// Reference to the MemTable's partition data.
+    batches: Vec<PartitionData>,
+    /// Schema of the MemTable
+    table_schema: SchemaRef,
+    /// Schema of the produced row count
+    schema: SchemaRef,
+    /// Version of the MemTable, bumped by each modification
+    version: Option<TableVersion>,
+}
+
+impl fmt::Debug for MemoryDmlExec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "op: {}, schema: {:?}", self.op, self.table_schema)
+    }
+}
+
+impl MemoryDmlExec {
+    /// Create a new execution plan applying `op` to the rows of `batches`
+    /// that match `filter`
+    pub fn new(
+        op: MemoryDmlOp,
+        filter: Option<Arc<dyn PhysicalExpr>>,
+        batches: Vec<PartitionData>,
+        table_schema: SchemaRef,
+    ) -> Self {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "count",
+            DataType::UInt64,
+            false,
+        )]));
+        Self {
+            op,
+            filter,
+            batches,
+            table_schema,
+            schema,
+            version: None,
+        }
+    }
+
+    /// Bump `version` while holding its lock after modifying the batches, so
+    /// concurrent transactions modifying a snapshot of the table detect the
+    /// modification when they commit
+    pub fn with_version(mut self, version: TableVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+}
+
+impl ExecutionPlan for MemoryDmlExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "MemoryDmlExec invalid partition {partition}"
+            )));
+        }
+
+        let op = self.op.clone();
+        let filter = self.filter.clone();
+        let batches = self.batches.clone();
+        let table_schema = self.table_schema.clone();
+        let schema = self.schema.clone();
+        let version = self.version.clone();
+        let stream = futures::stream::once(async move {
+            let count = match version {
+                Some(version) => {
+                    let mut version = version.lock().await;
+                    let count =
+                        apply_dml(&op, filter.as_ref(), &batches, &table_schema).await?;
+                    *version += 1;
+                    count
+                }
+                None => apply_dml(&op, filter.as_ref(), &batches, &table_schema).await?,
+            };
+            let count: ArrayRef = Arc::new(UInt64Array::from(vec![count]));
+            Ok(RecordBatch::try_new(schema, vec![count])?)
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            stream,
+        )))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(f, "MemoryDmlExec: op={}", self.op)?;
+                if let Some(filter) = &self.filter {
+                    write!(f, ", filter={filter}")?;
+                }
+                write!(f, ", partitions={}", self.batches.len())
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// Applies `op` to the rows of all `partitions` that match `filter`, and
+/// returns the number of affected rows
+async fn apply_dml(
+    op: &MemoryDmlOp,
+    filter: Option<&Arc<dyn PhysicalExpr>>,
+    partitions: &[PartitionData],
+    table_schema: &SchemaRef,
+) -> Result<u64> {
+    // Lock all partitions up front, so the modification is applied to
+    // either all of them or none
+    let mut guards = Vec::with_capacity(partitions.len());
+    for partition in partitions {
+        guards.push(partition.write().await);
+    }
+
+    let mut count = 0;
+    let mut modified = Vec::with_capacity(guards.len());
+    for guard in guards.iter() {
+        let mut batches = Vec::with_capacity(guard.len());
+        for batch in guard.iter() {
+            let mask = match filter {
+                Some(filter) => {
+                    let mask = filter.evaluate(batch)?.into_array(batch.num_rows());
+                    let mask = as_boolean_array(&mask)?;
+                    // rows the filter evaluates to NULL for do not match
+                    match mask.null_count() {
+                        0 => mask.clone(),
+                        _ => prep_null_mask_filter(mask),
+                    }
+                }
+                None => BooleanArray::from(vec![true; batch.num_rows()]),
+            };
+            count += mask.true_count() as u64;
+
+            match op {
+                MemoryDmlOp::Delete => {
+                    let batch = filter_record_batch(batch, &not(&mask)?)?;
+                    if batch.num_rows() > 0 {
+                        batches.push(batch);
+                    }
+                }
+                MemoryDmlOp::Update(assignments) => {
+                    let mut columns = batch.columns().to_vec();
+                    for (index, expr) in assignments {
+                        let values = expr.evaluate(batch)?.into_array(batch.num_rows());
+                        let values =
+                            cast(&values, table_schema.field(*index).data_type())?;
+                        columns[*index] = zip(&mask, &values, &batch.columns()[*index])?;
+                    }
+                    batches.push(RecordBatch::try_new(batch.schema(), columns)?);
+                }
+            }
+        }
+        modified.push(batches);
+    }
+
+    for (guard, batches) in guards.iter_mut().zip(modified) {
+        **guard = batches;
+    }
+    Ok(count)
 }
 
 #[cfg(test)]
@@ -669,6 +669,7 @@ pub mod memory;
 pub mod metrics;
 pub mod planner;
 pub mod projection;
+pub mod recursive_query;
 pub mod repartition;
 pub mod sorts;
 pub mod stream;
@@ -679,6 +680,7 @@ pub mod union;
 pub mod unnest;
 pub mod values;
 pub mod windows;
+pub mod work_table;
 
 use crate::execution::context::TaskContext;
 use crate::physical_plan::common::AbortOnDropSingle;
@@ -18,17 +18,24 @@
 //! Physical query planner
 
 use super::analyze::AnalyzeExec;
+use super::recursive_query::RecursiveQueryExec;
 use super::unnest::UnnestExec;
 use super::{
     aggregates, empty::EmptyExec, joins::PartitionMode, udaf, union::UnionExec,
     values::ValuesExec, windows,
 };
-use crate::datasource::source_as_provider;
+use crate::datasource::file_format::csv::CsvFormat;
+use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
+use crate::datasource::file_format::json::JsonFormat;
+use crate::datasource::file_format::parquet::ParquetFormat;
+use crate::datasource::file_format::FileFormat;
+use crate::datasource::listing::ListingTableUrl;
+use crate::datasource::{source_as_provider, TableProvider};
 use crate::execution::context::{ExecutionProps, SessionState};
 use crate::logical_expr::utils::generate_sort_key;
 use crate::logical_expr::{
-    Aggregate, EmptyRelation, Join, Projection, Sort, SubqueryAlias, TableScan, Unnest,
-    Window,
+    Aggregate, EmptyRelation, Join, Projection, RecursiveQuery, Sort, SubqueryAlias,
+    TableScan, Unnest, Window,
 };
 use crate::logical_expr::{
     CrossJoin, Expr, LogicalPlan, Partitioning as LogicalPartitioning, PlanType,
@@ -38,8 +45,10 @@ use crate::logical_expr::{Limit, Values};
 use crate::physical_expr::create_physical_expr;
 use crate::physical_optimizer::optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
+use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
 use crate::physical_plan::explain::ExplainExec;
 use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
+use crate::physical_plan::file_format::{FileWriteConfig, FileWriteExec};
 use crate::physical_plan::filter::FilterExec;
 use crate::physical_plan::joins::HashJoinExec;
 use crate::physical_plan::joins::SortMergeJoinExec;
@@ -60,24 +69,31 @@ use crate::{
 use arrow::compute::SortOptions;
 use arrow::datatypes::{Schema, SchemaRef};
 use async_trait::async_trait;
-use datafusion_common::{DFSchema, ScalarValue};
+use datafusion_common::parsers::CompressionTypeVariant;
+use datafusion_common::{DFSchema, OwnedTableReference, ScalarValue};
 use datafusion_expr::expr::{
     self, AggregateFunction, AggregateUDF, Between, BinaryExpr, Cast, GetIndexedField,
     GroupingSet, InList, Like, ScalarUDF, TryCast, WindowFunction,
 };
-use datafusion_expr::expr_rewriter::unnormalize_cols;
+use datafusion_expr::expr_rewriter::{unnormalize_col, unnormalize_cols};
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
-use datafusion_expr::{logical_plan, DmlStatement, StringifiedPlan, WriteOp};
+use datafusion_expr::{
+    logical_plan, CopyTo, DmlStatement, StringifiedPlan, TransactionAccessMode, WriteOp,
+};
 use datafusion_expr::{WindowFrame, WindowFrameBound};
-use datafusion_optimizer::utils::unalias;
+use datafusion_optimizer::utils::{split_conjunction, unalias};
 use datafusion_physical_expr::expressions::Literal;
 use datafusion_sql::utils::window_expr_common_partition_keys;
 use futures::future::BoxFuture;
 use futures::{FutureExt, StreamExt, TryStreamExt};
 use itertools::Itertools;
 use log::{debug, trace};
+use object_store::path::Path;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::str::FromStr;
 use std::sync::Arc;
 
 fn create_function_physical_name(
@@ -543,6 +559,11 @@ impl DefaultPhysicalPlanner {
                     let name = table_name.table();
                     let schema = session_state.schema_for_ref(table_name)?;
                     if let Some(provider) = schema.table(name).await {
+                        check_transactional_dml(
+                            session_state,
+                            provider.as_ref(),
+                            table_name,
+                        )?;
                         let input_exec = self.create_initial_plan(input, session_state).await?;
                         provider.insert_into(session_state, input_exec).await
                     } else {
@@ -551,6 +572,58 @@ impl DefaultPhysicalPlanner {
                         )));
                     }
                 }
+                LogicalPlan::Dml(DmlStatement {
+                    table_name,
+                    op: WriteOp::Delete,
+                    input,
+                    ..
+                }) => {
+                    let name = table_name.table();
+                    let schema = session_state.schema_for_ref(table_name)?;
+                    if let Some(provider) = schema.table(name).await {
+                        check_transactional_dml(
+                            session_state,
+                            provider.as_ref(),
+                            table_name,
+                        )?;
+                        let mut filters = vec![];
+                        extract_dml_filters(input, &mut filters)?;
+                        provider.delete_from(session_state, filters).await
+                    } else {
+                        return Err(DataFusionError::Execution(format!(
+                            "Table '{table_name}' does not exist"
+                        )));
+                    }
+                }
+                LogicalPlan::Dml(DmlStatement {
+                    table_name,
+                    op: WriteOp::Update,
+                    input,
+                    ..
+                }) => {
+                    let name = table_name.table();
+                    let schema = session_state.schema_for_ref(table_name)?;
+                    if let Some(provider) = schema.table(name).await {
+                        check_transactional_dml(
+                            session_state,
+                            provider.as_ref(),
+                            table_name,
+                        )?;
+                        let (assignments, input) = extract_dml_assignments(input)?;
+                        let mut filters = vec![];
+                        extract_dml_filters(input, &mut filters)?;
+                        provider.update(session_state, assignments, filters).await
+                    } else {
+                        return Err(DataFusionError::Execution(format!(
+                            "Table '{table_name}' does not exist"
+                        )));
+                    }
+                }
+                LogicalPlan::Copy(copy) => {
+                    let input_exec =
+                        self.create_initial_plan(&copy.input, session_state).await?;
+                    create_copy_to_exec(copy, input_exec)
+                }
                 LogicalPlan::Values(Values {
                     values,
                     schema,
@@ -852,8 +925,22 @@ impl DefaultPhysicalPlanner {
                                 .collect::<Result<Vec<_>>>()?;
                             Partitioning::Hash(runtime_expr, *n)
                         }
-                        LogicalPartitioning::DistributeBy(_) => {
-                            return Err(DataFusionError::NotImplemented("Physical plan does not support DistributeBy partitioning".to_string()));
+                        LogicalPartitioning::DistributeBy(expr) => {
+                            let runtime_expr = expr
+                                .iter()
+                                .map(|e| {
+                                    self.create_physical_expr(
+                                        e,
+                                        input_dfschema,
+                                        &input_schema,
+                                        session_state,
+                                    )
+                                })
+                                .collect::<Result<Vec<_>>>()?;
+                            Partitioning::Hash(
+                                runtime_expr,
+                                session_state.config().target_partitions(),
+                            )
                         }
                     };
                     Ok(Arc::new(RepartitionExec::try_new(
@@ -861,7 +948,12 @@ impl DefaultPhysicalPlanner {
                         physical_partitioning,
                     )?))
                 }
-                LogicalPlan::Sort(Sort { expr, input, fetch, .. }) => {
+                LogicalPlan::Sort(Sort {
+                    expr,
+                    input,
+                    fetch,
+                    preserve_partitioning,
+                }) => {
                     let physical_input = self.create_initial_plan(input, session_state).await?;
                     let input_schema = physical_input.as_ref().schema();
                     let input_dfschema = input.as_ref().schema();
@@ -888,7 +980,8 @@ impl DefaultPhysicalPlanner {
                         })
                         .collect::<Result<Vec<_>>>()?;
                     let new_sort = SortExec::new(sort_expr, physical_input)
-                        .with_fetch(*fetch);
+                        .with_fetch(*fetch)
+                        .with_preserve_partitioning(*preserve_partitioning);
                     Ok(Arc::new(new_sort))
                 }
                 LogicalPlan::Join(Join {
@@ -1147,6 +1240,16 @@ impl DefaultPhysicalPlanner {
                     let schema = SchemaRef::new(schema.as_ref().to_owned().into());
                     Ok(Arc::new(UnnestExec::new(input, column_exec, schema)))
                 }
+                LogicalPlan::RecursiveQuery(RecursiveQuery { name, static_term, recursive_term, is_distinct }) => {
+                    let static_term = self.create_initial_plan(static_term, session_state).await?;
+                    let recursive_term = self.create_initial_plan(recursive_term, session_state).await?;
+                    Ok(Arc::new(RecursiveQueryExec::try_new(
+                        name.clone(),
+                        static_term,
+                        recursive_term,
+                        *is_distinct,
+                    )?))
+                }
                 LogicalPlan::Ddl(ddl) => {
                     // There is no default plan for DDl statements --
                     // it must be handled at a higher level (so that
@@ -1864,6 +1967,257 @@ impl DefaultPhysicalPlanner {
     }
 }
 
+/// Check that a DML statement on `provider` can run in the transaction in
+/// progress, if any
+fn check_transactional_dml(
+    session_state: &SessionState,
+    provider: &dyn TableProvider,
+    table_name: &OwnedTableReference,
+) -> Result<()> {
+    match session_state.transaction() {
+        Some(txn) if *txn.access_mode() == TransactionAccessMode::ReadOnly => {
+            Err(DataFusionError::Plan(format!(
+                "Cannot modify table '{table_name}' in a read-only transaction"
+            )))
+        }
+        Some(_) if !provider.supports_transactions() => {
+            Err(DataFusionError::NotImplemented(format!(
+                "Table '{table_name}' does not support transactions"
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Creates the [`WriterProperties`] of Parquet files written by `COPY TO`
+/// from its compression and format specific options
+fn parquet_writer_properties(
+    compression: Option<Compression>,
+    options: &HashMap<String, String>,
+) -> Result<WriterProperties> {
+    fn parse<T: FromStr>(key: &str, value: &str) -> Result<T> {
+        value.parse().map_err(|_| {
+            DataFusionError::Plan(format!(
+                "Invalid value {value} for COPY TO option {key}"
+            ))
+        })
+    }
+
+    let mut builder = WriterProperties::builder();
+    if let Some(compression) = compression {
+        builder = builder.set_compression(compression);
+    }
+    for (key, value) in options {
+        builder = match key.to_lowercase().as_str() {
+            "max_row_group_size" => builder.set_max_row_group_size(parse(key, value)?),
+            "data_pagesize_limit" => builder.set_data_pagesize_limit(parse(key, value)?),
+            "write_batch_size" => builder.set_write_batch_size(parse(key, value)?),
+            "dictionary_enabled" => builder.set_dictionary_enabled(parse(key, value)?),
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "Unsupported COPY TO option {key} for PARQUET files"
+                )))
+            }
+        };
+    }
+    Ok(builder.build())
+}
+
+/// Creates the plan writing the output of `input` into the files of a
+/// COPY TO statement
+fn create_copy_to_exec(
+    copy: &CopyTo,
+    input: Arc<dyn ExecutionPlan>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let CopyTo {
+        output_url,
+        file_type,
+        has_header,
+        delimiter,
+        file_compression_type,
+        partition_by,
+        options,
+        ..
+    } = copy;
+
+    let file_type = FileType::from_str(file_type)?;
+    if !matches!(file_type, FileType::PARQUET) {
+        if let Some(key) = options.keys().next() {
+            return Err(DataFusionError::Plan(format!(
+                "Unsupported COPY TO option {key} for {file_type:?} files"
+            )));
+        }
+    }
+    let (format, file_compression_type): (Arc<dyn FileFormat>, _) = match file_type {
+        FileType::CSV => (
+            Arc::new(
+                CsvFormat::default()
+                    .with_has_header(*has_header)
+                    .with_delimiter(*delimiter as u8),
+            ),
+            FileCompressionType::from(*file_compression_type),
+        ),
+        FileType::JSON => (
+            Arc::new(JsonFormat::default()),
+            FileCompressionType::from(*file_compression_type),
+        ),
+        FileType::PARQUET => {
+            // Parquet files are not compressed as a whole, but page by page
+            let compression = match file_compression_type {
+                CompressionTypeVariant::UNCOMPRESSED => None,
+                CompressionTypeVariant::GZIP => {
+                    Some(Compression::GZIP(Default::default()))
+                }
+                CompressionTypeVariant::ZSTD => {
+                    Some(Compression::ZSTD(Default::default()))
+                }
+                other => {
+                    return Err(DataFusionError::Plan(format!(
+                        "Compression {} is not supported for PARQUET files",
+                        other.to_string()
+                    )))
+                }
+            };
+            let props = if compression.is_none() && options.is_empty() {
+                None
+            } else {
+                Some(parquet_writer_properties(compression, options)?)
+            };
+            (
+                Arc::new(ParquetFormat::default().with_writer_properties(props)),
+                FileCompressionType::UNCOMPRESSED,
+            )
+        }
+        FileType::AVRO => {
+            return Err(DataFusionError::NotImplemented(
+                "Writing AVRO files is not implemented".to_string(),
+            ))
+        }
+    };
+    let file_extension =
+        file_type.get_ext_with_compression(file_compression_type.clone())?;
+
+    // Partitioned output is always written into a directory, otherwise the
+    // output is a single file unless the location is a directory
+    let url = ListingTableUrl::parse_output(output_url)?;
+    let (table_path, file_name, input) =
+        if url.is_collection() || !partition_by.is_empty() {
+            (url.prefix().clone(), None, input)
+        } else {
+            let mut parts: Vec<_> = url.prefix().parts().collect();
+            let file_name = parts.pop().ok_or_else(|| {
+                DataFusionError::Plan(format!("Invalid COPY TO location {output_url}"))
+            })?;
+            let input: Arc<dyn ExecutionPlan> =
+                if input.output_partitioning().partition_count() > 1 {
+                    Arc::new(CoalescePartitionsExec::new(input))
+                } else {
+                    input
+                };
+            (
+                Path::from_iter(parts),
+                Some(file_name.as_ref().to_string()),
+                input,
+            )
+        };
+
+    // The partition columns are the last columns of the input
+    let input_schema = input.schema();
+    let num_file_columns = input_schema.fields().len() - partition_by.len();
+    let file_schema =
+        Arc::new(input_schema.project(&(0..num_file_columns).collect::<Vec<_>>())?);
+
+    let config = FileWriteConfig {
+        object_store_url: url.object_store(),
+        table_path,
+        file_extension,
+        file_name,
+        format,
+        file_compression_type,
+        file_schema,
+        table_partition_cols: partition_by.clone(),
+    };
+    Ok(Arc::new(FileWriteExec::new(input, config)))
+}
+
+/// Collects the predicates selecting the rows a DELETE or UPDATE statement
+/// modifies from the (optimized) `input` of its [`DmlStatement`], with the
+/// column qualifiers removed.
+///
+/// The input is expected to be a scan of the target table, possibly below
+/// filters, aliases and column-only projections, or an empty relation if the
+/// filters can never be true.
+fn extract_dml_filters(input: &LogicalPlan, filters: &mut Vec<Expr>) -> Result<()> {
+    match input {
+        LogicalPlan::Filter(filter) => {
+            filters.extend(unnormalize_cols(
+                split_conjunction(&filter.predicate).into_iter().cloned(),
+            ));
+            extract_dml_filters(&filter.input, filters)
+        }
+        LogicalPlan::TableScan(TableScan {
+            filters: scan_filters,
+            ..
+        }) => {
+            filters.extend(unnormalize_cols(scan_filters.iter().cloned()));
+            Ok(())
+        }
+        LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+            extract_dml_filters(input, filters)
+        }
+        // the optimizer replaces the scan if the filters can never be true
+        LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            ..
+        }) => {
+            filters.push(Expr::Literal(ScalarValue::Boolean(Some(false))));
+            Ok(())
+        }
+        LogicalPlan::Projection(Projection { expr, input, .. })
+            if expr.iter().all(|e| matches!(e, Expr::Column(_))) =>
+        {
+            extract_dml_filters(input, filters)
+        }
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "Unsupported input of DML statement: {}",
+            input.display()
+        ))),
+    }
+}
+
+/// Splits the input of an UPDATE statement into the `(column, value)`
+/// assignments of its top-level projection, omitting columns that are
+/// left unchanged, and the input of that projection.
+fn extract_dml_assignments(
+    input: &LogicalPlan,
+) -> Result<(Vec<(String, Expr)>, &LogicalPlan)> {
+    match input {
+        LogicalPlan::Projection(Projection { expr, input, .. }) => {
+            let assignments = expr
+                .iter()
+                .filter_map(|e| {
+                    let (name, value) = match e {
+                        Expr::Alias(value, name) => (name.clone(), value.as_ref()),
+                        Expr::Column(column) => (column.name.clone(), e),
+                        _ => {
+                            return Some(Err(DataFusionError::Internal(format!(
+                                "Unexpected expression in UPDATE statement: {e}"
+                            ))))
+                        }
+                    };
+                    match value {
+                        Expr::Column(column) if column.name == name => None,
+                        _ => Some(Ok((name, unnormalize_col(value.clone())))),
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok((assignments, input))
+        }
+        // the optimizer removes projections that leave all columns unchanged
+        _ => Ok((vec![], input)),
+    }
+}
+
 fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
     match value {
         (Ok(e), Ok(e1)) => Ok((e, e1)),
@@ -37,10 +37,12 @@ use arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use futures::stream::{Stream, StreamExt};
 use log::trace;
 
-use super::expressions::{Column, PhysicalSortExpr};
+use super::expressions::{Column, Literal, PhysicalSortExpr};
 use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
 use super::{RecordBatchStream, SendableRecordBatchStream, Statistics};
 
+use datafusion_common::ScalarValue;
+use datafusion_physical_expr::intervals::statistics::estimate_range;
 use datafusion_physical_expr::{
     normalize_out_expr_with_columns_map, project_equivalence_properties,
     project_ordering_equivalence_properties, OrderingEquivalenceProperties,
@@ -292,6 +294,7 @@ impl ExecutionPlan for ProjectionExec {
     fn statistics(&self) -> Statistics {
         stats_projection(
             self.input.statistics(),
+            &self.input.schema(),
             self.expr.iter().map(|(e, _)| Arc::clone(e)),
         )
     }
@@ -317,17 +320,19 @@ fn get_field_metadata(
 
 fn stats_projection(
     stats: Statistics,
+    input_schema: &Schema,
     exprs: impl Iterator<Item = Arc<dyn PhysicalExpr>>,
 ) -> Statistics {
+    let num_rows = stats.num_rows;
     let column_statistics = stats.column_statistics.map(|input_col_stats| {
         exprs
             .map(|e| {
                 if let Some(col) = e.as_any().downcast_ref::<Column>() {
                     input_col_stats[col.index()].clone()
+                } else if let Some(literal) = e.as_any().downcast_ref::<Literal>() {
+                    literal_statistics(literal.value(), num_rows)
                 } else {
-                    // TODO stats: estimate more statistics from expressions
-                    // (expressions should compute their statistics themselves)
-                    ColumnStatistics::default()
+                    expr_statistics(&e, input_schema, &input_col_stats)
                 }
             })
             .collect()
@@ -335,13 +340,50 @@ fn stats_projection(
 
     Statistics {
         is_exact: stats.is_exact,
-        num_rows: stats.num_rows,
+        num_rows,
         column_statistics,
         // TODO stats: knowing the type of the new columns we can guess the output size
         total_byte_size: None,
     }
 }
 
+/// Statistics of a column whose value is `value` in all `num_rows` rows
+fn literal_statistics(value: &ScalarValue, num_rows: Option<usize>) -> ColumnStatistics {
+    if value.is_null() {
+        ColumnStatistics {
+            null_count: num_rows,
+            distinct_count: Some(0),
+            ..Default::default()
+        }
+    } else {
+        ColumnStatistics {
+            null_count: Some(0),
+            max_value: Some(value.clone()),
+            min_value: Some(value.clone()),
+            distinct_count: Some(1),
+        }
+    }
+}
+
+/// Estimates the range of the values of `expr` from the ranges of the input
+/// columns it references, if interval arithmetic supports the expression
+fn expr_statistics(
+    expr: &Arc<dyn PhysicalExpr>,
+    input_schema: &Schema,
+    input_col_stats: &[ColumnStatistics],
+) -> ColumnStatistics {
+    match estimate_range(expr, input_schema, input_col_stats) {
+        Ok(Some(range)) if !range.lower.is_unbounded() && !range.upper.is_unbounded() => {
+            ColumnStatistics {
+                max_value: Some(range.upper.value),
+                min_value: Some(range.lower.value),
+                ..Default::default()
+            }
+        }
+        _ => ColumnStatistics::default(),
+    }
+}
+
 impl ProjectionStream {
     fn batch_project(&self, batch: &RecordBatch) -> Result<RecordBatch> {
         // records time on drop
@@ -410,6 +452,7 @@ mod tests {
     use crate::scalar::ScalarValue;
     use crate::test::{self};
     use crate::test_util;
+    use arrow::datatypes::DataType;
     use datafusion_expr::Operator;
     use datafusion_physical_expr::expressions::binary;
     use futures::future;
@@ -512,6 +555,14 @@ mod tests {
         Ok(())
     }
 
+    fn get_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("col0", DataType::Int64, false),
+            Field::new("col1", DataType::Utf8, true),
+            Field::new("col2", DataType::Float32, true),
+        ])
+    }
+
     #[tokio::test]
     async fn test_stats_projection_columns_only() {
         let source = Statistics {
@@ -545,7 +596,7 @@ mod tests {
             Arc::new(expressions::Column::new("col0", 0)),
         ];
 
-        let result = stats_projection(source, exprs.into_iter());
+        let result = stats_projection(source, &get_schema(), exprs.into_iter());
 
         let expected = Statistics {
             is_exact: true,
@@ -569,4 +620,68 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[tokio::test]
+    async fn test_stats_projection_expressions() -> Result<()> {
+        let schema = get_schema();
+        let source = Statistics {
+            is_exact: true,
+            num_rows: Some(5),
+            total_byte_size: Some(23),
+            column_statistics: Some(vec![
+                ColumnStatistics {
+                    distinct_count: Some(5),
+                    max_value: Some(ScalarValue::Int64(Some(21))),
+                    min_value: Some(ScalarValue::Int64(Some(-4))),
+                    null_count: Some(0),
+                },
+                ColumnStatistics::default(),
+                ColumnStatistics::default(),
+            ]),
+        };
+
+        let exprs: Vec<Arc<dyn PhysicalExpr>> = vec![
+            // col0 + 10
+            binary(
+                col("col0", &schema)?,
+                Operator::Plus,
+                expressions::lit(10i64),
+                &schema,
+            )?,
+            expressions::lit("a"),
+            // col1 || 'a' is not supported by interval arithmetic
+            binary(
+                col("col1", &schema)?,
+                Operator::StringConcat,
+                expressions::lit("a"),
+                &schema,
+            )?,
+        ];
+
+        let result = stats_projection(source, &schema, exprs.into_iter());
+
+        let expected = Statistics {
+            is_exact: true,
+            num_rows: Some(5),
+            total_byte_size: None,
+            column_statistics: Some(vec![
+                ColumnStatistics {
+                    distinct_count: None,
+                    max_value: Some(ScalarValue::Int64(Some(31))),
+                    min_value: Some(ScalarValue::Int64(Some(6))),
+                    null_count: None,
+                },
+                ColumnStatistics {
+                    distinct_count: Some(1),
+                    max_value: Some(ScalarValue::Utf8(Some(String::from("a")))),
+                    min_value: Some(ScalarValue::Utf8(Some(String::from("a")))),
+                    null_count: Some(0),
+                },
+                ColumnStatistics::default(),
+            ]),
+        };
+
+        assert_eq!(result, expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,411 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the recursive query plan
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow::array::BooleanArray;
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use futures::{ready, Stream, StreamExt};
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+use crate::physical_plan::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet,
+};
+use crate::physical_plan::work_table::{WorkTable, WorkTableExec};
+use crate::physical_plan::{
+    DisplayFormatType, Distribution, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+
+use super::expressions::PhysicalSortExpr;
+
+/// Execution plan of a recursive query, i.e. of a `WITH RECURSIVE` common
+/// table expression.
+///
+/// The plan first outputs the rows of the static term. It then repeatedly
+/// executes the recursive term, which reads the rows output by the previous
+/// term through the [`WorkTableExec`]s of the query, until an iteration
+/// outputs no rows. If the query is distinct (`UNION` rather than
+/// `UNION ALL`), rows that were already output are discarded.
+#[derive(Debug)]
+pub struct RecursiveQueryExec {
+    /// Name of the query, matching the name of its work table scans
+    name: String,
+    /// The work table shared with the work table scans of the recursive term
+    work_table: Arc<WorkTable>,
+    /// The term that is executed once to start the recursion
+    static_term: Arc<dyn ExecutionPlan>,
+    /// The term that is executed once per iteration
+    recursive_term: Arc<dyn ExecutionPlan>,
+    /// Whether rows that were already output are discarded
+    is_distinct: bool,
+    /// Output schema, whose columns are nullable if they are nullable in
+    /// either of the terms
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl RecursiveQueryExec {
+    /// Create a new RecursiveQueryExec, assigning a new work table to the
+    /// [`WorkTableExec`]s called `name` in `recursive_term`
+    pub fn try_new(
+        name: String,
+        static_term: Arc<dyn ExecutionPlan>,
+        recursive_term: Arc<dyn ExecutionPlan>,
+        is_distinct: bool,
+    ) -> Result<Self> {
+        let work_table = Arc::new(WorkTable::new());
+        let recursive_term = assign_work_table(recursive_term, &name, &work_table)?;
+        let static_schema = static_term.schema();
+        let fields = static_schema
+            .fields()
+            .iter()
+            .zip(recursive_term.schema().fields())
+            .map(|(field, recursive_field)| {
+                field
+                    .as_ref()
+                    .clone()
+                    .with_nullable(field.is_nullable() || recursive_field.is_nullable())
+            })
+            .collect::<Vec<_>>();
+        let schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            static_schema.metadata().clone(),
+        ));
+        Ok(Self {
+            name,
+            work_table,
+            static_term,
+            recursive_term,
+            is_distinct,
+            schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+        })
+    }
+
+    /// Name of the query
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The term that is executed once to start the recursion
+    pub fn static_term(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.static_term
+    }
+
+    /// The term that is executed once per iteration
+    pub fn recursive_term(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.recursive_term
+    }
+
+    /// Whether rows that were already output are discarded
+    pub fn is_distinct(&self) -> bool {
+        self.is_distinct
+    }
+}
+
+impl ExecutionPlan for RecursiveQueryExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.static_term.clone(), self.recursive_term.clone()]
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition, Distribution::SinglePartition]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![false, false]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> bool {
+        false
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(RecursiveQueryExec::try_new(
+            self.name.clone(),
+            children[0].clone(),
+            children[1].clone(),
+            self.is_distinct,
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        // RecursiveQueryExec has a single output partition
+        if 0 != partition {
+            return Err(DataFusionError::Internal(format!(
+                "RecursiveQueryExec invalid partition {partition}"
+            )));
+        }
+
+        // RecursiveQueryExec requires a single input partition for both terms
+        if 1 != self.static_term.output_partitioning().partition_count()
+            || 1 != self.recursive_term.output_partitioning().partition_count()
+        {
+            return Err(DataFusionError::Internal(
+                "RecursiveQueryExec requires a single input partition".to_owned(),
+            ));
+        }
+
+        let schema = self.schema();
+        let distinct_rows = if self.is_distinct {
+            let converter = RowConverter::new(
+                schema
+                    .fields()
+                    .iter()
+                    .map(|f| SortField::new(f.data_type().clone()))
+                    .collect(),
+            )?;
+            Some((converter, HashSet::new()))
+        } else {
+            None
+        };
+
+        let reservation =
+            MemoryConsumer::new(format!("RecursiveQueryExec[{}]", self.name))
+                .register(context.memory_pool());
+        let input = self.static_term.execute(0, context.clone())?;
+
+        Ok(Box::pin(RecursiveQueryStream {
+            schema,
+            context,
+            work_table: self.work_table.clone(),
+            recursive_term: self.recursive_term.clone(),
+            input,
+            buffer: vec![],
+            buffer_size: 0,
+            work_table_size: 0,
+            distinct_rows,
+            reservation,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "RecursiveQueryExec: name={}, is_distinct={}",
+                    self.name, self.is_distinct
+                )
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// Replace the [`WorkTableExec`]s called `name` in `plan` with copies
+/// reading from `work_table`
+fn assign_work_table(
+    plan: Arc<dyn ExecutionPlan>,
+    name: &str,
+    work_table: &Arc<WorkTable>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    plan.transform_up(
+        &|plan| match plan.as_any().downcast_ref::<WorkTableExec>() {
+            Some(exec) if exec.name() == name => Ok(Transformed::Yes(Arc::new(
+                exec.with_work_table(work_table.clone()),
+            ))),
+            _ => Ok(Transformed::No(plan)),
+        },
+    )
+}
+
+/// Create a fresh copy of `plan`, so that state an operator keeps between
+/// executions, such as the build side of a hash join, is not reused by the
+/// next iteration
+fn reset_plan_states(plan: Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
+    plan.transform_up(&|plan| {
+        let children = plan.children();
+        if children.is_empty() {
+            Ok(Transformed::No(plan))
+        } else {
+            Ok(Transformed::Yes(plan.with_new_children(children)?))
+        }
+    })
+}
+
+/// Stream of the rows of a [`RecursiveQueryExec`]
+struct RecursiveQueryStream {
+    /// Output schema
+    schema: SchemaRef,
+    /// Context used to execute the recursive term
+    context: Arc<TaskContext>,
+    /// The work table read by the recursive term
+    work_table: Arc<WorkTable>,
+    /// The term that is executed once per iteration
+    recursive_term: Arc<dyn ExecutionPlan>,
+    /// The output of the static term, or of the current iteration
+    input: SendableRecordBatchStream,
+    /// The rows output by the current term, which are written to the work
+    /// table once the term is complete
+    buffer: Vec<RecordBatch>,
+    /// Memory used by `buffer`
+    buffer_size: usize,
+    /// Memory used by the work table
+    work_table_size: usize,
+    /// The rows that were already output, if the query is distinct
+    distinct_rows: Option<(RowConverter, HashSet<OwnedRow>)>,
+    /// Memory used by the buffered rows and the work table
+    reservation: MemoryReservation,
+    /// Execution metrics
+    baseline_metrics: BaselineMetrics,
+}
+
+impl RecursiveQueryStream {
+    /// Remove the rows of `batch` that were already output, if the query is
+    /// distinct
+    fn deduplicate(&mut self, batch: RecordBatch) -> Result<RecordBatch> {
+        let (converter, seen) = match self.distinct_rows.as_mut() {
+            Some(distinct_rows) => distinct_rows,
+            None => return Ok(batch),
+        };
+
+        let rows = converter.convert_columns(batch.columns())?;
+        let mut added_size = 0;
+        let keep: BooleanArray = rows
+            .iter()
+            .map(|row| {
+                let size = row.as_ref().len() + std::mem::size_of::<OwnedRow>();
+                let is_new = seen.insert(row.owned());
+                if is_new {
+                    added_size += size;
+                }
+                Some(is_new)
+            })
+            .collect();
+        self.reservation.try_grow(added_size)?;
+
+        Ok(filter_record_batch(&batch, &keep)?)
+    }
+
+    /// Start the next iteration, reading the rows of the completed term.
+    /// Returns false if the completed term output no rows
+    fn next_iteration(&mut self) -> Result<bool> {
+        if self.buffer.is_empty() {
+            return Ok(false);
+        }
+
+        // The rows of the previous work table are released
+        self.work_table.write(std::mem::take(&mut self.buffer));
+        self.reservation.shrink(self.work_table_size);
+        self.work_table_size = std::mem::take(&mut self.buffer_size);
+
+        let plan = reset_plan_states(self.recursive_term.clone())?;
+        self.input = plan.execute(0, self.context.clone())?;
+        Ok(true)
+    }
+
+    fn poll_next_inner(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        loop {
+            match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    // The batches of both terms are output with the schema of
+                    // the query, which differs in the nullability of columns
+                    let batch = RecordBatch::try_new(
+                        self.schema.clone(),
+                        batch.columns().to_vec(),
+                    )?;
+                    let batch = self.deduplicate(batch)?;
+                    if batch.num_rows() == 0 {
+                        continue;
+                    }
+                    let size = batch.get_array_memory_size();
+                    self.reservation.try_grow(size)?;
+                    self.buffer_size += size;
+                    self.buffer.push(batch.clone());
+                    return Poll::Ready(Some(Ok(batch)));
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {
+                    if !self.next_iteration()? {
+                        return Poll::Ready(None);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Stream for RecursiveQueryStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_inner(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for RecursiveQueryStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
@@ -24,6 +24,7 @@ mod merge;
 pub mod sort;
 pub mod sort_preserving_merge;
 mod stream;
+mod topk;
 
 pub use index::RowIndex;
 pub(crate) use merge::streaming_merge;
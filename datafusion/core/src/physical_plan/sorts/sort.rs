@@ -31,6 +31,7 @@ use crate::physical_plan::metrics::{
     BaselineMetrics, CompositeMetricsSet, MemTrackingMetrics, MetricsSet,
 };
 use crate::physical_plan::sorts::merge::streaming_merge;
+use crate::physical_plan::sorts::topk::TopK;
 use crate::physical_plan::stream::{RecordBatchReceiverStream, RecordBatchStreamAdapter};
 use crate::physical_plan::{
     DisplayFormatType, Distribution, EmptyRecordBatchStream, ExecutionPlan, Partitioning,
@@ -179,6 +180,7 @@ impl ExternalSorter {
                 &self.expr,
                 merge_metrics,
                 self.session_config.batch_size(),
+                self.fetch,
             )
         } else if !self.in_mem_batches.is_empty() {
             let tracking_metrics = self
@@ -291,14 +293,13 @@ impl ExternalSorter {
             })
             .collect::<Result<_>>()?;
 
-        // TODO: Pushdown fetch to streaming merge (#6000)
-
         streaming_merge(
             streams,
             self.schema.clone(),
             &self.expr,
             metrics,
             self.session_config.batch_size(),
+            self.fetch,
         )
     }
 }
@@ -365,14 +366,21 @@ async fn spill_sorted_batches(
     }
 }
 
-fn read_spill_as_stream(
-    path: NamedTempFile,
+/// Reads the batches of the spill file at `path` as a stream.
+///
+/// `path` is kept alive until the whole file has been read, which also allows
+/// passing a [`NamedTempFile`] that is deleted afterwards.
+pub(crate) fn read_spill_as_stream<P>(
+    path: P,
     schema: SchemaRef,
-) -> Result<SendableRecordBatchStream> {
+) -> Result<SendableRecordBatchStream>
+where
+    P: AsRef<Path> + Debug + Send + 'static,
+{
     let (sender, receiver): (Sender<Result<RecordBatch>>, Receiver<Result<RecordBatch>>) =
         tokio::sync::mpsc::channel(2);
     let join_handle = task::spawn_blocking(move || {
-        if let Err(e) = read_spill(sender, path.path()) {
+        if let Err(e) = read_spill(sender, path.as_ref()) {
             error!("Failure while reading spill file: {:?}. Error: {}", path, e);
         }
     });
@@ -406,9 +414,10 @@ fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
     let file = BufReader::new(File::open(path)?);
     let reader = FileReader::try_new(file, None)?;
     for batch in reader {
-        sender
-            .blocking_send(batch.map_err(Into::into))
-            .map_err(|e| DataFusionError::Execution(format!("{e}")))?;
+        if sender.blocking_send(batch.map_err(Into::into)).is_err() {
+            // the stream was dropped before reading all the batches
+            break;
+        }
     }
     Ok(())
 }
@@ -417,6 +426,9 @@ fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
 ///
 /// This operator supports sorting datasets that are larger than the
 /// memory allotted by the memory manager, by spilling to disk.
+///
+/// If a `fetch` is set, only the first `fetch` rows of each partition are
+/// kept in memory while reading the input, instead of the whole input.
 #[derive(Debug)]
 pub struct SortExec {
     /// Input schema
@@ -646,6 +658,40 @@ async fn do_sort(
         context.task_id()
     );
     let schema = input.schema();
+    let mut heap_batches = vec![];
+    if let Some(fetch) = fetch {
+        let mut topk = TopK::try_new(
+            partition_id,
+            schema.clone(),
+            expr.clone(),
+            fetch,
+            context.session_config().batch_size(),
+            &context.runtime_env(),
+            &metrics_set,
+        )?;
+        let mut exhausted = false;
+        while let Some(batch) = input.next().await {
+            topk.insert_batch(batch?)?;
+            match topk.try_reserve() {
+                Ok(()) => {}
+                Err(DataFusionError::ResourcesExhausted(e)) => {
+                    debug!(
+                        "TopK of partition {partition_id} exceeds the memory limit, \
+                         falling back to a spilling sort: {e}"
+                    );
+                    exhausted = true;
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if !exhausted {
+            return topk.emit();
+        }
+        // The rows of the heap are the first rows of the input read so far,
+        // the sorter below takes over with the rest of the input
+        heap_batches = topk.into_batches()?;
+    }
     let mut sorter = ExternalSorter::new(
         partition_id,
         schema.clone(),
@@ -655,6 +701,9 @@ async fn do_sort(
         context.runtime_env(),
         fetch,
     );
+    for batch in heap_batches {
+        sorter.insert_batch(batch).await?;
+    }
     while let Some(batch) = input.next().await {
         let batch = batch?;
         sorter.insert_batch(batch).await?;
@@ -874,6 +923,166 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sort_fetch_topk() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+
+        // a permutation of 0..200, in 20 batches
+        let batches = (0..20)
+            .map(|batch| {
+                let values = (0..10).map(|row| Some((batch * 10 + row) * 37 % 200));
+                let array = Int32Array::from_iter(values);
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(array)])
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+
+        // (fetch, expected values)
+        let test_options = vec![
+            (0, vec![]),
+            (7, (193..200).rev().collect::<Vec<_>>()),
+            (250, (0..200).rev().collect::<Vec<_>>()),
+        ];
+        for (fetch, expected) in test_options {
+            let sort_exec = Arc::new(
+                SortExec::new(
+                    vec![PhysicalSortExpr {
+                        expr: col("a", &schema)?,
+                        options: SortOptions {
+                            descending: true,
+                            nulls_first: true,
+                        },
+                    }],
+                    input.clone(),
+                )
+                .with_fetch(Some(fetch)),
+            );
+
+            let result = collect(sort_exec.clone(), task_ctx.clone()).await?;
+            let values = result
+                .iter()
+                .flat_map(|batch| {
+                    as_primitive_array::<Int32Type>(batch.column(0)).unwrap()
+                })
+                .map(|value| value.unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(values, expected, "with fetch: {fetch}");
+
+            let metrics = sort_exec.metrics().unwrap();
+            assert_eq!(metrics.output_rows().unwrap(), expected.len());
+            assert_eq!(metrics.spill_count().unwrap(), 0);
+        }
+
+        assert_eq!(
+            session_ctx.runtime_env().memory_pool.reserved(),
+            0,
+            "The sort should have returned all memory used back to the memory manager"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sort_fetch_topk_ties() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+
+        // `a` repeats 0, 1, 2 and `b` numbers the rows, in 20 batches
+        let batches = (0..20)
+            .map(|batch| {
+                let b = (0..10).map(|row| batch * 10 + row);
+                let a = b.clone().map(|b| b % 3);
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(Int32Array::from_iter_values(a)),
+                        Arc::new(Int32Array::from_iter_values(b)),
+                    ],
+                )
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+
+        let sort_exec = Arc::new(
+            SortExec::new(
+                vec![PhysicalSortExpr {
+                    expr: col("a", &schema)?,
+                    options: SortOptions::default(),
+                }],
+                input,
+            )
+            .with_fetch(Some(50)),
+        );
+
+        // rows with equal keys are kept in input order
+        let result = collect(sort_exec, task_ctx).await?;
+        let values = result
+            .iter()
+            .flat_map(|batch| as_primitive_array::<Int32Type>(batch.column(1)).unwrap())
+            .map(|value| value.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, (0..50).map(|row| row * 3).collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sort_fetch_topk_spill() -> Result<()> {
+        // the top rows of a large fetch do not fit in memory
+        let config = RuntimeConfig::new().with_memory_limit(64 * 1024, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(config)?);
+        let session_ctx = SessionContext::with_config_rt(SessionConfig::new(), runtime);
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+
+        // a permutation of 0..100_000, in 100 batches
+        let batches = (0..100)
+            .map(|batch| {
+                let values =
+                    (0..1000).map(|row| Some((batch * 1000 + row) * 37 % 100_000));
+                let array = Int32Array::from_iter(values);
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(array)])
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+
+        let sort_exec = Arc::new(
+            SortExec::new(
+                vec![PhysicalSortExpr {
+                    expr: col("a", &schema)?,
+                    options: SortOptions::default(),
+                }],
+                input,
+            )
+            .with_fetch(Some(50_000)),
+        );
+
+        let result = collect(sort_exec.clone(), session_ctx.task_ctx()).await?;
+        let values = result
+            .iter()
+            .flat_map(|batch| as_primitive_array::<Int32Type>(batch.column(0)).unwrap())
+            .map(|value| value.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, (0..50_000).collect::<Vec<_>>());
+
+        let metrics = sort_exec.metrics().unwrap();
+        assert_eq!(metrics.output_rows().unwrap(), 50_000);
+        assert!(metrics.spill_count().unwrap() > 0);
+
+        assert_eq!(
+            session_ctx.runtime_env().memory_pool.reserved(),
+            0,
+            "The sort should have returned all memory used back to the memory manager"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_sort_metadata() -> Result<()> {
         let session_ctx = SessionContext::new();
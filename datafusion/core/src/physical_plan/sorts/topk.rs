@@ -0,0 +1,425 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! TopK: the first `k` rows of a sort, computed with bounded memory
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Arc;
+
+use arrow::compute::interleave;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use arrow::row::{OwnedRow, Row, RowConverter, SortField};
+use futures::stream;
+
+use crate::error::Result;
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use crate::execution::runtime_env::RuntimeEnv;
+use crate::physical_plan::expressions::PhysicalSortExpr;
+use crate::physical_plan::metrics::{BaselineMetrics, CompositeMetricsSet};
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::SendableRecordBatchStream;
+
+/// Computes the first `k` rows of its input according to the sort
+/// expressions, as `SortExec` does when a `fetch` is known.
+///
+/// Instead of buffering and sorting the whole input, `TopK` keeps the sort
+/// keys of the best `k` rows seen so far in a max-heap, encoded with a
+/// [`RowConverter`] as done by the streaming merge. Each row of a new batch
+/// is compared with the worst row of the heap and only replaces it if it
+/// sorts before it, so batches without any qualifying row are discarded
+/// right away. Rows with equal sort keys are kept in input order, as
+/// done by the full sort.
+///
+/// The batches holding rows of the heap are retained until these rows are
+/// evicted, and are periodically compacted into a single batch, so that
+/// the memory used is proportional to `k` rather than to the input size.
+pub(crate) struct TopK {
+    /// Schema of the input and output batches
+    schema: SchemaRef,
+    /// Runtime metrics
+    metrics: BaselineMetrics,
+    /// Reservation for the memory used by the heap and the retained batches
+    reservation: MemoryReservation,
+    /// Target number of rows of the output batches
+    batch_size: usize,
+    /// Sort expressions
+    expr: Arc<[PhysicalSortExpr]>,
+    /// Converts the sort keys to comparable rows
+    row_converter: RowConverter,
+    /// The best `k` rows seen so far
+    heap: TopKHeap,
+}
+
+impl TopK {
+    /// Creates a new [`TopK`] computing the first `k` rows of the
+    /// partition `partition_id` ordered by `expr`
+    pub fn try_new(
+        partition_id: usize,
+        schema: SchemaRef,
+        expr: Vec<PhysicalSortExpr>,
+        k: usize,
+        batch_size: usize,
+        runtime: &Arc<RuntimeEnv>,
+        metrics_set: &CompositeMetricsSet,
+    ) -> Result<Self> {
+        let reservation = MemoryConsumer::new(format!("TopK[{partition_id}]"))
+            .register(&runtime.memory_pool);
+
+        let sort_fields = expr
+            .iter()
+            .map(|expr| {
+                let data_type = expr.expr.data_type(&schema)?;
+                Ok(SortField::new_with_options(data_type, expr.options))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let row_converter = RowConverter::new(sort_fields)?;
+
+        Ok(Self {
+            heap: TopKHeap::new(k, schema.clone()),
+            schema,
+            metrics: metrics_set.new_final_baseline(partition_id),
+            reservation,
+            batch_size,
+            expr: expr.into(),
+            row_converter,
+        })
+    }
+
+    /// Updates the heap with the rows of `batch` that sort before its
+    /// current worst row
+    pub fn insert_batch(&mut self, batch: RecordBatch) -> Result<()> {
+        let _timer = self.metrics.elapsed_compute().timer();
+        if batch.num_rows() == 0 || self.heap.k == 0 {
+            return Ok(());
+        }
+
+        let sort_keys = self
+            .expr
+            .iter()
+            .map(|expr| Ok(expr.expr.evaluate(&batch)?.into_array(batch.num_rows())))
+            .collect::<Result<Vec<_>>>()?;
+        let rows = self.row_converter.convert_columns(&sort_keys)?;
+
+        let mut entry = self.heap.store.register(batch);
+        for (index, row) in rows.iter().enumerate() {
+            let qualifies = match self.heap.max() {
+                Some(max) => row < max.row.row(),
+                None => true,
+            };
+            if qualifies {
+                self.heap.add(&mut entry, row, index);
+            }
+        }
+        self.heap.store.insert(entry);
+        self.heap.maybe_compact()
+    }
+
+    /// Reserves the memory used by the heap, failing with
+    /// [`DataFusionError::ResourcesExhausted`] if the memory pool cannot
+    /// provide it
+    ///
+    /// [`DataFusionError::ResourcesExhausted`]: crate::error::DataFusionError::ResourcesExhausted
+    pub fn try_reserve(&mut self) -> Result<()> {
+        self.reservation.try_resize(self.heap.size())
+    }
+
+    /// Returns the rows of the heap in sorted order
+    pub fn emit(self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.clone();
+        let metrics = self.metrics.clone();
+        let _timer = metrics.elapsed_compute().timer();
+
+        let batches = self.into_batches()?;
+        metrics.record_output(batches.iter().map(|batch| batch.num_rows()).sum());
+        metrics.done();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            stream::iter(batches.into_iter().map(Ok)),
+        )))
+    }
+
+    /// Returns the rows of the heap in sorted order, in batches of at most
+    /// `batch_size` rows that do not share their buffers
+    pub fn into_batches(self) -> Result<Vec<RecordBatch>> {
+        let rows = self.heap.inner.into_sorted_vec();
+        rows.chunks(self.batch_size.max(1))
+            .filter_map(|rows| self.heap.store.interleave(rows).transpose())
+            .collect()
+    }
+}
+
+/// A max-heap of the best `k` rows seen so far, along with the batches
+/// these rows come from
+struct TopKHeap {
+    /// The number of rows to keep
+    k: usize,
+    /// The rows, the worst of which is at the top
+    inner: BinaryHeap<TopKRow>,
+    /// The batches referenced by the rows
+    store: RecordBatchStore,
+    /// The memory used by the rows
+    owned_bytes: usize,
+    /// The sequence number of the next added row
+    next_seq: u64,
+}
+
+impl TopKHeap {
+    fn new(k: usize, schema: SchemaRef) -> Self {
+        Self {
+            k,
+            inner: BinaryHeap::new(),
+            store: RecordBatchStore::new(schema),
+            owned_bytes: 0,
+            next_seq: 0,
+        }
+    }
+
+    /// Returns the worst row of the heap once it holds `k` rows, which a new
+    /// row must sort before to be added
+    fn max(&self) -> Option<&TopKRow> {
+        if self.inner.len() < self.k {
+            None
+        } else {
+            self.inner.peek()
+        }
+    }
+
+    /// Adds row `index` of the batch of `entry`, evicting the current worst
+    /// row if the heap is full
+    fn add(&mut self, entry: &mut RecordBatchEntry, row: Row<'_>, index: usize) {
+        let new_row = TopKRow::new(row, self.next_seq, entry.id, index);
+        self.next_seq += 1;
+        self.owned_bytes += new_row.size();
+        entry.uses += 1;
+
+        if self.inner.len() == self.k {
+            if let Some(prev) = self.inner.pop() {
+                self.owned_bytes -= prev.size();
+                if prev.batch_id == entry.id {
+                    entry.uses -= 1;
+                } else {
+                    self.store.unuse(prev.batch_id);
+                }
+            }
+        }
+        self.inner.push(new_row);
+    }
+
+    /// Copies the rows of the heap into a single batch if the retained
+    /// batches hold many more rows than the heap
+    fn maybe_compact(&mut self) -> Result<()> {
+        if self.store.len() <= 2 || self.store.num_rows() <= 2 * self.k {
+            return Ok(());
+        }
+
+        let rows = std::mem::take(&mut self.inner).into_vec();
+        let batch = match self.store.interleave(&rows)? {
+            Some(batch) => batch,
+            None => return Ok(()),
+        };
+        self.store.clear();
+
+        let mut entry = self.store.register(batch);
+        entry.uses = rows.len();
+        let batch_id = entry.id;
+        self.store.insert(entry);
+
+        self.inner = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| TopKRow {
+                batch_id,
+                index,
+                ..row
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Returns the memory used by the rows and the retained batches
+    fn size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.owned_bytes + self.store.memory_size
+    }
+}
+
+/// A row of the heap: its sort key and its location in the retained batches
+#[derive(Debug)]
+struct TopKRow {
+    /// The sort key of the row
+    row: OwnedRow,
+    /// The order in which the row was added, breaking ties between equal keys
+    seq: u64,
+    /// The id of the batch of the row in the [`RecordBatchStore`]
+    batch_id: u32,
+    /// The index of the row in its batch
+    index: usize,
+}
+
+impl TopKRow {
+    fn new(row: Row<'_>, seq: u64, batch_id: u32, index: usize) -> Self {
+        Self {
+            row: row.owned(),
+            seq,
+            batch_id,
+            index,
+        }
+    }
+
+    /// Returns the memory used by this row
+    fn size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.row.row().as_ref().len()
+    }
+}
+
+impl PartialEq for TopKRow {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TopKRow {}
+
+impl PartialOrd for TopKRow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TopKRow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.row
+            .cmp(&other.row)
+            .then_with(|| self.seq.cmp(&other.seq))
+    }
+}
+
+/// A batch retained by the [`TopKHeap`] and the number of its rows in the heap
+#[derive(Debug)]
+struct RecordBatchEntry {
+    id: u32,
+    batch: RecordBatch,
+    uses: usize,
+}
+
+/// The batches referenced by the rows of a [`TopKHeap`]
+#[derive(Debug)]
+struct RecordBatchStore {
+    /// The id of the next registered batch
+    next_id: u32,
+    /// The retained batches by id
+    batches: HashMap<u32, RecordBatchEntry>,
+    /// The total number of rows in the retained batches
+    rows: usize,
+    /// The memory used by the retained batches
+    memory_size: usize,
+    /// The schema of the batches
+    schema: SchemaRef,
+}
+
+impl RecordBatchStore {
+    fn new(schema: SchemaRef) -> Self {
+        Self {
+            next_id: 0,
+            batches: HashMap::new(),
+            rows: 0,
+            memory_size: 0,
+            schema,
+        }
+    }
+
+    /// Creates an entry with a new id for `batch`, which is only retained
+    /// once passed to [`Self::insert`]
+    fn register(&mut self, batch: RecordBatch) -> RecordBatchEntry {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        RecordBatchEntry { id, batch, uses: 0 }
+    }
+
+    /// Retains the batch of `entry` if any of its rows are in the heap
+    fn insert(&mut self, entry: RecordBatchEntry) {
+        if entry.uses > 0 {
+            self.rows += entry.batch.num_rows();
+            self.memory_size += entry.batch.get_array_memory_size();
+            self.batches.insert(entry.id, entry);
+        }
+    }
+
+    /// Records the eviction of a row of batch `id` from the heap, releasing
+    /// the batch once none of its rows are left
+    fn unuse(&mut self, id: u32) {
+        let remove = match self.batches.get_mut(&id) {
+            Some(entry) => {
+                entry.uses -= 1;
+                entry.uses == 0
+            }
+            None => false,
+        };
+        if remove {
+            if let Some(entry) = self.batches.remove(&id) {
+                self.rows -= entry.batch.num_rows();
+                self.memory_size -= entry.batch.get_array_memory_size();
+            }
+        }
+    }
+
+    /// Copies `rows` from the retained batches into a new batch, in order
+    fn interleave(&self, rows: &[TopKRow]) -> Result<Option<RecordBatch>> {
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let mut positions = HashMap::with_capacity(self.batches.len());
+        let mut batches = Vec::with_capacity(self.batches.len());
+        for (id, entry) in &self.batches {
+            positions.insert(*id, batches.len());
+            batches.push(&entry.batch);
+        }
+        let indices = rows
+            .iter()
+            .map(|row| (positions[&row.batch_id], row.index))
+            .collect::<Vec<_>>();
+
+        let columns = (0..self.schema.fields().len())
+            .map(|column_idx| {
+                let arrays = batches
+                    .iter()
+                    .map(|batch| batch.column(column_idx).as_ref())
+                    .collect::<Vec<_>>();
+                Ok(interleave(&arrays, &indices)?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(RecordBatch::try_new(self.schema.clone(), columns)?))
+    }
+
+    fn clear(&mut self) {
+        self.batches.clear();
+        self.rows = 0;
+        self.memory_size = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.batches.len()
+    }
+
+    fn num_rows(&self) -> usize {
+        self.rows
+    }
+}
@@ -27,9 +27,10 @@ use arrow::{
 };
 
 use super::{expressions::format_state_name, Accumulator, AggregateExpr};
-use crate::error::Result;
+use crate::error::{DataFusionError, Result};
 use crate::physical_plan::PhysicalExpr;
 pub use datafusion_expr::AggregateUDF;
+use datafusion_expr::GroupsAccumulator;
 
 use datafusion_physical_expr::aggregate::utils::down_cast_any_ref;
 use std::sync::Arc;
@@ -106,6 +107,20 @@ impl AggregateExpr for AggregateFunctionExpr {
         (self.fun.accumulator)(&self.data_type)
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        self.fun.groups_accumulator.is_some()
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        match &self.fun.groups_accumulator {
+            Some(groups_accumulator) => groups_accumulator(&self.data_type),
+            None => Err(DataFusionError::NotImplemented(format!(
+                "GroupsAccumulator hasn't been implemented for {} yet",
+                self.fun.name
+            ))),
+        }
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -61,6 +61,16 @@ impl UnnestExec {
             column,
         }
     }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The unnest column
+    pub fn column(&self) -> &Column {
+        &self.column
+    }
 }
 
 impl ExecutionPlan for UnnestExec {
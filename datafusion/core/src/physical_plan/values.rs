@@ -88,8 +88,29 @@ impl ValuesExec {
         Ok(Self { schema, data })
     }
 
+    /// create a new values exec from already evaluated record batches
+    pub fn try_new_from_batches(
+        schema: SchemaRef,
+        batches: Vec<RecordBatch>,
+    ) -> Result<Self> {
+        if batches.is_empty() {
+            return Err(DataFusionError::Plan("Values list cannot be empty".into()));
+        }
+        for batch in &batches {
+            if batch.schema() != schema {
+                return Err(DataFusionError::Plan(
+                    "Mismatch between values list schema and batch schema".into(),
+                ));
+            }
+        }
+        Ok(Self {
+            schema,
+            data: batches,
+        })
+    }
+
     /// provides the data
-    fn data(&self) -> Vec<RecordBatch> {
+    pub fn data(&self) -> Vec<RecordBatch> {
         self.data.clone()
     }
 }
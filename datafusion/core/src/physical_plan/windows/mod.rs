@@ -28,15 +28,16 @@ use crate::physical_plan::{
     udaf, ExecutionPlan, PhysicalExpr,
 };
 use crate::scalar::ScalarValue;
-use arrow::datatypes::Schema;
+use arrow::datatypes::{DataType, Field, Schema};
 use arrow_schema::{SchemaRef, SortOptions};
 use datafusion_expr::{
     window_function::{signature_for_built_in, BuiltInWindowFunction, WindowFunction},
-    WindowFrame,
+    PartitionEvaluator, WindowFrame, WindowUDF,
 };
 use datafusion_physical_expr::window::{
     BuiltInWindowFunctionExpr, SlidingAggregateWindowExpr,
 };
+use std::any::Any;
 use std::borrow::Borrow;
 use std::convert::TryInto;
 use std::sync::Arc;
@@ -100,6 +101,12 @@ pub fn create_window_expr(
             order_by,
             window_frame,
         )),
+        WindowFunction::WindowUDF(fun) => Arc::new(BuiltInWindowExpr::new(
+            create_udwf_window_expr(fun, args, input_schema, name)?,
+            partition_by,
+            order_by,
+            window_frame,
+        )),
     })
 }
 
@@ -197,6 +204,77 @@ fn create_built_in_window_expr(
     })
 }
 
+/// Creates a `BuiltInWindowFunctionExpr` suitable for a user defined window function
+fn create_udwf_window_expr(
+    fun: &Arc<WindowUDF>,
+    args: &[Arc<dyn PhysicalExpr>],
+    input_schema: &Schema,
+    name: String,
+) -> Result<Arc<dyn BuiltInWindowFunctionExpr>> {
+    let args = coerce(args, input_schema, &fun.signature)?;
+    let input_types = args
+        .iter()
+        .map(|arg| arg.data_type(input_schema))
+        .collect::<Result<Vec<_>>>()?;
+    let data_type = (fun.return_type)(&input_types)?.as_ref().clone();
+
+    // The evaluation strategy is a property of the evaluator, so ask a
+    // throwaway instance of it
+    let evaluator = (fun.partition_evaluator_factory)()?;
+    Ok(Arc::new(WindowUDFExpr {
+        fun: Arc::clone(fun),
+        args,
+        name,
+        data_type,
+        uses_window_frame: evaluator.uses_window_frame(),
+        supports_bounded_execution: evaluator.supports_bounded_execution(),
+    }))
+}
+
+/// Implements [`BuiltInWindowFunctionExpr`] for [`WindowUDF`]
+#[derive(Debug)]
+struct WindowUDFExpr {
+    fun: Arc<WindowUDF>,
+    args: Vec<Arc<dyn PhysicalExpr>>,
+    /// Display name
+    name: String,
+    /// result type
+    data_type: DataType,
+    uses_window_frame: bool,
+    supports_bounded_execution: bool,
+}
+
+impl BuiltInWindowFunctionExpr for WindowUDFExpr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        let nullable = true;
+        Ok(Field::new(&self.name, self.data_type.clone(), nullable))
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        self.args.clone()
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create_evaluator(&self) -> Result<Box<dyn PartitionEvaluator>> {
+        (self.fun.partition_evaluator_factory)()
+    }
+
+    fn supports_bounded_execution(&self) -> bool {
+        self.supports_bounded_execution
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        self.uses_window_frame
+    }
+}
+
 pub(crate) fn calc_requirements<
     T: Borrow<Arc<dyn PhysicalExpr>>,
     S: Borrow<PhysicalSortExpr>,
@@ -319,7 +397,7 @@ mod tests {
     use arrow::datatypes::{DataType, Field, SchemaRef};
     use arrow::record_batch::RecordBatch;
     use datafusion_common::cast::as_primitive_array;
-    use datafusion_expr::{create_udaf, Accumulator, Volatility};
+    use datafusion_expr::{create_udaf, create_udwf, Accumulator, Volatility};
     use futures::FutureExt;
 
     fn create_test_schema(partitions: usize) -> Result<(Arc<CsvExec>, SchemaRef)> {
@@ -526,6 +604,61 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn window_function_with_udwf() -> Result<()> {
+        /// Numbers the rows of the partition, counting from 1
+        #[derive(Debug)]
+        struct MyRowNumber;
+
+        impl PartitionEvaluator for MyRowNumber {
+            fn evaluate(
+                &self,
+                _values: &[ArrayRef],
+                num_rows: usize,
+            ) -> Result<ArrayRef> {
+                Ok(Arc::new(UInt64Array::from_iter_values(1..=num_rows as u64)))
+            }
+        }
+
+        let my_row_number = create_udwf(
+            "my_row_number",
+            vec![DataType::Int32],
+            Arc::new(DataType::UInt64),
+            Volatility::Immutable,
+            Arc::new(|| Ok(Box::new(MyRowNumber))),
+        );
+
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let (input, schema) = create_test_schema(1)?;
+
+        let window_exec = Arc::new(WindowAggExec::try_new(
+            vec![create_window_expr(
+                &WindowFunction::WindowUDF(Arc::new(my_row_number)),
+                "my_row_number".to_owned(),
+                &[col("c3", &schema)?],
+                &[],
+                &[],
+                Arc::new(WindowFrame::new(false)),
+                schema.as_ref(),
+            )?],
+            input,
+            schema.clone(),
+            vec![],
+        )?);
+
+        let result: Vec<RecordBatch> = collect(window_exec, task_ctx).await?;
+        assert_eq!(result.len(), 1);
+
+        let n_schema_fields = schema.fields().len();
+        let columns = result[0].columns();
+
+        let row_number: &UInt64Array = as_primitive_array(&columns[n_schema_fields])?;
+        assert_eq!(row_number.value(0), 1);
+        assert_eq!(row_number.value(99), 100);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn window_function() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -0,0 +1,203 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the work table query plan
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use parking_lot::Mutex;
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+use crate::physical_plan::memory::MemoryStream;
+use crate::physical_plan::{
+    project_schema, DisplayFormatType, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+
+use super::expressions::PhysicalSortExpr;
+
+/// The rows produced by the previous iteration of a recursive query,
+/// which are read by the next iteration.
+///
+/// The name is taken from PostgreSQL's terminology.
+#[derive(Debug, Default)]
+pub struct WorkTable {
+    batches: Mutex<Option<Vec<RecordBatch>>>,
+}
+
+impl WorkTable {
+    /// Create a new, not yet written work table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the rows of the work table, which may be read any number of
+    /// times until the table is written again
+    fn read(&self) -> Result<Vec<RecordBatch>> {
+        self.batches.lock().clone().ok_or_else(|| {
+            DataFusionError::Internal(
+                "Unexpected read of a work table that was not written".to_string(),
+            )
+        })
+    }
+
+    /// Replace the rows of the work table
+    pub(crate) fn write(&self, batches: Vec<RecordBatch>) {
+        self.batches.lock().replace(batches);
+    }
+}
+
+/// Execution plan that reads the rows of a [`WorkTable`], i.e. the
+/// reference of a recursive query to itself.
+///
+/// The work table is shared with the [`RecursiveQueryExec`] the plan is
+/// part of, which assigns it when it is created.
+///
+/// [`RecursiveQueryExec`]: crate::physical_plan::recursive_query::RecursiveQueryExec
+#[derive(Debug)]
+pub struct WorkTableExec {
+    /// Name of the recursive query, used to match the plan with its
+    /// [`RecursiveQueryExec`](crate::physical_plan::recursive_query::RecursiveQueryExec)
+    name: String,
+    /// Schema of the work table
+    table_schema: SchemaRef,
+    /// Optional projection applied to the rows of the work table
+    projection: Option<Vec<usize>>,
+    /// Schema after the projection is applied
+    projected_schema: SchemaRef,
+    /// The work table this plan reads from
+    work_table: Arc<WorkTable>,
+}
+
+impl WorkTableExec {
+    /// Create a new plan reading the work table called `name`
+    pub fn try_new(
+        name: String,
+        table_schema: SchemaRef,
+        projection: Option<Vec<usize>>,
+    ) -> Result<Self> {
+        let projected_schema = project_schema(&table_schema, projection.as_ref())?;
+        Ok(Self {
+            name,
+            table_schema,
+            projection,
+            projected_schema,
+            work_table: Arc::new(WorkTable::new()),
+        })
+    }
+
+    /// Name of the work table
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Optional projection applied to the rows of the work table
+    pub fn projection(&self) -> Option<&Vec<usize>> {
+        self.projection.as_ref()
+    }
+
+    /// Return a copy of this plan reading from `work_table`
+    pub(crate) fn with_work_table(&self, work_table: Arc<WorkTable>) -> Self {
+        Self {
+            name: self.name.clone(),
+            table_schema: self.table_schema.clone(),
+            projection: self.projection.clone(),
+            projected_schema: self.projected_schema.clone(),
+            work_table,
+        }
+    }
+}
+
+impl ExecutionPlan for WorkTableExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.projected_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        // WorkTableExec has a single output partition
+        if 0 != partition {
+            return Err(DataFusionError::Internal(format!(
+                "WorkTableExec invalid partition {partition}"
+            )));
+        }
+
+        // The rows may come from the recursive term, whose output only
+        // matches the work table in its column types
+        let batches = self
+            .work_table
+            .read()?
+            .into_iter()
+            .map(|batch| {
+                RecordBatch::try_new(self.table_schema.clone(), batch.columns().to_vec())
+                    .map_err(Into::into)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Box::pin(MemoryStream::try_new(
+            batches,
+            self.projected_schema.clone(),
+            self.projection.clone(),
+        )?))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(f, "WorkTableExec: name={}", self.name)
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
@@ -36,5 +36,5 @@ pub use datafusion_expr::{
     expr_fn::*,
     lit, lit_timestamp_nano,
     logical_plan::{JoinType, Partitioning},
-    Expr,
+    Expr, WildcardOptions,
 };
@@ -0,0 +1,151 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use super::*;
+use datafusion::parquet::file::reader::{FileReader, SerializedFileReader};
+
+#[tokio::test]
+async fn copy_query_to_single_file() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    for (file_name, options, reader) in [
+        ("out.csv", "", "read_csv"),
+        ("out.header.csv", "(HEADER true)", "read_csv"),
+        ("out.json", "", "read_json"),
+        ("out.parquet", "", "read_parquet"),
+        ("out.csv.gz", "(COMPRESSION gzip)", "read_csv"),
+        ("out.zst.parquet", "(COMPRESSION zstd)", "read_parquet"),
+        (
+            "out.data",
+            "(FORMAT csv, HEADER false, DELIMITER '|')",
+            "read_csv",
+        ),
+    ] {
+        let ctx =
+            SessionContext::with_config(SessionConfig::new().with_target_partitions(4));
+        let path = tmp_dir.path().join(file_name);
+        let path = path.to_str().unwrap();
+
+        let sql = format!(
+            "COPY (SELECT * FROM (VALUES (1, 'x'), (2, 'y'), (3, 'z')) AS t(a, b)) \
+             TO '{path}' {options}"
+        );
+        ctx.sql(&sql).await?.collect().await?;
+        assert!(tmp_dir.path().join(file_name).is_file());
+
+        let sql = match file_name {
+            "out.csv" => {
+                format!("SELECT column_1 AS a, column_2 AS b FROM {reader}('{path}')")
+            }
+            "out.header.csv" => {
+                format!("SELECT * FROM {reader}('{path}', has_header => true)")
+            }
+            "out.csv.gz" => format!(
+                "SELECT column_1 AS a, column_2 AS b \
+                 FROM {reader}('{path}', compression => 'gzip')"
+            ),
+            "out.data" => format!(
+                "SELECT column_1 AS a, column_2 AS b \
+                 FROM {reader}('{path}', has_header => false, delimiter => '|')"
+            ),
+            _ => format!("SELECT * FROM {reader}('{path}')"),
+        };
+        let batches = ctx.sql(&sql).await?.collect().await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | x |",
+            "| 2 | y |",
+            "| 3 | z |",
+            "+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn copy_table_to_partitioned_directory() -> Result<()> {
+    let ctx = SessionContext::new();
+    let tmp_dir = TempDir::new()?;
+    let sql =
+        "CREATE TABLE t AS VALUES (1, '2022', 'x'), (2, '2023', 'y'), (3, '2022', 'z')";
+    ctx.sql(sql).await?.collect().await?;
+
+    let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+    let sql = format!("COPY t TO '{path}' (FORMAT parquet, PARTITION_BY (column2))");
+    ctx.sql(&sql).await?.collect().await?;
+    assert!(tmp_dir.path().join("column2=2022").is_dir());
+    assert!(tmp_dir.path().join("column2=2023").is_dir());
+
+    let sql = format!(
+        "SELECT column1, column3, column2 FROM read_parquet('{path}', hive => true)"
+    );
+    let batches = ctx.sql(&sql).await?.collect().await?;
+    let expected = vec![
+        "+---------+---------+---------+",
+        "| column1 | column3 | column2 |",
+        "+---------+---------+---------+",
+        "| 1       | x       | 2022    |",
+        "| 2       | y       | 2023    |",
+        "| 3       | z       | 2022    |",
+        "+---------+---------+---------+",
+    ];
+    assert_batches_sorted_eq!(expected, &batches);
+    Ok(())
+}
+
+#[tokio::test]
+async fn copy_with_format_options() -> Result<()> {
+    let ctx = SessionContext::new();
+    let tmp_dir = TempDir::new()?;
+    let sql = "CREATE TABLE t AS VALUES (1, 'x'), (2, 'y'), (3, 'z')";
+    ctx.sql(sql).await?.collect().await?;
+
+    let path = tmp_dir.path().join("out.parquet");
+    let sql = format!(
+        "COPY t TO '{}' (MAX_ROW_GROUP_SIZE 2, 'dictionary_enabled' 'false')",
+        path.to_str().unwrap()
+    );
+    ctx.sql(&sql).await?.collect().await?;
+
+    let reader = SerializedFileReader::new(std::fs::File::open(&path)?)?;
+    let row_groups = reader.metadata().row_groups();
+    assert_eq!(row_groups.len(), 2);
+    assert_eq!(row_groups[0].num_rows(), 2);
+    assert_eq!(row_groups[1].num_rows(), 1);
+
+    for (options, error) in [
+        (
+            "(FORMAT parquet, ROW_GROUP_SIZE 2)",
+            "Unsupported COPY TO option ROW_GROUP_SIZE for PARQUET files",
+        ),
+        (
+            "(FORMAT parquet, MAX_ROW_GROUP_SIZE 'many')",
+            "Invalid value many for COPY TO option MAX_ROW_GROUP_SIZE",
+        ),
+        (
+            "(FORMAT csv, MAX_ROW_GROUP_SIZE 2)",
+            "Unsupported COPY TO option MAX_ROW_GROUP_SIZE for CSV files",
+        ),
+    ] {
+        let sql = format!("COPY t TO '{}' {options}", path.to_str().unwrap());
+        let err = ctx.sql(&sql).await?.collect().await.unwrap_err();
+        assert_eq!(err.to_string(), format!("Error during planning: {error}"));
+    }
+    Ok(())
+}
@@ -81,6 +81,7 @@ macro_rules! test_expression {
 pub mod aggregates;
 #[cfg(feature = "avro")]
 pub mod avro;
+pub mod copy;
 pub mod create_drop;
 pub mod explain_analyze;
 pub mod expr;
@@ -19,7 +19,9 @@ use super::*;
 use datafusion::datasource::datasource::TableProviderFactory;
 use datafusion::datasource::listing::ListingTable;
 use datafusion::datasource::listing_table_factory::ListingTableFactory;
+use datafusion_common::cast::as_string_array;
 use datafusion_expr::logical_plan::DdlStatement;
+use std::collections::HashMap;
 use test_utils::{batches_to_vec, partitions_to_sorted_vec};
 
 #[tokio::test]
@@ -251,3 +253,31 @@ ORDER BY 1, 2;
     }
     Ok(())
 }
+
+#[tokio::test]
+async fn cluster_by_sorts_within_partitions() -> Result<()> {
+    let ctx = SessionContext::with_config(SessionConfig::new().with_target_partitions(4));
+    register_aggregate_csv(&ctx).await?;
+
+    let sql = "SELECT c1, c2 FROM aggregate_test_100 CLUSTER BY c1";
+    let partitions = ctx.sql(sql).await?.collect_partitioned().await?;
+    assert_eq!(partitions.len(), 4);
+
+    // each value of c1 is in a single partition, and each partition is sorted
+    let mut value_partitions = HashMap::new();
+    let mut num_rows = 0;
+    for (i, partition) in partitions.iter().enumerate() {
+        let mut values = vec![];
+        for batch in partition {
+            let c1 = as_string_array(batch.column(0))?;
+            values.extend(c1.iter().map(|v| v.unwrap().to_string()));
+        }
+        assert!(values.windows(2).all(|w| w[0] <= w[1]), "{values:?}");
+        for value in values {
+            assert_eq!(*value_partitions.entry(value).or_insert(i), i);
+            num_rows += 1;
+        }
+    }
+    assert_eq!(num_rows, 100);
+    Ok(())
+}
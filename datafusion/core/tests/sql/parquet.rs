@@ -383,3 +383,53 @@ async fn parquet_query_with_max_min() {
 
     assert_batches_eq!(expected, &actual);
 }
+
+#[tokio::test]
+async fn join_reorder_with_parquet_statistics() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, false),
+        Field::new("b", DataType::Int32, false),
+    ]));
+
+    // t1 and t2 have 1000 rows, t3 only 10
+    for (name, num_rows) in [("t1", 1000), ("t2", 1000), ("t3", 10)] {
+        let path = tmp_dir.path().join(format!("{name}.parquet"));
+        let file = fs::File::create(path)?;
+        let mut writer = ArrowWriter::try_new(file, schema.clone(), None)?;
+        let values = Int32Array::from_iter_values(0..num_rows);
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(values.clone()), Arc::new(values)],
+        )?;
+        writer.write(&batch)?;
+        writer.close()?;
+    }
+
+    let sql = "SELECT * FROM t1 JOIN t2 ON t1.a = t2.a JOIN t3 ON t2.b = t3.b";
+    for collect_statistics in [false, true] {
+        let config = SessionConfig::new().with_collect_statistics(collect_statistics);
+        let ctx = SessionContext::with_config(config);
+        for name in ["t1", "t2", "t3"] {
+            let path = tmp_dir.path().join(format!("{name}.parquet"));
+            ctx.register_parquet(
+                name,
+                path.to_str().unwrap(),
+                ParquetReadOptions::default(),
+            )
+            .await?;
+        }
+
+        let plan = ctx.sql(sql).await?.into_optimized_plan()?;
+        let plan = format!("{}", plan.display_indent());
+        if collect_statistics {
+            // The row counts of the parquet metadata make t3 JOIN t2 first
+            assert_contains!(&plan, "Inner Join: t2.a = t1.a");
+            assert_contains!(&plan, "Inner Join: t3.b = t2.b");
+        } else {
+            assert_contains!(&plan, "Inner Join: t1.a = t2.a");
+            assert_contains!(&plan, "Inner Join: t2.b = t3.b");
+        }
+    }
+    Ok(())
+}
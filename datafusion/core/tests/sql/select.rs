@@ -620,3 +620,24 @@ async fn unprojected_filter() {
     ];
     assert_batches_sorted_eq!(expected, &results);
 }
+
+#[tokio::test]
+async fn select_wildcard_replace() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql = "CREATE TABLE t AS VALUES (1, 'a', 10), (2, 'b', 20)";
+    ctx.sql(sql).await?.collect().await?;
+
+    let sql = "SELECT * REPLACE (column3 + 1 AS column3, upper(column2) AS column2) \
+               FROM t";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---------+---------+---------+",
+        "| column1 | column2 | column3 |",
+        "+---------+---------+---------+",
+        "| 1       | A       | 11      |",
+        "| 2       | B       | 21      |",
+        "+---------+---------+---------+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+    Ok(())
+}
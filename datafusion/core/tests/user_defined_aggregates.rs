@@ -19,22 +19,28 @@
 //! user defined aggregate functions
 
 use arrow::datatypes::Fields;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use datafusion::{
     arrow::{
-        array::{ArrayRef, Float64Array, TimestampNanosecondArray},
+        array::{
+            Array, ArrayRef, BooleanArray, Float64Array, StringArray,
+            TimestampNanosecondArray,
+        },
         datatypes::{DataType, Field, Float64Type, TimeUnit, TimestampNanosecondType},
         record_batch::RecordBatch,
     },
     assert_batches_eq,
     error::Result,
+    execution::FunctionRegistry,
     logical_expr::{
-        AccumulatorFunctionImplementation, AggregateUDF, ReturnTypeFunction, Signature,
+        expr, AccumulatorFunctionImplementation, AggregateUDF, GroupsAccumulator,
+        GroupsAccumulatorFunctionImplementation, ReturnTypeFunction, Signature,
         StateTypeFunction, TypeSignature, Volatility,
     },
     physical_plan::Accumulator,
-    prelude::SessionContext,
+    prelude::{col, lit, Expr, SessionContext},
     scalar::ScalarValue,
 };
 use datafusion_common::cast::as_primitive_array;
@@ -69,6 +75,52 @@ async fn test_udf_returning_struct_sq() {
     assert_batches_eq!(expected, &execute(&ctx, sql).await);
 }
 
+#[tokio::test]
+/// A udaf with a groups accumulator uses it for grouped aggregation
+async fn test_udaf_groups_accumulator() {
+    let used = Arc::new(AtomicBool::new(false));
+    let ctx = udaf_groups_context(used.clone());
+    let my_sum = ctx.udaf("my_sum").unwrap();
+    // SELECT g, my_sum(v) AS s, my_sum(v) FILTER (WHERE v > 1) AS fs
+    // FROM t GROUP BY g ORDER BY g
+    let aggr_expr = vec![
+        Expr::AggregateUDF(expr::AggregateUDF::new(
+            my_sum.clone(),
+            vec![col("v")],
+            None,
+        ))
+        .alias("s"),
+        Expr::AggregateUDF(expr::AggregateUDF::new(
+            my_sum,
+            vec![col("v")],
+            Some(Box::new(col("v").gt(lit(1.0)))),
+        ))
+        .alias("fs"),
+    ];
+    let batches = ctx
+        .table("t")
+        .await
+        .unwrap()
+        .aggregate(vec![col("g")], aggr_expr)
+        .unwrap()
+        .sort(vec![col("g").sort(true, true)])
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let expected = vec![
+        "+---+-----+-----+",
+        "| g | s   | fs  |",
+        "+---+-----+-----+",
+        "| a | 4.0 | 3.0 |",
+        "| b | 6.0 | 6.0 |",
+        "| c |     |     |",
+        "+---+-----+-----+",
+    ];
+    assert_batches_eq!(expected, &batches);
+    assert!(used.load(Ordering::SeqCst));
+}
+
 async fn execute(ctx: &SessionContext, sql: &str) -> Vec<RecordBatch> {
     ctx.sql(sql).await.unwrap().collect().await.unwrap()
 }
@@ -239,3 +291,139 @@ impl Accumulator for FirstSelector {
         std::mem::size_of_val(self)
     }
 }
+
+/// Returns a context with a table "t" and the "my_sum" aggregate registered,
+/// which sets `used` when its groups accumulator is created.
+///
+/// "t" contains this data:
+///
+/// ```text
+/// g | v
+/// a | 1.0
+/// b | 2.0
+/// a | 3.0
+/// c | NULL
+/// b | 4.0
+/// ```
+fn udaf_groups_context(used: Arc<AtomicBool>) -> SessionContext {
+    let g = StringArray::from(vec!["a", "b", "a", "c", "b"]);
+    let v = Float64Array::from(vec![Some(1.0), Some(2.0), Some(3.0), None, Some(4.0)]);
+
+    let batch = RecordBatch::try_from_iter(vec![
+        ("g", Arc::new(g) as _),
+        ("v", Arc::new(v) as _),
+    ])
+    .unwrap();
+
+    let ctx = SessionContext::new();
+    ctx.register_batch("t", batch).unwrap();
+
+    let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    let state_type: StateTypeFunction =
+        Arc::new(|_| Ok(Arc::new(vec![DataType::Float64])));
+    let accumulator: AccumulatorFunctionImplementation =
+        Arc::new(|_| Ok(Box::<SumAccumulator>::default()));
+    let groups_accumulator: GroupsAccumulatorFunctionImplementation =
+        Arc::new(move |_| {
+            used.store(true, Ordering::SeqCst);
+            Ok(Box::<SumGroupsAccumulator>::default())
+        });
+
+    let my_sum = AggregateUDF::new(
+        "my_sum",
+        &Signature::exact(vec![DataType::Float64], Volatility::Immutable),
+        &return_type,
+        &accumulator,
+        &state_type,
+    )
+    .with_groups_accumulator(groups_accumulator);
+    ctx.register_udaf(my_sum);
+
+    ctx
+}
+
+/// Sums up `Float64` values, `NULL` if there are none
+#[derive(Debug, Default)]
+struct SumAccumulator {
+    sum: Option<f64>,
+}
+
+impl Accumulator for SumAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Float64(self.sum))
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = as_primitive_array::<Float64Type>(&values[0])?;
+        for value in values.iter().flatten() {
+            *self.sum.get_or_insert(0.0) += value;
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        // the state is a partial sum
+        self.update_batch(states)
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// Vectorized version of [`SumAccumulator`], keeping the sums of all groups
+#[derive(Debug, Default)]
+struct SumGroupsAccumulator {
+    sums: Vec<Option<f64>>,
+}
+
+impl GroupsAccumulator for SumGroupsAccumulator {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.sums.resize(total_num_groups, None);
+        let values = as_primitive_array::<Float64Type>(&values[0])?;
+        for (row, (value, group_index)) in values.iter().zip(group_indices).enumerate() {
+            let selected = opt_filter
+                .map(|filter| filter.is_valid(row) && filter.value(row))
+                .unwrap_or(true);
+            if let (true, Some(value)) = (selected, value) {
+                *self.sums[*group_index].get_or_insert(0.0) += value;
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        states: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        // the state is a partial sum of each group
+        self.update_batch(states, group_indices, opt_filter, total_num_groups)
+    }
+
+    fn evaluate(&mut self) -> Result<ArrayRef> {
+        let sums = std::mem::take(&mut self.sums);
+        Ok(Arc::new(Float64Array::from(sums)))
+    }
+
+    fn state(&mut self) -> Result<Vec<ArrayRef>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.sums.capacity() * std::mem::size_of::<Option<f64>>()
+    }
+}
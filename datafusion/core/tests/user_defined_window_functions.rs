@@ -0,0 +1,202 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This module contains end to end demonstrations of creating
+//! user defined window functions
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use datafusion::{
+    arrow::{
+        array::{Array, ArrayRef, Int64Array, StringArray},
+        datatypes::DataType,
+        record_batch::RecordBatch,
+    },
+    assert_batches_eq,
+    error::Result,
+    logical_expr::{
+        create_udwf, window_state::WindowAggState, PartitionEvaluator,
+        PartitionEvaluatorFunctionImplementation, Volatility,
+    },
+    physical_plan::displayable,
+    prelude::SessionContext,
+    scalar::ScalarValue,
+};
+use datafusion_common::cast::as_int64_array;
+
+const QUERY: &str =
+    "SELECT p, o, x, odd_counter(x) OVER (PARTITION BY p ORDER BY o) AS c FROM t ORDER BY p, o";
+
+#[tokio::test]
+/// Basic query for a udwf evaluated over whole partitions
+async fn test_udwf() {
+    let ctx = udwf_context(Arc::new(|| Ok(Box::new(OddCounter))));
+    let plan = physical_plan(&ctx, QUERY).await;
+    assert!(plan.contains("WindowAggExec"), "{plan}");
+    assert!(!plan.contains("BoundedWindowAggExec"), "{plan}");
+
+    assert_batches_eq!(expected(), &execute(&ctx, QUERY).await);
+}
+
+#[tokio::test]
+/// A udwf supporting bounded execution runs in a BoundedWindowAggExec
+async fn test_udwf_bounded_execution() {
+    let ctx = udwf_context(Arc::new(|| Ok(Box::<BoundedOddCounter>::default())));
+    let plan = physical_plan(&ctx, QUERY).await;
+    assert!(plan.contains("BoundedWindowAggExec"), "{plan}");
+
+    assert_batches_eq!(expected(), &execute(&ctx, QUERY).await);
+}
+
+#[tokio::test]
+async fn test_udwf_not_registered() {
+    let ctx = SessionContext::new();
+    let err = ctx.sql("SELECT odd_counter(1) OVER ()").await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: There is no window function named odd_counter"
+    );
+}
+
+fn expected() -> Vec<&'static str> {
+    vec![
+        "+---+---+---+---+",
+        "| p | o | x | c |",
+        "+---+---+---+---+",
+        "| a | 1 | 1 | 1 |",
+        "| a | 2 | 2 | 1 |",
+        "| a | 3 | 3 | 2 |",
+        "| b | 1 | 4 | 0 |",
+        "| b | 2 | 5 | 1 |",
+        "+---+---+---+---+",
+    ]
+}
+
+async fn execute(ctx: &SessionContext, sql: &str) -> Vec<RecordBatch> {
+    ctx.sql(sql).await.unwrap().collect().await.unwrap()
+}
+
+async fn physical_plan(ctx: &SessionContext, sql: &str) -> String {
+    let plan = ctx
+        .sql(sql)
+        .await
+        .unwrap()
+        .create_physical_plan()
+        .await
+        .unwrap();
+    let formatted = displayable(plan.as_ref()).indent().to_string();
+    formatted
+}
+
+/// Returns a context with a table "t" and the "odd_counter" window function
+/// registered, evaluated by the partition evaluators `factory` creates.
+///
+/// "t" contains this data:
+///
+/// ```text
+/// p | o | x
+/// a | 1 | 1
+/// b | 2 | 5
+/// a | 3 | 3
+/// b | 1 | 4
+/// a | 2 | 2
+/// ```
+fn udwf_context(factory: PartitionEvaluatorFunctionImplementation) -> SessionContext {
+    let p = StringArray::from(vec!["a", "b", "a", "b", "a"]);
+    let o = Int64Array::from(vec![1, 2, 3, 1, 2]);
+    let x = Int64Array::from(vec![1, 5, 3, 4, 2]);
+    let batch = RecordBatch::try_from_iter(vec![
+        ("p", Arc::new(p) as _),
+        ("o", Arc::new(o) as _),
+        ("x", Arc::new(x) as _),
+    ])
+    .unwrap();
+
+    let ctx = SessionContext::new();
+    ctx.register_batch("t", batch).unwrap();
+
+    // Tell datafusion about the "odd_counter" function
+    ctx.register_udwf(create_udwf(
+        "odd_counter",
+        vec![DataType::Int64],
+        Arc::new(DataType::Int64),
+        Volatility::Immutable,
+        factory,
+    ));
+
+    ctx
+}
+
+/// Whether the value at `idx` is odd
+fn is_odd(values: &Int64Array, idx: usize) -> bool {
+    values.is_valid(idx) && values.value(idx) % 2 != 0
+}
+
+/// Computes `odd_counter` for a whole partition at once
+#[derive(Debug)]
+struct OddCounter;
+
+impl PartitionEvaluator for OddCounter {
+    fn evaluate(&self, values: &[ArrayRef], num_rows: usize) -> Result<ArrayRef> {
+        let values = as_int64_array(&values[0])?;
+        let mut count = 0;
+        let counts: Int64Array = (0..num_rows)
+            .map(|idx| {
+                count += is_odd(values, idx) as i64;
+                Some(count)
+            })
+            .collect();
+        Ok(Arc::new(counts))
+    }
+}
+
+/// Computes `odd_counter` one row at a time, as the input arrives
+#[derive(Debug, Default)]
+struct BoundedOddCounter {
+    /// Index of the row to compute the result of
+    idx: usize,
+    /// Number of odd values seen so far
+    count: i64,
+}
+
+impl PartitionEvaluator for BoundedOddCounter {
+    fn supports_bounded_execution(&self) -> bool {
+        true
+    }
+
+    fn get_range(&self, idx: usize, _n_rows: usize) -> Result<Range<usize>> {
+        Ok(idx..idx + 1)
+    }
+
+    fn update_state(
+        &mut self,
+        _state: &WindowAggState,
+        idx: usize,
+        _range_columns: &[ArrayRef],
+        _sort_partition_points: &[Range<usize>],
+    ) -> Result<()> {
+        self.idx = idx;
+        Ok(())
+    }
+
+    fn evaluate_stateful(&mut self, values: &[ArrayRef]) -> Result<ScalarValue> {
+        let values = as_int64_array(&values[0])?;
+        self.count += is_odd(values, self.idx) as i64;
+        Ok(ScalarValue::Int64(Some(self.count)))
+    }
+}
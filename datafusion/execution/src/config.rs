@@ -250,6 +250,22 @@ impl SessionConfig {
         self
     }
 
+    /// Get the number of partitions a spilling hash join splits its inputs into
+    ///
+    /// A configured value of 0 is treated as 1, as there must be at least one
+    /// partition
+    pub fn hash_join_spill_partitions(&self) -> usize {
+        self.options.execution.hash_join_spill_partitions.max(1)
+    }
+
+    /// Customize the number of partitions a spilling hash join splits its inputs into
+    pub fn with_hash_join_spill_partitions(mut self, n: usize) -> Self {
+        // there must be at least one partition
+        assert!(n > 0);
+        self.options.execution.hash_join_spill_partitions = n;
+        self
+    }
+
     /// Convert configuration options to name-value pairs with values
     /// converted to strings.
     ///
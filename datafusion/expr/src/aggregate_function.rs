@@ -194,6 +194,7 @@ pub fn signature(fun: &AggregateFunction) -> Signature {
                 .chain(TIMESTAMPS.iter())
                 .chain(DATES.iter())
                 .chain(TIMES.iter())
+                .chain([DataType::Binary].iter())
                 .cloned()
                 .collect::<Vec<_>>();
             Signature::uniform(1, valid, Volatility::Immutable)
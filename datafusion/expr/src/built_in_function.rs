@@ -97,9 +97,39 @@ pub enum BuiltinScalarFunction {
     /// trunc
     Trunc,
 
-    // string functions
+    // array functions
     /// construct an array from columns
     MakeArray,
+    /// array_append
+    ArrayAppend,
+    /// array_concat
+    ArrayConcat,
+    /// array_contains
+    ArrayContains,
+    /// array_dims
+    ArrayDims,
+    /// array_length
+    ArrayLength,
+    /// array_position
+    ArrayPosition,
+    /// array_positions
+    ArrayPositions,
+    /// array_prepend
+    ArrayPrepend,
+    /// array_remove
+    ArrayRemove,
+    /// array_replace
+    ArrayReplace,
+    /// array_slice
+    ArraySlice,
+    /// array_to_string
+    ArrayToString,
+    /// cardinality
+    Cardinality,
+    /// flatten
+    Flatten,
+
+    // string functions
     /// ascii
     Ascii,
     /// bit_length
@@ -254,6 +284,20 @@ impl BuiltinScalarFunction {
             BuiltinScalarFunction::Tanh => Volatility::Immutable,
             BuiltinScalarFunction::Trunc => Volatility::Immutable,
             BuiltinScalarFunction::MakeArray => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayAppend => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayConcat => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayContains => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayDims => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayLength => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayPosition => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayPositions => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayPrepend => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayRemove => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayReplace => Volatility::Immutable,
+            BuiltinScalarFunction::ArraySlice => Volatility::Immutable,
+            BuiltinScalarFunction::ArrayToString => Volatility::Immutable,
+            BuiltinScalarFunction::Cardinality => Volatility::Immutable,
+            BuiltinScalarFunction::Flatten => Volatility::Immutable,
             BuiltinScalarFunction::Ascii => Volatility::Immutable,
             BuiltinScalarFunction::BitLength => Volatility::Immutable,
             BuiltinScalarFunction::Btrim => Volatility::Immutable,
@@ -428,6 +472,20 @@ impl FromStr for BuiltinScalarFunction {
 
             // array functions
             "make_array" => BuiltinScalarFunction::MakeArray,
+            "array_append" => BuiltinScalarFunction::ArrayAppend,
+            "array_concat" => BuiltinScalarFunction::ArrayConcat,
+            "array_contains" => BuiltinScalarFunction::ArrayContains,
+            "array_dims" => BuiltinScalarFunction::ArrayDims,
+            "array_length" => BuiltinScalarFunction::ArrayLength,
+            "array_position" => BuiltinScalarFunction::ArrayPosition,
+            "array_positions" => BuiltinScalarFunction::ArrayPositions,
+            "array_prepend" => BuiltinScalarFunction::ArrayPrepend,
+            "array_remove" => BuiltinScalarFunction::ArrayRemove,
+            "array_replace" => BuiltinScalarFunction::ArrayReplace,
+            "array_slice" => BuiltinScalarFunction::ArraySlice,
+            "array_to_string" => BuiltinScalarFunction::ArrayToString,
+            "cardinality" => BuiltinScalarFunction::Cardinality,
+            "flatten" => BuiltinScalarFunction::Flatten,
 
             _ => {
                 return Err(DataFusionError::Plan(format!(
@@ -573,6 +573,47 @@ impl Placeholder {
     }
 }
 
+/// Additional options of a wildcard, as in
+/// `SELECT * EXCLUDE (a) REPLACE (b + 1 AS b) RENAME (c AS d)`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WildcardOptions {
+    /// Names of the columns left out of the expansion (`EXCLUDE` / `EXCEPT`)
+    pub exclude: Vec<String>,
+    /// Column names with the expressions that replace them (`REPLACE`)
+    pub replace: Vec<(String, Expr)>,
+    /// Old and new names of the columns that are renamed (`RENAME`)
+    pub rename: Vec<(String, String)>,
+}
+
+impl WildcardOptions {
+    /// Create options that expand to all columns
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leave the column called `name` out of the expansion
+    pub fn with_exclude(mut self, name: impl Into<String>) -> Self {
+        self.exclude.push(name.into());
+        self
+    }
+
+    /// Replace the column called `name` with `expr`, keeping its name
+    pub fn with_replace(mut self, name: impl Into<String>, expr: Expr) -> Self {
+        self.replace.push((name.into(), expr));
+        self
+    }
+
+    /// Rename the column called `old_name` to `new_name`
+    pub fn with_rename(
+        mut self,
+        old_name: impl Into<String>,
+        new_name: impl Into<String>,
+    ) -> Self {
+        self.rename.push((old_name.into(), new_name.into()));
+        self
+    }
+}
+
 /// Grouping sets
 /// See <https://www.postgresql.org/docs/current/queries-table-expressions.html#QUERIES-GROUPING-SETS>
 /// for Postgres definition.
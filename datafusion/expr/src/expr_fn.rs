@@ -24,8 +24,10 @@ use crate::expr::{
 use crate::{
     aggregate_function, built_in_function, conditional_expressions::CaseBuilder,
     logical_plan::Subquery, AccumulatorFunctionImplementation, AggregateUDF,
-    BuiltinScalarFunction, Expr, LogicalPlan, Operator, ReturnTypeFunction,
+    BuiltinScalarFunction, Expr, LogicalPlan, Operator,
+    PartitionEvaluatorFunctionImplementation, ReturnTypeFunction,
     ScalarFunctionImplementation, ScalarUDF, Signature, StateTypeFunction, Volatility,
+    WindowUDF,
 };
 use arrow::datatypes::DataType;
 use datafusion_common::{Column, Result};
@@ -601,7 +603,7 @@ nary_scalar_expr!(
 nary_scalar_expr!(
     MakeArray,
     array,
-    "returns an array of fixed size with each argument on it."
+    "returns an array with each argument on it."
 );
 nary_scalar_expr!(Coalesce, coalesce, "returns `coalesce(args...)`, which evaluates to the value of the first [Expr] which is not NULL");
 //there is a func concat_ws before, so use concat_ws_expr as name.c
@@ -612,6 +614,85 @@ nary_scalar_expr!(
 );
 nary_scalar_expr!(Concat, concat_expr, "concatenates several strings");
 
+// array functions
+scalar_expr!(
+    ArrayAppend,
+    array_append,
+    array element,
+    "appends an element to the end of an array."
+);
+nary_scalar_expr!(ArrayConcat, array_concat, "concatenates arrays.");
+scalar_expr!(
+    ArrayContains,
+    array_contains,
+    first_array second_array,
+    "returns true, if each element of the second array appears in the first array, otherwise false."
+);
+scalar_expr!(
+    ArrayDims,
+    array_dims,
+    array,
+    "returns an array of the array's dimensions."
+);
+nary_scalar_expr!(
+    ArrayLength,
+    array_length,
+    "returns the length of the array dimension."
+);
+nary_scalar_expr!(
+    ArrayPosition,
+    array_position,
+    "searches for an element in the array, returns first occurrence."
+);
+scalar_expr!(
+    ArrayPositions,
+    array_positions,
+    array element,
+    "searches for an element in the array, returns all occurrences."
+);
+scalar_expr!(
+    ArrayPrepend,
+    array_prepend,
+    element array,
+    "prepends an element to the beginning of an array."
+);
+scalar_expr!(
+    ArrayRemove,
+    array_remove,
+    array element,
+    "removes all elements equal to the given value from the array."
+);
+scalar_expr!(
+    ArrayReplace,
+    array_replace,
+    array from to,
+    "replaces all occurrences of the specified element with another specified element."
+);
+scalar_expr!(
+    ArraySlice,
+    array_slice,
+    array begin end,
+    "returns a slice of the array between the 1-based begin and end indexes, inclusive."
+);
+scalar_expr!(
+    ArrayToString,
+    array_to_string,
+    array delimiter,
+    "converts each element to its text representation and joins them with a delimiter."
+);
+scalar_expr!(
+    Cardinality,
+    cardinality,
+    array,
+    "returns the total number of elements in the array."
+);
+scalar_expr!(
+    Flatten,
+    flatten,
+    array,
+    "flattens an array of arrays into a single array."
+);
+
 // date functions
 scalar_expr!(DatePart, date_part, part date, "extracts a subfield from the date");
 scalar_expr!(DateTrunc, date_trunc, part date, "truncates the date to a specified level of precision");
@@ -699,6 +780,24 @@ pub fn create_udaf(
     )
 }
 
+/// Creates a new UDWF with a specific signature and return type.
+/// The signature and return type must match the `PartitionEvaluator`'s implementation.
+pub fn create_udwf(
+    name: &str,
+    input_types: Vec<DataType>,
+    return_type: Arc<DataType>,
+    volatility: Volatility,
+    partition_evaluator_factory: PartitionEvaluatorFunctionImplementation,
+) -> WindowUDF {
+    let return_type: ReturnTypeFunction = Arc::new(move |_| Ok(return_type.clone()));
+    WindowUDF::new(
+        name,
+        &Signature::exact(input_types, volatility),
+        &return_type,
+        &partition_evaluator_factory,
+    )
+}
+
 /// Calls a named built in function
 /// ```
 /// use datafusion_expr::{col, lit, call_fn};
@@ -874,6 +973,21 @@ mod test {
         test_scalar_expr!(FromUnixtime, from_unixtime, unixtime);
 
         test_unary_scalar_expr!(ArrowTypeof, arrow_typeof);
+
+        test_scalar_expr!(ArrayAppend, array_append, array, element);
+        test_nary_scalar_expr!(ArrayConcat, array_concat, first, second);
+        test_scalar_expr!(ArrayContains, array_contains, first, second);
+        test_scalar_expr!(ArrayDims, array_dims, array);
+        test_nary_scalar_expr!(ArrayLength, array_length, array, dimension);
+        test_nary_scalar_expr!(ArrayPosition, array_position, array, element, index);
+        test_scalar_expr!(ArrayPositions, array_positions, array, element);
+        test_scalar_expr!(ArrayPrepend, array_prepend, element, array);
+        test_scalar_expr!(ArrayRemove, array_remove, array, element);
+        test_scalar_expr!(ArrayReplace, array_replace, array, from, to);
+        test_scalar_expr!(ArraySlice, array_slice, array, begin, end);
+        test_scalar_expr!(ArrayToString, array_to_string, array, delimiter);
+        test_scalar_expr!(Cardinality, cardinality, array);
+        test_scalar_expr!(Flatten, flatten, array);
     }
 
     #[test]
@@ -22,7 +22,8 @@ use crate::type_coercion::functions::data_types;
 use crate::ColumnarValue;
 use crate::{
     array_expressions, conditional_expressions, struct_expressions, Accumulator,
-    BuiltinScalarFunction, Signature, TypeSignature,
+    ArrayArgument, BuiltinScalarFunction, GroupsAccumulator, PartitionEvaluator,
+    Signature, TypeSignature,
 };
 use arrow::datatypes::{DataType, Field, Fields, IntervalUnit, TimeUnit};
 use datafusion_common::{DataFusionError, Result};
@@ -48,11 +49,21 @@ pub type ReturnTypeFunction =
 pub type AccumulatorFunctionImplementation =
     Arc<dyn Fn(&DataType) -> Result<Box<dyn Accumulator>> + Send + Sync>;
 
+/// Factory that returns a [`GroupsAccumulator`] for the given aggregate,
+/// given its return datatype.
+pub type GroupsAccumulatorFunctionImplementation =
+    Arc<dyn Fn(&DataType) -> Result<Box<dyn GroupsAccumulator>> + Send + Sync>;
+
 /// Factory that returns the types used by an aggregator to serialize
 /// its state, given its return datatype.
 pub type StateTypeFunction =
     Arc<dyn Fn(&DataType) -> Result<Arc<Vec<DataType>>> + Send + Sync>;
 
+/// Factory that returns a new partition evaluator for a user-defined window
+/// function. It is called once for each partition the function is evaluated on.
+pub type PartitionEvaluatorFunctionImplementation =
+    Arc<dyn Fn() -> Result<Box<dyn PartitionEvaluator>> + Send + Sync>;
+
 macro_rules! make_utf8_to_return_type {
     ($FUNC:ident, $largeUtf8Type:expr, $utf8Type:expr) => {
         fn $FUNC(arg_type: &DataType, name: &str) -> Result<DataType> {
@@ -111,10 +122,47 @@ pub fn return_type(
     // the return type of the built in function.
     // Some built-in functions' return type depends on the incoming type.
     match fun {
-        BuiltinScalarFunction::MakeArray => Ok(DataType::FixedSizeList(
-            Arc::new(Field::new("item", input_expr_types[0].clone(), true)),
-            input_expr_types.len() as i32,
-        )),
+        BuiltinScalarFunction::MakeArray => {
+            // the arguments might get coerced, get a preview of this
+            let coerced_types = data_types(input_expr_types, &signature(fun))?;
+            Ok(DataType::List(Arc::new(Field::new(
+                "item",
+                coerced_types[0].clone(),
+                true,
+            ))))
+        }
+        BuiltinScalarFunction::ArrayAppend
+        | BuiltinScalarFunction::ArrayConcat
+        | BuiltinScalarFunction::ArrayRemove
+        | BuiltinScalarFunction::ArrayReplace
+        | BuiltinScalarFunction::ArraySlice => {
+            // the list and the elements might get coerced, get a preview of this
+            let coerced_types = data_types(input_expr_types, &signature(fun))?;
+            Ok(coerced_types[0].clone())
+        }
+        BuiltinScalarFunction::ArrayPrepend => {
+            let coerced_types = data_types(input_expr_types, &signature(fun))?;
+            Ok(coerced_types[1].clone())
+        }
+        BuiltinScalarFunction::ArrayContains => Ok(DataType::Boolean),
+        BuiltinScalarFunction::ArrayLength
+        | BuiltinScalarFunction::ArrayPosition
+        | BuiltinScalarFunction::Cardinality => Ok(DataType::UInt64),
+        BuiltinScalarFunction::ArrayDims | BuiltinScalarFunction::ArrayPositions => Ok(
+            DataType::List(Arc::new(Field::new("item", DataType::UInt64, true))),
+        ),
+        BuiltinScalarFunction::ArrayToString => Ok(DataType::Utf8),
+        BuiltinScalarFunction::Flatten => {
+            let mut item_type = &input_expr_types[0];
+            while let DataType::List(field) = item_type {
+                item_type = field.data_type();
+            }
+            Ok(DataType::List(Arc::new(Field::new(
+                "item",
+                item_type.clone(),
+                true,
+            ))))
+        }
         BuiltinScalarFunction::Ascii => Ok(DataType::Int32),
         BuiltinScalarFunction::BitLength => {
             utf8_to_int_type(&input_expr_types[0], "bit_length")
@@ -318,8 +366,74 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
 
     // for now, the list is small, as we do not have many built-in functions.
     match fun {
-        BuiltinScalarFunction::MakeArray => Signature::variadic(
-            array_expressions::SUPPORTED_ARRAY_TYPES.to_vec(),
+        BuiltinScalarFunction::MakeArray => Signature::one_of(
+            vec![
+                TypeSignature::Variadic(
+                    array_expressions::SUPPORTED_ARRAY_TYPES.to_vec(),
+                ),
+                TypeSignature::VariadicArray,
+            ],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ArrayAppend
+        | BuiltinScalarFunction::ArrayPositions
+        | BuiltinScalarFunction::ArrayRemove => Signature::array(
+            vec![ArrayArgument::Array, ArrayArgument::Element],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ArrayPrepend => Signature::array(
+            vec![ArrayArgument::Element, ArrayArgument::Array],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ArrayConcat => Signature::variadic_array(fun.volatility()),
+        BuiltinScalarFunction::ArrayContains => Signature::array(
+            vec![ArrayArgument::Array, ArrayArgument::Array],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ArrayDims
+        | BuiltinScalarFunction::Cardinality
+        | BuiltinScalarFunction::Flatten => {
+            Signature::array(vec![ArrayArgument::Array], fun.volatility())
+        }
+        BuiltinScalarFunction::ArrayLength => Signature::one_of(
+            vec![
+                TypeSignature::Array(vec![ArrayArgument::Array]),
+                TypeSignature::Array(vec![
+                    ArrayArgument::Array,
+                    ArrayArgument::Other(DataType::Int64),
+                ]),
+            ],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ArrayPosition => Signature::one_of(
+            vec![
+                TypeSignature::Array(vec![ArrayArgument::Array, ArrayArgument::Element]),
+                TypeSignature::Array(vec![
+                    ArrayArgument::Array,
+                    ArrayArgument::Element,
+                    ArrayArgument::Other(DataType::Int64),
+                ]),
+            ],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ArrayReplace => Signature::array(
+            vec![
+                ArrayArgument::Array,
+                ArrayArgument::Element,
+                ArrayArgument::Element,
+            ],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ArraySlice => Signature::array(
+            vec![
+                ArrayArgument::Array,
+                ArrayArgument::Other(DataType::Int64),
+                ArrayArgument::Other(DataType::Int64),
+            ],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ArrayToString => Signature::array(
+            vec![ArrayArgument::Array, ArrayArgument::Other(DataType::Utf8)],
             fun.volatility(),
         ),
         BuiltinScalarFunction::Struct => Signature::variadic(
@@ -0,0 +1,83 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! GroupsAccumulator module contains the trait definition for vectorized grouped aggregation.
+
+use arrow::array::{ArrayRef, BooleanArray};
+use datafusion_common::Result;
+use std::fmt::Debug;
+
+/// A [`GroupsAccumulator`] accumulates the state of *all* groups of a grouped
+/// aggregation, in contrast to an [`Accumulator`](crate::Accumulator), of which
+/// there is one instance per group.
+///
+/// Each group is identified by its group index, a number in the range
+/// `0..total_num_groups`. The states of the groups are typically stored in
+/// columnar vectors indexed by the group index, so that a whole input batch is
+/// aggregated with a single call instead of one call per distinct group key.
+///
+/// A [`GroupsAccumulator`] knows how to:
+/// * update the state of the groups from inputs via `update_batch`
+/// * update the state of the groups from intermediate states via `merge_batch`
+/// * convert the state of all groups to intermediate states via `state`
+/// * compute the final value of all groups via `evaluate`
+pub trait GroupsAccumulator: Send + Sync + Debug {
+    /// Updates the state of the groups from a vector of arrays.
+    ///
+    /// * `values`: the input arguments to the aggregate function
+    /// * `group_indices`: the group index of each row in `values`
+    /// * `opt_filter`: if present, only rows for which the filter is `true`
+    ///   are aggregated
+    /// * `total_num_groups`: the number of groups; all values in
+    ///   `group_indices` are smaller than this, and the state must grow to
+    ///   hold the groups not seen before
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()>;
+
+    /// Updates the state of the groups from intermediate states, as returned
+    /// by [`Self::state`], with the same meaning of `group_indices`,
+    /// `opt_filter` and `total_num_groups` as in [`Self::update_batch`].
+    fn merge_batch(
+        &mut self,
+        states: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()>;
+
+    /// Returns the final aggregate value of every group, ordered by group
+    /// index, and resets the state to contain no groups.
+    fn evaluate(&mut self) -> Result<ArrayRef>;
+
+    /// Returns the intermediate state of every group, one array per state
+    /// field ordered by group index, and resets the state to contain no
+    /// groups.
+    ///
+    /// The arrays have the same types as the state fields of the aggregate
+    /// and are passed to [`Self::merge_batch`] of other instances.
+    fn state(&mut self) -> Result<Vec<ArrayRef>>;
+
+    /// Allocated size required for this accumulator, in bytes, including `Self`.
+    /// Allocated means that for internal containers such as `Vec`, the `capacity` should be used
+    /// not the `len`
+    fn size(&self) -> usize;
+}
@@ -37,10 +37,12 @@ pub mod expr_rewriter;
 pub mod expr_schema;
 pub mod field_util;
 pub mod function;
+mod groups_accumulator;
 mod literal;
 pub mod logical_plan;
 mod nullif;
 mod operator;
+pub mod partition_evaluator;
 mod signature;
 pub mod struct_expressions;
 mod table_source;
@@ -48,9 +50,12 @@ pub mod tree_node;
 pub mod type_coercion;
 mod udaf;
 mod udf;
+mod udwf;
 pub mod utils;
 pub mod window_frame;
+pub mod window_frame_state;
 pub mod window_function;
+pub mod window_state;
 
 pub use accumulator::Accumulator;
 pub use aggregate_function::AggregateFunction;
@@ -58,20 +63,25 @@ pub use built_in_function::BuiltinScalarFunction;
 pub use columnar_value::ColumnarValue;
 pub use expr::{
     Between, BinaryExpr, Case, Cast, Expr, GetIndexedField, GroupingSet, Like, TryCast,
+    WildcardOptions,
 };
 pub use expr_fn::*;
 pub use expr_schema::ExprSchemable;
 pub use function::{
-    AccumulatorFunctionImplementation, ReturnTypeFunction, ScalarFunctionImplementation,
-    StateTypeFunction,
+    AccumulatorFunctionImplementation, GroupsAccumulatorFunctionImplementation,
+    PartitionEvaluatorFunctionImplementation, ReturnTypeFunction,
+    ScalarFunctionImplementation, StateTypeFunction,
 };
+pub use groups_accumulator::GroupsAccumulator;
 pub use literal::{lit, lit_timestamp_nano, Literal, TimestampLiteral};
 pub use logical_plan::*;
 pub use nullif::SUPPORTED_NULLIF_TYPES;
 pub use operator::Operator;
-pub use signature::{Signature, TypeSignature, Volatility};
+pub use partition_evaluator::PartitionEvaluator;
+pub use signature::{ArrayArgument, Signature, TypeSignature, Volatility};
 pub use table_source::{TableProviderFilterPushDown, TableSource, TableType};
 pub use udaf::AggregateUDF;
 pub use udf::ScalarUDF;
+pub use udwf::WindowUDF;
 pub use window_frame::{WindowFrame, WindowFrameBound, WindowFrameUnits};
 pub use window_function::{BuiltInWindowFunction, WindowFunction};
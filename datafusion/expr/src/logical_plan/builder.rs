@@ -29,8 +29,8 @@ use crate::{
     logical_plan::{
         Aggregate, Analyze, CrossJoin, Distinct, EmptyRelation, Explain, Filter, Join,
         JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-        Projection, Repartition, Sort, SubqueryAlias, TableScan, ToStringifiedPlan,
-        Union, Unnest, Values, Window,
+        Projection, RecursiveQuery, Repartition, Sort, SubqueryAlias, TableScan,
+        ToStringifiedPlan, Union, Unnest, Values, Window,
     },
     utils::{
         can_hash, expand_qualified_wildcard, expand_wildcard,
@@ -238,12 +238,12 @@ impl LogicalPlanBuilder {
         table_schema: &Schema,
     ) -> Result<Self> {
         let table_schema = table_schema.clone().to_dfschema_ref()?;
-        Ok(Self::from(LogicalPlan::Dml(DmlStatement {
-            table_name: table_name.into(),
+        Ok(Self::from(LogicalPlan::Dml(DmlStatement::new(
+            table_name.into(),
             table_schema,
-            op: WriteOp::Insert,
-            input: Arc::new(input),
-        })))
+            WriteOp::Insert,
+            Arc::new(input),
+        ))))
     }
 
     /// Convert a table provider into a builder with a TableScan
@@ -503,6 +503,23 @@ impl LogicalPlanBuilder {
     pub fn sort(
         self,
         exprs: impl IntoIterator<Item = impl Into<Expr>> + Clone,
+    ) -> Result<Self> {
+        self.sort_impl(exprs, false)
+    }
+
+    /// Apply a sort to each partition of the input on its own, as Hive's
+    /// `SORT BY` does
+    pub fn sort_within_partitions(
+        self,
+        exprs: impl IntoIterator<Item = impl Into<Expr>> + Clone,
+    ) -> Result<Self> {
+        self.sort_impl(exprs, true)
+    }
+
+    fn sort_impl(
+        self,
+        exprs: impl IntoIterator<Item = impl Into<Expr>> + Clone,
+        preserve_partitioning: bool,
     ) -> Result<Self> {
         let exprs = rewrite_sort_cols_by_aggs(exprs, &self.plan)?;
 
@@ -526,11 +543,14 @@ impl LogicalPlanBuilder {
             })?;
 
         if missing_cols.is_empty() {
-            return Ok(Self::from(LogicalPlan::Sort(Sort {
-                expr: normalize_cols(exprs, &self.plan)?,
-                input: Arc::new(self.plan),
-                fetch: None,
-            })));
+            return Ok(Self::from(LogicalPlan::Sort(
+                Sort::new(
+                    normalize_cols(exprs, &self.plan)?,
+                    Arc::new(self.plan),
+                    None,
+                )
+                .with_preserve_partitioning(preserve_partitioning),
+            )));
         }
 
         // remove pushed down sort columns
@@ -542,11 +562,10 @@ impl LogicalPlanBuilder {
 
         let is_distinct = false;
         let plan = Self::add_missing_columns(self.plan, &missing_cols, is_distinct)?;
-        let sort_plan = LogicalPlan::Sort(Sort {
-            expr: normalize_cols(exprs, &plan)?,
-            input: Arc::new(plan),
-            fetch: None,
-        });
+        let sort_plan = LogicalPlan::Sort(
+            Sort::new(normalize_cols(exprs, &plan)?, Arc::new(plan), None)
+                .with_preserve_partitioning(preserve_partitioning),
+        );
 
         Ok(Self::from(LogicalPlan::Projection(Projection::try_new(
             new_expr,
@@ -576,6 +595,35 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Make a recursive query out of this plan, the static term, and
+    /// `recursive_term`, which reads the rows of the previous iteration from
+    /// the work table called `name`.
+    ///
+    /// The output columns of the recursive term are cast to the types of the
+    /// static term.
+    pub fn to_recursive_query(
+        self,
+        name: String,
+        recursive_term: LogicalPlan,
+        is_distinct: bool,
+    ) -> Result<Self> {
+        let static_col_num = self.plan.schema().fields().len();
+        let recursive_col_num = recursive_term.schema().fields().len();
+        if static_col_num != recursive_col_num {
+            return Err(DataFusionError::Plan(format!(
+                "Non-recursive term and recursive term must have the same number of columns ({static_col_num} != {recursive_col_num})"
+            )));
+        }
+        let recursive_term =
+            coerce_plan_expr_for_schema(&recursive_term, self.plan.schema())?;
+        Ok(Self::from(LogicalPlan::RecursiveQuery(RecursiveQuery {
+            name,
+            static_term: Arc::new(self.plan),
+            recursive_term: Arc::new(recursive_term),
+            is_distinct,
+        })))
+    }
+
     /// Apply deduplication: Only distinct (different) values are returned)
     pub fn distinct(self) -> Result<Self> {
         Ok(Self::from(LogicalPlan::Distinct(Distinct {
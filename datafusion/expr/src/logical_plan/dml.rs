@@ -16,11 +16,15 @@
 // under the License.
 
 use std::{
+    collections::HashMap,
     fmt::{self, Display},
+    hash::{Hash, Hasher},
     sync::Arc,
 };
 
-use datafusion_common::{DFSchemaRef, OwnedTableReference};
+use arrow::datatypes::DataType;
+use datafusion_common::parsers::CompressionTypeVariant;
+use datafusion_common::{DFField, DFSchema, DFSchemaRef, OwnedTableReference};
 
 use crate::LogicalPlan;
 
@@ -36,6 +40,76 @@ pub struct DmlStatement {
     pub op: WriteOp,
     /// The relation that determines the tuples to add/remove/modify the schema must match with table_schema
     pub input: Arc<LogicalPlan>,
+    /// The schema of the output relation, the number of deleted or updated
+    /// rows for `DELETE` and `UPDATE`
+    pub output_schema: DFSchemaRef,
+}
+
+impl DmlStatement {
+    /// Creates a new DML statement, deriving its output schema from `op`
+    pub fn new(
+        table_name: OwnedTableReference,
+        table_schema: DFSchemaRef,
+        op: WriteOp,
+        input: Arc<LogicalPlan>,
+    ) -> Self {
+        let output_schema = match op {
+            WriteOp::Delete | WriteOp::Update => Arc::new(
+                DFSchema::new_with_metadata(
+                    vec![DFField::new_unqualified("count", DataType::UInt64, false)],
+                    HashMap::new(),
+                )
+                .unwrap(),
+            ),
+            WriteOp::Insert | WriteOp::Ctas => table_schema.clone(),
+        };
+        Self {
+            table_name,
+            table_schema,
+            op,
+            input,
+            output_schema,
+        }
+    }
+}
+
+/// Writes the results of a query into files, as done by the
+/// `COPY (query) TO 'path'` statement
+#[derive(Clone, PartialEq, Eq)]
+pub struct CopyTo {
+    /// The query whose results are written
+    pub input: Arc<LogicalPlan>,
+    /// The location of the output: a single file, or a directory if it ends
+    /// with `/` or the output is partitioned
+    pub output_url: String,
+    /// The file type (Parquet, NDJSON, CSV, etc)
+    pub file_type: String,
+    /// Whether CSV files start with a header row
+    pub has_header: bool,
+    /// Delimiter of CSV files
+    pub delimiter: char,
+    /// The compression of the files, or of the pages of Parquet files
+    pub file_compression_type: CompressionTypeVariant,
+    /// Columns whose values name the hive style `col=value/` directories
+    /// the rows are written into. They are the last columns of `input`.
+    pub partition_by: Vec<String>,
+    /// Format specific options, as in the `OPTIONS` of `CREATE EXTERNAL TABLE`
+    pub options: HashMap<String, String>,
+}
+
+// Hashing refers to a subset of fields considered in PartialEq.
+#[allow(clippy::derived_hash_with_manual_eq)]
+impl Hash for CopyTo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.input.hash(state);
+        self.output_url.hash(state);
+        self.file_type.hash(state);
+        self.has_header.hash(state);
+        self.delimiter.hash(state);
+        self.file_compression_type.hash(state);
+        self.partition_by.hash(state);
+        self.options.len().hash(state); // HashMap is not hashable
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
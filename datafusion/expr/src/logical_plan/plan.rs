@@ -20,7 +20,7 @@ use crate::expr::{Exists, Placeholder};
 ///! Logical plan types
 use crate::logical_plan::display::{GraphvizVisitor, IndentVisitor};
 use crate::logical_plan::extension::UserDefinedLogicalNode;
-use crate::logical_plan::{DmlStatement, Statement};
+use crate::logical_plan::{CopyTo, DmlStatement, Statement};
 use crate::utils::{
     enumerate_grouping_sets, exprlist_to_fields, find_out_reference_exprs, from_plan,
     grouping_set_expr_count, grouping_set_to_exprlist, inspect_expr_pre,
@@ -111,12 +111,16 @@ pub enum LogicalPlan {
     Prepare(Prepare),
     /// Insert / Update / Delete
     Dml(DmlStatement),
+    /// Write the results of a query into files
+    Copy(CopyTo),
     /// CREATE / DROP TABLES / VIEWS / SCHEMAs
     Ddl(DdlStatement),
     /// Describe the schema of table
     DescribeTable(DescribeTable),
     /// Unnest a column that contains a nested list type.
     Unnest(Unnest),
+    /// A variadic query (e.g. "Recursive CTEs")
+    RecursiveQuery(RecursiveQuery),
 }
 
 impl LogicalPlan {
@@ -149,9 +153,14 @@ impl LogicalPlan {
             LogicalPlan::DescribeTable(DescribeTable { dummy_schema, .. }) => {
                 dummy_schema
             }
-            LogicalPlan::Dml(DmlStatement { table_schema, .. }) => table_schema,
+            LogicalPlan::Dml(DmlStatement { output_schema, .. }) => output_schema,
+            LogicalPlan::Copy(CopyTo { input, .. }) => input.schema(),
             LogicalPlan::Ddl(ddl) => ddl.schema(),
             LogicalPlan::Unnest(Unnest { schema, .. }) => schema,
+            LogicalPlan::RecursiveQuery(RecursiveQuery { static_term, .. }) => {
+                // we take the schema of the static term as the schema of the entire recursive query
+                static_term.schema()
+            }
         }
     }
 
@@ -200,6 +209,7 @@ impl LogicalPlan {
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Union(_)
             | LogicalPlan::Extension(_)
+            | LogicalPlan::RecursiveQuery(_)
             | LogicalPlan::TableScan(_) => {
                 vec![self.schema()]
             }
@@ -210,6 +220,7 @@ impl LogicalPlan {
             | LogicalPlan::Sort(_)
             | LogicalPlan::Filter(_)
             | LogicalPlan::Distinct(_)
+            | LogicalPlan::Copy(_)
             | LogicalPlan::Prepare(_) => {
                 self.inputs().iter().map(|p| p.schema()).collect()
             }
@@ -335,8 +346,10 @@ impl LogicalPlan {
             | LogicalPlan::Union(_)
             | LogicalPlan::Distinct(_)
             | LogicalPlan::Dml(_)
+            | LogicalPlan::Copy(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::DescribeTable(_)
+            | LogicalPlan::RecursiveQuery(_)
             | LogicalPlan::Prepare(_) => Ok(()),
         }
     }
@@ -364,9 +377,15 @@ impl LogicalPlan {
             LogicalPlan::Explain(explain) => vec![&explain.plan],
             LogicalPlan::Analyze(analyze) => vec![&analyze.input],
             LogicalPlan::Dml(write) => vec![&write.input],
+            LogicalPlan::Copy(CopyTo { input, .. }) => vec![input],
             LogicalPlan::Ddl(ddl) => ddl.inputs(),
             LogicalPlan::Unnest(Unnest { input, .. }) => vec![input],
             LogicalPlan::Prepare(Prepare { input, .. }) => vec![input],
+            LogicalPlan::RecursiveQuery(RecursiveQuery {
+                static_term,
+                recursive_term,
+                ..
+            }) => vec![static_term, recursive_term],
             // plans without inputs
             LogicalPlan::TableScan { .. }
             | LogicalPlan::Statement { .. }
@@ -523,11 +542,12 @@ impl LogicalPlan {
             LogicalPlan::Limit(Limit { fetch, .. }) => *fetch,
             LogicalPlan::Distinct(Distinct { input }) => input.max_rows(),
             LogicalPlan::Values(v) => Some(v.values.len()),
-            LogicalPlan::Unnest(_) => None,
+            LogicalPlan::Unnest(_) | LogicalPlan::RecursiveQuery(_) => None,
             LogicalPlan::Ddl(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Analyze(_)
             | LogicalPlan::Dml(_)
+            | LogicalPlan::Copy(_)
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::Prepare(_)
             | LogicalPlan::Statement(_)
@@ -971,6 +991,18 @@ impl LogicalPlan {
                     LogicalPlan::Dml(DmlStatement { table_name, op, .. }) => {
                         write!(f, "Dml: op=[{op}] table=[{table_name}]")
                     }
+                    LogicalPlan::Copy(CopyTo {
+                        output_url,
+                        file_type,
+                        partition_by,
+                        ..
+                    }) => {
+                        write!(f, "CopyTo: format={file_type} output_url={output_url}")?;
+                        if !partition_by.is_empty() {
+                            write!(f, " partition_by=[{}]", partition_by.join(", "))?;
+                        }
+                        Ok(())
+                    }
                     LogicalPlan::Ddl(ddl) => {
                         write!(f, "{}", ddl.display())
                     }
@@ -991,7 +1023,12 @@ impl LogicalPlan {
                         f,
                         "Aggregate: groupBy=[{group_expr:?}], aggr=[{aggr_expr:?}]"
                     ),
-                    LogicalPlan::Sort(Sort { expr, fetch, .. }) => {
+                    LogicalPlan::Sort(Sort {
+                        expr,
+                        fetch,
+                        preserve_partitioning,
+                        ..
+                    }) => {
                         write!(f, "Sort: ")?;
                         for (i, expr_item) in expr.iter().enumerate() {
                             if i > 0 {
@@ -1002,6 +1039,9 @@ impl LogicalPlan {
                         if let Some(a) = fetch {
                             write!(f, ", fetch={a}")?;
                         }
+                        if *preserve_partitioning {
+                            write!(f, ", preserve_partitioning=true")?;
+                        }
 
                         Ok(())
                     }
@@ -1108,6 +1148,16 @@ impl LogicalPlan {
                     LogicalPlan::Unnest(Unnest { column, .. }) => {
                         write!(f, "Unnest: {column}")
                     }
+                    LogicalPlan::RecursiveQuery(RecursiveQuery {
+                        name,
+                        is_distinct,
+                        ..
+                    }) => {
+                        write!(
+                            f,
+                            "RecursiveQuery: name={name}, is_distinct={is_distinct}"
+                        )
+                    }
                 }
             }
         }
@@ -1607,6 +1657,9 @@ impl Aggregate {
 }
 
 /// Sorts its input according to a list of sort expressions.
+///
+/// Breaking change: struct literals must set `preserve_partitioning`, added to
+/// support Hive's `SORT BY`. Prefer creating it with [`Sort::new`].
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Sort {
     /// The sort expressions
@@ -1615,6 +1668,27 @@ pub struct Sort {
     pub input: Arc<LogicalPlan>,
     /// Optional fetch limit
     pub fetch: Option<usize>,
+    /// Sort each partition of the input on its own instead of producing a
+    /// single sorted output, as Hive's `SORT BY` does
+    pub preserve_partitioning: bool,
+}
+
+impl Sort {
+    /// Create a new sort of `input` producing a single sorted output
+    pub fn new(expr: Vec<Expr>, input: Arc<LogicalPlan>, fetch: Option<usize>) -> Self {
+        Self {
+            expr,
+            input,
+            fetch,
+            preserve_partitioning: false,
+        }
+    }
+
+    /// Whether to sort each partition of the input on its own
+    pub fn with_preserve_partitioning(mut self, preserve_partitioning: bool) -> Self {
+        self.preserve_partitioning = preserve_partitioning;
+        self
+    }
 }
 
 /// Join two logical plans on one or more join columns
@@ -1816,6 +1890,27 @@ pub struct Unnest {
     pub schema: DFSchemaRef,
 }
 
+/// A variadic query operation, e.g. a recursive CTE.
+///
+/// The static term is evaluated once, and its output is made available to
+/// the recursive term as the work table `name`. The recursive term is then
+/// evaluated repeatedly, each time on the rows it produced in the previous
+/// iteration, until it produces no more rows. The output of the query is
+/// the union of the outputs of all iterations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecursiveQuery {
+    /// Name of the query, by which the recursive term refers to the work table
+    pub name: String,
+    /// The static term (initial contents of the working table)
+    pub static_term: Arc<LogicalPlan>,
+    /// The recursive term (evaluated on the contents of the working table
+    /// until it returns an empty set)
+    pub recursive_term: Arc<LogicalPlan>,
+    /// Should the output of the recursive term be deduplicated (`UNION`) or
+    /// not (`UNION ALL`).
+    pub is_distinct: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
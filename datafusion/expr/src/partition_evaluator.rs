@@ -15,18 +15,40 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! partition evaluation module
+//! Partition evaluation module
 
-use crate::window::window_expr::BuiltinWindowState;
-use crate::window::WindowAggState;
+use crate::window_state::{BuiltinWindowState, WindowAggState};
 use arrow::array::ArrayRef;
 use datafusion_common::Result;
 use datafusion_common::{DataFusionError, ScalarValue};
 use std::fmt::Debug;
 use std::ops::Range;
 
-/// Partition evaluator
+/// Partition evaluator, which computes the result of a window function over
+/// the rows of a single partition.
+///
+/// Built-in window functions and [`WindowUDF`](crate::WindowUDF)s implement
+/// this trait. A window function is evaluated with [`Self::evaluate`] unless
+/// it [uses the window frame](Self::uses_window_frame), in which case
+/// [`Self::evaluate_inside_range`] is called for each row, or it
+/// [includes the rank](Self::include_rank), in which case
+/// [`Self::evaluate_with_rank`] is called. Window functions that
+/// [support bounded execution](Self::supports_bounded_execution) are instead
+/// evaluated row by row with [`Self::update_state`] and
+/// [`Self::evaluate_stateful`], as input batches arrive.
 pub trait PartitionEvaluator: Debug + Send {
+    /// Whether the evaluator computes each row's result from the rows of the
+    /// window frame of that row
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    /// Whether the evaluator can compute results incrementally, without
+    /// buffering its whole partition
+    fn supports_bounded_execution(&self) -> bool {
+        false
+    }
+
     /// Whether the evaluator should be evaluated with rank
     fn include_rank(&self) -> bool {
         false
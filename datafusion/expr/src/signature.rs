@@ -58,6 +58,24 @@ pub enum TypeSignature {
     Any(usize),
     /// One of a list of signatures
     OneOf(Vec<TypeSignature>),
+    /// fixed number of arguments of the kinds of [ArrayArgument], the lists and elements
+    /// among them being coerced to a common item type
+    // A function such as `array_append` is `Array(vec![ArrayArgument::Array, ArrayArgument::Element])`
+    Array(Vec<ArrayArgument>),
+    /// arbitrary number of lists, coerced to lists of a common item type
+    // A function such as `array_concat` is `VariadicArray`
+    VariadicArray,
+}
+
+/// The kind of an argument of a [TypeSignature::Array] signature
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArrayArgument {
+    /// A `List`, coerced to a list of the common item type
+    Array,
+    /// A value, coerced to the common item type
+    Element,
+    /// A value coerced to the given type, such as an index or a delimiter
+    Other(DataType),
 }
 
 /// The signature of a function defines the supported argument types
@@ -124,6 +142,20 @@ impl Signature {
             volatility,
         }
     }
+    /// array - Creates a signature of arguments of the given kinds, whose lists and elements are coerced to a common item type.
+    pub fn array(arguments: Vec<ArrayArgument>, volatility: Volatility) -> Self {
+        Signature {
+            type_signature: TypeSignature::Array(arguments),
+            volatility,
+        }
+    }
+    /// variadic_array - Creates a variadic signature of lists, which are coerced to lists of a common item type.
+    pub fn variadic_array(volatility: Volatility) -> Self {
+        Signature {
+            type_signature: TypeSignature::VariadicArray,
+            volatility,
+        }
+    }
     /// one_of Creates a signature which can match any of the [TypeSignature]s which are passed in.
     pub fn one_of(type_signatures: Vec<TypeSignature>, volatility: Volatility) -> Self {
         Signature {
@@ -17,7 +17,7 @@
 
 use crate::{Expr, LogicalPlan};
 use arrow::datatypes::SchemaRef;
-use datafusion_common::Result;
+use datafusion_common::{Result, Statistics};
 use std::any::Any;
 
 ///! Table source
@@ -96,4 +96,10 @@ pub trait TableSource: Sync + Send {
     fn get_logical_plan(&self) -> Option<&LogicalPlan> {
         None
     }
+
+    /// Get statistics for this table, if available. They are used to
+    /// estimate the cost of logical plans, e.g. when reordering joins.
+    fn statistics(&self) -> Option<Statistics> {
+        None
+    }
 }
@@ -15,12 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::{Signature, TypeSignature};
+use crate::type_coercion::binary::comparison_coercion;
+use crate::{ArrayArgument, Signature, TypeSignature};
 use arrow::{
     compute::can_cast_types,
-    datatypes::{DataType, TimeUnit},
+    datatypes::{DataType, Field, TimeUnit},
 };
 use datafusion_common::{DataFusionError, Result};
+use std::sync::Arc;
 
 /// Performs type coercion for function arguments.
 ///
@@ -97,11 +99,69 @@ fn get_valid_types(
             .filter_map(|t| get_valid_types(t, current_types).ok())
             .flatten()
             .collect::<Vec<_>>(),
+        TypeSignature::Array(arguments) => {
+            if current_types.len() != arguments.len() {
+                return Err(DataFusionError::Plan(format!(
+                    "The function expected {} arguments but received {}",
+                    arguments.len(),
+                    current_types.len()
+                )));
+            }
+            let item_type = array_item_type(arguments.iter().zip(current_types))?;
+            vec![arguments
+                .iter()
+                .map(|argument| match argument {
+                    ArrayArgument::Array => list_type(&item_type),
+                    ArrayArgument::Element => item_type.clone(),
+                    ArrayArgument::Other(data_type) => data_type.clone(),
+                })
+                .collect()]
+        }
+        TypeSignature::VariadicArray => {
+            let item_type = array_item_type(
+                std::iter::repeat(&ArrayArgument::Array).zip(current_types),
+            )?;
+            vec![current_types
+                .iter()
+                .map(|_| list_type(&item_type))
+                .collect()]
+        }
     };
 
     Ok(valid_types)
 }
 
+/// Returns the item type that the lists and the elements among the
+/// arguments of an array function are coerced to
+fn array_item_type<'a>(
+    arguments: impl Iterator<Item = (&'a ArrayArgument, &'a DataType)>,
+) -> Result<DataType> {
+    let mut item_type = DataType::Null;
+    for (argument, current_type) in arguments {
+        let current_item_type = match (argument, current_type) {
+            (ArrayArgument::Array, DataType::List(field)) => field.data_type(),
+            (ArrayArgument::Array, DataType::Null) => &DataType::Null,
+            (ArrayArgument::Array, _) => {
+                return Err(DataFusionError::Plan(format!(
+                    "Expected a List argument but received {current_type:?}"
+                )))
+            }
+            (ArrayArgument::Element, _) => current_type,
+            (ArrayArgument::Other(_), _) => continue,
+        };
+        item_type = comparison_coercion(&item_type, current_item_type).ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "Cannot coerce the array items {item_type:?} and {current_item_type:?} to a common type"
+            ))
+        })?;
+    }
+    Ok(item_type)
+}
+
+fn list_type(item_type: &DataType) -> DataType {
+    DataType::List(Arc::new(Field::new("item", item_type.clone(), true)))
+}
+
 /// Try to coerce current_types into valid_types.
 fn maybe_data_types(
     valid_types: &[DataType],
@@ -189,6 +249,13 @@ pub fn can_coerce_from(type_into: &DataType, type_from: &DataType) -> bool {
             matches!(type_from, Utf8 | LargeUtf8)
         }
         Utf8 | LargeUtf8 => true,
+        List(field_into) => match type_from {
+            Null => true,
+            List(field_from) => {
+                can_coerce_from(field_into.data_type(), field_from.data_type())
+            }
+            _ => false,
+        },
         Null => can_cast_types(type_from, type_into),
         _ => false,
     }
@@ -236,6 +303,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_array_coercion() -> Result<()> {
+        let list = |item_type: DataType| {
+            DataType::List(Arc::new(Field::new("item", item_type, true)))
+        };
+        let signature = Signature::array(
+            vec![
+                ArrayArgument::Array,
+                ArrayArgument::Element,
+                ArrayArgument::Other(DataType::Int64),
+            ],
+            crate::Volatility::Immutable,
+        );
+
+        // the element and the items of the list are coerced to a common type
+        let types = data_types(
+            &[list(DataType::Int32), DataType::Float64, DataType::Int32],
+            &signature,
+        )?;
+        assert_eq!(
+            types,
+            vec![list(DataType::Float64), DataType::Float64, DataType::Int64]
+        );
+
+        let types = data_types(
+            &[DataType::Null, DataType::Int8, DataType::Int64],
+            &signature,
+        )?;
+        assert_eq!(
+            types,
+            vec![list(DataType::Int8), DataType::Int8, DataType::Int64]
+        );
+
+        // the first argument must be a list
+        let err = data_types(
+            &[DataType::Int32, DataType::Int32, DataType::Int64],
+            &signature,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Expected a List argument"));
+
+        let signature = Signature::variadic_array(crate::Volatility::Immutable);
+        let types = data_types(
+            &[list(DataType::Int64), list(DataType::UInt8), DataType::Null],
+            &signature,
+        )?;
+        assert_eq!(types, vec![list(DataType::Int64); 3]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_get_valid_types_one_of() -> Result<()> {
         let signature =
@@ -19,7 +19,8 @@
 
 use crate::Expr;
 use crate::{
-    AccumulatorFunctionImplementation, ReturnTypeFunction, Signature, StateTypeFunction,
+    AccumulatorFunctionImplementation, GroupsAccumulatorFunctionImplementation,
+    ReturnTypeFunction, Signature, StateTypeFunction,
 };
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
@@ -38,6 +39,9 @@ pub struct AggregateUDF {
     pub accumulator: AccumulatorFunctionImplementation,
     /// the accumulator's state's description as a function of the return type
     pub state_type: StateTypeFunction,
+    /// optional vectorized implementation, accumulating all groups of a
+    /// grouped aggregation at once
+    pub groups_accumulator: Option<GroupsAccumulatorFunctionImplementation>,
 }
 
 impl Debug for AggregateUDF {
@@ -80,9 +84,22 @@ impl AggregateUDF {
             return_type: return_type.clone(),
             accumulator: accumulator.clone(),
             state_type: state_type.clone(),
+            groups_accumulator: None,
         }
     }
 
+    /// Provides a [`GroupsAccumulator`](crate::GroupsAccumulator) for this
+    /// UDAF, used instead of one `accumulator` per group in grouped
+    /// aggregations. Its state must have the same description as the state
+    /// of `accumulator`.
+    pub fn with_groups_accumulator(
+        mut self,
+        groups_accumulator: GroupsAccumulatorFunctionImplementation,
+    ) -> Self {
+        self.groups_accumulator = Some(groups_accumulator);
+        self
+    }
+
     /// creates a logical expression with a call of the UDAF
     /// This utility allows using the UDAF without requiring access to the registry.
     pub fn call(&self, args: Vec<Expr>) -> Expr {
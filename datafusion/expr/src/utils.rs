@@ -21,13 +21,13 @@ use crate::expr::{Sort, WindowFunction};
 use crate::logical_plan::builder::build_join_schema;
 use crate::logical_plan::{
     Aggregate, Analyze, Distinct, Extension, Filter, Join, Limit, Partitioning, Prepare,
-    Projection, Repartition, Sort as SortPlan, Subquery, SubqueryAlias, Union, Unnest,
-    Values, Window,
+    Projection, RecursiveQuery, Repartition, Sort as SortPlan, Subquery, SubqueryAlias,
+    Union, Unnest, Values, Window,
 };
 use crate::{
-    BinaryExpr, Cast, CreateMemoryTable, CreateView, DdlStatement, DmlStatement, Expr,
-    ExprSchemable, GroupingSet, LogicalPlan, LogicalPlanBuilder, Operator, TableScan,
-    TryCast,
+    BinaryExpr, Cast, CopyTo, CreateMemoryTable, CreateView, DdlStatement, DmlStatement,
+    Expr, ExprSchemable, GroupingSet, LogicalPlan, LogicalPlanBuilder, Operator,
+    TableScan, TryCast, WildcardOptions,
 };
 use arrow::datatypes::{DataType, TimeUnit};
 use datafusion_common::tree_node::{
@@ -317,6 +317,16 @@ pub fn expr_to_columns(expr: &Expr, accum: &mut HashSet<Column>) -> Result<()> {
 
 /// Resolves an `Expr::Wildcard` to a collection of `Expr::Column`'s.
 pub fn expand_wildcard(schema: &DFSchema, plan: &LogicalPlan) -> Result<Vec<Expr>> {
+    expand_wildcard_with_options(schema, plan, None)
+}
+
+/// Resolves an `Expr::Wildcard` to a collection of `Expr::Column`'s, adjusted
+/// by the wildcard `options`, if any.
+pub fn expand_wildcard_with_options(
+    schema: &DFSchema,
+    plan: &LogicalPlan,
+    options: Option<&WildcardOptions>,
+) -> Result<Vec<Expr>> {
     let using_columns = plan.using_columns()?;
     let columns_to_skip = using_columns
         .into_iter()
@@ -340,32 +350,29 @@ pub fn expand_wildcard(schema: &DFSchema, plan: &LogicalPlan) -> Result<Vec<Expr
         })
         .collect::<HashSet<_>>();
 
-    if columns_to_skip.is_empty() {
-        Ok(schema
-            .fields()
-            .iter()
-            .map(|f| Expr::Column(f.qualified_column()))
-            .collect::<Vec<Expr>>())
-    } else {
-        Ok(schema
-            .fields()
-            .iter()
-            .filter_map(|f| {
-                let col = f.qualified_column();
-                if !columns_to_skip.contains(&col) {
-                    Some(Expr::Column(col))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<Expr>>())
-    }
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|f| f.qualified_column())
+        .filter(|col| !columns_to_skip.contains(col))
+        .collect::<Vec<_>>();
+    apply_wildcard_options(columns, options)
 }
 
 /// Resolves an `Expr::Wildcard` to a collection of qualified `Expr::Column`'s.
 pub fn expand_qualified_wildcard(
     qualifier: &str,
     schema: &DFSchema,
+) -> Result<Vec<Expr>> {
+    expand_qualified_wildcard_with_options(qualifier, schema, None)
+}
+
+/// Resolves an `Expr::QualifiedWildcard` to a collection of qualified
+/// `Expr::Column`'s, adjusted by the wildcard `options`, if any.
+pub fn expand_qualified_wildcard_with_options(
+    qualifier: &str,
+    schema: &DFSchema,
+    options: Option<&WildcardOptions>,
 ) -> Result<Vec<Expr>> {
     let qualifier = TableReference::from(qualifier);
     let qualified_fields: Vec<DFField> = schema
@@ -381,11 +388,60 @@ pub fn expand_qualified_wildcard(
     let qualified_schema =
         DFSchema::new_with_metadata(qualified_fields, schema.metadata().clone())?;
     // if qualified, allow all columns in output (i.e. ignore using column check)
-    Ok(qualified_schema
+    let columns = qualified_schema
         .fields()
         .iter()
-        .map(|f| Expr::Column(f.qualified_column()))
-        .collect::<Vec<Expr>>())
+        .map(|f| f.qualified_column())
+        .collect::<Vec<_>>();
+    apply_wildcard_options(columns, options)
+}
+
+/// Excludes, replaces and renames the `columns` a wildcard expands to
+fn apply_wildcard_options(
+    columns: Vec<Column>,
+    options: Option<&WildcardOptions>,
+) -> Result<Vec<Expr>> {
+    let options = match options {
+        Some(options) => options,
+        None => return Ok(columns.into_iter().map(Expr::Column).collect()),
+    };
+
+    let option_columns = options
+        .exclude
+        .iter()
+        .chain(options.replace.iter().map(|(name, _)| name))
+        .chain(options.rename.iter().map(|(old_name, _)| old_name));
+    for name in option_columns {
+        if !columns.iter().any(|col| &col.name == name) {
+            return Err(DataFusionError::Plan(format!(
+                "Column {name} referenced by wildcard options is not part of the wildcard"
+            )));
+        }
+    }
+
+    Ok(columns
+        .into_iter()
+        .filter(|col| !options.exclude.contains(&col.name))
+        .map(|col| {
+            let replacement = options
+                .replace
+                .iter()
+                .find(|(name, _)| name == &col.name)
+                .map(|(_, expr)| expr.clone());
+            let new_name = options
+                .rename
+                .iter()
+                .find(|(old_name, _)| old_name == &col.name)
+                .map(|(_, new_name)| new_name.clone());
+            match (replacement, new_name) {
+                (replacement, Some(new_name)) => {
+                    replacement.unwrap_or(Expr::Column(col)).alias(new_name)
+                }
+                (Some(replacement), None) => replacement.alias(col.name),
+                (None, None) => Expr::Column(col),
+            }
+        })
+        .collect())
 }
 
 /// (expr, "is the SortExpr for window (either comes from PARTITION BY or ORDER BY columns)")
@@ -666,11 +722,15 @@ pub fn from_plan(
             table_schema,
             op,
             ..
-        }) => Ok(LogicalPlan::Dml(DmlStatement {
-            table_name: table_name.clone(),
-            table_schema: table_schema.clone(),
-            op: op.clone(),
+        }) => Ok(LogicalPlan::Dml(DmlStatement::new(
+            table_name.clone(),
+            table_schema.clone(),
+            op.clone(),
+            Arc::new(inputs[0].clone()),
+        ))),
+        LogicalPlan::Copy(copy) => Ok(LogicalPlan::Copy(CopyTo {
             input: Arc::new(inputs[0].clone()),
+            ..copy.clone()
         })),
         LogicalPlan::Values(Values { schema, .. }) => Ok(LogicalPlan::Values(Values {
             schema: schema.clone(),
@@ -762,11 +822,14 @@ pub fn from_plan(
             expr[group_expr.len()..].to_vec(),
             schema.clone(),
         )?)),
-        LogicalPlan::Sort(SortPlan { fetch, .. }) => Ok(LogicalPlan::Sort(SortPlan {
-            expr: expr.to_vec(),
-            input: Arc::new(inputs[0].clone()),
-            fetch: *fetch,
-        })),
+        LogicalPlan::Sort(SortPlan {
+            fetch,
+            preserve_partitioning,
+            ..
+        }) => Ok(LogicalPlan::Sort(
+            SortPlan::new(expr.to_vec(), Arc::new(inputs[0].clone()), *fetch)
+                .with_preserve_partitioning(*preserve_partitioning),
+        )),
         LogicalPlan::Join(Join {
             join_type,
             join_constraint,
@@ -949,6 +1012,14 @@ pub fn from_plan(
                 schema,
             }))
         }
+        LogicalPlan::RecursiveQuery(RecursiveQuery {
+            name, is_distinct, ..
+        }) => Ok(LogicalPlan::RecursiveQuery(RecursiveQuery {
+            name: name.clone(),
+            static_term: Arc::new(inputs[0].clone()),
+            recursive_term: Arc::new(inputs[1].clone()),
+            is_distinct: *is_distinct,
+        })),
     }
 }
 
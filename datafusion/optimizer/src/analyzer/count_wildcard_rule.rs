@@ -86,16 +86,20 @@ fn analyze_internal(plan: LogicalPlan) -> Result<Transformed<LogicalPlan>> {
                 )?,
             )))
         }
-        LogicalPlan::Sort(Sort { expr, input, fetch }) => {
+        LogicalPlan::Sort(Sort {
+            expr,
+            input,
+            fetch,
+            preserve_partitioning,
+        }) => {
             let sort_expr = expr
                 .iter()
                 .map(|expr| expr.clone().rewrite(&mut rewriter))
                 .collect::<Result<Vec<_>>>()?;
-            Ok(Transformed::Yes(LogicalPlan::Sort(Sort {
-                expr: sort_expr,
-                input,
-                fetch,
-            })))
+            Ok(Transformed::Yes(LogicalPlan::Sort(
+                Sort::new(sort_expr, input, fetch)
+                    .with_preserve_partitioning(preserve_partitioning),
+            )))
         }
         LogicalPlan::Projection(projection) => {
             let projection_expr = projection
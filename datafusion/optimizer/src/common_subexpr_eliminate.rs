@@ -306,7 +306,12 @@ impl OptimizerRule for CommonSubexprEliminate {
                     )?))
                 }
             }
-            LogicalPlan::Sort(Sort { expr, input, fetch }) => {
+            LogicalPlan::Sort(Sort {
+                expr,
+                input,
+                fetch,
+                preserve_partitioning,
+            }) => {
                 let input_schema = Arc::clone(input.schema());
                 let arrays =
                     to_arrays(expr, input_schema, &mut expr_set, ExprMask::Normal)?;
@@ -314,11 +319,10 @@ impl OptimizerRule for CommonSubexprEliminate {
                 let (mut new_expr, new_input) =
                     self.rewrite_expr(&[expr], &[&arrays], input, &mut expr_set, config)?;
 
-                Some(LogicalPlan::Sort(Sort {
-                    expr: pop_expr(&mut new_expr)?,
-                    input: Arc::new(new_input),
-                    fetch: *fetch,
-                }))
+                Some(LogicalPlan::Sort(
+                    Sort::new(pop_expr(&mut new_expr)?, Arc::new(new_input), *fetch)
+                        .with_preserve_partitioning(*preserve_partitioning),
+                ))
             }
             LogicalPlan::Join(_)
             | LogicalPlan::CrossJoin(_)
@@ -338,7 +342,9 @@ impl OptimizerRule for CommonSubexprEliminate {
             | LogicalPlan::Distinct(_)
             | LogicalPlan::Extension(_)
             | LogicalPlan::Dml(_)
+            | LogicalPlan::Copy(_)
             | LogicalPlan::Unnest(_)
+            | LogicalPlan::RecursiveQuery(_)
             | LogicalPlan::Prepare(_) => {
                 // apply the optimization to all inputs of the plan
                 utils::optimize_children(self, plan, config)?
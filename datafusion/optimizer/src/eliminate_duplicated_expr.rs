@@ -67,11 +67,14 @@ impl OptimizerRule for EliminateDuplicatedExpr {
                 if dedup_expr.len() == sort.expr.len() {
                     Ok(None)
                 } else {
-                    Ok(Some(LogicalPlan::Sort(Sort {
-                        expr: dedup_expr.into_iter().cloned().collect::<Vec<_>>(),
-                        input: sort.input.clone(),
-                        fetch: sort.fetch,
-                    })))
+                    Ok(Some(LogicalPlan::Sort(
+                        Sort::new(
+                            dedup_expr.into_iter().cloned().collect::<Vec<_>>(),
+                            sort.input.clone(),
+                            sort.fetch,
+                        )
+                        .with_preserve_partitioning(sort.preserve_partitioning),
+                    )))
                 }
             }
             LogicalPlan::Aggregate(agg) => {
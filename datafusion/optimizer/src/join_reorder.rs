@@ -0,0 +1,709 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Optimizer rule to reorder multi-way inner joins based on table statistics.
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::{Column, Result};
+use datafusion_expr::logical_plan::{Join, JoinType, LogicalPlan, Projection};
+use datafusion_expr::{Expr, LogicalPlanBuilder};
+
+/// Selectivity assumed for filters, whose selectivity is not estimated
+const DEFAULT_FILTER_SELECTIVITY: f64 = 0.2;
+
+/// Join graphs with at most this many relations are ordered by dynamic
+/// programming, larger ones greedily
+const MAX_DP_RELATIONS: usize = 10;
+
+/// Join graphs with more relations are not reordered, as sets of relations
+/// are represented by the bits of a `u64`
+const MAX_RELATIONS: usize = 64;
+
+#[derive(Default)]
+pub struct JoinReorder;
+
+impl JoinReorder {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reorders trees of inner equijoins between three or more relations, to
+/// minimize the estimated number of rows of the intermediate results.
+///
+/// The number of rows of a relation is estimated from the statistics of the
+/// tables it scans, and the number of rows of a join from the number of
+/// distinct values of its join keys, as `|A| * |B| / max(ndv(A.x), ndv(B.y))`
+/// for a predicate `A.x = B.y`. Keys without a distinct count are assumed to
+/// be unique.
+///
+/// Join graphs of up to 10 relations are ordered by dynamic programming over
+/// their connected subsets, larger ones greedily, by repeatedly joining the
+/// two subtrees with the smallest result. Cross joins are never introduced,
+/// and the smaller input of every join is placed on its left, i.e. build,
+/// side. The plan is only rewritten if the new order is estimated to be
+/// cheaper, and join trees scanning a table without statistics are left
+/// unchanged.
+impl OptimizerRule for JoinReorder {
+    fn try_optimize(
+        &self,
+        plan: &LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        if !config.options().optimizer.enable_join_reordering {
+            return Ok(None);
+        }
+
+        match plan {
+            LogicalPlan::Join(join) if is_reorderable(join) => {}
+            _ => return Ok(None),
+        }
+
+        let mut graph = JoinGraph::default();
+        let original = match graph.add_plan(plan)? {
+            Some(tree) => tree,
+            None => return Ok(None),
+        };
+        let num_relations = graph.relations.len();
+        if !(3..=MAX_RELATIONS).contains(&num_relations) {
+            return Ok(None);
+        }
+        let model = match CostModel::try_new(&graph) {
+            Some(model) => model,
+            None => return Ok(None),
+        };
+
+        let reordered = if num_relations <= MAX_DP_RELATIONS {
+            dp_order(&model, num_relations)
+        } else {
+            greedy_order(&model, num_relations)
+        };
+        let reordered = match reordered {
+            Some(tree) => tree,
+            None => return Ok(None),
+        };
+        if model.cost(&reordered).1 >= model.cost(&original).1 {
+            return Ok(None);
+        }
+
+        let (new_plan, _) = graph.build_plan(&reordered, &model)?;
+        // The columns of the reordered joins are in a different order
+        Ok(Some(LogicalPlan::Projection(Projection::new_from_schema(
+            Arc::new(new_plan),
+            plan.schema().clone(),
+        ))))
+    }
+
+    fn name(&self) -> &str {
+        "join_reorder"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+/// Whether the inputs of `join` can be joined in any order
+fn is_reorderable(join: &Join) -> bool {
+    join.join_type == JoinType::Inner
+        && join.filter.is_none()
+        && !join.null_equals_null
+        && !join.on.is_empty()
+}
+
+/// A tree of joins between the relations of a [`JoinGraph`]
+enum JoinTree {
+    Relation(usize),
+    Join(Box<JoinTree>, Box<JoinTree>),
+}
+
+/// Equijoin predicate `left_key = right_key` between two relations
+struct JoinEdge {
+    left: usize,
+    right: usize,
+    left_key: Expr,
+    right_key: Expr,
+}
+
+/// The relations joined by a tree of inner joins, and the equijoin
+/// predicates between them
+#[derive(Default)]
+struct JoinGraph {
+    relations: Vec<LogicalPlan>,
+    edges: Vec<JoinEdge>,
+}
+
+impl JoinGraph {
+    /// Add the relations and predicates of `plan` to the graph, returning
+    /// the tree of joins between them, or `None` if a join key does not
+    /// reference exactly one relation
+    fn add_plan(&mut self, plan: &LogicalPlan) -> Result<Option<JoinTree>> {
+        match plan {
+            LogicalPlan::Join(join) if is_reorderable(join) => {
+                let left = match self.add_plan(&join.left)? {
+                    Some(tree) => tree,
+                    None => return Ok(None),
+                };
+                let right = match self.add_plan(&join.right)? {
+                    Some(tree) => tree,
+                    None => return Ok(None),
+                };
+                for (left_key, right_key) in &join.on {
+                    match (self.relation_of(left_key)?, self.relation_of(right_key)?) {
+                        (Some(left_relation), Some(right_relation))
+                            if left_relation != right_relation =>
+                        {
+                            self.edges.push(JoinEdge {
+                                left: left_relation,
+                                right: right_relation,
+                                left_key: left_key.clone(),
+                                right_key: right_key.clone(),
+                            })
+                        }
+                        _ => return Ok(None),
+                    }
+                }
+                Ok(Some(JoinTree::Join(Box::new(left), Box::new(right))))
+            }
+            _ => {
+                self.relations.push(plan.clone());
+                Ok(Some(JoinTree::Relation(self.relations.len() - 1)))
+            }
+        }
+    }
+
+    /// The only relation whose columns are referenced by `expr`, if any
+    fn relation_of(&self, expr: &Expr) -> Result<Option<usize>> {
+        let columns = expr.to_columns()?;
+        if columns.is_empty() {
+            return Ok(None);
+        }
+        let mut relations = self.relations.iter().enumerate().filter(|(_, plan)| {
+            columns
+                .iter()
+                .all(|column| plan.schema().has_column(column))
+        });
+        match (relations.next(), relations.next()) {
+            (Some((index, _)), None) => Ok(Some(index)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Build the joins of `tree`, returning the plan and the set of its
+    /// relations
+    fn build_plan(
+        &self,
+        tree: &JoinTree,
+        model: &CostModel,
+    ) -> Result<(LogicalPlan, u64)> {
+        match tree {
+            JoinTree::Relation(index) => Ok((self.relations[*index].clone(), 1 << index)),
+            JoinTree::Join(left, right) => {
+                let (mut left, mut left_set) = self.build_plan(left, model)?;
+                let (mut right, mut right_set) = self.build_plan(right, model)?;
+                if model.rows(right_set) < model.rows(left_set) {
+                    std::mem::swap(&mut left, &mut right);
+                    std::mem::swap(&mut left_set, &mut right_set);
+                }
+
+                let (left_keys, right_keys): (Vec<_>, Vec<_>) = self
+                    .edges
+                    .iter()
+                    .filter_map(|edge| {
+                        let (left_bit, right_bit) = (1 << edge.left, 1 << edge.right);
+                        if left_set & left_bit != 0 && right_set & right_bit != 0 {
+                            Some((edge.left_key.clone(), edge.right_key.clone()))
+                        } else if left_set & right_bit != 0 && right_set & left_bit != 0 {
+                            Some((edge.right_key.clone(), edge.left_key.clone()))
+                        } else {
+                            None
+                        }
+                    })
+                    .unzip();
+
+                let plan = LogicalPlanBuilder::from(left)
+                    .join_with_expr_keys(
+                        right,
+                        JoinType::Inner,
+                        (left_keys, right_keys),
+                        None,
+                    )?
+                    .build()?;
+                Ok((plan, left_set | right_set))
+            }
+        }
+    }
+}
+
+/// Estimates the number of rows of joins between the relations of a
+/// [`JoinGraph`], with sets of relations represented as bitmasks
+struct CostModel {
+    /// Estimated number of rows of each relation
+    rows: Vec<f64>,
+    /// For each pair of relations joined by predicates, the number the
+    /// product of their rows is divided by when they are joined
+    divisors: Vec<(u64, f64)>,
+}
+
+impl CostModel {
+    /// Create the cost model of `graph`, if the number of rows of all its
+    /// relations can be estimated
+    fn try_new(graph: &JoinGraph) -> Option<Self> {
+        let rows = graph
+            .relations
+            .iter()
+            .map(estimate_rows)
+            .collect::<Option<Vec<_>>>()?;
+
+        // Only the most selective predicate between two relations is taken
+        // into account, as the keys of a multi-column join are often
+        // correlated
+        let mut divisors = BTreeMap::new();
+        for edge in &graph.edges {
+            let left_distinct =
+                key_distinct_count(&graph.relations[edge.left], &edge.left_key)
+                    .map_or(rows[edge.left], |count| count.min(rows[edge.left]));
+            let right_distinct =
+                key_distinct_count(&graph.relations[edge.right], &edge.right_key)
+                    .map_or(rows[edge.right], |count| count.min(rows[edge.right]));
+            let divisor = left_distinct.max(right_distinct).max(1.0);
+
+            let pair = (1u64 << edge.left) | (1u64 << edge.right);
+            let current = divisors.entry(pair).or_insert(1.0);
+            *current = divisor.max(*current);
+        }
+
+        Some(Self {
+            rows,
+            divisors: divisors.into_iter().collect(),
+        })
+    }
+
+    /// Estimated number of rows of the join of the relations in `set`
+    fn rows(&self, set: u64) -> f64 {
+        let rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| set & (1 << index) != 0)
+            .map(|(_, rows)| rows)
+            .product::<f64>();
+        self.divisors
+            .iter()
+            .filter(|(pair, _)| set & pair == *pair)
+            .fold(rows, |rows, (_, divisor)| rows / divisor)
+    }
+
+    /// Whether a predicate joins a relation of `left` to one of `right`
+    fn connected(&self, left: u64, right: u64) -> bool {
+        self.divisors
+            .iter()
+            .any(|(pair, _)| pair & left != 0 && pair & right != 0)
+    }
+
+    /// The set of relations of `tree`, and the sum of the estimated number
+    /// of rows of its joins
+    fn cost(&self, tree: &JoinTree) -> (u64, f64) {
+        match tree {
+            JoinTree::Relation(index) => (1 << index, 0.0),
+            JoinTree::Join(left, right) => {
+                let (left_set, left_cost) = self.cost(left);
+                let (right_set, right_cost) = self.cost(right);
+                let set = left_set | right_set;
+                (set, left_cost + right_cost + self.rows(set))
+            }
+        }
+    }
+}
+
+/// Find the cheapest join tree without cross joins between `num_relations`
+/// relations, by computing the cheapest tree of every connected set of
+/// relations from those of its subsets
+fn dp_order(model: &CostModel, num_relations: usize) -> Option<JoinTree> {
+    let all: u64 = (1 << num_relations) - 1;
+    // For every set, the cost of its cheapest tree and the relations of the
+    // left subtree
+    let mut best: Vec<Option<(f64, u64)>> = vec![None; 1 << num_relations];
+    for index in 0..num_relations {
+        best[1 << index] = Some((0.0, 0));
+    }
+
+    for set in 1..=all {
+        if set.count_ones() < 2 {
+            continue;
+        }
+        let rows = model.rows(set);
+        // Only subsets containing the first relation of the set are used as
+        // left subtree, as the sides of a join are chosen when building it
+        let first = set & set.wrapping_neg();
+        let mut left = (set - 1) & set;
+        while left != 0 {
+            let right = set & !left;
+            if left & first != 0 && model.connected(left, right) {
+                if let (Some((left_cost, _)), Some((right_cost, _))) =
+                    (best[left as usize], best[right as usize])
+                {
+                    let cost = left_cost + right_cost + rows;
+                    if best[set as usize].map_or(true, |(best_cost, _)| cost < best_cost)
+                    {
+                        best[set as usize] = Some((cost, left));
+                    }
+                }
+            }
+            left = (left - 1) & set;
+        }
+    }
+
+    dp_tree(&best, all)
+}
+
+/// The cheapest tree of `set` found by [`dp_order`]
+fn dp_tree(best: &[Option<(f64, u64)>], set: u64) -> Option<JoinTree> {
+    if set.count_ones() == 1 {
+        return Some(JoinTree::Relation(set.trailing_zeros() as usize));
+    }
+    let (_, left) = best[set as usize]?;
+    Some(JoinTree::Join(
+        Box::new(dp_tree(best, left)?),
+        Box::new(dp_tree(best, set & !left)?),
+    ))
+}
+
+/// Build a join tree without cross joins between `num_relations` relations
+/// by repeatedly joining the two subtrees with the smallest result
+fn greedy_order(model: &CostModel, num_relations: usize) -> Option<JoinTree> {
+    let mut trees: Vec<(u64, JoinTree)> = (0..num_relations)
+        .map(|index| (1 << index, JoinTree::Relation(index)))
+        .collect();
+
+    while trees.len() > 1 {
+        let mut cheapest: Option<(f64, usize, usize)> = None;
+        for i in 0..trees.len() {
+            for j in i + 1..trees.len() {
+                let (left, right) = (trees[i].0, trees[j].0);
+                if !model.connected(left, right) {
+                    continue;
+                }
+                let rows = model.rows(left | right);
+                if cheapest.map_or(true, |(cheapest_rows, _, _)| rows < cheapest_rows) {
+                    cheapest = Some((rows, i, j));
+                }
+            }
+        }
+
+        // The join graph is not connected if no subtrees can be joined
+        let (_, i, j) = cheapest?;
+        let (right_set, right) = trees.remove(j);
+        let (left_set, left) = trees.remove(i);
+        trees.push((
+            left_set | right_set,
+            JoinTree::Join(Box::new(left), Box::new(right)),
+        ));
+    }
+
+    trees.pop().map(|(_, tree)| tree)
+}
+
+/// Estimate the number of rows of `plan`, if the number of rows of the
+/// tables it scans are known
+fn estimate_rows(plan: &LogicalPlan) -> Option<f64> {
+    match plan {
+        LogicalPlan::TableScan(scan) => {
+            let mut rows = scan.source.statistics()?.num_rows? as f64;
+            if !scan.filters.is_empty() {
+                rows *= DEFAULT_FILTER_SELECTIVITY;
+            }
+            Some(scan.fetch.map_or(rows, |fetch| rows.min(fetch as f64)))
+        }
+        LogicalPlan::Filter(filter) => {
+            Some(estimate_rows(&filter.input)? * DEFAULT_FILTER_SELECTIVITY)
+        }
+        LogicalPlan::Limit(limit) => {
+            let rows = (estimate_rows(&limit.input)? - limit.skip as f64).max(0.0);
+            Some(limit.fetch.map_or(rows, |fetch| rows.min(fetch as f64)))
+        }
+        LogicalPlan::Aggregate(aggregate) if aggregate.group_expr.is_empty() => Some(1.0),
+        // The number of groups is at most the number of input rows
+        LogicalPlan::Aggregate(aggregate) => estimate_rows(&aggregate.input),
+        LogicalPlan::Distinct(distinct) => estimate_rows(&distinct.input),
+        LogicalPlan::Projection(projection) => estimate_rows(&projection.input),
+        LogicalPlan::SubqueryAlias(alias) => estimate_rows(&alias.input),
+        LogicalPlan::Sort(sort) => estimate_rows(&sort.input),
+        LogicalPlan::EmptyRelation(empty) => {
+            Some(if empty.produce_one_row { 1.0 } else { 0.0 })
+        }
+        LogicalPlan::Values(values) => Some(values.values.len() as f64),
+        _ => None,
+    }
+}
+
+/// The number of distinct values of the join key `key` of `relation`, if it
+/// is a column of a table with a known distinct count
+fn key_distinct_count(relation: &LogicalPlan, key: &Expr) -> Option<f64> {
+    match key {
+        Expr::Column(column) => distinct_count(relation, column),
+        _ => None,
+    }
+}
+
+/// The number of distinct values of `column` of `plan`, if it is a column of
+/// a table with a known distinct count
+fn distinct_count(plan: &LogicalPlan, column: &Column) -> Option<f64> {
+    match plan {
+        LogicalPlan::TableScan(scan) => {
+            let index = scan.source.schema().index_of(&column.name).ok()?;
+            let column_statistics = scan.source.statistics()?.column_statistics?;
+            Some(column_statistics.get(index)?.distinct_count? as f64)
+        }
+        LogicalPlan::Projection(projection) => {
+            let index = projection.schema.index_of_column(column).ok()?;
+            match &projection.expr[index] {
+                Expr::Column(column) => distinct_count(&projection.input, column),
+                Expr::Alias(expr, _) => match expr.as_ref() {
+                    Expr::Column(column) => distinct_count(&projection.input, column),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        // These plans output the columns of their input
+        LogicalPlan::Filter(_)
+        | LogicalPlan::Limit(_)
+        | LogicalPlan::Sort(_)
+        | LogicalPlan::SubqueryAlias(_) => {
+            let index = plan.schema().index_of_column(column).ok()?;
+            let input = plan.inputs()[0];
+            distinct_count(input, &input.schema().field(index).qualified_column())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimizer::OptimizerContext;
+    use crate::test::*;
+    use arrow::datatypes::{Schema, SchemaRef};
+    use datafusion_common::{ColumnStatistics, Statistics};
+    use datafusion_expr::{col, lit, TableSource};
+    use std::any::Any;
+
+    struct StatisticsTableSource {
+        schema: SchemaRef,
+        statistics: Statistics,
+    }
+
+    impl TableSource for StatisticsTableSource {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            self.schema.clone()
+        }
+
+        fn statistics(&self) -> Option<Statistics> {
+            Some(self.statistics.clone())
+        }
+    }
+
+    /// Scan a table with columns `a`, `b` and `c`, whose statistics contain
+    /// `num_rows` and the distinct counts of the columns
+    fn scan_with_statistics(
+        name: &str,
+        num_rows: usize,
+        distinct_counts: [Option<usize>; 3],
+    ) -> Result<LogicalPlan> {
+        let source = StatisticsTableSource {
+            schema: Arc::new(Schema::new(test_table_scan_fields())),
+            statistics: Statistics {
+                num_rows: Some(num_rows),
+                column_statistics: Some(
+                    distinct_counts
+                        .into_iter()
+                        .map(|distinct_count| ColumnStatistics {
+                            distinct_count,
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+        };
+        LogicalPlanBuilder::scan(name.to_string(), Arc::new(source), None)?.build()
+    }
+
+    /// `t1 JOIN t2 ON t1.a = t2.a JOIN t3 ON t2.b = t3.b`
+    fn three_way_join(
+        t1: LogicalPlan,
+        t2: LogicalPlan,
+        t3: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        LogicalPlanBuilder::from(t1)
+            .join(t2, JoinType::Inner, (vec!["t1.a"], vec!["t2.a"]), None)?
+            .join(t3, JoinType::Inner, (vec!["t2.b"], vec!["t3.b"]), None)?
+            .build()
+    }
+
+    fn optimize(plan: &LogicalPlan) -> Option<LogicalPlan> {
+        JoinReorder::new()
+            .try_optimize(plan, &OptimizerContext::new())
+            .unwrap()
+    }
+
+    fn assert_optimized_plan_eq(plan: &LogicalPlan, expected: Vec<&str>) {
+        let optimized_plan = optimize(plan).expect("failed to optimize plan");
+        let formatted = optimized_plan.display_indent().to_string();
+        let actual: Vec<&str> = formatted.trim().lines().collect();
+
+        assert_eq!(
+            expected, actual,
+            "\n\nexpected:\n\n{expected:#?}\nactual:\n\n{actual:#?}\n\n"
+        );
+
+        assert_eq!(plan.schema(), optimized_plan.schema())
+    }
+
+    #[test]
+    fn reorder_joins() -> Result<()> {
+        // t1 JOIN t2 is estimated at 1,000,000 rows, t2 JOIN t3 at 10 rows
+        let t1 = scan_with_statistics("t1", 10_000, [Some(100), None, None])?;
+        let t2 = scan_with_statistics("t2", 10_000, [Some(100), Some(10_000), None])?;
+        let t3 = scan_with_statistics("t3", 10, [None, Some(10), None])?;
+        let plan = three_way_join(t1, t2, t3)?;
+
+        let expected = vec![
+            "Projection: t1.a, t1.b, t1.c, t2.a, t2.b, t2.c, t3.a, t3.b, t3.c",
+            "  Inner Join: t2.a = t1.a",
+            "    Inner Join: t3.b = t2.b",
+            "      TableScan: t3",
+            "      TableScan: t2",
+            "    TableScan: t1",
+        ];
+
+        assert_optimized_plan_eq(&plan, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reorder_joins_with_filtered_relation() -> Result<()> {
+        // Without distinct counts, join keys are assumed to be unique. The
+        // filter makes t3 the smallest relation
+        let t1 = scan_with_statistics("t1", 1_000, [None, None, None])?;
+        let t2 = scan_with_statistics("t2", 1_000, [None, None, None])?;
+        let t3 = LogicalPlanBuilder::from(scan_with_statistics(
+            "t3",
+            100,
+            [None, None, None],
+        )?)
+        .filter(col("t3.c").eq(lit(1u32)))?
+        .build()?;
+        let plan = three_way_join(t1, t2, t3)?;
+
+        let expected = vec![
+            "Projection: t1.a, t1.b, t1.c, t2.a, t2.b, t2.c, t3.a, t3.b, t3.c",
+            "  Inner Join: t2.a = t1.a",
+            "    Inner Join: t3.b = t2.b",
+            "      Filter: t3.c = UInt32(1)",
+            "        TableScan: t3",
+            "      TableScan: t2",
+            "    TableScan: t1",
+        ];
+
+        assert_optimized_plan_eq(&plan, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn keep_cheapest_order() -> Result<()> {
+        let t1 = scan_with_statistics("t1", 10, [Some(10), None, None])?;
+        let t2 = scan_with_statistics("t2", 10_000, [Some(10), Some(100), None])?;
+        let t3 = scan_with_statistics("t3", 10_000, [None, Some(100), None])?;
+        let plan = three_way_join(t1, t2, t3)?;
+
+        assert!(optimize(&plan).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn keep_order_without_statistics() -> Result<()> {
+        let t1 = scan_with_statistics("t1", 10_000, [Some(100), None, None])?;
+        let t2 = scan_with_statistics("t2", 10_000, [Some(100), Some(10_000), None])?;
+        let t3 = test_table_scan_with_name("t3")?;
+        let plan = three_way_join(t1, t2, t3)?;
+
+        assert!(optimize(&plan).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn keep_two_way_join() -> Result<()> {
+        let t1 = scan_with_statistics("t1", 10_000, [Some(100), None, None])?;
+        let t2 = scan_with_statistics("t2", 10, [Some(10), None, None])?;
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(t2, JoinType::Inner, (vec!["t1.a"], vec!["t2.a"]), None)?
+            .build()?;
+
+        assert!(optimize(&plan).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn greedy_order_of_large_join_graph() -> Result<()> {
+        // A chain t0 - t1 - ... - t11, whose last relation is the smallest
+        let mut builder = LogicalPlanBuilder::from(scan_with_statistics(
+            "t0",
+            1_000,
+            [None, None, None],
+        )?);
+        for i in 1..12 {
+            let num_rows = if i == 11 { 1 } else { 1_000 };
+            let table = scan_with_statistics(&format!("t{i}"), num_rows, [None; 3])?;
+            builder = builder.join(
+                table,
+                JoinType::Inner,
+                (vec![format!("t{}.a", i - 1)], vec![format!("t{i}.a")]),
+                None,
+            )?;
+        }
+        let plan = builder.build()?;
+
+        let optimized_plan = optimize(&plan).expect("failed to optimize plan");
+        assert_eq!(plan.schema(), optimized_plan.schema());
+
+        // The smallest relation is joined first
+        let formatted = optimized_plan.display_indent().to_string();
+        let scans: Vec<&str> = formatted
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("TableScan"))
+            .collect();
+        assert_eq!(scans[..2], ["TableScan: t11", "TableScan: t10"]);
+
+        Ok(())
+    }
+}
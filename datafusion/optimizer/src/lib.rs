@@ -27,6 +27,7 @@ pub mod eliminate_outer_join;
 pub mod eliminate_project;
 pub mod extract_equijoin_predicate;
 pub mod filter_null_join_keys;
+pub mod join_reorder;
 pub mod merge_projection;
 pub mod optimizer;
 pub mod propagate_empty_relation;
@@ -187,11 +187,14 @@ impl OptimizerRule for PushDownLimit {
                 if new_fetch == sort.fetch {
                     None
                 } else {
-                    let new_sort = LogicalPlan::Sort(Sort {
-                        expr: sort.expr.clone(),
-                        input: Arc::new((*sort.input).clone()),
-                        fetch: new_fetch,
-                    });
+                    let new_sort = LogicalPlan::Sort(
+                        Sort::new(
+                            sort.expr.clone(),
+                            Arc::new((*sort.input).clone()),
+                            new_fetch,
+                        )
+                        .with_preserve_partitioning(sort.preserve_partitioning),
+                    );
                     Some(plan.with_new_inputs(&[new_sort])?)
                 }
             }
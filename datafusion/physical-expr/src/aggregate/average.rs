@@ -21,6 +21,7 @@ use std::any::Any;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
+use crate::aggregate::groups_accumulator::AvgGroupsAccumulator;
 use crate::aggregate::row_accumulator::{
     is_row_accumulator_support_dtype, RowAccumulator,
 };
@@ -39,7 +40,7 @@ use arrow::{
 use arrow_array::Array;
 use datafusion_common::{downcast_value, ScalarValue};
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::Accumulator;
+use datafusion_expr::{Accumulator, GroupsAccumulator};
 use datafusion_row::accessor::RowAccessor;
 
 /// AVG aggregate expression
@@ -149,6 +150,14 @@ impl AggregateExpr for Avg {
         )))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        self.sum_data_type == DataType::Float64 && self.rt_data_type == DataType::Float64
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(Box::<AvgGroupsAccumulator>::default())
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -22,6 +22,7 @@ use std::fmt::Debug;
 use std::ops::BitAnd;
 use std::sync::Arc;
 
+use crate::aggregate::groups_accumulator::CountGroupsAccumulator;
 use crate::aggregate::row_accumulator::RowAccumulator;
 use crate::aggregate::utils::down_cast_any_ref;
 use crate::{AggregateExpr, PhysicalExpr};
@@ -32,7 +33,7 @@ use arrow::{array::ArrayRef, datatypes::Field};
 use arrow_buffer::BooleanBuffer;
 use datafusion_common::{downcast_value, ScalarValue};
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::Accumulator;
+use datafusion_expr::{Accumulator, GroupsAccumulator};
 use datafusion_row::accessor::RowAccessor;
 
 use crate::expressions::format_state_name;
@@ -144,6 +145,14 @@ impl AggregateExpr for Count {
         Ok(Box::new(CountRowAccumulator::new(start_index)))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        true
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(Box::<CountGroupsAccumulator>::default())
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -0,0 +1,652 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Vectorized [`GroupsAccumulator`] implementations of built-in aggregates
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use arrow::array::{
+    as_primitive_array, Array, ArrayRef, BooleanArray, Float64Array, Int64Array,
+    PrimitiveArray, UInt64Array,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{
+    ArrowNativeTypeOp, ArrowPrimitiveType, DataType, Decimal128Type, Float32Type,
+    Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use datafusion_common::{downcast_value, DataFusionError, Result};
+use datafusion_expr::GroupsAccumulator;
+
+/// Invokes `f(group_index, row)` for every row that is valid in all `values`
+/// and selected by `opt_filter`
+fn accumulate_rows<F>(
+    values: &[ArrayRef],
+    group_indices: &[usize],
+    opt_filter: Option<&BooleanArray>,
+    mut f: F,
+) where
+    F: FnMut(usize, usize),
+{
+    for (row, group_index) in group_indices.iter().enumerate() {
+        let selected = opt_filter
+            .map(|filter| filter.is_valid(row) && filter.value(row))
+            .unwrap_or(true);
+        if selected && values.iter().all(|array| array.is_valid(row)) {
+            f(*group_index, row)
+        }
+    }
+}
+
+/// Casts `values` to `data_type` if needed, e.g. for dictionary encoded input
+fn cast_if_needed(values: &ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    if values.data_type() == data_type {
+        Ok(values.clone())
+    } else {
+        Ok(cast(values, data_type)?)
+    }
+}
+
+/// Returns if `data_type` is supported by [`PrimitiveGroupsAccumulator`]
+pub(crate) fn is_primitive_groups_accumulator_support_dtype(
+    data_type: &DataType,
+) -> bool {
+    matches!(
+        data_type,
+        DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal128(_, _)
+    )
+}
+
+/// A [`GroupsAccumulator`] whose state is a single primitive value per group,
+/// combining the values of a group with `prim_fn` (e.g. `SUM`, `MIN`, `MAX`)
+pub(crate) struct PrimitiveGroupsAccumulator<T, F>
+where
+    T: ArrowPrimitiveType,
+    F: Fn(&mut T::Native, T::Native) + Send + Sync,
+{
+    /// The value of each group, only meaningful if its count is not zero
+    values: Vec<T::Native>,
+    /// The number of values aggregated into each group
+    counts: Vec<u64>,
+    /// The type of the values, e.g. including the precision of decimals
+    data_type: DataType,
+    /// If the counts are part of the intermediate state, after the values
+    state_with_counts: bool,
+    prim_fn: F,
+}
+
+impl<T, F> PrimitiveGroupsAccumulator<T, F>
+where
+    T: ArrowPrimitiveType,
+    F: Fn(&mut T::Native, T::Native) + Send + Sync,
+{
+    pub fn new(data_type: &DataType, state_with_counts: bool, prim_fn: F) -> Self {
+        Self {
+            values: vec![],
+            counts: vec![],
+            data_type: data_type.clone(),
+            state_with_counts,
+            prim_fn,
+        }
+    }
+
+    /// Combines `values` into the groups, adding `counts` (or one per value
+    /// if `None` or `NULL`) to the number of values of each group
+    fn accumulate(
+        &mut self,
+        values: &ArrayRef,
+        counts: Option<&UInt64Array>,
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.values.resize(total_num_groups, T::Native::default());
+        self.counts.resize(total_num_groups, 0);
+
+        let values = cast_if_needed(values, &self.data_type)?;
+        let array = as_primitive_array::<T>(&values);
+        let Self {
+            values: group_values,
+            counts: group_counts,
+            prim_fn,
+            ..
+        } = self;
+        accumulate_rows(
+            std::slice::from_ref(&values),
+            group_indices,
+            opt_filter,
+            |group_index, row| {
+                let value = array.value(row);
+                if group_counts[group_index] == 0 {
+                    group_values[group_index] = value;
+                } else {
+                    prim_fn(&mut group_values[group_index], value);
+                }
+                group_counts[group_index] += match counts {
+                    Some(counts) if counts.is_valid(row) => counts.value(row),
+                    _ => 1,
+                };
+            },
+        );
+        Ok(())
+    }
+
+    /// Takes the values of all groups out of the state, `NULL` for the groups
+    /// without any value
+    fn take_values(&mut self) -> (PrimitiveArray<T>, Vec<u64>) {
+        let values = std::mem::take(&mut self.values);
+        let counts = std::mem::take(&mut self.counts);
+        let array = values
+            .into_iter()
+            .zip(counts.iter())
+            .map(|(value, count)| (*count > 0).then_some(value))
+            .collect::<PrimitiveArray<T>>()
+            .with_data_type(self.data_type.clone());
+        (array, counts)
+    }
+}
+
+impl<T, F> Debug for PrimitiveGroupsAccumulator<T, F>
+where
+    T: ArrowPrimitiveType,
+    F: Fn(&mut T::Native, T::Native) + Send + Sync,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("PrimitiveGroupsAccumulator")
+            .field("data_type", &self.data_type)
+            .field("num_groups", &self.values.len())
+            .finish()
+    }
+}
+
+impl<T, F> GroupsAccumulator for PrimitiveGroupsAccumulator<T, F>
+where
+    T: ArrowPrimitiveType,
+    F: Fn(&mut T::Native, T::Native) + Send + Sync,
+{
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.accumulate(
+            &values[0],
+            None,
+            group_indices,
+            opt_filter,
+            total_num_groups,
+        )
+    }
+
+    fn merge_batch(
+        &mut self,
+        states: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let counts = if self.state_with_counts {
+            Some(downcast_value!(states[1], UInt64Array))
+        } else {
+            None
+        };
+        self.accumulate(
+            &states[0],
+            counts,
+            group_indices,
+            opt_filter,
+            total_num_groups,
+        )
+    }
+
+    fn evaluate(&mut self) -> Result<ArrayRef> {
+        let (values, _) = self.take_values();
+        Ok(Arc::new(values))
+    }
+
+    fn state(&mut self) -> Result<Vec<ArrayRef>> {
+        let (values, counts) = self.take_values();
+        let mut state: Vec<ArrayRef> = vec![Arc::new(values)];
+        if self.state_with_counts {
+            state.push(Arc::new(UInt64Array::from(counts)));
+        }
+        Ok(state)
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.values.capacity() * std::mem::size_of::<T::Native>()
+            + self.counts.capacity() * std::mem::size_of::<u64>()
+    }
+}
+
+/// Creates a [`PrimitiveGroupsAccumulator`] of the primitive type matching `$DATA_TYPE`
+macro_rules! primitive_groups_accumulator {
+    ($DATA_TYPE:expr, $STATE_WITH_COUNTS:expr, $FN:expr) => {{
+        let data_type: &DataType = $DATA_TYPE;
+        let accumulator: Box<dyn GroupsAccumulator> = match data_type {
+            DataType::Int8 => Box::new(PrimitiveGroupsAccumulator::<Int8Type, _>::new(
+                data_type,
+                $STATE_WITH_COUNTS,
+                $FN,
+            )),
+            DataType::Int16 => Box::new(PrimitiveGroupsAccumulator::<Int16Type, _>::new(
+                data_type,
+                $STATE_WITH_COUNTS,
+                $FN,
+            )),
+            DataType::Int32 => Box::new(PrimitiveGroupsAccumulator::<Int32Type, _>::new(
+                data_type,
+                $STATE_WITH_COUNTS,
+                $FN,
+            )),
+            DataType::Int64 => Box::new(PrimitiveGroupsAccumulator::<Int64Type, _>::new(
+                data_type,
+                $STATE_WITH_COUNTS,
+                $FN,
+            )),
+            DataType::UInt8 => Box::new(PrimitiveGroupsAccumulator::<UInt8Type, _>::new(
+                data_type,
+                $STATE_WITH_COUNTS,
+                $FN,
+            )),
+            DataType::UInt16 => {
+                Box::new(PrimitiveGroupsAccumulator::<UInt16Type, _>::new(
+                    data_type,
+                    $STATE_WITH_COUNTS,
+                    $FN,
+                ))
+            }
+            DataType::UInt32 => {
+                Box::new(PrimitiveGroupsAccumulator::<UInt32Type, _>::new(
+                    data_type,
+                    $STATE_WITH_COUNTS,
+                    $FN,
+                ))
+            }
+            DataType::UInt64 => {
+                Box::new(PrimitiveGroupsAccumulator::<UInt64Type, _>::new(
+                    data_type,
+                    $STATE_WITH_COUNTS,
+                    $FN,
+                ))
+            }
+            DataType::Float32 => {
+                Box::new(PrimitiveGroupsAccumulator::<Float32Type, _>::new(
+                    data_type,
+                    $STATE_WITH_COUNTS,
+                    $FN,
+                ))
+            }
+            DataType::Float64 => {
+                Box::new(PrimitiveGroupsAccumulator::<Float64Type, _>::new(
+                    data_type,
+                    $STATE_WITH_COUNTS,
+                    $FN,
+                ))
+            }
+            DataType::Decimal128(_, _) => Box::new(PrimitiveGroupsAccumulator::<
+                Decimal128Type,
+                _,
+            >::new(
+                data_type, $STATE_WITH_COUNTS, $FN
+            )),
+            other => {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "GroupsAccumulator is not supported for type {other:?}"
+                )))
+            }
+        };
+        Ok(accumulator)
+    }};
+}
+
+/// Creates a [`GroupsAccumulator`] for `SUM`, whose state is the sum and
+/// the number of values of each group
+pub(crate) fn sum_groups_accumulator(
+    data_type: &DataType,
+) -> Result<Box<dyn GroupsAccumulator>> {
+    primitive_groups_accumulator!(data_type, true, |sum, value| {
+        *sum = sum.add_wrapping(value)
+    })
+}
+
+/// Creates a [`GroupsAccumulator`] for `MIN`
+pub(crate) fn min_groups_accumulator(
+    data_type: &DataType,
+) -> Result<Box<dyn GroupsAccumulator>> {
+    primitive_groups_accumulator!(data_type, false, |min, value| {
+        if value.compare(*min).is_lt() {
+            *min = value
+        }
+    })
+}
+
+/// Creates a [`GroupsAccumulator`] for `MAX`
+pub(crate) fn max_groups_accumulator(
+    data_type: &DataType,
+) -> Result<Box<dyn GroupsAccumulator>> {
+    primitive_groups_accumulator!(data_type, false, |max, value| {
+        if value.compare(*max).is_gt() {
+            *max = value
+        }
+    })
+}
+
+/// A [`GroupsAccumulator`] for `COUNT`, counting the rows of each group in
+/// which all arguments are non-null
+#[derive(Debug, Default)]
+pub(crate) struct CountGroupsAccumulator {
+    /// The count of each group
+    counts: Vec<i64>,
+}
+
+impl GroupsAccumulator for CountGroupsAccumulator {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.counts.resize(total_num_groups, 0);
+        let counts = &mut self.counts;
+        accumulate_rows(values, group_indices, opt_filter, |group_index, _| {
+            counts[group_index] += 1;
+        });
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        states: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.counts.resize(total_num_groups, 0);
+        let partial_counts = downcast_value!(states[0], Int64Array);
+        let counts = &mut self.counts;
+        accumulate_rows(states, group_indices, opt_filter, |group_index, row| {
+            counts[group_index] += partial_counts.value(row);
+        });
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ArrayRef> {
+        let counts = std::mem::take(&mut self.counts);
+        Ok(Arc::new(Int64Array::from(counts)))
+    }
+
+    fn state(&mut self) -> Result<Vec<ArrayRef>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.counts.capacity() * std::mem::size_of::<i64>()
+    }
+}
+
+/// A [`GroupsAccumulator`] for `AVG` of `Float64` values, whose state is the
+/// number of values and the sum of each group
+#[derive(Debug, Default)]
+pub(crate) struct AvgGroupsAccumulator {
+    /// The number of values of each group
+    counts: Vec<u64>,
+    /// The sum of each group
+    sums: Vec<f64>,
+}
+
+impl AvgGroupsAccumulator {
+    /// Takes the counts and sums of all groups out of the state
+    fn take_state(&mut self) -> (Vec<u64>, Vec<f64>) {
+        (
+            std::mem::take(&mut self.counts),
+            std::mem::take(&mut self.sums),
+        )
+    }
+}
+
+impl GroupsAccumulator for AvgGroupsAccumulator {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.counts.resize(total_num_groups, 0);
+        self.sums.resize(total_num_groups, 0.0);
+        let values = cast_if_needed(&values[0], &DataType::Float64)?;
+        let array = as_primitive_array::<Float64Type>(&values);
+        let Self { counts, sums } = self;
+        accumulate_rows(
+            std::slice::from_ref(&values),
+            group_indices,
+            opt_filter,
+            |group_index, row| {
+                counts[group_index] += 1;
+                sums[group_index] += array.value(row);
+            },
+        );
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        states: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.counts.resize(total_num_groups, 0);
+        self.sums.resize(total_num_groups, 0.0);
+        let partial_counts = downcast_value!(states[0], UInt64Array);
+        let partial_sums = cast_if_needed(&states[1], &DataType::Float64)?;
+        let partial_sums_array = as_primitive_array::<Float64Type>(&partial_sums);
+        let Self { counts, sums } = self;
+        accumulate_rows(
+            std::slice::from_ref(&partial_sums),
+            group_indices,
+            opt_filter,
+            |group_index, row| {
+                counts[group_index] += partial_counts.value(row);
+                sums[group_index] += partial_sums_array.value(row);
+            },
+        );
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ArrayRef> {
+        let (counts, sums) = self.take_state();
+        let averages = counts
+            .into_iter()
+            .zip(sums)
+            .map(|(count, sum)| (count > 0).then(|| sum / count as f64))
+            .collect::<Float64Array>();
+        Ok(Arc::new(averages))
+    }
+
+    fn state(&mut self) -> Result<Vec<ArrayRef>> {
+        let (counts, sums) = self.take_state();
+        let sums = counts
+            .iter()
+            .zip(sums)
+            .map(|(count, sum)| (*count > 0).then_some(sum))
+            .collect::<Float64Array>();
+        Ok(vec![Arc::new(UInt64Array::from(counts)), Arc::new(sums)])
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.counts.capacity() * std::mem::size_of::<u64>()
+            + self.sums.capacity() * std::mem::size_of::<f64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Decimal128Array, Int32Array};
+
+    #[test]
+    fn sum_groups_accumulator_update_and_merge() -> Result<()> {
+        let mut partial = sum_groups_accumulator(&DataType::Int64)?;
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(2),
+            None,
+            Some(4),
+            Some(5),
+        ]));
+        partial.update_batch(&[values], &[0, 1, 2, 0, 1], None, 3)?;
+
+        let state = partial.state()?;
+        assert_eq!(
+            as_primitive_array::<Int64Type>(&state[0]),
+            &Int64Array::from(vec![Some(5), Some(7), None])
+        );
+        assert_eq!(
+            as_primitive_array::<UInt64Type>(&state[1]),
+            &UInt64Array::from(vec![2, 2, 0])
+        );
+        // the state is reset to no groups
+        assert_eq!(partial.state()?[0].len(), 0);
+
+        let mut last = sum_groups_accumulator(&DataType::Int64)?;
+        last.merge_batch(&state, &[1, 0, 2], None, 3)?;
+        last.merge_batch(&state, &[1, 1, 1], None, 3)?;
+        let result = last.evaluate()?;
+        assert_eq!(
+            as_primitive_array::<Int64Type>(&result),
+            &Int64Array::from(vec![Some(7), Some(17), None])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sum_groups_accumulator_decimal() -> Result<()> {
+        let data_type = DataType::Decimal128(20, 2);
+        let mut accumulator = sum_groups_accumulator(&data_type)?;
+        let values: ArrayRef = Arc::new(
+            Decimal128Array::from(vec![100, 250, 300]).with_precision_and_scale(10, 2)?,
+        );
+        accumulator.update_batch(&[values], &[0, 0, 1], None, 2)?;
+
+        let result = accumulator.evaluate()?;
+        assert_eq!(result.data_type(), &data_type);
+        assert_eq!(
+            as_primitive_array::<Decimal128Type>(&result),
+            &Decimal128Array::from(vec![350, 300]).with_data_type(data_type)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn min_max_groups_accumulator_with_filter() -> Result<()> {
+        let values: ArrayRef =
+            Arc::new(Float64Array::from(vec![3.0, 1.0, 2.0, 5.0, 4.0, 0.0]));
+        let group_indices = [0, 0, 1, 1, 0, 2];
+        let filter = BooleanArray::from(vec![
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(false),
+            Some(true),
+            None,
+        ]);
+
+        let mut min = min_groups_accumulator(&DataType::Float64)?;
+        min.update_batch(&[values.clone()], &group_indices, Some(&filter), 3)?;
+        assert_eq!(
+            as_primitive_array::<Float64Type>(&min.evaluate()?),
+            &Float64Array::from(vec![Some(1.0), Some(2.0), None])
+        );
+
+        let mut max = max_groups_accumulator(&DataType::Float64)?;
+        max.update_batch(&[values], &group_indices, Some(&filter), 3)?;
+        assert_eq!(
+            as_primitive_array::<Float64Type>(&max.evaluate()?),
+            &Float64Array::from(vec![Some(4.0), Some(2.0), None])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn count_groups_accumulator() -> Result<()> {
+        let values: ArrayRef =
+            Arc::new(Int32Array::from(vec![Some(1), None, Some(3), Some(4)]));
+        let mut partial = CountGroupsAccumulator::default();
+        partial.update_batch(&[values], &[0, 0, 1, 1], None, 3)?;
+        let state = partial.state()?;
+        assert_eq!(
+            as_primitive_array::<Int64Type>(&state[0]),
+            &Int64Array::from(vec![1, 2, 0])
+        );
+
+        let mut last = CountGroupsAccumulator::default();
+        last.merge_batch(&state, &[0, 1, 2], None, 3)?;
+        last.merge_batch(&state, &[2, 2, 2], None, 3)?;
+        assert_eq!(
+            as_primitive_array::<Int64Type>(&last.evaluate()?),
+            &Int64Array::from(vec![1, 2, 3])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn avg_groups_accumulator() -> Result<()> {
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.0),
+            Some(2.0),
+            None,
+            Some(4.0),
+        ]));
+        let mut partial = AvgGroupsAccumulator::default();
+        partial.update_batch(&[values], &[0, 0, 1, 2], None, 4)?;
+        let state = partial.state()?;
+        assert_eq!(
+            as_primitive_array::<UInt64Type>(&state[0]),
+            &UInt64Array::from(vec![2, 0, 1, 0])
+        );
+        assert_eq!(
+            as_primitive_array::<Float64Type>(&state[1]),
+            &Float64Array::from(vec![Some(3.0), None, Some(4.0), None])
+        );
+
+        let mut last = AvgGroupsAccumulator::default();
+        last.merge_batch(&state, &[0, 1, 2, 3], None, 4)?;
+        assert_eq!(
+            as_primitive_array::<Float64Type>(&last.evaluate()?),
+            &Float64Array::from(vec![Some(1.5), None, Some(4.0), None])
+        );
+        Ok(())
+    }
+}
@@ -26,8 +26,8 @@ use arrow::compute;
 use arrow::datatypes::{DataType, TimeUnit};
 use arrow::{
     array::{
-        ArrayRef, Date32Array, Date64Array, Float32Array, Float64Array, Int16Array,
-        Int32Array, Int64Array, Int8Array, LargeStringArray, StringArray,
+        ArrayRef, BinaryArray, Date32Array, Date64Array, Float32Array, Float64Array,
+        Int16Array, Int32Array, Int64Array, Int8Array, LargeStringArray, StringArray,
         Time32MillisecondArray, Time32SecondArray, Time64MicrosecondArray,
         Time64NanosecondArray, TimestampMicrosecondArray, TimestampMillisecondArray,
         TimestampNanosecondArray, TimestampSecondArray, UInt16Array, UInt32Array,
@@ -37,7 +37,12 @@ use arrow::{
 };
 use datafusion_common::ScalarValue;
 use datafusion_common::{downcast_value, DataFusionError, Result};
-use datafusion_expr::Accumulator;
+use datafusion_expr::{Accumulator, GroupsAccumulator};
+
+use crate::aggregate::groups_accumulator::{
+    is_primitive_groups_accumulator_support_dtype, max_groups_accumulator,
+    min_groups_accumulator,
+};
 
 use crate::aggregate::row_accumulator::{
     is_row_accumulator_support_dtype, RowAccumulator,
@@ -50,6 +55,16 @@ use datafusion_row::accessor::RowAccessor;
 
 use super::moving_min_max;
 
+/// Besides the fixed-width types, min/max row accumulators can keep
+/// variable-length values in the row's variable-length region
+fn is_min_max_row_accumulator_support_dtype(data_type: &DataType) -> bool {
+    is_row_accumulator_support_dtype(data_type)
+        || matches!(
+            data_type,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary
+        )
+}
+
 // Min/max aggregation can take Dictionary encode input but always produces unpacked
 // (aka non Dictionary) output. We need to adjust the output data type to reflect this.
 // The reason min/max aggregate produces unpacked output because there is only one
@@ -122,7 +137,7 @@ impl AggregateExpr for Max {
     }
 
     fn row_accumulator_supported(&self) -> bool {
-        is_row_accumulator_support_dtype(&self.data_type)
+        is_min_max_row_accumulator_support_dtype(&self.data_type)
     }
 
     fn supports_bounded_execution(&self) -> bool {
@@ -139,6 +154,14 @@ impl AggregateExpr for Max {
         )))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        is_primitive_groups_accumulator_support_dtype(&self.data_type)
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        max_groups_accumulator(&self.data_type)
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -172,6 +195,16 @@ macro_rules! typed_min_max_batch_string {
     }};
 }
 
+// Statically-typed version of min/max(array) -> ScalarValue for binary types.
+macro_rules! typed_min_max_batch_binary {
+    ($VALUES:expr, $ARRAYTYPE:ident, $SCALAR:ident, $OP:ident) => {{
+        let array = downcast_value!($VALUES, $ARRAYTYPE);
+        let value = compute::$OP(array);
+        let value = value.map(|e| e.to_vec());
+        ScalarValue::$SCALAR(value)
+    }};
+}
+
 // Statically-typed version of min/max(array) -> ScalarValue for non-string types.
 macro_rules! typed_min_max_batch {
     ($VALUES:expr, $ARRAYTYPE:ident, $SCALAR:ident, $OP:ident $(, $EXTRA_ARGS:ident)*) => {{
@@ -290,6 +323,9 @@ fn min_batch(values: &ArrayRef) -> Result<ScalarValue> {
         DataType::LargeUtf8 => {
             typed_min_max_batch_string!(values, LargeStringArray, LargeUtf8, min_string)
         }
+        DataType::Binary => {
+            typed_min_max_batch_binary!(values, BinaryArray, Binary, min_binary)
+        }
         _ => min_max_batch!(values, min),
     })
 }
@@ -303,6 +339,9 @@ fn max_batch(values: &ArrayRef) -> Result<ScalarValue> {
         DataType::LargeUtf8 => {
             typed_min_max_batch_string!(values, LargeStringArray, LargeUtf8, max_string)
         }
+        DataType::Binary => {
+            typed_min_max_batch_binary!(values, BinaryArray, Binary, max_binary)
+        }
         _ => min_max_batch!(values, max),
     })
 }
@@ -334,6 +373,18 @@ macro_rules! typed_min_max_v2 {
     }};
 }
 
+// min/max of a variable-length scalar value and the value stored in the row.
+macro_rules! typed_min_max_var_length_v2 {
+    ($INDEX:ident, $ACC:ident, $SCALAR:expr, $TYPE:ident, $OP:ident) => {{
+        paste::item! {
+            match $SCALAR {
+                None => {}
+                Some(v) => $ACC.[<$OP _ $TYPE>]($INDEX, v)
+            }
+        }
+    }};
+}
+
 // min/max of two scalar string values.
 macro_rules! typed_min_max_string {
     ($VALUE:expr, $DELTA:expr, $SCALAR:ident, $OP:ident) => {{
@@ -422,6 +473,9 @@ macro_rules! min_max {
             (ScalarValue::LargeUtf8(lhs), ScalarValue::LargeUtf8(rhs)) => {
                 typed_min_max_string!(lhs, rhs, LargeUtf8, $OP)
             }
+            (ScalarValue::Binary(lhs), ScalarValue::Binary(rhs)) => {
+                typed_min_max_string!(lhs, rhs, Binary, $OP)
+            }
             (ScalarValue::TimestampSecond(lhs, l_tz), ScalarValue::TimestampSecond(rhs, _)) => {
                 typed_min_max!(lhs, rhs, TimestampSecond, $OP, l_tz)
             }
@@ -565,6 +619,12 @@ macro_rules! min_max_v2 {
             ScalarValue::Decimal128(rhs, ..) => {
                 typed_min_max_v2!($INDEX, $ACC, rhs, i128, $OP)
             }
+            ScalarValue::Utf8(rhs) | ScalarValue::LargeUtf8(rhs) => {
+                typed_min_max_var_length_v2!($INDEX, $ACC, rhs, utf8, $OP)
+            }
+            ScalarValue::Binary(rhs) => {
+                typed_min_max_var_length_v2!($INDEX, $ACC, rhs, binary, $OP)
+            }
             ScalarValue::Null => {
                 // do nothing
             }
@@ -810,7 +870,7 @@ impl AggregateExpr for Min {
     }
 
     fn row_accumulator_supported(&self) -> bool {
-        is_row_accumulator_support_dtype(&self.data_type)
+        is_min_max_row_accumulator_support_dtype(&self.data_type)
     }
 
     fn supports_bounded_execution(&self) -> bool {
@@ -827,6 +887,14 @@ impl AggregateExpr for Min {
         )))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        is_primitive_groups_accumulator_support_dtype(&self.data_type)
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        min_groups_accumulator(&self.data_type)
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -1429,4 +1497,79 @@ mod tests {
             ScalarValue::Time64Nanosecond(Some(5))
         )
     }
+
+    #[test]
+    fn max_binary() -> Result<()> {
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec![
+            b"d".as_ref(),
+            b"a".as_ref(),
+            b"dd".as_ref(),
+            b"b".as_ref(),
+        ]));
+        generic_test_op!(
+            a,
+            DataType::Binary,
+            Max,
+            ScalarValue::Binary(Some(b"dd".to_vec()))
+        )
+    }
+
+    fn row_aggregate(
+        agg: Arc<dyn AggregateExpr>,
+        batches: Vec<ArrayRef>,
+    ) -> Result<ScalarValue> {
+        let row_schema = Schema::new(agg.state_fields()?);
+        let mut accessor = RowAccessor::new(&row_schema);
+        let mut buffer: Vec<u8> = vec![0; 16];
+        accessor.point_to(0, &mut buffer);
+
+        let mut accum = agg.create_row_accumulator(0)?;
+        for batch in batches {
+            accum.update_batch(&[batch], &mut accessor)?;
+        }
+        accum.evaluate(&accessor)
+    }
+
+    #[test]
+    fn max_utf8_row_accumulator() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Utf8, true)]);
+        let agg = Arc::new(Max::new(col("a", &schema)?, "bla", DataType::Utf8));
+        assert!(agg.row_accumulator_supported());
+
+        let batches: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec![
+                Some("b"),
+                None,
+                Some("a long value"),
+            ])),
+            // longer than the stored value, appended to the row
+            Arc::new(StringArray::from(vec!["bbbbbbbbbb longer value"])),
+            // shorter than the stored value, overwritten in place
+            Arc::new(StringArray::from(vec!["a", "c"])),
+        ];
+        let actual = row_aggregate(agg, batches)?;
+        assert_eq!(actual, ScalarValue::Utf8(Some("c".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn min_binary_row_accumulator() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Binary, true)]);
+        let agg = Arc::new(Min::new(col("a", &schema)?, "bla", DataType::Binary));
+        assert!(agg.row_accumulator_supported());
+
+        let batches: Vec<ArrayRef> = vec![
+            Arc::new(BinaryArray::from(vec![
+                b"\x05\x06".as_ref(),
+                b"\x07".as_ref(),
+            ])),
+            Arc::new(BinaryArray::from(vec![
+                b"\x01\x02\x03\x04\x05\x06\x07\x08\x09".as_ref(),
+            ])),
+            Arc::new(BinaryArray::from(vec![Some(b"".as_ref()), None])),
+        ];
+        let actual = row_aggregate(agg, batches)?;
+        assert_eq!(actual, ScalarValue::Binary(Some(vec![])));
+        Ok(())
+    }
 }
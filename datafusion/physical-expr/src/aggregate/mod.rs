@@ -19,7 +19,7 @@ use crate::aggregate::row_accumulator::RowAccumulator;
 use crate::PhysicalExpr;
 use arrow::datatypes::Field;
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::Accumulator;
+use datafusion_expr::{Accumulator, GroupsAccumulator};
 use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -36,6 +36,7 @@ pub(crate) mod count;
 pub(crate) mod count_distinct;
 pub(crate) mod covariance;
 pub(crate) mod grouping;
+pub(crate) mod groups_accumulator;
 pub(crate) mod median;
 #[macro_use]
 pub(crate) mod min_max;
@@ -99,7 +100,8 @@ pub trait AggregateExpr: Send + Sync + Debug + PartialEq<dyn Any> {
     }
 
     /// RowAccumulator to access/update row-based aggregation state in-place.
-    /// Currently, row accumulator only supports states of fixed-sized type.
+    /// Currently, row accumulator only supports states of fixed-sized type, as well as
+    /// `Utf8`, `LargeUtf8` and `Binary` states stored in the row's variable-length region.
     ///
     /// We recommend implementing `RowAccumulator` along with the standard `Accumulator`,
     /// when its state is of fixed size, as RowAccumulator is more memory efficient and CPU-friendly.
@@ -112,6 +114,22 @@ pub trait AggregateExpr: Send + Sync + Debug + PartialEq<dyn Any> {
         )))
     }
 
+    /// If the aggregate expression supports accumulating all groups of a
+    /// grouped aggregation at once through a [`GroupsAccumulator`]
+    fn groups_accumulator_supported(&self) -> bool {
+        false
+    }
+
+    /// GroupsAccumulator to update the state of all groups with a single call
+    /// per input batch, instead of one `Accumulator` per group.
+    ///
+    /// Its state must have the same description as `state_fields`.
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Err(DataFusionError::NotImplemented(format!(
+            "GroupsAccumulator hasn't been implemented for {self:?} yet"
+        )))
+    }
+
     /// Construct an expression that calculates the aggregate in reverse.
     /// Typically the "reverse" expression is itself (e.g. SUM, COUNT).
     /// For aggregates that do not support calculation in reverse,
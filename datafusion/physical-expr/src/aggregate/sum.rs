@@ -32,7 +32,11 @@ use arrow::{
     datatypes::Field,
 };
 use datafusion_common::{downcast_value, DataFusionError, Result, ScalarValue};
-use datafusion_expr::Accumulator;
+use datafusion_expr::{Accumulator, GroupsAccumulator};
+
+use crate::aggregate::groups_accumulator::{
+    is_primitive_groups_accumulator_support_dtype, sum_groups_accumulator,
+};
 
 use crate::aggregate::row_accumulator::{
     is_row_accumulator_support_dtype, RowAccumulator,
@@ -145,6 +149,14 @@ impl AggregateExpr for Sum {
         )))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        is_primitive_groups_accumulator_support_dtype(&self.data_type)
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        sum_groups_accumulator(&self.data_type)
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -386,7 +398,11 @@ impl RowAccumulator for SumRowAccumulator {
     ) -> Result<()> {
         let values = &values[0];
         let delta = sum_batch(values, &self.datatype)?;
-        add_to_row(self.index, accessor, &delta)
+        add_to_row(self.index, accessor, &delta)?;
+        // count
+        let delta = (values.len() - values.null_count()) as u64;
+        accessor.add_u64(self.index + 1, delta);
+        Ok(())
     }
 
     fn update_scalar_values(
@@ -394,8 +410,7 @@ impl RowAccumulator for SumRowAccumulator {
         values: &[ScalarValue],
         accessor: &mut RowAccessor,
     ) -> Result<()> {
-        let value = &values[0];
-        add_to_row(self.index, accessor, value)
+        self.update_scalar(&values[0], accessor)
     }
 
     fn update_scalar(
@@ -403,7 +418,10 @@ impl RowAccumulator for SumRowAccumulator {
         value: &ScalarValue,
         accessor: &mut RowAccessor,
     ) -> Result<()> {
-        add_to_row(self.index, accessor, value)
+        add_to_row(self.index, accessor, value)?;
+        // count
+        accessor.add_u64(self.index + 1, u64::from(!value.is_null()));
+        Ok(())
     }
 
     fn merge_batch(
@@ -411,7 +429,13 @@ impl RowAccumulator for SumRowAccumulator {
         states: &[ArrayRef],
         accessor: &mut RowAccessor,
     ) -> Result<()> {
-        self.update_batch(states, accessor)
+        let difference = sum_batch(&states[0], &self.datatype)?;
+        add_to_row(self.index, accessor, &difference)?;
+        // count
+        let counts = downcast_value!(states[1], UInt64Array);
+        let delta = compute::sum(counts).unwrap_or(0);
+        accessor.add_u64(self.index + 1, delta);
+        Ok(())
     }
 
     fn evaluate(&self, accessor: &RowAccessor) -> Result<ScalarValue> {
@@ -578,9 +602,12 @@ mod tests {
 
         let a: ArrayRef = Arc::new(DictionaryArray::try_new(keys, values).unwrap());
 
-        let row_schema = Schema::new(vec![Field::new("a", DataType::Float64, true)]);
+        let row_schema = Schema::new(vec![
+            Field::new("a", DataType::Float64, true),
+            Field::new("count", DataType::UInt64, true),
+        ]);
         let mut row_accessor = RowAccessor::new(&row_schema);
-        let mut buffer: Vec<u8> = vec![0; 16];
+        let mut buffer: Vec<u8> = vec![0; 24];
         row_accessor.point_to(0, &mut buffer);
 
         let expected = ScalarValue::from(9_f64);
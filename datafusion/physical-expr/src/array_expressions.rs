@@ -18,59 +18,100 @@
 //! Array expressions
 
 use arrow::array::*;
-use arrow::datatypes::DataType;
-use datafusion_common::{DataFusionError, Result};
+use arrow::buffer::{Buffer, NullBuffer};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use arrow::util::display::array_value_to_string;
+use datafusion_common::cast::{as_int64_array, as_list_array, as_string_array};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
 use datafusion_expr::ColumnarValue;
 use std::sync::Arc;
 
-macro_rules! downcast_vec {
-    ($ARGS:expr, $ARRAY_TYPE:ident) => {{
-        $ARGS
-            .iter()
-            .map(|e| match e.as_any().downcast_ref::<$ARRAY_TYPE>() {
-                Some(array) => Ok(array),
-                _ => Err(DataFusionError::Internal("failed to downcast".to_string())),
-            })
-    }};
-}
-
-macro_rules! new_builder {
-    (BooleanBuilder, $len:expr) => {
-        BooleanBuilder::with_capacity($len)
-    };
-    (StringBuilder, $len:expr) => {
-        StringBuilder::new()
-    };
-    (LargeStringBuilder, $len:expr) => {
-        LargeStringBuilder::new()
-    };
-    ($el:ident, $len:expr) => {{
-        <$el>::with_capacity($len)
-    }};
-}
-
-macro_rules! array {
-    ($ARGS:expr, $ARRAY_TYPE:ident, $BUILDER_TYPE:ident) => {{
-        // downcast all arguments to their common format
-        let args =
-            downcast_vec!($ARGS, $ARRAY_TYPE).collect::<Result<Vec<&$ARRAY_TYPE>>>()?;
-
-        let builder = new_builder!($BUILDER_TYPE, args[0].len());
-        let mut builder =
-            FixedSizeListBuilder::<$BUILDER_TYPE>::new(builder, args.len() as i32);
-        // for each entry in the array
-        for index in 0..args[0].len() {
-            for arg in &args {
-                if arg.is_null(index) {
-                    builder.values().append_null();
-                } else {
-                    builder.values().append_value(arg.value(index));
-                }
-            }
-            builder.append(true);
+/// Builds a `ListArray` of `num_rows` rows from slices of the `sources`,
+/// which must all be of the item type of `field`.
+///
+/// `build_row` appends the values of a row to the values of the list, and
+/// returns whether the row is valid.
+fn build_list_array(
+    field: FieldRef,
+    sources: Vec<&ArrayData>,
+    num_rows: usize,
+    mut build_row: impl FnMut(usize, &mut MutableArrayData) -> Result<bool>,
+) -> Result<ArrayRef> {
+    let mut values = MutableArrayData::new(sources, true, num_rows);
+    let mut offsets = Vec::with_capacity(num_rows + 1);
+    offsets.push(0i32);
+    let mut valid = BooleanBufferBuilder::new(num_rows);
+    for row in 0..num_rows {
+        valid.append(build_row(row, &mut values)?);
+        offsets.push(values.len() as i32);
+    }
+    let values = make_array(values.freeze());
+
+    let data = ArrayData::builder(DataType::List(field))
+        .len(num_rows)
+        .add_buffer(Buffer::from_slice_ref(&offsets))
+        .add_child_data(values.to_data())
+        .nulls(Some(NullBuffer::new(valid.finish())))
+        .build()?;
+    Ok(Arc::new(ListArray::from(data)))
+}
+
+fn list_field(list: &ListArray) -> Result<FieldRef> {
+    match list.data_type() {
+        DataType::List(field) => Ok(field.clone()),
+        data_type => Err(DataFusionError::Internal(format!(
+            "Expected a List array but found {data_type:?}"
+        ))),
+    }
+}
+
+/// Returns the range of the values of `list` that make up `row`
+fn value_range(list: &ListArray, row: usize) -> (usize, usize) {
+    let offsets = list.value_offsets();
+    (offsets[row] as usize, offsets[row + 1] as usize)
+}
+
+/// Returns the innermost values of a (possibly nested) list, along with
+/// the range of them that makes up each row of the list
+fn leaf_values(list: &ListArray) -> Result<(ArrayRef, Vec<(usize, usize)>)> {
+    let mut ranges = (0..list.len())
+        .map(|row| value_range(list, row))
+        .collect::<Vec<_>>();
+    let mut leaves = list.values().clone();
+    while let DataType::List(_) = leaves.data_type() {
+        let inner = as_list_array(&leaves)?;
+        let offsets = inner.value_offsets();
+        for (start, end) in ranges.iter_mut() {
+            *start = offsets[*start] as usize;
+            *end = offsets[*end] as usize;
         }
-        Arc::new(builder.finish())
-    }};
+        let values = inner.values().clone();
+        leaves = values;
+    }
+    Ok((leaves, ranges))
+}
+
+/// Returns the length of each dimension of `array`, the dimensions below
+/// the first being read from the first element of the dimension above
+fn dimensions(array: ArrayRef) -> Result<Vec<u64>> {
+    let mut dimensions = vec![array.len() as u64];
+    let mut current = array;
+    while let DataType::List(_) = current.data_type() {
+        let list = as_list_array(&current)?;
+        if list.is_empty() || list.is_null(0) {
+            break;
+        }
+        let first = list.value(0);
+        dimensions.push(first.len() as u64);
+        current = first;
+    }
+    Ok(dimensions)
+}
+
+/// Returns true if the value at `index` of `values` equals `element`,
+/// nulls being equal to each other
+fn value_eq(values: &dyn Array, index: usize, element: &ScalarValue) -> Result<bool> {
+    Ok(&ScalarValue::try_from_array(values, index)? == element)
 }
 
 fn array_array(args: &[ArrayRef]) -> Result<ArrayRef> {
@@ -81,37 +122,569 @@ fn array_array(args: &[ArrayRef]) -> Result<ArrayRef> {
         ));
     }
 
-    let res = match args[0].data_type() {
-        DataType::Utf8 => array!(args, StringArray, StringBuilder),
-        DataType::LargeUtf8 => array!(args, LargeStringArray, LargeStringBuilder),
-        DataType::Boolean => array!(args, BooleanArray, BooleanBuilder),
-        DataType::Float32 => array!(args, Float32Array, Float32Builder),
-        DataType::Float64 => array!(args, Float64Array, Float64Builder),
-        DataType::Int8 => array!(args, Int8Array, Int8Builder),
-        DataType::Int16 => array!(args, Int16Array, Int16Builder),
-        DataType::Int32 => array!(args, Int32Array, Int32Builder),
-        DataType::Int64 => array!(args, Int64Array, Int64Builder),
-        DataType::UInt8 => array!(args, UInt8Array, UInt8Builder),
-        DataType::UInt16 => array!(args, UInt16Array, UInt16Builder),
-        DataType::UInt32 => array!(args, UInt32Array, UInt32Builder),
-        DataType::UInt64 => array!(args, UInt64Array, UInt64Builder),
-        data_type => {
-            return Err(DataFusionError::NotImplemented(format!(
-                "Array is not implemented for type '{data_type:?}'."
-            )))
-        }
-    };
-    Ok(res)
+    let field = Arc::new(Field::new("item", args[0].data_type().clone(), true));
+    let data = args.iter().map(|arg| arg.to_data()).collect::<Vec<_>>();
+    build_list_array(
+        field,
+        data.iter().collect(),
+        args[0].len(),
+        |row, values| {
+            for arg in 0..args.len() {
+                values.extend(arg, row, row + 1);
+            }
+            Ok(true)
+        },
+    )
 }
 
 /// put values in an array.
 pub fn array(values: &[ColumnarValue]) -> Result<ColumnarValue> {
+    // scalars are expanded to the length of the array arguments, if any
+    let len = values
+        .iter()
+        .find_map(|x| match x {
+            ColumnarValue::Array(array) => Some(array.len()),
+            ColumnarValue::Scalar(_) => None,
+        })
+        .unwrap_or(1);
     let arrays: Vec<ArrayRef> = values
         .iter()
         .map(|x| match x {
             ColumnarValue::Array(array) => array.clone(),
-            ColumnarValue::Scalar(scalar) => scalar.to_array().clone(),
+            ColumnarValue::Scalar(scalar) => scalar.to_array_of_size(len),
         })
         .collect();
     Ok(ColumnarValue::Array(array_array(arrays.as_slice())?))
 }
+
+/// Array_append SQL function
+pub fn array_append(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let values = list.values().to_data();
+    let element = args[1].to_data();
+    build_list_array(
+        list_field(list)?,
+        vec![&values, &element],
+        list.len(),
+        |row, values| {
+            if list.is_valid(row) {
+                let (start, end) = value_range(list, row);
+                values.extend(0, start, end);
+            }
+            values.extend(1, row, row + 1);
+            Ok(true)
+        },
+    )
+}
+
+/// Array_prepend SQL function
+pub fn array_prepend(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[1])?;
+    let values = list.values().to_data();
+    let element = args[0].to_data();
+    build_list_array(
+        list_field(list)?,
+        vec![&values, &element],
+        list.len(),
+        |row, values| {
+            values.extend(1, row, row + 1);
+            if list.is_valid(row) {
+                let (start, end) = value_range(list, row);
+                values.extend(0, start, end);
+            }
+            Ok(true)
+        },
+    )
+}
+
+/// Array_concat SQL function
+pub fn array_concat(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let lists = args
+        .iter()
+        .map(|arg| as_list_array(arg.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    let values = lists
+        .iter()
+        .map(|list| list.values().to_data())
+        .collect::<Vec<_>>();
+    build_list_array(
+        list_field(lists[0])?,
+        values.iter().collect(),
+        lists[0].len(),
+        |row, values| {
+            // null arrays are skipped, the result is null only if they all are
+            let mut valid = false;
+            for (index, list) in lists.iter().enumerate() {
+                if list.is_valid(row) {
+                    let (start, end) = value_range(list, row);
+                    values.extend(index, start, end);
+                    valid = true;
+                }
+            }
+            Ok(valid)
+        },
+    )
+}
+
+/// Array_length SQL function
+pub fn array_length(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let dimension = args
+        .get(1)
+        .map(|arg| as_int64_array(arg.as_ref()))
+        .transpose()?;
+    let result = (0..list.len())
+        .map(|row| -> Result<Option<u64>> {
+            let dimension = match dimension {
+                Some(dimension) if dimension.is_null(row) => return Ok(None),
+                Some(dimension) => dimension.value(row),
+                None => 1,
+            };
+            if list.is_null(row) || dimension < 1 {
+                return Ok(None);
+            }
+            let dimensions = dimensions(list.value(row))?;
+            Ok(dimensions.get(dimension as usize - 1).copied())
+        })
+        .collect::<Result<UInt64Array>>()?;
+    Ok(Arc::new(result))
+}
+
+/// Array_dims SQL function
+pub fn array_dims(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let mut builder = ListBuilder::new(UInt64Builder::new());
+    for row in 0..list.len() {
+        if list.is_null(row) {
+            builder.append(false);
+        } else {
+            builder.values().append_slice(&dimensions(list.value(row))?);
+            builder.append(true);
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Cardinality SQL function
+pub fn cardinality(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let (_, ranges) = leaf_values(list)?;
+    let result = ranges
+        .iter()
+        .enumerate()
+        .map(|(row, (start, end))| list.is_valid(row).then_some((end - start) as u64))
+        .collect::<UInt64Array>();
+    Ok(Arc::new(result))
+}
+
+/// Flatten SQL function
+pub fn flatten(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let (leaves, ranges) = leaf_values(list)?;
+    let field = Arc::new(Field::new("item", leaves.data_type().clone(), true));
+    let leaves = leaves.to_data();
+    build_list_array(field, vec![&leaves], list.len(), |row, values| {
+        let (start, end) = ranges[row];
+        values.extend(0, start, end);
+        Ok(list.is_valid(row))
+    })
+}
+
+/// Array_position SQL function
+pub fn array_position(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let index = args
+        .get(2)
+        .map(|arg| as_int64_array(arg.as_ref()))
+        .transpose()?;
+    let result = (0..list.len())
+        .map(|row| -> Result<Option<u64>> {
+            let from = match index {
+                Some(index) if index.is_null(row) => return Ok(None),
+                Some(index) => index.value(row).max(1) as usize - 1,
+                None => 0,
+            };
+            if list.is_null(row) {
+                return Ok(None);
+            }
+            let element = ScalarValue::try_from_array(&args[1], row)?;
+            let (start, end) = value_range(list, row);
+            for i in (start + from)..end {
+                if value_eq(list.values(), i, &element)? {
+                    return Ok(Some((i - start + 1) as u64));
+                }
+            }
+            Ok(None)
+        })
+        .collect::<Result<UInt64Array>>()?;
+    Ok(Arc::new(result))
+}
+
+/// Array_positions SQL function
+pub fn array_positions(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let mut builder = ListBuilder::new(UInt64Builder::new());
+    for row in 0..list.len() {
+        if list.is_null(row) {
+            builder.append(false);
+            continue;
+        }
+        let element = ScalarValue::try_from_array(&args[1], row)?;
+        let (start, end) = value_range(list, row);
+        for i in start..end {
+            if value_eq(list.values(), i, &element)? {
+                builder.values().append_value((i - start + 1) as u64);
+            }
+        }
+        builder.append(true);
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Array_remove SQL function
+pub fn array_remove(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let values = list.values().to_data();
+    build_list_array(
+        list_field(list)?,
+        vec![&values],
+        list.len(),
+        |row, values| {
+            if list.is_null(row) {
+                return Ok(false);
+            }
+            let element = ScalarValue::try_from_array(&args[1], row)?;
+            let (start, end) = value_range(list, row);
+            for i in start..end {
+                if !value_eq(list.values(), i, &element)? {
+                    values.extend(0, i, i + 1);
+                }
+            }
+            Ok(true)
+        },
+    )
+}
+
+/// Array_replace SQL function
+pub fn array_replace(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let values = list.values().to_data();
+    let to = args[2].to_data();
+    build_list_array(
+        list_field(list)?,
+        vec![&values, &to],
+        list.len(),
+        |row, values| {
+            if list.is_null(row) {
+                return Ok(false);
+            }
+            let from = ScalarValue::try_from_array(&args[1], row)?;
+            let (start, end) = value_range(list, row);
+            for i in start..end {
+                if value_eq(list.values(), i, &from)? {
+                    values.extend(1, row, row + 1);
+                } else {
+                    values.extend(0, i, i + 1);
+                }
+            }
+            Ok(true)
+        },
+    )
+}
+
+/// Array_slice SQL function
+///
+/// The bounds are 1-based and inclusive, and clamped to the array.
+pub fn array_slice(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let begin = as_int64_array(&args[1])?;
+    let end = as_int64_array(&args[2])?;
+    let values = list.values().to_data();
+    build_list_array(
+        list_field(list)?,
+        vec![&values],
+        list.len(),
+        |row, values| {
+            if list.is_null(row) || begin.is_null(row) || end.is_null(row) {
+                return Ok(false);
+            }
+            let (start, stop) = value_range(list, row);
+            let len = (stop - start) as i64;
+            let from = begin.value(row).max(1);
+            let to = end.value(row).min(len);
+            if from <= to {
+                values.extend(0, start + from as usize - 1, start + to as usize);
+            }
+            Ok(true)
+        },
+    )
+}
+
+/// Array_contains SQL function
+///
+/// Returns true if each element of the second array appears in the first one.
+pub fn array_contains(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let first = as_list_array(&args[0])?;
+    let second = as_list_array(&args[1])?;
+    let result = (0..first.len())
+        .map(|row| -> Result<Option<bool>> {
+            if first.is_null(row) || second.is_null(row) {
+                return Ok(None);
+            }
+            let (start, end) = value_range(first, row);
+            let elements = (start..end)
+                .map(|i| ScalarValue::try_from_array(first.values(), i))
+                .collect::<Result<Vec<_>>>()?;
+            let (start, end) = value_range(second, row);
+            for i in start..end {
+                let element = ScalarValue::try_from_array(second.values(), i)?;
+                if !elements.contains(&element) {
+                    return Ok(Some(false));
+                }
+            }
+            Ok(Some(true))
+        })
+        .collect::<Result<BooleanArray>>()?;
+    Ok(Arc::new(result))
+}
+
+/// Array_to_string SQL function
+///
+/// Nested arrays are flattened, and null elements are skipped.
+pub fn array_to_string(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let list = as_list_array(&args[0])?;
+    let delimiter = as_string_array(&args[1])?;
+    let (leaves, ranges) = leaf_values(list)?;
+    let mut builder = StringBuilder::new();
+    for (row, (start, end)) in ranges.into_iter().enumerate() {
+        if list.is_null(row) || delimiter.is_null(row) {
+            builder.append_null();
+            continue;
+        }
+        let elements = (start..end)
+            .filter(|i| leaves.is_valid(*i))
+            .map(|i| array_value_to_string(&leaves, i))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        builder.append_value(elements.join(delimiter.value(row)));
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{Int64Type, UInt64Type};
+    use datafusion_common::cast::{as_boolean_array, as_uint64_array};
+
+    fn int64_list(rows: Vec<Option<Vec<Option<i64>>>>) -> ArrayRef {
+        Arc::new(ListArray::from_iter_primitive::<Int64Type, _, _>(rows))
+    }
+
+    fn int64(values: Vec<Option<i64>>) -> ArrayRef {
+        Arc::new(Int64Array::from(values))
+    }
+
+    /// [[1, 2], [3, 4, 5]], [[6], [7, 8]] and null
+    fn nested_list() -> Result<ArrayRef> {
+        let first = int64_list(vec![
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(6)]),
+            None,
+        ]);
+        let second = int64_list(vec![
+            Some(vec![Some(3), Some(4), Some(5)]),
+            Some(vec![Some(7), Some(8)]),
+            None,
+        ]);
+        let nested = array_array(&[first, second])?;
+        // null out the last row
+        let nulls = BooleanArray::from(vec![true, true, false]);
+        Ok(arrow::compute::nullif(
+            &nested,
+            &arrow::compute::not(&nulls)?,
+        )?)
+    }
+
+    #[test]
+    fn test_array() -> Result<()> {
+        let array = array(&[
+            ColumnarValue::Array(int64(vec![Some(1), None])),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(3))),
+        ])?
+        .into_array(2);
+        let expected = int64_list(vec![
+            Some(vec![Some(1), Some(3)]),
+            Some(vec![None, Some(3)]),
+        ]);
+        assert_eq!(&array, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_append_prepend_concat() -> Result<()> {
+        let list = int64_list(vec![Some(vec![Some(1), Some(2)]), None, Some(vec![])]);
+        let element = int64(vec![Some(3), Some(4), None]);
+
+        let result = array_append(&[list.clone(), element.clone()])?;
+        let expected = int64_list(vec![
+            Some(vec![Some(1), Some(2), Some(3)]),
+            Some(vec![Some(4)]),
+            Some(vec![None]),
+        ]);
+        assert_eq!(&result, &expected);
+
+        let result = array_prepend(&[element, list.clone()])?;
+        let expected = int64_list(vec![
+            Some(vec![Some(3), Some(1), Some(2)]),
+            Some(vec![Some(4)]),
+            Some(vec![None]),
+        ]);
+        assert_eq!(&result, &expected);
+
+        let other = int64_list(vec![Some(vec![Some(5)]), None, Some(vec![Some(6)])]);
+        let result = array_concat(&[list, other])?;
+        let expected = int64_list(vec![
+            Some(vec![Some(1), Some(2), Some(5)]),
+            None,
+            Some(vec![Some(6)]),
+        ]);
+        assert_eq!(&result, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_dimensions() -> Result<()> {
+        let list = nested_list()?;
+
+        let result = array_length(&[list.clone()])?;
+        assert_eq!(
+            as_uint64_array(&result)?,
+            &UInt64Array::from(vec![Some(2), Some(2), None])
+        );
+
+        let dimension = int64(vec![Some(2), Some(3), Some(2)]);
+        let result = array_length(&[list.clone(), dimension])?;
+        assert_eq!(
+            as_uint64_array(&result)?,
+            &UInt64Array::from(vec![Some(2), None, None])
+        );
+
+        let result = array_dims(&[list.clone()])?;
+        let expected = ListArray::from_iter_primitive::<UInt64Type, _, _>(vec![
+            Some(vec![Some(2), Some(2)]),
+            Some(vec![Some(2), Some(1)]),
+            None,
+        ]);
+        assert_eq!(as_list_array(&result)?, &expected);
+
+        let result = cardinality(&[list.clone()])?;
+        assert_eq!(
+            as_uint64_array(&result)?,
+            &UInt64Array::from(vec![Some(5), Some(3), None])
+        );
+
+        let result = flatten(&[list])?;
+        let expected = int64_list(vec![
+            Some(vec![Some(1), Some(2), Some(3), Some(4), Some(5)]),
+            Some(vec![Some(6), Some(7), Some(8)]),
+            None,
+        ]);
+        assert_eq!(&result, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_search() -> Result<()> {
+        let list = int64_list(vec![
+            Some(vec![Some(1), Some(2), Some(1), None]),
+            Some(vec![Some(3)]),
+            None,
+        ]);
+        let element = int64(vec![Some(1), Some(4), Some(1)]);
+
+        let result = array_position(&[list.clone(), element.clone()])?;
+        assert_eq!(
+            as_uint64_array(&result)?,
+            &UInt64Array::from(vec![Some(1), None, None])
+        );
+
+        let index = int64(vec![Some(2), Some(1), Some(1)]);
+        let result = array_position(&[list.clone(), element.clone(), index])?;
+        assert_eq!(
+            as_uint64_array(&result)?,
+            &UInt64Array::from(vec![Some(3), None, None])
+        );
+
+        let result = array_positions(&[list.clone(), element.clone()])?;
+        let expected = ListArray::from_iter_primitive::<UInt64Type, _, _>(vec![
+            Some(vec![Some(1), Some(3)]),
+            Some(vec![]),
+            None,
+        ]);
+        assert_eq!(as_list_array(&result)?, &expected);
+
+        let result = array_remove(&[list.clone(), element.clone()])?;
+        let expected =
+            int64_list(vec![Some(vec![Some(2), None]), Some(vec![Some(3)]), None]);
+        assert_eq!(&result, &expected);
+
+        let to = int64(vec![Some(9), Some(9), Some(9)]);
+        let result = array_replace(&[list.clone(), element, to])?;
+        let expected = int64_list(vec![
+            Some(vec![Some(9), Some(2), Some(9), None]),
+            Some(vec![Some(3)]),
+            None,
+        ]);
+        assert_eq!(&result, &expected);
+
+        let sub_list = int64_list(vec![
+            Some(vec![Some(2), Some(1)]),
+            Some(vec![Some(4)]),
+            None,
+        ]);
+        let result = array_contains(&[list, sub_list])?;
+        assert_eq!(
+            as_boolean_array(&result)?,
+            &BooleanArray::from(vec![Some(true), Some(false), None])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_slice() -> Result<()> {
+        let list = int64_list(vec![
+            Some(vec![Some(1), Some(2), Some(3), Some(4)]),
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(1), Some(2)]),
+            None,
+        ]);
+        let begin = int64(vec![Some(2), Some(0), Some(2), Some(1)]);
+        let end = int64(vec![Some(3), Some(5), Some(1), Some(1)]);
+        let result = array_slice(&[list, begin, end])?;
+        let expected = int64_list(vec![
+            Some(vec![Some(2), Some(3)]),
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![]),
+            None,
+        ]);
+        assert_eq!(&result, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_to_string() -> Result<()> {
+        let list = nested_list()?;
+        let delimiter: ArrayRef = Arc::new(StringArray::from(vec![",", "-", ","]));
+        let result = array_to_string(&[list, delimiter])?;
+        assert_eq!(
+            as_string_array(&result)?,
+            &StringArray::from(vec![Some("1,2,3,4,5"), Some("6-7-8"), None])
+        );
+
+        let list = int64_list(vec![Some(vec![Some(1), None, Some(3)])]);
+        let delimiter: ArrayRef = Arc::new(StringArray::from(vec!["|"]));
+        let result = array_to_string(&[list, delimiter])?;
+        assert_eq!(as_string_array(&result)?, &StringArray::from(vec!["1|3"]));
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@
 pub mod cp_solver;
 pub mod interval_aritmetic;
 pub mod rounding;
+pub mod statistics;
 
 pub mod test_utils;
 pub use cp_solver::{check_support, ExprIntervalGraph};
@@ -0,0 +1,301 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Estimation of column statistics using interval arithmetic
+
+use std::sync::Arc;
+
+use arrow_schema::Schema;
+use datafusion_common::{ColumnStatistics, Result, ScalarValue};
+
+use crate::expressions::Column;
+use crate::intervals::cp_solver::PropagationResult;
+use crate::intervals::{
+    check_support, is_datatype_supported, ExprIntervalGraph, Interval, IntervalBound,
+};
+use crate::utils::collect_columns;
+use crate::PhysicalExpr;
+
+/// The effect of a predicate on the columns it references, as estimated by
+/// [`estimate_predicate`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredicateEstimate {
+    /// Estimated fraction of the input rows for which the predicate is true,
+    /// assuming uniformly distributed and independent columns
+    pub selectivity: f64,
+    /// The ranges of the referenced columns in the rows for which the
+    /// predicate is true, as pairs of column index and range
+    pub column_ranges: Vec<(usize, Interval)>,
+}
+
+/// Returns the closed range `[min, max]` of a column, if both bounds are known
+pub fn interval_from_statistics(statistics: &ColumnStatistics) -> Option<Interval> {
+    match (&statistics.min_value, &statistics.max_value) {
+        (Some(min), Some(max)) if !min.is_null() && !max.is_null() => {
+            Some(Interval::new(
+                IntervalBound::new(min.clone(), false),
+                IntervalBound::new(max.clone(), false),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Estimates the selectivity of `predicate` and the ranges of the columns it
+/// references from the ranges of those columns in the input.
+///
+/// Returns `None` if interval arithmetic does not support the predicate, or
+/// the range of one of the referenced columns is unknown.
+pub fn estimate_predicate(
+    predicate: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+    column_statistics: &[ColumnStatistics],
+) -> Result<Option<PredicateEstimate>> {
+    let (mut graph, columns, mut leaf_bounds) =
+        match build_graph(predicate, schema, column_statistics)? {
+            Some(graph) => graph,
+            None => return Ok(None),
+        };
+    let input_ranges = leaf_bounds
+        .iter()
+        .map(|(_, interval)| interval.clone())
+        .collect::<Vec<_>>();
+
+    let selectivity = match graph.update_ranges(&mut leaf_bounds)? {
+        PropagationResult::Infeasible => 0.0,
+        PropagationResult::CannotPropagate => 1.0,
+        PropagationResult::Success => input_ranges
+            .iter()
+            .zip(&leaf_bounds)
+            .map(|(input, (_, output))| range_fraction(input, output))
+            .product(),
+    };
+    let column_ranges = columns
+        .iter()
+        .zip(leaf_bounds)
+        .map(|(column, (_, interval))| (column.index(), interval))
+        .collect();
+
+    Ok(Some(PredicateEstimate {
+        selectivity,
+        column_ranges,
+    }))
+}
+
+/// Estimates the range of the values of `expr` from the ranges of the columns
+/// it references.
+///
+/// Returns `None` if interval arithmetic does not support the expression, or
+/// the range of one of the referenced columns is unknown.
+pub fn estimate_range(
+    expr: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+    column_statistics: &[ColumnStatistics],
+) -> Result<Option<Interval>> {
+    match build_graph(expr, schema, column_statistics)? {
+        Some((mut graph, _, leaf_bounds)) => {
+            graph.assign_intervals(&leaf_bounds);
+            graph
+                .evaluate_bounds()
+                .map(|interval| Some(interval.clone()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Graph of an expression, the columns it references and their node indices
+/// and input ranges
+type ExprGraph = (ExprIntervalGraph, Vec<Column>, Vec<(usize, Interval)>);
+
+fn build_graph(
+    expr: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+    column_statistics: &[ColumnStatistics],
+) -> Result<Option<ExprGraph>> {
+    if !check_support(expr) {
+        return Ok(None);
+    }
+
+    let mut columns = collect_columns(expr).into_iter().collect::<Vec<_>>();
+    columns.sort_by_key(|column| column.index());
+    let mut ranges = Vec::with_capacity(columns.len());
+    for column in &columns {
+        let supported = schema
+            .fields()
+            .get(column.index())
+            .map(|field| is_datatype_supported(field.data_type()))
+            .unwrap_or(false);
+        let range = column_statistics
+            .get(column.index())
+            .and_then(interval_from_statistics);
+        match range {
+            Some(range) if supported => ranges.push(range),
+            _ => return Ok(None),
+        }
+    }
+
+    let mut graph = ExprIntervalGraph::try_new(expr.clone())?;
+    let column_exprs = columns
+        .iter()
+        .map(|column| Arc::new(column.clone()) as Arc<dyn PhysicalExpr>)
+        .collect::<Vec<_>>();
+    let leaf_bounds = graph
+        .gather_node_indices(&column_exprs)
+        .into_iter()
+        .zip(ranges)
+        .map(|((_, node), range)| (node, range))
+        .collect();
+
+    Ok(Some((graph, columns, leaf_bounds)))
+}
+
+/// Fraction of the values of `input` that are also values of `output`, which
+/// is a subrange of `input`
+fn range_fraction(input: &Interval, output: &Interval) -> f64 {
+    match (range_width(input), range_width(output)) {
+        (Some(input), Some(output)) if input > 0.0 => (output / input).clamp(0.0, 1.0),
+        _ => 1.0,
+    }
+}
+
+/// Returns the length of a floating point range, or the number of values in
+/// an integer range
+fn range_width(interval: &Interval) -> Option<f64> {
+    let (lower, upper) = (&interval.lower, &interval.upper);
+    match (&lower.value, &upper.value) {
+        (ScalarValue::Float32(Some(lower)), ScalarValue::Float32(Some(upper))) => {
+            Some((upper - lower) as f64)
+        }
+        (ScalarValue::Float64(Some(lower)), ScalarValue::Float64(Some(upper))) => {
+            Some(upper - lower)
+        }
+        (lower_value, upper_value) => {
+            let values = upper_value.distance(lower_value)? + 1;
+            let open_bounds = lower.open as usize + upper.open as usize;
+            Some(values.saturating_sub(open_bounds) as f64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{binary, col, lit};
+    use arrow_schema::{DataType, Field};
+    use datafusion_expr::Operator;
+
+    fn int_statistics(min: i32, max: i32) -> ColumnStatistics {
+        ColumnStatistics {
+            min_value: Some(ScalarValue::Int32(Some(min))),
+            max_value: Some(ScalarValue::Int32(Some(max))),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn estimate_range_conjunction() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let statistics = vec![int_statistics(1, 100), int_statistics(1, 50)];
+
+        // a >= 11 AND a <= 30 AND b > 40
+        let a_range = binary(
+            binary(col("a", &schema)?, Operator::GtEq, lit(11i32), &schema)?,
+            Operator::And,
+            binary(col("a", &schema)?, Operator::LtEq, lit(30i32), &schema)?,
+            &schema,
+        )?;
+        let predicate = binary(
+            a_range,
+            Operator::And,
+            binary(col("b", &schema)?, Operator::Gt, lit(40i32), &schema)?,
+            &schema,
+        )?;
+
+        let estimate = estimate_predicate(&predicate, &schema, &statistics)?.unwrap();
+        assert!((estimate.selectivity - 0.2 * 0.2).abs() < 1e-9);
+        assert_eq!(
+            estimate.column_ranges,
+            vec![
+                (
+                    0,
+                    interval_from_statistics(&int_statistics(11, 30)).unwrap()
+                ),
+                (
+                    1,
+                    Interval::new(
+                        IntervalBound::new(ScalarValue::Int32(Some(40)), true),
+                        IntervalBound::new(ScalarValue::Int32(Some(50)), false),
+                    )
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn estimate_infeasible_and_certain_predicates() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let statistics = vec![int_statistics(1, 100)];
+
+        let predicate = binary(col("a", &schema)?, Operator::Gt, lit(200i32), &schema)?;
+        let estimate = estimate_predicate(&predicate, &schema, &statistics)?.unwrap();
+        assert_eq!(estimate.selectivity, 0.0);
+
+        let predicate = binary(col("a", &schema)?, Operator::GtEq, lit(1i32), &schema)?;
+        let estimate = estimate_predicate(&predicate, &schema, &statistics)?.unwrap();
+        assert_eq!(estimate.selectivity, 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn estimate_unknown_ranges() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let predicate = binary(col("a", &schema)?, Operator::Gt, lit(5i32), &schema)?;
+
+        let statistics = vec![ColumnStatistics::default()];
+        assert_eq!(estimate_predicate(&predicate, &schema, &statistics)?, None);
+
+        // Equality is not supported by interval arithmetic
+        let statistics = vec![int_statistics(1, 100)];
+        let predicate = binary(col("a", &schema)?, Operator::Eq, lit(5i32), &schema)?;
+        assert_eq!(estimate_predicate(&predicate, &schema, &statistics)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn estimate_expression_range() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let statistics = vec![int_statistics(1, 100), int_statistics(-5, 5)];
+
+        // a + b
+        let expr = binary(
+            col("a", &schema)?,
+            Operator::Plus,
+            col("b", &schema)?,
+            &schema,
+        )?;
+        let range = estimate_range(&expr, &schema, &statistics)?;
+        assert_eq!(range, interval_from_statistics(&int_statistics(-4, 105)));
+        Ok(())
+    }
+}
@@ -25,7 +25,9 @@ use crate::physical_plan::{
 use crate::protobuf;
 use datafusion::physical_plan::functions::make_scalar_function;
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::{create_udaf, create_udf, Expr, LogicalPlan, Volatility};
+use datafusion_expr::{
+    create_udaf, create_udf, create_udwf, Expr, LogicalPlan, Volatility,
+};
 use prost::{
     bytes::{Bytes, BytesMut},
     Message,
@@ -128,6 +130,16 @@ impl Serializeable for Expr {
                     Arc::new(vec![]),
                 )))
             }
+
+            fn udwf(&self, name: &str) -> Result<Arc<datafusion_expr::WindowUDF>> {
+                Ok(Arc::new(create_udwf(
+                    name,
+                    vec![],
+                    Arc::new(arrow::datatypes::DataType::Null),
+                    Volatility::Immutable,
+                    Arc::new(|| unimplemented!()),
+                )))
+            }
         }
         Expr::from_bytes_with_registry(&bytes, &PlaceHolderRegistry)?;
 
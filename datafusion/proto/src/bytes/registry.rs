@@ -19,7 +19,7 @@ use std::{collections::HashSet, sync::Arc};
 
 use datafusion::execution::registry::FunctionRegistry;
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::{AggregateUDF, ScalarUDF};
+use datafusion_expr::{AggregateUDF, ScalarUDF, WindowUDF};
 
 /// A default [`FunctionRegistry`] registry that does not resolve any
 /// user defined functions
@@ -41,4 +41,10 @@ impl FunctionRegistry for NoRegistry {
             format!("No function registry provided to deserialize, so can not deserialize User Defined Aggregate Function '{name}'"))
         )
     }
+
+    fn udwf(&self, name: &str) -> Result<Arc<WindowUDF>> {
+        Err(DataFusionError::Plan(
+            format!("No function registry provided to deserialize, so can not deserialize User Defined Window Function '{name}'"))
+        )
+    }
 }
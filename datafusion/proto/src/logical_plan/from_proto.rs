@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::logical_plan::{AsLogicalPlan, LogicalExtensionCodec};
 use crate::protobuf::{
     self,
     plan_type::PlanTypeEnum::{
@@ -30,18 +31,25 @@ use arrow::datatypes::{
     UnionFields, UnionMode,
 };
 use datafusion::execution::registry::FunctionRegistry;
+use datafusion::prelude::SessionContext;
 use datafusion_common::{
-    Column, DFField, DFSchema, DFSchemaRef, DataFusionError, OwnedTableReference, Result,
-    ScalarValue,
+    Column, DFField, DFSchema, DFSchemaRef, DataFusionError, OwnedSchemaReference,
+    OwnedTableReference, Result, ScalarValue,
 };
-use datafusion_expr::expr::Placeholder;
+use datafusion_expr::expr::{Exists, InSubquery, Placeholder};
 use datafusion_expr::{
-    abs, acos, acosh, array, ascii, asin, asinh, atan, atan2, atanh, bit_length, btrim,
-    cbrt, ceil, character_length, chr, coalesce, concat_expr, concat_ws_expr, cos, cosh,
-    date_bin, date_part, date_trunc, degrees, digest, exp,
+    abs, acos, acosh, array, array_append, array_concat, array_contains, array_dims,
+    array_length, array_position, array_positions, array_prepend, array_remove,
+    array_replace, array_slice, array_to_string, ascii, asin, asinh, atan, atan2, atanh,
+    bit_length, btrim, cardinality, cbrt, ceil, character_length, chr, coalesce,
+    concat_expr, concat_ws_expr, cos, cosh, date_bin, date_part, date_trunc, degrees,
+    digest, exp,
     expr::{self, InList, Sort, WindowFunction},
-    factorial, floor, from_unixtime, gcd, lcm, left, ln, log, log10, log2,
-    logical_plan::{PlanType, StringifiedPlan},
+    factorial, flatten, floor, from_unixtime, gcd, lcm, left, ln, log, log10, log2,
+    logical_plan::{
+        PlanType, StringifiedPlan, Subquery, TransactionAccessMode,
+        TransactionConclusion, TransactionIsolationLevel, WriteOp,
+    },
     lower, lpad, ltrim, md5, now, nullif, octet_length, pi, power, radians, random,
     regexp_match, regexp_replace, repeat, replace, reverse, right, round, rpad, rtrim,
     sha224, sha256, sha384, sha512, signum, sin, sinh, split_part, sqrt, starts_with,
@@ -232,6 +240,32 @@ impl TryFrom<protobuf::OwnedTableReference> for OwnedTableReference {
     }
 }
 
+impl TryFrom<protobuf::OwnedSchemaReference> for OwnedSchemaReference {
+    type Error = Error;
+
+    fn try_from(value: protobuf::OwnedSchemaReference) -> Result<Self, Self::Error> {
+        use protobuf::owned_schema_reference::SchemaReferenceEnum;
+        let schema_reference_enum = value
+            .schema_reference_enum
+            .ok_or_else(|| Error::required("schema_reference_enum"))?;
+
+        match schema_reference_enum {
+            SchemaReferenceEnum::Bare(protobuf::BareSchemaReference { schema }) => {
+                Ok(OwnedSchemaReference::Bare {
+                    schema: schema.into(),
+                })
+            }
+            SchemaReferenceEnum::Full(protobuf::FullSchemaReference {
+                catalog,
+                schema,
+            }) => Ok(OwnedSchemaReference::Full {
+                schema: schema.into(),
+                catalog: catalog.into(),
+            }),
+        }
+    }
+}
+
 impl TryFrom<&protobuf::ArrowType> for DataType {
     type Error = Error;
 
@@ -445,6 +479,20 @@ impl From<&protobuf::ScalarFunction> for BuiltinScalarFunction {
             ScalarFunction::Rtrim => Self::Rtrim,
             ScalarFunction::ToTimestamp => Self::ToTimestamp,
             ScalarFunction::Array => Self::MakeArray,
+            ScalarFunction::ArrayAppend => Self::ArrayAppend,
+            ScalarFunction::ArrayConcat => Self::ArrayConcat,
+            ScalarFunction::ArrayDims => Self::ArrayDims,
+            ScalarFunction::ArrayLength => Self::ArrayLength,
+            ScalarFunction::ArrayPosition => Self::ArrayPosition,
+            ScalarFunction::ArrayPositions => Self::ArrayPositions,
+            ScalarFunction::ArrayPrepend => Self::ArrayPrepend,
+            ScalarFunction::ArrayRemove => Self::ArrayRemove,
+            ScalarFunction::ArrayReplace => Self::ArrayReplace,
+            ScalarFunction::ArraySlice => Self::ArraySlice,
+            ScalarFunction::ArrayToString => Self::ArrayToString,
+            ScalarFunction::ArrayContains => Self::ArrayContains,
+            ScalarFunction::Cardinality => Self::Cardinality,
+            ScalarFunction::Flatten => Self::Flatten,
             ScalarFunction::NullIf => Self::NullIf,
             ScalarFunction::DatePart => Self::DatePart,
             ScalarFunction::DateTrunc => Self::DateTrunc,
@@ -822,6 +870,56 @@ impl From<protobuf::JoinConstraint> for JoinConstraint {
     }
 }
 
+impl From<protobuf::WriteOp> for WriteOp {
+    fn from(op: protobuf::WriteOp) -> Self {
+        match op {
+            protobuf::WriteOp::Insert => WriteOp::Insert,
+            protobuf::WriteOp::Delete => WriteOp::Delete,
+            protobuf::WriteOp::Update => WriteOp::Update,
+            protobuf::WriteOp::Ctas => WriteOp::Ctas,
+        }
+    }
+}
+
+impl From<protobuf::TransactionAccessMode> for TransactionAccessMode {
+    fn from(mode: protobuf::TransactionAccessMode) -> Self {
+        match mode {
+            protobuf::TransactionAccessMode::ReadOnly => TransactionAccessMode::ReadOnly,
+            protobuf::TransactionAccessMode::ReadWrite => {
+                TransactionAccessMode::ReadWrite
+            }
+        }
+    }
+}
+
+impl From<protobuf::TransactionIsolationLevel> for TransactionIsolationLevel {
+    fn from(level: protobuf::TransactionIsolationLevel) -> Self {
+        match level {
+            protobuf::TransactionIsolationLevel::ReadUncommitted => {
+                TransactionIsolationLevel::ReadUncommitted
+            }
+            protobuf::TransactionIsolationLevel::ReadCommitted => {
+                TransactionIsolationLevel::ReadCommitted
+            }
+            protobuf::TransactionIsolationLevel::RepeatableRead => {
+                TransactionIsolationLevel::RepeatableRead
+            }
+            protobuf::TransactionIsolationLevel::Serializable => {
+                TransactionIsolationLevel::Serializable
+            }
+        }
+    }
+}
+
+impl From<protobuf::TransactionConclusion> for TransactionConclusion {
+    fn from(conclusion: protobuf::TransactionConclusion) -> Self {
+        match conclusion {
+            protobuf::TransactionConclusion::Commit => TransactionConclusion::Commit,
+            protobuf::TransactionConclusion::Rollback => TransactionConclusion::Rollback,
+        }
+    }
+}
+
 pub fn parse_i32_to_time_unit(value: &i32) -> Result<TimeUnit, Error> {
     protobuf::TimeUnit::from_i32(*value)
         .map(|t| t.into())
@@ -856,9 +954,68 @@ fn validate_list_values(field: &Field, values: &[ScalarValue]) -> Result<(), Err
     Ok(())
 }
 
+/// Parse a [`protobuf::LogicalExprNode`] into an [`Expr`].
+///
+/// Subquery expressions carry a logical plan and can only be parsed with
+/// [`parse_expr_in_plan`].
 pub fn parse_expr(
     proto: &protobuf::LogicalExprNode,
     registry: &dyn FunctionRegistry,
+) -> Result<Expr, Error> {
+    parse_expr_impl(proto, registry, None)
+}
+
+/// Parse a [`protobuf::LogicalExprNode`] that is part of a logical plan,
+/// deserializing the plans of any subquery expressions with `extension_codec`.
+pub fn parse_expr_in_plan(
+    proto: &protobuf::LogicalExprNode,
+    ctx: &SessionContext,
+    extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<Expr, Error> {
+    parse_expr_impl(
+        proto,
+        ctx,
+        Some(PlanContext {
+            ctx,
+            extension_codec,
+        }),
+    )
+}
+
+/// Parse a [`protobuf::SubqueryNode`] into a [`Subquery`].
+pub fn parse_subquery(
+    proto: &protobuf::SubqueryNode,
+    ctx: &SessionContext,
+    extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<Subquery, Error> {
+    let subquery = proto
+        .subquery
+        .as_ref()
+        .ok_or_else(|| Error::required("subquery"))?
+        .try_into_logical_plan(ctx, extension_codec)?;
+    let outer_ref_columns = proto
+        .outer_ref_columns
+        .iter()
+        .map(|expr| parse_expr_in_plan(expr, ctx, extension_codec))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Subquery {
+        subquery: Arc::new(subquery),
+        outer_ref_columns,
+    })
+}
+
+/// What is needed to deserialize the plans of subquery expressions
+#[derive(Clone, Copy)]
+struct PlanContext<'a> {
+    ctx: &'a SessionContext,
+    extension_codec: &'a dyn LogicalExtensionCodec,
+}
+
+fn parse_expr_impl(
+    proto: &protobuf::LogicalExprNode,
+    registry: &dyn FunctionRegistry,
+    plan_ctx: Option<PlanContext>,
 ) -> Result<Expr, Error> {
     use protobuf::{logical_expr_node::ExprType, window_expr_node, ScalarFunction};
 
@@ -873,7 +1030,7 @@ pub fn parse_expr(
             let operands = binary_expr
                 .operands
                 .iter()
-                .map(|expr| parse_expr(expr, registry))
+                .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                 .collect::<Result<Vec<_>, _>>()?;
 
             if operands.len() < 2 {
@@ -898,7 +1055,8 @@ pub fn parse_expr(
                 .ok_or_else(|| Error::required("value"))?
                 .try_into()?;
 
-            let expr = parse_required_expr(field.expr.as_deref(), registry, "expr")?;
+            let expr =
+                parse_required_expr(field.expr.as_deref(), registry, plan_ctx, "expr")?;
 
             Ok(Expr::GetIndexedField(GetIndexedField::new(
                 Box::new(expr),
@@ -918,12 +1076,12 @@ pub fn parse_expr(
             let partition_by = expr
                 .partition_by
                 .iter()
-                .map(|e| parse_expr(e, registry))
+                .map(|e| parse_expr_impl(e, registry, plan_ctx))
                 .collect::<Result<Vec<_>, _>>()?;
             let order_by = expr
                 .order_by
                 .iter()
-                .map(|e| parse_expr(e, registry))
+                .map(|e| parse_expr_impl(e, registry, plan_ctx))
                 .collect::<Result<Vec<_>, _>>()?;
             let window_frame = expr
                 .window_frame
@@ -947,7 +1105,7 @@ pub fn parse_expr(
                         datafusion_expr::window_function::WindowFunction::AggregateFunction(
                             aggr_function,
                         ),
-                        vec![parse_required_expr(expr.expr.as_deref(), registry, "expr")?],
+                        vec![parse_required_expr(expr.expr.as_deref(), registry, plan_ctx, "expr")?],
                         partition_by,
                         order_by,
                         window_frame,
@@ -958,9 +1116,10 @@ pub fn parse_expr(
                         .ok_or_else(|| Error::unknown("BuiltInWindowFunction", *i))?
                         .into();
 
-                    let args = parse_optional_expr(expr.expr.as_deref(), registry)?
-                        .map(|e| vec![e])
-                        .unwrap_or_else(Vec::new);
+                    let args =
+                        parse_optional_expr(expr.expr.as_deref(), registry, plan_ctx)?
+                            .map(|e| vec![e])
+                            .unwrap_or_else(Vec::new);
 
                     Ok(Expr::WindowFunction(WindowFunction::new(
                         datafusion_expr::window_function::WindowFunction::BuiltInWindowFunction(
@@ -972,6 +1131,38 @@ pub fn parse_expr(
                         window_frame,
                     )))
                 }
+                window_expr_node::WindowFunction::Udaf(udaf_name) => {
+                    let udaf_function = registry.udaf(udaf_name)?;
+                    let args =
+                        parse_optional_expr(expr.expr.as_deref(), registry, plan_ctx)?
+                            .map(|e| vec![e])
+                            .unwrap_or_else(Vec::new);
+                    Ok(Expr::WindowFunction(WindowFunction::new(
+                        datafusion_expr::window_function::WindowFunction::AggregateUDF(
+                            udaf_function,
+                        ),
+                        args,
+                        partition_by,
+                        order_by,
+                        window_frame,
+                    )))
+                }
+                window_expr_node::WindowFunction::Udwf(udwf_name) => {
+                    let udwf_function = registry.udwf(udwf_name)?;
+                    let args =
+                        parse_optional_expr(expr.expr.as_deref(), registry, plan_ctx)?
+                            .map(|e| vec![e])
+                            .unwrap_or_else(Vec::new);
+                    Ok(Expr::WindowFunction(WindowFunction::new(
+                        datafusion_expr::window_function::WindowFunction::WindowUDF(
+                            udwf_function,
+                        ),
+                        args,
+                        partition_by,
+                        order_by,
+                        window_frame,
+                    )))
+                }
             }
         }
         ExprType::AggregateExpr(expr) => {
@@ -981,16 +1172,18 @@ pub fn parse_expr(
                 fun,
                 expr.expr
                     .iter()
-                    .map(|e| parse_expr(e, registry))
+                    .map(|e| parse_expr_impl(e, registry, plan_ctx))
                     .collect::<Result<Vec<_>, _>>()?,
                 expr.distinct,
-                parse_optional_expr(expr.filter.as_deref(), registry)?.map(Box::new),
+                parse_optional_expr(expr.filter.as_deref(), registry, plan_ctx)?
+                    .map(Box::new),
             )))
         }
         ExprType::Alias(alias) => Ok(Expr::Alias(
             Box::new(parse_required_expr(
                 alias.expr.as_deref(),
                 registry,
+                plan_ctx,
                 "expr",
             )?),
             alias.alias.clone(),
@@ -998,88 +1191,116 @@ pub fn parse_expr(
         ExprType::IsNullExpr(is_null) => Ok(Expr::IsNull(Box::new(parse_required_expr(
             is_null.expr.as_deref(),
             registry,
+            plan_ctx,
             "expr",
         )?))),
         ExprType::IsNotNullExpr(is_not_null) => Ok(Expr::IsNotNull(Box::new(
-            parse_required_expr(is_not_null.expr.as_deref(), registry, "expr")?,
+            parse_required_expr(is_not_null.expr.as_deref(), registry, plan_ctx, "expr")?,
         ))),
         ExprType::NotExpr(not) => Ok(Expr::Not(Box::new(parse_required_expr(
             not.expr.as_deref(),
             registry,
+            plan_ctx,
             "expr",
         )?))),
         ExprType::IsTrue(msg) => Ok(Expr::IsTrue(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            plan_ctx,
             "expr",
         )?))),
         ExprType::IsFalse(msg) => Ok(Expr::IsFalse(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            plan_ctx,
             "expr",
         )?))),
         ExprType::IsUnknown(msg) => Ok(Expr::IsUnknown(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            plan_ctx,
             "expr",
         )?))),
         ExprType::IsNotTrue(msg) => Ok(Expr::IsNotTrue(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            plan_ctx,
             "expr",
         )?))),
         ExprType::IsNotFalse(msg) => Ok(Expr::IsNotFalse(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            plan_ctx,
             "expr",
         )?))),
         ExprType::IsNotUnknown(msg) => Ok(Expr::IsNotUnknown(Box::new(
-            parse_required_expr(msg.expr.as_deref(), registry, "expr")?,
+            parse_required_expr(msg.expr.as_deref(), registry, plan_ctx, "expr")?,
         ))),
         ExprType::Between(between) => Ok(Expr::Between(Between::new(
             Box::new(parse_required_expr(
                 between.expr.as_deref(),
                 registry,
+                plan_ctx,
                 "expr",
             )?),
             between.negated,
             Box::new(parse_required_expr(
                 between.low.as_deref(),
                 registry,
+                plan_ctx,
                 "expr",
             )?),
             Box::new(parse_required_expr(
                 between.high.as_deref(),
                 registry,
+                plan_ctx,
                 "expr",
             )?),
         ))),
         ExprType::Like(like) => Ok(Expr::Like(Like::new(
             like.negated,
-            Box::new(parse_required_expr(like.expr.as_deref(), registry, "expr")?),
+            Box::new(parse_required_expr(
+                like.expr.as_deref(),
+                registry,
+                plan_ctx,
+                "expr",
+            )?),
             Box::new(parse_required_expr(
                 like.pattern.as_deref(),
                 registry,
+                plan_ctx,
                 "pattern",
             )?),
             parse_escape_char(&like.escape_char)?,
         ))),
         ExprType::Ilike(like) => Ok(Expr::ILike(Like::new(
             like.negated,
-            Box::new(parse_required_expr(like.expr.as_deref(), registry, "expr")?),
+            Box::new(parse_required_expr(
+                like.expr.as_deref(),
+                registry,
+                plan_ctx,
+                "expr",
+            )?),
             Box::new(parse_required_expr(
                 like.pattern.as_deref(),
                 registry,
+                plan_ctx,
                 "pattern",
             )?),
             parse_escape_char(&like.escape_char)?,
         ))),
         ExprType::SimilarTo(like) => Ok(Expr::SimilarTo(Like::new(
             like.negated,
-            Box::new(parse_required_expr(like.expr.as_deref(), registry, "expr")?),
+            Box::new(parse_required_expr(
+                like.expr.as_deref(),
+                registry,
+                plan_ctx,
+                "expr",
+            )?),
             Box::new(parse_required_expr(
                 like.pattern.as_deref(),
                 registry,
+                plan_ctx,
                 "pattern",
             )?),
             parse_escape_char(&like.escape_char)?,
@@ -1089,49 +1310,73 @@ pub fn parse_expr(
                 .when_then_expr
                 .iter()
                 .map(|e| {
-                    let when_expr =
-                        parse_required_expr(e.when_expr.as_ref(), registry, "when_expr")?;
-                    let then_expr =
-                        parse_required_expr(e.then_expr.as_ref(), registry, "then_expr")?;
+                    let when_expr = parse_required_expr(
+                        e.when_expr.as_ref(),
+                        registry,
+                        plan_ctx,
+                        "when_expr",
+                    )?;
+                    let then_expr = parse_required_expr(
+                        e.then_expr.as_ref(),
+                        registry,
+                        plan_ctx,
+                        "then_expr",
+                    )?;
                     Ok((Box::new(when_expr), Box::new(then_expr)))
                 })
                 .collect::<Result<Vec<(Box<Expr>, Box<Expr>)>, Error>>()?;
             Ok(Expr::Case(Case::new(
-                parse_optional_expr(case.expr.as_deref(), registry)?.map(Box::new),
+                parse_optional_expr(case.expr.as_deref(), registry, plan_ctx)?
+                    .map(Box::new),
                 when_then_expr,
-                parse_optional_expr(case.else_expr.as_deref(), registry)?.map(Box::new),
+                parse_optional_expr(case.else_expr.as_deref(), registry, plan_ctx)?
+                    .map(Box::new),
             )))
         }
         ExprType::Cast(cast) => {
-            let expr =
-                Box::new(parse_required_expr(cast.expr.as_deref(), registry, "expr")?);
+            let expr = Box::new(parse_required_expr(
+                cast.expr.as_deref(),
+                registry,
+                plan_ctx,
+                "expr",
+            )?);
             let data_type = cast.arrow_type.as_ref().required("arrow_type")?;
             Ok(Expr::Cast(Cast::new(expr, data_type)))
         }
         ExprType::TryCast(cast) => {
-            let expr =
-                Box::new(parse_required_expr(cast.expr.as_deref(), registry, "expr")?);
+            let expr = Box::new(parse_required_expr(
+                cast.expr.as_deref(),
+                registry,
+                plan_ctx,
+                "expr",
+            )?);
             let data_type = cast.arrow_type.as_ref().required("arrow_type")?;
             Ok(Expr::TryCast(TryCast::new(expr, data_type)))
         }
         ExprType::Sort(sort) => Ok(Expr::Sort(Sort::new(
-            Box::new(parse_required_expr(sort.expr.as_deref(), registry, "expr")?),
+            Box::new(parse_required_expr(
+                sort.expr.as_deref(),
+                registry,
+                plan_ctx,
+                "expr",
+            )?),
             sort.asc,
             sort.nulls_first,
         ))),
         ExprType::Negative(negative) => Ok(Expr::Negative(Box::new(
-            parse_required_expr(negative.expr.as_deref(), registry, "expr")?,
+            parse_required_expr(negative.expr.as_deref(), registry, plan_ctx, "expr")?,
         ))),
         ExprType::InList(in_list) => Ok(Expr::InList(InList::new(
             Box::new(parse_required_expr(
                 in_list.expr.as_deref(),
                 registry,
+                plan_ctx,
                 "expr",
             )?),
             in_list
                 .list
                 .iter()
-                .map(|expr| parse_expr(expr, registry))
+                .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                 .collect::<Result<Vec<_>, _>>()?,
             in_list.negated,
         ))),
@@ -1142,224 +1387,365 @@ pub fn parse_expr(
             let args = &expr.args;
 
             match scalar_function {
-                ScalarFunction::Asin => Ok(asin(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Acos => Ok(acos(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Asinh => Ok(asinh(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Acosh => Ok(acosh(parse_expr(&args[0], registry)?)),
+                ScalarFunction::Asin => {
+                    Ok(asin(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Acos => {
+                    Ok(acos(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Asinh => {
+                    Ok(asinh(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Acosh => {
+                    Ok(acosh(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
                 ScalarFunction::Array => Ok(array(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::ArrayAppend => Ok(array_append(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ArrayConcat => Ok(array_concat(
+                    args.to_owned()
+                        .iter()
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::ArrayContains => Ok(array_contains(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ArrayDims => {
+                    Ok(array_dims(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::ArrayLength => Ok(array_length(
+                    args.to_owned()
+                        .iter()
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::ArrayPosition => Ok(array_position(
+                    args.to_owned()
+                        .iter()
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
-                ScalarFunction::Sqrt => Ok(sqrt(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Cbrt => Ok(cbrt(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Sin => Ok(sin(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Cos => Ok(cos(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Tan => Ok(tan(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Atan => Ok(atan(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Sinh => Ok(sinh(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Cosh => Ok(cosh(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Tanh => Ok(tanh(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Atanh => Ok(atanh(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Exp => Ok(exp(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Degrees => Ok(degrees(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Radians => Ok(radians(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Log2 => Ok(log2(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Ln => Ok(ln(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Log10 => Ok(log10(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Floor => Ok(floor(parse_expr(&args[0], registry)?)),
+                ScalarFunction::ArrayPositions => Ok(array_positions(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ArrayPrepend => Ok(array_prepend(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ArrayRemove => Ok(array_remove(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ArrayReplace => Ok(array_replace(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                    parse_expr_impl(&args[2], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ArraySlice => Ok(array_slice(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                    parse_expr_impl(&args[2], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ArrayToString => Ok(array_to_string(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                )),
+                ScalarFunction::Cardinality => {
+                    Ok(cardinality(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Flatten => {
+                    Ok(flatten(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Sqrt => {
+                    Ok(sqrt(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Cbrt => {
+                    Ok(cbrt(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Sin => {
+                    Ok(sin(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Cos => {
+                    Ok(cos(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Tan => {
+                    Ok(tan(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Atan => {
+                    Ok(atan(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Sinh => {
+                    Ok(sinh(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Cosh => {
+                    Ok(cosh(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Tanh => {
+                    Ok(tanh(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Atanh => {
+                    Ok(atanh(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Exp => {
+                    Ok(exp(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Degrees => {
+                    Ok(degrees(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Radians => {
+                    Ok(radians(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Log2 => {
+                    Ok(log2(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Ln => {
+                    Ok(ln(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Log10 => {
+                    Ok(log10(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Floor => {
+                    Ok(floor(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
                 ScalarFunction::Factorial => {
-                    Ok(factorial(parse_expr(&args[0], registry)?))
+                    Ok(factorial(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Ceil => {
+                    Ok(ceil(parse_expr_impl(&args[0], registry, plan_ctx)?))
                 }
-                ScalarFunction::Ceil => Ok(ceil(parse_expr(&args[0], registry)?)),
                 ScalarFunction::Round => Ok(round(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
-                ScalarFunction::Trunc => Ok(trunc(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Abs => Ok(abs(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Signum => Ok(signum(parse_expr(&args[0], registry)?)),
+                ScalarFunction::Trunc => {
+                    Ok(trunc(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Abs => {
+                    Ok(abs(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Signum => {
+                    Ok(signum(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
                 ScalarFunction::OctetLength => {
-                    Ok(octet_length(parse_expr(&args[0], registry)?))
+                    Ok(octet_length(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Lower => {
+                    Ok(lower(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Upper => {
+                    Ok(upper(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Trim => {
+                    Ok(trim(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Ltrim => {
+                    Ok(ltrim(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Rtrim => {
+                    Ok(rtrim(parse_expr_impl(&args[0], registry, plan_ctx)?))
                 }
-                ScalarFunction::Lower => Ok(lower(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Upper => Ok(upper(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Trim => Ok(trim(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Ltrim => Ok(ltrim(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Rtrim => Ok(rtrim(parse_expr(&args[0], registry)?)),
                 ScalarFunction::DatePart => Ok(date_part(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::DateTrunc => Ok(date_trunc(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::DateBin => Ok(date_bin(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
-                    parse_expr(&args[2], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                    parse_expr_impl(&args[2], registry, plan_ctx)?,
                 )),
-                ScalarFunction::Sha224 => Ok(sha224(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Sha256 => Ok(sha256(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Sha384 => Ok(sha384(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Sha512 => Ok(sha512(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Md5 => Ok(md5(parse_expr(&args[0], registry)?)),
+                ScalarFunction::Sha224 => {
+                    Ok(sha224(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Sha256 => {
+                    Ok(sha256(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Sha384 => {
+                    Ok(sha384(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Sha512 => {
+                    Ok(sha512(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::Md5 => {
+                    Ok(md5(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
                 ScalarFunction::NullIf => Ok(nullif(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Digest => Ok(digest(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
-                ScalarFunction::Ascii => Ok(ascii(parse_expr(&args[0], registry)?)),
+                ScalarFunction::Ascii => {
+                    Ok(ascii(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
                 ScalarFunction::BitLength => {
-                    Ok(bit_length(parse_expr(&args[0], registry)?))
+                    Ok(bit_length(parse_expr_impl(&args[0], registry, plan_ctx)?))
                 }
-                ScalarFunction::CharacterLength => {
-                    Ok(character_length(parse_expr(&args[0], registry)?))
+                ScalarFunction::CharacterLength => Ok(character_length(parse_expr_impl(
+                    &args[0], registry, plan_ctx,
+                )?)),
+                ScalarFunction::Chr => {
+                    Ok(chr(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
+                ScalarFunction::InitCap => {
+                    Ok(ascii(parse_expr_impl(&args[0], registry, plan_ctx)?))
                 }
-                ScalarFunction::Chr => Ok(chr(parse_expr(&args[0], registry)?)),
-                ScalarFunction::InitCap => Ok(ascii(parse_expr(&args[0], registry)?)),
                 ScalarFunction::Gcd => Ok(gcd(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Lcm => Ok(lcm(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Left => Ok(left(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Random => Ok(random()),
                 ScalarFunction::Uuid => Ok(uuid()),
                 ScalarFunction::Repeat => Ok(repeat(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Replace => Ok(replace(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
-                    parse_expr(&args[2], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                    parse_expr_impl(&args[2], registry, plan_ctx)?,
                 )),
-                ScalarFunction::Reverse => Ok(reverse(parse_expr(&args[0], registry)?)),
+                ScalarFunction::Reverse => {
+                    Ok(reverse(parse_expr_impl(&args[0], registry, plan_ctx)?))
+                }
                 ScalarFunction::Right => Ok(right(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Concat => Ok(concat_expr(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
                 ScalarFunction::ConcatWithSeparator => Ok(concat_ws_expr(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
                 ScalarFunction::Lpad => Ok(lpad(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
                 ScalarFunction::Rpad => Ok(rpad(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
                 ScalarFunction::RegexpReplace => Ok(regexp_replace(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
                 ScalarFunction::RegexpMatch => Ok(regexp_match(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
                 ScalarFunction::Btrim => Ok(btrim(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
                 ScalarFunction::SplitPart => Ok(split_part(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
-                    parse_expr(&args[2], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                    parse_expr_impl(&args[2], registry, plan_ctx)?,
                 )),
                 ScalarFunction::StartsWith => Ok(starts_with(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Strpos => Ok(strpos(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Substr => {
                     if args.len() > 2 {
                         assert_eq!(args.len(), 3);
                         Ok(substring(
-                            parse_expr(&args[0], registry)?,
-                            parse_expr(&args[1], registry)?,
-                            parse_expr(&args[2], registry)?,
+                            parse_expr_impl(&args[0], registry, plan_ctx)?,
+                            parse_expr_impl(&args[1], registry, plan_ctx)?,
+                            parse_expr_impl(&args[2], registry, plan_ctx)?,
                         ))
                     } else {
                         Ok(substr(
-                            parse_expr(&args[0], registry)?,
-                            parse_expr(&args[1], registry)?,
+                            parse_expr_impl(&args[0], registry, plan_ctx)?,
+                            parse_expr_impl(&args[1], registry, plan_ctx)?,
                         ))
                     }
                 }
-                ScalarFunction::ToHex => Ok(to_hex(parse_expr(&args[0], registry)?)),
-                ScalarFunction::ToTimestampMillis => {
-                    Ok(to_timestamp_millis(parse_expr(&args[0], registry)?))
-                }
-                ScalarFunction::ToTimestampMicros => {
-                    Ok(to_timestamp_micros(parse_expr(&args[0], registry)?))
-                }
-                ScalarFunction::ToTimestampSeconds => {
-                    Ok(to_timestamp_seconds(parse_expr(&args[0], registry)?))
+                ScalarFunction::ToHex => {
+                    Ok(to_hex(parse_expr_impl(&args[0], registry, plan_ctx)?))
                 }
+                ScalarFunction::ToTimestampMillis => Ok(to_timestamp_millis(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ToTimestampMicros => Ok(to_timestamp_micros(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                )),
+                ScalarFunction::ToTimestampSeconds => Ok(to_timestamp_seconds(
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                )),
                 ScalarFunction::Now => Ok(now()),
                 ScalarFunction::Translate => Ok(translate(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
-                    parse_expr(&args[2], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
+                    parse_expr_impl(&args[2], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Coalesce => Ok(coalesce(
                     args.to_owned()
                         .iter()
-                        .map(|expr| parse_expr(expr, registry))
+                        .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
                 ScalarFunction::Pi => Ok(pi()),
                 ScalarFunction::Power => Ok(power(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 ScalarFunction::Log => Ok(log(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
-                ScalarFunction::FromUnixtime => {
-                    Ok(from_unixtime(parse_expr(&args[0], registry)?))
-                }
+                ScalarFunction::FromUnixtime => Ok(from_unixtime(parse_expr_impl(
+                    &args[0], registry, plan_ctx,
+                )?)),
                 ScalarFunction::Atan2 => Ok(atan2(
-                    parse_expr(&args[0], registry)?,
-                    parse_expr(&args[1], registry)?,
+                    parse_expr_impl(&args[0], registry, plan_ctx)?,
+                    parse_expr_impl(&args[1], registry, plan_ctx)?,
                 )),
                 _ => Err(proto_error(
                     "Protobuf deserialization error: Unsupported scalar function",
@@ -1371,7 +1757,7 @@ pub fn parse_expr(
             Ok(Expr::ScalarUDF(expr::ScalarUDF::new(
                 scalar_fn,
                 args.iter()
-                    .map(|expr| parse_expr(expr, registry))
+                    .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                     .collect::<Result<Vec<_>, Error>>()?,
             )))
         }
@@ -1382,9 +1768,10 @@ pub fn parse_expr(
                 agg_fn,
                 pb.args
                     .iter()
-                    .map(|expr| parse_expr(expr, registry))
+                    .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                     .collect::<Result<Vec<_>, Error>>()?,
-                parse_optional_expr(pb.filter.as_deref(), registry)?.map(Box::new),
+                parse_optional_expr(pb.filter.as_deref(), registry, plan_ctx)?
+                    .map(Box::new),
             )))
         }
 
@@ -1395,7 +1782,7 @@ pub fn parse_expr(
                         expr_list
                             .expr
                             .iter()
-                            .map(|expr| parse_expr(expr, registry))
+                            .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                             .collect::<Result<Vec<_>, Error>>()
                     })
                     .collect::<Result<Vec<_>, Error>>()?,
@@ -1403,16 +1790,46 @@ pub fn parse_expr(
         }
         ExprType::Cube(CubeNode { expr }) => Ok(Expr::GroupingSet(GroupingSet::Cube(
             expr.iter()
-                .map(|expr| parse_expr(expr, registry))
+                .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                 .collect::<Result<Vec<_>, Error>>()?,
         ))),
         ExprType::Rollup(RollupNode { expr }) => {
             Ok(Expr::GroupingSet(GroupingSet::Rollup(
                 expr.iter()
-                    .map(|expr| parse_expr(expr, registry))
+                    .map(|expr| parse_expr_impl(expr, registry, plan_ctx))
                     .collect::<Result<Vec<_>, Error>>()?,
             )))
         }
+        ExprType::Exists(exists) => Ok(Expr::Exists(Exists::new(
+            parse_required_subquery(exists.subquery.as_deref(), plan_ctx)?,
+            exists.negated,
+        ))),
+        ExprType::InSubquery(in_subquery) => Ok(Expr::InSubquery(InSubquery::new(
+            Box::new(parse_required_expr(
+                in_subquery.expr.as_deref(),
+                registry,
+                plan_ctx,
+                "expr",
+            )?),
+            parse_required_subquery(in_subquery.subquery.as_deref(), plan_ctx)?,
+            in_subquery.negated,
+        ))),
+        ExprType::ScalarSubquery(subquery) => Ok(Expr::ScalarSubquery(
+            parse_required_subquery(Some(subquery.as_ref()), plan_ctx)?,
+        )),
+        ExprType::OuterReferenceColumn(outer_ref) => {
+            let data_type: DataType = outer_ref
+                .data_type
+                .as_ref()
+                .ok_or_else(|| Error::required("data_type"))?
+                .try_into()?;
+            let column: Column = outer_ref
+                .column
+                .as_ref()
+                .ok_or_else(|| Error::required("column"))?
+                .into();
+            Ok(Expr::OuterReferenceColumn(data_type, column))
+        }
         ExprType::Placeholder(PlaceholderNode { id, data_type }) => match data_type {
             None => Ok(Expr::Placeholder(Placeholder::new(id.clone(), None))),
             Some(data_type) => Ok(Expr::Placeholder(Placeholder::new(
@@ -1477,9 +1894,10 @@ pub fn from_proto_binary_op(op: &str) -> Result<Operator, Error> {
 fn parse_optional_expr(
     p: Option<&protobuf::LogicalExprNode>,
     registry: &dyn FunctionRegistry,
+    plan_ctx: Option<PlanContext>,
 ) -> Result<Option<Expr>, Error> {
     match p {
-        Some(expr) => parse_expr(expr, registry).map(Some),
+        Some(expr) => parse_expr_impl(expr, registry, plan_ctx).map(Some),
         None => Ok(None),
     }
 }
@@ -1487,14 +1905,33 @@ fn parse_optional_expr(
 fn parse_required_expr(
     p: Option<&protobuf::LogicalExprNode>,
     registry: &dyn FunctionRegistry,
+    plan_ctx: Option<PlanContext>,
     field: impl Into<String>,
 ) -> Result<Expr, Error> {
     match p {
-        Some(expr) => parse_expr(expr, registry),
+        Some(expr) => parse_expr_impl(expr, registry, plan_ctx),
         None => Err(Error::required(field)),
     }
 }
 
+fn parse_required_subquery(
+    p: Option<&protobuf::SubqueryNode>,
+    plan_ctx: Option<PlanContext>,
+) -> Result<Subquery, Error> {
+    let PlanContext {
+        ctx,
+        extension_codec,
+    } = plan_ctx.ok_or_else(|| {
+        proto_error(
+            "Subquery expressions can only be deserialized as part of a logical plan",
+        )
+    })?;
+    match p {
+        Some(subquery) => parse_subquery(subquery, ctx, extension_codec),
+        None => Err(Error::required("subquery")),
+    }
+}
+
 fn proto_error<S: Into<String>>(message: S) -> Error {
     Error::General(message.into())
 }
@@ -39,16 +39,18 @@ use datafusion::{
     prelude::SessionContext,
 };
 use datafusion_common::{
-    context, parsers::CompressionTypeVariant, DataFusionError, OwnedTableReference,
-    Result,
+    context, parsers::CompressionTypeVariant, Column, DFSchemaRef, DataFusionError,
+    OwnedTableReference, Result,
 };
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::{
     logical_plan::{
-        builder::project, Aggregate, CreateCatalog, CreateCatalogSchema,
-        CreateExternalTable, CreateView, CrossJoin, Distinct, EmptyRelation, Extension,
-        Join, JoinConstraint, Limit, Prepare, Projection, Repartition, Sort,
-        SubqueryAlias, TableScan, Values, Window,
+        builder::project, Aggregate, CopyTo, CreateCatalog, CreateCatalogSchema,
+        CreateExternalTable, CreateMemoryTable, CreateView, CrossJoin, DescribeTable,
+        Distinct, DmlStatement, DropCatalogSchema, DropTable, DropView, EmptyRelation,
+        Extension, Join, JoinConstraint, Limit, Prepare, Projection, Repartition,
+        SetVariable, Sort, Statement, SubqueryAlias, TableScan, TransactionEnd,
+        TransactionStart, Unnest, Values, Window,
     },
     Expr, LogicalPlan, LogicalPlanBuilder,
 };
@@ -188,6 +190,19 @@ fn from_owned_table_reference(
     Ok(table_ref.clone().try_into()?)
 }
 
+fn from_df_schema(
+    schema: Option<&protobuf::DfSchema>,
+    error_context: &str,
+) -> Result<DFSchemaRef> {
+    let schema = schema.ok_or_else(|| {
+        DataFusionError::Internal(format!(
+            "Protobuf deserialization error, {error_context} was missing required field schema."
+        ))
+    })?;
+
+    Ok(schema.clone().try_into()?)
+}
+
 impl AsLogicalPlan for LogicalPlanNode {
     fn try_decode(buf: &[u8]) -> Result<Self>
     where
@@ -235,7 +250,13 @@ impl AsLogicalPlan for LogicalPlanNode {
                         .chunks_exact(n_cols)
                         .map(|r| {
                             r.iter()
-                                .map(|expr| from_proto::parse_expr(expr, ctx))
+                                .map(|expr| {
+                                    from_proto::parse_expr_in_plan(
+                                        expr,
+                                        ctx,
+                                        extension_codec,
+                                    )
+                                })
                                 .collect::<Result<Vec<_>, from_proto::Error>>()
                         })
                         .collect::<Result<Vec<_>, _>>()
@@ -249,7 +270,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let expr: Vec<Expr> = projection
                     .expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
 
                 let new_proj = project(input, expr)?;
@@ -271,7 +294,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let expr: Expr = selection
                     .expr
                     .as_ref()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .transpose()?
                     .ok_or_else(|| {
                         DataFusionError::Internal("expression required".to_string())
@@ -285,7 +310,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let window_expr = window
                     .window_expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<Expr>, _>>()?;
                 LogicalPlanBuilder::from(input).window(window_expr)?.build()
             }
@@ -295,12 +322,16 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let group_expr = aggregate
                     .group_expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<Expr>, _>>()?;
                 let aggr_expr = aggregate
                     .aggr_expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<Expr>, _>>()?;
                 LogicalPlanBuilder::from(input)
                     .aggregate(group_expr, aggr_expr)?
@@ -322,13 +353,17 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let filters = scan
                     .filters
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
 
                 let file_sort_order = scan
                     .file_sort_order
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
 
                 // Protobuf doesn't distinguish between "not present"
@@ -422,7 +457,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let filters = scan
                     .filters
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
                 let provider = extension_codec.try_decode_table_provider(
                     &scan.custom_table_data,
@@ -447,9 +484,16 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let sort_expr: Vec<Expr> = sort
                     .expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<Expr>, _>>()?;
-                LogicalPlanBuilder::from(input).sort(sort_expr)?.build()
+                let builder = LogicalPlanBuilder::from(input);
+                if sort.preserve_partitioning {
+                    builder.sort_within_partitions(sort_expr)?.build()
+                } else {
+                    builder.sort(sort_expr)?.build()
+                }
             }
             LogicalPlanType::Repartition(repartition) => {
                 use datafusion::logical_expr::Partitioning;
@@ -469,7 +513,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                     }) => Partitioning::Hash(
                         pb_hash_expr
                             .iter()
-                            .map(|expr| from_proto::parse_expr(expr, ctx))
+                            .map(|expr| {
+                                from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                            })
                             .collect::<Result<Vec<_>, _>>()?,
                         partition_count as usize,
                     ),
@@ -508,7 +554,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let order_exprs = create_extern_table
                     .order_exprs
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<Expr>, _>>()?;
 
                 Ok(LogicalPlan::Ddl(DdlStatement::CreateExternalTable(CreateExternalTable {
@@ -622,12 +670,16 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let left_keys: Vec<Expr> = join
                     .left_join_key
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
                 let right_keys: Vec<Expr> = join
                     .right_join_key
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
                 let join_type =
                     protobuf::JoinType::from_i32(join.join_type).ok_or_else(|| {
@@ -648,7 +700,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let filter: Option<Expr> = join
                     .filter
                     .as_ref()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| {
+                        from_proto::parse_expr_in_plan(expr, ctx, extension_codec)
+                    })
                     .map_or(Ok(None), |v| v.map(Some))?;
 
                 let builder = LogicalPlanBuilder::from(into_logical_plan!(
@@ -768,6 +822,196 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .prepare(prepare.name.clone(), data_types)?
                     .build()
             }
+            LogicalPlanType::Dml(dml) => {
+                let input: LogicalPlan =
+                    into_logical_plan!(dml.input, ctx, extension_codec)?;
+                let op = protobuf::WriteOp::from_i32(dml.op).ok_or_else(|| {
+                    proto_error(format!(
+                        "Received a DmlNode message with unknown WriteOp {}",
+                        dml.op
+                    ))
+                })?;
+                Ok(LogicalPlan::Dml(DmlStatement::new(
+                    from_owned_table_reference(dml.table_name.as_ref(), "Dml")?,
+                    from_df_schema(dml.table_schema.as_ref(), "Dml")?,
+                    op.into(),
+                    Arc::new(input),
+                )))
+            }
+            LogicalPlanType::DropTable(drop_table) => {
+                Ok(LogicalPlan::Ddl(DdlStatement::DropTable(DropTable {
+                    name: from_owned_table_reference(
+                        drop_table.name.as_ref(),
+                        "DropTable",
+                    )?,
+                    if_exists: drop_table.if_exists,
+                    schema: from_df_schema(drop_table.schema.as_ref(), "DropTable")?,
+                })))
+            }
+            LogicalPlanType::DropView(drop_view) => {
+                Ok(LogicalPlan::Ddl(DdlStatement::DropView(DropView {
+                    name: from_owned_table_reference(
+                        drop_view.name.as_ref(),
+                        "DropView",
+                    )?,
+                    if_exists: drop_view.if_exists,
+                    schema: from_df_schema(drop_view.schema.as_ref(), "DropView")?,
+                })))
+            }
+            LogicalPlanType::DropCatalogSchema(drop_catalog_schema) => {
+                let name = drop_catalog_schema.name.clone().ok_or_else(|| {
+                    DataFusionError::Internal(String::from(
+                        "Protobuf deserialization error, DropCatalogSchema was missing required field name.",
+                    ))
+                })?;
+
+                Ok(LogicalPlan::Ddl(DdlStatement::DropCatalogSchema(
+                    DropCatalogSchema {
+                        name: name.try_into()?,
+                        if_exists: drop_catalog_schema.if_exists,
+                        cascade: drop_catalog_schema.cascade,
+                        schema: from_df_schema(
+                            drop_catalog_schema.schema.as_ref(),
+                            "DropCatalogSchema",
+                        )?,
+                    },
+                )))
+            }
+            LogicalPlanType::CreateMemoryTable(create_memory_table) => {
+                let input: LogicalPlan =
+                    into_logical_plan!(create_memory_table.input, ctx, extension_codec)?;
+
+                Ok(LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(
+                    CreateMemoryTable {
+                        name: from_owned_table_reference(
+                            create_memory_table.name.as_ref(),
+                            "CreateMemoryTable",
+                        )?,
+                        primary_key: create_memory_table
+                            .primary_key
+                            .iter()
+                            .map(|c| c.into())
+                            .collect(),
+                        input: Arc::new(input),
+                        if_not_exists: create_memory_table.if_not_exists,
+                        or_replace: create_memory_table.or_replace,
+                    },
+                )))
+            }
+            LogicalPlanType::DescribeTable(describe_table) => {
+                let schema: Schema = convert_required!(describe_table.schema)?;
+                Ok(LogicalPlan::DescribeTable(DescribeTable {
+                    schema: Arc::new(schema),
+                    dummy_schema: from_df_schema(
+                        describe_table.dummy_schema.as_ref(),
+                        "DescribeTable",
+                    )?,
+                }))
+            }
+            LogicalPlanType::TransactionStart(transaction_start) => {
+                let access_mode = protobuf::TransactionAccessMode::from_i32(
+                    transaction_start.access_mode,
+                )
+                .ok_or_else(|| {
+                    proto_error(format!(
+                        "Received a TransactionStartNode message with unknown TransactionAccessMode {}",
+                        transaction_start.access_mode
+                    ))
+                })?;
+                let isolation_level = protobuf::TransactionIsolationLevel::from_i32(
+                    transaction_start.isolation_level,
+                )
+                .ok_or_else(|| {
+                    proto_error(format!(
+                        "Received a TransactionStartNode message with unknown TransactionIsolationLevel {}",
+                        transaction_start.isolation_level
+                    ))
+                })?;
+
+                Ok(LogicalPlan::Statement(Statement::TransactionStart(
+                    TransactionStart {
+                        access_mode: access_mode.into(),
+                        isolation_level: isolation_level.into(),
+                        schema: from_df_schema(
+                            transaction_start.schema.as_ref(),
+                            "TransactionStart",
+                        )?,
+                    },
+                )))
+            }
+            LogicalPlanType::TransactionEnd(transaction_end) => {
+                let conclusion = protobuf::TransactionConclusion::from_i32(
+                    transaction_end.conclusion,
+                )
+                .ok_or_else(|| {
+                    proto_error(format!(
+                        "Received a TransactionEndNode message with unknown TransactionConclusion {}",
+                        transaction_end.conclusion
+                    ))
+                })?;
+
+                Ok(LogicalPlan::Statement(Statement::TransactionEnd(
+                    TransactionEnd {
+                        conclusion: conclusion.into(),
+                        chain: transaction_end.chain,
+                        schema: from_df_schema(
+                            transaction_end.schema.as_ref(),
+                            "TransactionEnd",
+                        )?,
+                    },
+                )))
+            }
+            LogicalPlanType::SetVariable(set_variable) => Ok(LogicalPlan::Statement(
+                Statement::SetVariable(SetVariable {
+                    variable: set_variable.variable.clone(),
+                    value: set_variable.value.clone(),
+                    schema: from_df_schema(set_variable.schema.as_ref(), "SetVariable")?,
+                }),
+            )),
+            LogicalPlanType::Unnest(unnest) => {
+                let input: LogicalPlan =
+                    into_logical_plan!(unnest.input, ctx, extension_codec)?;
+                let column: Column = unnest
+                    .column
+                    .as_ref()
+                    .ok_or_else(|| {
+                        DataFusionError::Internal(String::from(
+                            "Protobuf deserialization error, Unnest was missing required field column.",
+                        ))
+                    })?
+                    .into();
+                LogicalPlanBuilder::from(input)
+                    .unnest_column(column)?
+                    .build()
+            }
+            LogicalPlanType::Subquery(subquery) => Ok(LogicalPlan::Subquery(
+                from_proto::parse_subquery(subquery, ctx, extension_codec)?,
+            )),
+            LogicalPlanType::CopyTo(copy) => {
+                let input: LogicalPlan =
+                    into_logical_plan!(copy.input, ctx, extension_codec)?;
+                let delimiter = copy.delimiter.chars().next().ok_or_else(|| {
+                    proto_error("Received a CopyToNode message with an empty delimiter")
+                })?;
+                let file_compression_type =
+                    CompressionTypeVariant::from_str(&copy.file_compression_type)
+                        .map_err(|_| {
+                            DataFusionError::NotImplemented(format!(
+                                "Unsupported file compression type {}",
+                                copy.file_compression_type
+                            ))
+                        })?;
+                Ok(LogicalPlan::Copy(CopyTo {
+                    input: Arc::new(input),
+                    output_url: copy.output_url.clone(),
+                    file_type: copy.file_type.clone(),
+                    has_header: copy.has_header,
+                    delimiter,
+                    file_compression_type,
+                    partition_by: copy.partition_by.clone(),
+                    options: copy.options.clone(),
+                }))
+            }
         }
     }
 
@@ -788,7 +1032,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let values_list = values
                     .iter()
                     .flatten()
-                    .map(|v| v.try_into())
+                    .map(|v| to_proto::serialize_expr_in_plan(v, extension_codec))
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(protobuf::LogicalPlanNode {
                     logical_plan_type: Some(LogicalPlanType::Values(
@@ -826,7 +1070,9 @@ impl AsLogicalPlan for LogicalPlanNode {
 
                 let filters: Vec<protobuf::LogicalExprNode> = filters
                     .iter()
-                    .map(|filter| filter.try_into())
+                    .map(|filter| {
+                        to_proto::serialize_expr_in_plan(filter, extension_codec)
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
 
                 if let Some(listing_table) = source.downcast_ref::<ListingTable>() {
@@ -848,15 +1094,18 @@ impl AsLogicalPlan for LogicalPlanNode {
                     };
 
                     let options = listing_table.options();
-                    let file_sort_order =
-                        if let Some(file_sort_order) = &options.file_sort_order {
-                            file_sort_order
-                                .iter()
-                                .map(|expr| expr.try_into())
-                                .collect::<Result<Vec<protobuf::LogicalExprNode>, _>>()?
-                        } else {
-                            vec![]
-                        };
+                    let file_sort_order = if let Some(file_sort_order) =
+                        &options.file_sort_order
+                    {
+                        file_sort_order
+                            .iter()
+                            .map(|expr| {
+                                to_proto::serialize_expr_in_plan(expr, extension_codec)
+                            })
+                            .collect::<Result<Vec<protobuf::LogicalExprNode>, _>>()?
+                    } else {
+                        vec![]
+                    };
 
                     Ok(protobuf::LogicalPlanNode {
                         logical_plan_type: Some(LogicalPlanType::ListingScan(
@@ -933,7 +1182,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                             )),
                             expr: expr
                                 .iter()
-                                .map(|expr| expr.try_into())
+                                .map(|expr| {
+                                    to_proto::serialize_expr_in_plan(
+                                        expr,
+                                        extension_codec,
+                                    )
+                                })
                                 .collect::<Result<Vec<_>, to_proto::Error>>()?,
                             optional_alias: None,
                         },
@@ -950,7 +1204,10 @@ impl AsLogicalPlan for LogicalPlanNode {
                     logical_plan_type: Some(LogicalPlanType::Selection(Box::new(
                         protobuf::SelectionNode {
                             input: Some(Box::new(input)),
-                            expr: Some((&filter.predicate).try_into()?),
+                            expr: Some(Box::new(to_proto::serialize_expr_in_plan(
+                                &filter.predicate,
+                                extension_codec,
+                            )?)),
                         },
                     ))),
                 })
@@ -983,7 +1240,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                             input: Some(Box::new(input)),
                             window_expr: window_expr
                                 .iter()
-                                .map(|expr| expr.try_into())
+                                .map(|expr| {
+                                    to_proto::serialize_expr_in_plan(
+                                        expr,
+                                        extension_codec,
+                                    )
+                                })
                                 .collect::<Result<Vec<_>, _>>()?,
                         },
                     ))),
@@ -1006,11 +1268,21 @@ impl AsLogicalPlan for LogicalPlanNode {
                             input: Some(Box::new(input)),
                             group_expr: group_expr
                                 .iter()
-                                .map(|expr| expr.try_into())
+                                .map(|expr| {
+                                    to_proto::serialize_expr_in_plan(
+                                        expr,
+                                        extension_codec,
+                                    )
+                                })
                                 .collect::<Result<Vec<_>, _>>()?,
                             aggr_expr: aggr_expr
                                 .iter()
-                                .map(|expr| expr.try_into())
+                                .map(|expr| {
+                                    to_proto::serialize_expr_in_plan(
+                                        expr,
+                                        extension_codec,
+                                    )
+                                })
                                 .collect::<Result<Vec<_>, _>>()?,
                         },
                     ))),
@@ -1038,7 +1310,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                     )?;
                 let (left_join_key, right_join_key) = on
                     .iter()
-                    .map(|(l, r)| Ok((l.try_into()?, r.try_into()?)))
+                    .map(|(l, r)| {
+                        Ok((
+                            to_proto::serialize_expr_in_plan(l, extension_codec)?,
+                            to_proto::serialize_expr_in_plan(r, extension_codec)?,
+                        ))
+                    })
                     .collect::<Result<Vec<_>, to_proto::Error>>()?
                     .into_iter()
                     .unzip();
@@ -1047,7 +1324,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                     join_constraint.to_owned().into();
                 let filter = filter
                     .as_ref()
-                    .map(|e| e.try_into())
+                    .map(|e| {
+                        to_proto::serialize_expr_in_plan(e, extension_codec).map(Box::new)
+                    })
                     .map_or(Ok(None), |v| v.map(Some))?;
                 Ok(protobuf::LogicalPlanNode {
                     logical_plan_type: Some(LogicalPlanType::Join(Box::new(
@@ -1064,9 +1343,11 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
-            LogicalPlan::Subquery(_) => Err(DataFusionError::NotImplemented(
-                "LogicalPlan serde is not yet implemented for subqueries".to_string(),
-            )),
+            LogicalPlan::Subquery(subquery) => Ok(protobuf::LogicalPlanNode {
+                logical_plan_type: Some(LogicalPlanType::Subquery(Box::new(
+                    to_proto::serialize_subquery(subquery, extension_codec)?,
+                ))),
+            }),
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, alias, .. }) => {
                 let input: protobuf::LogicalPlanNode =
                     protobuf::LogicalPlanNode::try_from_logical_plan(
@@ -1098,7 +1379,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
-            LogicalPlan::Sort(Sort { input, expr, fetch }) => {
+            LogicalPlan::Sort(Sort {
+                input,
+                expr,
+                fetch,
+                preserve_partitioning,
+            }) => {
                 let input: protobuf::LogicalPlanNode =
                     protobuf::LogicalPlanNode::try_from_logical_plan(
                         input.as_ref(),
@@ -1106,7 +1392,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                     )?;
                 let selection_expr: Vec<protobuf::LogicalExprNode> = expr
                     .iter()
-                    .map(|expr| expr.try_into())
+                    .map(|expr| to_proto::serialize_expr_in_plan(expr, extension_codec))
                     .collect::<Result<Vec<_>, to_proto::Error>>()?;
                 Ok(protobuf::LogicalPlanNode {
                     logical_plan_type: Some(LogicalPlanType::Sort(Box::new(
@@ -1114,6 +1400,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                             input: Some(Box::new(input)),
                             expr: selection_expr,
                             fetch: fetch.map(|f| f as i64).unwrap_or(-1i64),
+                            preserve_partitioning: *preserve_partitioning,
                         },
                     ))),
                 })
@@ -1138,7 +1425,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                         PartitionMethod::Hash(protobuf::HashRepartition {
                             hash_expr: exprs
                                 .iter()
-                                .map(|expr| expr.try_into())
+                                .map(|expr| {
+                                    to_proto::serialize_expr_in_plan(
+                                        expr,
+                                        extension_codec,
+                                    )
+                                })
                                 .collect::<Result<Vec<_>, to_proto::Error>>()?,
                             partition_count: *partition_count as u64,
                         })
@@ -1199,7 +1491,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                         delimiter: String::from(*delimiter),
                         order_exprs: order_exprs
                             .iter()
-                            .map(|expr| expr.try_into())
+                            .map(|expr| {
+                                to_proto::serialize_expr_in_plan(expr, extension_codec)
+                            })
                             .collect::<Result<Vec<_>, to_proto::Error>>()?,
                         definition: definition.clone().unwrap_or_default(),
                         file_compression_type: file_compression_type.to_string(),
@@ -1360,30 +1654,203 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
-            LogicalPlan::Unnest(_) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for Unnest",
-            )),
-            LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(_)) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for CreateMemoryTable",
-            )),
-            LogicalPlan::Ddl(DdlStatement::DropTable(_)) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for DropTable",
-            )),
-            LogicalPlan::Ddl(DdlStatement::DropView(_)) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for DropView",
-            )),
-            LogicalPlan::Ddl(DdlStatement::DropCatalogSchema(_)) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for DropCatalogSchema",
-            )),
-            LogicalPlan::Statement(_) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for Statement",
-            )),
-            LogicalPlan::Dml(_) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for Dml",
-            )),
-            LogicalPlan::DescribeTable(_) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for DescribeTable",
+            LogicalPlan::Unnest(Unnest { input, column, .. }) => {
+                let input: protobuf::LogicalPlanNode =
+                    protobuf::LogicalPlanNode::try_from_logical_plan(
+                        input.as_ref(),
+                        extension_codec,
+                    )?;
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::Unnest(Box::new(
+                        protobuf::UnnestNode {
+                            input: Some(Box::new(input)),
+                            column: Some(column.into()),
+                        },
+                    ))),
+                })
+            }
+            LogicalPlan::RecursiveQuery(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for RecursiveQuery",
             )),
+            LogicalPlan::Copy(CopyTo {
+                input,
+                output_url,
+                file_type,
+                has_header,
+                delimiter,
+                file_compression_type,
+                partition_by,
+                options,
+            }) => {
+                let input: protobuf::LogicalPlanNode =
+                    protobuf::LogicalPlanNode::try_from_logical_plan(
+                        input.as_ref(),
+                        extension_codec,
+                    )?;
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::CopyTo(Box::new(
+                        protobuf::CopyToNode {
+                            input: Some(Box::new(input)),
+                            output_url: output_url.clone(),
+                            file_type: file_type.clone(),
+                            has_header: *has_header,
+                            delimiter: String::from(*delimiter),
+                            file_compression_type: file_compression_type.to_string(),
+                            partition_by: partition_by.clone(),
+                            options: options.clone(),
+                        },
+                    ))),
+                })
+            }
+            LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(CreateMemoryTable {
+                name,
+                primary_key,
+                input,
+                if_not_exists,
+                or_replace,
+            })) => {
+                let input: protobuf::LogicalPlanNode =
+                    protobuf::LogicalPlanNode::try_from_logical_plan(
+                        input.as_ref(),
+                        extension_codec,
+                    )?;
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::CreateMemoryTable(
+                        Box::new(protobuf::CreateMemoryTableNode {
+                            name: Some(name.clone().into()),
+                            primary_key: primary_key.iter().map(|c| c.into()).collect(),
+                            input: Some(Box::new(input)),
+                            if_not_exists: *if_not_exists,
+                            or_replace: *or_replace,
+                        }),
+                    )),
+                })
+            }
+            LogicalPlan::Ddl(DdlStatement::DropTable(DropTable {
+                name,
+                if_exists,
+                schema,
+            })) => Ok(protobuf::LogicalPlanNode {
+                logical_plan_type: Some(LogicalPlanType::DropTable(
+                    protobuf::DropTableNode {
+                        name: Some(name.clone().into()),
+                        if_exists: *if_exists,
+                        schema: Some(schema.try_into()?),
+                    },
+                )),
+            }),
+            LogicalPlan::Ddl(DdlStatement::DropView(DropView {
+                name,
+                if_exists,
+                schema,
+            })) => Ok(protobuf::LogicalPlanNode {
+                logical_plan_type: Some(LogicalPlanType::DropView(
+                    protobuf::DropViewNode {
+                        name: Some(name.clone().into()),
+                        if_exists: *if_exists,
+                        schema: Some(schema.try_into()?),
+                    },
+                )),
+            }),
+            LogicalPlan::Ddl(DdlStatement::DropCatalogSchema(DropCatalogSchema {
+                name,
+                if_exists,
+                cascade,
+                schema,
+            })) => Ok(protobuf::LogicalPlanNode {
+                logical_plan_type: Some(LogicalPlanType::DropCatalogSchema(
+                    protobuf::DropCatalogSchemaNode {
+                        name: Some(name.clone().into()),
+                        if_exists: *if_exists,
+                        cascade: *cascade,
+                        schema: Some(schema.try_into()?),
+                    },
+                )),
+            }),
+            LogicalPlan::Statement(Statement::TransactionStart(TransactionStart {
+                access_mode,
+                isolation_level,
+                schema,
+            })) => {
+                let access_mode: protobuf::TransactionAccessMode =
+                    access_mode.to_owned().into();
+                let isolation_level: protobuf::TransactionIsolationLevel =
+                    isolation_level.to_owned().into();
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::TransactionStart(
+                        protobuf::TransactionStartNode {
+                            access_mode: access_mode.into(),
+                            isolation_level: isolation_level.into(),
+                            schema: Some(schema.try_into()?),
+                        },
+                    )),
+                })
+            }
+            LogicalPlan::Statement(Statement::TransactionEnd(TransactionEnd {
+                conclusion,
+                chain,
+                schema,
+            })) => {
+                let conclusion: protobuf::TransactionConclusion =
+                    conclusion.to_owned().into();
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::TransactionEnd(
+                        protobuf::TransactionEndNode {
+                            conclusion: conclusion.into(),
+                            chain: *chain,
+                            schema: Some(schema.try_into()?),
+                        },
+                    )),
+                })
+            }
+            LogicalPlan::Statement(Statement::SetVariable(SetVariable {
+                variable,
+                value,
+                schema,
+            })) => Ok(protobuf::LogicalPlanNode {
+                logical_plan_type: Some(LogicalPlanType::SetVariable(
+                    protobuf::SetVariableNode {
+                        variable: variable.clone(),
+                        value: value.clone(),
+                        schema: Some(schema.try_into()?),
+                    },
+                )),
+            }),
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                table_schema,
+                op,
+                input,
+                ..
+            }) => {
+                let input: protobuf::LogicalPlanNode =
+                    protobuf::LogicalPlanNode::try_from_logical_plan(
+                        input.as_ref(),
+                        extension_codec,
+                    )?;
+                let op: protobuf::WriteOp = op.to_owned().into();
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::Dml(Box::new(
+                        protobuf::DmlNode {
+                            table_name: Some(table_name.clone().into()),
+                            table_schema: Some(table_schema.try_into()?),
+                            op: op.into(),
+                            input: Some(Box::new(input)),
+                        },
+                    ))),
+                })
+            }
+            LogicalPlan::DescribeTable(DescribeTable {
+                schema,
+                dummy_schema,
+            }) => Ok(protobuf::LogicalPlanNode {
+                logical_plan_type: Some(LogicalPlanType::DescribeTable(
+                    protobuf::DescribeTableNode {
+                        schema: Some(schema.as_ref().try_into()?),
+                        dummy_schema: Some(dummy_schema.try_into()?),
+                    },
+                )),
+            }),
         }
     }
 }
@@ -1421,12 +1888,13 @@ mod roundtrip_tests {
     };
     use datafusion_expr::logical_plan::{Extension, UserDefinedLogicalNodeCore};
     use datafusion_expr::{
-        col, lit, Accumulator, AggregateFunction,
+        array, col, lit, Accumulator, AggregateFunction,
         BuiltinScalarFunction::{Sqrt, Substr},
-        Expr, LogicalPlan, Operator, TryCast, Volatility,
+        Expr, LogicalPlan, LogicalPlanBuilder, Operator, Subquery, TryCast, Volatility,
     };
     use datafusion_expr::{
-        create_udaf, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunction,
+        create_udaf, create_udwf, PartitionEvaluator, WindowFrame, WindowFrameBound,
+        WindowFrameUnits, WindowFunction,
     };
     use prost::Message;
     use std::collections::HashMap;
@@ -1597,6 +2065,158 @@ mod roundtrip_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn roundtrip_logical_plan_statements() -> Result<()> {
+        let ctx = SessionContext::new();
+
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Decimal128(15, 2), true),
+        ]);
+
+        ctx.register_csv(
+            "t1",
+            "testdata/test.csv",
+            CsvReadOptions::default().schema(&schema),
+        )
+        .await?;
+
+        let queries = [
+            "CREATE TABLE t2 AS SELECT * FROM t1",
+            "DROP TABLE IF EXISTS t2",
+            "DROP VIEW IF EXISTS v1",
+            "DROP SCHEMA IF EXISTS s1 CASCADE",
+            "DESCRIBE t1",
+            "INSERT INTO t1 SELECT * FROM t1",
+            "DELETE FROM t1 WHERE a = 1",
+            "UPDATE t1 SET b = 1.5 WHERE a = 1",
+            "START TRANSACTION READ ONLY ISOLATION LEVEL SERIALIZABLE",
+            "COMMIT",
+            "ROLLBACK",
+            "SET datafusion.execution.batch_size = 1024",
+        ];
+        for query in queries {
+            // only plan the statements, executing them would change the catalog
+            let plan = ctx.state().create_logical_plan(query).await?;
+
+            let bytes = logical_plan_to_bytes(&plan)?;
+            let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+            assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_logical_plan_subqueries() -> Result<()> {
+        let ctx = SessionContext::new();
+
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Decimal128(15, 2), true),
+        ]);
+
+        for table in ["t1", "t2"] {
+            ctx.register_csv(
+                table,
+                "testdata/test.csv",
+                CsvReadOptions::default().schema(&schema),
+            )
+            .await?;
+        }
+
+        let queries = [
+            "SELECT a FROM t1 WHERE EXISTS (SELECT * FROM t2 WHERE t2.a = t1.a)",
+            "SELECT a FROM t1 WHERE a NOT IN (SELECT a FROM t2 WHERE t2.b > t1.b)",
+            "SELECT a, (SELECT MAX(b) FROM t2 WHERE t2.a = t1.a) AS m FROM t1",
+        ];
+        for query in queries {
+            // the optimizer would decorrelate the subqueries into joins
+            let plan = ctx.sql(query).await?.into_unoptimized_plan();
+
+            let bytes = logical_plan_to_bytes(&plan)?;
+            let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+            assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_logical_plan_copy_to() -> Result<()> {
+        let ctx = SessionContext::new();
+
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Decimal128(15, 2), true),
+        ]);
+        ctx.register_csv(
+            "t1",
+            "testdata/test.csv",
+            CsvReadOptions::default().schema(&schema),
+        )
+        .await?;
+
+        let queries = [
+            "COPY t1 TO 'out.parquet' (COMPRESSION zstd, MAX_ROW_GROUP_SIZE 1024)",
+            "COPY (SELECT b, a FROM t1) TO 'out/' \
+             (FORMAT csv, HEADER false, DELIMITER '|', COMPRESSION gzip, PARTITION_BY (a))",
+        ];
+        for query in queries {
+            // only plan the statements, executing them would write the files
+            let plan = ctx.state().create_logical_plan(query).await?;
+
+            let bytes = logical_plan_to_bytes(&plan)?;
+            let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+            assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+
+            let (copy, round_trip) = match (&plan, &logical_round_trip) {
+                (LogicalPlan::Copy(copy), LogicalPlan::Copy(round_trip)) => {
+                    (copy, round_trip)
+                }
+                _ => panic!("expected COPY TO plans, got {plan:?}"),
+            };
+            assert_eq!(copy.output_url, round_trip.output_url);
+            assert_eq!(copy.file_type, round_trip.file_type);
+            assert_eq!(copy.has_header, round_trip.has_header);
+            assert_eq!(copy.delimiter, round_trip.delimiter);
+            assert_eq!(copy.file_compression_type, round_trip.file_compression_type);
+            assert_eq!(copy.partition_by, round_trip.partition_by);
+            assert_eq!(copy.options, round_trip.options);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_logical_plan_unnest() -> Result<()> {
+        let ctx = SessionContext::new();
+
+        let plan = LogicalPlanBuilder::values(vec![vec![array(vec![lit(1), lit(2)])]])?
+            .unnest_column("column1")?
+            .build()?;
+
+        let bytes = logical_plan_to_bytes(&plan)?;
+        let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+        assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn subquery_expr_requires_plan() {
+        let subquery = Expr::Exists(expr::Exists::new(
+            Subquery {
+                subquery: Arc::new(LogicalPlanBuilder::empty(false).build().unwrap()),
+                outer_ref_columns: vec![],
+            },
+            false,
+        ));
+
+        let res: Result<protobuf::LogicalExprNode, _> = (&subquery).try_into();
+        assert!(res.is_err());
+    }
+
     #[tokio::test]
     async fn roundtrip_single_count_distinct() -> Result<()> {
         let ctx = SessionContext::new();
@@ -2683,6 +3303,36 @@ mod roundtrip_tests {
         roundtrip_expr_test(test_expr, ctx.clone());
         roundtrip_expr_test(test_expr_with_count, ctx);
     }
+
+    #[test]
+    fn roundtrip_array_functions() {
+        use datafusion_expr::{
+            array_append, array_concat, array_contains, array_dims, array_length,
+            array_position, array_positions, array_prepend, array_remove, array_replace,
+            array_slice, array_to_string, cardinality, flatten,
+        };
+
+        let ctx = SessionContext::new();
+        for test_expr in [
+            array_append(col("col"), lit(1_i64)),
+            array_concat(vec![col("col"), col("col")]),
+            array_contains(col("col"), col("col")),
+            array_dims(col("col")),
+            array_length(vec![col("col"), lit(1_i64)]),
+            array_position(vec![col("col"), lit(1_i64), lit(2_i64)]),
+            array_positions(col("col"), lit(1_i64)),
+            array_prepend(lit(1_i64), col("col")),
+            array_remove(col("col"), lit(1_i64)),
+            array_replace(col("col"), lit(1_i64), lit(2_i64)),
+            array_slice(col("col"), lit(1_i64), lit(2_i64)),
+            array_to_string(col("col"), lit(",")),
+            cardinality(col("col")),
+            flatten(col("col")),
+        ] {
+            roundtrip_expr_test(test_expr, ctx.clone());
+        }
+    }
+
     #[test]
     fn roundtrip_window() {
         let ctx = SessionContext::new();
@@ -2746,4 +3396,81 @@ mod roundtrip_tests {
         roundtrip_expr_test(test_expr3, ctx.clone());
         roundtrip_expr_test(test_expr4, ctx);
     }
+
+    #[test]
+    fn roundtrip_window_udf() {
+        #[derive(Debug)]
+        struct DummyWindow {}
+
+        impl PartitionEvaluator for DummyWindow {}
+
+        let dummy_window_udf = create_udwf(
+            "dummy_window_udf",
+            vec![DataType::Int64],
+            Arc::new(DataType::Int64),
+            Volatility::Immutable,
+            Arc::new(|| Ok(Box::new(DummyWindow {}))),
+        );
+
+        let dummy_agg = create_udaf(
+            "dummy_agg",
+            DataType::Int64,
+            Arc::new(DataType::Int64),
+            Volatility::Immutable,
+            Arc::new(|_| unimplemented!("only planned")),
+            Arc::new(vec![DataType::Int64]),
+        );
+
+        let ctx = SessionContext::new();
+        ctx.register_udwf(dummy_window_udf.clone());
+        ctx.register_udaf(dummy_agg.clone());
+
+        let test_expr1 = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunction::WindowUDF(Arc::new(dummy_window_udf)),
+            vec![col("col1")],
+            vec![col("col1")],
+            vec![col("col2")],
+            WindowFrame::new(true),
+        ));
+        let test_expr2 = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunction::AggregateUDF(Arc::new(dummy_agg)),
+            vec![col("col1")],
+            vec![col("col1")],
+            vec![col("col2")],
+            WindowFrame::new(true),
+        ));
+
+        roundtrip_expr_test(test_expr1, ctx.clone());
+        roundtrip_expr_test(test_expr2, ctx);
+    }
+
+    #[test]
+    fn window_udf_must_be_registered() {
+        #[derive(Debug)]
+        struct DummyWindow {}
+
+        impl PartitionEvaluator for DummyWindow {}
+
+        let dummy_window_udf = create_udwf(
+            "dummy_window_udf",
+            vec![DataType::Int64],
+            Arc::new(DataType::Int64),
+            Volatility::Immutable,
+            Arc::new(|| Ok(Box::new(DummyWindow {}))),
+        );
+        let test_expr = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunction::WindowUDF(Arc::new(dummy_window_udf)),
+            vec![col("col1")],
+            vec![],
+            vec![],
+            WindowFrame::new(false),
+        ));
+
+        let proto: protobuf::LogicalExprNode = (&test_expr).try_into().unwrap();
+        let err = parse_expr(&proto, &SessionContext::new()).unwrap_err();
+        assert!(
+            err.to_string().contains("dummy_window_udf"),
+            "unexpected error: {err}"
+        );
+    }
 }
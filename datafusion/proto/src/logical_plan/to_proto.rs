@@ -19,6 +19,7 @@
 //! DataFusion logical plans to be serialized and transmitted between
 //! processes.
 
+use crate::logical_plan::{AsLogicalPlan, LogicalExtensionCodec};
 use crate::protobuf::{
     self,
     arrow_type::ArrowTypeEnum,
@@ -34,13 +35,17 @@ use arrow::datatypes::{
     DataType, Field, IntervalMonthDayNanoType, IntervalUnit, Schema, SchemaRef, TimeUnit,
     UnionMode,
 };
-use datafusion_common::{Column, DFField, DFSchemaRef, OwnedTableReference, ScalarValue};
+use datafusion_common::{
+    Column, DFField, DFSchemaRef, OwnedSchemaReference, OwnedTableReference, ScalarValue,
+};
 use datafusion_expr::expr::{
-    self, Between, BinaryExpr, Cast, GetIndexedField, GroupingSet, InList, Like,
-    Placeholder, ScalarFunction, ScalarUDF, Sort,
+    self, Between, BinaryExpr, Cast, Exists, GetIndexedField, GroupingSet, InList,
+    InSubquery, Like, Placeholder, ScalarFunction, ScalarUDF, Sort,
 };
 use datafusion_expr::{
-    logical_plan::PlanType, logical_plan::StringifiedPlan, AggregateFunction,
+    logical_plan::PlanType, logical_plan::StringifiedPlan, logical_plan::Subquery,
+    logical_plan::TransactionAccessMode, logical_plan::TransactionConclusion,
+    logical_plan::TransactionIsolationLevel, logical_plan::WriteOp, AggregateFunction,
     BuiltInWindowFunction, BuiltinScalarFunction, Expr, JoinConstraint, JoinType,
     TryCast, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunction,
 };
@@ -455,481 +460,571 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
     type Error = Error;
 
     fn try_from(expr: &Expr) -> Result<Self, Self::Error> {
-        use protobuf::logical_expr_node::ExprType;
+        serialize_expr_impl(expr, None)
+    }
+}
 
-        let expr_node = match expr {
-            Expr::Column(c) => Self {
-                expr_type: Some(ExprType::Column(c.into())),
-            },
-            Expr::Alias(expr, alias) => {
-                let alias = Box::new(protobuf::AliasNode {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    alias: alias.to_owned(),
-                });
-                Self {
-                    expr_type: Some(ExprType::Alias(alias)),
-                }
+/// Serialize an [`Expr`] that is part of a logical plan, serializing the plans
+/// of any subquery expressions with `extension_codec`.
+pub fn serialize_expr_in_plan(
+    expr: &Expr,
+    extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<protobuf::LogicalExprNode, Error> {
+    serialize_expr_impl(expr, Some(extension_codec))
+}
+
+/// Serialize a [`Subquery`] into a [`protobuf::SubqueryNode`].
+pub fn serialize_subquery(
+    subquery: &Subquery,
+    extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<protobuf::SubqueryNode, Error> {
+    let plan = protobuf::LogicalPlanNode::try_from_logical_plan(
+        subquery.subquery.as_ref(),
+        extension_codec,
+    )
+    .map_err(|e| Error::General(e.to_string()))?;
+    let outer_ref_columns = subquery
+        .outer_ref_columns
+        .iter()
+        .map(|expr| serialize_expr_in_plan(expr, extension_codec))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(protobuf::SubqueryNode {
+        subquery: Some(Box::new(plan)),
+        outer_ref_columns,
+    })
+}
+
+fn serialize_subquery_expr(
+    subquery: &Subquery,
+    extension_codec: Option<&dyn LogicalExtensionCodec>,
+) -> Result<protobuf::SubqueryNode, Error> {
+    match extension_codec {
+        Some(extension_codec) => serialize_subquery(subquery, extension_codec),
+        // the subquery plan may contain extension nodes and custom tables, so
+        // it can't be serialized without the codec of the enclosing plan
+        None => Err(Error::General(
+            "Proto serialization error: subquery expressions can only be serialized as part of a logical plan"
+                .to_string(),
+        )),
+    }
+}
+
+fn serialize_expr_impl(
+    expr: &Expr,
+    extension_codec: Option<&dyn LogicalExtensionCodec>,
+) -> Result<protobuf::LogicalExprNode, Error> {
+    use protobuf::logical_expr_node::ExprType;
+
+    let expr_node = match expr {
+        Expr::Column(c) => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::Column(c.into())),
+        },
+        Expr::Alias(expr, alias) => {
+            let alias = Box::new(protobuf::AliasNode {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                alias: alias.to_owned(),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Alias(alias)),
             }
-            Expr::Literal(value) => {
-                let pb_value: protobuf::ScalarValue = value.try_into()?;
-                Self {
-                    expr_type: Some(ExprType::Literal(pb_value)),
+        }
+        Expr::Literal(value) => {
+            let pb_value: protobuf::ScalarValue = value.try_into()?;
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Literal(pb_value)),
+            }
+        }
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+            // Try to linerize a nested binary expression tree of the same operator
+            // into a flat vector of expressions.
+            let mut exprs = vec![right.as_ref()];
+            let mut current_expr = left.as_ref();
+            while let Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: current_op,
+                right,
+            }) = current_expr
+            {
+                if current_op == op {
+                    exprs.push(right.as_ref());
+                    current_expr = left.as_ref();
+                } else {
+                    break;
                 }
             }
-            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
-                // Try to linerize a nested binary expression tree of the same operator
-                // into a flat vector of expressions.
-                let mut exprs = vec![right.as_ref()];
-                let mut current_expr = left.as_ref();
-                while let Expr::BinaryExpr(BinaryExpr {
-                    left,
-                    op: current_op,
-                    right,
-                }) = current_expr
-                {
-                    if current_op == op {
-                        exprs.push(right.as_ref());
-                        current_expr = left.as_ref();
-                    } else {
-                        break;
-                    }
+            exprs.push(current_expr);
+
+            let binary_expr = protobuf::BinaryExprNode {
+                // We need to reverse exprs since operands are expected to be
+                // linearized from left innermost to right outermost (but while
+                // traversing the chain we do the exact opposite).
+                operands: exprs
+                    .into_iter()
+                    .rev()
+                    .map(|expr| serialize_expr_impl(expr, extension_codec))
+                    .collect::<Result<Vec<_>, Error>>()?,
+                op: format!("{op:?}"),
+            };
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::BinaryExpr(binary_expr)),
+            }
+        }
+        Expr::Like(Like {
+            negated,
+            expr,
+            pattern,
+            escape_char,
+        }) => {
+            let pb = Box::new(protobuf::LikeNode {
+                negated: *negated,
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                pattern: Some(Box::new(serialize_expr_impl(pattern, extension_codec)?)),
+                escape_char: escape_char.map(|ch| ch.to_string()).unwrap_or_default(),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Like(pb)),
+            }
+        }
+        Expr::ILike(Like {
+            negated,
+            expr,
+            pattern,
+            escape_char,
+        }) => {
+            let pb = Box::new(protobuf::ILikeNode {
+                negated: *negated,
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                pattern: Some(Box::new(serialize_expr_impl(pattern, extension_codec)?)),
+                escape_char: escape_char.map(|ch| ch.to_string()).unwrap_or_default(),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Ilike(pb)),
+            }
+        }
+        Expr::SimilarTo(Like {
+            negated,
+            expr,
+            pattern,
+            escape_char,
+        }) => {
+            let pb = Box::new(protobuf::SimilarToNode {
+                negated: *negated,
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                pattern: Some(Box::new(serialize_expr_impl(pattern, extension_codec)?)),
+                escape_char: escape_char.map(|ch| ch.to_string()).unwrap_or_default(),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::SimilarTo(pb)),
+            }
+        }
+        Expr::WindowFunction(expr::WindowFunction {
+            ref fun,
+            ref args,
+            ref partition_by,
+            ref order_by,
+            ref window_frame,
+        }) => {
+            let window_function = match fun {
+                WindowFunction::AggregateFunction(fun) => {
+                    protobuf::window_expr_node::WindowFunction::AggrFunction(
+                        protobuf::AggregateFunction::from(fun).into(),
+                    )
                 }
-                exprs.push(current_expr);
-
-                let binary_expr = protobuf::BinaryExprNode {
-                    // We need to reverse exprs since operands are expected to be
-                    // linearized from left innermost to right outermost (but while
-                    // traversing the chain we do the exact opposite).
-                    operands: exprs
-                        .into_iter()
-                        .rev()
-                        .map(|expr| expr.try_into())
-                        .collect::<Result<Vec<_>, Error>>()?,
-                    op: format!("{op:?}"),
-                };
-                Self {
-                    expr_type: Some(ExprType::BinaryExpr(binary_expr)),
+                WindowFunction::BuiltInWindowFunction(fun) => {
+                    protobuf::window_expr_node::WindowFunction::BuiltInFunction(
+                        protobuf::BuiltInWindowFunction::from(fun).into(),
+                    )
                 }
-            }
-            Expr::Like(Like {
-                negated,
-                expr,
-                pattern,
-                escape_char,
-            }) => {
-                let pb = Box::new(protobuf::LikeNode {
-                    negated: *negated,
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    pattern: Some(Box::new(pattern.as_ref().try_into()?)),
-                    escape_char: escape_char.map(|ch| ch.to_string()).unwrap_or_default(),
-                });
-                Self {
-                    expr_type: Some(ExprType::Like(pb)),
+                WindowFunction::AggregateUDF(aggr_udf) => {
+                    protobuf::window_expr_node::WindowFunction::Udaf(
+                        aggr_udf.name.clone(),
+                    )
                 }
-            }
-            Expr::ILike(Like {
-                negated,
-                expr,
-                pattern,
-                escape_char,
-            }) => {
-                let pb = Box::new(protobuf::ILikeNode {
-                    negated: *negated,
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    pattern: Some(Box::new(pattern.as_ref().try_into()?)),
-                    escape_char: escape_char.map(|ch| ch.to_string()).unwrap_or_default(),
-                });
-                Self {
-                    expr_type: Some(ExprType::Ilike(pb)),
+                WindowFunction::WindowUDF(window_udf) => {
+                    protobuf::window_expr_node::WindowFunction::Udwf(
+                        window_udf.name.clone(),
+                    )
                 }
+            };
+            let arg_expr: Option<Box<protobuf::LogicalExprNode>> = if !args.is_empty() {
+                let arg = &args[0];
+                Some(Box::new(serialize_expr_impl(arg, extension_codec)?))
+            } else {
+                None
+            };
+            let partition_by = partition_by
+                .iter()
+                .map(|e| serialize_expr_impl(e, extension_codec))
+                .collect::<Result<Vec<_>, _>>()?;
+            let order_by = order_by
+                .iter()
+                .map(|e| serialize_expr_impl(e, extension_codec))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let window_frame: Option<protobuf::WindowFrame> =
+                Some(window_frame.try_into()?);
+            let window_expr = Box::new(protobuf::WindowExprNode {
+                expr: arg_expr,
+                window_function: Some(window_function),
+                partition_by,
+                order_by,
+                window_frame,
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::WindowExpr(window_expr)),
             }
-            Expr::SimilarTo(Like {
-                negated,
-                expr,
-                pattern,
-                escape_char,
-            }) => {
-                let pb = Box::new(protobuf::SimilarToNode {
-                    negated: *negated,
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    pattern: Some(Box::new(pattern.as_ref().try_into()?)),
-                    escape_char: escape_char.map(|ch| ch.to_string()).unwrap_or_default(),
-                });
-                Self {
-                    expr_type: Some(ExprType::SimilarTo(pb)),
+        }
+        Expr::AggregateFunction(expr::AggregateFunction {
+            ref fun,
+            ref args,
+            ref distinct,
+            ref filter,
+        }) => {
+            let aggr_function = match fun {
+                AggregateFunction::ApproxDistinct => {
+                    protobuf::AggregateFunction::ApproxDistinct
                 }
-            }
-            Expr::WindowFunction(expr::WindowFunction {
-                ref fun,
-                ref args,
-                ref partition_by,
-                ref order_by,
-                ref window_frame,
-            }) => {
-                let window_function = match fun {
-                    WindowFunction::AggregateFunction(fun) => {
-                        protobuf::window_expr_node::WindowFunction::AggrFunction(
-                            protobuf::AggregateFunction::from(fun).into(),
-                        )
-                    }
-                    WindowFunction::BuiltInWindowFunction(fun) => {
-                        protobuf::window_expr_node::WindowFunction::BuiltInFunction(
-                            protobuf::BuiltInWindowFunction::from(fun).into(),
-                        )
-                    }
-                    // TODO: Tracked in https://github.com/apache/arrow-datafusion/issues/4584
-                    WindowFunction::AggregateUDF(_) => {
-                        return Err(Error::NotImplemented(
-                            "UDAF as window function in proto".to_string(),
-                        ))
-                    }
-                };
-                let arg_expr: Option<Box<Self>> = if !args.is_empty() {
-                    let arg = &args[0];
-                    Some(Box::new(arg.try_into()?))
-                } else {
-                    None
-                };
-                let partition_by = partition_by
-                    .iter()
-                    .map(|e| e.try_into())
-                    .collect::<Result<Vec<_>, _>>()?;
-                let order_by = order_by
-                    .iter()
-                    .map(|e| e.try_into())
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                let window_frame: Option<protobuf::WindowFrame> =
-                    Some(window_frame.try_into()?);
-                let window_expr = Box::new(protobuf::WindowExprNode {
-                    expr: arg_expr,
-                    window_function: Some(window_function),
-                    partition_by,
-                    order_by,
-                    window_frame,
-                });
-                Self {
-                    expr_type: Some(ExprType::WindowExpr(window_expr)),
+                AggregateFunction::ApproxPercentileCont => {
+                    protobuf::AggregateFunction::ApproxPercentileCont
                 }
-            }
-            Expr::AggregateFunction(expr::AggregateFunction {
-                ref fun,
-                ref args,
-                ref distinct,
-                ref filter,
-            }) => {
-                let aggr_function = match fun {
-                    AggregateFunction::ApproxDistinct => {
-                        protobuf::AggregateFunction::ApproxDistinct
-                    }
-                    AggregateFunction::ApproxPercentileCont => {
-                        protobuf::AggregateFunction::ApproxPercentileCont
-                    }
-                    AggregateFunction::ApproxPercentileContWithWeight => {
-                        protobuf::AggregateFunction::ApproxPercentileContWithWeight
-                    }
-                    AggregateFunction::ArrayAgg => protobuf::AggregateFunction::ArrayAgg,
-                    AggregateFunction::Min => protobuf::AggregateFunction::Min,
-                    AggregateFunction::Max => protobuf::AggregateFunction::Max,
-                    AggregateFunction::Sum => protobuf::AggregateFunction::Sum,
-                    AggregateFunction::Avg => protobuf::AggregateFunction::Avg,
-                    AggregateFunction::Count => protobuf::AggregateFunction::Count,
-                    AggregateFunction::Variance => protobuf::AggregateFunction::Variance,
-                    AggregateFunction::VariancePop => {
-                        protobuf::AggregateFunction::VariancePop
-                    }
-                    AggregateFunction::Covariance => {
-                        protobuf::AggregateFunction::Covariance
-                    }
-                    AggregateFunction::CovariancePop => {
-                        protobuf::AggregateFunction::CovariancePop
-                    }
-                    AggregateFunction::Stddev => protobuf::AggregateFunction::Stddev,
-                    AggregateFunction::StddevPop => {
-                        protobuf::AggregateFunction::StddevPop
-                    }
-                    AggregateFunction::Correlation => {
-                        protobuf::AggregateFunction::Correlation
-                    }
-                    AggregateFunction::ApproxMedian => {
-                        protobuf::AggregateFunction::ApproxMedian
-                    }
-                    AggregateFunction::Grouping => protobuf::AggregateFunction::Grouping,
-                    AggregateFunction::Median => protobuf::AggregateFunction::Median,
-                };
-
-                let aggregate_expr = protobuf::AggregateExprNode {
-                    aggr_function: aggr_function.into(),
-                    expr: args
-                        .iter()
-                        .map(|v| v.try_into())
-                        .collect::<Result<Vec<_>, _>>()?,
-                    distinct: *distinct,
-                    filter: match filter {
-                        Some(e) => Some(Box::new(e.as_ref().try_into()?)),
-                        None => None,
-                    },
-                };
-                Self {
-                    expr_type: Some(ExprType::AggregateExpr(Box::new(aggregate_expr))),
+                AggregateFunction::ApproxPercentileContWithWeight => {
+                    protobuf::AggregateFunction::ApproxPercentileContWithWeight
                 }
-            }
-            Expr::ScalarVariable(_, _) => {
-                return Err(Error::General(
-                    "Proto serialization error: Scalar Variable not supported"
-                        .to_string(),
-                ))
-            }
-            Expr::ScalarFunction(ScalarFunction { fun, args }) => {
-                let fun: protobuf::ScalarFunction = fun.try_into()?;
-                let args: Vec<Self> = args
-                    .iter()
-                    .map(|e| e.try_into())
-                    .collect::<Result<Vec<Self>, Error>>()?;
-                Self {
-                    expr_type: Some(ExprType::ScalarFunction(
-                        protobuf::ScalarFunctionNode {
-                            fun: fun.into(),
-                            args,
-                        },
-                    )),
+                AggregateFunction::ArrayAgg => protobuf::AggregateFunction::ArrayAgg,
+                AggregateFunction::Min => protobuf::AggregateFunction::Min,
+                AggregateFunction::Max => protobuf::AggregateFunction::Max,
+                AggregateFunction::Sum => protobuf::AggregateFunction::Sum,
+                AggregateFunction::Avg => protobuf::AggregateFunction::Avg,
+                AggregateFunction::Count => protobuf::AggregateFunction::Count,
+                AggregateFunction::Variance => protobuf::AggregateFunction::Variance,
+                AggregateFunction::VariancePop => {
+                    protobuf::AggregateFunction::VariancePop
+                }
+                AggregateFunction::Covariance => protobuf::AggregateFunction::Covariance,
+                AggregateFunction::CovariancePop => {
+                    protobuf::AggregateFunction::CovariancePop
+                }
+                AggregateFunction::Stddev => protobuf::AggregateFunction::Stddev,
+                AggregateFunction::StddevPop => protobuf::AggregateFunction::StddevPop,
+                AggregateFunction::Correlation => {
+                    protobuf::AggregateFunction::Correlation
                 }
+                AggregateFunction::ApproxMedian => {
+                    protobuf::AggregateFunction::ApproxMedian
+                }
+                AggregateFunction::Grouping => protobuf::AggregateFunction::Grouping,
+                AggregateFunction::Median => protobuf::AggregateFunction::Median,
+            };
+
+            let aggregate_expr = protobuf::AggregateExprNode {
+                aggr_function: aggr_function.into(),
+                expr: args
+                    .iter()
+                    .map(|v| serialize_expr_impl(v, extension_codec))
+                    .collect::<Result<Vec<_>, _>>()?,
+                distinct: *distinct,
+                filter: match filter {
+                    Some(e) => Some(Box::new(serialize_expr_impl(e, extension_codec)?)),
+                    None => None,
+                },
+            };
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::AggregateExpr(Box::new(aggregate_expr))),
             }
-            Expr::ScalarUDF(ScalarUDF { fun, args }) => Self {
-                expr_type: Some(ExprType::ScalarUdfExpr(protobuf::ScalarUdfExprNode {
-                    fun_name: fun.name.clone(),
-                    args: args
-                        .iter()
-                        .map(|expr| expr.try_into())
-                        .collect::<Result<Vec<_>, Error>>()?,
+        }
+        Expr::ScalarVariable(_, _) => {
+            return Err(Error::General(
+                "Proto serialization error: Scalar Variable not supported".to_string(),
+            ))
+        }
+        Expr::ScalarFunction(ScalarFunction { fun, args }) => {
+            let fun: protobuf::ScalarFunction = fun.try_into()?;
+            let args: Vec<protobuf::LogicalExprNode> = args
+                .iter()
+                .map(|e| serialize_expr_impl(e, extension_codec))
+                .collect::<Result<Vec<protobuf::LogicalExprNode>, Error>>()?;
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::ScalarFunction(protobuf::ScalarFunctionNode {
+                    fun: fun.into(),
+                    args,
                 })),
-            },
-            Expr::AggregateUDF(expr::AggregateUDF { fun, args, filter }) => Self {
+            }
+        }
+        Expr::ScalarUDF(ScalarUDF { fun, args }) => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::ScalarUdfExpr(protobuf::ScalarUdfExprNode {
+                fun_name: fun.name.clone(),
+                args: args
+                    .iter()
+                    .map(|expr| serialize_expr_impl(expr, extension_codec))
+                    .collect::<Result<Vec<_>, Error>>()?,
+            })),
+        },
+        Expr::AggregateUDF(expr::AggregateUDF { fun, args, filter }) => {
+            protobuf::LogicalExprNode {
                 expr_type: Some(ExprType::AggregateUdfExpr(Box::new(
                     protobuf::AggregateUdfExprNode {
                         fun_name: fun.name.clone(),
-                        args: args.iter().map(|expr| expr.try_into()).collect::<Result<
-                            Vec<_>,
-                            Error,
-                        >>(
-                        )?,
+                        args: args
+                            .iter()
+                            .map(|expr| serialize_expr_impl(expr, extension_codec))
+                            .collect::<Result<Vec<_>, Error>>()?,
                         filter: match filter {
-                            Some(e) => Some(Box::new(e.as_ref().try_into()?)),
+                            Some(e) => {
+                                Some(Box::new(serialize_expr_impl(e, extension_codec)?))
+                            }
                             None => None,
                         },
                     },
                 ))),
-            },
-            Expr::Not(expr) => {
-                let expr = Box::new(protobuf::Not {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::NotExpr(expr)),
-                }
             }
-            Expr::IsNull(expr) => {
-                let expr = Box::new(protobuf::IsNull {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::IsNullExpr(expr)),
-                }
+        }
+        Expr::Not(expr) => {
+            let expr = Box::new(protobuf::Not {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::NotExpr(expr)),
             }
-            Expr::IsNotNull(expr) => {
-                let expr = Box::new(protobuf::IsNotNull {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::IsNotNullExpr(expr)),
-                }
+        }
+        Expr::IsNull(expr) => {
+            let expr = Box::new(protobuf::IsNull {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::IsNullExpr(expr)),
             }
-            Expr::IsTrue(expr) => {
-                let expr = Box::new(protobuf::IsTrue {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::IsTrue(expr)),
-                }
+        }
+        Expr::IsNotNull(expr) => {
+            let expr = Box::new(protobuf::IsNotNull {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::IsNotNullExpr(expr)),
             }
-            Expr::IsFalse(expr) => {
-                let expr = Box::new(protobuf::IsFalse {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::IsFalse(expr)),
-                }
+        }
+        Expr::IsTrue(expr) => {
+            let expr = Box::new(protobuf::IsTrue {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::IsTrue(expr)),
             }
-            Expr::IsUnknown(expr) => {
-                let expr = Box::new(protobuf::IsUnknown {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::IsUnknown(expr)),
-                }
+        }
+        Expr::IsFalse(expr) => {
+            let expr = Box::new(protobuf::IsFalse {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::IsFalse(expr)),
             }
-            Expr::IsNotTrue(expr) => {
-                let expr = Box::new(protobuf::IsNotTrue {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::IsNotTrue(expr)),
-                }
+        }
+        Expr::IsUnknown(expr) => {
+            let expr = Box::new(protobuf::IsUnknown {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::IsUnknown(expr)),
             }
-            Expr::IsNotFalse(expr) => {
-                let expr = Box::new(protobuf::IsNotFalse {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::IsNotFalse(expr)),
-                }
+        }
+        Expr::IsNotTrue(expr) => {
+            let expr = Box::new(protobuf::IsNotTrue {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::IsNotTrue(expr)),
             }
-            Expr::IsNotUnknown(expr) => {
-                let expr = Box::new(protobuf::IsNotUnknown {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::IsNotUnknown(expr)),
-                }
+        }
+        Expr::IsNotFalse(expr) => {
+            let expr = Box::new(protobuf::IsNotFalse {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::IsNotFalse(expr)),
             }
-            Expr::Between(Between {
-                expr,
-                negated,
-                low,
-                high,
-            }) => {
-                let expr = Box::new(protobuf::BetweenNode {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    negated: *negated,
-                    low: Some(Box::new(low.as_ref().try_into()?)),
-                    high: Some(Box::new(high.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::Between(expr)),
-                }
+        }
+        Expr::IsNotUnknown(expr) => {
+            let expr = Box::new(protobuf::IsNotUnknown {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::IsNotUnknown(expr)),
             }
-            Expr::Case(case) => {
-                let when_then_expr = case
-                    .when_then_expr
-                    .iter()
-                    .map(|(w, t)| {
-                        Ok(protobuf::WhenThen {
-                            when_expr: Some(w.as_ref().try_into()?),
-                            then_expr: Some(t.as_ref().try_into()?),
-                        })
+        }
+        Expr::Between(Between {
+            expr,
+            negated,
+            low,
+            high,
+        }) => {
+            let expr = Box::new(protobuf::BetweenNode {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                negated: *negated,
+                low: Some(Box::new(serialize_expr_impl(low, extension_codec)?)),
+                high: Some(Box::new(serialize_expr_impl(high, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Between(expr)),
+            }
+        }
+        Expr::Case(case) => {
+            let when_then_expr = case
+                .when_then_expr
+                .iter()
+                .map(|(w, t)| {
+                    Ok(protobuf::WhenThen {
+                        when_expr: Some(serialize_expr_impl(w, extension_codec)?),
+                        then_expr: Some(serialize_expr_impl(t, extension_codec)?),
                     })
-                    .collect::<Result<Vec<protobuf::WhenThen>, Error>>()?;
-                let expr = Box::new(protobuf::CaseNode {
-                    expr: match &case.expr {
-                        Some(e) => Some(Box::new(e.as_ref().try_into()?)),
-                        None => None,
-                    },
-                    when_then_expr,
-                    else_expr: match &case.else_expr {
-                        Some(e) => Some(Box::new(e.as_ref().try_into()?)),
-                        None => None,
-                    },
-                });
-                Self {
-                    expr_type: Some(ExprType::Case(expr)),
-                }
+                })
+                .collect::<Result<Vec<protobuf::WhenThen>, Error>>()?;
+            let expr = Box::new(protobuf::CaseNode {
+                expr: match &case.expr {
+                    Some(e) => Some(Box::new(serialize_expr_impl(e, extension_codec)?)),
+                    None => None,
+                },
+                when_then_expr,
+                else_expr: match &case.else_expr {
+                    Some(e) => Some(Box::new(serialize_expr_impl(e, extension_codec)?)),
+                    None => None,
+                },
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Case(expr)),
             }
-            Expr::Cast(Cast { expr, data_type }) => {
-                let expr = Box::new(protobuf::CastNode {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    arrow_type: Some(data_type.try_into()?),
-                });
-                Self {
-                    expr_type: Some(ExprType::Cast(expr)),
-                }
+        }
+        Expr::Cast(Cast { expr, data_type }) => {
+            let expr = Box::new(protobuf::CastNode {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                arrow_type: Some(data_type.try_into()?),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Cast(expr)),
             }
-            Expr::TryCast(TryCast { expr, data_type }) => {
-                let expr = Box::new(protobuf::TryCastNode {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    arrow_type: Some(data_type.try_into()?),
-                });
-                Self {
-                    expr_type: Some(ExprType::TryCast(expr)),
-                }
+        }
+        Expr::TryCast(TryCast { expr, data_type }) => {
+            let expr = Box::new(protobuf::TryCastNode {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                arrow_type: Some(data_type.try_into()?),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::TryCast(expr)),
             }
-            Expr::Sort(Sort {
-                expr,
-                asc,
-                nulls_first,
-            }) => {
-                let expr = Box::new(protobuf::SortExprNode {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    asc: *asc,
-                    nulls_first: *nulls_first,
-                });
-                Self {
-                    expr_type: Some(ExprType::Sort(expr)),
-                }
+        }
+        Expr::Sort(Sort {
+            expr,
+            asc,
+            nulls_first,
+        }) => {
+            let expr = Box::new(protobuf::SortExprNode {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                asc: *asc,
+                nulls_first: *nulls_first,
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Sort(expr)),
             }
-            Expr::Negative(expr) => {
-                let expr = Box::new(protobuf::NegativeNode {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                });
-                Self {
-                    expr_type: Some(ExprType::Negative(expr)),
-                }
+        }
+        Expr::Negative(expr) => {
+            let expr = Box::new(protobuf::NegativeNode {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Negative(expr)),
             }
-            Expr::InList(InList {
-                expr,
-                list,
-                negated,
-            }) => {
-                let expr = Box::new(protobuf::InListNode {
-                    expr: Some(Box::new(expr.as_ref().try_into()?)),
-                    list: list
-                        .iter()
-                        .map(|expr| expr.try_into())
-                        .collect::<Result<Vec<_>, Error>>()?,
-                    negated: *negated,
-                });
-                Self {
-                    expr_type: Some(ExprType::InList(expr)),
-                }
+        }
+        Expr::InList(InList {
+            expr,
+            list,
+            negated,
+        }) => {
+            let expr = Box::new(protobuf::InListNode {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                list: list
+                    .iter()
+                    .map(|expr| serialize_expr_impl(expr, extension_codec))
+                    .collect::<Result<Vec<_>, Error>>()?,
+                negated: *negated,
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::InList(expr)),
             }
-            Expr::Wildcard => Self {
-                expr_type: Some(ExprType::Wildcard(true)),
-            },
-            Expr::ScalarSubquery(_)
-            | Expr::InSubquery(_)
-            | Expr::Exists { .. }
-            | Expr::OuterReferenceColumn { .. } => {
-                // we would need to add logical plan operators to datafusion.proto to support this
-                // see discussion in https://github.com/apache/arrow-datafusion/issues/2565
-                return Err(Error::General("Proto serialization error: Expr::ScalarSubquery(_) | Expr::InSubquery(_) | Expr::Exists { .. } | Exp:OuterReferenceColumn not supported".to_string()));
-            }
-            Expr::GetIndexedField(GetIndexedField { key, expr }) => Self {
+        }
+        Expr::Wildcard => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::Wildcard(true)),
+        },
+        Expr::Exists(Exists { subquery, negated }) => {
+            let exists = Box::new(protobuf::ExistsNode {
+                subquery: Some(Box::new(serialize_subquery_expr(
+                    subquery,
+                    extension_codec,
+                )?)),
+                negated: *negated,
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Exists(exists)),
+            }
+        }
+        Expr::InSubquery(InSubquery {
+            expr,
+            subquery,
+            negated,
+        }) => {
+            let in_subquery = Box::new(protobuf::InSubqueryNode {
+                expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
+                subquery: Some(Box::new(serialize_subquery_expr(
+                    subquery,
+                    extension_codec,
+                )?)),
+                negated: *negated,
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::InSubquery(in_subquery)),
+            }
+        }
+        Expr::ScalarSubquery(subquery) => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::ScalarSubquery(Box::new(serialize_subquery_expr(
+                subquery,
+                extension_codec,
+            )?))),
+        },
+        Expr::OuterReferenceColumn(data_type, column) => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::OuterReferenceColumn(
+                protobuf::OuterReferenceColumnNode {
+                    data_type: Some(data_type.try_into()?),
+                    column: Some(column.into()),
+                },
+            )),
+        },
+        Expr::GetIndexedField(GetIndexedField { key, expr }) => {
+            protobuf::LogicalExprNode {
                 expr_type: Some(ExprType::GetIndexedField(Box::new(
                     protobuf::GetIndexedField {
                         key: Some(key.try_into()?),
-                        expr: Some(Box::new(expr.as_ref().try_into()?)),
+                        expr: Some(Box::new(serialize_expr_impl(expr, extension_codec)?)),
                     },
                 ))),
-            },
+            }
+        }
 
-            Expr::GroupingSet(GroupingSet::Cube(exprs)) => Self {
-                expr_type: Some(ExprType::Cube(CubeNode {
-                    expr: exprs.iter().map(|expr| expr.try_into()).collect::<Result<
-                        Vec<_>,
-                        Self::Error,
-                    >>(
-                    )?,
-                })),
-            },
-            Expr::GroupingSet(GroupingSet::Rollup(exprs)) => Self {
-                expr_type: Some(ExprType::Rollup(RollupNode {
-                    expr: exprs.iter().map(|expr| expr.try_into()).collect::<Result<
-                        Vec<_>,
-                        Self::Error,
-                    >>(
-                    )?,
-                })),
-            },
-            Expr::GroupingSet(GroupingSet::GroupingSets(exprs)) => Self {
+        Expr::GroupingSet(GroupingSet::Cube(exprs)) => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::Cube(CubeNode {
+                expr: exprs
+                    .iter()
+                    .map(|expr| serialize_expr_impl(expr, extension_codec))
+                    .collect::<Result<Vec<_>, Error>>()?,
+            })),
+        },
+        Expr::GroupingSet(GroupingSet::Rollup(exprs)) => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::Rollup(RollupNode {
+                expr: exprs
+                    .iter()
+                    .map(|expr| serialize_expr_impl(expr, extension_codec))
+                    .collect::<Result<Vec<_>, Error>>()?,
+            })),
+        },
+        Expr::GroupingSet(GroupingSet::GroupingSets(exprs)) => {
+            protobuf::LogicalExprNode {
                 expr_type: Some(ExprType::GroupingSet(GroupingSetNode {
                     expr: exprs
                         .iter()
@@ -937,34 +1032,38 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                             Ok(LogicalExprList {
                                 expr: expr_list
                                     .iter()
-                                    .map(|expr| expr.try_into())
-                                    .collect::<Result<Vec<_>, Self::Error>>()?,
+                                    .map(|expr| {
+                                        serialize_expr_impl(expr, extension_codec)
+                                    })
+                                    .collect::<Result<Vec<_>, Error>>()?,
                             })
                         })
-                        .collect::<Result<Vec<_>, Self::Error>>()?,
+                        .collect::<Result<Vec<_>, Error>>()?,
+                })),
+            }
+        }
+        Expr::Placeholder(Placeholder { id, data_type }) => {
+            let data_type = match data_type {
+                Some(data_type) => Some(data_type.try_into()?),
+                None => None,
+            };
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Placeholder(PlaceholderNode {
+                    id: id.clone(),
+                    data_type,
                 })),
-            },
-            Expr::Placeholder(Placeholder { id, data_type }) => {
-                let data_type = match data_type {
-                    Some(data_type) => Some(data_type.try_into()?),
-                    None => None,
-                };
-                Self {
-                    expr_type: Some(ExprType::Placeholder(PlaceholderNode {
-                        id: id.clone(),
-                        data_type,
-                    })),
-                }
             }
+        }
 
-            Expr::QualifiedWildcard { .. } => return Err(Error::General(
+        Expr::QualifiedWildcard { .. } => {
+            return Err(Error::General(
                 "Proto serialization error: Expr::QualifiedWildcard { .. } not supported"
                     .to_string(),
-            )),
-        };
+            ))
+        }
+    };
 
-        Ok(expr_node)
-    }
+    Ok(expr_node)
 }
 
 impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
@@ -1299,6 +1398,20 @@ impl TryFrom<&BuiltinScalarFunction> for protobuf::ScalarFunction {
             BuiltinScalarFunction::Rtrim => Self::Rtrim,
             BuiltinScalarFunction::ToTimestamp => Self::ToTimestamp,
             BuiltinScalarFunction::MakeArray => Self::Array,
+            BuiltinScalarFunction::ArrayAppend => Self::ArrayAppend,
+            BuiltinScalarFunction::ArrayConcat => Self::ArrayConcat,
+            BuiltinScalarFunction::ArrayDims => Self::ArrayDims,
+            BuiltinScalarFunction::ArrayLength => Self::ArrayLength,
+            BuiltinScalarFunction::ArrayPosition => Self::ArrayPosition,
+            BuiltinScalarFunction::ArrayPositions => Self::ArrayPositions,
+            BuiltinScalarFunction::ArrayPrepend => Self::ArrayPrepend,
+            BuiltinScalarFunction::ArrayRemove => Self::ArrayRemove,
+            BuiltinScalarFunction::ArrayReplace => Self::ArrayReplace,
+            BuiltinScalarFunction::ArraySlice => Self::ArraySlice,
+            BuiltinScalarFunction::ArrayToString => Self::ArrayToString,
+            BuiltinScalarFunction::ArrayContains => Self::ArrayContains,
+            BuiltinScalarFunction::Cardinality => Self::Cardinality,
+            BuiltinScalarFunction::Flatten => Self::Flatten,
             BuiltinScalarFunction::NullIf => Self::NullIf,
             BuiltinScalarFunction::DatePart => Self::DatePart,
             BuiltinScalarFunction::DateTrunc => Self::DateTrunc,
@@ -1431,6 +1544,79 @@ impl From<JoinConstraint> for protobuf::JoinConstraint {
     }
 }
 
+impl From<OwnedSchemaReference> for protobuf::OwnedSchemaReference {
+    fn from(s: OwnedSchemaReference) -> Self {
+        use protobuf::owned_schema_reference::SchemaReferenceEnum;
+        let schema_reference_enum = match s {
+            OwnedSchemaReference::Bare { schema } => {
+                SchemaReferenceEnum::Bare(protobuf::BareSchemaReference {
+                    schema: schema.to_string(),
+                })
+            }
+            OwnedSchemaReference::Full { schema, catalog } => {
+                SchemaReferenceEnum::Full(protobuf::FullSchemaReference {
+                    catalog: catalog.to_string(),
+                    schema: schema.to_string(),
+                })
+            }
+        };
+
+        protobuf::OwnedSchemaReference {
+            schema_reference_enum: Some(schema_reference_enum),
+        }
+    }
+}
+
+impl From<WriteOp> for protobuf::WriteOp {
+    fn from(op: WriteOp) -> Self {
+        match op {
+            WriteOp::Insert => protobuf::WriteOp::Insert,
+            WriteOp::Delete => protobuf::WriteOp::Delete,
+            WriteOp::Update => protobuf::WriteOp::Update,
+            WriteOp::Ctas => protobuf::WriteOp::Ctas,
+        }
+    }
+}
+
+impl From<TransactionAccessMode> for protobuf::TransactionAccessMode {
+    fn from(mode: TransactionAccessMode) -> Self {
+        match mode {
+            TransactionAccessMode::ReadOnly => protobuf::TransactionAccessMode::ReadOnly,
+            TransactionAccessMode::ReadWrite => {
+                protobuf::TransactionAccessMode::ReadWrite
+            }
+        }
+    }
+}
+
+impl From<TransactionIsolationLevel> for protobuf::TransactionIsolationLevel {
+    fn from(level: TransactionIsolationLevel) -> Self {
+        match level {
+            TransactionIsolationLevel::ReadUncommitted => {
+                protobuf::TransactionIsolationLevel::ReadUncommitted
+            }
+            TransactionIsolationLevel::ReadCommitted => {
+                protobuf::TransactionIsolationLevel::ReadCommitted
+            }
+            TransactionIsolationLevel::RepeatableRead => {
+                protobuf::TransactionIsolationLevel::RepeatableRead
+            }
+            TransactionIsolationLevel::Serializable => {
+                protobuf::TransactionIsolationLevel::Serializable
+            }
+        }
+    }
+}
+
+impl From<TransactionConclusion> for protobuf::TransactionConclusion {
+    fn from(conclusion: TransactionConclusion) -> Self {
+        match conclusion {
+            TransactionConclusion::Commit => protobuf::TransactionConclusion::Commit,
+            TransactionConclusion::Rollback => protobuf::TransactionConclusion::Rollback,
+        }
+    }
+}
+
 /// Creates a scalar protobuf value from an optional value (T), and
 /// encoding None as the appropriate datatype
 fn create_proto_scalar<I, T: FnOnce(&I) -> protobuf::scalar_value::Value>(
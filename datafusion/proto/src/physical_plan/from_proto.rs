@@ -22,17 +22,21 @@ use arrow::datatypes::DataType;
 use chrono::TimeZone;
 use chrono::Utc;
 use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::ipc::reader::StreamReader;
+use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::datasource::listing::{FileRange, PartitionedFile};
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::execution::context::ExecutionProps;
 use datafusion::execution::FunctionRegistry;
 use datafusion::logical_expr::window_function::WindowFunction;
+use datafusion::logical_expr::WindowFrame;
 use datafusion::physical_expr::{PhysicalSortExpr, ScalarFunctionExpr};
 use datafusion::physical_plan::expressions::{
     date_time_interval_expr, GetIndexedFieldExpr,
 };
 use datafusion::physical_plan::expressions::{in_list, LikeExpr};
 use datafusion::physical_plan::file_format::FileScanConfig;
+use datafusion::physical_plan::windows::create_window_expr;
 use datafusion::physical_plan::{
     expressions::{
         BinaryExpr, CaseExpr, CastExpr, Column, IsNotNullExpr, IsNullExpr, Literal,
@@ -40,7 +44,7 @@ use datafusion::physical_plan::{
     },
     functions, Partitioning,
 };
-use datafusion::physical_plan::{ColumnStatistics, PhysicalExpr, Statistics};
+use datafusion::physical_plan::{ColumnStatistics, PhysicalExpr, Statistics, WindowExpr};
 use datafusion_common::{DataFusionError, Result};
 use object_store::path::Path;
 use object_store::ObjectMeta;
@@ -313,34 +317,109 @@ fn parse_required_physical_expr(
         })
 }
 
-impl TryFrom<&protobuf::physical_window_expr_node::WindowFunction> for WindowFunction {
-    type Error = DataFusionError;
-
-    fn try_from(
-        expr: &protobuf::physical_window_expr_node::WindowFunction,
-    ) -> Result<Self, Self::Error> {
-        match expr {
-            protobuf::physical_window_expr_node::WindowFunction::AggrFunction(n) => {
-                let f = protobuf::AggregateFunction::from_i32(*n).ok_or_else(|| {
-                    proto_error(format!(
-                        "Received an unknown window aggregate function: {n}"
-                    ))
-                })?;
+/// Parses a physical window expression from a protobuf.
+///
+/// # Arguments
+///
+/// * `proto` - Input proto with physical window expression node
+/// * `registry` - A registry knows how to build user-defined aggregate functions out of their names
+/// * `input_schema` - The Arrow schema for the input of the window operator
+/// * `name` - The name of the window expression's output field
+pub fn parse_physical_window_expr(
+    proto: &protobuf::PhysicalWindowExprNode,
+    registry: &dyn FunctionRegistry,
+    input_schema: &Schema,
+    name: String,
+) -> Result<Arc<dyn WindowExpr>> {
+    let window_function = match proto.window_function.as_ref() {
+        Some(protobuf::physical_window_expr_node::WindowFunction::AggrFunction(n)) => {
+            let f = protobuf::AggregateFunction::from_i32(*n).ok_or_else(|| {
+                proto_error(format!(
+                    "Received an unknown window aggregate function: {n}"
+                ))
+            })?;
+
+            WindowFunction::AggregateFunction(f.into())
+        }
+        Some(protobuf::physical_window_expr_node::WindowFunction::BuiltInFunction(n)) => {
+            let f = protobuf::BuiltInWindowFunction::from_i32(*n).ok_or_else(|| {
+                proto_error(format!("Received an unknown window builtin function: {n}"))
+            })?;
 
-                Ok(WindowFunction::AggregateFunction(f.into()))
-            }
-            protobuf::physical_window_expr_node::WindowFunction::BuiltInFunction(n) => {
-                let f =
-                    protobuf::BuiltInWindowFunction::from_i32(*n).ok_or_else(|| {
-                        proto_error(format!(
-                            "Received an unknown window builtin function: {n}"
-                        ))
-                    })?;
-
-                Ok(WindowFunction::BuiltInWindowFunction(f.into()))
-            }
+            WindowFunction::BuiltInWindowFunction(f.into())
         }
+        Some(
+            protobuf::physical_window_expr_node::WindowFunction::UserDefinedAggrFunction(
+                udaf_name,
+            ),
+        ) => WindowFunction::AggregateUDF(registry.udaf(udaf_name)?),
+        None => return Err(proto_error("Missing required field window_function")),
+    };
+
+    let args = proto
+        .args
+        .iter()
+        .map(|e| parse_physical_expr(e, registry, input_schema))
+        .collect::<Result<Vec<_>>>()?;
+
+    let partition_by = proto
+        .partition_by
+        .iter()
+        .map(|e| parse_physical_expr(e, registry, input_schema))
+        .collect::<Result<Vec<_>>>()?;
+
+    let order_by = proto
+        .order_by
+        .iter()
+        .map(|e| parse_physical_sort_expr(e, registry, input_schema))
+        .collect::<Result<Vec<_>>>()?;
+
+    let window_frame: WindowFrame = proto
+        .window_frame
+        .as_ref()
+        .map(|wf| WindowFrame::try_from(wf.clone()))
+        .transpose()?
+        .ok_or_else(|| proto_error("Missing required field window_frame"))?;
+
+    create_window_expr(
+        &window_function,
+        name,
+        &args,
+        &partition_by,
+        &order_by,
+        Arc::new(window_frame),
+        input_schema,
+    )
+}
+
+/// Parses a physical sort expression from a protobuf.
+pub fn parse_physical_sort_expr(
+    proto: &protobuf::PhysicalSortExprNode,
+    registry: &dyn FunctionRegistry,
+    input_schema: &Schema,
+) -> Result<PhysicalSortExpr> {
+    let expr = parse_required_physical_expr(
+        proto.expr.as_deref(),
+        registry,
+        "expr",
+        input_schema,
+    )?;
+    Ok(PhysicalSortExpr {
+        expr,
+        options: SortOptions {
+            descending: !proto.asc,
+            nulls_first: proto.nulls_first,
+        },
+    })
+}
+
+/// Decodes record batches serialized in the Arrow IPC stream format.
+pub(crate) fn parse_record_batches(buf: &[u8]) -> Result<Vec<RecordBatch>> {
+    if buf.is_empty() {
+        return Ok(vec![]);
     }
+    let reader = StreamReader::try_new(buf, None)?;
+    reader.collect::<Result<Vec<_>, _>>().map_err(Into::into)
 }
 
 pub fn parse_protobuf_hash_partitioning(
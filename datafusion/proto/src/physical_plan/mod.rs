@@ -17,14 +17,14 @@
 
 use std::convert::TryInto;
 use std::fmt::Debug;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use datafusion::arrow::compute::SortOptions;
-use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
 use datafusion::datasource::file_format::file_type::FileCompressionType;
 use datafusion::execution::runtime_env::RuntimeEnv;
 use datafusion::execution::FunctionRegistry;
-use datafusion::logical_expr::WindowFrame;
 use datafusion::physical_plan::aggregates::{create_aggregate_expr, AggregateMode};
 use datafusion::physical_plan::aggregates::{AggregateExec, PhysicalGroupBy};
 use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
@@ -32,18 +32,28 @@ use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
 use datafusion::physical_plan::empty::EmptyExec;
 use datafusion::physical_plan::explain::ExplainExec;
 use datafusion::physical_plan::expressions::{Column, PhysicalSortExpr};
-use datafusion::physical_plan::file_format::{AvroExec, CsvExec, ParquetExec};
+use datafusion::physical_plan::file_format::{
+    AvroExec, CsvExec, NdJsonExec, ParquetExec,
+};
 use datafusion::physical_plan::filter::FilterExec;
 use datafusion::physical_plan::joins::utils::{ColumnIndex, JoinFilter};
 use datafusion::physical_plan::joins::CrossJoinExec;
-use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode};
+use datafusion::physical_plan::joins::{
+    HashJoinExec, NestedLoopJoinExec, PartitionMode, SortMergeJoinExec,
+    SymmetricHashJoinExec,
+};
 use datafusion::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
+use datafusion::physical_plan::memory::MemoryExec;
 use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::repartition::RepartitionExec;
 use datafusion::physical_plan::sorts::sort::SortExec;
 use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use datafusion::physical_plan::union::UnionExec;
-use datafusion::physical_plan::windows::{create_window_expr, WindowAggExec};
+use datafusion::physical_plan::unnest::UnnestExec;
+use datafusion::physical_plan::values::ValuesExec;
+use datafusion::physical_plan::windows::{
+    BoundedWindowAggExec, PartitionSearchMode, WindowAggExec,
+};
 use datafusion::physical_plan::{
     udaf, AggregateExpr, ExecutionPlan, Partitioning, PhysicalExpr, WindowExpr,
 };
@@ -54,8 +64,11 @@ use prost::Message;
 use crate::common::proto_error;
 use crate::common::{csv_delimiter_to_string, str_to_byte};
 use crate::physical_plan::from_proto::{
-    parse_physical_expr, parse_protobuf_file_scan_config,
+    parse_physical_expr, parse_physical_sort_expr, parse_physical_window_expr,
+    parse_protobuf_file_scan_config, parse_record_batches,
 };
+use crate::physical_plan::to_proto::serialize_record_batches;
+use crate::protobuf::bounded_window_agg_exec_node;
 use crate::protobuf::physical_aggregate_expr_node::AggregateFunction;
 use crate::protobuf::physical_expr_node::ExprType;
 use crate::protobuf::physical_plan_node::PhysicalPlanType;
@@ -157,7 +170,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 )?,
                 scan.has_header,
                 str_to_byte(&scan.delimiter)?,
-                FileCompressionType::UNCOMPRESSED,
+                FileCompressionType::from_str(&scan.file_compression_type)?,
             ))),
             PhysicalPlanType::ParquetScan(scan) => {
                 let base_config = parse_protobuf_file_scan_config(
@@ -183,6 +196,13 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     registry,
                 )?)))
             }
+            PhysicalPlanType::JsonScan(scan) => Ok(Arc::new(NdJsonExec::new(
+                parse_protobuf_file_scan_config(
+                    scan.base_conf.as_ref().unwrap(),
+                    registry,
+                )?,
+                FileCompressionType::from_str(&scan.file_compression_type)?,
+            ))),
             PhysicalPlanType::CoalesceBatches(coalesce_batches) => {
                 let input: Arc<dyn ExecutionPlan> = into_physical_plan!(
                     coalesce_batches.input,
@@ -284,57 +304,89 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 let physical_schema: SchemaRef =
                     SchemaRef::new((&input_schema).try_into()?);
 
-                let physical_window_expr: Vec<Arc<dyn WindowExpr>> = window_agg
-                    .window_expr
+                let physical_window_expr = parse_window_exprs(
+                    &window_agg.window_expr,
+                    &window_agg.window_expr_name,
+                    registry,
+                    &physical_schema,
+                )?;
+                let partition_keys = window_agg
+                    .partition_keys
                     .iter()
-                    .zip(window_agg.window_expr_name.iter())
-                    .map(|(expr, name)| {
-                        let expr_type = expr.expr_type.as_ref().ok_or_else(|| {
-                            proto_error("Unexpected empty window physical expression")
-                        })?;
-
-                        match expr_type {
-                            ExprType::WindowExpr(window_node) => {
-                                let window_node_expr = window_node
-                                    .expr
-                                    .as_ref()
-                                    .map(|e| {
-                                        parse_physical_expr(
-                                            e.as_ref(),
-                                            registry,
-                                            &physical_schema,
-                                        )
-                                    })
-                                    .transpose()?
-                                    .ok_or_else(|| {
-                                        proto_error(
-                                            "missing window_node expr expression"
-                                                .to_string(),
-                                        )
-                                    })?;
-
-                                Ok(create_window_expr(
-                                    &convert_required!(window_node.window_function)?,
-                                    name.to_owned(),
-                                    &[window_node_expr],
-                                    &[],
-                                    &[],
-                                    Arc::new(WindowFrame::new(false)),
-                                    &physical_schema,
-                                )?)
-                            }
-                            _ => Err(DataFusionError::Internal(
-                                "Invalid expression for WindowAggrExec".to_string(),
-                            )),
-                        }
+                    .map(|expr| {
+                        parse_physical_expr(expr, registry, input.schema().as_ref())
                     })
-                    .collect::<Result<Vec<_>, _>>()?;
-                //todo fill partition keys and sort keys
+                    .collect::<Result<Vec<_>>>()?;
+
                 Ok(Arc::new(WindowAggExec::try_new(
                     physical_window_expr,
                     input,
-                    Arc::new((&input_schema).try_into()?),
-                    vec![],
+                    physical_schema,
+                    partition_keys,
+                )?))
+            }
+            PhysicalPlanType::BoundedWindow(window_agg) => {
+                let input: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    window_agg.input,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let input_schema = window_agg
+                    .input_schema
+                    .as_ref()
+                    .ok_or_else(|| {
+                        DataFusionError::Internal(
+                            "input_schema in BoundedWindowAggExecNode is missing."
+                                .to_owned(),
+                        )
+                    })?
+                    .clone();
+                let physical_schema: SchemaRef =
+                    SchemaRef::new((&input_schema).try_into()?);
+
+                let physical_window_expr = parse_window_exprs(
+                    &window_agg.window_expr,
+                    &window_agg.window_expr_name,
+                    registry,
+                    &physical_schema,
+                )?;
+                let partition_keys = window_agg
+                    .partition_keys
+                    .iter()
+                    .map(|expr| {
+                        parse_physical_expr(expr, registry, input.schema().as_ref())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let partition_search_mode = match window_agg
+                    .partition_search_mode
+                    .as_ref()
+                    .ok_or_else(|| {
+                        proto_error("Missing required field partition_search_mode")
+                    })? {
+                    bounded_window_agg_exec_node::PartitionSearchMode::Linear(_) => {
+                        PartitionSearchMode::Linear
+                    }
+                    bounded_window_agg_exec_node::PartitionSearchMode::PartiallySorted(
+                        partially_sorted,
+                    ) => PartitionSearchMode::PartiallySorted(
+                        partially_sorted
+                            .columns
+                            .iter()
+                            .map(|c| *c as usize)
+                            .collect(),
+                    ),
+                    bounded_window_agg_exec_node::PartitionSearchMode::Sorted(_) => {
+                        PartitionSearchMode::Sorted
+                    }
+                };
+
+                Ok(Arc::new(BoundedWindowAggExec::try_new(
+                    physical_window_expr,
+                    input,
+                    physical_schema,
+                    partition_keys,
+                    partition_search_mode,
                 )?))
             }
             PhysicalPlanType::Aggregate(hash_agg) => {
@@ -490,15 +542,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     runtime,
                     extension_codec
                 )?;
-                let on: Vec<(Column, Column)> = hashjoin
-                    .on
-                    .iter()
-                    .map(|col| {
-                        let left = into_required!(col.left)?;
-                        let right = into_required!(col.right)?;
-                        Ok((left, right))
-                    })
-                    .collect::<Result<_>>()?;
+                let on = parse_join_on(&hashjoin.on)?;
                 let join_type = protobuf::JoinType::from_i32(hashjoin.join_type)
                     .ok_or_else(|| {
                         proto_error(format!(
@@ -509,38 +553,8 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 let filter = hashjoin
                     .filter
                     .as_ref()
-                    .map(|f| {
-                        let schema = f
-                            .schema
-                            .as_ref()
-                            .ok_or_else(|| proto_error("Missing JoinFilter schema"))?
-                            .try_into()?;
-
-                        let expression = parse_physical_expr(
-                            f.expression.as_ref().ok_or_else(|| {
-                                proto_error("Unexpected empty filter expression")
-                            })?,
-                            registry, &schema
-                        )?;
-                        let column_indices = f.column_indices
-                            .iter()
-                            .map(|i| {
-                                let side = protobuf::JoinSide::from_i32(i.side)
-                                    .ok_or_else(|| proto_error(format!(
-                                        "Received a HashJoinNode message with JoinSide in Filter {}",
-                                        i.side))
-                                    )?;
-
-                                Ok(ColumnIndex{
-                                    index: i.index as usize,
-                                    side: side.into(),
-                                })
-                            })
-                            .collect::<Result<Vec<_>>>()?;
-
-                        Ok(JoinFilter::new(expression, column_indices, schema))
-                    })
-                    .map_or(Ok(None), |v: Result<JoinFilter>| v.map(Some))?;
+                    .map(|f| parse_join_filter(f, registry))
+                    .transpose()?;
 
                 let partition_mode =
                     protobuf::PartitionMode::from_i32(hashjoin.partition_mode)
@@ -591,10 +605,160 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 )?;
                 Ok(Arc::new(CrossJoinExec::new(left, right)))
             }
+            PhysicalPlanType::SortMergeJoin(sort_merge_join) => {
+                let left: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    sort_merge_join.left,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let right: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    sort_merge_join.right,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let on = parse_join_on(&sort_merge_join.on)?;
+                let join_type = protobuf::JoinType::from_i32(sort_merge_join.join_type)
+                    .ok_or_else(|| {
+                        proto_error(format!(
+                            "Received a SortMergeJoinExecNode message with unknown JoinType {}",
+                            sort_merge_join.join_type
+                        ))
+                    })?;
+                let sort_options = sort_merge_join
+                    .sort_options
+                    .iter()
+                    .map(|options| SortOptions {
+                        descending: !options.asc,
+                        nulls_first: options.nulls_first,
+                    })
+                    .collect();
+                Ok(Arc::new(SortMergeJoinExec::try_new(
+                    left,
+                    right,
+                    on,
+                    join_type.into(),
+                    sort_options,
+                    sort_merge_join.null_equals_null,
+                )?))
+            }
+            PhysicalPlanType::NestedLoopJoin(nested_loop_join) => {
+                let left: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    nested_loop_join.left,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let right: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    nested_loop_join.right,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let join_type = protobuf::JoinType::from_i32(nested_loop_join.join_type)
+                    .ok_or_else(|| {
+                        proto_error(format!(
+                            "Received a NestedLoopJoinExecNode message with unknown JoinType {}",
+                            nested_loop_join.join_type
+                        ))
+                    })?;
+                let filter = nested_loop_join
+                    .filter
+                    .as_ref()
+                    .map(|f| parse_join_filter(f, registry))
+                    .transpose()?;
+                Ok(Arc::new(NestedLoopJoinExec::try_new(
+                    left,
+                    right,
+                    filter,
+                    &join_type.into(),
+                )?))
+            }
+            PhysicalPlanType::SymmetricHashJoin(sym_join) => {
+                let left: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    sym_join.left,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let right: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    sym_join.right,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let on = parse_join_on(&sym_join.on)?;
+                let join_type = protobuf::JoinType::from_i32(sym_join.join_type)
+                    .ok_or_else(|| {
+                        proto_error(format!(
+                            "Received a SymmetricHashJoinExecNode message with unknown JoinType {}",
+                            sym_join.join_type
+                        ))
+                    })?;
+                let filter = sym_join
+                    .filter
+                    .as_ref()
+                    .map(|f| parse_join_filter(f, registry))
+                    .transpose()?;
+                Ok(Arc::new(SymmetricHashJoinExec::try_new(
+                    left,
+                    right,
+                    on,
+                    filter,
+                    &join_type.into(),
+                    sym_join.null_equals_null,
+                )?))
+            }
             PhysicalPlanType::Empty(empty) => {
                 let schema = Arc::new(convert_required!(empty.schema)?);
                 Ok(Arc::new(EmptyExec::new(empty.produce_one_row, schema)))
             }
+            PhysicalPlanType::Unnest(unnest) => {
+                let input: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    unnest.input,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let column = into_required!(unnest.column)?;
+                let schema = Arc::new(convert_required!(unnest.schema)?);
+                Ok(Arc::new(UnnestExec::new(input, column, schema)))
+            }
+            PhysicalPlanType::Values(values) => {
+                let schema = Arc::new(convert_required!(values.schema)?);
+                let batches = parse_record_batches(&values.data)?;
+                Ok(Arc::new(ValuesExec::try_new_from_batches(schema, batches)?))
+            }
+            PhysicalPlanType::Memory(memory) => {
+                let schema: SchemaRef = Arc::new(convert_required!(memory.schema)?);
+                let partitions = memory
+                    .partitions
+                    .iter()
+                    .map(|partition| parse_record_batches(partition))
+                    .collect::<Result<Vec<_>>>()?;
+                let projection = if memory.projection.is_empty() {
+                    None
+                } else {
+                    Some(memory.projection.iter().map(|i| *i as usize).collect())
+                };
+                let exec =
+                    MemoryExec::try_new_owned_data(partitions, schema, projection)?;
+                // sort expressions refer to the projected schema
+                let projected_schema = exec.schema();
+                let sort_information = memory
+                    .sort_information
+                    .iter()
+                    .map(|expr| {
+                        parse_physical_sort_expr(expr, registry, &projected_schema)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if sort_information.is_empty() {
+                    Ok(Arc::new(exec))
+                } else {
+                    Ok(Arc::new(exec.with_sort_information(sort_information)))
+                }
+            }
             PhysicalPlanType::Sort(sort) => {
                 let input: Arc<dyn ExecutionPlan> =
                     into_physical_plan!(sort.input, registry, runtime, extension_codec)?;
@@ -795,45 +959,9 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 exec.right().to_owned(),
                 extension_codec,
             )?;
-            let on: Vec<protobuf::JoinOn> = exec
-                .on()
-                .iter()
-                .map(|tuple| protobuf::JoinOn {
-                    left: Some(protobuf::PhysicalColumn {
-                        name: tuple.0.name().to_string(),
-                        index: tuple.0.index() as u32,
-                    }),
-                    right: Some(protobuf::PhysicalColumn {
-                        name: tuple.1.name().to_string(),
-                        index: tuple.1.index() as u32,
-                    }),
-                })
-                .collect();
+            let on = serialize_join_on(exec.on());
             let join_type: protobuf::JoinType = exec.join_type().to_owned().into();
-            let filter = exec
-                .filter()
-                .as_ref()
-                .map(|f| {
-                    let expression = f.expression().to_owned().try_into()?;
-                    let column_indices = f
-                        .column_indices()
-                        .iter()
-                        .map(|i| {
-                            let side: protobuf::JoinSide = i.side.to_owned().into();
-                            protobuf::ColumnIndex {
-                                index: i.index as u32,
-                                side: side.into(),
-                            }
-                        })
-                        .collect();
-                    let schema = f.schema().try_into()?;
-                    Ok(protobuf::JoinFilter {
-                        expression: Some(expression),
-                        column_indices,
-                        schema: Some(schema),
-                    })
-                })
-                .map_or(Ok(None), |v: Result<protobuf::JoinFilter>| v.map(Some))?;
+            let filter = exec.filter().map(serialize_join_filter).transpose()?;
 
             let partition_mode = match exec.partition_mode() {
                 PartitionMode::CollectLeft => protobuf::PartitionMode::CollectLeft,
@@ -871,6 +999,164 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     },
                 ))),
             })
+        } else if let Some(exec) = plan.downcast_ref::<SortMergeJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let on = serialize_join_on(exec.on());
+            let join_type: protobuf::JoinType = exec.join_type().into();
+            let sort_options = exec
+                .sort_options()
+                .iter()
+                .map(|options| protobuf::SortOptions {
+                    asc: !options.descending,
+                    nulls_first: options.nulls_first,
+                })
+                .collect();
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::SortMergeJoin(Box::new(
+                    protobuf::SortMergeJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        on,
+                        join_type: join_type.into(),
+                        sort_options,
+                        null_equals_null: exec.null_equals_null(),
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<NestedLoopJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let join_type: protobuf::JoinType = exec.join_type().to_owned().into();
+            let filter = exec.filter().map(serialize_join_filter).transpose()?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::NestedLoopJoin(Box::new(
+                    protobuf::NestedLoopJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        join_type: join_type.into(),
+                        filter,
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<SymmetricHashJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let on = serialize_join_on(exec.on());
+            let join_type: protobuf::JoinType = exec.join_type().to_owned().into();
+            let filter = exec.filter().map(serialize_join_filter).transpose()?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::SymmetricHashJoin(Box::new(
+                    protobuf::SymmetricHashJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        on,
+                        join_type: join_type.into(),
+                        null_equals_null: exec.null_equals_null(),
+                        filter,
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<WindowAggExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+            let window_expr = exec
+                .window_expr()
+                .iter()
+                .map(|expr| expr.to_owned().try_into())
+                .collect::<Result<Vec<_>>>()?;
+            let window_expr_name = exec
+                .window_expr()
+                .iter()
+                .map(|expr| expr.name().to_owned())
+                .collect();
+            let partition_keys = exec
+                .partition_keys
+                .iter()
+                .map(|expr| expr.to_owned().try_into())
+                .collect::<Result<Vec<_>>>()?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Window(Box::new(
+                    protobuf::WindowAggExecNode {
+                        input: Some(Box::new(input)),
+                        window_expr,
+                        window_expr_name,
+                        input_schema: Some(exec.input_schema().as_ref().try_into()?),
+                        partition_keys,
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<BoundedWindowAggExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+            let window_expr = exec
+                .window_expr()
+                .iter()
+                .map(|expr| expr.to_owned().try_into())
+                .collect::<Result<Vec<_>>>()?;
+            let window_expr_name = exec
+                .window_expr()
+                .iter()
+                .map(|expr| expr.name().to_owned())
+                .collect();
+            let partition_keys = exec
+                .partition_keys
+                .iter()
+                .map(|expr| expr.to_owned().try_into())
+                .collect::<Result<Vec<_>>>()?;
+            let partition_search_mode = match &exec.partition_search_mode {
+                PartitionSearchMode::Linear => {
+                    bounded_window_agg_exec_node::PartitionSearchMode::Linear(
+                        protobuf::EmptyMessage {},
+                    )
+                }
+                PartitionSearchMode::PartiallySorted(columns) => {
+                    bounded_window_agg_exec_node::PartitionSearchMode::PartiallySorted(
+                        protobuf::PartiallySortedPartitionSearchMode {
+                            columns: columns.iter().map(|c| *c as u64).collect(),
+                        },
+                    )
+                }
+                PartitionSearchMode::Sorted => {
+                    bounded_window_agg_exec_node::PartitionSearchMode::Sorted(
+                        protobuf::EmptyMessage {},
+                    )
+                }
+            };
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::BoundedWindow(Box::new(
+                    protobuf::BoundedWindowAggExecNode {
+                        input: Some(Box::new(input)),
+                        window_expr,
+                        window_expr_name,
+                        input_schema: Some(exec.input_schema().as_ref().try_into()?),
+                        partition_keys,
+                        partition_search_mode: Some(partition_search_mode),
+                    },
+                ))),
+            })
         } else if let Some(exec) = plan.downcast_ref::<AggregateExec>() {
             let groups: Vec<bool> = exec
                 .group_expr()
@@ -961,6 +1247,61 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     },
                 )),
             })
+        } else if let Some(exec) = plan.downcast_ref::<UnnestExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Unnest(Box::new(
+                    protobuf::UnnestExecNode {
+                        input: Some(Box::new(input)),
+                        column: Some(protobuf::PhysicalColumn {
+                            name: exec.column().name().to_string(),
+                            index: exec.column().index() as u32,
+                        }),
+                        schema: Some(exec.schema().as_ref().try_into()?),
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<ValuesExec>() {
+            let schema = exec.schema();
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Values(
+                    protobuf::ValuesExecNode {
+                        schema: Some(schema.as_ref().try_into()?),
+                        data: serialize_record_batches(&schema, &exec.data())?,
+                    },
+                )),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<MemoryExec>() {
+            let schema = exec.original_schema();
+            let partitions = exec
+                .partitions()
+                .iter()
+                .map(|partition| serialize_record_batches(&schema, partition))
+                .collect::<Result<Vec<_>>>()?;
+            let projection = exec
+                .projection()
+                .as_ref()
+                .map(|p| p.iter().map(|i| *i as u32).collect())
+                .unwrap_or_default();
+            let sort_information = exec
+                .sort_information()
+                .unwrap_or_default()
+                .iter()
+                .map(|expr| expr.try_into())
+                .collect::<Result<Vec<_>>>()?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Memory(
+                    protobuf::MemoryExecNode {
+                        schema: Some(schema.as_ref().try_into()?),
+                        partitions,
+                        projection,
+                        sort_information,
+                    },
+                )),
+            })
         } else if let Some(coalesce_batches) = plan.downcast_ref::<CoalesceBatchesExec>()
         {
             let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
@@ -982,6 +1323,10 @@ impl AsExecutionPlan for PhysicalPlanNode {
                         base_conf: Some(exec.base_config().try_into()?),
                         has_header: exec.has_header(),
                         delimiter: csv_delimiter_to_string(exec.delimiter())?,
+                        file_compression_type: exec
+                            .file_compression_type()
+                            .get_variant()
+                            .to_string(),
                     },
                 )),
             })
@@ -1006,6 +1351,18 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     },
                 )),
             })
+        } else if let Some(exec) = plan.downcast_ref::<NdJsonExec>() {
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::JsonScan(
+                    protobuf::JsonScanExecNode {
+                        base_conf: Some(exec.base_config().try_into()?),
+                        file_compression_type: exec
+                            .file_compression_type()
+                            .get_variant()
+                            .to_string(),
+                    },
+                )),
+            })
         } else if let Some(exec) = plan.downcast_ref::<CoalescePartitionsExec>() {
             let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
                 exec.input().to_owned(),
@@ -1155,6 +1512,119 @@ impl AsExecutionPlan for PhysicalPlanNode {
     }
 }
 
+fn parse_window_exprs(
+    exprs: &[protobuf::PhysicalExprNode],
+    names: &[String],
+    registry: &dyn FunctionRegistry,
+    input_schema: &Schema,
+) -> Result<Vec<Arc<dyn WindowExpr>>> {
+    exprs
+        .iter()
+        .zip(names.iter())
+        .map(|(expr, name)| {
+            let expr_type = expr.expr_type.as_ref().ok_or_else(|| {
+                proto_error("Unexpected empty window physical expression")
+            })?;
+
+            match expr_type {
+                ExprType::WindowExpr(window_node) => parse_physical_window_expr(
+                    window_node,
+                    registry,
+                    input_schema,
+                    name.to_owned(),
+                ),
+                _ => Err(DataFusionError::Internal(
+                    "Invalid expression for WindowAggrExec".to_string(),
+                )),
+            }
+        })
+        .collect()
+}
+
+fn parse_join_on(on: &[protobuf::JoinOn]) -> Result<Vec<(Column, Column)>> {
+    on.iter()
+        .map(|col| {
+            let left = into_required!(col.left)?;
+            let right = into_required!(col.right)?;
+            Ok((left, right))
+        })
+        .collect()
+}
+
+fn parse_join_filter(
+    f: &protobuf::JoinFilter,
+    registry: &dyn FunctionRegistry,
+) -> Result<JoinFilter> {
+    let schema = f
+        .schema
+        .as_ref()
+        .ok_or_else(|| proto_error("Missing JoinFilter schema"))?
+        .try_into()?;
+
+    let expression = parse_physical_expr(
+        f.expression
+            .as_ref()
+            .ok_or_else(|| proto_error("Unexpected empty filter expression"))?,
+        registry,
+        &schema,
+    )?;
+    let column_indices = f
+        .column_indices
+        .iter()
+        .map(|i| {
+            let side = protobuf::JoinSide::from_i32(i.side).ok_or_else(|| {
+                proto_error(format!(
+                    "Received a join message with JoinSide in Filter {}",
+                    i.side
+                ))
+            })?;
+
+            Ok(ColumnIndex {
+                index: i.index as usize,
+                side: side.into(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(JoinFilter::new(expression, column_indices, schema))
+}
+
+fn serialize_join_on(on: &[(Column, Column)]) -> Vec<protobuf::JoinOn> {
+    on.iter()
+        .map(|tuple| protobuf::JoinOn {
+            left: Some(protobuf::PhysicalColumn {
+                name: tuple.0.name().to_string(),
+                index: tuple.0.index() as u32,
+            }),
+            right: Some(protobuf::PhysicalColumn {
+                name: tuple.1.name().to_string(),
+                index: tuple.1.index() as u32,
+            }),
+        })
+        .collect()
+}
+
+fn serialize_join_filter(f: &JoinFilter) -> Result<protobuf::JoinFilter> {
+    let expression = f.expression().to_owned().try_into()?;
+    let column_indices = f
+        .column_indices()
+        .iter()
+        .map(|i| {
+            let side: protobuf::JoinSide = i.side.to_owned().into();
+            protobuf::ColumnIndex {
+                index: i.index as u32,
+                side: side.into(),
+            }
+        })
+        .collect();
+    let schema = f.schema().try_into()?;
+    Ok(protobuf::JoinFilter {
+        expression: Some(expression),
+        column_indices,
+        schema: Some(schema),
+    })
+}
+
 pub trait AsExecutionPlan: Debug + Send + Sync + Clone {
     fn try_decode(buf: &[u8]) -> Result<Self>
     where
@@ -1237,12 +1707,17 @@ mod roundtrip_tests {
 
     use super::super::protobuf;
     use crate::physical_plan::{AsExecutionPlan, DefaultPhysicalExtensionCodec};
-    use datafusion::arrow::array::ArrayRef;
+    use datafusion::arrow::array::{ArrayRef, Int64Array, StringArray};
     use datafusion::arrow::datatypes::IntervalUnit;
+    use datafusion::arrow::record_batch::RecordBatch;
+    use datafusion::datasource::file_format::file_type::FileCompressionType;
     use datafusion::datasource::object_store::ObjectStoreUrl;
     use datafusion::execution::context::ExecutionProps;
     use datafusion::logical_expr::create_udf;
-    use datafusion::logical_expr::{BuiltinScalarFunction, Volatility};
+    use datafusion::logical_expr::{
+        AggregateFunction, BuiltInWindowFunction, BuiltinScalarFunction, Volatility,
+        WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunction,
+    };
     use datafusion::physical_expr::expressions::in_list;
     use datafusion::physical_expr::ScalarFunctionExpr;
     use datafusion::physical_plan::aggregates::PhysicalGroupBy;
@@ -1250,7 +1725,17 @@ mod roundtrip_tests {
         date_time_interval_expr, like, BinaryExpr, GetIndexedFieldExpr,
     };
     use datafusion::physical_plan::functions::make_scalar_function;
+    use datafusion::physical_plan::joins::utils::{ColumnIndex, JoinFilter, JoinSide};
+    use datafusion::physical_plan::joins::{
+        NestedLoopJoinExec, SortMergeJoinExec, SymmetricHashJoinExec,
+    };
+    use datafusion::physical_plan::memory::MemoryExec;
     use datafusion::physical_plan::projection::ProjectionExec;
+    use datafusion::physical_plan::unnest::UnnestExec;
+    use datafusion::physical_plan::values::ValuesExec;
+    use datafusion::physical_plan::windows::{
+        create_window_expr, BoundedWindowAggExec, PartitionSearchMode, WindowAggExec,
+    };
     use datafusion::physical_plan::{functions, udaf};
     use datafusion::{
         arrow::{
@@ -1264,7 +1749,7 @@ mod roundtrip_tests {
             empty::EmptyExec,
             expressions::{binary, col, lit, NotExpr},
             expressions::{Avg, Column, DistinctCount, PhysicalSortExpr},
-            file_format::{FileScanConfig, ParquetExec},
+            file_format::{CsvExec, FileScanConfig, NdJsonExec, ParquetExec},
             filter::FilterExec,
             joins::{HashJoinExec, PartitionMode},
             limit::{GlobalLimitExec, LocalLimitExec},
@@ -1402,6 +1887,231 @@ mod roundtrip_tests {
         Ok(())
     }
 
+    #[test]
+    fn roundtrip_sort_merge_join() -> Result<()> {
+        let field_a = Field::new("col", DataType::Int64, false);
+        let schema_left = Arc::new(Schema::new(vec![field_a.clone()]));
+        let schema_right = Arc::new(Schema::new(vec![field_a]));
+        let on = vec![(Column::new("col", 0), Column::new("col", 0))];
+
+        for join_type in &[
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftAnti,
+            JoinType::LeftSemi,
+        ] {
+            roundtrip_test(Arc::new(SortMergeJoinExec::try_new(
+                Arc::new(EmptyExec::new(false, schema_left.clone())),
+                Arc::new(EmptyExec::new(false, schema_right.clone())),
+                on.clone(),
+                *join_type,
+                vec![SortOptions {
+                    descending: true,
+                    nulls_first: false,
+                }],
+                true,
+            )?))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip_nested_loop_join() -> Result<()> {
+        let schema_left =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let schema_right =
+            Arc::new(Schema::new(vec![Field::new("b", DataType::Int64, false)]));
+
+        let filter_schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, false),
+        ]);
+        let filter = JoinFilter::new(
+            binary(
+                col("a", &filter_schema)?,
+                Operator::Gt,
+                col("b", &filter_schema)?,
+                &filter_schema,
+            )?,
+            vec![
+                ColumnIndex {
+                    index: 0,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 0,
+                    side: JoinSide::Right,
+                },
+            ],
+            filter_schema,
+        );
+
+        for join_type in &[JoinType::Inner, JoinType::Left, JoinType::Full] {
+            roundtrip_test(Arc::new(NestedLoopJoinExec::try_new(
+                Arc::new(EmptyExec::new(false, schema_left.clone())),
+                Arc::new(EmptyExec::new(false, schema_right.clone())),
+                Some(filter.clone()),
+                join_type,
+            )?))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip_symmetric_hash_join() -> Result<()> {
+        let field_a = Field::new("col", DataType::Int64, false);
+        let schema_left = Arc::new(Schema::new(vec![field_a.clone()]));
+        let schema_right = Arc::new(Schema::new(vec![field_a]));
+        let on = vec![(Column::new("col", 0), Column::new("col", 0))];
+
+        for join_type in &[
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+        ] {
+            roundtrip_test(Arc::new(SymmetricHashJoinExec::try_new(
+                Arc::new(EmptyExec::new(false, schema_left.clone())),
+                Arc::new(EmptyExec::new(false, schema_right.clone())),
+                on.clone(),
+                None,
+                join_type,
+                false,
+            )?))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip_window() -> Result<()> {
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Int64, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+
+        let partition_by = vec![col("a", &schema)?];
+        let order_by = vec![PhysicalSortExpr {
+            expr: col("b", &schema)?,
+            options: SortOptions::default(),
+        }];
+        let sliding_frame = Arc::new(WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(ScalarValue::UInt64(Some(1))),
+            end_bound: WindowFrameBound::CurrentRow,
+        });
+
+        let window_expr = vec![
+            create_window_expr(
+                &WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+                "row_number".to_string(),
+                &[],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::Lag),
+                "lag".to_string(),
+                &[col("b", &schema)?, lit(2i64), lit(0i64)],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::Lead),
+                "lead".to_string(),
+                &[col("b", &schema)?],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::Ntile),
+                "ntile".to_string(),
+                &[lit(3i64)],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::NthValue),
+                "nth_value".to_string(),
+                &[col("b", &schema)?, lit(2i64)],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::AggregateFunction(AggregateFunction::Sum),
+                "plain_sum".to_string(),
+                &[col("b", &schema)?],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::AggregateFunction(AggregateFunction::Sum),
+                "sliding_sum".to_string(),
+                &[col("b", &schema)?],
+                &partition_by,
+                &order_by,
+                sliding_frame,
+                &schema,
+            )?,
+        ];
+
+        roundtrip_test(Arc::new(WindowAggExec::try_new(
+            window_expr,
+            Arc::new(EmptyExec::new(false, schema.clone())),
+            schema.clone(),
+            partition_by,
+        )?))
+    }
+
+    #[test]
+    fn roundtrip_bounded_window() -> Result<()> {
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Int64, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+
+        let partition_by = vec![col("a", &schema)?];
+        let order_by = vec![PhysicalSortExpr {
+            expr: col("b", &schema)?,
+            options: SortOptions::default(),
+        }];
+
+        for partition_search_mode in [
+            PartitionSearchMode::Linear,
+            PartitionSearchMode::PartiallySorted(vec![0]),
+            PartitionSearchMode::Sorted,
+        ] {
+            let window_expr = vec![create_window_expr(
+                &WindowFunction::AggregateFunction(AggregateFunction::Count),
+                "count".to_string(),
+                &[col("b", &schema)?],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?];
+            roundtrip_test(Arc::new(BoundedWindowAggExec::try_new(
+                window_expr,
+                Arc::new(EmptyExec::new(false, schema.clone())),
+                schema.clone(),
+                partition_by.clone(),
+                partition_search_mode,
+            )?))?;
+        }
+        Ok(())
+    }
+
     #[test]
     fn rountrip_aggregate() -> Result<()> {
         let field_a = Field::new("a", DataType::Int64, false);
@@ -1622,6 +2332,128 @@ mod roundtrip_tests {
         )))
     }
 
+    #[test]
+    fn roundtrip_json_exec() -> Result<()> {
+        let scan_config = FileScanConfig {
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_schema: Arc::new(Schema::new(vec![Field::new(
+                "col",
+                DataType::Utf8,
+                false,
+            )])),
+            file_groups: vec![vec![PartitionedFile::new(
+                "/path/to/file.json".to_string(),
+                1024,
+            )]],
+            statistics: Statistics::default(),
+            projection: None,
+            limit: Some(10),
+            table_partition_cols: vec![],
+            output_ordering: None,
+            infinite_source: false,
+        };
+
+        roundtrip_test(Arc::new(NdJsonExec::new(
+            scan_config,
+            FileCompressionType::GZIP,
+        )))
+    }
+
+    #[test]
+    fn roundtrip_csv_exec() -> Result<()> {
+        let scan_config = FileScanConfig {
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_schema: Arc::new(Schema::new(vec![Field::new(
+                "col",
+                DataType::Utf8,
+                false,
+            )])),
+            file_groups: vec![vec![PartitionedFile::new(
+                "/path/to/file.csv.zst".to_string(),
+                1024,
+            )]],
+            statistics: Statistics::default(),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            output_ordering: None,
+            infinite_source: false,
+        };
+
+        roundtrip_test(Arc::new(CsvExec::new(
+            scan_config,
+            true,
+            b'|',
+            FileCompressionType::ZSTD,
+        )))
+    }
+
+    #[test]
+    fn roundtrip_unnest() -> Result<()> {
+        let input_schema = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            DataType::List(Arc::new(Field::new("item", DataType::Int64, true))),
+            true,
+        )]));
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+
+        roundtrip_test(Arc::new(UnnestExec::new(
+            Arc::new(EmptyExec::new(false, input_schema)),
+            Column::new("a", 0),
+            schema,
+        )))
+    }
+
+    #[test]
+    fn roundtrip_values() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("x"), None, Some("z")])),
+            ],
+        )?;
+
+        roundtrip_test(Arc::new(ValuesExec::try_new_from_batches(
+            schema,
+            vec![batch],
+        )?))
+    }
+
+    #[test]
+    fn roundtrip_memory() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("x"), None, Some("z")])),
+            ],
+        )?;
+        let partitions = vec![vec![batch.clone(), batch], vec![]];
+
+        roundtrip_test(Arc::new(MemoryExec::try_new(
+            &partitions,
+            schema.clone(),
+            None,
+        )?))?;
+
+        // projection and sort information refer to the projected schema
+        let exec = MemoryExec::try_new(&partitions, schema, Some(vec![1]))?
+            .with_sort_information(vec![PhysicalSortExpr {
+                expr: Arc::new(Column::new("b", 0)),
+                options: SortOptions::default(),
+            }]);
+        roundtrip_test(Arc::new(exec))
+    }
+
     #[test]
     fn roundtrip_builtin_scalar_function() -> Result<()> {
         let field_a = Field::new("a", DataType::Int64, false);
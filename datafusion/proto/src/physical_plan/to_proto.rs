@@ -22,7 +22,16 @@ use std::{
     sync::Arc,
 };
 
-use datafusion::physical_plan::expressions::{CastExpr, TryCastExpr};
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::ipc::writer::StreamWriter;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::physical_expr::window::{
+    BuiltInWindowExpr, NthValueKind, PlainAggregateWindowExpr, SlidingAggregateWindowExpr,
+};
+use datafusion::physical_plan::expressions::{
+    CastExpr, CumeDist, NthValue, Ntile, PhysicalSortExpr, Rank, RankType, RowNumber,
+    TryCastExpr, WindowShift,
+};
 use datafusion::physical_plan::ColumnStatistics;
 use datafusion::physical_plan::{
     expressions::{
@@ -39,7 +48,7 @@ use datafusion::physical_plan::expressions::{Count, DistinctCount, Literal};
 use datafusion::physical_plan::expressions::{
     Avg, BinaryExpr, Column, LikeExpr, Max, Min, Sum,
 };
-use datafusion::physical_plan::{AggregateExpr, PhysicalExpr};
+use datafusion::physical_plan::{AggregateExpr, PhysicalExpr, WindowExpr};
 
 use crate::protobuf;
 use crate::protobuf::{physical_aggregate_expr_node, PhysicalSortExprNode, ScalarValue};
@@ -171,6 +180,158 @@ impl TryFrom<Arc<dyn AggregateExpr>> for protobuf::PhysicalExprNode {
     }
 }
 
+impl TryFrom<Arc<dyn WindowExpr>> for protobuf::PhysicalExprNode {
+    type Error = DataFusionError;
+
+    fn try_from(window_expr: Arc<dyn WindowExpr>) -> Result<Self, Self::Error> {
+        use protobuf::physical_window_expr_node::WindowFunction;
+        use protobuf::BuiltInWindowFunction;
+
+        let expr = window_expr.as_any();
+        let mut args = window_expr.expressions();
+
+        let window_function = if let Some(built_in_window_expr) =
+            expr.downcast_ref::<BuiltInWindowExpr>()
+        {
+            let built_in_fn_expr = built_in_window_expr.get_built_in_func_expr().as_any();
+            let built_in_fn = if built_in_fn_expr.downcast_ref::<RowNumber>().is_some() {
+                BuiltInWindowFunction::RowNumber
+            } else if let Some(rank_expr) = built_in_fn_expr.downcast_ref::<Rank>() {
+                match rank_expr.get_type() {
+                    RankType::Basic => BuiltInWindowFunction::Rank,
+                    RankType::Dense => BuiltInWindowFunction::DenseRank,
+                    RankType::Percent => BuiltInWindowFunction::PercentRank,
+                }
+            } else if built_in_fn_expr.downcast_ref::<CumeDist>().is_some() {
+                BuiltInWindowFunction::CumeDist
+            } else if let Some(ntile_expr) = built_in_fn_expr.downcast_ref::<Ntile>() {
+                args.insert(
+                    0,
+                    Arc::new(Literal::new(datafusion_common::ScalarValue::Int64(Some(
+                        ntile_expr.get_n() as i64,
+                    )))),
+                );
+                BuiltInWindowFunction::Ntile
+            } else if let Some(window_shift_expr) =
+                built_in_fn_expr.downcast_ref::<WindowShift>()
+            {
+                // lead() stores a negated offset, see `lead_lag::lead`
+                let shift_offset = window_shift_expr.get_shift_offset();
+                let (built_in_fn, shift_offset) = if shift_offset >= 0 {
+                    (BuiltInWindowFunction::Lag, shift_offset)
+                } else {
+                    (BuiltInWindowFunction::Lead, -shift_offset)
+                };
+                args.push(Arc::new(Literal::new(
+                    datafusion_common::ScalarValue::Int64(Some(shift_offset)),
+                )));
+                if let Some(default_value) = window_shift_expr.get_default_value() {
+                    args.push(Arc::new(Literal::new(default_value)));
+                }
+                built_in_fn
+            } else if let Some(nth_value_expr) =
+                built_in_fn_expr.downcast_ref::<NthValue>()
+            {
+                match nth_value_expr.get_kind() {
+                    NthValueKind::First => BuiltInWindowFunction::FirstValue,
+                    NthValueKind::Last => BuiltInWindowFunction::LastValue,
+                    NthValueKind::Nth(n) => {
+                        args.push(Arc::new(Literal::new(
+                            datafusion_common::ScalarValue::Int64(Some(n as i64)),
+                        )));
+                        BuiltInWindowFunction::NthValue
+                    }
+                }
+            } else {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "BuiltIn window function not supported: {window_expr:?}"
+                )));
+            };
+            WindowFunction::BuiltInFunction(built_in_fn as i32)
+        } else {
+            let aggr_expr = if let Some(plain_aggr_window_expr) =
+                expr.downcast_ref::<PlainAggregateWindowExpr>()
+            {
+                plain_aggr_window_expr.get_aggregate_expr()
+            } else if let Some(sliding_aggr_window_expr) =
+                expr.downcast_ref::<SlidingAggregateWindowExpr>()
+            {
+                sliding_aggr_window_expr.get_aggregate_expr()
+            } else {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "WindowExpr not supported: {window_expr:?}"
+                )));
+            };
+            let aggr_expr: protobuf::PhysicalExprNode = aggr_expr.clone().try_into()?;
+            match aggr_expr.expr_type {
+                Some(protobuf::physical_expr_node::ExprType::AggregateExpr(
+                    protobuf::PhysicalAggregateExprNode {
+                        aggregate_function: Some(aggregate_function),
+                        distinct: false,
+                        ..
+                    },
+                )) => match aggregate_function {
+                    physical_aggregate_expr_node::AggregateFunction::AggrFunction(i) => {
+                        WindowFunction::AggrFunction(i)
+                    }
+                    physical_aggregate_expr_node::AggregateFunction::UserDefinedAggrFunction(
+                        name,
+                    ) => WindowFunction::UserDefinedAggrFunction(name),
+                },
+                _ => {
+                    return Err(DataFusionError::NotImplemented(format!(
+                        "Aggregate window function not supported: {window_expr:?}"
+                    )))
+                }
+            }
+        };
+
+        let args = args
+            .into_iter()
+            .map(|e| e.try_into())
+            .collect::<Result<Vec<_>>>()?;
+
+        let partition_by = window_expr
+            .partition_by()
+            .iter()
+            .map(|e| e.clone().try_into())
+            .collect::<Result<Vec<_>>>()?;
+
+        let order_by = window_expr
+            .order_by()
+            .iter()
+            .map(|e| e.try_into())
+            .collect::<Result<Vec<_>>>()?;
+
+        let window_frame: protobuf::WindowFrame =
+            window_expr.get_window_frame().as_ref().try_into()?;
+
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::WindowExpr(
+                protobuf::PhysicalWindowExprNode {
+                    args,
+                    partition_by,
+                    order_by,
+                    window_frame: Some(window_frame),
+                    window_function: Some(window_function),
+                },
+            )),
+        })
+    }
+}
+
+impl TryFrom<&PhysicalSortExpr> for protobuf::PhysicalSortExprNode {
+    type Error = DataFusionError;
+
+    fn try_from(sort_expr: &PhysicalSortExpr) -> Result<Self, Self::Error> {
+        Ok(protobuf::PhysicalSortExprNode {
+            expr: Some(Box::new(sort_expr.expr.clone().try_into()?)),
+            asc: !sort_expr.options.descending,
+            nulls_first: sort_expr.options.nulls_first,
+        })
+    }
+}
+
 impl TryFrom<Arc<dyn PhysicalExpr>> for protobuf::PhysicalExprNode {
     type Error = DataFusionError;
 
@@ -530,3 +691,19 @@ impl TryFrom<Option<Arc<dyn PhysicalExpr>>> for protobuf::MaybeFilter {
         }
     }
 }
+
+/// Encodes record batches in the Arrow IPC stream format.
+pub(crate) fn serialize_record_batches(
+    schema: &Schema,
+    batches: &[RecordBatch],
+) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    {
+        let mut writer = StreamWriter::try_new(&mut buf, schema)?;
+        for batch in batches {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+    }
+    Ok(buf)
+}
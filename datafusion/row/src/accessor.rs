@@ -15,13 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! [`RowAccessor`] provides a Read/Write/Modify access for row with all fixed-sized fields:
+//! [`RowAccessor`] provides a Read/Write/Modify access for row with fixed-sized and variable-length fields:
 
-use crate::layout::RowLayout;
+use crate::layout::{decode_var_length, encode_var_length, RowLayout};
 use crate::validity::NullBitsFormatter;
 use crate::{fn_get_idx, fn_get_idx_opt, fn_set_idx};
 use arrow::datatypes::{DataType, Schema};
-use arrow::util::bit_util::{get_bit_raw, set_bit_raw};
+use arrow::util::bit_util::{get_bit_raw, round_upto_power_of_2, set_bit_raw};
 use datafusion_common::ScalarValue;
 use std::sync::Arc;
 
@@ -30,6 +30,9 @@ use std::sync::Arc;
 /// Provides read/write/modify access to a tuple stored in Row format
 /// at `data[base_offset..]`
 ///
+/// Setting a variable-length field may grow `data` to make room for
+/// the new value in the variable-length region of the tuple.
+///
 /// ```text
 /// Set / Update data
 ///     in [u8]
@@ -45,8 +48,8 @@ use std::sync::Arc;
 pub struct RowAccessor<'a> {
     /// Layout on how to read each field
     layout: Arc<RowLayout>,
-    /// Raw bytes slice where the tuple stores
-    data: &'a mut [u8],
+    /// Raw bytes where the tuple stores
+    data: Option<&'a mut Vec<u8>>,
     /// Start position for the current tuple in the raw bytes slice.
     base_offset: usize,
 }
@@ -102,6 +105,25 @@ macro_rules! fn_max_min_idx {
     };
 }
 
+macro_rules! fn_max_min_var_length_idx {
+    ($NAME: ident, $NATIVE: ty, $OP: ident, $CMP: tt) => {
+        paste::item! {
+            /// check max/min then update, comparing values byte-wise
+            #[inline(always)]
+            pub fn [<$OP _ $NAME>](&mut self, idx: usize, value: &$NATIVE) {
+                if self.is_valid_at(idx) {
+                    if value $CMP self.[<get_ $NAME>](idx) {
+                        self.set_var_length(idx, value.as_ref());
+                    }
+                } else {
+                    self.set_non_null_at(idx);
+                    self.set_var_length(idx, value.as_ref());
+                }
+            }
+        }
+    };
+}
+
 macro_rules! fn_get_idx_scalar {
     ($NATIVE: ident, $SCALAR:ident) => {
         paste::item! {
@@ -122,7 +144,7 @@ impl<'a> RowAccessor<'a> {
     pub fn new(schema: &Schema) -> Self {
         Self {
             layout: Arc::new(RowLayout::new(schema)),
-            data: &mut [],
+            data: None,
             base_offset: 0,
         }
     }
@@ -130,15 +152,30 @@ impl<'a> RowAccessor<'a> {
     pub fn new_from_layout(layout: Arc<RowLayout>) -> Self {
         Self {
             layout,
-            data: &mut [],
+            data: None,
             base_offset: 0,
         }
     }
 
     /// Update this row to point to position `offset` in `base`
-    pub fn point_to(&mut self, offset: usize, data: &'a mut [u8]) {
+    pub fn point_to(&mut self, offset: usize, data: &'a mut Vec<u8>) {
         self.base_offset = offset;
-        self.data = data;
+        self.data = Some(data);
+    }
+
+    #[inline(always)]
+    fn data(&self) -> &[u8] {
+        match self.data.as_deref() {
+            Some(data) => data.as_slice(),
+            None => &[],
+        }
+    }
+
+    #[inline(always)]
+    fn data_mut(&mut self) -> &mut Vec<u8> {
+        self.data
+            .as_deref_mut()
+            .expect("RowAccessor should point to a row before being updated")
     }
 
     #[inline]
@@ -162,7 +199,7 @@ impl<'a> RowAccessor<'a> {
             &[]
         } else {
             let start = self.base_offset;
-            &self.data[start..start + self.layout.null_width]
+            &self.data()[start..start + self.layout.null_width]
         }
     }
 
@@ -177,14 +214,14 @@ impl<'a> RowAccessor<'a> {
     fn get_bool(&self, idx: usize) -> bool {
         self.assert_index_valid(idx);
         let offset = self.field_offsets()[idx];
-        let value = &self.data[self.base_offset + offset..];
+        let value = &self.data()[self.base_offset + offset..];
         value[0] != 0
     }
 
     fn get_u8(&self, idx: usize) -> u8 {
         self.assert_index_valid(idx);
         let offset = self.field_offsets()[idx];
-        self.data[self.base_offset + offset]
+        self.data()[self.base_offset + offset]
     }
 
     fn_get_idx!(u16, 2);
@@ -198,6 +235,20 @@ impl<'a> RowAccessor<'a> {
     fn_get_idx!(f64, 8);
     fn_get_idx!(i128, 16);
 
+    fn get_var_length(&self, idx: usize) -> &[u8] {
+        let (offset, len) = decode_var_length(self.get_u64(idx));
+        let start = self.base_offset + offset;
+        &self.data()[start..start + len]
+    }
+
+    fn get_utf8(&self, idx: usize) -> &str {
+        std::str::from_utf8(self.get_var_length(idx)).unwrap()
+    }
+
+    fn get_binary(&self, idx: usize) -> &[u8] {
+        self.get_var_length(idx)
+    }
+
     fn_get_idx_opt!(bool);
     fn_get_idx_opt!(u8);
     fn_get_idx_opt!(u16);
@@ -231,6 +282,30 @@ impl<'a> RowAccessor<'a> {
         }
     }
 
+    fn get_utf8_scalar(&self, idx: usize) -> ScalarValue {
+        if self.is_valid_at(idx) {
+            ScalarValue::Utf8(Some(self.get_utf8(idx).to_string()))
+        } else {
+            ScalarValue::Utf8(None)
+        }
+    }
+
+    fn get_large_utf8_scalar(&self, idx: usize) -> ScalarValue {
+        if self.is_valid_at(idx) {
+            ScalarValue::LargeUtf8(Some(self.get_utf8(idx).to_string()))
+        } else {
+            ScalarValue::LargeUtf8(None)
+        }
+    }
+
+    fn get_binary_scalar(&self, idx: usize) -> ScalarValue {
+        if self.is_valid_at(idx) {
+            ScalarValue::Binary(Some(self.get_binary(idx).to_vec()))
+        } else {
+            ScalarValue::Binary(None)
+        }
+    }
+
     pub fn get_as_scalar(&self, dt: &DataType, index: usize) -> ScalarValue {
         match dt {
             DataType::Boolean => self.get_bool_scalar(index),
@@ -245,6 +320,9 @@ impl<'a> RowAccessor<'a> {
             DataType::Float32 => self.get_f32_scalar(index),
             DataType::Float64 => self.get_f64_scalar(index),
             DataType::Decimal128(p, s) => self.get_decimal128_scalar(index, *p, *s),
+            DataType::Utf8 => self.get_utf8_scalar(index),
+            DataType::LargeUtf8 => self.get_large_utf8_scalar(index),
+            DataType::Binary => self.get_binary_scalar(index),
             _ => unreachable!(),
         }
     }
@@ -258,7 +336,8 @@ impl<'a> RowAccessor<'a> {
             !self.null_free(),
             "Unexpected call to set_non_null_at on null-free row writer"
         );
-        let null_bits = &mut self.data[0..self.layout.null_width];
+        let null_width = self.layout.null_width;
+        let null_bits = &mut self.data_mut()[0..null_width];
         unsafe {
             set_bit_raw(null_bits.as_mut_ptr(), idx);
         }
@@ -267,7 +346,7 @@ impl<'a> RowAccessor<'a> {
     fn set_u8(&mut self, idx: usize, value: u8) {
         self.assert_index_valid(idx);
         let offset = self.field_offsets()[idx];
-        self.data[offset] = value;
+        self.data_mut()[offset] = value;
     }
 
     fn_set_idx!(u16, 2);
@@ -283,7 +362,62 @@ impl<'a> RowAccessor<'a> {
     fn set_i8(&mut self, idx: usize, value: i8) {
         self.assert_index_valid(idx);
         let offset = self.field_offsets()[idx];
-        self.data[offset] = value.to_le_bytes()[0];
+        self.data_mut()[offset] = value.to_le_bytes()[0];
+    }
+
+    // ------------------------------
+    // --- Variable length setters --
+    // ------------------------------
+
+    /// Set the variable-length field at `idx` to `value`.
+    ///
+    /// The value is written in place when it fits the space of the previous value,
+    /// or when the previous value is the last one of the row, which is resized.
+    /// Otherwise the other values are compacted, dropping the space of the previous
+    /// value, and `value` is appended to the end of the row.
+    fn set_var_length(&mut self, idx: usize, value: &[u8]) {
+        let (current_offset, current_len) = decode_var_length(self.get_u64(idx));
+        let current_end = current_offset + round_upto_power_of_2(current_len, 8);
+        let len = value.len();
+        let padded_len = round_upto_power_of_2(len, 8);
+        let offset = if current_offset != 0 && current_end == self.data().len() {
+            self.data_mut().resize(current_offset + padded_len, 0);
+            current_offset
+        } else if current_offset != 0 && current_offset + padded_len <= current_end {
+            current_offset
+        } else {
+            self.compact_var_length(idx);
+            let data = self.data_mut();
+            let offset = data.len();
+            data.resize(offset + padded_len, 0);
+            offset
+        };
+        self.data_mut()[offset..offset + len].copy_from_slice(value);
+        self.set_u64(idx, encode_var_length(offset, len));
+    }
+
+    /// Move the values of the variable-length fields other than `skip` to the start
+    /// of the variable-length region, in order, and truncate the row after them
+    fn compact_var_length(&mut self, skip: usize) {
+        let mut values: Vec<(usize, usize, usize)> = self
+            .layout
+            .var_length_fields
+            .iter()
+            .filter(|idx| **idx != skip)
+            .filter_map(|idx| {
+                let (offset, len) = decode_var_length(self.get_u64(*idx));
+                (offset != 0).then_some((offset, len, *idx))
+            })
+            .collect();
+        values.sort_unstable();
+
+        let mut end = self.layout.fixed_part_width();
+        for (offset, len, idx) in values {
+            self.data_mut().copy_within(offset..offset + len, end);
+            self.set_u64(idx, encode_var_length(end, len));
+            end += round_upto_power_of_2(len, 8);
+        }
+        self.data_mut().truncate(end);
     }
 
     // ------------------------------
@@ -325,4 +459,9 @@ impl<'a> RowAccessor<'a> {
     fn_max_min_idx!(f32, min);
     fn_max_min_idx!(f64, min);
     fn_max_min_idx!(i128, min);
+
+    fn_max_min_var_length_idx!(utf8, str, max, >);
+    fn_max_min_var_length_idx!(binary, [u8], max, >);
+    fn_max_min_var_length_idx!(utf8, str, min, <);
+    fn_max_min_var_length_idx!(binary, [u8], min, <);
 }
@@ -36,11 +36,16 @@ use arrow::util::bit_util::{ceil, round_upto_power_of_2};
 /// In the region of the values, we store the fields in the order they are defined in the schema.
 /// Each field is stored in one or multiple 8-byte words.
 ///
+/// Variable-length fields (`Utf8`, `LargeUtf8` and `Binary`) occupy a single 8-byte word in the
+/// values region, holding the offset of the bytes relative to the start of the tuple (lower 4 bytes)
+/// and their length (upper 4 bytes). The bytes themselves are stored in a variable-length region
+/// appended right after the fixed part, each value padded to a multiple of 8 bytes.
+///
 /// ```plaintext
-/// ┌─────────────────┬─────────────────────┐
-/// │Validity Bitmask │      Fields         │
-/// │ (8-byte aligned)│   (8-byte words)    │
-/// └─────────────────┴─────────────────────┘
+/// ┌─────────────────┬─────────────────────┬─────────────────────────┐
+/// │Validity Bitmask │      Fields         │ Variable length region  │
+/// │ (8-byte aligned)│   (8-byte words)    │ (8-byte aligned values) │
+/// └─────────────────┴─────────────────────┴─────────────────────────┘
 /// ```
 ///
 ///  For example, given the schema (Int8, Float32, Int64) with a null-free tuple
@@ -67,6 +72,17 @@ use arrow::util::bit_util::{ceil, round_upto_power_of_2};
 /// └──────────────────────────┴──────────────────────┴──────────────────────┴──────────────────────┘
 /// 0                          8                      16                     24                     32
 /// ```
+///
+///  Given the schema (Int64, Utf8) with a null-free tuple, encoding the tuple (42, "hello")
+///
+///  Requires 24 bytes (2 fields * 8 bytes each + "hello" padded to 8 bytes):
+///
+/// ```plaintext
+/// ┌──────────────────────┬──────────────────────┬──────────────────────┐
+/// │      0x0000002A      │ offset: 16, len: 5   │  "hello" (3 padding) │
+/// └──────────────────────┴──────────────────────┴──────────────────────┘
+/// 0                      8                      16                     24
+/// ```
 #[derive(Debug, Clone)]
 pub struct RowLayout {
     /// If a row is null free according to its schema
@@ -79,6 +95,8 @@ pub struct RowLayout {
     pub(crate) field_count: usize,
     /// Starting offset for each fields in the raw bytes.
     pub(crate) field_offsets: Vec<usize>,
+    /// Indices of the variable-length fields.
+    pub(crate) var_length_fields: Vec<usize>,
 }
 
 impl RowLayout {
@@ -96,12 +114,25 @@ impl RowLayout {
             round_upto_power_of_2(ceil(field_count, 8), 8)
         };
         let (field_offsets, values_width) = word_aligned_offsets(null_width, schema);
+        let var_length_fields = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| {
+                matches!(
+                    f.data_type(),
+                    DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary
+                )
+            })
+            .map(|(idx, _)| idx)
+            .collect();
         Self {
             null_free,
             null_width,
             values_width,
             field_count,
             field_offsets,
+            var_length_fields,
         }
     }
 
@@ -112,6 +143,23 @@ impl RowLayout {
     }
 }
 
+/// Encode the `offset` (relative to the start of the tuple) and `len` of a
+/// variable-length value into the 8-byte word stored in its field slot
+#[inline(always)]
+pub(crate) fn encode_var_length(offset: usize, len: usize) -> u64 {
+    assert!(
+        offset <= u32::MAX as usize && len <= u32::MAX as usize,
+        "Variable length value at offset {offset} with length {len} does not fit in a row"
+    );
+    (offset as u64) | ((len as u64) << 32)
+}
+
+/// Decode the 8-byte word of a variable-length field into `(offset, len)`
+#[inline(always)]
+pub(crate) fn decode_var_length(word: u64) -> (usize, usize) {
+    ((word & u32::MAX as u64) as usize, (word >> 32) as usize)
+}
+
 fn word_aligned_offsets(null_width: usize, schema: &Schema) -> (Vec<usize>, usize) {
     let mut offsets = vec![];
     let mut offset = null_width;
@@ -119,7 +167,8 @@ fn word_aligned_offsets(null_width: usize, schema: &Schema) -> (Vec<usize>, usiz
         offsets.push(offset);
         assert!(!matches!(f.data_type(), DataType::Decimal256(_, _)));
         // All of the current support types can fit into one single 8-bytes word except for Decimal128.
-        // For Decimal128, its width is of two 8-bytes words.
+        // For Decimal128, its width is of two 8-bytes words. Variable-length types store
+        // their (offset, length) pair in a single word.
         match f.data_type() {
             DataType::Decimal128(_, _) => offset += 16,
             _ => offset += 8,
@@ -152,6 +201,9 @@ pub fn row_supported(schema: &Schema) -> bool {
                 | Date32
                 | Date64
                 | Decimal128(_, _)
+                | Utf8
+                | LargeUtf8
+                | Binary
         )
     })
 }
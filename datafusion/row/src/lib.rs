@@ -31,8 +31,8 @@
 //!
 //! [this paper]: https://db.in.tum.de/~kersten/vectorization_vs_compilation.pdf
 
-use arrow::array::{make_builder, ArrayBuilder, ArrayRef};
-use arrow::datatypes::Schema;
+use arrow::array::{make_builder, ArrayBuilder, ArrayRef, LargeStringBuilder};
+use arrow::datatypes::{DataType, Schema};
 use arrow::error::Result as ArrowResult;
 use arrow::record_batch::RecordBatch;
 pub use layout::row_supported;
@@ -77,9 +77,12 @@ fn new_arrays(schema: &Schema, batch_size: usize) -> Vec<Box<dyn ArrayBuilder>>
     schema
         .fields()
         .iter()
-        .map(|field| {
-            let dt = field.data_type();
-            make_builder(dt, batch_size)
+        .map(|field| match field.data_type() {
+            // not supported by `make_builder` yet
+            DataType::LargeUtf8 => {
+                Box::new(LargeStringBuilder::with_capacity(batch_size, 1024)) as _
+            }
+            dt => make_builder(dt, batch_size),
         })
         .collect::<Vec<_>>()
 }
@@ -99,12 +102,13 @@ fn get_columns(mut arrays: Vec<Box<dyn ArrayBuilder>>) -> Vec<ArrayRef> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::accessor::RowAccessor;
     use crate::layout::RowLayout;
     use crate::reader::read_as_batch;
     use crate::writer::write_batch_unchecked;
     use arrow::record_batch::RecordBatch;
     use arrow::{array::*, datatypes::*};
-    use datafusion_common::Result;
+    use datafusion_common::{Result, ScalarValue};
     use DataType::*;
 
     macro_rules! fn_test_single_type {
@@ -222,6 +226,154 @@ mod tests {
         vec![Some(5), Some(7), None, Some(0), Some(111)]
     );
 
+    macro_rules! fn_test_var_length_type {
+        ($ARRAY: ident, $TYPE: expr, $VEC: expr) => {
+            paste::item! {
+                #[test]
+                #[allow(non_snake_case)]
+                fn [<test _single_ $TYPE>]() -> Result<()> {
+                    let schema = Arc::new(Schema::new(vec![Field::new("a", $TYPE, true)]));
+                    let a = $ARRAY::from($VEC);
+                    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(a)])?;
+                    // leave enough room for the variable-length region of each row
+                    let mut vector = vec![0; 1024];
+                    let row_offsets =
+                        { write_batch_unchecked(&mut vector, 0, &batch, 0, schema.clone()) };
+                    let output_batch = { read_as_batch(&vector, schema, &row_offsets)? };
+                    assert_eq!(batch, output_batch);
+                    Ok(())
+                }
+
+                #[test]
+                #[allow(non_snake_case)]
+                fn [<test_single_ $TYPE _null_free>]() -> Result<()> {
+                    let schema = Arc::new(Schema::new(vec![Field::new("a", $TYPE, false)]));
+                    let v = $VEC.into_iter().filter(|o| o.is_some()).collect::<Vec<_>>();
+                    let a = $ARRAY::from(v);
+                    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(a)])?;
+                    let mut vector = vec![0; 1024];
+                    let row_offsets =
+                        { write_batch_unchecked(&mut vector, 0, &batch, 0, schema.clone()) };
+                    let output_batch = { read_as_batch(&vector, schema, &row_offsets)? };
+                    assert_eq!(batch, output_batch);
+                    Ok(())
+                }
+            }
+        };
+    }
+
+    fn_test_var_length_type!(
+        StringArray,
+        Utf8,
+        vec![
+            Some("hello"),
+            None,
+            Some(""),
+            Some("a rather long string"),
+            Some("world")
+        ]
+    );
+
+    fn_test_var_length_type!(
+        LargeStringArray,
+        LargeUtf8,
+        vec![
+            Some("hello"),
+            None,
+            Some(""),
+            Some("a rather long string"),
+            Some("world")
+        ]
+    );
+
+    fn_test_var_length_type!(
+        BinaryArray,
+        Binary,
+        vec![
+            Some(b"hello".as_ref()),
+            None,
+            Some(b"".as_ref()),
+            Some(b"a rather long binary".as_ref()),
+            Some(b"world".as_ref())
+        ]
+    );
+
+    #[test]
+    fn test_multiple_var_length_fields() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]));
+        let b: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("datafusion"),
+            Some("arrow"),
+            None,
+        ]));
+        let c: ArrayRef = Arc::new(BinaryArray::from(vec![
+            None,
+            Some(b"row".as_ref()),
+            Some(b"variable length".as_ref()),
+        ]));
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b), ("c", c)])?;
+        let schema = batch.schema();
+        let mut vector = vec![0; 1024];
+        let row_offsets =
+            { write_batch_unchecked(&mut vector, 0, &batch, 0, schema.clone()) };
+        let output_batch = { read_as_batch(&vector, schema, &row_offsets)? };
+        assert_eq!(batch, output_batch);
+        Ok(())
+    }
+
+    #[test]
+    fn test_accessor_var_length_no_dead_space() {
+        let schema = Schema::new(vec![
+            Field::new("a", Int64, true),
+            Field::new("b", Utf8, true),
+            Field::new("c", Utf8, true),
+        ]);
+        let layout = Arc::new(RowLayout::new(&schema));
+        let fixed_width = layout.fixed_part_width();
+        let mut buffer = vec![0; fixed_width];
+        let mut accessor = RowAccessor::new_from_layout(layout);
+        accessor.point_to(0, &mut buffer);
+
+        // interleaved growing values are compacted instead of leaving their
+        // previous space behind
+        for len in 1..=64 {
+            accessor.max_utf8(1, &"b".repeat(len));
+            accessor.max_utf8(2, &"c".repeat(len));
+        }
+        accessor.min_utf8(1, "a");
+        accessor.max_utf8(1, &"z".repeat(64));
+
+        assert_eq!(
+            accessor.get_as_scalar(&Utf8, 1),
+            ScalarValue::Utf8(Some("z".repeat(64)))
+        );
+        assert_eq!(
+            accessor.get_as_scalar(&Utf8, 2),
+            ScalarValue::Utf8(Some("c".repeat(64)))
+        );
+        assert_eq!(buffer.len(), fixed_width + 64 + 64);
+    }
+
+    #[test]
+    fn test_accessor_var_length_last_value_resized() {
+        let schema = Schema::new(vec![Field::new("a", Binary, true)]);
+        let layout = Arc::new(RowLayout::new(&schema));
+        let fixed_width = layout.fixed_part_width();
+        let mut buffer = vec![0; fixed_width];
+        let mut accessor = RowAccessor::new_from_layout(layout);
+        accessor.point_to(0, &mut buffer);
+
+        for len in 1..=100 {
+            accessor.max_binary(0, &vec![1; len]);
+        }
+        accessor.min_binary(0, &[0]);
+        assert_eq!(
+            accessor.get_as_scalar(&Binary, 0),
+            ScalarValue::Binary(Some(vec![0]))
+        );
+        assert_eq!(buffer.len(), fixed_width + 8);
+    }
+
     #[test]
     fn test_single_decimal128() -> Result<()> {
         let v = vec![
@@ -275,7 +427,10 @@ mod tests {
     #[test]
     #[should_panic(expected = "not supported yet")]
     fn test_unsupported_type() {
-        let a: ArrayRef = Arc::new(StringArray::from(vec!["hello", "world"]));
+        let a: ArrayRef = Arc::new(LargeBinaryArray::from(vec![
+            b"hello".as_ref(),
+            b"world".as_ref(),
+        ]));
         let batch = RecordBatch::try_from_iter(vec![("a", a)]).unwrap();
         let schema = batch.schema();
         let mut vector = vec![0; 1024];
@@ -295,7 +450,7 @@ mod tests {
     #[test]
     #[should_panic(expected = "not supported yet")]
     fn test_unsupported_type_read() {
-        let schema = Arc::new(Schema::new(vec![Field::new("a", Utf8, false)]));
+        let schema = Arc::new(Schema::new(vec![Field::new("a", LargeBinary, false)]));
         let vector = vec![0; 1024];
         let row_offsets = vec![0];
         read_as_batch(&vector, schema, &row_offsets).unwrap();
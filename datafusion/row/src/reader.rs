@@ -17,7 +17,7 @@
 
 //! [`read_as_batch`] converts raw bytes to [`RecordBatch`]
 
-use crate::layout::RowLayout;
+use crate::layout::{decode_var_length, RowLayout};
 use crate::validity::{all_valid, NullBitsFormatter};
 use crate::MutableRecordBatch;
 use arrow::array::*;
@@ -49,8 +49,8 @@ pub fn read_as_batch(
     offsets: &[usize],
 ) -> Result<RecordBatch> {
     let row_num = offsets.len();
-    let mut output = MutableRecordBatch::new(row_num, schema.clone());
     let mut row = RowReader::new(&schema);
+    let mut output = MutableRecordBatch::new(row_num, schema.clone());
 
     for offset in offsets.iter().take(row_num) {
         row.point_to(*offset, data);
@@ -67,7 +67,7 @@ macro_rules! get_idx {
         let offset = $SELF.field_offsets()[$IDX];
         let start = $SELF.base_offset + offset;
         let end = start + $WIDTH;
-        $NATIVE::from_le_bytes($SELF.data[start..end].try_into().unwrap())
+        $NATIVE::from_le_bytes($SELF.data()[start..end].try_into().unwrap())
     }};
 }
 
@@ -80,7 +80,7 @@ macro_rules! fn_get_idx {
                 let offset = self.field_offsets()[idx];
                 let start = self.base_offset + offset;
                 let end = start + $WIDTH;
-                $NATIVE::from_le_bytes(self.data[start..end].try_into().unwrap())
+                $NATIVE::from_le_bytes(self.data()[start..end].try_into().unwrap())
             }
         }
     };
@@ -142,6 +142,11 @@ impl<'a> RowReader<'a> {
         self.data = data;
     }
 
+    #[inline(always)]
+    fn data(&self) -> &[u8] {
+        self.data
+    }
+
     #[inline]
     fn assert_index_valid(&self, idx: usize) {
         assert!(idx < self.layout.field_count);
@@ -216,6 +221,20 @@ impl<'a> RowReader<'a> {
         get_idx!(i128, self, idx, 16)
     }
 
+    fn get_var_length(&self, idx: usize) -> &[u8] {
+        let (offset, len) = decode_var_length(self.get_u64(idx));
+        let start = self.base_offset + offset;
+        &self.data[start..start + len]
+    }
+
+    fn get_utf8(&self, idx: usize) -> &str {
+        std::str::from_utf8(self.get_var_length(idx)).unwrap()
+    }
+
+    fn get_binary(&self, idx: usize) -> &[u8] {
+        self.get_var_length(idx)
+    }
+
     fn_get_idx_opt!(bool);
     fn_get_idx_opt!(u8);
     fn_get_idx_opt!(u16);
@@ -251,6 +270,22 @@ impl<'a> RowReader<'a> {
             None
         }
     }
+
+    fn get_utf8_opt(&self, idx: usize) -> Option<&str> {
+        if self.is_valid_at(idx) {
+            Some(self.get_utf8(idx))
+        } else {
+            None
+        }
+    }
+
+    fn get_binary_opt(&self, idx: usize) -> Option<&[u8]> {
+        if self.is_valid_at(idx) {
+            Some(self.get_binary(idx))
+        } else {
+            None
+        }
+    }
 }
 
 /// Read the row currently pointed by RowWriter to the output columnar batch buffer
@@ -312,6 +347,32 @@ fn_read_field!(f64, Float64Builder);
 fn_read_field!(date32, Date32Builder);
 fn_read_field!(date64, Date64Builder);
 fn_read_field!(decimal128, Decimal128Builder);
+fn_read_field!(utf8, StringBuilder);
+fn_read_field!(binary, BinaryBuilder);
+
+pub(crate) fn read_field_large_utf8(
+    to: &mut Box<dyn ArrayBuilder>,
+    col_idx: usize,
+    row: &RowReader,
+) {
+    let to = to
+        .as_any_mut()
+        .downcast_mut::<LargeStringBuilder>()
+        .unwrap();
+    to.append_option(row.get_utf8_opt(col_idx));
+}
+
+pub(crate) fn read_field_large_utf8_null_free(
+    to: &mut Box<dyn ArrayBuilder>,
+    col_idx: usize,
+    row: &RowReader,
+) {
+    let to = to
+        .as_any_mut()
+        .downcast_mut::<LargeStringBuilder>()
+        .unwrap();
+    to.append_value(row.get_utf8(col_idx));
+}
 
 fn read_field(
     to: &mut Box<dyn ArrayBuilder>,
@@ -335,6 +396,9 @@ fn read_field(
         Date32 => read_field_date32(to, col_idx, row),
         Date64 => read_field_date64(to, col_idx, row),
         Decimal128(_, _) => read_field_decimal128(to, col_idx, row),
+        Utf8 => read_field_utf8(to, col_idx, row),
+        LargeUtf8 => read_field_large_utf8(to, col_idx, row),
+        Binary => read_field_binary(to, col_idx, row),
         _ => unimplemented!(),
     }
 }
@@ -361,6 +425,9 @@ fn read_field_null_free(
         Date32 => read_field_date32_null_free(to, col_idx, row),
         Date64 => read_field_date64_null_free(to, col_idx, row),
         Decimal128(_, _) => read_field_decimal128_null_free(to, col_idx, row),
+        Utf8 => read_field_utf8_null_free(to, col_idx, row),
+        LargeUtf8 => read_field_large_utf8_null_free(to, col_idx, row),
+        Binary => read_field_binary_null_free(to, col_idx, row),
         _ => unimplemented!(),
     }
 }
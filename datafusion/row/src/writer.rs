@@ -17,19 +17,23 @@
 
 //! [`RowWriter`] writes [`RecordBatch`]es to `Vec<u8>` to stitch attributes together
 
-use crate::layout::RowLayout;
+use crate::layout::{encode_var_length, RowLayout};
 use arrow::array::*;
 use arrow::datatypes::{DataType, Schema};
 use arrow::record_batch::RecordBatch;
-use arrow::util::bit_util::{set_bit_raw, unset_bit_raw};
-use datafusion_common::cast::{as_date32_array, as_date64_array, as_decimal128_array};
+use arrow::util::bit_util::{round_upto_power_of_2, set_bit_raw, unset_bit_raw};
+use datafusion_common::cast::{
+    as_binary_array, as_date32_array, as_date64_array, as_decimal128_array,
+    as_generic_string_array, as_string_array,
+};
 use datafusion_common::Result;
 use std::sync::Arc;
 
 /// Append batch from `row_idx` to `output` buffer start from `offset`
 /// # Panics
 ///
-/// This function will panic if the output buffer doesn't have enough space to hold all the rows
+/// This function will panic if the output buffer doesn't have enough space to hold all the rows,
+/// including the variable-length region of each row
 pub fn write_batch_unchecked(
     output: &mut [u8],
     offset: usize,
@@ -78,7 +82,7 @@ macro_rules! set_idx {
     ($WIDTH: literal, $SELF: ident, $IDX: ident, $VALUE: ident) => {{
         $SELF.assert_index_valid($IDX);
         let offset = $SELF.field_offsets()[$IDX];
-        $SELF.data[offset..offset + $WIDTH].copy_from_slice(&$VALUE.to_le_bytes());
+        $SELF.data_mut()[offset..offset + $WIDTH].copy_from_slice(&$VALUE.to_le_bytes());
     }};
 }
 
@@ -89,7 +93,7 @@ macro_rules! fn_set_idx {
             fn [<set_ $NATIVE>](&mut self, idx: usize, value: $NATIVE) {
                 self.assert_index_valid(idx);
                 let offset = self.field_offsets()[idx];
-                self.data[offset..offset + $WIDTH].copy_from_slice(&value.to_le_bytes());
+                self.data_mut()[offset..offset + $WIDTH].copy_from_slice(&value.to_le_bytes());
             }
         }
     };
@@ -135,8 +139,14 @@ impl RowWriter {
 
     /// Reset the row writer state for new tuple
     pub fn reset(&mut self) {
-        self.data.fill(0);
         self.row_width = self.layout.fixed_part_width();
+        self.data.truncate(self.row_width);
+        self.data.fill(0);
+    }
+
+    #[inline(always)]
+    fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
     }
 
     #[inline]
@@ -215,6 +225,26 @@ impl RowWriter {
         set_idx!(16, self, idx, value)
     }
 
+    /// Append `value` to the variable-length region of the current tuple
+    /// and point the field at `idx` to it
+    fn set_var_length(&mut self, idx: usize, value: &[u8]) {
+        let offset = self.row_width;
+        let len = value.len();
+        let word = encode_var_length(offset, len);
+        self.row_width += round_upto_power_of_2(len, 8);
+        self.data.resize(self.row_width, 0);
+        self.data[offset..offset + len].copy_from_slice(value);
+        set_idx!(8, self, idx, word)
+    }
+
+    fn set_utf8(&mut self, idx: usize, value: &str) {
+        self.set_var_length(idx, value.as_bytes())
+    }
+
+    fn set_binary(&mut self, idx: usize, value: &[u8]) {
+        self.set_var_length(idx, value)
+    }
+
     /// Get raw bytes
     pub fn get_row(&self) -> &[u8] {
         &self.data[0..self.row_width]
@@ -305,6 +335,36 @@ pub(crate) fn write_field_decimal128(
     to.set_decimal128(col_idx, from.value(row_idx));
 }
 
+pub(crate) fn write_field_utf8(
+    to: &mut RowWriter,
+    from: &Arc<dyn Array>,
+    col_idx: usize,
+    row_idx: usize,
+) {
+    let from = as_string_array(from).unwrap();
+    to.set_utf8(col_idx, from.value(row_idx));
+}
+
+pub(crate) fn write_field_large_utf8(
+    to: &mut RowWriter,
+    from: &Arc<dyn Array>,
+    col_idx: usize,
+    row_idx: usize,
+) {
+    let from = as_generic_string_array::<i64>(from).unwrap();
+    to.set_utf8(col_idx, from.value(row_idx));
+}
+
+pub(crate) fn write_field_binary(
+    to: &mut RowWriter,
+    from: &Arc<dyn Array>,
+    col_idx: usize,
+    row_idx: usize,
+) {
+    let from = as_binary_array(from).unwrap();
+    to.set_binary(col_idx, from.value(row_idx));
+}
+
 fn write_field(
     col_idx: usize,
     row_idx: usize,
@@ -328,6 +388,9 @@ fn write_field(
         Date32 => write_field_date32(row, col, col_idx, row_idx),
         Date64 => write_field_date64(row, col, col_idx, row_idx),
         Decimal128(_, _) => write_field_decimal128(row, col, col_idx, row_idx),
+        Utf8 => write_field_utf8(row, col, col_idx, row_idx),
+        LargeUtf8 => write_field_large_utf8(row, col, col_idx, row_idx),
+        Binary => write_field_binary(row, col, col_idx, row_idx),
         _ => unimplemented!(),
     }
 }
@@ -160,6 +160,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     .get_aggregate_meta(name)
                     .map(WindowFunction::AggregateUDF)
             })
+            .or_else(|| {
+                self.schema_provider
+                    .get_window_meta(name)
+                    .map(WindowFunction::WindowUDF)
+            })
             .ok_or_else(|| {
                 DataFusionError::Plan(format!("There is no window function named {name}"))
             })
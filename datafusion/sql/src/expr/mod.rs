@@ -36,7 +36,7 @@ use datafusion_expr::{
     col, expr, lit, AggregateFunction, Between, BinaryExpr, BuiltinScalarFunction, Cast,
     Expr, ExprSchemable, GetIndexedField, Like, Operator, TryCast,
 };
-use sqlparser::ast::{ArrayAgg, Expr as SQLExpr, TrimWhereField, Value};
+use sqlparser::ast::{ArrayAgg, Expr as SQLExpr, JsonOperator, TrimWhereField, Value};
 use sqlparser::parser::ParserError::ParserError;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -300,12 +300,32 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
             SQLExpr::ArrayAgg(array_agg) => self.parse_array_agg(array_agg, schema, planner_context),
 
+            // `a @> b` and `b <@ a` test whether the array `a` contains all the elements of `b`
+            SQLExpr::JsonAccess { left, operator: JsonOperator::AtArrow, right } => self.sql_array_contains_to_expr(*left, *right, schema, planner_context),
+            SQLExpr::JsonAccess { left, operator: JsonOperator::ArrowAt, right } => self.sql_array_contains_to_expr(*right, *left, schema, planner_context),
+
             _ => Err(DataFusionError::NotImplemented(format!(
                 "Unsupported ast node in sqltorel: {sql:?}"
             ))),
         }
     }
 
+    fn sql_array_contains_to_expr(
+        &self,
+        array: SQLExpr,
+        elements: SQLExpr,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        Ok(Expr::ScalarFunction(ScalarFunction::new(
+            BuiltinScalarFunction::ArrayContains,
+            vec![
+                self.sql_expr_to_logical_expr(array, schema, planner_context)?,
+                self.sql_expr_to_logical_expr(elements, schema, planner_context)?,
+            ],
+        )))
+    }
+
     fn parse_array_agg(
         &self,
         array_agg: ArrayAgg,
@@ -21,8 +21,8 @@ use datafusion_common::parsers::CompressionTypeVariant;
 use sqlparser::ast::OrderByExpr;
 use sqlparser::{
     ast::{
-        ColumnDef, ColumnOptionDef, ObjectName, Statement as SQLStatement,
-        TableConstraint,
+        ColumnDef, ColumnOptionDef, Ident, ObjectName, Query, Statement as SQLStatement,
+        TableConstraint, Value,
     },
     dialect::{keywords::Keyword, Dialect, GenericDialect},
     parser::{Parser, ParserError},
@@ -42,6 +42,15 @@ fn parse_file_type(s: &str) -> Result<String, ParserError> {
     Ok(s.to_uppercase())
 }
 
+fn ensure_not_set<T>(field: &Option<T>, name: &str) -> Result<(), ParserError> {
+    if field.is_some() {
+        return Err(ParserError::ParserError(format!(
+            "{name} specified more than once",
+        )));
+    }
+    Ok(())
+}
+
 /// DataFusion extension DDL for `CREATE EXTERNAL TABLE`
 ///
 /// Syntax:
@@ -93,15 +102,49 @@ pub struct CreateExternalTable {
     pub options: HashMap<String, String>,
 }
 
+/// Displays the statement in a form that [`DFParser`] parses back into the
+/// same `CreateExternalTable`
 impl fmt::Display for CreateExternalTable {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quoted = |s: &str| Value::SingleQuotedString(s.to_string());
         write!(f, "CREATE EXTERNAL TABLE ")?;
         if self.if_not_exists {
-            write!(f, "IF NOT EXSISTS ")?;
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{}", self.name)?;
+        if !self.columns.is_empty() {
+            let columns: Vec<_> = self.columns.iter().map(|c| c.to_string()).collect();
+            write!(f, " ({})", columns.join(", "))?;
+        }
+        write!(f, " STORED AS {}", self.file_type)?;
+        if self.has_header {
+            write!(f, " WITH HEADER ROW")?;
+        }
+        if self.delimiter != ',' {
+            write!(f, " DELIMITER {}", quoted(&self.delimiter.to_string()))?;
+        }
+        if self.file_compression_type.is_compressed() {
+            let compression = self.file_compression_type.to_string();
+            write!(f, " COMPRESSION TYPE {compression}")?;
+        }
+        if !self.table_partition_cols.is_empty() {
+            let partitions = self.table_partition_cols.join(", ");
+            write!(f, " PARTITIONED BY ({partitions})")?;
         }
-        write!(f, "{} ", self.name)?;
-        write!(f, "STORED AS {} ", self.file_type)?;
-        write!(f, "LOCATION {} ", self.location)
+        if !self.order_exprs.is_empty() {
+            let exprs: Vec<_> = self.order_exprs.iter().map(|e| e.to_string()).collect();
+            write!(f, " WITH ORDER ({})", exprs.join(", "))?;
+        }
+        if !self.options.is_empty() {
+            let mut options: Vec<_> = self
+                .options
+                .iter()
+                .map(|(key, value)| format!("{} {}", quoted(key), quoted(value)))
+                .collect();
+            options.sort();
+            write!(f, " OPTIONS ({})", options.join(", "))?;
+        }
+        write!(f, " LOCATION {}", quoted(&self.location))
     }
 }
 
@@ -112,6 +155,66 @@ pub struct DescribeTableStmt {
     pub table_name: ObjectName,
 }
 
+/// DataFusion extension statement for `COPY ... TO`, writing the contents
+/// of a table or the results of a query into files
+///
+/// Syntax:
+///
+/// ```text
+/// COPY { <table_name> | (<query>) }
+/// TO <literal>
+/// [ (<option>, ...) ]
+///
+/// <option> := FORMAT <file_type>
+///           | COMPRESSION <GZIP | BZIP2 | XZ | ZSTD>
+///           | PARTITION_BY (<column list>)
+///           | HEADER <TRUE | FALSE>
+///           | DELIMITER <char>
+///           | <key> <value>
+/// ```
+///
+/// Any other `<key> <value>` pair is a format specific option, as in the
+/// `OPTIONS` of `CREATE EXTERNAL TABLE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyToStatement {
+    /// What is written
+    pub source: CopyToSource,
+    /// Location of the output file, or directory if it ends with `/`
+    pub target: String,
+    /// File type (Parquet, NDJSON, CSV, etc), inferred from the extension of
+    /// the target if not specified
+    pub file_type: Option<String>,
+    /// Whether CSV files start with a header row
+    pub has_header: bool,
+    /// User defined delimiter for CSVs
+    pub delimiter: char,
+    /// File compression type (GZIP, BZIP2, XZ, ZSTD)
+    pub file_compression_type: CompressionTypeVariant,
+    /// Columns the output is partitioned by into hive style directories
+    pub partition_by: Vec<Ident>,
+    /// Format specific options
+    pub options: HashMap<String, String>,
+}
+
+impl fmt::Display for CopyToStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            CopyToSource::Relation(table_name) => write!(f, "COPY {table_name} ")?,
+            CopyToSource::Query(query) => write!(f, "COPY ({query}) ")?,
+        }
+        write!(f, "TO '{}'", self.target)
+    }
+}
+
+/// The source of a `COPY ... TO` statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyToSource {
+    /// `COPY table TO ...`
+    Relation(ObjectName),
+    /// `COPY (SELECT ...) TO ...`
+    Query(Box<Query>),
+}
+
 /// DataFusion Statement representations.
 ///
 /// Tokens parsed by [`DFParser`] are converted into these values.
@@ -123,6 +226,8 @@ pub enum Statement {
     CreateExternalTable(CreateExternalTable),
     /// Extension: `DESCRIBE TABLE`
     DescribeTableStmt(DescribeTableStmt),
+    /// Extension: `COPY ... TO`
+    CopyTo(CopyToStatement),
 }
 
 /// DataFusion SQL Parser based on [`sqlparser`]
@@ -217,6 +322,12 @@ impl<'a> DFParser<'a> {
                         // use custom parsing
                         self.parse_describe()
                     }
+                    Keyword::COPY => {
+                        // move one token forward
+                        self.parser.next_token();
+                        // use custom parsing
+                        self.parse_copy()
+                    }
                     _ => {
                         // use the native parser
                         Ok(Statement::Statement(Box::from(
@@ -242,6 +353,87 @@ impl<'a> DFParser<'a> {
         }))
     }
 
+    /// Parse a SQL `COPY ... TO` statement
+    pub fn parse_copy(&mut self) -> Result<Statement, ParserError> {
+        let source = if self.parser.consume_token(&Token::LParen) {
+            let query = self.parser.parse_query()?;
+            self.parser.expect_token(&Token::RParen)?;
+            CopyToSource::Query(Box::new(query))
+        } else {
+            CopyToSource::Relation(self.parser.parse_object_name()?)
+        };
+        self.parser.expect_keyword(Keyword::TO)?;
+        let target = self.parser.parse_literal_string()?;
+
+        #[derive(Default)]
+        struct Builder {
+            file_type: Option<String>,
+            has_header: Option<bool>,
+            delimiter: Option<char>,
+            file_compression_type: Option<CompressionTypeVariant>,
+            partition_by: Option<Vec<Ident>>,
+        }
+        let mut builder = Builder::default();
+        let mut options = HashMap::new();
+
+        if self.parser.consume_token(&Token::LParen) {
+            loop {
+                let option = match self.parser.peek_token().token {
+                    Token::Word(_) => self.parser.parse_identifier()?.value,
+                    _ => self.parser.parse_literal_string()?,
+                };
+                match option.to_uppercase().as_str() {
+                    "FORMAT" => {
+                        ensure_not_set(&builder.file_type, "FORMAT")?;
+                        builder.file_type = Some(self.parse_file_format()?);
+                    }
+                    "COMPRESSION" => {
+                        ensure_not_set(&builder.file_compression_type, "COMPRESSION")?;
+                        builder.file_compression_type =
+                            Some(self.parse_file_compression_type()?);
+                    }
+                    "PARTITION_BY" => {
+                        ensure_not_set(&builder.partition_by, "PARTITION_BY")?;
+                        builder.partition_by = Some(self.parse_partition_idents()?);
+                    }
+                    "HEADER" => {
+                        ensure_not_set(&builder.has_header, "HEADER")?;
+                        builder.has_header = Some(self.parse_boolean()?);
+                    }
+                    "DELIMITER" => {
+                        ensure_not_set(&builder.delimiter, "DELIMITER")?;
+                        builder.delimiter = Some(self.parse_delimiter()?);
+                    }
+                    _ => {
+                        let value = self.parse_option_value()?;
+                        if options.insert(option.clone(), value).is_some() {
+                            return parser_err!(format!(
+                                "{option} specified more than once"
+                            ));
+                        }
+                    }
+                }
+                if !self.parser.consume_token(&Token::Comma) {
+                    self.parser.expect_token(&Token::RParen)?;
+                    break;
+                }
+            }
+        }
+
+        Ok(Statement::CopyTo(CopyToStatement {
+            source,
+            target,
+            file_type: builder.file_type,
+            has_header: builder.has_header.unwrap_or(false),
+            delimiter: builder.delimiter.unwrap_or(','),
+            file_compression_type: builder
+                .file_compression_type
+                .unwrap_or(CompressionTypeVariant::UNCOMPRESSED),
+            partition_by: builder.partition_by.unwrap_or_default(),
+            options,
+        }))
+    }
+
     /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE`
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
         if self.parser.parse_keyword(Keyword::EXTERNAL) {
@@ -252,7 +444,15 @@ impl<'a> DFParser<'a> {
     }
 
     fn parse_partitions(&mut self) -> Result<Vec<String>, ParserError> {
-        let mut partitions: Vec<String> = vec![];
+        Ok(self
+            .parse_partition_idents()?
+            .iter()
+            .map(|identifier| identifier.to_string())
+            .collect())
+    }
+
+    fn parse_partition_idents(&mut self) -> Result<Vec<Ident>, ParserError> {
+        let mut partitions: Vec<Ident> = vec![];
         if !self.parser.consume_token(&Token::LParen)
             || self.parser.consume_token(&Token::RParen)
         {
@@ -261,8 +461,7 @@ impl<'a> DFParser<'a> {
 
         loop {
             if let Token::Word(_) = self.parser.peek_token().token {
-                let identifier = self.parser.parse_identifier()?;
-                partitions.push(identifier.to_string());
+                partitions.push(self.parser.parse_identifier()?);
             } else {
                 return self.expected("partition name", self.parser.peek_token());
             }
@@ -417,15 +616,6 @@ impl<'a> DFParser<'a> {
         }
         let mut builder = Builder::default();
 
-        fn ensure_not_set<T>(field: &Option<T>, name: &str) -> Result<(), ParserError> {
-            if field.is_some() {
-                return Err(ParserError::ParserError(format!(
-                    "{name} specified more than once",
-                )));
-            }
-            Ok(())
-        }
-
         loop {
             if self.parser.parse_keyword(Keyword::STORED) {
                 self.parser.expect_keyword(Keyword::AS)?;
@@ -519,8 +709,8 @@ impl<'a> DFParser<'a> {
 
         loop {
             let key = self.parser.parse_literal_string()?;
-            let value = self.parser.parse_literal_string()?;
-            options.insert(key.to_string(), value.to_string());
+            let value = self.parse_option_value()?;
+            options.insert(key, value);
             let comma = self.parser.consume_token(&Token::Comma);
             if self.parser.consume_token(&Token::RParen) {
                 // allow a trailing comma, even though it's not in standard
@@ -535,6 +725,23 @@ impl<'a> DFParser<'a> {
         Ok(options)
     }
 
+    /// Parses the value of an option, a string or a number
+    fn parse_option_value(&mut self) -> Result<String, ParserError> {
+        if let Token::Number(value, _) = self.parser.peek_token().token {
+            self.parser.next_token();
+            return Ok(value);
+        }
+        self.parser.parse_literal_string()
+    }
+
+    fn parse_boolean(&mut self) -> Result<bool, ParserError> {
+        let token = self.parser.peek_token();
+        match self.parser.parse_value()? {
+            Value::Boolean(value) => Ok(value),
+            _ => self.expected("TRUE or FALSE", token),
+        }
+    }
+
     fn parse_delimiter(&mut self) -> Result<char, ParserError> {
         let token = self.parser.parse_literal_string()?;
         match token.len() {
@@ -550,7 +757,7 @@ impl<'a> DFParser<'a> {
 mod tests {
     use super::*;
     use sqlparser::ast::Expr::Identifier;
-    use sqlparser::ast::{BinaryOperator, DataType, Expr, Ident};
+    use sqlparser::ast::{BinaryOperator, DataType, Expr};
     use CompressionTypeVariant::UNCOMPRESSED;
 
     fn expect_parse_ok(sql: &str, expected: Statement) -> Result<(), ParserError> {
@@ -980,4 +1187,95 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn display_create_external_table() -> Result<(), ParserError> {
+        let sqls = [
+            "CREATE EXTERNAL TABLE t STORED AS CSV LOCATION 'foo.csv'",
+            "CREATE EXTERNAL TABLE IF NOT EXISTS t (c1 INT NOT NULL, c2 VARCHAR) \
+            STORED AS CSV WITH HEADER ROW DELIMITER '|' COMPRESSION TYPE GZIP \
+            PARTITIONED BY (p1, p2) WITH ORDER (c1 ASC NULLS FIRST) \
+            OPTIONS ('k1' 'it''s', 'k2' '1') LOCATION 'it''s/foo.csv.gz'",
+        ];
+        for sql in sqls {
+            let statement = DFParser::parse_sql(sql)?.pop_front().unwrap();
+            let displayed = match &statement {
+                Statement::CreateExternalTable(cmd) => cmd.to_string(),
+                _ => unreachable!(),
+            };
+            assert_eq!(displayed, sql);
+            expect_parse_ok(&displayed, statement)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn copy_to() -> Result<(), ParserError> {
+        let sql = "COPY t TO 'out/'";
+        let expected = Statement::CopyTo(CopyToStatement {
+            source: CopyToSource::Relation(ObjectName(vec![Ident::new("t")])),
+            target: "out/".into(),
+            file_type: None,
+            has_header: false,
+            delimiter: ',',
+            file_compression_type: UNCOMPRESSED,
+            partition_by: vec![],
+            options: HashMap::new(),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "COPY (SELECT a, b FROM t WHERE a > 1) TO 'out.csv' \
+                   (FORMAT csv, HEADER false, DELIMITER '|', COMPRESSION gzip, PARTITION_BY (b))";
+        let query = match Parser::parse_sql(
+            &GenericDialect {},
+            "SELECT a, b FROM t WHERE a > 1",
+        )?
+        .remove(0)
+        {
+            SQLStatement::Query(query) => query,
+            _ => unreachable!(),
+        };
+        let expected = Statement::CopyTo(CopyToStatement {
+            source: CopyToSource::Query(query),
+            target: "out.csv".into(),
+            file_type: Some("CSV".into()),
+            has_header: false,
+            delimiter: '|',
+            file_compression_type: CompressionTypeVariant::GZIP,
+            partition_by: vec![Ident::new("b")],
+            options: HashMap::new(),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "COPY t TO 'out/' (FORMAT parquet, PARTITION_BY (\"B\", c), \
+                   MAX_ROW_GROUP_SIZE 1024, 'dictionary_enabled' 'false')";
+        let expected = Statement::CopyTo(CopyToStatement {
+            source: CopyToSource::Relation(ObjectName(vec![Ident::new("t")])),
+            target: "out/".into(),
+            file_type: Some("PARQUET".into()),
+            has_header: false,
+            delimiter: ',',
+            file_compression_type: UNCOMPRESSED,
+            partition_by: vec![Ident::with_quote('"', "B"), Ident::new("c")],
+            options: HashMap::from([
+                ("MAX_ROW_GROUP_SIZE".into(), "1024".into()),
+                ("dictionary_enabled".into(), "false".into()),
+            ]),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        expect_parse_error(
+            "COPY t TO 'out.csv' (FORMAT csv, FORMAT json)",
+            "FORMAT specified more than once",
+        );
+        expect_parse_error(
+            "COPY t TO 'out.csv' (ROW_GROUP_SIZE 10, ROW_GROUP_SIZE 20)",
+            "ROW_GROUP_SIZE specified more than once",
+        );
+        expect_parse_error(
+            "COPY t TO 'out.csv' (HEADER 'yes')",
+            "Expected TRUE or FALSE, found: 'yes'",
+        );
+        Ok(())
+    }
 }
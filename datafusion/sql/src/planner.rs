@@ -33,7 +33,7 @@ use datafusion_common::{OwnedTableReference, TableReference};
 use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder};
 use datafusion_expr::utils::find_column_exprs;
 use datafusion_expr::TableSource;
-use datafusion_expr::{col, AggregateUDF, Expr, ScalarUDF, SubqueryAlias};
+use datafusion_expr::{col, AggregateUDF, Expr, ScalarUDF, SubqueryAlias, WindowUDF};
 
 use crate::utils::make_decimal_type;
 
@@ -46,11 +46,40 @@ pub trait ContextProvider {
     fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>>;
     /// Getter for a UDAF description
     fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>>;
+    /// Getter for a UDWF description
+    fn get_window_meta(&self, _name: &str) -> Option<Arc<WindowUDF>> {
+        None
+    }
     /// Getter for system/user-defined variable type
     fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType>;
 
     /// Get configuration options
     fn options(&self) -> &ConfigOptions;
+
+    /// Create the table a recursive query called `name` reads the rows of
+    /// its previous iteration from
+    fn create_cte_work_table(
+        &self,
+        _name: &str,
+        _schema: SchemaRef,
+    ) -> Result<Arc<dyn TableSource>> {
+        Err(DataFusionError::NotImplemented(
+            "Recursive CTEs are not supported".to_string(),
+        ))
+    }
+
+    /// Create the table returned by calling the table function `name` in a
+    /// FROM clause. Named arguments are passed as an [`Expr::Alias`] of the
+    /// argument value.
+    fn get_table_function_source(
+        &self,
+        name: &str,
+        _args: Vec<Expr>,
+    ) -> Result<Arc<dyn TableSource>> {
+        Err(DataFusionError::Plan(format!(
+            "table function '{name}' not found"
+        )))
+    }
 }
 
 /// SQL parser options
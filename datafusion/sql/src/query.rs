@@ -17,9 +17,17 @@
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
-use datafusion_common::{DataFusionError, Result, ScalarValue};
+use std::sync::Arc;
+
+use arrow_schema::{Field, Schema};
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
+use datafusion_common::{DataFusionError, Result, ScalarValue, TableReference};
+use datafusion_expr::expr::{Exists, InSubquery};
 use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::{Expr as SQLExpr, Offset as SQLOffset, OrderByExpr, Query, Value};
+use sqlparser::ast::{
+    Expr as SQLExpr, Offset as SQLOffset, OrderByExpr, Query, SetExpr, SetOperator,
+    SetQuantifier, TableAlias, Value,
+};
 
 use sqlparser::parser::ParserError::ParserError;
 
@@ -44,12 +52,8 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let set_expr = query.body;
         if let Some(with) = query.with {
             // Process CTEs from top to bottom
-            // do not allow self-references
-            if with.recursive {
-                return Err(DataFusionError::NotImplemented(
-                    "Recursive CTEs are not supported".to_string(),
-                ));
-            }
+            // only allow self-references in `WITH RECURSIVE`
+            let is_recursive = with.recursive;
 
             for cte in with.cte_tables {
                 // A `WITH` block can't use the same name more than once
@@ -59,14 +63,23 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         "WITH query name {cte_name:?} specified more than once"
                     ))));
                 }
-                // create logical plan & pass backreferencing CTEs
-                // CTE expr don't need extend outer_query_schema
-                let logical_plan =
-                    self.query_to_plan(*cte.query, &mut planner_context.clone())?;
+                let logical_plan = if is_recursive {
+                    self.recursive_cte_to_plan(
+                        &cte_name,
+                        *cte.query,
+                        cte.alias,
+                        planner_context,
+                    )?
+                } else {
+                    // create logical plan & pass backreferencing CTEs
+                    // CTE expr don't need extend outer_query_schema
+                    let logical_plan =
+                        self.query_to_plan(*cte.query, &mut planner_context.clone())?;
 
-                // Each `WITH` block can change the column names in the last
-                // projection (e.g. "WITH table(t1, t2) AS SELECT 1, 2").
-                let logical_plan = self.apply_table_alias(logical_plan, cte.alias)?;
+                    // Each `WITH` block can change the column names in the last
+                    // projection (e.g. "WITH table(t1, t2) AS SELECT 1, 2").
+                    self.apply_table_alias(logical_plan, cte.alias)?
+                };
 
                 planner_context.insert_cte(cte_name, logical_plan);
             }
@@ -76,6 +89,93 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         self.limit(plan, query.offset, query.limit)
     }
 
+    /// Generate a logical plan for a CTE of a `WITH RECURSIVE` block.
+    ///
+    /// A CTE that is a `UNION [ALL]` whose right side refers to the CTE
+    /// itself becomes a recursive query: the left side (the static term) is
+    /// executed once, and the right side (the recursive term) is executed
+    /// repeatedly, reading the rows of the previous iteration from a work
+    /// table, until it returns no rows. Any other CTE is planned as usual.
+    fn recursive_cte_to_plan(
+        &self,
+        cte_name: &str,
+        query: Query,
+        alias: TableAlias,
+        planner_context: &PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let is_plain_union = query.with.is_none()
+            && query.order_by.is_empty()
+            && query.limit.is_none()
+            && query.offset.is_none()
+            && query.fetch.is_none();
+        let (left, right, set_quantifier) = match *query.body {
+            SetExpr::SetOperation {
+                op: SetOperator::Union,
+                left,
+                right,
+                set_quantifier,
+            } if is_plain_union => (left, right, set_quantifier),
+            body => {
+                let query = Query {
+                    body: Box::new(body),
+                    ..query
+                };
+                let logical_plan =
+                    self.query_to_plan(query, &mut planner_context.clone())?;
+                return self.apply_table_alias(logical_plan, alias);
+            }
+        };
+        let is_distinct = !matches!(set_quantifier, SetQuantifier::All);
+        let table_alias = TableAlias {
+            name: alias.name,
+            columns: vec![],
+        };
+
+        // The static term determines the columns of the CTE
+        let static_plan = self.set_expr_to_plan(*left, &mut planner_context.clone())?;
+        let static_plan = self.apply_expr_alias(static_plan, alias.columns)?;
+
+        // Within the recursive term, the CTE refers to the work table. Its
+        // columns are nullable, as the recursive term may produce nulls
+        let work_table_schema = Arc::new(Schema::new(
+            static_plan
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| Field::new(f.name(), f.data_type().clone(), true))
+                .collect::<Vec<_>>(),
+        ));
+        let work_table = self
+            .schema_provider
+            .create_cte_work_table(cte_name, work_table_schema)?;
+        let work_table_scan = LogicalPlanBuilder::scan(
+            TableReference::bare(cte_name.to_string()),
+            work_table,
+            None,
+        )?
+        .build()?;
+
+        let mut recursive_context = planner_context.clone();
+        recursive_context.insert_cte(cte_name, work_table_scan);
+        let recursive_plan = self.set_expr_to_plan(*right, &mut recursive_context)?;
+
+        let builder = LogicalPlanBuilder::from(static_plan);
+        let logical_plan = if references_table(&recursive_plan, cte_name)? {
+            builder.to_recursive_query(
+                cte_name.to_string(),
+                recursive_plan,
+                is_distinct,
+            )?
+        } else if is_distinct {
+            builder.union_distinct(recursive_plan)?
+        } else {
+            builder.union(recursive_plan)?
+        }
+        .build()?;
+
+        self.apply_table_alias(logical_plan, table_alias)
+    }
+
     /// Wrap a plan in a limit
     fn limit(
         &self,
@@ -151,3 +251,37 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         LogicalPlanBuilder::from(plan).sort(order_by_rex)?.build()
     }
 }
+
+/// Returns true if `plan` scans the table called `table_name`, including
+/// within the subqueries of its expressions
+fn references_table(plan: &LogicalPlan, table_name: &str) -> Result<bool> {
+    let mut found = false;
+    plan.apply(&mut |plan| {
+        if let LogicalPlan::TableScan(scan) = plan {
+            if scan.table_name.table() == table_name {
+                found = true;
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        for expr in plan.expressions() {
+            expr.apply(&mut |expr| {
+                let subquery = match expr {
+                    Expr::Exists(Exists { subquery, .. })
+                    | Expr::InSubquery(InSubquery { subquery, .. })
+                    | Expr::ScalarSubquery(subquery) => subquery,
+                    _ => return Ok(VisitRecursion::Continue),
+                };
+                if references_table(&subquery.subquery, table_name)? {
+                    found = true;
+                    return Ok(VisitRecursion::Stop);
+                }
+                Ok(VisitRecursion::Continue)
+            })?;
+            if found {
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    })?;
+    Ok(found)
+}
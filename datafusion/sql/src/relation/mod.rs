@@ -16,9 +16,9 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::{LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::TableFactor;
+use datafusion_common::{DFSchema, DataFusionError, Result, TableReference};
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder};
+use sqlparser::ast::{FunctionArg, FunctionArgExpr, ObjectName, TableFactor};
 
 mod join;
 
@@ -29,6 +29,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         let (plan, alias) = match relation {
+            TableFactor::Table {
+                name,
+                alias,
+                args: Some(args),
+                ..
+            } => (
+                self.plan_table_function(name, args, planner_context)?,
+                alias,
+            ),
             TableFactor::Table { name, alias, .. } => {
                 // normalize name and alias
                 let table_ref = self.object_name_to_table_reference(name)?;
@@ -74,4 +83,45 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             Ok(plan)
         }
     }
+
+    /// Plan a call of a table function such as `generate_series(1, 10)`,
+    /// whose arguments must not refer to any column
+    fn plan_table_function(
+        &self,
+        name: ObjectName,
+        args: Vec<FunctionArg>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let name = if name.0.len() > 1 {
+            // DF doesn't handle compound identifiers
+            // (e.g. "foo.bar") for function names yet
+            name.to_string()
+        } else {
+            self.normalizer.normalize(name.0[0].clone())
+        };
+
+        let schema = DFSchema::empty();
+        let args = args
+            .into_iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) => {
+                    self.sql_expr_to_logical_expr(arg, &schema, planner_context)
+                }
+                FunctionArg::Named {
+                    name: arg_name,
+                    arg: FunctionArgExpr::Expr(arg),
+                } => Ok(self
+                    .sql_expr_to_logical_expr(arg, &schema, planner_context)?
+                    .alias(self.normalizer.normalize(arg_name))),
+                _ => Err(DataFusionError::Plan(format!(
+                    "Unsupported argument {arg} of table function '{name}'"
+                ))),
+            })
+            .collect::<Result<Vec<Expr>>>()?;
+
+        let source = self
+            .schema_provider
+            .get_table_function_source(&name, args)?;
+        LogicalPlanBuilder::scan(TableReference::bare(name), source, None)?.build()
+    }
 }
@@ -26,15 +26,18 @@ use datafusion_expr::expr_rewriter::{
 };
 use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::utils::{
-    expand_qualified_wildcard, expand_wildcard, expr_as_column_expr, expr_to_columns,
-    find_aggregate_exprs, find_window_exprs,
+    expand_qualified_wildcard_with_options, expand_wildcard_with_options,
+    expr_as_column_expr, expr_to_columns, find_aggregate_exprs, find_window_exprs,
 };
 use datafusion_expr::Expr::Alias;
 use datafusion_expr::{
     CreateMemoryTable, DdlStatement, Expr, Filter, GroupingSet, LogicalPlan,
-    LogicalPlanBuilder, Partitioning,
+    LogicalPlanBuilder, Partitioning, WildcardOptions,
+};
+use sqlparser::ast::{
+    ExceptSelectItem, ExcludeSelectItem, Expr as SQLExpr, IdentWithAlias,
+    RenameSelectItem, ReplaceSelectElement, WildcardAdditionalOptions,
 };
-use sqlparser::ast::{Expr as SQLExpr, WildcardAdditionalOptions};
 use sqlparser::ast::{Select, SelectItem, TableWithJoins};
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -47,20 +50,19 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         // check for unsupported syntax first
-        if !select.cluster_by.is_empty() {
-            return Err(DataFusionError::NotImplemented("CLUSTER BY".to_string()));
-        }
         if !select.lateral_views.is_empty() {
             return Err(DataFusionError::NotImplemented("LATERAL VIEWS".to_string()));
         }
-        if select.qualify.is_some() {
-            return Err(DataFusionError::NotImplemented("QUALIFY".to_string()));
-        }
         if select.top.is_some() {
             return Err(DataFusionError::NotImplemented("TOP".to_string()));
         }
-        if !select.sort_by.is_empty() {
-            return Err(DataFusionError::NotImplemented("SORT BY".to_string()));
+        if !select.cluster_by.is_empty()
+            && (!select.distribute_by.is_empty() || !select.sort_by.is_empty())
+        {
+            return Err(DataFusionError::Plan(
+                "CLUSTER BY cannot be used together with DISTRIBUTE BY or SORT BY"
+                    .to_string(),
+            ));
         }
 
         // process `from` clause
@@ -113,6 +115,24 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             })
             .transpose()?;
 
+        // Optionally the QUALIFY expression, which may also refer to aliased
+        // window functions, e.g.
+        //
+        //   SELECT c1, ROW_NUMBER() OVER (PARTITION BY c2) AS rn FROM t QUALIFY rn = 1;
+        //
+        let qualify_expr_opt = select
+            .qualify
+            .map::<Result<Expr>, _>(|qualify_expr| {
+                let qualify_expr = self.sql_expr_to_logical_expr(
+                    qualify_expr,
+                    &combined_schema,
+                    planner_context,
+                )?;
+                let qualify_expr = resolve_aliases_to_exprs(&qualify_expr, &alias_map)?;
+                normalize_col(qualify_expr, &projected_plan)
+            })
+            .transpose()?;
+
         // The outer expressions we will search through for
         // aggregates. Aggregates may be sourced from the SELECT...
         let mut aggr_expr_haystack = select_exprs.clone();
@@ -120,6 +140,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         if let Some(having_expr) = &having_expr_opt {
             aggr_expr_haystack.push(having_expr.clone());
         }
+        // ... or from the QUALIFY.
+        if let Some(qualify_expr) = &qualify_expr_opt {
+            aggr_expr_haystack.push(qualify_expr.clone());
+        }
 
         // All of the aggregate expressions (deduplicated).
         let aggr_exprs = find_aggregate_exprs(&aggr_expr_haystack);
@@ -149,6 +173,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             })
             .collect::<Result<Vec<Expr>>>()?;
 
+        // The QUALIFY expression is rewritten along with the SELECT
+        // expressions, as the last of them
+        let mut select_exprs = select_exprs;
+        select_exprs.extend(qualify_expr_opt.iter().cloned());
+
         // process group by, aggregation or having
         let (plan, mut select_exprs_post_aggr, having_expr_post_aggr) = if !group_by_exprs
             .is_empty()
@@ -194,6 +223,28 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             plan
         };
 
+        // The rewritten QUALIFY expression is the last of the expressions
+        let qualify_expr_post_window = if qualify_expr_opt.is_some() {
+            select_exprs_post_aggr.pop()
+        } else {
+            None
+        };
+
+        // process qualify clause, which filters on the results of the window
+        // functions
+        let plan = if let Some(qualify_expr_post_window) = qualify_expr_post_window {
+            if window_func_exprs.is_empty() {
+                return Err(DataFusionError::Plan(
+                    "QUALIFY clause requires a window function in the SELECT list or the QUALIFY clause".to_string(),
+                ));
+            }
+            LogicalPlanBuilder::from(plan)
+                .filter(qualify_expr_post_window)?
+                .build()?
+        } else {
+            plan
+        };
+
         // final projection
         let plan = project(plan, select_exprs_post_aggr)?;
 
@@ -204,21 +255,44 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             Ok(plan)
         }?;
 
+        // CLUSTER BY is a shorthand for DISTRIBUTE BY and SORT BY the same
+        // expressions
+        let (distribute_by, sort_by) = if select.cluster_by.is_empty() {
+            (select.distribute_by, select.sort_by)
+        } else {
+            (select.cluster_by.clone(), select.cluster_by)
+        };
+
         // DISTRIBUTE BY
-        let plan = if !select.distribute_by.is_empty() {
-            let x = select
-                .distribute_by
-                .iter()
+        let plan = if !distribute_by.is_empty() {
+            let x = distribute_by
+                .into_iter()
+                .map(|e| {
+                    self.sql_expr_to_logical_expr(e, &combined_schema, planner_context)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            LogicalPlanBuilder::from(plan)
+                .repartition(Partitioning::DistributeBy(x))?
+                .build()?
+        } else {
+            plan
+        };
+
+        // SORT BY sorts each partition on its own, in ascending order
+        let plan = if !sort_by.is_empty() {
+            let sort_exprs = sort_by
+                .into_iter()
                 .map(|e| {
-                    self.sql_expr_to_logical_expr(
-                        e.clone(),
+                    let expr = self.sql_expr_to_logical_expr(
+                        e,
                         &combined_schema,
                         planner_context,
-                    )
+                    )?;
+                    Ok(expr.sort(true, false))
                 })
                 .collect::<Result<Vec<_>>>()?;
             LogicalPlanBuilder::from(plan)
-                .repartition(Partitioning::DistributeBy(x))?
+                .sort_within_partitions(sort_exprs)?
                 .build()?
         } else {
             plan
@@ -351,27 +425,36 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 Ok(vec![expr])
             }
             SelectItem::Wildcard(options) => {
-                Self::check_wildcard_options(options)?;
-
                 if empty_from {
                     return Err(DataFusionError::Plan(
                         "SELECT * with no tables specified is not valid".to_string(),
                     ));
                 }
+                let options = self.wildcard_options(options, plan, planner_context)?;
                 // do not expand from outer schema
-                expand_wildcard(plan.schema().as_ref(), plan)
+                expand_wildcard_with_options(plan.schema().as_ref(), plan, Some(&options))
             }
             SelectItem::QualifiedWildcard(ref object_name, options) => {
-                Self::check_wildcard_options(options)?;
-
+                let options = self.wildcard_options(options, plan, planner_context)?;
                 let qualifier = format!("{object_name}");
                 // do not expand from outer schema
-                expand_qualified_wildcard(&qualifier, plan.schema().as_ref())
+                expand_qualified_wildcard_with_options(
+                    &qualifier,
+                    plan.schema().as_ref(),
+                    Some(&options),
+                )
             }
         }
     }
 
-    fn check_wildcard_options(options: WildcardAdditionalOptions) -> Result<()> {
+    /// Plan the `EXCLUDE`, `EXCEPT`, `REPLACE` and `RENAME` options of a
+    /// wildcard
+    fn wildcard_options(
+        &self,
+        options: WildcardAdditionalOptions,
+        plan: &LogicalPlan,
+        planner_context: &mut PlannerContext,
+    ) -> Result<WildcardOptions> {
         let WildcardAdditionalOptions {
             opt_exclude,
             opt_except,
@@ -379,18 +462,60 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             opt_replace,
         } = options;
 
-        if opt_exclude.is_some()
-            || opt_except.is_some()
-            || opt_rename.is_some()
-            || opt_replace.is_some()
+        let mut exclude = match opt_exclude {
+            Some(ExcludeSelectItem::Single(ident)) => vec![ident],
+            Some(ExcludeSelectItem::Multiple(idents)) => idents,
+            None => vec![],
+        };
+        if let Some(ExceptSelectItem {
+            first_element,
+            additional_elements,
+        }) = opt_except
         {
-            Err(DataFusionError::NotImplemented(
-                "wildcard * with EXCLUDE, EXCEPT, RENAME or REPLACE not supported "
-                    .to_string(),
-            ))
-        } else {
-            Ok(())
+            exclude.push(first_element);
+            exclude.extend(additional_elements);
         }
+
+        let rename = match opt_rename {
+            Some(RenameSelectItem::Single(ident)) => vec![ident],
+            Some(RenameSelectItem::Multiple(idents)) => idents,
+            None => vec![],
+        };
+
+        let replace = opt_replace
+            .map(|replace| replace.items)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| {
+                let ReplaceSelectElement {
+                    expr, column_name, ..
+                } = *item;
+                let expr = self.sql_to_expr(expr, plan.schema(), planner_context)?;
+                let expr = normalize_col_with_schemas_and_ambiguity_check(
+                    expr,
+                    &[&[plan.schema()]],
+                    &plan.using_columns()?,
+                )?;
+                Ok((self.normalizer.normalize(column_name), expr))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(WildcardOptions {
+            exclude: exclude
+                .into_iter()
+                .map(|ident| self.normalizer.normalize(ident))
+                .collect(),
+            replace,
+            rename: rename
+                .into_iter()
+                .map(|IdentWithAlias { ident, alias }| {
+                    (
+                        self.normalizer.normalize(ident),
+                        self.normalizer.normalize(alias),
+                    )
+                })
+                .collect(),
+        })
     }
 
     /// Wrap a plan in a projection
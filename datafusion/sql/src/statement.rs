@@ -16,7 +16,8 @@
 // under the License.
 
 use crate::parser::{
-    CreateExternalTable, DFParser, DescribeTableStmt, Statement as DFStatement,
+    CopyToSource, CopyToStatement, CreateExternalTable, DFParser, DescribeTableStmt,
+    Statement as DFStatement,
 };
 use crate::planner::{
     object_name_to_qualifier, ContextProvider, PlannerContext, SqlToRel,
@@ -33,7 +34,7 @@ use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::utils::expr_to_columns;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
+    cast, col, Analyze, CopyTo, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateMemoryTable, CreateView,
     DescribeTable, DmlStatement, DropCatalogSchema, DropTable, DropView, EmptyRelation,
     Explain, ExprSchemable, Filter, LogicalPlan, LogicalPlanBuilder, PlanType, Prepare,
@@ -66,6 +67,31 @@ fn object_name_to_string(object_name: &ObjectName) -> String {
         .join(".")
 }
 
+/// Infer the file type of the output of COPY TO from the extensions of
+/// `target`, like `out.csv` or `out.csv.gz`
+fn infer_file_type(target: &str) -> Result<String> {
+    let file_name = target
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("");
+    file_name
+        .split('.')
+        .skip(1)
+        .find_map(|extension| match extension.to_lowercase().as_str() {
+            "parquet" => Some("PARQUET"),
+            "csv" => Some("CSV"),
+            "json" | "ndjson" => Some("JSON"),
+            _ => None,
+        })
+        .map(str::to_string)
+        .ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "Cannot infer the format of '{target}', specify it with the FORMAT option"
+            ))
+        })
+}
+
 fn get_schema_name(schema_name: &SchemaName) -> String {
     match schema_name {
         SchemaName::Simple(schema_name) => object_name_to_string(schema_name),
@@ -85,6 +111,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             DFStatement::CreateExternalTable(s) => self.external_table_to_plan(s),
             DFStatement::Statement(s) => self.sql_statement_to_plan(*s),
             DFStatement::DescribeTableStmt(s) => self.describe_table_to_plan(s),
+            DFStatement::CopyTo(s) => self.copy_to_plan(s),
         }
     }
 
@@ -537,6 +564,100 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }))
     }
 
+    /// Generate a logical plan from a COPY ... TO statement
+    fn copy_to_plan(&self, statement: CopyToStatement) -> Result<LogicalPlan> {
+        let CopyToStatement {
+            source,
+            target,
+            file_type,
+            has_header,
+            delimiter,
+            file_compression_type,
+            partition_by,
+            options,
+        } = statement;
+        let partition_by: Vec<String> = partition_by
+            .into_iter()
+            .map(|ident| self.normalizer.normalize(ident))
+            .collect();
+
+        let input = match source {
+            CopyToSource::Relation(table_name) => {
+                let table_ref = self.object_name_to_table_reference(table_name)?;
+                let table_source =
+                    self.schema_provider.get_table_provider(table_ref.clone())?;
+                LogicalPlanBuilder::scan(table_ref, table_source, None)?.build()?
+            }
+            CopyToSource::Query(query) => {
+                self.query_to_plan(*query, &mut PlannerContext::new())?
+            }
+        };
+
+        let file_type = match file_type {
+            Some(file_type) => file_type,
+            None => infer_file_type(&target)?,
+        };
+        match file_type.as_str() {
+            "PARQUET"
+                if matches!(
+                    file_compression_type,
+                    CompressionTypeVariant::BZIP2 | CompressionTypeVariant::XZ
+                ) =>
+            {
+                return Err(DataFusionError::Plan(format!(
+                    "Compression {} is not supported for PARQUET files",
+                    file_compression_type.to_string()
+                )));
+            }
+            "PARQUET" | "CSV" | "JSON" | "NDJSON" => {}
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "COPY TO does not support writing {file_type} files"
+                )));
+            }
+        }
+
+        // The partition columns are written as the directories of the files,
+        // so they are moved last, as expected when writing the files
+        let input = if partition_by.is_empty() {
+            input
+        } else {
+            let schema = input.schema().clone();
+            let mut partition_columns = Vec::with_capacity(partition_by.len());
+            for name in &partition_by {
+                let field = schema.field_with_unqualified_name(name).map_err(|_| {
+                    DataFusionError::Plan(format!(
+                        "Partition column {name} is not in the output of COPY TO"
+                    ))
+                })?;
+                partition_columns.push(col(field.qualified_column()));
+            }
+            let file_columns: Vec<_> = schema
+                .fields()
+                .iter()
+                .filter(|field| !partition_by.contains(field.name()))
+                .map(|field| col(field.qualified_column()))
+                .collect();
+            if file_columns.is_empty() {
+                return Err(DataFusionError::Plan(
+                    "COPY TO requires at least one column not in PARTITION_BY".into(),
+                ));
+            }
+            project(input, file_columns.into_iter().chain(partition_columns))?
+        };
+
+        Ok(LogicalPlan::Copy(CopyTo {
+            input: Arc::new(input),
+            output_url: target,
+            file_type,
+            has_header,
+            delimiter,
+            file_compression_type,
+            partition_by,
+            options,
+        }))
+    }
+
     fn build_order_by(
         &self,
         order_exprs: Vec<OrderByExpr>,
@@ -792,7 +913,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let table_ref = self.object_name_to_table_reference(table_name.clone())?;
         let provider = self.schema_provider.get_table_provider(table_ref.clone())?;
         let schema = (*provider.schema()).clone();
-        let schema = DFSchema::try_from(schema)?;
+        let schema = DFSchema::try_from_qualified_schema(table_ref.clone(), &schema)?;
         let scan =
             LogicalPlanBuilder::scan(object_name_to_string(&table_name), provider, None)?
                 .build()?;
@@ -815,12 +936,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             }
         };
 
-        let plan = LogicalPlan::Dml(DmlStatement {
-            table_name: table_ref,
-            table_schema: schema.into(),
-            op: WriteOp::Delete,
-            input: Arc::new(source),
-        });
+        let plan = LogicalPlan::Dml(DmlStatement::new(
+            table_ref,
+            schema.into(),
+            WriteOp::Delete,
+            Arc::new(source),
+        ));
         Ok(plan)
     }
 
@@ -844,7 +965,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             .schema_provider
             .get_table_provider(table_name.clone())?;
         let arrow_schema = (*provider.schema()).clone();
-        let table_schema = Arc::new(DFSchema::try_from(arrow_schema)?);
+        let table_schema = Arc::new(DFSchema::try_from_qualified_schema(
+            table_name.clone(),
+            &arrow_schema,
+        )?);
         let values = table_schema.fields().iter().map(|f| {
             (
                 f.name().clone(),
@@ -923,12 +1047,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
         let source = project(source, exprs)?;
 
-        let plan = LogicalPlan::Dml(DmlStatement {
+        let plan = LogicalPlan::Dml(DmlStatement::new(
             table_name,
             table_schema,
-            op: WriteOp::Update,
-            input: Arc::new(source),
-        });
+            WriteOp::Update,
+            Arc::new(source),
+        ));
         Ok(plan)
     }
 
@@ -1014,12 +1138,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             .collect::<Result<Vec<datafusion_expr::Expr>>>()?;
         let source = project(source, exprs)?;
 
-        let plan = LogicalPlan::Dml(DmlStatement {
+        let plan = LogicalPlan::Dml(DmlStatement::new(
             table_name,
-            table_schema: Arc::new(table_schema),
-            op: WriteOp::Insert,
-            input: Arc::new(source),
-        });
+            Arc::new(table_schema),
+            WriteOp::Insert,
+            Arc::new(source),
+        ));
         Ok(plan)
     }
 
@@ -20,7 +20,9 @@ use std::collections::HashMap;
 use std::{sync::Arc, vec};
 
 use arrow_schema::*;
-use sqlparser::dialect::{Dialect, GenericDialect, HiveDialect, MySqlDialect};
+use sqlparser::dialect::{
+    Dialect, GenericDialect, HiveDialect, MySqlDialect, PostgreSqlDialect,
+};
 
 use datafusion_common::{
     assert_contains, config::ConfigOptions, DataFusionError, Result, ScalarValue,
@@ -379,7 +381,7 @@ fn plan_update() {
     let plan = r#"
 Dml: op=[Update] table=[person]
   Projection: person.id AS id, person.first_name AS first_name, Utf8("Kay") AS last_name, person.age AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀
-    Filter: id = Int64(1)
+    Filter: person.id = Int64(1)
       TableScan: person
       "#
     .trim();
@@ -403,7 +405,7 @@ fn plan_delete() {
     let sql = "delete from person where id=1";
     let plan = r#"
 Dml: op=[Delete] table=[person]
-  Filter: id = Int64(1)
+  Filter: person.id = Int64(1)
     TableScan: person
     "#
     .trim();
@@ -437,6 +439,15 @@ fn select_wildcard_with_repeated_column() {
     );
 }
 
+#[test]
+fn select_wildcard_with_options() {
+    quick_test(
+        r#"SELECT * EXCLUDE (birth_date, "😀") RENAME (id AS person_id) REPLACE (salary * 2 AS salary) FROM person"#,
+        "Projection: person.id AS person_id, person.first_name, person.last_name, person.age, person.state, person.salary * Int64(2) AS salary\
+        \n  TableScan: person",
+    );
+}
+
 #[test]
 fn select_wildcard_with_repeated_column_but_is_aliased() {
     quick_test(
@@ -1278,11 +1289,57 @@ fn recursive_ctes() {
               select n + 1 FROM numbers WHERE N < 10
         )
         select * from numbers;";
-    let err = logical_plan(sql).expect_err("query should have failed");
-    assert_eq!(
-        r#"NotImplemented("Recursive CTEs are not supported")"#,
-        format!("{err:?}")
-    );
+    let expected = "Projection: numbers.n\
+        \n  SubqueryAlias: numbers\
+        \n    RecursiveQuery: name=numbers, is_distinct=false\
+        \n      Projection: Int64(1) AS n\
+        \n        EmptyRelation\
+        \n      Projection: numbers.n + Int64(1)\
+        \n        Filter: numbers.n < Int64(10)\
+        \n          TableScan: numbers";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn recursive_ctes_with_self_reference_in_subquery() {
+    let sql = "
+        WITH RECURSIVE numbers AS (
+              select 1 as n
+            UNION ALL
+              select id + 1 FROM person WHERE id IN (select n FROM numbers)
+        )
+        select * from numbers;";
+    let expected = "Projection: numbers.n\
+        \n  SubqueryAlias: numbers\
+        \n    RecursiveQuery: name=numbers, is_distinct=false\
+        \n      Projection: Int64(1) AS n\
+        \n        EmptyRelation\
+        \n      Projection: person.id + Int64(1)\
+        \n        Filter: person.id IN (<subquery>)\
+        \n          Subquery:\
+        \n            Projection: numbers.n\
+        \n              TableScan: numbers\
+        \n          TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn recursive_ctes_without_self_reference() {
+    let sql = "
+        WITH RECURSIVE t AS (
+              select 1 as a
+            UNION ALL
+              select 2
+        )
+        select * from t;";
+    let expected = "Projection: t.a\
+        \n  SubqueryAlias: t\
+        \n    Union\
+        \n      Projection: Int64(1) AS a\
+        \n        EmptyRelation\
+        \n      Projection: Int64(2) AS a\
+        \n        EmptyRelation";
+    quick_test(sql, expected);
 }
 
 #[test]
@@ -2355,6 +2412,40 @@ fn over_partition_by_order_by() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn qualify_window_alias() {
+    let sql = "SELECT order_id, ROW_NUMBER() OVER (PARTITION BY customer_id ORDER BY qty) AS rn \
+        FROM orders QUALIFY rn = 1";
+    let expected = "\
+        Projection: orders.order_id, ROW_NUMBER() PARTITION BY [orders.customer_id] ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS rn\
+        \n  Filter: ROW_NUMBER() PARTITION BY [orders.customer_id] ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW = Int64(1)\
+        \n    WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [orders.customer_id] ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_window_not_in_projection() {
+    let sql = "SELECT order_id FROM orders \
+        QUALIFY MAX(qty) OVER (PARTITION BY customer_id) = qty";
+    let expected = "\
+        Projection: orders.order_id\
+        \n  Filter: MAX(orders.qty) PARTITION BY [orders.customer_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING = orders.qty\
+        \n    WindowAggr: windowExpr=[[MAX(orders.qty) PARTITION BY [orders.customer_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_without_window_function() {
+    let sql = "SELECT order_id FROM orders QUALIFY order_id = 1";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: QUALIFY clause requires a window function in the SELECT list or the QUALIFY clause",
+        err.to_string()
+    );
+}
+
 /// psql result
 /// ```text
 ///                               QUERY PLAN
@@ -2683,6 +2774,14 @@ impl ContextProvider for MockContextProvider {
     fn options(&self) -> &ConfigOptions {
         &self.options
     }
+
+    fn create_cte_work_table(
+        &self,
+        _name: &str,
+        schema: SchemaRef,
+    ) -> Result<Arc<dyn TableSource>> {
+        Ok(Arc::new(EmptyTable::new(schema)))
+    }
 }
 
 #[test]
@@ -3161,6 +3260,117 @@ fn test_distribute_by() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn test_distribute_by_sort_by() {
+    let sql = "select id, state from person distribute by state sort by id";
+    let expected = "Sort: person.id ASC NULLS LAST, preserve_partitioning=true\
+        \n  Repartition: DistributeBy(state)\
+        \n    Projection: person.id, person.state\
+        \n      TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn test_sort_by() {
+    let sql = "select id from person sort by id";
+    let expected = "Sort: person.id ASC NULLS LAST, preserve_partitioning=true\
+        \n  Projection: person.id\
+        \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn test_cluster_by() {
+    let sql = "select id, state from person cluster by state";
+    let expected = "Sort: person.state ASC NULLS LAST, preserve_partitioning=true\
+        \n  Repartition: DistributeBy(state)\
+        \n    Projection: person.id, person.state\
+        \n      TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn test_cluster_by_with_sort_by() {
+    let sql = "select id, state from person cluster by state sort by id";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"CLUSTER BY cannot be used together with DISTRIBUTE BY or SORT BY\")",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn test_copy_to() {
+    let sql = "COPY person TO 'out.parquet'";
+    let expected = "CopyTo: format=PARQUET output_url=out.parquet\
+        \n  TableScan: person";
+    quick_test(sql, expected);
+
+    let sql = "COPY (SELECT state, id FROM person WHERE age > 20) TO 'out/' \
+               (FORMAT csv, PARTITION_BY (state))";
+    let expected = "CopyTo: format=CSV output_url=out/ partition_by=[state]\
+        \n  Projection: person.id, person.state\
+        \n    Projection: person.state, person.id\
+        \n      Filter: person.age > Int64(20)\
+        \n        TableScan: person";
+    quick_test(sql, expected);
+
+    // Partition columns are normalized like any other identifier
+    let sql = "COPY (SELECT state, id FROM person) TO 'out/' \
+               (FORMAT parquet, PARTITION_BY (State), MAX_ROW_GROUP_SIZE 1024)";
+    let expected = "CopyTo: format=PARQUET output_url=out/ partition_by=[state]\
+        \n  Projection: person.id, person.state\
+        \n    Projection: person.state, person.id\
+        \n      TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn test_copy_to_errors() {
+    for (sql, error) in [
+        (
+            "COPY person TO 'out/'",
+            "Cannot infer the format of 'out/', specify it with the FORMAT option",
+        ),
+        (
+            "COPY person TO 'out.avro' (FORMAT avro)",
+            "COPY TO does not support writing AVRO files",
+        ),
+        (
+            "COPY person TO 'out.parquet' (COMPRESSION xz)",
+            "Compression XZ is not supported for PARQUET files",
+        ),
+        (
+            "COPY person TO 'out/' (FORMAT json, PARTITION_BY (country))",
+            "Partition column country is not in the output of COPY TO",
+        ),
+        (
+            "COPY person TO 'out/' (FORMAT json, PARTITION_BY (\"State\"))",
+            "Partition column State is not in the output of COPY TO",
+        ),
+    ] {
+        let err = logical_plan(sql).expect_err("query should have failed");
+        assert_eq!(format!("Plan({error:?})"), format!("{err:?}"));
+    }
+}
+
+#[test]
+fn test_array_contains_operators() {
+    // the generic dialect reads `@` as the start of an identifier
+    let dialect = &PostgreSqlDialect {};
+    let expected =
+        "Projection: arraycontains(makearray(person.age), makearray(person.id))\
+        \n  TableScan: person";
+
+    let sql = "SELECT make_array(age) @> make_array(id) FROM person";
+    let plan = logical_plan_with_dialect(sql, dialect).unwrap();
+    assert_eq!(format!("{plan:?}"), expected);
+
+    let sql = "SELECT make_array(id) <@ make_array(age) FROM person";
+    let plan = logical_plan_with_dialect(sql, dialect).unwrap();
+    assert_eq!(format!("{plan:?}"), expected);
+}
+
 #[test]
 fn test_double_quoted_literal_string() {
     // Assert double quoted literal string is parsed correctly like single quoted one in specific
@@ -3372,26 +3582,14 @@ fn test_select_distinct_order_by() {
 }
 
 #[rstest]
-#[case::select_cluster_by_unsupported(
-    "SELECT customer_name, SUM(order_total) as total_order_amount FROM orders CLUSTER BY customer_name",
-    "This feature is not implemented: CLUSTER BY"
-)]
 #[case::select_lateral_view_unsupported(
     "SELECT id, number FROM person LATERAL VIEW explode(numbers) exploded_table AS number",
     "This feature is not implemented: LATERAL VIEWS"
 )]
-#[case::select_qualify_unsupported(
-    "SELECT i, p, o FROM person QUALIFY ROW_NUMBER() OVER (PARTITION BY p ORDER BY o) = 1",
-    "This feature is not implemented: QUALIFY"
-)]
 #[case::select_top_unsupported(
     "SELECT TOP (5) * FROM person",
     "This feature is not implemented: TOP"
 )]
-#[case::select_sort_by_unsupported(
-    "SELECT * FROM person SORT BY id",
-    "This feature is not implemented: SORT BY"
-)]
 #[test]
 fn test_select_unsupported_syntax_errors(#[case] sql: &str, #[case] error: &str) {
     let err = logical_plan(sql).unwrap_err();
@@ -3781,7 +3979,7 @@ fn test_prepare_statement_update_infer() {
     let expected_plan = r#"
 Dml: op=[Update] table=[person]
   Projection: person.id AS id, person.first_name AS first_name, person.last_name AS last_name, $1 AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀
-    Filter: id = $2
+    Filter: person.id = $2
       TableScan: person
         "#
         .trim();
@@ -3801,7 +3999,7 @@ Dml: op=[Update] table=[person]
     let expected_plan = r#"
 Dml: op=[Update] table=[person]
   Projection: person.id AS id, person.first_name AS first_name, person.last_name AS last_name, Int32(42) AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀
-    Filter: id = UInt32(1)
+    Filter: person.id = UInt32(1)
       TableScan: person
         "#
         .trim();
@@ -16,13 +16,15 @@
 // under the License.
 
 use async_recursion::async_recursion;
-use datafusion::arrow::datatypes::{DataType, Field, TimeUnit};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
 use datafusion::common::{DFField, DFSchema, DFSchemaRef};
 use datafusion::logical_expr::{
-    aggregate_function, window_function::find_df_window_func, BinaryExpr, Case, Expr,
-    LogicalPlan, Operator,
+    aggregate_function, window_function::find_df_window_func, BinaryExpr,
+    BuiltinScalarFunction, Case, Expr, LogicalPlan, Operator,
+};
+use datafusion::logical_expr::{
+    build_join_schema, EmptyRelation, LogicalPlanBuilder, Values,
 };
-use datafusion::logical_expr::{build_join_schema, LogicalPlanBuilder};
 use datafusion::logical_expr::{expr, Cast, WindowFrameBound, WindowFrameUnits};
 use datafusion::prelude::JoinType;
 use datafusion::sql::TableReference;
@@ -47,16 +49,19 @@ use substrait::proto::{
     join_rel, plan_rel, r#type,
     read_rel::ReadType,
     rel::RelType,
+    set_rel,
     sort_field::{SortDirection, SortKind::*},
-    AggregateFunction, Expression, Plan, Rel, Type,
+    AggregateFunction, Expression, NamedStruct, Plan, Rel, Type,
 };
 use substrait::proto::{FunctionArgument, SortField};
 
 use datafusion::logical_expr::expr::Sort;
+use prost_types::Any as ProtoAny;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use crate::logical_plan::{DefaultSubstraitExtensionCodec, SubstraitExtensionCodec};
 use crate::variation_const::{
     DATE_32_TYPE_REF, DATE_64_TYPE_REF, DECIMAL_128_TYPE_REF, DECIMAL_256_TYPE_REF,
     DEFAULT_CONTAINER_TYPE_REF, DEFAULT_TYPE_REF, LARGE_CONTAINER_TYPE_REF,
@@ -101,6 +106,21 @@ pub fn name_to_op(name: &str) -> Result<Operator> {
 pub async fn from_substrait_plan(
     ctx: &mut SessionContext,
     plan: &Plan,
+) -> Result<LogicalPlan> {
+    from_substrait_plan_with_extension_codec(
+        ctx,
+        plan,
+        &DefaultSubstraitExtensionCodec {},
+    )
+    .await
+}
+
+/// Convert Substrait Plan to DataFusion DataFrame, deserializing extension
+/// relations with `extension_codec`
+pub async fn from_substrait_plan_with_extension_codec(
+    ctx: &mut SessionContext,
+    plan: &Plan,
+    extension_codec: &dyn SubstraitExtensionCodec,
 ) -> Result<LogicalPlan> {
     // Register function extension
     let function_extension = plan
@@ -126,10 +146,10 @@ pub async fn from_substrait_plan(
             match plan.relations[0].rel_type.as_ref() {
                 Some(rt) => match rt {
                     plan_rel::RelType::Rel(rel) => {
-                        Ok(from_substrait_rel(ctx, rel, &function_extension).await?)
+                        Ok(from_substrait_rel(ctx, rel, &function_extension, extension_codec).await?)
                     },
                     plan_rel::RelType::Root(root) => {
-                        Ok(from_substrait_rel(ctx, root.input.as_ref().unwrap(), &function_extension).await?)
+                        Ok(from_substrait_rel(ctx, root.input.as_ref().unwrap(), &function_extension, extension_codec).await?)
                     }
                 },
                 None => Err(DataFusionError::Internal("Cannot parse plan relation: None".to_string()))
@@ -148,12 +168,13 @@ pub async fn from_substrait_rel(
     ctx: &mut SessionContext,
     rel: &Rel,
     extensions: &HashMap<u32, &String>,
+    extension_codec: &dyn SubstraitExtensionCodec,
 ) -> Result<LogicalPlan> {
     match &rel.rel_type {
         Some(RelType::Project(p)) => {
             if let Some(input) = p.input.as_ref() {
                 let mut input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, extensions, extension_codec).await?,
                 );
                 let mut exprs: Vec<Expr> = vec![];
                 for e in &p.expressions {
@@ -182,7 +203,7 @@ pub async fn from_substrait_rel(
         Some(RelType::Filter(filter)) => {
             if let Some(input) = filter.input.as_ref() {
                 let input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, extensions, extension_codec).await?,
                 );
                 if let Some(condition) = filter.condition.as_ref() {
                     let expr =
@@ -202,7 +223,7 @@ pub async fn from_substrait_rel(
         Some(RelType::Fetch(fetch)) => {
             if let Some(input) = fetch.input.as_ref() {
                 let input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, extensions, extension_codec).await?,
                 );
                 let offset = fetch.offset as usize;
                 let count = fetch.count as usize;
@@ -216,7 +237,7 @@ pub async fn from_substrait_rel(
         Some(RelType::Sort(sort)) => {
             if let Some(input) = sort.input.as_ref() {
                 let input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, extensions, extension_codec).await?,
                 );
                 let sorts =
                     from_substrait_sorts(&sort.sorts, input.schema(), extensions).await?;
@@ -230,7 +251,7 @@ pub async fn from_substrait_rel(
         Some(RelType::Aggregate(agg)) => {
             if let Some(input) = agg.input.as_ref() {
                 let input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, extensions, extension_codec).await?,
                 );
                 let mut group_expr = vec![];
                 let mut aggr_expr = vec![];
@@ -299,10 +320,22 @@ pub async fn from_substrait_rel(
         }
         Some(RelType::Join(join)) => {
             let left = LogicalPlanBuilder::from(
-                from_substrait_rel(ctx, join.left.as_ref().unwrap(), extensions).await?,
+                from_substrait_rel(
+                    ctx,
+                    join.left.as_ref().unwrap(),
+                    extensions,
+                    extension_codec,
+                )
+                .await?,
             );
             let right = LogicalPlanBuilder::from(
-                from_substrait_rel(ctx, join.right.as_ref().unwrap(), extensions).await?,
+                from_substrait_rel(
+                    ctx,
+                    join.right.as_ref().unwrap(),
+                    extensions,
+                    extension_codec,
+                )
+                .await?,
             );
             let join_type = from_substrait_jointype(join.r#type)?;
             // The join condition expression needs full input schema and not the output schema from join since we lose columns from
@@ -423,10 +456,166 @@ pub async fn from_substrait_rel(
                     _ => Ok(t),
                 }
             }
+            Some(ReadType::VirtualTable(vt)) => {
+                let base_schema = read.base_schema.as_ref().ok_or_else(|| {
+                    DataFusionError::Substrait(
+                        "No base schema provided for VirtualTable".to_string(),
+                    )
+                })?;
+                let schema = DFSchemaRef::new(
+                    from_substrait_named_struct(base_schema)?.try_into()?,
+                );
+                match vt.values.as_slice() {
+                    // see the producer of EmptyRelation
+                    [] => Ok(LogicalPlan::EmptyRelation(EmptyRelation {
+                        produce_one_row: false,
+                        schema,
+                    })),
+                    [row] if row.fields.is_empty() => {
+                        LogicalPlanBuilder::empty(true).build()
+                    }
+                    rows => {
+                        let values = rows
+                            .iter()
+                            .map(|row| {
+                                if row.fields.len() != schema.fields().len() {
+                                    return Err(DataFusionError::Substrait(format!(
+                                        "VirtualTable row has {} values but the base schema has {} fields",
+                                        row.fields.len(),
+                                        schema.fields().len()
+                                    )));
+                                }
+                                row.fields
+                                    .iter()
+                                    .map(|lit| {
+                                        Ok(Expr::Literal(from_substrait_literal(lit)?))
+                                    })
+                                    .collect::<Result<Vec<_>>>()
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        Ok(LogicalPlan::Values(Values { schema, values }))
+                    }
+                }
+            }
             _ => Err(DataFusionError::NotImplemented(
-                "Only NamedTable reads are supported".to_string(),
+                "Only NamedTable and VirtualTable reads are supported".to_string(),
             )),
         },
+        Some(RelType::Set(set)) => {
+            let mut inputs = Vec::with_capacity(set.inputs.len());
+            for input in &set.inputs {
+                inputs.push(
+                    from_substrait_rel(ctx, input, extensions, extension_codec).await?,
+                );
+            }
+            let op = set_rel::SetOp::from_i32(set.op).ok_or_else(|| {
+                DataFusionError::Substrait(format!("invalid set operation {}", set.op))
+            })?;
+            match op {
+                set_rel::SetOp::UnionAll | set_rel::SetOp::UnionDistinct => {
+                    let mut inputs = inputs.into_iter();
+                    let first = inputs.next().ok_or_else(|| {
+                        DataFusionError::Substrait(
+                            "Union relation requires at least one input".to_string(),
+                        )
+                    })?;
+                    let mut builder = LogicalPlanBuilder::from(first);
+                    for input in inputs {
+                        builder = builder.union(input)?;
+                    }
+                    if op == set_rel::SetOp::UnionDistinct {
+                        builder = builder.distinct()?;
+                    }
+                    builder.build()
+                }
+                set_rel::SetOp::IntersectionPrimary
+                | set_rel::SetOp::IntersectionMultiset
+                | set_rel::SetOp::MinusPrimary
+                | set_rel::SetOp::MinusMultiset => {
+                    if inputs.len() != 2 {
+                        return Err(DataFusionError::NotImplemented(format!(
+                            "Set operation {op:?} is only supported with two inputs"
+                        )));
+                    }
+                    let right = inputs.pop().unwrap();
+                    let left = inputs.pop().unwrap();
+                    match op {
+                        set_rel::SetOp::IntersectionPrimary => {
+                            LogicalPlanBuilder::intersect(left, right, false)
+                        }
+                        set_rel::SetOp::IntersectionMultiset => {
+                            LogicalPlanBuilder::intersect(left, right, true)
+                        }
+                        set_rel::SetOp::MinusPrimary => {
+                            LogicalPlanBuilder::except(left, right, false)
+                        }
+                        _ => LogicalPlanBuilder::except(left, right, true),
+                    }
+                }
+                set_rel::SetOp::Unspecified => Err(DataFusionError::Substrait(
+                    "Unspecified set operation".to_string(),
+                )),
+            }
+        }
+        Some(RelType::Cross(cross)) => {
+            let left = match cross.left.as_ref() {
+                Some(left) => {
+                    from_substrait_rel(ctx, left, extensions, extension_codec).await?
+                }
+                None => {
+                    return Err(DataFusionError::Substrait(
+                        "Cross relation without a left input is not valid".to_string(),
+                    ))
+                }
+            };
+            let right = match cross.right.as_ref() {
+                Some(right) => {
+                    from_substrait_rel(ctx, right, extensions, extension_codec).await?
+                }
+                None => {
+                    return Err(DataFusionError::Substrait(
+                        "Cross relation without a right input is not valid".to_string(),
+                    ))
+                }
+            };
+            LogicalPlanBuilder::from(left).cross_join(right)?.build()
+        }
+        Some(RelType::ExtensionLeaf(extension)) => {
+            from_substrait_extension(ctx, extension.detail.as_ref(), &[], extension_codec)
+        }
+        Some(RelType::ExtensionSingle(extension)) => {
+            let input = match extension.input.as_ref() {
+                Some(input) => {
+                    from_substrait_rel(ctx, input, extensions, extension_codec).await?
+                }
+                None => {
+                    return Err(DataFusionError::Substrait(
+                        "ExtensionSingle relation without an input is not valid"
+                            .to_string(),
+                    ))
+                }
+            };
+            from_substrait_extension(
+                ctx,
+                extension.detail.as_ref(),
+                &[input],
+                extension_codec,
+            )
+        }
+        Some(RelType::ExtensionMulti(extension)) => {
+            let mut inputs = Vec::with_capacity(extension.inputs.len());
+            for input in &extension.inputs {
+                inputs.push(
+                    from_substrait_rel(ctx, input, extensions, extension_codec).await?,
+                );
+            }
+            from_substrait_extension(
+                ctx,
+                extension.detail.as_ref(),
+                &inputs,
+                extension_codec,
+            )
+        }
         _ => Err(DataFusionError::NotImplemented(format!(
             "Unsupported RelType: {:?}",
             rel.rel_type
@@ -434,6 +623,45 @@ pub async fn from_substrait_rel(
     }
 }
 
+fn from_substrait_extension(
+    ctx: &SessionContext,
+    detail: Option<&ProtoAny>,
+    inputs: &[LogicalPlan],
+    extension_codec: &dyn SubstraitExtensionCodec,
+) -> Result<LogicalPlan> {
+    let detail = detail.ok_or_else(|| {
+        DataFusionError::Substrait(
+            "Extension relation without a detail is not valid".to_string(),
+        )
+    })?;
+    let extension =
+        extension_codec.try_decode(&detail.type_url, &detail.value, inputs, ctx)?;
+    Ok(LogicalPlan::Extension(extension))
+}
+
+pub(crate) fn from_substrait_named_struct(base_schema: &NamedStruct) -> Result<Schema> {
+    let types = match base_schema.r#struct.as_ref() {
+        Some(s) => &s.types,
+        None => {
+            return Err(DataFusionError::Substrait(
+                "Named struct must contain a struct".to_string(),
+            ))
+        }
+    };
+    if types.len() != base_schema.names.len() {
+        return Err(DataFusionError::NotImplemented(
+            "Named struct with nested fields is not supported".to_string(),
+        ));
+    }
+    let fields = base_schema
+        .names
+        .iter()
+        .zip(types)
+        .map(|(name, dt)| Ok(Field::new(name, from_substrait_type(dt)?, true)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Schema::new(fields))
+}
+
 fn from_substrait_jointype(join_type: i32) -> Result<JoinType> {
     if let Some(substrait_join_type) = join_rel::JoinType::from_i32(join_type) {
         match substrait_join_type {
@@ -677,6 +905,17 @@ pub async fn from_substrait_rex(
             })))
         }
         Some(RexType::ScalarFunction(f)) => {
+            if let Some(fun) = extensions
+                .get(&f.function_reference)
+                .and_then(|fname| BuiltinScalarFunction::from_str(fname).ok())
+            {
+                let args =
+                    from_substriat_func_args(&f.arguments, input_schema, extensions)
+                        .await?;
+                return Ok(Arc::new(Expr::ScalarFunction(expr::ScalarFunction::new(
+                    fun, args,
+                ))));
+            }
             assert!(f.arguments.len() == 2);
             let op = match extensions.get(&f.function_reference) {
                 Some(fname) => name_to_op(fname),
@@ -777,7 +1016,7 @@ pub async fn from_substrait_rex(
     }
 }
 
-fn from_substrait_type(dt: &substrait::proto::Type) -> Result<DataType> {
+pub(crate) fn from_substrait_type(dt: &substrait::proto::Type) -> Result<DataType> {
     match &dt.kind {
         Some(s_kind) => match s_kind {
             r#type::Kind::Bool(_) => Ok(DataType::Boolean),
@@ -15,5 +15,58 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fmt::Debug;
+
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::{Extension, LogicalPlan};
+use datafusion::prelude::SessionContext;
+
 pub mod consumer;
 pub mod producer;
+
+/// Serializes the [`Extension`] nodes of a logical plan into the `detail` of
+/// Substrait extension relations, and deserializes them back.
+///
+/// The producer records [`UserDefinedLogicalNode::name`] as the type url of
+/// the detail, which is handed back to [`Self::try_decode`] by the consumer.
+///
+/// [`UserDefinedLogicalNode::name`]: datafusion::logical_expr::UserDefinedLogicalNode::name
+pub trait SubstraitExtensionCodec: Debug + Send + Sync {
+    /// Rebuild the extension node called `name` from `buf` and its already
+    /// converted `inputs`
+    fn try_decode(
+        &self,
+        name: &str,
+        buf: &[u8],
+        inputs: &[LogicalPlan],
+        ctx: &SessionContext,
+    ) -> Result<Extension>;
+
+    /// Serialize `node`, without its inputs, into `buf`
+    fn try_encode(&self, node: &Extension, buf: &mut Vec<u8>) -> Result<()>;
+}
+
+/// A [`SubstraitExtensionCodec`] that doesn't support any extension node
+#[derive(Debug, Clone)]
+pub struct DefaultSubstraitExtensionCodec {}
+
+impl SubstraitExtensionCodec for DefaultSubstraitExtensionCodec {
+    fn try_decode(
+        &self,
+        name: &str,
+        _buf: &[u8],
+        _inputs: &[LogicalPlan],
+        _ctx: &SessionContext,
+    ) -> Result<Extension> {
+        Err(DataFusionError::NotImplemented(format!(
+            "SubstraitExtensionCodec is not provided, can't decode extension {name}"
+        )))
+    }
+
+    fn try_encode(&self, node: &Extension, _buf: &mut Vec<u8>) -> Result<()> {
+        Err(DataFusionError::NotImplemented(format!(
+            "SubstraitExtensionCodec is not provided, can't encode extension {}",
+            node.node.name()
+        )))
+    }
+}
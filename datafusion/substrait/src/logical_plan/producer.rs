@@ -18,7 +18,7 @@
 use std::{collections::HashMap, sync::Arc};
 
 use datafusion::{
-    arrow::datatypes::{DataType, TimeUnit},
+    arrow::datatypes::{DataType, Schema, TimeUnit},
     error::{DataFusionError, Result},
     logical_expr::{WindowFrame, WindowFrameBound},
     prelude::JoinType,
@@ -29,8 +29,11 @@ use datafusion::common::DFSchemaRef;
 #[allow(unused_imports)]
 use datafusion::logical_expr::aggregate_function;
 use datafusion::logical_expr::expr::{BinaryExpr, Case, Cast, Sort, WindowFunction};
-use datafusion::logical_expr::{expr, Between, JoinConstraint, LogicalPlan, Operator};
+use datafusion::logical_expr::{
+    expr, Between, BuiltinScalarFunction, JoinConstraint, LogicalPlan, Operator,
+};
 use datafusion::prelude::{binary_expr, Expr};
+use prost_types::Any as ProtoAny;
 use substrait::{
     proto::{
         aggregate_function::AggregationInvocation,
@@ -38,7 +41,7 @@ use substrait::{
         expression::{
             field_reference::ReferenceType,
             if_then::IfClause,
-            literal::{Decimal, LiteralType},
+            literal::{Decimal, LiteralType, Struct},
             mask_expression::{StructItem, StructSelect},
             reference_segment,
             window_function::bound as SubstraitBound,
@@ -53,16 +56,19 @@ use substrait::{
         },
         function_argument::ArgType,
         join_rel, plan_rel, r#type,
-        read_rel::{NamedTable, ReadType},
+        read_rel::{NamedTable, ReadType, VirtualTable},
         rel::RelType,
+        set_rel,
         sort_field::{SortDirection, SortKind},
-        AggregateFunction, AggregateRel, AggregationPhase, Expression, FetchRel,
-        FilterRel, FunctionArgument, JoinRel, NamedStruct, Plan, PlanRel, ProjectRel,
-        ReadRel, Rel, RelRoot, SortField, SortRel,
+        AggregateFunction, AggregateRel, AggregationPhase, CrossRel, Expression,
+        ExtensionLeafRel, ExtensionMultiRel, ExtensionSingleRel, FetchRel, FilterRel,
+        FunctionArgument, JoinRel, NamedStruct, Plan, PlanRel, ProjectRel, ReadRel, Rel,
+        RelRoot, SetRel, SortField, SortRel,
     },
     version,
 };
 
+use crate::logical_plan::{DefaultSubstraitExtensionCodec, SubstraitExtensionCodec};
 use crate::variation_const::{
     DATE_32_TYPE_REF, DATE_64_TYPE_REF, DECIMAL_128_TYPE_REF, DECIMAL_256_TYPE_REF,
     DEFAULT_CONTAINER_TYPE_REF, DEFAULT_TYPE_REF, LARGE_CONTAINER_TYPE_REF,
@@ -72,6 +78,15 @@ use crate::variation_const::{
 
 /// Convert DataFusion LogicalPlan to Substrait Plan
 pub fn to_substrait_plan(plan: &LogicalPlan) -> Result<Box<Plan>> {
+    to_substrait_plan_with_extension_codec(plan, &DefaultSubstraitExtensionCodec {})
+}
+
+/// Convert DataFusion LogicalPlan to Substrait Plan, serializing extension
+/// nodes with `extension_codec`
+pub fn to_substrait_plan_with_extension_codec(
+    plan: &LogicalPlan,
+    extension_codec: &dyn SubstraitExtensionCodec,
+) -> Result<Box<Plan>> {
     // Parse relation nodes
     let mut extension_info: (
         Vec<extensions::SimpleExtensionDeclaration>,
@@ -81,7 +96,11 @@ pub fn to_substrait_plan(plan: &LogicalPlan) -> Result<Box<Plan>> {
     // Note: Only 1 relation tree is currently supported
     let plan_rels = vec![PlanRel {
         rel_type: Some(plan_rel::RelType::Root(RelRoot {
-            input: Some(*to_substrait_rel(plan, &mut extension_info)?),
+            input: Some(*to_substrait_rel(
+                plan,
+                &mut extension_info,
+                extension_codec,
+            )?),
             names: plan.schema().field_names(),
         })),
     }];
@@ -106,6 +125,7 @@ pub fn to_substrait_rel(
         Vec<extensions::SimpleExtensionDeclaration>,
         HashMap<String, u32>,
     ),
+    extension_codec: &dyn SubstraitExtensionCodec,
 ) -> Result<Box<Rel>> {
     match plan {
         LogicalPlan::TableScan(scan) => {
@@ -156,14 +176,19 @@ pub fn to_substrait_rel(
             Ok(Box::new(Rel {
                 rel_type: Some(RelType::Project(Box::new(ProjectRel {
                     common: None,
-                    input: Some(to_substrait_rel(p.input.as_ref(), extension_info)?),
+                    input: Some(to_substrait_rel(
+                        p.input.as_ref(),
+                        extension_info,
+                        extension_codec,
+                    )?),
                     expressions,
                     advanced_extension: None,
                 }))),
             }))
         }
         LogicalPlan::Filter(filter) => {
-            let input = to_substrait_rel(filter.input.as_ref(), extension_info)?;
+            let input =
+                to_substrait_rel(filter.input.as_ref(), extension_info, extension_codec)?;
             let filter_expr = to_substrait_rex(
                 &filter.predicate,
                 filter.input.schema(),
@@ -179,7 +204,8 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Limit(limit) => {
-            let input = to_substrait_rel(limit.input.as_ref(), extension_info)?;
+            let input =
+                to_substrait_rel(limit.input.as_ref(), extension_info, extension_codec)?;
             let limit_fetch = limit.fetch.unwrap_or(0);
             Ok(Box::new(Rel {
                 rel_type: Some(RelType::Fetch(Box::new(FetchRel {
@@ -192,7 +218,13 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Sort(sort) => {
-            let input = to_substrait_rel(sort.input.as_ref(), extension_info)?;
+            if sort.preserve_partitioning {
+                return Err(DataFusionError::NotImplemented(
+                    "Sort within partitions".to_string(),
+                ));
+            }
+            let input =
+                to_substrait_rel(sort.input.as_ref(), extension_info, extension_codec)?;
             let sort_fields = sort
                 .expr
                 .iter()
@@ -208,7 +240,8 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Aggregate(agg) => {
-            let input = to_substrait_rel(agg.input.as_ref(), extension_info)?;
+            let input =
+                to_substrait_rel(agg.input.as_ref(), extension_info, extension_codec)?;
             // Translate aggregate expression to Substrait's groupings (repeated repeated Expression)
             let grouping = agg
                 .group_expr
@@ -234,8 +267,21 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Distinct(distinct) => {
+            // UNION (DISTINCT) is planned as a Distinct on top of a Union
+            if let LogicalPlan::Union(union) = distinct.input.as_ref() {
+                return to_substrait_set_rel(
+                    &union.inputs,
+                    set_rel::SetOp::UnionDistinct,
+                    extension_info,
+                    extension_codec,
+                );
+            }
             // Use Substrait's AggregateRel with empty measures to represent `select distinct`
-            let input = to_substrait_rel(distinct.input.as_ref(), extension_info)?;
+            let input = to_substrait_rel(
+                distinct.input.as_ref(),
+                extension_info,
+                extension_codec,
+            )?;
             // Get grouping keys from the input relation's number of output fields
             let grouping = (0..distinct.input.schema().fields().len())
                 .map(substrait_field_ref)
@@ -254,8 +300,10 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Join(join) => {
-            let left = to_substrait_rel(join.left.as_ref(), extension_info)?;
-            let right = to_substrait_rel(join.right.as_ref(), extension_info)?;
+            let left =
+                to_substrait_rel(join.left.as_ref(), extension_info, extension_codec)?;
+            let right =
+                to_substrait_rel(join.right.as_ref(), extension_info, extension_codec)?;
             let join_type = to_substrait_jointype(join.join_type);
             // we only support basic joins so return an error for anything not yet supported
             if join.filter.is_some() {
@@ -319,10 +367,11 @@ pub fn to_substrait_rel(
         LogicalPlan::SubqueryAlias(alias) => {
             // Do nothing if encounters SubqueryAlias
             // since there is no corresponding relation type in Substrait
-            to_substrait_rel(alias.input.as_ref(), extension_info)
+            to_substrait_rel(alias.input.as_ref(), extension_info, extension_codec)
         }
         LogicalPlan::Window(window) => {
-            let input = to_substrait_rel(window.input.as_ref(), extension_info)?;
+            let input =
+                to_substrait_rel(window.input.as_ref(), extension_info, extension_codec)?;
             // If the input is a Project relation, we can just append the WindowFunction expressions
             // before returning
             // Otherwise, wrap the input in a Project relation before appending the WindowFunction
@@ -357,12 +406,176 @@ pub fn to_substrait_rel(
                 rel_type: Some(RelType::Project(project_rel)),
             }))
         }
+        LogicalPlan::Union(union) => to_substrait_set_rel(
+            &union.inputs,
+            set_rel::SetOp::UnionAll,
+            extension_info,
+            extension_codec,
+        ),
+        LogicalPlan::CrossJoin(cross_join) => {
+            let left = to_substrait_rel(
+                cross_join.left.as_ref(),
+                extension_info,
+                extension_codec,
+            )?;
+            let right = to_substrait_rel(
+                cross_join.right.as_ref(),
+                extension_info,
+                extension_codec,
+            )?;
+            Ok(Box::new(Rel {
+                rel_type: Some(RelType::Cross(Box::new(CrossRel {
+                    common: None,
+                    left: Some(left),
+                    right: Some(right),
+                    advanced_extension: None,
+                }))),
+            }))
+        }
+        LogicalPlan::Values(values) => {
+            let base_schema = to_substrait_named_struct(&values.schema.as_ref().into())?;
+            let values = values
+                .values
+                .iter()
+                .map(|row| {
+                    let fields = row
+                        .iter()
+                        .map(|value| match value {
+                            Expr::Literal(value) => {
+                                match to_substrait_literal(value)?.rex_type {
+                                    Some(RexType::Literal(literal)) => Ok(literal),
+                                    _ => Err(DataFusionError::Internal(
+                                        "Expected a Substrait literal".to_string(),
+                                    )),
+                                }
+                            }
+                            _ => Err(DataFusionError::NotImplemented(format!(
+                                "Only literal values are supported in a VirtualTable, got {value}"
+                            ))),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(Struct { fields })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Box::new(Rel {
+                rel_type: Some(RelType::Read(Box::new(ReadRel {
+                    common: None,
+                    base_schema: Some(base_schema),
+                    filter: None,
+                    best_effort_filter: None,
+                    projection: None,
+                    advanced_extension: None,
+                    read_type: Some(ReadType::VirtualTable(VirtualTable { values })),
+                }))),
+            }))
+        }
+        LogicalPlan::EmptyRelation(empty) => {
+            // Use a VirtualTable with a single row without columns, or without
+            // any rows at all, to represent an EmptyRelation
+            let values = if empty.produce_one_row {
+                vec![Struct { fields: vec![] }]
+            } else {
+                vec![]
+            };
+            Ok(Box::new(Rel {
+                rel_type: Some(RelType::Read(Box::new(ReadRel {
+                    common: None,
+                    base_schema: Some(to_substrait_named_struct(
+                        &empty.schema.as_ref().into(),
+                    )?),
+                    filter: None,
+                    best_effort_filter: None,
+                    projection: None,
+                    advanced_extension: None,
+                    read_type: Some(ReadType::VirtualTable(VirtualTable { values })),
+                }))),
+            }))
+        }
+        LogicalPlan::Extension(extension) => {
+            let mut value = vec![];
+            extension_codec.try_encode(extension, &mut value)?;
+            let detail = ProtoAny {
+                type_url: extension.node.name().to_string(),
+                value,
+            };
+            let mut inputs = extension
+                .node
+                .inputs()
+                .into_iter()
+                .map(|input| to_substrait_rel(input, extension_info, extension_codec))
+                .collect::<Result<Vec<_>>>()?;
+            let rel_type = match inputs.len() {
+                0 => RelType::ExtensionLeaf(ExtensionLeafRel {
+                    common: None,
+                    detail: Some(detail),
+                }),
+                1 => RelType::ExtensionSingle(Box::new(ExtensionSingleRel {
+                    common: None,
+                    input: inputs.pop(),
+                    detail: Some(detail),
+                })),
+                _ => RelType::ExtensionMulti(ExtensionMultiRel {
+                    common: None,
+                    inputs: inputs.into_iter().map(|input| *input).collect(),
+                    detail: Some(detail),
+                }),
+            };
+            Ok(Box::new(Rel {
+                rel_type: Some(rel_type),
+            }))
+        }
         _ => Err(DataFusionError::NotImplemented(format!(
             "Unsupported operator: {plan:?}"
         ))),
     }
 }
 
+fn to_substrait_set_rel(
+    inputs: &[Arc<LogicalPlan>],
+    op: set_rel::SetOp,
+    extension_info: &mut (
+        Vec<extensions::SimpleExtensionDeclaration>,
+        HashMap<String, u32>,
+    ),
+    extension_codec: &dyn SubstraitExtensionCodec,
+) -> Result<Box<Rel>> {
+    let inputs = inputs
+        .iter()
+        .map(|input| {
+            to_substrait_rel(input.as_ref(), extension_info, extension_codec)
+                .map(|rel| *rel)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(Rel {
+        rel_type: Some(RelType::Set(SetRel {
+            common: None,
+            inputs,
+            op: op as i32,
+            advanced_extension: None,
+        })),
+    }))
+}
+
+pub(crate) fn to_substrait_named_struct(schema: &Schema) -> Result<NamedStruct> {
+    let types = schema
+        .fields()
+        .iter()
+        .map(|f| to_substrait_type(f.data_type()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(NamedStruct {
+        names: schema
+            .fields()
+            .iter()
+            .map(|f| f.name().to_owned())
+            .collect(),
+        r#struct: Some(r#type::Struct {
+            types,
+            type_variation_reference: DEFAULT_TYPE_REF,
+            nullability: r#type::Nullability::Required as i32,
+        }),
+    })
+}
+
 fn to_substrait_jointype(join_type: JoinType) -> join_rel::JoinType {
     match join_type {
         JoinType::Inner => join_rel::JoinType::Inner,
@@ -453,7 +666,7 @@ pub fn to_substrait_agg_measure(
     }
 }
 
-fn _register_function(
+pub(crate) fn _register_function(
     function_name: String,
     extension_info: &mut (
         Vec<extensions::SimpleExtensionDeclaration>,
@@ -524,6 +737,53 @@ pub fn make_binary_op_scalar_func(
     }
 }
 
+/// Return Substrait scalar function with the given arguments
+#[allow(deprecated)]
+fn make_scalar_func(
+    function_name: &str,
+    arguments: Vec<FunctionArgument>,
+    extension_info: &mut (
+        Vec<extensions::SimpleExtensionDeclaration>,
+        HashMap<String, u32>,
+    ),
+) -> Expression {
+    let function_anchor = _register_function(function_name.to_string(), extension_info);
+    Expression {
+        rex_type: Some(RexType::ScalarFunction(ScalarFunction {
+            function_reference: function_anchor,
+            arguments,
+            output_type: None,
+            args: vec![],
+            options: vec![],
+        })),
+    }
+}
+
+/// Return the name of the Substrait function a built-in scalar function is
+/// converted to
+fn scalar_function_name(fun: &BuiltinScalarFunction) -> Result<&'static str> {
+    match fun {
+        BuiltinScalarFunction::MakeArray => Ok("make_array"),
+        BuiltinScalarFunction::ArrayAppend => Ok("array_append"),
+        BuiltinScalarFunction::ArrayConcat => Ok("array_concat"),
+        BuiltinScalarFunction::ArrayContains => Ok("array_contains"),
+        BuiltinScalarFunction::ArrayDims => Ok("array_dims"),
+        BuiltinScalarFunction::ArrayLength => Ok("array_length"),
+        BuiltinScalarFunction::ArrayPosition => Ok("array_position"),
+        BuiltinScalarFunction::ArrayPositions => Ok("array_positions"),
+        BuiltinScalarFunction::ArrayPrepend => Ok("array_prepend"),
+        BuiltinScalarFunction::ArrayRemove => Ok("array_remove"),
+        BuiltinScalarFunction::ArrayReplace => Ok("array_replace"),
+        BuiltinScalarFunction::ArraySlice => Ok("array_slice"),
+        BuiltinScalarFunction::ArrayToString => Ok("array_to_string"),
+        BuiltinScalarFunction::Cardinality => Ok("cardinality"),
+        BuiltinScalarFunction::Flatten => Ok("flatten"),
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "Unsupported scalar function: {fun:?}"
+        ))),
+    }
+}
+
 /// Convert DataFusion Expr to Substrait Rex
 pub fn to_substrait_rex(
     expr: &Expr,
@@ -649,6 +909,20 @@ pub fn to_substrait_rex(
                 ))),
             })
         }
+        Expr::ScalarFunction(expr::ScalarFunction { fun, args }) => {
+            let function_name = scalar_function_name(fun)?;
+            let mut arguments: Vec<FunctionArgument> = vec![];
+            for arg in args {
+                arguments.push(FunctionArgument {
+                    arg_type: Some(ArgType::Value(to_substrait_rex(
+                        arg,
+                        schema,
+                        extension_info,
+                    )?)),
+                });
+            }
+            Ok(make_scalar_func(function_name, arguments, extension_info))
+        }
         Expr::Literal(value) => to_substrait_literal(value),
         Expr::Alias(expr, _alias) => to_substrait_rex(expr, schema, extension_info),
         Expr::WindowFunction(WindowFunction {
@@ -698,7 +972,7 @@ pub fn to_substrait_rex(
     }
 }
 
-fn to_substrait_type(dt: &DataType) -> Result<substrait::proto::Type> {
+pub(crate) fn to_substrait_type(dt: &DataType) -> Result<substrait::proto::Type> {
     let default_nullability = r#type::Nullability::Required as i32;
     match dt {
         DataType::Null => Err(DataFusionError::Internal(
@@ -1014,7 +1288,7 @@ fn to_substrait_bounds(window_frame: &WindowFrame) -> Result<(Bound, Bound)> {
     ))
 }
 
-fn to_substrait_literal(value: &ScalarValue) -> Result<Expression> {
+pub(crate) fn to_substrait_literal(value: &ScalarValue) -> Result<Expression> {
     let (literal_type, type_variation_reference) = match value {
         ScalarValue::Boolean(Some(b)) => (LiteralType::Boolean(*b), DEFAULT_TYPE_REF),
         ScalarValue::Int8(Some(n)) => (LiteralType::I8(*n as i32), DEFAULT_TYPE_REF),
@@ -17,27 +17,64 @@
 
 use async_recursion::async_recursion;
 use chrono::DateTime;
-use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::compute::SortOptions;
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
 use datafusion::datasource::listing::PartitionedFile;
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::AggregateFunction;
+use datafusion::physical_plan::aggregates::{
+    AggregateExec, AggregateMode, PhysicalGroupBy,
+};
+use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::expressions::{
+    binary, cast, create_aggregate_expr, Column, Literal, PhysicalSortExpr,
+};
 use datafusion::physical_plan::file_format::{FileScanConfig, ParquetExec};
-use datafusion::physical_plan::ExecutionPlan;
-use datafusion::prelude::SessionContext;
+use datafusion::physical_plan::filter::FilterExec;
+use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode, SortMergeJoinExec};
+use datafusion::physical_plan::limit::GlobalLimitExec;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::sorts::sort::SortExec;
+use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use datafusion::physical_plan::{
+    AggregateExpr, ExecutionPlan, Partitioning, PhysicalExpr,
+};
+use datafusion::prelude::{JoinType, SessionContext};
 use object_store::ObjectMeta;
+use prost::Message;
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
+use substrait::proto::aggregate_function::AggregationInvocation;
+use substrait::proto::aggregate_rel::Measure;
+use substrait::proto::exchange_rel::ExchangeKind;
+use substrait::proto::expression::field_reference::ReferenceType::DirectReference;
+use substrait::proto::expression::reference_segment::ReferenceType::StructField;
+use substrait::proto::expression::{FieldReference, RexType};
+use substrait::proto::extensions::AdvancedExtension;
+use substrait::proto::function_argument::ArgType;
 use substrait::proto::read_rel::local_files::file_or_files::PathType;
+use substrait::proto::sort_field::{SortDirection, SortKind};
 use substrait::proto::{
-    expression::MaskExpression, read_rel::ReadType, rel::RelType, Rel,
+    expression::MaskExpression, hash_join_rel, merge_join_rel, read_rel::ReadType,
+    rel::RelType, AggregateFunction as SubstraitAggregateFunction, AggregationPhase,
+    Expression, FunctionArgument, NamedStruct, Rel, SortField,
 };
 
+use crate::logical_plan::consumer::{
+    from_substrait_literal, from_substrait_named_struct, from_substrait_type, name_to_op,
+};
+use crate::physical_plan::{OUTPUT_NAMES_TYPE_URL, PARTITION_MODE_TYPE_URL};
+
 /// Convert Substrait Rel to DataFusion ExecutionPlan
 #[async_recursion]
 pub async fn from_substrait_rel(
-    _ctx: &mut SessionContext,
+    ctx: &mut SessionContext,
     rel: &Rel,
-    _extensions: &HashMap<u32, &String>,
+    extensions: &HashMap<u32, &String>,
 ) -> Result<Arc<dyn ExecutionPlan>> {
     match &rel.rel_type {
         Some(RelType::Read(read)) => {
@@ -46,11 +83,6 @@ pub async fn from_substrait_rel(
                     "Read with filter is not supported".to_string(),
                 ));
             }
-            if read.base_schema.is_some() {
-                return Err(DataFusionError::NotImplemented(
-                    "Read with schema is not supported".to_string(),
-                ));
-            }
             if read.advanced_extension.is_some() {
                 return Err(DataFusionError::NotImplemented(
                     "Read with AdvancedExtension is not supported".to_string(),
@@ -74,8 +106,8 @@ pub async fn from_substrait_rel(
                             ))
                         }?;
 
-                        // TODO substrait plans do not have `last_modified` or `size` but `ObjectMeta`
-                        // requires them both - perhaps we can change the object-store crate
+                        // TODO substrait plans do not have `last_modified` but `ObjectMeta`
+                        // requires it - perhaps we can change the object-store crate
                         // to make these optional? We cannot guarantee that we have access to the
                         // files to get this information, depending on how this library is being
                         // used
@@ -84,7 +116,8 @@ pub async fn from_substrait_rel(
                             "%Y %b %d %H:%M:%S%.3f %z",
                         )
                         .unwrap();
-                        let size = 0;
+                        // the producer reads whole files, whose length is the file size
+                        let size = file.length as usize;
 
                         let partitioned_file = PartitionedFile {
                             object_meta: ObjectMeta {
@@ -104,9 +137,16 @@ pub async fn from_substrait_rel(
                         file_groups[part_index].push(partitioned_file)
                     }
 
+                    let file_schema = match &read.base_schema {
+                        Some(base_schema) => {
+                            Arc::new(from_substrait_named_struct(base_schema)?)
+                        }
+                        None => Arc::new(Schema::empty()),
+                    };
+
                     let mut base_config = FileScanConfig {
                         object_store_url: ObjectStoreUrl::local_filesystem(),
-                        file_schema: Arc::new(Schema::empty()),
+                        file_schema,
                         file_groups,
                         statistics: Default::default(),
                         projection: None,
@@ -136,9 +176,647 @@ pub async fn from_substrait_rel(
                 )),
             }
         }
+        Some(RelType::Project(p)) => {
+            let input =
+                from_substrait_input(ctx, p.input.as_deref(), extensions, "Projection")
+                    .await?;
+            let input_schema = input.schema();
+            let names = from_substrait_output_names(
+                p.advanced_extension.as_ref(),
+                p.expressions.len(),
+            );
+            let exprs = p
+                .expressions
+                .iter()
+                .enumerate()
+                .map(|(i, e)| {
+                    let expr = from_substrait_rex(e, &input_schema, extensions)?;
+                    let name = match &names {
+                        Some(names) => names[i].clone(),
+                        None => physical_expr_name(&expr),
+                    };
+                    Ok((expr, name))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Arc::new(ProjectionExec::try_new(exprs, input)?))
+        }
+        Some(RelType::Filter(filter)) => {
+            let input =
+                from_substrait_input(ctx, filter.input.as_deref(), extensions, "Filter")
+                    .await?;
+            match filter.condition.as_ref() {
+                Some(condition) => {
+                    let predicate =
+                        from_substrait_rex(condition, &input.schema(), extensions)?;
+                    Ok(Arc::new(FilterExec::try_new(predicate, input)?))
+                }
+                None => Err(DataFusionError::NotImplemented(
+                    "Filter without an condition is not valid".to_string(),
+                )),
+            }
+        }
+        Some(RelType::Fetch(fetch)) => {
+            let input =
+                from_substrait_input(ctx, fetch.input.as_deref(), extensions, "Fetch")
+                    .await?;
+            let offset = fetch.offset as usize;
+            let count = if fetch.count < 0 {
+                None
+            } else {
+                Some(fetch.count as usize)
+            };
+            // A fetch directly on top of a sort is the sort's own limit
+            if let (0, Some(sort)) = (offset, input.as_any().downcast_ref::<SortExec>()) {
+                if sort.fetch().is_none() {
+                    return Ok(Arc::new(
+                        SortExec::new(sort.expr().to_vec(), sort.input().clone())
+                            .with_fetch(count),
+                    ));
+                }
+            }
+            // A sort of multiple partitions merges per-partition sorts, each of
+            // which only needs to keep the rows up to the end of the fetch
+            if let (Some(count), Some(merge)) = (
+                count,
+                input.as_any().downcast_ref::<SortPreservingMergeExec>(),
+            ) {
+                if let Some(sort) = merge.input().as_any().downcast_ref::<SortExec>() {
+                    if sort.preserve_partitioning() && sort.fetch().is_none() {
+                        let sort =
+                            SortExec::new(sort.expr().to_vec(), sort.input().clone())
+                                .with_preserve_partitioning(true)
+                                .with_fetch(Some(offset + count));
+                        let merge = SortPreservingMergeExec::new(
+                            merge.expr().to_vec(),
+                            Arc::new(sort),
+                        );
+                        return Ok(Arc::new(GlobalLimitExec::new(
+                            Arc::new(merge),
+                            offset,
+                            Some(count),
+                        )));
+                    }
+                }
+            }
+            Ok(Arc::new(GlobalLimitExec::new(input, offset, count)))
+        }
+        Some(RelType::Sort(sort)) => {
+            let input =
+                from_substrait_input(ctx, sort.input.as_deref(), extensions, "Sort")
+                    .await?;
+            let input_schema = input.schema();
+            let sorts = sort
+                .sorts
+                .iter()
+                .map(|s| from_substrait_sort_field(s, &input_schema, extensions))
+                .collect::<Result<Vec<_>>>()?;
+            // A sort produces a single partition: multiple partitions are sorted
+            // separately and then merged
+            if input.output_partitioning().partition_count() > 1 {
+                let sort =
+                    SortExec::new(sorts.clone(), input).with_preserve_partitioning(true);
+                Ok(Arc::new(SortPreservingMergeExec::new(
+                    sorts,
+                    Arc::new(sort),
+                )))
+            } else {
+                Ok(Arc::new(SortExec::new(sorts, input)))
+            }
+        }
+        Some(RelType::Exchange(exchange)) => {
+            let input = from_substrait_input(
+                ctx,
+                exchange.input.as_deref(),
+                extensions,
+                "Exchange",
+            )
+            .await?;
+            let partition_count = exchange.partition_count as usize;
+            match &exchange.exchange_kind {
+                Some(ExchangeKind::RoundRobin(_)) if partition_count == 1 => {
+                    Ok(Arc::new(CoalescePartitionsExec::new(input)))
+                }
+                Some(ExchangeKind::RoundRobin(_)) => {
+                    Ok(Arc::new(RepartitionExec::try_new(
+                        input,
+                        Partitioning::RoundRobinBatch(partition_count),
+                    )?))
+                }
+                Some(ExchangeKind::ScatterByFields(scatter)) => {
+                    let input_schema = input.schema();
+                    let exprs = scatter
+                        .fields
+                        .iter()
+                        .map(|f| {
+                            let index = from_substrait_field_reference(f)?;
+                            Ok(Arc::new(column_at(&input_schema, index)?)
+                                as Arc<dyn PhysicalExpr>)
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(Arc::new(RepartitionExec::try_new(
+                        input,
+                        Partitioning::Hash(exprs, partition_count),
+                    )?))
+                }
+                _ => Err(DataFusionError::NotImplemented(format!(
+                    "Unsupported exchange kind: {:?}",
+                    exchange.exchange_kind
+                ))),
+            }
+        }
+        Some(RelType::HashJoin(join)) => {
+            if join.post_join_filter.is_some() {
+                return Err(DataFusionError::NotImplemented("join filter".to_string()));
+            }
+            let left =
+                from_substrait_input(ctx, join.left.as_deref(), extensions, "HashJoin")
+                    .await?;
+            let right =
+                from_substrait_input(ctx, join.right.as_deref(), extensions, "HashJoin")
+                    .await?;
+            let on = from_substrait_join_keys(
+                &join.left_keys,
+                &join.right_keys,
+                &left.schema(),
+                &right.schema(),
+            )?;
+            let join_type = from_substrait_hash_jointype(join.r#type)?;
+            let partition_mode =
+                match from_substrait_partition_mode(join.advanced_extension.as_ref())? {
+                    Some(partition_mode) => partition_mode,
+                    // Without a hint from the producer, both inputs must be hash
+                    // partitioned for the join to run partitioned
+                    None => {
+                        match (left.output_partitioning(), right.output_partitioning()) {
+                            (Partitioning::Hash(_, l), Partitioning::Hash(_, r))
+                                if l == r =>
+                            {
+                                PartitionMode::Partitioned
+                            }
+                            _ => PartitionMode::CollectLeft,
+                        }
+                    }
+                };
+            Ok(Arc::new(HashJoinExec::try_new(
+                left,
+                right,
+                on,
+                None,
+                &join_type,
+                partition_mode,
+                false,
+            )?))
+        }
+        Some(RelType::MergeJoin(join)) => {
+            if join.post_join_filter.is_some() {
+                return Err(DataFusionError::NotImplemented("join filter".to_string()));
+            }
+            let left =
+                from_substrait_input(ctx, join.left.as_deref(), extensions, "MergeJoin")
+                    .await?;
+            let right =
+                from_substrait_input(ctx, join.right.as_deref(), extensions, "MergeJoin")
+                    .await?;
+            let on = from_substrait_join_keys(
+                &join.left_keys,
+                &join.right_keys,
+                &left.schema(),
+                &right.schema(),
+            )?;
+            let join_type = from_substrait_merge_jointype(join.r#type)?;
+            let sort_options = vec![SortOptions::default(); on.len()];
+            Ok(Arc::new(SortMergeJoinExec::try_new(
+                left,
+                right,
+                on,
+                join_type,
+                sort_options,
+                false,
+            )?))
+        }
+        Some(RelType::Aggregate(agg)) => {
+            let input =
+                from_substrait_input(ctx, agg.input.as_deref(), extensions, "Aggregate")
+                    .await?;
+            let grouping = match agg.groupings.as_slice() {
+                [grouping] => grouping,
+                _ => {
+                    return Err(DataFusionError::NotImplemented(
+                        "Aggregate with multiple grouping sets is not supported"
+                            .to_string(),
+                    ))
+                }
+            };
+            let mode = from_substrait_aggregate_mode(&agg.measures, &input)?;
+            // Final aggregates evaluate their arguments against the input of the
+            // partial aggregate rather than against their own input
+            let aggregate_input_schema = match mode {
+                AggregateMode::Final | AggregateMode::FinalPartitioned => {
+                    partial_aggregate_input_schema(&input).ok_or_else(|| {
+                        DataFusionError::Substrait(
+                            "Final aggregate without a partial aggregate input is not valid"
+                                .to_string(),
+                        )
+                    })?
+                }
+                AggregateMode::Partial | AggregateMode::Single => input.schema(),
+            };
+
+            let input_schema = input.schema();
+            let group_count = grouping.grouping_expressions.len();
+            let names = from_substrait_output_names(
+                agg.advanced_extension.as_ref(),
+                group_count + agg.measures.len(),
+            );
+            let group_expr = grouping
+                .grouping_expressions
+                .iter()
+                .enumerate()
+                .map(|(i, e)| {
+                    let expr = from_substrait_rex(e, &input_schema, extensions)?;
+                    let name = match &names {
+                        Some(names) => names[i].clone(),
+                        None => physical_expr_name(&expr),
+                    };
+                    Ok((expr, name))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut aggr_expr = vec![];
+            let mut filter_expr = vec![];
+            for (i, m) in agg.measures.iter().enumerate() {
+                let f = m.measure.as_ref().ok_or_else(|| {
+                    DataFusionError::NotImplemented(
+                        "Aggregate without aggregate function is not supported"
+                            .to_string(),
+                    )
+                })?;
+                aggr_expr.push(from_substrait_agg_func(
+                    f,
+                    &aggregate_input_schema,
+                    extensions,
+                    names.as_ref().map(|names| names[group_count + i].clone()),
+                )?);
+                filter_expr.push(match &m.filter {
+                    Some(filter) => Some(from_substrait_rex(
+                        filter,
+                        &aggregate_input_schema,
+                        extensions,
+                    )?),
+                    None => None,
+                });
+            }
+
+            Ok(Arc::new(AggregateExec::try_new(
+                mode,
+                PhysicalGroupBy::new_single(group_expr),
+                aggr_expr,
+                filter_expr,
+                input,
+                aggregate_input_schema,
+            )?))
+        }
         _ => Err(DataFusionError::NotImplemented(format!(
             "Unsupported RelType: {:?}",
             rel.rel_type
         ))),
     }
 }
+
+async fn from_substrait_input(
+    ctx: &mut SessionContext,
+    input: Option<&Rel>,
+    extensions: &HashMap<u32, &String>,
+    rel_name: &str,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    match input {
+        Some(input) => from_substrait_rel(ctx, input, extensions).await,
+        None => Err(DataFusionError::NotImplemented(format!(
+            "{rel_name} without an input is not valid"
+        ))),
+    }
+}
+
+/// Convert Substrait Rex to DataFusion PhysicalExpr
+pub fn from_substrait_rex(
+    e: &Expression,
+    input_schema: &Schema,
+    extensions: &HashMap<u32, &String>,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    match &e.rex_type {
+        Some(RexType::Selection(field_ref)) => {
+            let index = from_substrait_field_reference(field_ref)?;
+            Ok(Arc::new(column_at(input_schema, index)?))
+        }
+        Some(RexType::Literal(lit)) => {
+            Ok(Arc::new(Literal::new(from_substrait_literal(lit)?)))
+        }
+        Some(RexType::ScalarFunction(f)) => {
+            let op = match extensions.get(&f.function_reference) {
+                Some(fname) => name_to_op(fname),
+                None => Err(DataFusionError::NotImplemented(format!(
+                    "Scalar function not found: function reference = {:?}",
+                    f.function_reference
+                ))),
+            }?;
+            match f.arguments.as_slice() {
+                [FunctionArgument {
+                    arg_type: Some(ArgType::Value(l)),
+                }, FunctionArgument {
+                    arg_type: Some(ArgType::Value(r)),
+                }] => binary(
+                    from_substrait_rex(l, input_schema, extensions)?,
+                    op,
+                    from_substrait_rex(r, input_schema, extensions)?,
+                    input_schema,
+                ),
+                _ => Err(DataFusionError::NotImplemented(format!(
+                    "Operator {op} is only supported with two value arguments"
+                ))),
+            }
+        }
+        Some(RexType::Cast(c)) => match (c.r#type.as_ref(), c.input.as_ref()) {
+            (Some(output_type), Some(input)) => cast(
+                from_substrait_rex(input, input_schema, extensions)?,
+                input_schema,
+                from_substrait_type(output_type)?,
+            ),
+            _ => Err(DataFusionError::Substrait(
+                "Cast expression without an input or output type is not valid"
+                    .to_string(),
+            )),
+        },
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "Unsupported expression type in physical plan: {:?}",
+            e.rex_type
+        ))),
+    }
+}
+
+fn from_substrait_agg_func(
+    f: &SubstraitAggregateFunction,
+    input_schema: &Schema,
+    extensions: &HashMap<u32, &String>,
+    name: Option<String>,
+) -> Result<Arc<dyn AggregateExpr>> {
+    let args = f
+        .arguments
+        .iter()
+        .map(|arg| match &arg.arg_type {
+            Some(ArgType::Value(e)) => from_substrait_rex(e, input_schema, extensions),
+            _ => Err(DataFusionError::NotImplemented(
+                "Aggregated function argument non-Value type not supported".to_string(),
+            )),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let fun = match extensions.get(&f.function_reference) {
+        Some(function_name) => AggregateFunction::from_str(function_name),
+        None => Err(DataFusionError::NotImplemented(format!(
+            "Aggregated function not found: function anchor = {:?}",
+            f.function_reference
+        ))),
+    }?;
+    let distinct = f.invocation == AggregationInvocation::Distinct as i32;
+    let name = name.unwrap_or_else(|| {
+        format!(
+            "{fun}({}{})",
+            if distinct { "DISTINCT " } else { "" },
+            args.iter()
+                .map(physical_expr_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    });
+    create_aggregate_expr(&fun, distinct, &args, input_schema, name)
+}
+
+/// Substrait measures carry the aggregation phase, which maps to the DataFusion
+/// aggregate mode. Aggregates without measures are final when they consume a
+/// partial aggregate.
+fn from_substrait_aggregate_mode(
+    measures: &[Measure],
+    input: &Arc<dyn ExecutionPlan>,
+) -> Result<AggregateMode> {
+    let mut phases = measures
+        .iter()
+        .filter_map(|m| m.measure.as_ref())
+        .map(|f| f.phase);
+    let phase = match phases.next() {
+        Some(phase) if phases.all(|p| p == phase) => AggregationPhase::from_i32(phase),
+        Some(_) => {
+            return Err(DataFusionError::NotImplemented(
+                "Aggregate with measures in different phases is not supported"
+                    .to_string(),
+            ))
+        }
+        None => None,
+    };
+    let is_final = match phase {
+        Some(AggregationPhase::InitialToIntermediate) => {
+            return Ok(AggregateMode::Partial)
+        }
+        Some(AggregationPhase::IntermediateToIntermediate) => {
+            return Err(DataFusionError::NotImplemented(
+                "Intermediate to intermediate aggregation is not supported".to_string(),
+            ))
+        }
+        Some(AggregationPhase::InitialToResult) => false,
+        Some(AggregationPhase::IntermediateToResult) => true,
+        Some(AggregationPhase::Unspecified) | None => {
+            partial_aggregate_input_schema(input).is_some()
+        }
+    };
+    Ok(match (is_final, input.output_partitioning()) {
+        (false, _) => AggregateMode::Single,
+        (true, Partitioning::Hash(_, _)) => AggregateMode::FinalPartitioned,
+        (true, _) => AggregateMode::Final,
+    })
+}
+
+/// Returns the input schema of the partial aggregate feeding `plan`, looking
+/// through the exchanges placed between partial and final aggregates
+fn partial_aggregate_input_schema(plan: &Arc<dyn ExecutionPlan>) -> Option<SchemaRef> {
+    if let Some(agg) = plan.as_any().downcast_ref::<AggregateExec>() {
+        return match agg.mode() {
+            AggregateMode::Partial => Some(agg.input_schema()),
+            _ => None,
+        };
+    }
+    if plan.as_any().is::<RepartitionExec>()
+        || plan.as_any().is::<CoalescePartitionsExec>()
+        || plan.as_any().is::<CoalesceBatchesExec>()
+    {
+        return plan
+            .children()
+            .first()
+            .and_then(partial_aggregate_input_schema);
+    }
+    None
+}
+
+fn from_substrait_sort_field(
+    s: &SortField,
+    input_schema: &Schema,
+    extensions: &HashMap<u32, &String>,
+) -> Result<PhysicalSortExpr> {
+    let expr = s.expr.as_ref().ok_or_else(|| {
+        DataFusionError::Substrait(
+            "Sort field without an expression is not valid".to_string(),
+        )
+    })?;
+    let (descending, nulls_first) = match &s.sort_kind {
+        Some(SortKind::Direction(d)) => match SortDirection::from_i32(*d) {
+            Some(SortDirection::AscNullsFirst) => (false, true),
+            Some(SortDirection::AscNullsLast) => (false, false),
+            Some(SortDirection::DescNullsFirst) => (true, true),
+            Some(SortDirection::DescNullsLast) => (true, false),
+            _ => {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "Unsupported Substrait SortDirection value {d}"
+                )))
+            }
+        },
+        _ => {
+            return Err(DataFusionError::NotImplemented(
+                "Sort without a sort direction is not supported".to_string(),
+            ))
+        }
+    };
+    Ok(PhysicalSortExpr {
+        expr: from_substrait_rex(expr, input_schema, extensions)?,
+        options: SortOptions {
+            descending,
+            nulls_first,
+        },
+    })
+}
+
+fn from_substrait_join_keys(
+    left_keys: &[FieldReference],
+    right_keys: &[FieldReference],
+    left_schema: &Schema,
+    right_schema: &Schema,
+) -> Result<Vec<(Column, Column)>> {
+    if left_keys.len() != right_keys.len() {
+        return Err(DataFusionError::Substrait(
+            "Join with a different number of left and right keys is not valid"
+                .to_string(),
+        ));
+    }
+    left_keys
+        .iter()
+        .zip(right_keys)
+        .map(|(l, r)| {
+            Ok((
+                column_at(left_schema, from_substrait_field_reference(l)?)?,
+                column_at(right_schema, from_substrait_field_reference(r)?)?,
+            ))
+        })
+        .collect()
+}
+
+fn from_substrait_hash_jointype(join_type: i32) -> Result<JoinType> {
+    match hash_join_rel::JoinType::from_i32(join_type) {
+        Some(hash_join_rel::JoinType::Inner) => Ok(JoinType::Inner),
+        Some(hash_join_rel::JoinType::Left) => Ok(JoinType::Left),
+        Some(hash_join_rel::JoinType::Right) => Ok(JoinType::Right),
+        Some(hash_join_rel::JoinType::Outer) => Ok(JoinType::Full),
+        Some(hash_join_rel::JoinType::LeftSemi) => Ok(JoinType::LeftSemi),
+        Some(hash_join_rel::JoinType::RightSemi) => Ok(JoinType::RightSemi),
+        Some(hash_join_rel::JoinType::LeftAnti) => Ok(JoinType::LeftAnti),
+        Some(hash_join_rel::JoinType::RightAnti) => Ok(JoinType::RightAnti),
+        _ => Err(DataFusionError::Internal(format!(
+            "invalid join type variant {join_type:?}"
+        ))),
+    }
+}
+
+fn from_substrait_merge_jointype(join_type: i32) -> Result<JoinType> {
+    match merge_join_rel::JoinType::from_i32(join_type) {
+        Some(merge_join_rel::JoinType::Inner) => Ok(JoinType::Inner),
+        Some(merge_join_rel::JoinType::Left) => Ok(JoinType::Left),
+        Some(merge_join_rel::JoinType::Right) => Ok(JoinType::Right),
+        Some(merge_join_rel::JoinType::Outer) => Ok(JoinType::Full),
+        Some(merge_join_rel::JoinType::LeftSemi) => Ok(JoinType::LeftSemi),
+        Some(merge_join_rel::JoinType::RightSemi) => Ok(JoinType::RightSemi),
+        Some(merge_join_rel::JoinType::LeftAnti) => Ok(JoinType::LeftAnti),
+        Some(merge_join_rel::JoinType::RightAnti) => Ok(JoinType::RightAnti),
+        _ => Err(DataFusionError::Internal(format!(
+            "invalid join type variant {join_type:?}"
+        ))),
+    }
+}
+
+fn from_substrait_field_reference(field_ref: &FieldReference) -> Result<usize> {
+    match &field_ref.reference_type {
+        Some(DirectReference(direct)) => match &direct.reference_type {
+            Some(StructField(x)) => match &x.child {
+                Some(_) => Err(DataFusionError::NotImplemented(
+                    "Direct reference StructField with child is not supported"
+                        .to_string(),
+                )),
+                None => Ok(x.field as usize),
+            },
+            _ => Err(DataFusionError::NotImplemented(
+                "Direct reference with types other than StructField is not supported"
+                    .to_string(),
+            )),
+        },
+        _ => Err(DataFusionError::NotImplemented(
+            "unsupported field ref type".to_string(),
+        )),
+    }
+}
+
+fn column_at(schema: &Schema, index: usize) -> Result<Column> {
+    if index >= schema.fields().len() {
+        return Err(DataFusionError::Substrait(format!(
+            "Field reference {index} is out of bounds for a schema with {} fields",
+            schema.fields().len()
+        )));
+    }
+    Ok(Column::new(schema.field(index).name(), index))
+}
+
+/// Returns the `count` output names recorded by the producer, if any
+fn from_substrait_output_names(
+    extension: Option<&AdvancedExtension>,
+    count: usize,
+) -> Option<Vec<String>> {
+    let hint = extension?.optimization.as_ref()?;
+    if hint.type_url != OUTPUT_NAMES_TYPE_URL {
+        return None;
+    }
+    let names = NamedStruct::decode(hint.value.as_slice()).ok()?.names;
+    (names.len() == count).then_some(names)
+}
+
+/// Returns the partition mode of a hash join recorded by the producer, if any
+fn from_substrait_partition_mode(
+    extension: Option<&AdvancedExtension>,
+) -> Result<Option<PartitionMode>> {
+    let hint = match extension.and_then(|e| e.optimization.as_ref()) {
+        Some(hint) if hint.type_url == PARTITION_MODE_TYPE_URL => hint,
+        _ => return Ok(None),
+    };
+    match hint.value.as_slice() {
+        b"Partitioned" => Ok(Some(PartitionMode::Partitioned)),
+        b"CollectLeft" => Ok(Some(PartitionMode::CollectLeft)),
+        b"Auto" => Ok(Some(PartitionMode::Auto)),
+        value => Err(DataFusionError::Substrait(format!(
+            "invalid partition mode {}",
+            String::from_utf8_lossy(value)
+        ))),
+    }
+}
+
+/// Substrait does not name the outputs of intermediate relations, so without
+/// names recorded by the producer, projected, grouped and aggregated
+/// expressions are named after the column they reference or after their
+/// display form otherwise
+fn physical_expr_name(expr: &Arc<dyn PhysicalExpr>) -> String {
+    match expr.as_any().downcast_ref::<Column>() {
+        Some(col) => col.name().to_string(),
+        None => expr.to_string(),
+    }
+}
@@ -17,3 +17,12 @@
 
 pub mod consumer;
 pub mod producer;
+
+/// Type URL of the optimization hint carrying the output names of a relation.
+/// Substrait does not name the outputs of intermediate relations, so the
+/// producer records the names of projected, grouped and aggregated
+/// expressions for DataFusion consumers
+pub(crate) const OUTPUT_NAMES_TYPE_URL: &str = "OutputNames";
+
+/// Type URL of the optimization hint carrying the `PartitionMode` of a hash join
+pub(crate) const PARTITION_MODE_TYPE_URL: &str = "PartitionMode";
@@ -15,25 +15,67 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use datafusion::arrow::compute::SortOptions;
 use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::AggregateFunction;
+use datafusion::physical_plan::aggregates::{AggregateExec, AggregateMode};
+use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::expressions::{
+    Avg, BinaryExpr, CastExpr, Column, Count, DistinctCount, DistinctSum, Literal, Max,
+    Min, PhysicalSortExpr, Sum,
+};
 use datafusion::physical_plan::file_format::ParquetExec;
-use datafusion::physical_plan::{displayable, ExecutionPlan};
-use std::collections::HashMap;
-use substrait::proto::expression::MaskExpression;
-use substrait::proto::extensions;
+use datafusion::physical_plan::filter::FilterExec;
+use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode, SortMergeJoinExec};
+use datafusion::physical_plan::limit::GlobalLimitExec;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::sorts::sort::SortExec;
+use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use datafusion::physical_plan::{
+    displayable, AggregateExpr, ExecutionPlan, Partitioning, PhysicalExpr,
+};
+use datafusion::prelude::JoinType;
+use prost::Message;
+use prost_types::Any as ProtoAny;
+use substrait::proto::aggregate_function::AggregationInvocation;
+use substrait::proto::aggregate_rel::{Grouping, Measure};
+use substrait::proto::exchange_rel::{ExchangeKind, RoundRobin, ScatterFields};
+use substrait::proto::expression::field_reference::ReferenceType;
+use substrait::proto::expression::mask_expression::{StructItem, StructSelect};
+use substrait::proto::expression::{
+    reference_segment, FieldReference, MaskExpression, ReferenceSegment, RexType,
+};
+use substrait::proto::extensions::{self, AdvancedExtension};
+use substrait::proto::function_argument::ArgType;
 use substrait::proto::read_rel::local_files::file_or_files::ParquetReadOptions;
 use substrait::proto::read_rel::local_files::file_or_files::{FileFormat, PathType};
 use substrait::proto::read_rel::local_files::FileOrFiles;
 use substrait::proto::read_rel::LocalFiles;
 use substrait::proto::read_rel::ReadType;
 use substrait::proto::rel::RelType;
-use substrait::proto::ReadRel;
-use substrait::proto::Rel;
+use substrait::proto::sort_field::{SortDirection, SortKind};
+use substrait::proto::{
+    hash_join_rel, merge_join_rel, AggregateFunction as SubstraitAggregateFunction,
+    AggregateRel, AggregationPhase, ExchangeRel, Expression, FetchRel, FilterRel,
+    FunctionArgument, HashJoinRel, MergeJoinRel, NamedStruct, ProjectRel, ReadRel, Rel,
+    SortField, SortRel,
+};
+
+use crate::logical_plan::producer::{
+    _register_function, make_binary_op_scalar_func, to_substrait_literal,
+    to_substrait_named_struct, to_substrait_type,
+};
+use crate::physical_plan::{OUTPUT_NAMES_TYPE_URL, PARTITION_MODE_TYPE_URL};
 
 /// Convert DataFusion ExecutionPlan to Substrait Rel
 pub fn to_substrait_rel(
     plan: &dyn ExecutionPlan,
-    _extension_info: &mut (
+    extension_info: &mut (
         Vec<extensions::SimpleExtensionDeclaration>,
         HashMap<String, u32>,
     ),
@@ -55,16 +97,26 @@ pub fn to_substrait_rel(
             }
         }
 
+        let projection = base_config.projection.as_ref().map(|p| MaskExpression {
+            select: Some(StructSelect {
+                struct_items: p
+                    .iter()
+                    .map(|i| StructItem {
+                        field: *i as i32,
+                        child: None,
+                    })
+                    .collect(),
+            }),
+            maintain_singular_struct: false,
+        });
+
         Ok(Box::new(Rel {
             rel_type: Some(RelType::Read(Box::new(ReadRel {
                 common: None,
-                base_schema: None,
+                base_schema: Some(to_substrait_named_struct(&base_config.file_schema)?),
                 filter: None,
                 best_effort_filter: None,
-                projection: Some(MaskExpression {
-                    select: None,
-                    maintain_singular_struct: false,
-                }),
+                projection,
                 advanced_extension: None,
                 read_type: Some(ReadType::LocalFiles(LocalFiles {
                     items: substrait_files,
@@ -72,6 +124,220 @@ pub fn to_substrait_rel(
                 })),
             }))),
         }))
+    } else if let Some(projection) = plan.as_any().downcast_ref::<ProjectionExec>() {
+        let input = to_substrait_rel(projection.input().as_ref(), extension_info)?;
+        let expressions = projection
+            .expr()
+            .iter()
+            .map(|(e, _)| to_substrait_rex(e, extension_info))
+            .collect::<Result<Vec<_>>>()?;
+        let names = projection.expr().iter().map(|(_, name)| name.clone());
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::Project(Box::new(ProjectRel {
+                common: None,
+                input: Some(input),
+                expressions,
+                advanced_extension: Some(to_substrait_output_names(names)),
+            }))),
+        }))
+    } else if let Some(filter) = plan.as_any().downcast_ref::<FilterExec>() {
+        let input = to_substrait_rel(filter.input().as_ref(), extension_info)?;
+        let condition = to_substrait_rex(filter.predicate(), extension_info)?;
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::Filter(Box::new(FilterRel {
+                common: None,
+                input: Some(input),
+                condition: Some(Box::new(condition)),
+                advanced_extension: None,
+            }))),
+        }))
+    } else if let Some(limit) = plan.as_any().downcast_ref::<GlobalLimitExec>() {
+        // A limit over the merge of per-partition top-k sorts is a limit over a
+        // single sort of the whole input, as long as each partition keeps
+        // enough rows
+        if let (Some(fetch), Some(sort)) =
+            (limit.fetch(), merged_partition_sort(limit.input().as_ref()))
+        {
+            if matches!(sort.fetch(), Some(k) if k >= limit.skip() + fetch) {
+                let input = to_substrait_sort_rel(
+                    sort.input(),
+                    sort.expr(),
+                    None,
+                    extension_info,
+                )?;
+                return Ok(to_substrait_fetch_rel(input, limit.skip(), Some(fetch)));
+            }
+        }
+        let input = to_substrait_rel(limit.input().as_ref(), extension_info)?;
+        Ok(to_substrait_fetch_rel(input, limit.skip(), limit.fetch()))
+    } else if let Some(sort) = plan.as_any().downcast_ref::<SortExec>() {
+        if sort.preserve_partitioning() {
+            // A Substrait SortRel sorts its whole input, sorting each
+            // partition separately can only be expressed along with the merge
+            return Err(DataFusionError::NotImplemented(
+                "Converting a SortExec that sorts each partition separately to Substrait is only supported below a SortPreservingMergeExec on the same sort expressions"
+                    .to_string(),
+            ));
+        }
+        to_substrait_sort_rel(sort.input(), sort.expr(), sort.fetch(), extension_info)
+    } else if let Some(merge) = plan.as_any().downcast_ref::<SortPreservingMergeExec>() {
+        // A per-partition sort followed by a merge of the sorted partitions is a
+        // single sort of the whole input
+        match merged_partition_sort(plan) {
+            Some(sort) if sort.fetch().is_some() => Err(DataFusionError::NotImplemented(
+                "Merging per-partition sorts with a fetch is only supported below a GlobalLimitExec"
+                    .to_string(),
+            )),
+            Some(sort) => {
+                to_substrait_sort_rel(sort.input(), sort.expr(), None, extension_info)
+            }
+            None => {
+                to_substrait_sort_rel(merge.input(), merge.expr(), None, extension_info)
+            }
+        }
+    } else if let Some(coalesce) = plan.as_any().downcast_ref::<CoalescePartitionsExec>()
+    {
+        let input = to_substrait_rel(coalesce.input().as_ref(), extension_info)?;
+        Ok(to_substrait_exchange_rel(
+            input,
+            1,
+            ExchangeKind::RoundRobin(RoundRobin { exact: false }),
+        ))
+    } else if let Some(repartition) = plan.as_any().downcast_ref::<RepartitionExec>() {
+        let input = to_substrait_rel(repartition.input().as_ref(), extension_info)?;
+        match repartition.partitioning() {
+            Partitioning::RoundRobinBatch(partition_count) => {
+                Ok(to_substrait_exchange_rel(
+                    input,
+                    *partition_count,
+                    ExchangeKind::RoundRobin(RoundRobin { exact: false }),
+                ))
+            }
+            Partitioning::Hash(exprs, partition_count) => {
+                let fields = exprs
+                    .iter()
+                    .map(|e| match e.as_any().downcast_ref::<Column>() {
+                        Some(col) => Ok(substrait_field_reference(col.index())),
+                        None => Err(DataFusionError::NotImplemented(format!(
+                            "Hash repartitioning on expression {e} is not supported"
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(to_substrait_exchange_rel(
+                    input,
+                    *partition_count,
+                    ExchangeKind::ScatterByFields(ScatterFields { fields }),
+                ))
+            }
+            Partitioning::UnknownPartitioning(_) => Err(DataFusionError::NotImplemented(
+                "Repartitioning with unknown partitioning is not supported".to_string(),
+            )),
+        }
+    } else if let Some(coalesce) = plan.as_any().downcast_ref::<CoalesceBatchesExec>() {
+        // Coalescing batches only changes batch sizes, there is nothing to express in Substrait
+        to_substrait_rel(coalesce.input().as_ref(), extension_info)
+    } else if let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() {
+        // we only support basic joins so return an error for anything not yet supported
+        if join.filter().is_some() {
+            return Err(DataFusionError::NotImplemented("join filter".to_string()));
+        }
+        if join.null_equals_null() {
+            return Err(DataFusionError::NotImplemented(
+                "join with null equal to null".to_string(),
+            ));
+        }
+        let left = to_substrait_rel(join.left().as_ref(), extension_info)?;
+        let right = to_substrait_rel(join.right().as_ref(), extension_info)?;
+        let (left_keys, right_keys) = to_substrait_join_keys(join.on());
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::HashJoin(Box::new(HashJoinRel {
+                common: None,
+                left: Some(left),
+                right: Some(right),
+                left_keys,
+                right_keys,
+                post_join_filter: None,
+                r#type: to_substrait_hash_jointype(*join.join_type()) as i32,
+                advanced_extension: Some(to_substrait_partition_mode(
+                    join.partition_mode(),
+                )),
+            }))),
+        }))
+    } else if let Some(join) = plan.as_any().downcast_ref::<SortMergeJoinExec>() {
+        if join.null_equals_null() {
+            return Err(DataFusionError::NotImplemented(
+                "join with null equal to null".to_string(),
+            ));
+        }
+        if join
+            .sort_options()
+            .iter()
+            .any(|options| *options != SortOptions::default())
+        {
+            return Err(DataFusionError::NotImplemented(
+                "Sort-merge join with non-default sort options is not supported"
+                    .to_string(),
+            ));
+        }
+        let left = to_substrait_rel(join.left().as_ref(), extension_info)?;
+        let right = to_substrait_rel(join.right().as_ref(), extension_info)?;
+        let (left_keys, right_keys) = to_substrait_join_keys(join.on());
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::MergeJoin(Box::new(MergeJoinRel {
+                common: None,
+                left: Some(left),
+                right: Some(right),
+                left_keys,
+                right_keys,
+                post_join_filter: None,
+                r#type: to_substrait_merge_jointype(join.join_type()) as i32,
+                advanced_extension: None,
+            }))),
+        }))
+    } else if let Some(agg) = plan.as_any().downcast_ref::<AggregateExec>() {
+        let group_by = agg.group_expr();
+        if !group_by.null_expr().is_empty() || group_by.groups().len() > 1 {
+            return Err(DataFusionError::NotImplemented(
+                "Aggregate with grouping sets is not supported".to_string(),
+            ));
+        }
+        let input = to_substrait_rel(agg.input().as_ref(), extension_info)?;
+        let grouping_expressions = group_by
+            .expr()
+            .iter()
+            .map(|(e, _)| to_substrait_rex(e, extension_info))
+            .collect::<Result<Vec<_>>>()?;
+        let phase = match agg.mode() {
+            AggregateMode::Partial => AggregationPhase::InitialToIntermediate,
+            AggregateMode::Final | AggregateMode::FinalPartitioned => {
+                AggregationPhase::IntermediateToResult
+            }
+            AggregateMode::Single => AggregationPhase::InitialToResult,
+        };
+        let measures = agg
+            .aggr_expr()
+            .iter()
+            .zip(agg.filter_expr())
+            .map(|(e, filter)| {
+                to_substrait_agg_measure(e, filter.as_ref(), phase, extension_info)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let names = group_by
+            .expr()
+            .iter()
+            .map(|(_, name)| name.clone())
+            .chain(agg.aggr_expr().iter().map(|e| e.name().to_string()));
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::Aggregate(Box::new(AggregateRel {
+                common: None,
+                input: Some(input),
+                groupings: vec![Grouping {
+                    grouping_expressions,
+                }],
+                measures,
+                advanced_extension: Some(to_substrait_output_names(names)),
+            }))),
+        }))
     } else {
         Err(DataFusionError::Substrait(format!(
             "Unsupported plan in Substrait physical plan producer: {}",
@@ -79,3 +345,274 @@ pub fn to_substrait_rel(
         )))
     }
 }
+
+/// Convert DataFusion PhysicalExpr to Substrait Rex
+pub fn to_substrait_rex(
+    expr: &Arc<dyn PhysicalExpr>,
+    extension_info: &mut (
+        Vec<extensions::SimpleExtensionDeclaration>,
+        HashMap<String, u32>,
+    ),
+) -> Result<Expression> {
+    if let Some(col) = expr.as_any().downcast_ref::<Column>() {
+        Ok(Expression {
+            rex_type: Some(RexType::Selection(Box::new(substrait_field_reference(
+                col.index(),
+            )))),
+        })
+    } else if let Some(lit) = expr.as_any().downcast_ref::<Literal>() {
+        to_substrait_literal(lit.value())
+    } else if let Some(binary) = expr.as_any().downcast_ref::<BinaryExpr>() {
+        let l = to_substrait_rex(binary.left(), extension_info)?;
+        let r = to_substrait_rex(binary.right(), extension_info)?;
+        Ok(make_binary_op_scalar_func(
+            &l,
+            &r,
+            *binary.op(),
+            extension_info,
+        ))
+    } else if let Some(cast) = expr.as_any().downcast_ref::<CastExpr>() {
+        Ok(Expression {
+            rex_type: Some(RexType::Cast(Box::new(
+                substrait::proto::expression::Cast {
+                    r#type: Some(to_substrait_type(cast.cast_type())?),
+                    input: Some(Box::new(to_substrait_rex(cast.expr(), extension_info)?)),
+                    failure_behavior: 0, // FAILURE_BEHAVIOR_UNSPECIFIED
+                },
+            ))),
+        })
+    } else {
+        Err(DataFusionError::NotImplemented(format!(
+            "Unsupported physical expression: {expr}"
+        )))
+    }
+}
+
+fn to_substrait_agg_measure(
+    expr: &Arc<dyn AggregateExpr>,
+    filter: Option<&Arc<dyn PhysicalExpr>>,
+    phase: AggregationPhase,
+    extension_info: &mut (
+        Vec<extensions::SimpleExtensionDeclaration>,
+        HashMap<String, u32>,
+    ),
+) -> Result<Measure> {
+    let aggr = expr.as_any();
+    let (fun, distinct) = if aggr.downcast_ref::<Sum>().is_some() {
+        (AggregateFunction::Sum, false)
+    } else if aggr.downcast_ref::<DistinctSum>().is_some() {
+        (AggregateFunction::Sum, true)
+    } else if aggr.downcast_ref::<Avg>().is_some() {
+        (AggregateFunction::Avg, false)
+    } else if aggr.downcast_ref::<Count>().is_some() {
+        (AggregateFunction::Count, false)
+    } else if aggr.downcast_ref::<DistinctCount>().is_some() {
+        (AggregateFunction::Count, true)
+    } else if aggr.downcast_ref::<Min>().is_some() {
+        (AggregateFunction::Min, false)
+    } else if aggr.downcast_ref::<Max>().is_some() {
+        (AggregateFunction::Max, false)
+    } else {
+        return Err(DataFusionError::NotImplemented(format!(
+            "Unsupported aggregate expression: {}",
+            expr.name()
+        )));
+    };
+    let arguments = expr
+        .expressions()
+        .iter()
+        .map(|e| {
+            Ok(FunctionArgument {
+                arg_type: Some(ArgType::Value(to_substrait_rex(e, extension_info)?)),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let function_anchor =
+        _register_function(fun.to_string().to_lowercase(), extension_info);
+    Ok(Measure {
+        measure: Some(SubstraitAggregateFunction {
+            function_reference: function_anchor,
+            arguments,
+            sorts: vec![],
+            output_type: None,
+            invocation: match distinct {
+                true => AggregationInvocation::Distinct as i32,
+                false => AggregationInvocation::All as i32,
+            },
+            phase: phase as i32,
+            args: vec![],
+            options: vec![],
+        }),
+        filter: match filter {
+            Some(f) => Some(to_substrait_rex(f, extension_info)?),
+            None => None,
+        },
+    })
+}
+
+/// Returns the per-partition sort below `plan` if `plan` merges the sorted
+/// partitions of a `SortExec` on the same expressions
+fn merged_partition_sort(plan: &dyn ExecutionPlan) -> Option<&SortExec> {
+    let merge = plan.as_any().downcast_ref::<SortPreservingMergeExec>()?;
+    let sort = merge.input().as_any().downcast_ref::<SortExec>()?;
+    (sort.preserve_partitioning() && sort.expr() == merge.expr()).then_some(sort)
+}
+
+fn to_substrait_sort_rel(
+    input: &Arc<dyn ExecutionPlan>,
+    expr: &[PhysicalSortExpr],
+    fetch: Option<usize>,
+    extension_info: &mut (
+        Vec<extensions::SimpleExtensionDeclaration>,
+        HashMap<String, u32>,
+    ),
+) -> Result<Box<Rel>> {
+    let input = to_substrait_rel(input.as_ref(), extension_info)?;
+    let sorts = expr
+        .iter()
+        .map(|e| to_substrait_sort_field(e, extension_info))
+        .collect::<Result<Vec<_>>>()?;
+    let sort = Box::new(Rel {
+        rel_type: Some(RelType::Sort(Box::new(SortRel {
+            common: None,
+            input: Some(input),
+            sorts,
+            advanced_extension: None,
+        }))),
+    });
+    // SortRel has no limit of its own, a sort with a fetch is a FetchRel on top of it
+    Ok(match fetch {
+        Some(fetch) => to_substrait_fetch_rel(sort, 0, Some(fetch)),
+        None => sort,
+    })
+}
+
+fn to_substrait_sort_field(
+    expr: &PhysicalSortExpr,
+    extension_info: &mut (
+        Vec<extensions::SimpleExtensionDeclaration>,
+        HashMap<String, u32>,
+    ),
+) -> Result<SortField> {
+    let d = match (expr.options.descending, expr.options.nulls_first) {
+        (false, true) => SortDirection::AscNullsFirst,
+        (false, false) => SortDirection::AscNullsLast,
+        (true, true) => SortDirection::DescNullsFirst,
+        (true, false) => SortDirection::DescNullsLast,
+    };
+    Ok(SortField {
+        expr: Some(to_substrait_rex(&expr.expr, extension_info)?),
+        sort_kind: Some(SortKind::Direction(d as i32)),
+    })
+}
+
+/// A fetch without a limit is encoded with a negative count
+fn to_substrait_fetch_rel(
+    input: Box<Rel>,
+    skip: usize,
+    fetch: Option<usize>,
+) -> Box<Rel> {
+    Box::new(Rel {
+        rel_type: Some(RelType::Fetch(Box::new(FetchRel {
+            common: None,
+            input: Some(input),
+            offset: skip as i64,
+            count: fetch.map(|f| f as i64).unwrap_or(-1),
+            advanced_extension: None,
+        }))),
+    })
+}
+
+fn to_substrait_exchange_rel(
+    input: Box<Rel>,
+    partition_count: usize,
+    exchange_kind: ExchangeKind,
+) -> Box<Rel> {
+    Box::new(Rel {
+        rel_type: Some(RelType::Exchange(Box::new(ExchangeRel {
+            common: None,
+            input: Some(input),
+            partition_count: partition_count as i32,
+            targets: vec![],
+            exchange_kind: Some(exchange_kind),
+            advanced_extension: None,
+        }))),
+    })
+}
+
+fn to_substrait_output_names(names: impl Iterator<Item = String>) -> AdvancedExtension {
+    let names = NamedStruct {
+        names: names.collect(),
+        r#struct: None,
+    };
+    AdvancedExtension {
+        optimization: Some(ProtoAny {
+            type_url: OUTPUT_NAMES_TYPE_URL.to_string(),
+            value: names.encode_to_vec(),
+        }),
+        enhancement: None,
+    }
+}
+
+fn to_substrait_partition_mode(mode: &PartitionMode) -> AdvancedExtension {
+    AdvancedExtension {
+        optimization: Some(ProtoAny {
+            type_url: PARTITION_MODE_TYPE_URL.to_string(),
+            value: format!("{mode:?}").into_bytes(),
+        }),
+        enhancement: None,
+    }
+}
+
+fn to_substrait_join_keys(
+    on: &[(Column, Column)],
+) -> (Vec<FieldReference>, Vec<FieldReference>) {
+    on.iter()
+        .map(|(l, r)| {
+            (
+                substrait_field_reference(l.index()),
+                substrait_field_reference(r.index()),
+            )
+        })
+        .unzip()
+}
+
+fn to_substrait_hash_jointype(join_type: JoinType) -> hash_join_rel::JoinType {
+    match join_type {
+        JoinType::Inner => hash_join_rel::JoinType::Inner,
+        JoinType::Left => hash_join_rel::JoinType::Left,
+        JoinType::Right => hash_join_rel::JoinType::Right,
+        JoinType::Full => hash_join_rel::JoinType::Outer,
+        JoinType::LeftSemi => hash_join_rel::JoinType::LeftSemi,
+        JoinType::RightSemi => hash_join_rel::JoinType::RightSemi,
+        JoinType::LeftAnti => hash_join_rel::JoinType::LeftAnti,
+        JoinType::RightAnti => hash_join_rel::JoinType::RightAnti,
+    }
+}
+
+fn to_substrait_merge_jointype(join_type: JoinType) -> merge_join_rel::JoinType {
+    match join_type {
+        JoinType::Inner => merge_join_rel::JoinType::Inner,
+        JoinType::Left => merge_join_rel::JoinType::Left,
+        JoinType::Right => merge_join_rel::JoinType::Right,
+        JoinType::Full => merge_join_rel::JoinType::Outer,
+        JoinType::LeftSemi => merge_join_rel::JoinType::LeftSemi,
+        JoinType::RightSemi => merge_join_rel::JoinType::RightSemi,
+        JoinType::LeftAnti => merge_join_rel::JoinType::LeftAnti,
+        JoinType::RightAnti => merge_join_rel::JoinType::RightAnti,
+    }
+}
+
+fn substrait_field_reference(index: usize) -> FieldReference {
+    FieldReference {
+        reference_type: Some(ReferenceType::DirectReference(ReferenceSegment {
+            reference_type: Some(reference_segment::ReferenceType::StructField(
+                Box::new(reference_segment::StructField {
+                    field: index as i32,
+                    child: None,
+                }),
+            )),
+        })),
+        root_type: None,
+    }
+}
@@ -20,11 +20,20 @@ use datafusion_substrait::logical_plan::{consumer, producer};
 #[cfg(test)]
 mod tests {
 
-    use crate::{consumer::from_substrait_plan, producer::to_substrait_plan};
+    use crate::{
+        consumer::{from_substrait_plan, from_substrait_plan_with_extension_codec},
+        producer::{to_substrait_plan, to_substrait_plan_with_extension_codec},
+    };
     use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::common::DFSchemaRef;
     use datafusion::error::Result;
+    use datafusion::logical_expr::{Extension, LogicalPlan, UserDefinedLogicalNodeCore};
     use datafusion::prelude::*;
+    use datafusion_substrait::logical_plan::SubstraitExtensionCodec;
+    use std::fmt;
+    use std::sync::Arc;
     use substrait::proto::extensions::simple_extension_declaration::MappingType;
+    use substrait::proto::{plan_rel, rel::RelType, set_rel};
 
     #[tokio::test]
     async fn simple_select() -> Result<()> {
@@ -263,11 +272,190 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn set_rel_intersection_and_minus() -> Result<()> {
+        // The producer plans INTERSECT and EXCEPT as joins, so build the
+        // SetRel by hand from a UNION ALL of the same inputs
+        for (op, sql) in [
+            (
+                set_rel::SetOp::IntersectionPrimary,
+                "SELECT data.a FROM data INTERSECT SELECT data2.a FROM data2",
+            ),
+            (
+                set_rel::SetOp::IntersectionMultiset,
+                "SELECT data.a FROM data INTERSECT ALL SELECT data2.a FROM data2",
+            ),
+            (
+                set_rel::SetOp::MinusPrimary,
+                "SELECT data.a FROM data EXCEPT SELECT data2.a FROM data2",
+            ),
+            (
+                set_rel::SetOp::MinusMultiset,
+                "SELECT data.a FROM data EXCEPT ALL SELECT data2.a FROM data2",
+            ),
+        ] {
+            let mut ctx = create_context().await?;
+            let plan = ctx
+                .sql("SELECT data.a FROM data UNION ALL SELECT data2.a FROM data2")
+                .await?
+                .into_optimized_plan()?;
+            let mut proto = to_substrait_plan(&plan)?;
+            let rel = match proto.relations[0].rel_type.as_mut() {
+                Some(plan_rel::RelType::Root(root)) => root.input.as_mut().unwrap(),
+                Some(plan_rel::RelType::Rel(rel)) => rel,
+                None => unreachable!(),
+            };
+            match rel.rel_type.as_mut() {
+                Some(RelType::Set(set)) => set.op = op as i32,
+                other => panic!("Expected a SetRel, got {other:?}"),
+            }
+
+            let plan2 = from_substrait_plan(&mut ctx, &proto).await?;
+            let plan2 = ctx.state().optimize(&plan2)?;
+            let expected = ctx.sql(sql).await?.into_optimized_plan()?;
+            assert_eq!(format!("{expected:?}"), format!("{plan2:?}"));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn set_rel_minus_requires_two_inputs() -> Result<()> {
+        let mut ctx = create_context().await?;
+        let plan = ctx
+            .sql(
+                "SELECT a FROM data UNION ALL SELECT a FROM data2 \
+                UNION ALL SELECT a FROM data",
+            )
+            .await?
+            .into_optimized_plan()?;
+        let mut proto = to_substrait_plan(&plan)?;
+        let rel = match proto.relations[0].rel_type.as_mut() {
+            Some(plan_rel::RelType::Root(root)) => root.input.as_mut().unwrap(),
+            Some(plan_rel::RelType::Rel(rel)) => rel,
+            None => unreachable!(),
+        };
+        match rel.rel_type.as_mut() {
+            Some(RelType::Set(set)) => {
+                assert_eq!(set.inputs.len(), 3);
+                set.op = set_rel::SetOp::MinusPrimary as i32;
+            }
+            other => panic!("Expected a SetRel, got {other:?}"),
+        }
+
+        let err = from_substrait_plan(&mut ctx, &proto).await.unwrap_err();
+        assert!(err.to_string().contains("only supported with two inputs"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn values_with_non_literal() -> Result<()> {
+        let ctx = create_context().await?;
+        let plan = ctx
+            .sql("VALUES (1, random())")
+            .await?
+            .into_optimized_plan()?;
+        let err = to_substrait_plan(&plan).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Only literal values are supported in a VirtualTable"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_union() -> Result<()> {
+        roundtrip("SELECT a, e FROM data UNION SELECT a, e FROM data").await
+    }
+
+    #[tokio::test]
+    async fn roundtrip_union_all() -> Result<()> {
+        roundtrip("SELECT a, e FROM data UNION ALL SELECT a, e FROM data").await
+    }
+
+    #[tokio::test]
+    async fn roundtrip_cross_join() -> Result<()> {
+        roundtrip("SELECT data.a, data2.a FROM data CROSS JOIN data2").await
+    }
+
+    #[tokio::test]
+    async fn roundtrip_values() -> Result<()> {
+        roundtrip("VALUES (1, 'a', TRUE), (2, 'b', FALSE)").await
+    }
+
+    #[tokio::test]
+    async fn roundtrip_values_schema() -> Result<()> {
+        let mut ctx = create_context().await?;
+        let plan = ctx
+            .sql("VALUES (1, 'a', 2.5)")
+            .await?
+            .into_optimized_plan()?;
+        let proto = to_substrait_plan(&plan)?;
+        let plan2 = from_substrait_plan(&mut ctx, &proto).await?;
+
+        let fields = |plan: &LogicalPlan| {
+            plan.schema()
+                .fields()
+                .iter()
+                .map(|f| (f.name().clone(), f.data_type().clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(fields(&plan), fields(&plan2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_empty_relation() -> Result<()> {
+        roundtrip("SELECT 1").await
+    }
+
+    #[tokio::test]
+    async fn roundtrip_extension() -> Result<()> {
+        let mut ctx = create_context().await?;
+        let input = ctx.sql("SELECT a FROM data").await?.into_optimized_plan()?;
+        let plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(MockUserDefinedLogicalPlan {
+                validation_bytes: vec![1, 2, 3],
+                input,
+            }),
+        });
+
+        let codec = MockExtensionCodec {};
+        let proto = to_substrait_plan_with_extension_codec(&plan, &codec)?;
+        let plan2 =
+            from_substrait_plan_with_extension_codec(&mut ctx, &proto, &codec).await?;
+
+        assert_eq!(format!("{plan:?}"), format!("{plan2:?}"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn extension_without_codec() -> Result<()> {
+        let ctx = create_context().await?;
+        let input = ctx.sql("SELECT a FROM data").await?.into_optimized_plan()?;
+        let plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(MockUserDefinedLogicalPlan {
+                validation_bytes: vec![],
+                input,
+            }),
+        });
+
+        assert!(to_substrait_plan(&plan).is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn simple_window_function() -> Result<()> {
         roundtrip("SELECT RANK() OVER (PARTITION BY a ORDER BY b), d, SUM(b) OVER (PARTITION BY a) FROM data;").await
     }
 
+    #[tokio::test]
+    async fn array_functions() -> Result<()> {
+        roundtrip(
+            "SELECT array_append(make_array(a, a), a), cardinality(make_array(a, e)) \
+             FROM data",
+        )
+        .await
+    }
+
     #[tokio::test]
     async fn qualified_schema_table_reference() -> Result<()> {
         roundtrip("SELECT * FROM public.data;").await
@@ -444,6 +632,76 @@ mod tests {
         Ok((function_names, function_anchors))
     }
 
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct MockUserDefinedLogicalPlan {
+        validation_bytes: Vec<u8>,
+        input: LogicalPlan,
+    }
+
+    impl UserDefinedLogicalNodeCore for MockUserDefinedLogicalPlan {
+        fn name(&self) -> &str {
+            "MockUserDefinedLogicalPlan"
+        }
+
+        fn inputs(&self) -> Vec<&LogicalPlan> {
+            vec![&self.input]
+        }
+
+        fn schema(&self) -> &DFSchemaRef {
+            self.input.schema()
+        }
+
+        fn expressions(&self) -> Vec<Expr> {
+            vec![]
+        }
+
+        fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "MockUserDefinedLogicalPlan: validation_bytes={:?}",
+                self.validation_bytes
+            )
+        }
+
+        fn from_template(&self, _exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+            Self {
+                validation_bytes: self.validation_bytes.clone(),
+                input: inputs[0].clone(),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockExtensionCodec {}
+
+    impl SubstraitExtensionCodec for MockExtensionCodec {
+        fn try_decode(
+            &self,
+            name: &str,
+            buf: &[u8],
+            inputs: &[LogicalPlan],
+            _ctx: &SessionContext,
+        ) -> Result<Extension> {
+            assert_eq!(name, "MockUserDefinedLogicalPlan");
+            Ok(Extension {
+                node: Arc::new(MockUserDefinedLogicalPlan {
+                    validation_bytes: buf.to_vec(),
+                    input: inputs[0].clone(),
+                }),
+            })
+        }
+
+        fn try_encode(&self, node: &Extension, buf: &mut Vec<u8>) -> Result<()> {
+            let node = node
+                .node
+                .as_any()
+                .downcast_ref::<MockUserDefinedLogicalPlan>()
+                .unwrap();
+            buf.extend_from_slice(&node.validation_bytes);
+            Ok(())
+        }
+    }
+
     async fn create_context() -> Result<SessionContext> {
         let ctx = SessionContext::new();
         let mut explicit_options = CsvReadOptions::new();
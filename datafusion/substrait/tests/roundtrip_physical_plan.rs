@@ -17,17 +17,39 @@
 
 #[cfg(test)]
 mod tests {
-    use datafusion::arrow::datatypes::Schema;
+    use datafusion::arrow::array::Int64Array;
+    use datafusion::arrow::compute::SortOptions;
+    use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use datafusion::arrow::record_batch::RecordBatch;
     use datafusion::datasource::listing::PartitionedFile;
     use datafusion::datasource::object_store::ObjectStoreUrl;
     use datafusion::error::Result;
+    use datafusion::logical_expr::{AggregateFunction, Operator};
+    use datafusion::parquet::arrow::ArrowWriter;
+    use datafusion::physical_plan::aggregates::{
+        AggregateExec, AggregateMode, PhysicalGroupBy,
+    };
+    use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+    use datafusion::physical_plan::expressions::{
+        binary, create_aggregate_expr, lit, Column, PhysicalSortExpr,
+    };
     use datafusion::physical_plan::file_format::{FileScanConfig, ParquetExec};
-    use datafusion::physical_plan::{displayable, ExecutionPlan};
-    use datafusion::prelude::SessionContext;
+    use datafusion::physical_plan::filter::FilterExec;
+    use datafusion::physical_plan::joins::{
+        HashJoinExec, PartitionMode, SortMergeJoinExec,
+    };
+    use datafusion::physical_plan::limit::GlobalLimitExec;
+    use datafusion::physical_plan::projection::ProjectionExec;
+    use datafusion::physical_plan::repartition::RepartitionExec;
+    use datafusion::physical_plan::sorts::sort::SortExec;
+    use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+    use datafusion::physical_plan::{collect, displayable, ExecutionPlan, Partitioning};
+    use datafusion::prelude::{JoinType, SessionContext};
     use datafusion_substrait::physical_plan::{consumer, producer};
     use std::collections::HashMap;
     use std::sync::Arc;
     use substrait::proto::extensions;
+    use tempfile::TempDir;
 
     #[tokio::test]
     async fn parquet_exec() -> Result<()> {
@@ -77,4 +99,399 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn projection_and_filter() -> Result<()> {
+        let scan = parquet_scan(test_schema());
+        let predicate = binary(
+            Arc::new(Column::new("a", 0)),
+            Operator::Gt,
+            lit(1_i64),
+            &scan.schema(),
+        )?;
+        let filter = Arc::new(FilterExec::try_new(predicate, scan)?);
+        let projection = Arc::new(ProjectionExec::try_new(
+            vec![
+                (Arc::new(Column::new("b", 1)), "b".to_string()),
+                (Arc::new(Column::new("a", 0)), "a".to_string()),
+            ],
+            filter,
+        )?);
+
+        roundtrip(projection).await
+    }
+
+    #[tokio::test]
+    async fn sort_with_fetch() -> Result<()> {
+        let sort = Arc::new(
+            SortExec::new(
+                vec![PhysicalSortExpr {
+                    expr: Arc::new(Column::new("a", 0)),
+                    options: SortOptions {
+                        descending: true,
+                        nulls_first: false,
+                    },
+                }],
+                parquet_scan(test_schema()),
+            )
+            .with_fetch(Some(10)),
+        );
+
+        roundtrip(sort).await
+    }
+
+    #[tokio::test]
+    async fn merge_of_partition_sorts() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let scan = parquet_files_scan(&tmp_dir, &[vec![3, 1, 5], vec![4, 2, 6]])?;
+        let sort =
+            SortExec::new(vec![sort_expr_a()], scan).with_preserve_partitioning(true);
+        let merge: Arc<dyn ExecutionPlan> = Arc::new(SortPreservingMergeExec::new(
+            vec![sort_expr_a()],
+            Arc::new(sort),
+        ));
+
+        let plan = assert_roundtrip(merge).await?;
+        assert_eq!(
+            collect_a(plan).await?,
+            vec![Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn partition_sort_without_merge() -> Result<()> {
+        let sort = SortExec::new(vec![sort_expr_a()], parquet_scan(test_schema()))
+            .with_preserve_partitioning(true);
+        let mut extension_info: (
+            Vec<extensions::SimpleExtensionDeclaration>,
+            HashMap<String, u32>,
+        ) = (vec![], HashMap::new());
+
+        let err = producer::to_substrait_rel(&sort, &mut extension_info).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("SortExec that sorts each partition separately"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn multi_partition_sort() -> Result<()> {
+        // The sort of a multi partition input is rebuilt as per-partition sorts
+        // and a merge, keeping the rows of all partitions
+        let tmp_dir = TempDir::new()?;
+        let scan = parquet_files_scan(&tmp_dir, &[vec![3, 1, 5], vec![4, 2, 6]])?;
+        let sort = Arc::new(SortExec::new(vec![sort_expr_a()], scan));
+        let plan = roundtrip_plan(sort).await?;
+        assert!(plan.as_any().is::<SortPreservingMergeExec>());
+        assert_eq!(plan.output_partitioning().partition_count(), 1);
+        assert_eq!(
+            collect_a(plan).await?,
+            vec![Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn limit_over_merge_of_partition_sorts() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let scan = parquet_files_scan(&tmp_dir, &[vec![3, 1, 5], vec![4, 2, 6]])?;
+        let sort = SortExec::new(vec![sort_expr_a()], scan)
+            .with_preserve_partitioning(true)
+            .with_fetch(Some(3));
+        let merge = Arc::new(SortPreservingMergeExec::new(
+            vec![sort_expr_a()],
+            Arc::new(sort),
+        ));
+        let limit = Arc::new(GlobalLimitExec::new(merge, 1, Some(2)));
+
+        let plan = assert_roundtrip(limit).await?;
+        assert_eq!(collect_a(plan).await?, vec![Some(2), Some(3)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn limit_over_coalesce_partitions() -> Result<()> {
+        let coalesce = Arc::new(CoalescePartitionsExec::new(parquet_scan(test_schema())));
+        let limit = Arc::new(GlobalLimitExec::new(coalesce, 2, Some(5)));
+
+        roundtrip(limit).await
+    }
+
+    #[tokio::test]
+    async fn partial_and_final_aggregate() -> Result<()> {
+        let schema = test_schema();
+        let scan = parquet_scan(schema.clone());
+        let aggr_expr = vec![create_aggregate_expr(
+            &AggregateFunction::Sum,
+            false,
+            &[Arc::new(Column::new("a", 0))],
+            &schema,
+            "SUM(a)",
+        )?];
+
+        let partial = Arc::new(AggregateExec::try_new(
+            AggregateMode::Partial,
+            PhysicalGroupBy::new_single(vec![(
+                Arc::new(Column::new("b", 1)),
+                "b".to_string(),
+            )]),
+            aggr_expr.clone(),
+            vec![None],
+            scan,
+            schema.clone(),
+        )?);
+        let repartition = Arc::new(RepartitionExec::try_new(
+            partial,
+            Partitioning::Hash(vec![Arc::new(Column::new("b", 0))], 4),
+        )?);
+        let final_aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::FinalPartitioned,
+            PhysicalGroupBy::new_single(vec![(
+                Arc::new(Column::new("b", 0)),
+                "b".to_string(),
+            )]),
+            aggr_expr,
+            vec![None],
+            repartition,
+            schema,
+        )?);
+
+        roundtrip(final_aggregate).await
+    }
+
+    #[tokio::test]
+    async fn hash_join() -> Result<()> {
+        let join = Arc::new(HashJoinExec::try_new(
+            parquet_scan(test_schema()),
+            parquet_scan(test_schema()),
+            vec![(Column::new("b", 1), Column::new("b", 1))],
+            None,
+            &JoinType::Inner,
+            PartitionMode::CollectLeft,
+            false,
+        )?);
+
+        roundtrip(join).await
+    }
+
+    #[tokio::test]
+    async fn partitioned_hash_join() -> Result<()> {
+        let repartition = |plan| -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(RepartitionExec::try_new(
+                plan,
+                Partitioning::Hash(vec![Arc::new(Column::new("b", 1))], 4),
+            )?))
+        };
+        let join = Arc::new(HashJoinExec::try_new(
+            repartition(parquet_scan(test_schema()))?,
+            repartition(parquet_scan(test_schema()))?,
+            vec![(Column::new("b", 1), Column::new("b", 1))],
+            None,
+            &JoinType::Left,
+            PartitionMode::Partitioned,
+            false,
+        )?);
+
+        roundtrip(join).await
+    }
+
+    #[tokio::test]
+    async fn collect_left_hash_join_over_partitioned_input() -> Result<()> {
+        // The partition mode cannot be derived from the partitioning of the inputs
+        let left = Arc::new(RepartitionExec::try_new(
+            parquet_scan(test_schema()),
+            Partitioning::Hash(vec![Arc::new(Column::new("b", 1))], 4),
+        )?);
+        let join = Arc::new(HashJoinExec::try_new(
+            left,
+            parquet_scan(test_schema()),
+            vec![(Column::new("b", 1), Column::new("b", 1))],
+            None,
+            &JoinType::Inner,
+            PartitionMode::CollectLeft,
+            false,
+        )?);
+
+        roundtrip(join).await
+    }
+
+    #[tokio::test]
+    async fn sort_merge_join() -> Result<()> {
+        let join = Arc::new(SortMergeJoinExec::try_new(
+            parquet_scan(test_schema()),
+            parquet_scan(test_schema()),
+            vec![(Column::new("a", 0), Column::new("a", 0))],
+            JoinType::Full,
+            vec![SortOptions::default()],
+            false,
+        )?);
+
+        roundtrip(join).await
+    }
+
+    #[tokio::test]
+    async fn hash_repartition() -> Result<()> {
+        let repartition = Arc::new(RepartitionExec::try_new(
+            parquet_scan(test_schema()),
+            Partitioning::Hash(
+                vec![Arc::new(Column::new("b", 1)), Arc::new(Column::new("a", 0))],
+                8,
+            ),
+        )?);
+
+        roundtrip(repartition).await
+    }
+
+    #[tokio::test]
+    async fn projection_with_aliases() -> Result<()> {
+        let scan = parquet_scan(test_schema());
+        let a_plus_one = binary(
+            Arc::new(Column::new("a", 0)),
+            Operator::Plus,
+            lit(1_i64),
+            &scan.schema(),
+        )?;
+        let projection = Arc::new(ProjectionExec::try_new(
+            vec![
+                (Arc::new(Column::new("b", 1)), "renamed_b".to_string()),
+                (a_plus_one, "a_plus_one".to_string()),
+            ],
+            scan,
+        )?);
+
+        let plan = assert_roundtrip(projection).await?;
+        let schema = plan.schema();
+        assert_eq!(schema.field(0).name(), "renamed_b");
+        assert_eq!(schema.field(1).name(), "a_plus_one");
+        Ok(())
+    }
+
+    fn test_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]))
+    }
+
+    fn parquet_scan(file_schema: SchemaRef) -> Arc<dyn ExecutionPlan> {
+        let scan_config = FileScanConfig {
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_schema,
+            file_groups: vec![vec![PartitionedFile::new(
+                "file://foo/part-0.parquet".to_string(),
+                123,
+            )]],
+            statistics: Default::default(),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            output_ordering: None,
+            infinite_source: false,
+        };
+        Arc::new(ParquetExec::new(scan_config, None, None))
+    }
+
+    fn sort_expr_a() -> PhysicalSortExpr {
+        PhysicalSortExpr {
+            expr: Arc::new(Column::new("a", 0)),
+            options: SortOptions::default(),
+        }
+    }
+
+    /// Writes a parquet file with a single column `a` per partition into `dir`
+    /// and scans them
+    fn parquet_files_scan(
+        dir: &TempDir,
+        partitions: &[Vec<i64>],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let mut file_groups = vec![];
+        for (i, values) in partitions.iter().enumerate() {
+            let path = dir.path().join(format!("part-{i}.parquet"));
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int64Array::from(values.clone()))],
+            )?;
+            let mut writer = ArrowWriter::try_new(
+                std::fs::File::create(&path)?,
+                schema.clone(),
+                None,
+            )?;
+            writer.write(&batch)?;
+            writer.close()?;
+            let size = std::fs::metadata(&path)?.len();
+            file_groups.push(vec![PartitionedFile::new(
+                path.to_str().unwrap().to_string(),
+                size,
+            )]);
+        }
+        let scan_config = FileScanConfig {
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_schema: schema,
+            file_groups,
+            statistics: Default::default(),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            output_ordering: None,
+            infinite_source: false,
+        };
+        Ok(Arc::new(ParquetExec::new(scan_config, None, None)))
+    }
+
+    /// Executes `plan` and returns the values of its first column
+    async fn collect_a(plan: Arc<dyn ExecutionPlan>) -> Result<Vec<Option<i64>>> {
+        let ctx = SessionContext::new();
+        let batches = collect(plan, ctx.task_ctx()).await?;
+        Ok(batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .iter()
+                    .collect::<Vec<_>>()
+            })
+            .collect())
+    }
+
+    async fn roundtrip(plan: Arc<dyn ExecutionPlan>) -> Result<()> {
+        assert_roundtrip(plan).await?;
+        Ok(())
+    }
+
+    /// Round trips `plan` through Substrait, checking that it is rebuilt as is
+    async fn assert_roundtrip(
+        plan: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let plan2 = roundtrip_plan(plan.clone()).await?;
+        let expected = format!("{}", displayable(plan.as_ref()).indent());
+        let actual = format!("{}", displayable(plan2.as_ref()).indent());
+        assert_eq!(expected, actual);
+        Ok(plan2)
+    }
+
+    async fn roundtrip_plan(
+        plan: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let mut extension_info: (
+            Vec<extensions::SimpleExtensionDeclaration>,
+            HashMap<String, u32>,
+        ) = (vec![], HashMap::new());
+
+        let substrait_rel =
+            producer::to_substrait_rel(plan.as_ref(), &mut extension_info)?;
+
+        let extensions: HashMap<u32, &String> = extension_info
+            .1
+            .iter()
+            .map(|(name, anchor)| (*anchor, name))
+            .collect();
+
+        let mut ctx = SessionContext::new();
+        consumer::from_substrait_rel(&mut ctx, substrait_rel.as_ref(), &extensions).await
+    }
 }